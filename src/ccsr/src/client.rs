@@ -7,6 +7,7 @@
 // the Business Source License, use of this software will be governed
 // by the Apache License, Version 2.0.
 
+use std::collections::HashSet;
 use std::error::Error;
 use std::fmt;
 
@@ -53,17 +54,61 @@ impl Client {
 
     /// Gets the latest schema for the specified subject.
     pub async fn get_schema_by_subject(&self, subject: &str) -> Result<Schema, GetBySubjectError> {
-        let req = self.make_request(
-            Method::GET,
-            format!("/subjects/{}/versions/latest", subject),
-        );
-        let res: GetBySubjectResponse = send_request(req).await?;
+        let res = self.get_subject_version(subject, "latest").await?;
         Ok(Schema {
             id: res.id,
             raw: res.schema,
         })
     }
 
+    /// Gets the latest schema for the specified subject, along with all
+    /// schemas that it references, directly or transitively.
+    ///
+    /// Each referenced schema is returned with the name by which it is
+    /// referenced, e.g., the path of an imported protobuf file.
+    pub async fn get_schema_and_references(
+        &self,
+        subject: &str,
+    ) -> Result<(Schema, Vec<(String, Schema)>), GetBySubjectError> {
+        let res = self.get_subject_version(subject, "latest").await?;
+        let schema = Schema {
+            id: res.id,
+            raw: res.schema,
+        };
+        let mut references = vec![];
+        let mut seen = HashSet::new();
+        let mut pending = res.references;
+        while let Some(reference) = pending.pop() {
+            if !seen.insert(reference.name.clone()) {
+                continue;
+            }
+            let res = self
+                .get_subject_version(&reference.subject, &reference.version.to_string())
+                .await?;
+            pending.extend(res.references);
+            references.push((
+                reference.name,
+                Schema {
+                    id: res.id,
+                    raw: res.schema,
+                },
+            ));
+        }
+        Ok((schema, references))
+    }
+
+    async fn get_subject_version(
+        &self,
+        subject: &str,
+        version: &str,
+    ) -> Result<GetBySubjectResponse, GetBySubjectError> {
+        let req = self.make_request(
+            Method::GET,
+            format!("/subjects/{}/versions/{}", subject, version),
+        );
+        Ok(send_request(req).await?)
+    }
+
     /// Publishes a new schema for the specified subject. The ID of the new
     /// schema is returned.
     ///
@@ -169,7 +214,7 @@ pub struct Schema {
 ///
 /// For more information, check out:
 /// <https://docs.confluent.io/platform/current/schema-registry/serdes-develop/index.html#referenced-schemas>
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct SchemaReference {
     pub name: String,
     pub subject: String,
@@ -229,6 +274,8 @@ impl fmt::Display for GetByIdError {
 struct GetBySubjectResponse {
     id: i32,
     schema: String,
+    #[serde(default)]
+    references: Vec<SchemaReference>,
 }
 
 /// Errors for schema lookups by subject.
@@ -236,6 +283,8 @@ struct GetBySubjectResponse {
 pub enum GetBySubjectError {
     /// The requested subject does not exist.
     SubjectNotFound,
+    /// The requested version of the subject does not exist.
+    VersionNotFound,
     /// The underlying HTTP transport failed.
     Transport(reqwest::Error),
     /// An internal server error occured.
@@ -248,6 +297,7 @@ impl From<UnhandledError> for GetBySubjectError {
             UnhandledError::Transport(err) => GetBySubjectError::Transport(err),
             UnhandledError::Api { code, message } => match code {
                 40401 => GetBySubjectError::SubjectNotFound,
+                40402 => GetBySubjectError::VersionNotFound,
                 _ => GetBySubjectError::Server { code, message },
             },
         }
@@ -257,7 +307,9 @@ impl From<UnhandledError> for GetBySubjectError {
 impl Error for GetBySubjectError {
     fn source(&self) -> Option<&(dyn Error + 'static)> {
         match self {
-            GetBySubjectError::SubjectNotFound | GetBySubjectError::Server { .. } => None,
+            GetBySubjectError::SubjectNotFound
+            | GetBySubjectError::VersionNotFound
+            | GetBySubjectError::Server { .. } => None,
             GetBySubjectError::Transport(err) => Some(err),
         }
     }
@@ -267,6 +319,7 @@ impl fmt::Display for GetBySubjectError {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
             GetBySubjectError::SubjectNotFound => write!(f, "subject not found"),
+            GetBySubjectError::VersionNotFound => write!(f, "version not found"),
             GetBySubjectError::Transport(err) => write!(f, "transport: {}", err),
             GetBySubjectError::Server { code, message } => {
                 write!(f, "server error {}: {}", code, message)
@@ -16,7 +16,9 @@ use hyper::StatusCode;
 use hyper::{Body, Response};
 use lazy_static::lazy_static;
 
-use ccsr::{Client, DeleteError, GetByIdError, GetBySubjectError, PublishError, SchemaType};
+use ccsr::{
+    Client, DeleteError, GetByIdError, GetBySubjectError, PublishError, SchemaReference, SchemaType,
+};
 
 lazy_static! {
     pub static ref SCHEMA_REGISTRY_URL: reqwest::Url = match env::var("SCHEMA_REGISTRY_URL") {
@@ -110,6 +112,37 @@ async fn test_client() -> Result<(), anyhow::Error> {
         .await?;
     assert_eq!(count_schemas(&client, "ccsr-test-").await?, 2);
 
+    let simple_id = client
+        .publish_schema(
+            "ccsr-test-simple",
+            r#"syntax = "proto3"; message SimpleId { string id = 1; }"#,
+            SchemaType::Protobuf,
+            &[],
+        )
+        .await?;
+    let imported_id = client
+        .publish_schema(
+            "ccsr-test-imported",
+            r#"syntax = "proto3"; import "simple.proto"; message Imported { SimpleId id = 1; }"#,
+            SchemaType::Protobuf,
+            &[SchemaReference {
+                name: "simple.proto".into(),
+                subject: "ccsr-test-simple".into(),
+                version: 1,
+            }],
+        )
+        .await?;
+
+    {
+        let (schema, references) = client
+            .get_schema_and_references("ccsr-test-imported")
+            .await?;
+        assert_eq!(imported_id, schema.id);
+        assert_eq!(references.len(), 1);
+        assert_eq!(references[0].0, "simple.proto");
+        assert_eq!(references[0].1.id, simple_id);
+    }
+
     Ok(())
 }
 
@@ -9,7 +9,7 @@
 
 //! Persistent metadata storage for the coordinator.
 
-use std::collections::{BTreeMap, HashMap, HashSet};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 use std::path::Path;
 use std::sync::{Arc, Mutex, MutexGuard};
 use std::time::{Duration, Instant};
@@ -34,9 +34,10 @@ use dataflow_types::{SinkConnector, SinkConnectorBuilder, SourceConnector, Timel
 use expr::{ExprHumanizer, GlobalId, MirScalarExpr, OptimizedMirRelationExpr};
 use persist::error::Error as PersistError;
 use persist::indexed::runtime::RuntimeClient as PersistClient;
-use repr::{RelationDesc, ScalarType};
+use repr::{ColumnName, RelationDesc, ScalarType};
 use sql::ast::display::AstDisplay;
 use sql::ast::{Expr, Raw};
+use sql::auth::ScramSha256Verifier;
 use sql::catalog::{
     CatalogError as SqlCatalogError, CatalogItem as SqlCatalogItem,
     CatalogItemType as SqlCatalogItemType, SessionCatalog,
@@ -44,8 +45,8 @@ use sql::catalog::{
 use sql::names::{DatabaseSpecifier, FullName, PartialName, SchemaName};
 use sql::plan::HirRelationExpr;
 use sql::plan::{
-    CreateIndexPlan, CreateSinkPlan, CreateSourcePlan, CreateTablePlan, CreateTypePlan,
-    CreateViewPlan, Params, Plan, PlanContext, StatementDesc,
+    CheckConstraint, CreateIndexPlan, CreateSinkPlan, CreateSourcePlan, CreateTablePlan,
+    CreateTypePlan, CreateViewPlan, Params, Plan, PlanContext, StatementDesc, UniqueConstraint,
 };
 use transform::Optimizer;
 use uuid::Uuid;
@@ -113,6 +114,9 @@ pub struct Catalog {
     storage: Arc<Mutex<storage::Connection>>,
     oid_counter: u32,
     config: sql::catalog::CatalogConfig,
+    /// A persistent secret that salts the SCRAM exchanges with roles that do
+    /// not have a password.
+    mock_auth_secret: Uuid,
     /// Handle to persistence runtime and feature configuration.
     persist: PersisterWithConfig,
 }
@@ -249,6 +253,9 @@ pub struct Role {
     pub id: i64,
     #[serde(skip)]
     pub oid: u32,
+    /// The verifier for the role's password, if the role has a password.
+    #[serde(skip)]
+    pub password: Option<ScramSha256Verifier>,
 }
 
 #[derive(Clone, Debug)]
@@ -277,6 +284,10 @@ pub struct Table {
     pub desc: RelationDesc,
     #[serde(skip)]
     pub defaults: Vec<Expr<Raw>>,
+    #[serde(skip)]
+    pub unique_constraints: Vec<UniqueConstraint>,
+    #[serde(skip)]
+    pub check_constraints: Vec<CheckConstraint>,
     pub conn_id: Option<u32>,
     pub depends_on: Vec<GlobalId>,
     pub persist: Option<PersistDetails>,
@@ -355,6 +366,12 @@ pub enum TypeInner {
         value_id: GlobalId,
     },
     Pseudo,
+    Enum {
+        labels: Vec<String>,
+    },
+    Record {
+        fields: Vec<(ColumnName, GlobalId)>,
+    },
 }
 
 impl From<sql::plan::TypeInner> for TypeInner {
@@ -362,6 +379,8 @@ impl From<sql::plan::TypeInner> for TypeInner {
         match t {
             sql::plan::TypeInner::List { element_id } => TypeInner::List { element_id },
             sql::plan::TypeInner::Map { key_id, value_id } => TypeInner::Map { key_id, value_id },
+            sql::plan::TypeInner::Enum { labels } => TypeInner::Enum { labels },
+            sql::plan::TypeInner::Record { fields } => TypeInner::Record { fields },
         }
     }
 }
@@ -593,7 +612,8 @@ impl Catalog {
     pub fn open(
         config: &Config,
     ) -> Result<(Catalog, Vec<BuiltinTableUpdate>, Option<PersistClient>), Error> {
-        let (storage, experimental_mode, cluster_id) = storage::Connection::open(&config)?;
+        let (storage, experimental_mode, cluster_id, mock_auth_secret) =
+            storage::Connection::open(&config)?;
 
         // This is somewhat incorrect in a services/multi-node world. The
         // reentrance id should be per-node not per-cluster. This is also an odd
@@ -626,6 +646,7 @@ impl Catalog {
                 now: config.now,
                 disable_user_indexes: config.disable_user_indexes,
             },
+            mock_auth_secret,
             persist,
         };
 
@@ -672,8 +693,18 @@ impl Catalog {
         }
 
         let roles = catalog.storage().load_roles()?;
-        let builtin_roles = BUILTIN_ROLES.iter().map(|b| (b.id, b.name.to_owned()));
-        for (id, name) in roles.into_iter().chain(builtin_roles) {
+        let builtin_roles = BUILTIN_ROLES
+            .iter()
+            .map(|b| (b.id, b.name.to_owned(), None));
+        for (id, name, password) in roles.into_iter().chain(builtin_roles) {
+            let password = match password.map(|p| p.parse()).transpose() {
+                Ok(password) => password,
+                Err(e) => {
+                    return Err(Error::new(ErrorKind::Corruption {
+                        detail: format!("failed to parse password for role {}: {}", name, e),
+                    }))
+                }
+            };
             let oid = catalog.allocate_oid()?;
             catalog.roles.insert(
                 name.clone(),
@@ -681,6 +712,7 @@ impl Catalog {
                     name: name.clone(),
                     id,
                     oid,
+                    password,
                 },
             );
         }
@@ -769,6 +801,8 @@ impl Catalog {
                             create_sql: "TODO".to_string(),
                             desc: table.desc.clone(),
                             defaults: vec![Expr::null(); table.desc.arity()],
+                            unique_constraints: vec![],
+                            check_constraints: vec![],
                             conn_id: None,
                             depends_on: vec![],
                             persist,
@@ -833,6 +867,9 @@ impl Catalog {
                                 }
                                 postgres_types::Kind::Pseudo => TypeInner::Pseudo,
                                 postgres_types::Kind::Simple => TypeInner::Base,
+                                // The element type of a builtin range type is
+                                // implied by its OID.
+                                postgres_types::Kind::Range(_) => TypeInner::Base,
                                 _ => unreachable!(),
                             },
                             depends_on: vec![],
@@ -936,7 +973,10 @@ impl Catalog {
                     }))
                 }
             };
-            let oid = c.allocate_oid()?;
+            let oid = match &item {
+                CatalogItem::Type(typ) => c.allocate_type_oid(typ)?,
+                _ => c.allocate_oid()?,
+            };
             c.insert_item(id, oid, name, item);
         }
         Ok(c)
@@ -1015,6 +1055,18 @@ impl Catalog {
         Ok(oid)
     }
 
+    /// Allocates an OID for the type `typ`.
+    ///
+    /// Enum types additionally reserve the OID that follows their own for
+    /// their array type, as described on [`pgrepr::Type::Enum`].
+    pub fn allocate_type_oid(&mut self, typ: &Type) -> Result<u32, Error> {
+        let oid = self.allocate_oid()?;
+        if let TypeInner::Enum { .. } = typ.inner {
+            self.allocate_oid()?;
+        }
+        Ok(oid)
+    }
+
     pub fn resolve_schema(
         &self,
         current_database: &str,
@@ -1158,6 +1210,11 @@ impl Catalog {
         &self.by_id[id]
     }
 
+    /// Returns the named role, if it exists.
+    pub fn try_get_role(&self, role_name: &str) -> Option<&Role> {
+        self.roles.get(role_name)
+    }
+
     /// Creates a new schema in the `Catalog` for temporary items
     /// indicated by the TEMPORARY or TEMP keywords.
     pub fn create_temporary_schema(&mut self, conn_id: u32) -> Result<(), Error> {
@@ -1399,6 +1456,113 @@ impl Catalog {
         })
     }
 
+    /// Returns the [`Op`]s necessary to replace the definition of the table
+    /// `id` with `table`, e.g. after a column has been added or dropped.
+    ///
+    /// Views that use the table directly are replanned against its new
+    /// definition, and must continue to produce the same columns. Indexes on
+    /// the table, and on anything that transitively depends upon it, are
+    /// recreated under new IDs in the order of their original IDs, as their
+    /// dataflows need to be rebuilt. The table's default index is replaced by
+    /// `default_index`, as its key may name columns that no longer exist.
+    pub fn alter_table_ops(
+        &mut self,
+        id: GlobalId,
+        table: Table,
+        default_index: Index,
+    ) -> Result<Vec<Op>, Error> {
+        let table_name = self.get_by_id(&id).name().to_string();
+        let dependency_error = |depender: &CatalogEntry, message: String| {
+            Error::new(ErrorKind::InvalidAlterDependency {
+                depender: depender.name().to_string(),
+                dependee: table_name.clone(),
+                message,
+            })
+        };
+
+        let mut dependents = BTreeSet::new();
+        let mut queue = self.get_by_id(&id).used_by().to_vec();
+        while let Some(dep_id) = queue.pop() {
+            if dependents.insert(dep_id) {
+                queue.extend(self.get_by_id(&dep_id).used_by());
+            }
+        }
+
+        // Replan dependents against a scratch copy of the catalog in which the
+        // table has already been replaced.
+        let mut scratch = self.clone();
+        scratch.by_id.get_mut(&id).expect("table exists").item = CatalogItem::Table(table.clone());
+
+        let mut ops = vec![Op::UpdateItem {
+            id,
+            to_item: CatalogItem::Table(table),
+        }];
+        let default_index_id = self.default_index_for(id);
+        let mut default_index = Some(default_index);
+        let mut indexes = vec![];
+        for dep_id in dependents {
+            let entry = self.get_by_id(&dep_id);
+            if entry.item().is_temporary() {
+                return Err(dependency_error(
+                    entry,
+                    "temporary items cannot be replanned".into(),
+                ));
+            }
+            if let CatalogItem::Sink(_) = entry.item() {
+                return Err(dependency_error(entry, "sinks cannot be replanned".into()));
+            }
+            let replanned = if Some(dep_id) == default_index_id {
+                CatalogItem::Index(default_index.take().expect("default index is unique"))
+            } else if entry.uses().contains(&id) {
+                let create_sql = match entry.item() {
+                    CatalogItem::View(View { create_sql, .. })
+                    | CatalogItem::Index(Index { create_sql, .. }) => create_sql.clone(),
+                    _ => continue,
+                };
+                scratch
+                    .parse_item(dep_id, create_sql, Some(&PlanContext::zero()), None)
+                    .map_err(|e| dependency_error(entry, e.to_string()))?
+            } else {
+                entry.item().clone()
+            };
+            match (entry.item(), replanned) {
+                (CatalogItem::View(old), CatalogItem::View(new)) => {
+                    if old.desc != new.desc {
+                        return Err(dependency_error(entry, "its columns would change".into()));
+                    }
+                    scratch.by_id.get_mut(&dep_id).expect("view exists").item =
+                        CatalogItem::View(new.clone());
+                    if entry.uses().contains(&id) {
+                        ops.push(Op::UpdateItem {
+                            id: dep_id,
+                            to_item: CatalogItem::View(new),
+                        });
+                    }
+                }
+                (CatalogItem::Index(old), CatalogItem::Index(new)) => {
+                    ops.push(Op::DropItem(dep_id));
+                    indexes.push((
+                        entry.name().clone(),
+                        Index {
+                            enabled: old.enabled,
+                            ..new
+                        },
+                    ));
+                }
+                _ => (),
+            }
+        }
+        for (name, index) in indexes {
+            ops.push(Op::CreateItem {
+                id: self.allocate_id()?,
+                oid: self.allocate_oid()?,
+                name,
+                item: CatalogItem::Index(index),
+            });
+        }
+        Ok(ops)
+    }
+
     /// Gets GlobalIds of temporary items to be created, checks for name collisions
     /// within a connection id.
     fn temporary_ids(
@@ -1521,6 +1685,7 @@ impl Catalog {
                 id: i64,
                 oid: u32,
                 name: String,
+                password: Option<ScramSha256Verifier>,
             },
             CreateItem {
                 id: GlobalId,
@@ -1545,6 +1710,10 @@ impl Catalog {
                 to_name: FullName,
                 to_item: CatalogItem,
             },
+            UpdateRole {
+                name: String,
+                password: Option<ScramSha256Verifier>,
+            },
         }
 
         let drop_ids: HashSet<_> = ops
@@ -1554,6 +1723,18 @@ impl Catalog {
                 _ => None,
             })
             .collect();
+        // Tables whose indexes are being recreated by this transaction, which
+        // may therefore drop their default index.
+        let index_creates: HashSet<_> = ops
+            .iter()
+            .filter_map(|op| match op {
+                Op::CreateItem {
+                    item: CatalogItem::Index(index),
+                    ..
+                } => Some(index.on),
+                _ => None,
+            })
+            .collect();
         let temporary_drops = drop_ids
             .iter()
             .filter_map(|id| {
@@ -1597,14 +1778,22 @@ impl Catalog {
                         schema_name,
                     }]
                 }
-                Op::CreateRole { name, oid } => {
+                Op::CreateRole {
+                    name,
+                    oid,
+                    password,
+                } => {
                     if is_reserved_name(&name) {
                         return Err(Error::new(ErrorKind::ReservedRoleName(name)));
                     }
                     vec![Action::CreateRole {
-                        id: tx.insert_role(&name)?,
+                        id: tx.insert_role(
+                            &name,
+                            password.as_ref().map(|p| p.to_string()).as_deref(),
+                        )?,
                         oid,
                         name,
+                        password,
                     }]
                 }
                 Op::CreateItem {
@@ -1692,6 +1881,16 @@ impl Catalog {
                     builtin_table_updates.push(self.pack_role_update(&name, -1));
                     vec![Action::DropRole { name }]
                 }
+                Op::AlterRole { name, password } => {
+                    if is_reserved_name(&name) {
+                        return Err(Error::new(ErrorKind::ReservedRoleName(name)));
+                    }
+                    tx.update_role_password(
+                        &name,
+                        password.as_ref().map(|p| p.to_string()).as_deref(),
+                    )?;
+                    vec![Action::UpdateRole { name, password }]
+                }
                 Op::DropItem(id) => {
                     let entry = self.get_by_id(&id);
                     // Prevent dropping a table's default index unless the table
@@ -1700,6 +1899,7 @@ impl Catalog {
                         if self.get_by_id(on).is_table()
                             && self.default_index_for(*on) == Some(id)
                             && !drop_ids.contains(on)
+                            && !index_creates.contains(on)
                         {
                             return Err(Error::new(ErrorKind::MandatoryTableIndex(
                                 entry.name().to_string(),
@@ -1837,7 +2037,12 @@ impl Catalog {
                     ));
                 }
 
-                Action::CreateRole { id, oid, name } => {
+                Action::CreateRole {
+                    id,
+                    oid,
+                    name,
+                    password,
+                } => {
                     info!("create role {}", name);
                     self.roles.insert(
                         name.clone(),
@@ -1845,6 +2050,7 @@ impl Catalog {
                             name: name.clone(),
                             id,
                             oid,
+                            password,
                         },
                     );
                     builtin_table_updates.push(self.pack_role_update(&name, 1));
@@ -1878,6 +2084,11 @@ impl Catalog {
                     }
                 }
 
+                Action::UpdateRole { name, password } => {
+                    info!("alter role {}", name);
+                    self.roles.get_mut(&name).unwrap().password = password;
+                }
+
                 Action::DropItem(id) => {
                     let metadata = self.by_id.remove(&id).unwrap();
                     if !metadata.item.is_placeholder() {
@@ -1926,7 +2137,24 @@ impl Catalog {
                         old_entry.name,
                         id
                     );
-                    assert_eq!(old_entry.uses(), to_item.uses());
+                    for u in old_entry.uses() {
+                        if !to_item.uses().contains(u) {
+                            if let Some(dep_metadata) = self.by_id.get_mut(u) {
+                                dep_metadata.used_by.retain(|u| *u != id)
+                            }
+                        }
+                    }
+                    for u in to_item.uses() {
+                        if !old_entry.uses().contains(u) {
+                            match self.by_id.get_mut(u) {
+                                Some(metadata) => metadata.used_by.push(id),
+                                None => panic!(
+                                    "Catalog: missing dependent catalog item {} while updating {}",
+                                    u, old_entry.name
+                                ),
+                            }
+                        }
+                    }
 
                     // Handle updating any indexes. n.b. only supports enabling
                     // indexes; does not support disabling indexes.
@@ -2012,6 +2240,8 @@ impl Catalog {
                     create_sql: table.create_sql,
                     desc: table.desc,
                     defaults: table.defaults,
+                    unique_constraints: table.unique_constraints,
+                    check_constraints: table.check_constraints,
                     conn_id: None,
                     depends_on: table.depends_on,
                     persist,
@@ -2265,6 +2495,12 @@ impl Catalog {
         &self.config
     }
 
+    /// Returns the secret that salts the SCRAM exchanges with roles that do
+    /// not have a password.
+    pub fn mock_auth_secret(&self) -> Uuid {
+        self.mock_auth_secret
+    }
+
     pub fn entries(&self) -> impl Iterator<Item = &CatalogEntry> {
         self.by_id.values()
     }
@@ -2337,6 +2573,7 @@ pub enum Op {
     CreateRole {
         name: String,
         oid: u32,
+        password: Option<ScramSha256Verifier>,
     },
     CreateItem {
         id: GlobalId,
@@ -2354,6 +2591,11 @@ pub enum Op {
     DropRole {
         name: String,
     },
+    /// Replaces the named role's password. `None` removes the password.
+    AlterRole {
+        name: String,
+        password: Option<ScramSha256Verifier>,
+    },
     /// Unconditionally removes the identified items. It is required that the
     /// IDs come from the output of `plan_remove`; otherwise consistency rules
     /// may be violated.
@@ -2454,6 +2696,14 @@ impl ExprHumanizer for ConnCatalog<'_> {
                 let full_name = self.get_item_by_oid(&custom_oid.unwrap()).name();
                 self.minimal_qualification(full_name).to_string()
             }
+            Record {
+                custom_oid: Some(custom_oid),
+                ..
+            }
+            | Enum { custom_oid, .. } => {
+                let full_name = self.get_item_by_oid(custom_oid).name();
+                self.minimal_qualification(full_name).to_string()
+            }
             List { element_type, .. } => {
                 format!("{} list", self.humanize_scalar_type(element_type))
             }
@@ -2594,6 +2844,13 @@ impl SessionCatalog for ConnCatalog<'_> {
         self.catalog.get_by_id(id)
     }
 
+    fn try_get_item_by_oid(&self, oid: &u32) -> Option<&dyn sql::catalog::CatalogItem> {
+        self.catalog
+            .by_oid
+            .get(oid)
+            .map(|id| self.catalog.get_by_id(id) as &dyn sql::catalog::CatalogItem)
+    }
+
     fn get_item_by_oid(&self, oid: &u32) -> &dyn sql::catalog::CatalogItem {
         let id = self.catalog.by_oid[oid];
         self.catalog.get_by_id(&id)
@@ -2610,17 +2867,17 @@ impl SessionCatalog for ConnCatalog<'_> {
             _ => return None,
         };
 
-        Some(match t.inner {
+        Some(match &t.inner {
             TypeInner::Array { element_id } => {
                 let element_type = self
-                    .try_get_lossy_scalar_type_by_id(&element_id)
+                    .try_get_lossy_scalar_type_by_id(element_id)
                     .expect("array's element_id refers to a valid type");
                 ScalarType::Array(Box::new(element_type))
             }
             TypeInner::Base => pgrepr::Type::from_oid(entry.oid())?.to_scalar_type_lossy(),
             TypeInner::List { element_id } => {
                 let element_type = self
-                    .try_get_lossy_scalar_type_by_id(&element_id)
+                    .try_get_lossy_scalar_type_by_id(element_id)
                     .expect("list's element_id refers to a valid type");
                 ScalarType::List {
                     element_type: Box::new(element_type),
@@ -2629,11 +2886,11 @@ impl SessionCatalog for ConnCatalog<'_> {
             }
             TypeInner::Map { key_id, value_id } => {
                 let key_type = self
-                    .try_get_lossy_scalar_type_by_id(&key_id)
+                    .try_get_lossy_scalar_type_by_id(key_id)
                     .expect("map's key_id refers to a valid type");
                 assert!(matches!(key_type, ScalarType::String));
                 let value_type = Box::new(
-                    self.try_get_lossy_scalar_type_by_id(&value_id)
+                    self.try_get_lossy_scalar_type_by_id(value_id)
                         .expect("map's value_id refers to a valid type"),
                 );
                 ScalarType::Map {
@@ -2642,6 +2899,22 @@ impl SessionCatalog for ConnCatalog<'_> {
                 }
             }
             TypeInner::Pseudo => return None,
+            TypeInner::Enum { .. } => ScalarType::Enum {
+                custom_oid: entry.oid,
+            },
+            TypeInner::Record { fields } => ScalarType::Record {
+                fields: fields
+                    .iter()
+                    .map(|(name, id)| {
+                        let scalar_type = self
+                            .try_get_lossy_scalar_type_by_id(id)
+                            .expect("record's field id refers to a valid type");
+                        (name.clone(), scalar_type.nullable(true))
+                    })
+                    .collect(),
+                custom_oid: Some(entry.oid),
+                custom_name: Some(entry.name().item.clone()),
+            },
         })
     }
 
@@ -2741,6 +3014,29 @@ impl sql::catalog::CatalogItem for CatalogEntry {
         }
     }
 
+    fn table_unique_constraints(&self) -> Option<&[UniqueConstraint]> {
+        if let CatalogItem::Table(Table {
+            unique_constraints, ..
+        }) = self.item()
+        {
+            Some(unique_constraints)
+        } else {
+            None
+        }
+    }
+
+    fn enum_labels(&self) -> Option<&[String]> {
+        if let CatalogItem::Type(Type {
+            inner: TypeInner::Enum { labels },
+            ..
+        }) = self.item()
+        {
+            Some(labels)
+        } else {
+            None
+        }
+    }
+
     fn uses(&self) -> &[GlobalId] {
         self.uses()
     }
@@ -387,6 +387,12 @@ pub const TYPE_ANYELEMENT: BuiltinType = BuiltinType {
     pgtype: &postgres_types::Type::ANYELEMENT,
 };
 
+pub const TYPE_ANYENUM: BuiltinType = BuiltinType {
+    schema: PG_CATALOG_SCHEMA,
+    id: GlobalId::System(1048),
+    pgtype: &postgres_types::Type::ANYENUM,
+};
+
 pub const TYPE_ANYNONARRAY: BuiltinType = BuiltinType {
     schema: PG_CATALOG_SCHEMA,
     id: GlobalId::System(1037),
@@ -453,6 +459,84 @@ pub const TYPE_REGPROC_ARRAY: BuiltinType = BuiltinType {
     pgtype: &postgres_types::Type::REGPROC_ARRAY,
 };
 
+pub const TYPE_ANYRANGE: BuiltinType = BuiltinType {
+    schema: PG_CATALOG_SCHEMA,
+    id: GlobalId::System(1049),
+    pgtype: &postgres_types::Type::ANYRANGE,
+};
+
+pub const TYPE_INT4_RANGE: BuiltinType = BuiltinType {
+    schema: PG_CATALOG_SCHEMA,
+    id: GlobalId::System(1050),
+    pgtype: &postgres_types::Type::INT4_RANGE,
+};
+
+pub const TYPE_INT4_RANGE_ARRAY: BuiltinType = BuiltinType {
+    schema: PG_CATALOG_SCHEMA,
+    id: GlobalId::System(1051),
+    pgtype: &postgres_types::Type::INT4_RANGE_ARRAY,
+};
+
+pub const TYPE_INT8_RANGE: BuiltinType = BuiltinType {
+    schema: PG_CATALOG_SCHEMA,
+    id: GlobalId::System(1052),
+    pgtype: &postgres_types::Type::INT8_RANGE,
+};
+
+pub const TYPE_INT8_RANGE_ARRAY: BuiltinType = BuiltinType {
+    schema: PG_CATALOG_SCHEMA,
+    id: GlobalId::System(1053),
+    pgtype: &postgres_types::Type::INT8_RANGE_ARRAY,
+};
+
+pub const TYPE_NUM_RANGE: BuiltinType = BuiltinType {
+    schema: PG_CATALOG_SCHEMA,
+    id: GlobalId::System(1054),
+    pgtype: &postgres_types::Type::NUM_RANGE,
+};
+
+pub const TYPE_NUM_RANGE_ARRAY: BuiltinType = BuiltinType {
+    schema: PG_CATALOG_SCHEMA,
+    id: GlobalId::System(1055),
+    pgtype: &postgres_types::Type::NUM_RANGE_ARRAY,
+};
+
+pub const TYPE_TS_RANGE: BuiltinType = BuiltinType {
+    schema: PG_CATALOG_SCHEMA,
+    id: GlobalId::System(1056),
+    pgtype: &postgres_types::Type::TS_RANGE,
+};
+
+pub const TYPE_TS_RANGE_ARRAY: BuiltinType = BuiltinType {
+    schema: PG_CATALOG_SCHEMA,
+    id: GlobalId::System(1057),
+    pgtype: &postgres_types::Type::TS_RANGE_ARRAY,
+};
+
+pub const TYPE_TSTZ_RANGE: BuiltinType = BuiltinType {
+    schema: PG_CATALOG_SCHEMA,
+    id: GlobalId::System(1058),
+    pgtype: &postgres_types::Type::TSTZ_RANGE,
+};
+
+pub const TYPE_TSTZ_RANGE_ARRAY: BuiltinType = BuiltinType {
+    schema: PG_CATALOG_SCHEMA,
+    id: GlobalId::System(1059),
+    pgtype: &postgres_types::Type::TSTZ_RANGE_ARRAY,
+};
+
+pub const TYPE_DATE_RANGE: BuiltinType = BuiltinType {
+    schema: PG_CATALOG_SCHEMA,
+    id: GlobalId::System(1060),
+    pgtype: &postgres_types::Type::DATE_RANGE,
+};
+
+pub const TYPE_DATE_RANGE_ARRAY: BuiltinType = BuiltinType {
+    schema: PG_CATALOG_SCHEMA,
+    id: GlobalId::System(1061),
+    pgtype: &postgres_types::Type::DATE_RANGE_ARRAY,
+};
+
 lazy_static! {
     pub static ref TYPE_LIST: BuiltinType = BuiltinType {
         schema: PG_CATALOG_SCHEMA,
@@ -677,6 +761,17 @@ lazy_static! {
         index_id: GlobalId::System(4008),
         persistent: false,
     };
+    pub static ref MZ_SINK_ERRORS: BuiltinTable = BuiltinTable {
+        name: "mz_sink_errors",
+        schema: MZ_CATALOG_SCHEMA,
+        desc: RelationDesc::empty()
+            .with_named_column("sink_id", ScalarType::String.nullable(false))
+            .with_named_column("error", ScalarType::String.nullable(false))
+            .with_key(vec![0]),
+        id: GlobalId::System(4055),
+        index_id: GlobalId::System(4056),
+        persistent: false,
+    };
     pub static ref MZ_DATABASES: BuiltinTable = BuiltinTable {
         name: "mz_databases",
         schema: MZ_CATALOG_SCHEMA,
@@ -867,6 +962,36 @@ lazy_static! {
         index_id: GlobalId::System(4040),
         persistent: false,
     };
+    pub static ref MZ_ENUM_TYPES: BuiltinTable = BuiltinTable {
+        name: "mz_enum_types",
+        schema: MZ_CATALOG_SCHEMA,
+        desc: RelationDesc::empty()
+            .with_named_column("type_id", ScalarType::String.nullable(false))
+            .with_named_column("array_oid", ScalarType::Oid.nullable(false)),
+        id: GlobalId::System(4049),
+        index_id: GlobalId::System(4050),
+        persistent: false,
+    };
+    pub static ref MZ_ENUM_LABELS: BuiltinTable = BuiltinTable {
+        name: "mz_enum_labels",
+        schema: MZ_CATALOG_SCHEMA,
+        desc: RelationDesc::empty()
+            .with_named_column("type_id", ScalarType::String.nullable(false))
+            .with_named_column("position", ScalarType::Int64.nullable(false))
+            .with_named_column("label", ScalarType::String.nullable(false)),
+        id: GlobalId::System(4051),
+        index_id: GlobalId::System(4052),
+        persistent: false,
+    };
+    pub static ref MZ_RECORD_TYPES: BuiltinTable = BuiltinTable {
+        name: "mz_record_types",
+        schema: MZ_CATALOG_SCHEMA,
+        desc: RelationDesc::empty()
+            .with_named_column("type_id", ScalarType::String.nullable(false)),
+        id: GlobalId::System(4053),
+        index_id: GlobalId::System(4054),
+        persistent: false,
+    };
     pub static ref MZ_FUNCTIONS: BuiltinTable = BuiltinTable {
         name: "mz_functions",
         schema: MZ_CATALOG_SCHEMA,
@@ -1288,6 +1413,7 @@ pub const PG_TYPE: BuiltinView = BuiltinView {
             WHERE
                 a.element_id = mz_types.id
         ),
+        (SELECT e.array_oid FROM mz_catalog.mz_enum_types AS e WHERE e.type_id = mz_types.id),
         0
     )
         AS typarray,
@@ -1305,6 +1431,8 @@ FROM
             -- converted to the correct value above.
             SELECT type_id, 'a' AS mztype FROM mz_catalog.mz_array_types
             UNION ALL SELECT type_id, 'b' FROM mz_catalog.mz_base_types
+            UNION ALL SELECT type_id, 'c' FROM mz_catalog.mz_record_types
+            UNION ALL SELECT type_id, 'e' FROM mz_catalog.mz_enum_types
             UNION ALL SELECT type_id, 'l' FROM mz_catalog.mz_list_types
             UNION ALL SELECT type_id, 'm' FROM mz_catalog.mz_map_types
             UNION ALL SELECT type_id, 'p' FROM mz_catalog.mz_pseudo_types
@@ -1364,10 +1492,12 @@ pub const PG_ENUM: BuiltinView = BuiltinView {
     schema: PG_CATALOG_SCHEMA,
     sql: "CREATE VIEW pg_enum AS SELECT
     NULL::pg_catalog.oid AS oid,
-    NULL::pg_catalog.oid AS enumtypid,
-    NULL::pg_catalog.float4 AS enumsortorder,
-    NULL::pg_catalog.text AS enumlabel
-    WHERE false",
+    mz_types.oid AS enumtypid,
+    (mz_enum_labels.position + 1)::pg_catalog.float4 AS enumsortorder,
+    mz_enum_labels.label AS enumlabel
+FROM
+    mz_catalog.mz_enum_labels
+    JOIN mz_catalog.mz_types ON mz_types.id = mz_enum_labels.type_id",
     id: GlobalId::System(5023),
     needs_logs: false,
 };
@@ -1555,7 +1685,9 @@ lazy_static! {
             Builtin::Type(&TYPE_ANY),
             Builtin::Type(&TYPE_ANYARRAY),
             Builtin::Type(&TYPE_ANYELEMENT),
+            Builtin::Type(&TYPE_ANYENUM),
             Builtin::Type(&TYPE_ANYNONARRAY),
+            Builtin::Type(&TYPE_ANYRANGE),
             Builtin::Type(&TYPE_BOOL),
             Builtin::Type(&TYPE_BOOL_ARRAY),
             Builtin::Type(&TYPE_BYTEA),
@@ -1566,14 +1698,20 @@ lazy_static! {
             Builtin::Type(&TYPE_CHAR_ARRAY),
             Builtin::Type(&TYPE_DATE),
             Builtin::Type(&TYPE_DATE_ARRAY),
+            Builtin::Type(&TYPE_DATE_RANGE),
+            Builtin::Type(&TYPE_DATE_RANGE_ARRAY),
             Builtin::Type(&TYPE_FLOAT4),
             Builtin::Type(&TYPE_FLOAT4_ARRAY),
             Builtin::Type(&TYPE_FLOAT8),
             Builtin::Type(&TYPE_FLOAT8_ARRAY),
             Builtin::Type(&TYPE_INT4),
             Builtin::Type(&TYPE_INT4_ARRAY),
+            Builtin::Type(&TYPE_INT4_RANGE),
+            Builtin::Type(&TYPE_INT4_RANGE_ARRAY),
             Builtin::Type(&TYPE_INT8),
             Builtin::Type(&TYPE_INT8_ARRAY),
+            Builtin::Type(&TYPE_INT8_RANGE),
+            Builtin::Type(&TYPE_INT8_RANGE_ARRAY),
             Builtin::Type(&TYPE_INTERVAL),
             Builtin::Type(&TYPE_INTERVAL_ARRAY),
             Builtin::Type(&TYPE_JSONB),
@@ -1582,6 +1720,8 @@ lazy_static! {
             Builtin::Type(&TYPE_MAP),
             Builtin::Type(&TYPE_NUMERIC),
             Builtin::Type(&TYPE_NUMERIC_ARRAY),
+            Builtin::Type(&TYPE_NUM_RANGE),
+            Builtin::Type(&TYPE_NUM_RANGE_ARRAY),
             Builtin::Type(&TYPE_OID),
             Builtin::Type(&TYPE_OID_ARRAY),
             Builtin::Type(&TYPE_RECORD),
@@ -1596,8 +1736,12 @@ lazy_static! {
             Builtin::Type(&TYPE_TIME_ARRAY),
             Builtin::Type(&TYPE_TIMESTAMP),
             Builtin::Type(&TYPE_TIMESTAMP_ARRAY),
+            Builtin::Type(&TYPE_TS_RANGE),
+            Builtin::Type(&TYPE_TS_RANGE_ARRAY),
             Builtin::Type(&TYPE_TIMESTAMPTZ),
             Builtin::Type(&TYPE_TIMESTAMPTZ_ARRAY),
+            Builtin::Type(&TYPE_TSTZ_RANGE),
+            Builtin::Type(&TYPE_TSTZ_RANGE_ARRAY),
             Builtin::Type(&TYPE_UUID),
             Builtin::Type(&TYPE_UUID_ARRAY),
             Builtin::Type(&TYPE_VARCHAR),
@@ -1626,6 +1770,7 @@ lazy_static! {
             Builtin::Table(&MZ_VIEW_FOREIGN_KEYS),
             Builtin::Table(&MZ_KAFKA_SINKS),
             Builtin::Table(&MZ_AVRO_OCF_SINKS),
+            Builtin::Table(&MZ_SINK_ERRORS),
             Builtin::Table(&MZ_DATABASES),
             Builtin::Table(&MZ_SCHEMAS),
             Builtin::Table(&MZ_COLUMNS),
@@ -1642,6 +1787,9 @@ lazy_static! {
             Builtin::Table(&MZ_MAP_TYPES),
             Builtin::Table(&MZ_ROLES),
             Builtin::Table(&MZ_PSEUDO_TYPES),
+            Builtin::Table(&MZ_ENUM_TYPES),
+            Builtin::Table(&MZ_ENUM_LABELS),
+            Builtin::Table(&MZ_RECORD_TYPES),
             Builtin::Table(&MZ_FUNCTIONS),
             Builtin::Table(&MZ_PROMETHEUS_READINGS),
             Builtin::Table(&MZ_PROMETHEUS_HISTOGRAMS),
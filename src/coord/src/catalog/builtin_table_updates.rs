@@ -20,9 +20,10 @@ use sql::names::DatabaseSpecifier;
 use sql_parser::ast::display::AstDisplay;
 
 use crate::catalog::builtin::{
-    MZ_ARRAY_TYPES, MZ_AVRO_OCF_SINKS, MZ_BASE_TYPES, MZ_COLUMNS, MZ_DATABASES, MZ_FUNCTIONS,
-    MZ_INDEXES, MZ_INDEX_COLUMNS, MZ_KAFKA_SINKS, MZ_LIST_TYPES, MZ_MAP_TYPES, MZ_PSEUDO_TYPES,
-    MZ_ROLES, MZ_SCHEMAS, MZ_SINKS, MZ_SOURCES, MZ_TABLES, MZ_TYPES, MZ_VIEWS,
+    MZ_ARRAY_TYPES, MZ_AVRO_OCF_SINKS, MZ_BASE_TYPES, MZ_COLUMNS, MZ_DATABASES, MZ_ENUM_LABELS,
+    MZ_ENUM_TYPES, MZ_FUNCTIONS, MZ_INDEXES, MZ_INDEX_COLUMNS, MZ_KAFKA_SINKS, MZ_LIST_TYPES,
+    MZ_MAP_TYPES, MZ_PSEUDO_TYPES, MZ_RECORD_TYPES, MZ_ROLES, MZ_SCHEMAS, MZ_SINKS, MZ_SOURCES,
+    MZ_TABLES, MZ_TYPES, MZ_VIEWS,
 };
 use crate::catalog::{
     Catalog, CatalogItem, Func, Index, Sink, SinkConnector, SinkConnectorState, Source, Table,
@@ -362,7 +363,8 @@ impl Catalog {
             diff,
         };
 
-        let (index_id, update) = match typ.inner {
+        let mut updates = vec![generic_update];
+        let (index_id, update) = match &typ.inner {
             TypeInner::Array { element_id } => (
                 MZ_ARRAY_TYPES.id,
                 vec![id.to_string(), element_id.to_string()],
@@ -377,14 +379,39 @@ impl Catalog {
                 vec![id.to_string(), key_id.to_string(), value_id.to_string()],
             ),
             TypeInner::Pseudo => (MZ_PSEUDO_TYPES.id, vec![id.to_string()]),
+            TypeInner::Enum { labels } => {
+                let type_id = id.to_string();
+                for (i, label) in labels.iter().enumerate() {
+                    updates.push(BuiltinTableUpdate {
+                        id: MZ_ENUM_LABELS.id,
+                        row: Row::pack_slice(&[
+                            Datum::String(&type_id),
+                            Datum::Int64(i as i64),
+                            Datum::String(label),
+                        ]),
+                        diff,
+                    });
+                }
+                let array_oid = pgrepr::Type::Array(Box::new(pgrepr::Type::Enum { oid })).oid();
+                updates.push(BuiltinTableUpdate {
+                    id: MZ_ENUM_TYPES.id,
+                    row: Row::pack_slice(&[
+                        Datum::String(&type_id),
+                        Datum::Int32(array_oid as i32),
+                    ]),
+                    diff,
+                });
+                return updates;
+            }
+            TypeInner::Record { .. } => (MZ_RECORD_TYPES.id, vec![id.to_string()]),
         };
-        let specific_update = BuiltinTableUpdate {
+        updates.push(BuiltinTableUpdate {
             id: index_id,
             row: Row::pack_slice(&update.iter().map(|c| Datum::String(c)).collect::<Vec<_>>()[..]),
             diff,
-        };
+        });
 
-        vec![generic_update, specific_update]
+        updates
     }
 
     fn pack_func_update(
@@ -55,6 +55,11 @@ pub enum ErrorKind {
         message: String,
     },
     TypeRename(String),
+    InvalidAlterDependency {
+        depender: String,
+        dependee: String,
+        message: String,
+    },
     ExperimentalModeRequired,
     ExperimentalModeUnavailable,
     FailedMigration {
@@ -136,6 +141,7 @@ impl std::error::Error for Error {
             | ErrorKind::UnsatisfiableLoggingDependency { .. }
             | ErrorKind::AmbiguousRename { .. }
             | ErrorKind::TypeRename(_)
+            | ErrorKind::InvalidAlterDependency { .. }
             | ErrorKind::ExperimentalModeRequired
             | ErrorKind::ExperimentalModeUnavailable
             | ErrorKind::FailedMigration { .. }
@@ -211,6 +217,15 @@ impl fmt::Display for Error {
                 }
             }
             ErrorKind::TypeRename(typ) => write!(f, "cannot rename type: {}", typ),
+            ErrorKind::InvalidAlterDependency {
+                depender,
+                dependee,
+                message,
+            } => write!(
+                f,
+                "cannot alter {}, as {} depends on it: {}",
+                dependee, depender, message
+            ),
             ErrorKind::ExperimentalModeRequired => write!(
                 f,
                 r#"Materialize previously started with --experimental to
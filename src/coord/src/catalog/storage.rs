@@ -120,6 +120,10 @@ const MIGRATIONS: &[&str] = &[
         offset blob NOT NULL,
         PRIMARY KEY (sid, pid, timestamp, offset)
     );",
+    // Adds password verifiers to roles to support password authentication.
+    //
+    // Introduced in v0.9.7.
+    "ALTER TABLE roles ADD COLUMN password text;",
     // Add new migrations here.
     //
     // Migrations should be preceded with a comment of the following form:
@@ -143,7 +147,7 @@ pub struct Connection {
 }
 
 impl Connection {
-    pub fn open(config: &Config) -> Result<(Connection, bool, Uuid), Error> {
+    pub fn open(config: &Config) -> Result<(Connection, bool, Uuid, Uuid), Error> {
         let mut sqlite = rusqlite::Connection::open(&config.path)?;
 
         // Validate application ID.
@@ -180,8 +184,14 @@ impl Connection {
         let experimental_mode =
             Self::set_or_get_experimental_mode(&mut sqlite, config.experimental_mode)?;
         let cluster_id = Self::set_or_get_cluster_id(&mut sqlite)?;
+        let mock_auth_secret = Self::set_or_get_mock_auth_secret(&mut sqlite)?;
 
-        Ok((Connection { inner: sqlite }, experimental_mode, cluster_id))
+        Ok((
+            Connection { inner: sqlite },
+            experimental_mode,
+            cluster_id,
+            mock_auth_secret,
+        ))
     }
 
     /// Sets catalog's `experimental_mode` setting on initialization or gets
@@ -275,6 +285,39 @@ impl Connection {
         res
     }
 
+    /// Sets catalog's `mock_auth_secret` setting on initialization or gets that
+    /// value.
+    ///
+    /// The secret salts the SCRAM exchanges with roles that do not have a
+    /// password, so it must never be revealed.
+    fn set_or_get_mock_auth_secret(sqlite: &mut rusqlite::Connection) -> Result<Uuid, Error> {
+        let tx = sqlite.transaction()?;
+        let current_setting: Option<SqlVal<Uuid>> = tx
+            .query_row(
+                "SELECT value FROM settings WHERE name = 'mock_auth_secret';",
+                params![],
+                |row| row.get(0),
+            )
+            .optional()?;
+
+        let res = match current_setting {
+            // Server init, or the first boot since the setting was introduced
+            None => {
+                // Generate a new version 4 UUID. These are generated from random input.
+                let mock_auth_secret = Uuid::new_v4();
+                tx.execute(
+                    "INSERT INTO settings VALUES ('mock_auth_secret', ?);",
+                    params![SqlVal(mock_auth_secret)],
+                )?;
+                Ok(mock_auth_secret)
+            }
+            // Server reboot
+            Some(cs) => Ok(cs.0),
+        };
+        tx.commit()?;
+        res
+    }
+
     pub fn get_catalog_content_version(&mut self) -> Result<String, Error> {
         let tx = self.inner.transaction()?;
         let current_setting: Option<String> = tx
@@ -334,13 +377,14 @@ impl Connection {
             .collect()
     }
 
-    pub fn load_roles(&self) -> Result<Vec<(i64, String)>, Error> {
+    pub fn load_roles(&self) -> Result<Vec<(i64, String, Option<String>)>, Error> {
         self.inner
-            .prepare("SELECT id, name FROM roles")?
+            .prepare("SELECT id, name, password FROM roles")?
             .query_and_then(params![], |row| -> Result<_, Error> {
                 let id: i64 = row.get(0)?;
                 let name: String = row.get(1)?;
-                Ok((id, name))
+                let password: Option<String> = row.get(2)?;
+                Ok((id, name, password))
             })?
             .collect()
     }
@@ -476,11 +520,11 @@ impl Transaction<'_> {
         }
     }
 
-    pub fn insert_role(&mut self, role_name: &str) -> Result<i64, Error> {
+    pub fn insert_role(&mut self, role_name: &str, password: Option<&str>) -> Result<i64, Error> {
         match self
             .inner
-            .prepare_cached("INSERT INTO roles (name) VALUES (?)")?
-            .execute(params![role_name])
+            .prepare_cached("INSERT INTO roles (name, password) VALUES (?, ?)")?
+            .execute(params![role_name, password])
         {
             Ok(_) => Ok(self.inner.last_insert_rowid()),
             Err(err) if is_constraint_violation(&err) => Err(Error::new(
@@ -639,6 +683,19 @@ impl Transaction<'_> {
         }
     }
 
+    pub fn update_role_password(&self, name: &str, password: Option<&str>) -> Result<(), Error> {
+        let n = self
+            .inner
+            .prepare_cached("UPDATE roles SET password = ? WHERE name = ?")?
+            .execute(params![password, name])?;
+        assert!(n <= 1);
+        if n == 1 {
+            Ok(())
+        } else {
+            Err(SqlCatalogError::UnknownRole(name.to_owned()).into())
+        }
+    }
+
     pub fn commit(self) -> Result<(), rusqlite::Error> {
         self.inner.commit()
     }
@@ -15,12 +15,14 @@ use std::sync::Arc;
 use derivative::Derivative;
 use serde::Serialize;
 use tokio::sync::oneshot;
+use uuid::Uuid;
 
 use dataflow_types::PeekResponse;
 use expr::GlobalId;
 use ore::str::StrExt;
 use repr::Row;
 use sql::ast::{FetchDirection, ObjectType, Raw, Statement};
+use sql::auth::ScramSha256Verifier;
 use sql::plan::ExecuteTimeout;
 use tokio::sync::watch;
 
@@ -107,6 +109,13 @@ pub struct StartupResponse {
     pub secret_key: u32,
     /// Notifications associated with session startup.
     pub messages: Vec<StartupMessage>,
+    /// The verifier for the session role's password, if the role has a
+    /// password. The client must prove knowledge of the password before the
+    /// session is usable.
+    pub password: Option<ScramSha256Verifier>,
+    /// The secret from which the mock SCRAM salt is derived, for roles without
+    /// a password that must nonetheless authenticate.
+    pub mock_auth_secret: Uuid,
 }
 
 /// Messages in a [`StartupResponse`].
@@ -80,30 +80,32 @@ use ore::now::{system_time, to_datetime, EpochMillis, NowFn};
 use ore::retry::Retry;
 use ore::thread::{JoinHandleExt as _, JoinOnDropHandle};
 use repr::adt::numeric;
-use repr::{Datum, Diff, RelationDesc, Row, RowArena, Timestamp};
+use repr::{Datum, Diff, RelationDesc, Row, RowArena, ScalarType, Timestamp};
 use sql::ast::display::AstDisplay;
 use sql::ast::{
     ConnectorType, CreateIndexStatement, CreateSchemaStatement, CreateSinkStatement,
     CreateSourceStatement, CreateTableStatement, DropObjectsStatement, ExplainStage,
     FetchStatement, Ident, InsertSource, ObjectType, Query, Raw, SetExpr, Statement,
 };
+use sql::auth::ScramSha256Verifier;
 use sql::catalog::{CatalogError, SessionCatalog as _};
 use sql::names::{DatabaseSpecifier, FullName};
 use sql::plan::{
     AlterIndexEnablePlan, AlterIndexResetOptionsPlan, AlterIndexSetOptionsPlan,
-    AlterItemRenamePlan, CreateDatabasePlan, CreateIndexPlan, CreateRolePlan, CreateSchemaPlan,
-    CreateSinkPlan, CreateSourcePlan, CreateTablePlan, CreateTypePlan, CreateViewPlan,
-    CreateViewsPlan, DropDatabasePlan, DropItemsPlan, DropRolesPlan, DropSchemaPlan, ExecutePlan,
-    ExplainPlan, FetchPlan, IndexOption, IndexOptionName, InsertPlan, MutationKind, Params,
-    PeekPlan, PeekWhen, Plan, ReadThenWritePlan, SendDiffsPlan, SetVariablePlan, ShowVariablePlan,
-    Source, TailPlan,
+    AlterItemRenamePlan, AlterRolePlan, AlterTablePlan, AlterTableRewrite, ConflictAction,
+    CreateDatabasePlan, CreateIndexPlan, CreateRolePlan, CreateSchemaPlan, CreateSinkPlan,
+    CreateSourcePlan, CreateTablePlan, CreateTypePlan, CreateViewPlan, CreateViewsPlan,
+    DropDatabasePlan, DropItemsPlan, DropRolesPlan, DropSchemaPlan, ExecutePlan, ExplainPlan,
+    FetchPlan, IndexOption, IndexOptionName, InsertPlan, MutationKind, Params, PeekPlan, PeekWhen,
+    Plan, ReadThenWritePlan, SendDiffsPlan, SetVariablePlan, ShowVariablePlan, Source, TailPlan,
 };
 use sql::plan::{StatementDesc, View};
 use transform::Optimizer;
 
 use self::arrangement_state::{ArrangementFrontiers, Frontiers, SinkWrites};
 use self::prometheus::Scraper;
-use crate::catalog::builtin::{BUILTINS, MZ_VIEW_FOREIGN_KEYS, MZ_VIEW_KEYS};
+use self::table_constraints::TableKeys;
+use crate::catalog::builtin::{BUILTINS, MZ_SINK_ERRORS, MZ_VIEW_FOREIGN_KEYS, MZ_VIEW_KEYS};
 use crate::catalog::{self, BuiltinTableUpdate, Catalog, CatalogItem, SinkConnectorState, Table};
 use crate::client::{Client, Handle};
 use crate::command::{
@@ -124,6 +126,7 @@ mod antichain;
 mod arrangement_state;
 mod dataflow_builder;
 mod prometheus;
+mod table_constraints;
 
 #[derive(Debug)]
 pub enum Message {
@@ -134,6 +137,7 @@ pub enum Message {
     SinkConnectorReady(SinkConnectorReady),
     ScrapeMetrics,
     SendDiffs(SendDiffs),
+    RewriteTable(RewriteTable),
     WriteLockGrant(tokio::sync::OwnedMutexGuard<()>),
     Shutdown,
 }
@@ -149,6 +153,18 @@ pub struct SendDiffs {
     pub kind: MutationKind,
 }
 
+/// The contents of a table read by `ALTER TABLE`, to be rewritten into the
+/// table's new definition.
+#[derive(Derivative)]
+#[derivative(Debug)]
+pub struct RewriteTable {
+    session: Session,
+    #[derivative(Debug = "ignore")]
+    tx: ClientTransmitter<ExecuteResponse>,
+    pub plan: AlterTablePlan,
+    pub rows: Result<Vec<Row>, CoordError>,
+}
+
 #[derive(Debug)]
 pub struct AdvanceSourceTimestamp {
     pub id: GlobalId,
@@ -218,6 +234,9 @@ pub struct Config<'a> {
     pub metrics_registry: MetricsRegistry,
     /// Persistence subsystem configuration.
     pub persist: PersistConfig,
+    /// A password to assign to the default role at startup, if that role does
+    /// not already have a password.
+    pub bootstrap_password: Option<ScramSha256Verifier>,
 }
 
 /// Glues the external world to the Timely workers.
@@ -270,6 +289,11 @@ pub struct Coordinator {
     txn_reads: HashMap<u32, TxnReads>,
     /// Tracks write frontiers for active exactly-once sinks.
     sink_writes: HashMap<GlobalId, SinkWrites<Timestamp>>,
+    /// The errors that stopped sinks from writing, as recorded in
+    /// `mz_sink_errors`.
+    sink_errors: HashMap<GlobalId, String>,
+    /// Tracks the keys of tables with unique constraints.
+    table_keys: TableKeys,
 
     /// A map from pending peeks to the queue into which responses are sent, and
     /// the IDs of workers who have responded.
@@ -476,6 +500,12 @@ impl Coordinator {
                     let connector = sink_connector::build(builder.clone(), entry.id())
                         .await
                         .with_context(|| format!("recreating sink {}", entry.name()))?;
+                    // A sink that can no longer resume where it left off must
+                    // not prevent booting, so that it can be dropped.
+                    if let Err(e) = self.determine_sink_as_of(sink, &connector) {
+                        self.record_sink_error(entry.id(), e.to_string());
+                        continue;
+                    }
                     self.handle_sink_connector_ready(entry.id(), entry.oid(), connector)?;
                 }
                 _ => (), // Handled in prior loop.
@@ -599,6 +629,7 @@ impl Coordinator {
                     // here.
                 }
                 Message::SendDiffs(diffs) => self.message_send_diffs(diffs),
+                Message::RewriteTable(rewrite) => self.message_rewrite_table(rewrite),
                 Message::AdvanceSourceTimestamp(advance) => {
                     self.message_advance_source_timestamp(advance)
                 }
@@ -715,6 +746,13 @@ impl Coordinator {
                     }
                 }
             }
+            WorkerFeedback::SinkError(sink_id, error) => {
+                // The sink may have been dropped while the error was in
+                // flight.
+                if self.catalog.try_get_by_id(sink_id).is_some() {
+                    self.record_sink_error(sink_id, error);
+                }
+            }
             WorkerFeedback::FrontierUppers(updates) => {
                 for (name, changes) in updates {
                     self.update_upper(&name, changes);
@@ -826,12 +864,14 @@ impl Coordinator {
                 // a Kafka topic) that's been created on our behalf. If
                 // we fail now, we'll leak that external state.
                 if self.catalog.try_get_by_id(id).is_some() {
-                    // TODO(benesch): this `expect` here is possibly scary, but
-                    // no better solution presents itself. Possibly sinks should
-                    // have an error bit, and an error here would set the error
-                    // bit on the sink.
-                    self.handle_sink_connector_ready(id, oid, connector)
-                        .expect("marking sink ready should never fail");
+                    // A sink that cannot resume where it left off has only
+                    // reused existing external state, so it fails instead.
+                    if let Err(e) = self.handle_sink_connector_ready(id, oid, connector) {
+                        self.catalog_transact(vec![catalog::Op::DropItem(id)])
+                            .expect("deleting placeholder sink cannot fail");
+                        tx.send(Err(e), session);
+                        return;
+                    }
                 } else {
                     // Another session dropped the sink while we were
                     // creating the connector. Report to the client that
@@ -891,6 +931,19 @@ impl Coordinator {
         }
     }
 
+    fn message_rewrite_table(
+        &mut self,
+        RewriteTable {
+            session,
+            tx,
+            plan,
+            rows,
+        }: RewriteTable,
+    ) {
+        let result = rows.and_then(|rows| self.sequence_rewrite_table(plan, rows));
+        tx.send(result, session);
+    }
+
     fn message_advance_source_timestamp(
         &mut self,
         AdvanceSourceTimestamp { id, update }: AdvanceSourceTimestamp,
@@ -918,14 +971,18 @@ impl Coordinator {
                     return;
                 }
 
+                let password = match self.catalog.try_get_role(session.user()) {
+                    Some(role) => role.password.clone(),
+                    None => {
+                        let _ = tx.send(Response {
+                            result: Err(CoordError::UnknownLoginRole(session.user().into())),
+                            session,
+                        });
+                        return;
+                    }
+                };
+
                 let catalog = self.catalog.for_session(&session);
-                if catalog.resolve_role(session.user()).is_err() {
-                    let _ = tx.send(Response {
-                        result: Err(CoordError::UnknownLoginRole(session.user().into())),
-                        session,
-                    });
-                    return;
-                }
 
                 let mut messages = vec![];
                 if catalog
@@ -951,6 +1008,8 @@ impl Coordinator {
                     Ok(StartupResponse {
                         messages,
                         secret_key,
+                        password,
+                        mock_auth_secret: self.catalog.mock_auth_secret(),
                     }),
                     session,
                 )
@@ -1052,7 +1111,7 @@ impl Coordinator {
                                             body: SetExpr::Values(..),
                                             ..
                                         }) | InsertSource::DefaultValues
-                                    ) =>
+                                    ) && insert_statment.on_conflict.is_none() =>
                                 {
                                     // Inserting from default? values statements
                                     // is always safe.
@@ -1061,6 +1120,8 @@ impl Coordinator {
                                 // Statements below must by run singly (in Started).
                                 Statement::AlterIndex(_)
                                 | Statement::AlterObjectRename(_)
+                                | Statement::AlterRole(_)
+                                | Statement::AlterTable(_)
                                 | Statement::CreateDatabase(_)
                                 | Statement::CreateIndex(_)
                                 | Statement::CreateRole(_)
@@ -1565,6 +1626,7 @@ impl Coordinator {
             CatalogItem::Sink(sink) => sink.clone(),
             _ => unreachable!(),
         };
+        let as_of = self.determine_sink_as_of(&sink, &connector)?;
         sink.connector = catalog::SinkConnectorState::Ready(connector.clone());
         let ops = vec![
             catalog::Op::DropItem(id),
@@ -1576,10 +1638,6 @@ impl Coordinator {
             },
         ];
         self.catalog_transact(ops)?;
-        let as_of = SinkAsOf {
-            frontier: self.determine_frontier(sink.from),
-            strict: !sink.with_snapshot,
-        };
         let sink_description = dataflow_types::SinkDesc {
             from: sink.from,
             from_desc: self.catalog.get_by_id(&sink.from).desc().unwrap().clone(),
@@ -1732,6 +1790,12 @@ impl Coordinator {
             Plan::AlterItemRename(plan) => {
                 tx.send(self.sequence_alter_item_rename(plan), session);
             }
+            Plan::AlterTable(plan) => {
+                self.sequence_alter_table(tx, session, plan);
+            }
+            Plan::AlterRole(plan) => {
+                tx.send(self.sequence_alter_role(plan), session);
+            }
             Plan::AlterIndexSetOptions(plan) => {
                 tx.send(self.sequence_alter_index_set_options(plan), session);
             }
@@ -1902,11 +1966,21 @@ impl Coordinator {
         let op = catalog::Op::CreateRole {
             name: plan.name,
             oid,
+            password: plan.password,
         };
         self.catalog_transact(vec![op])
             .map(|_| ExecuteResponse::CreatedRole)
     }
 
+    fn sequence_alter_role(&mut self, plan: AlterRolePlan) -> Result<ExecuteResponse, CoordError> {
+        let op = catalog::Op::AlterRole {
+            name: plan.name,
+            password: plan.password,
+        };
+        self.catalog_transact(vec![op])
+            .map(|_| ExecuteResponse::AlteredObject(ObjectType::Role))
+    }
+
     fn sequence_create_table(
         &mut self,
         session: &Session,
@@ -1930,10 +2004,19 @@ impl Coordinator {
             .catalog
             .persist_details(table_id, &name)
             .map_err(|err| anyhow!("{}", err))?;
+        // The keys of persisted tables would need to be recovered on restart,
+        // which is not yet supported.
+        if persist.is_some() && !table.unique_constraints.is_empty() {
+            return Err(CoordError::Unsupported(
+                "PRIMARY KEY and UNIQUE constraints on persisted tables",
+            ));
+        }
         let table = catalog::Table {
             create_sql: table.create_sql,
             desc: table.desc,
             defaults: table.defaults,
+            unique_constraints: table.unique_constraints,
+            check_constraints: table.check_constraints,
             conn_id,
             depends_on: table.depends_on,
             persist,
@@ -2392,7 +2475,7 @@ impl Coordinator {
             depends_on: plan.typ.depends_on,
         };
         let id = self.catalog.allocate_id()?;
-        let oid = self.catalog.allocate_oid()?;
+        let oid = self.catalog.allocate_type_oid(&typ)?;
         let op = catalog::Op::CreateItem {
             id,
             oid,
@@ -2563,7 +2646,42 @@ impl Coordinator {
                         let mut persist_streams = Vec::new();
                         let mut persist_updates = Vec::new();
                         let mut volatile_updates = Vec::new();
-                        for WriteOp { id, rows } in inserts {
+
+                        // Other transactions may have committed conflicting
+                        // keys since these writes were validated, so validate
+                        // them again before applying any of them.
+                        let mut table_writes = HashMap::<_, Vec<_>>::new();
+                        for WriteOp { id, desc, rows } in &inserts {
+                            if let Some(entry) = self.catalog.try_get_by_id(*id) {
+                                if let CatalogItem::Table(table) = entry.item() {
+                                    // The table may have been altered since
+                                    // this write was planned.
+                                    if table.desc != *desc {
+                                        coord_bail!(
+                                            "table {} was altered during the transaction",
+                                            entry.name()
+                                        );
+                                    }
+                                }
+                            }
+                            table_writes.entry(*id).or_default().extend(rows);
+                        }
+                        for (id, rows) in &table_writes {
+                            if let Some(CatalogItem::Table(table)) =
+                                self.catalog.try_get_by_id(*id).map(|e| e.item())
+                            {
+                                self.table_keys.validate(*id, table, rows.iter().copied())?;
+                            }
+                        }
+                        for WriteOp { id, rows, .. } in &inserts {
+                            if let Some(CatalogItem::Table(table)) =
+                                self.catalog.try_get_by_id(*id).map(|e| e.item())
+                            {
+                                self.table_keys.apply(*id, table, rows);
+                            }
+                        }
+
+                        for WriteOp { id, rows, .. } in inserts {
                             // Re-verify this id exists.
                             let catalog_entry =
                                 self.catalog.try_get_by_id(id).ok_or_else(|| {
@@ -3066,6 +3184,65 @@ impl Coordinator {
         }
     }
 
+    /// Determines the frontier of updates that a sink with `connector` emits.
+    ///
+    /// A sink that resumes writing where a previous incarnation left off only
+    /// emits the updates after the latest completely written timestamp, rather
+    /// than its entire snapshot. This is only possible if its inputs have not
+    /// been compacted beyond that timestamp, as they otherwise no longer
+    /// distinguish the updates that were written from those that were not, so
+    /// such a sink fails to start instead.
+    fn determine_sink_as_of(
+        &mut self,
+        sink: &catalog::Sink,
+        connector: &SinkConnector,
+    ) -> Result<SinkAsOf, CoordError> {
+        match connector.resume_ts() {
+            Some(resume_ts) => {
+                let since = self.sink_input_since(sink.from);
+                if !since.less_equal(&resume_ts) {
+                    coord_bail!(
+                        "sink cannot resume after timestamp {}, because its inputs are \
+                         compacted to {:?}",
+                        resume_ts,
+                        since.elements()
+                    );
+                }
+                Ok(SinkAsOf {
+                    frontier: Antichain::from_elem(resume_ts),
+                    strict: true,
+                })
+            }
+            None => Ok(SinkAsOf {
+                frontier: self.determine_frontier(sink.from),
+                strict: !sink.with_snapshot,
+            }),
+        }
+    }
+
+    /// Records the error that stopped the sink `id` in `mz_sink_errors`. Only
+    /// the first error of each sink is recorded.
+    fn record_sink_error(&mut self, id: GlobalId, error: String) {
+        if !self.sink_errors.contains_key(&id) {
+            self.send_builtin_table_updates(vec![pack_sink_error_update(id, &error, 1)]);
+            self.sink_errors.insert(id, error);
+        }
+    }
+
+    /// Determines the least frontier at which a sink or tail of `source_id`
+    /// can read its input, which is the join of the sinces of the nearest
+    /// indexes and unmaterialized sources that it reads from.
+    fn sink_input_since(&self, source_id: GlobalId) -> Antichain<Timestamp> {
+        let (index_ids, unmaterialized_source_ids) = self.catalog.nearest_indexes(&[source_id]);
+        let mut since = self.indexes.least_valid_since(index_ids.iter().copied());
+        since.join_assign(
+            &self
+                .sources
+                .least_valid_since(unmaterialized_source_ids.iter().copied()),
+        );
+        since
+    }
+
     /// Determine the frontier of updates to start *from* for a sink based on
     /// `source_id`.
     ///
@@ -3084,12 +3261,7 @@ impl Coordinator {
         // callers of this function (CREATE SINK and TAIL) are responsible for creating
         // indexes if needed.
         let (index_ids, unmaterialized_source_ids) = self.catalog.nearest_indexes(&[source_id]);
-        let mut since = self.indexes.least_valid_since(index_ids.iter().copied());
-        since.join_assign(
-            &self
-                .sources
-                .least_valid_since(unmaterialized_source_ids.iter().copied()),
-        );
+        let since = self.sink_input_since(source_id);
 
         let mut candidate = if index_ids.iter().any(|id| self.catalog.uses_tables(*id)) {
             // If the sink depends on any tables, we enforce linearizability by choosing
@@ -3194,6 +3366,15 @@ impl Coordinator {
         session: &mut Session,
         plan: SendDiffsPlan,
     ) -> Result<ExecuteResponse, CoordError> {
+        // Inserts count the rows they insert or update, even if an update
+        // leaves a row unchanged and is consolidated away below.
+        let inserted_rows: Diff = plan
+            .updates
+            .iter()
+            .map(|(_, d)| *d)
+            .filter(|d| *d > 0)
+            .sum();
+
         // Take a detour through ChangeBatch so we can consolidate updates. Useful
         // especially for UPDATE where a row shouldn't change.
         let mut rows = ChangeBatch::with_capacity(plan.updates.len());
@@ -3215,11 +3396,18 @@ impl Coordinator {
                 affected_rows += diff.abs();
                 (v, diff)
             })
-            .collect();
+            .collect::<Vec<_>>();
 
         let affected_rows = usize::try_from(affected_rows).expect("positive isize must fit");
 
-        session.add_transaction_ops(TransactionOps::Writes(vec![WriteOp { id: plan.id, rows }]))?;
+        self.validate_table_constraints(session, plan.id, &rows)?;
+        let desc = self.catalog.get_by_id(&plan.id).desc()?.clone();
+
+        session.add_transaction_ops(TransactionOps::Writes(vec![WriteOp {
+            id: plan.id,
+            desc,
+            rows,
+        }]))?;
         Ok(match plan.kind {
             MutationKind::Delete => ExecuteResponse::Deleted(affected_rows),
             MutationKind::Insert => {
@@ -3227,18 +3415,64 @@ impl Coordinator {
                     self.catalog.ensure_default_index_enabled(plan.id)?;
                 }
 
-                ExecuteResponse::Inserted(affected_rows)
+                ExecuteResponse::Inserted(
+                    usize::try_from(inserted_rows).expect("positive isize must fit"),
+                )
             }
             MutationKind::Update => ExecuteResponse::Updated(affected_rows / 2),
         })
     }
 
+    /// Verifies that writing `rows` to the table `id` in the session's
+    /// transaction would not violate any of the table's constraints.
+    fn validate_table_constraints(
+        &self,
+        session: &Session,
+        id: GlobalId,
+        rows: &[(Row, Diff)],
+    ) -> Result<(), CoordError> {
+        let entry = match self.catalog.try_get_by_id(id) {
+            Some(entry) => entry,
+            None => {
+                return Err(CoordError::SqlCatalog(CatalogError::UnknownItem(
+                    id.to_string(),
+                )))
+            }
+        };
+        let table = match entry.item() {
+            CatalogItem::Table(table) => table,
+            _ => return Ok(()),
+        };
+        for (row, diff) in rows {
+            if *diff > 0 {
+                table_constraints::validate_checks(table, &entry.name().item, row)?;
+            }
+        }
+        // Keys must be unique across all of the transaction's writes, not
+        // just the writes of this statement.
+        let pending = match session.transaction().inner().map(|txn| &txn.ops) {
+            Some(TransactionOps::Writes(writes)) => writes
+                .iter()
+                .filter(|write| write.id == id && write.desc == table.desc)
+                .flat_map(|write| &write.rows)
+                .collect(),
+            _ => vec![],
+        };
+        self.table_keys
+            .validate(id, table, pending.into_iter().chain(rows))
+    }
+
     fn sequence_insert(
         &mut self,
         tx: ClientTransmitter<ExecuteResponse>,
         mut session: Session,
         plan: InsertPlan,
     ) {
+        if plan.on_conflict.is_some() {
+            self.sequence_insert_on_conflict(tx, session, plan);
+            return;
+        }
+
         let optimized_mir = match self.prep_relation_expr(plan.values, ExprPrepStyle::Write) {
             Ok(m) => m,
             Err(e) => {
@@ -3325,6 +3559,150 @@ impl Coordinator {
         }
     }
 
+    // Inserts with an ON CONFLICT clause depend on the current contents of the
+    // table, so, like read-then-writes, they hold the write lock while they
+    // read the table and the inserted values in a single peek.
+    fn sequence_insert_on_conflict(
+        &mut self,
+        tx: ClientTransmitter<ExecuteResponse>,
+        mut session: Session,
+        plan: InsertPlan,
+    ) {
+        guard_write_critical_section!(self, tx, session, Plan::Insert(plan));
+
+        let InsertPlan {
+            id,
+            mut values,
+            on_conflict,
+        } = plan;
+        let mut on_conflict = on_conflict.expect("insert has an ON CONFLICT clause");
+
+        // Inserts can be queued, so re-verify the id exists.
+        let desc = match self.catalog.try_get_by_id(id) {
+            Some(table) => table.desc().expect("desc called on table").clone(),
+            None => {
+                tx.send(
+                    Err(CoordError::SqlCatalog(CatalogError::UnknownItem(
+                        id.to_string(),
+                    ))),
+                    session,
+                );
+                return;
+            }
+        };
+
+        // Ensure the inserted values only refer to user-defined tables.
+        for id in values.global_uses() {
+            let valid = match self.catalog.try_get_by_id(id) {
+                Some(entry) if id.is_user() => entry.is_table(),
+                _ => false,
+            };
+            if !valid {
+                tx.send(Err(CoordError::InvalidTableMutationSelection), session);
+                return;
+            }
+        }
+
+        let prepped = values
+            .try_visit_scalars_mut(&mut |s| Self::prep_scalar_expr(s, ExprPrepStyle::Write))
+            .and_then(|()| match &mut on_conflict.action {
+                ConflictAction::DoNothing => Ok(()),
+                ConflictAction::DoUpdate {
+                    assignments,
+                    selection,
+                } => {
+                    for expr in assignments.values_mut().chain(selection) {
+                        Self::prep_scalar_expr(expr, ExprPrepStyle::Write)?;
+                    }
+                    Ok(())
+                }
+            });
+        if let Err(e) = prepped {
+            tx.send(Err(e), session);
+            return;
+        }
+
+        // Read the inserted values and the current contents of the table at
+        // the same time, tagging each row with whether it is to be inserted.
+        let arity = desc.arity();
+        let tag = |expr: MirRelationExpr, proposed: bool| {
+            expr.map(vec![MirScalarExpr::literal_ok(
+                Datum::from(proposed),
+                ScalarType::Bool,
+            )])
+        };
+        let source = tag(values, true).union(tag(
+            MirRelationExpr::Get {
+                id: Id::Global(id),
+                typ: desc.typ().clone(),
+            },
+            false,
+        ));
+        let ts = self.get_read_ts();
+        let peek_response = match self.sequence_peek(
+            &mut session,
+            PeekPlan {
+                source,
+                when: PeekWhen::AtTimestamp(ts),
+                finishing: RowSetFinishing {
+                    order_by: vec![],
+                    limit: None,
+                    offset: 0,
+                    project: (0..arity + 1).collect(),
+                },
+                copy_to: None,
+            },
+        ) {
+            Ok(resp) => resp,
+            Err(e) => {
+                tx.send(Err(e), session);
+                return;
+            }
+        };
+
+        let internal_cmd_tx = self.internal_cmd_tx.clone();
+        tokio::spawn(async move {
+            let diffs = match peek_response {
+                ExecuteResponse::SendingRows(batch) => match batch.await {
+                    PeekResponse::Rows(rows) => {
+                        let mut existing = vec![];
+                        let mut proposed = vec![];
+                        for row in rows {
+                            let mut datums = row.unpack();
+                            let is_proposed = datums.pop() == Some(Datum::True);
+                            let row = Row::pack_slice(&datums);
+                            if is_proposed {
+                                proposed.push(row);
+                            } else {
+                                existing.push(row);
+                            }
+                        }
+                        table_constraints::resolve_conflicts(
+                            &desc,
+                            &on_conflict,
+                            existing,
+                            proposed,
+                        )
+                    }
+                    PeekResponse::Canceled => {
+                        Err(CoordError::Unstructured(anyhow!("execution canceled")))
+                    }
+                    PeekResponse::Error(e) => Err(CoordError::Unstructured(anyhow!(e))),
+                },
+                _ => Err(CoordError::Unstructured(anyhow!("expected SendingRows"))),
+            };
+            internal_cmd_tx
+                .send(Message::SendDiffs(SendDiffs {
+                    session,
+                    tx,
+                    id,
+                    diffs,
+                    kind: MutationKind::Insert,
+                }))
+                .expect("sending to internal_cmd_tx cannot fail");
+        });
+    }
+
     fn sequence_copy_rows(
         &mut self,
         session: &mut Session,
@@ -3487,6 +3865,224 @@ impl Coordinator {
         }
     }
 
+    fn sequence_alter_table(
+        &mut self,
+        tx: ClientTransmitter<ExecuteResponse>,
+        mut session: Session,
+        plan: AlterTablePlan,
+    ) {
+        guard_write_critical_section!(self, tx, session, Plan::AlterTable(plan));
+
+        // The table's contents are read under the write lock, so that no
+        // writes can land between reading and rewriting them.
+        let desc = match self.validate_alter_table(&plan) {
+            Ok(desc) => desc,
+            Err(e) => {
+                tx.send(Err(e), session);
+                return;
+            }
+        };
+        let ts = self.get_read_ts();
+        let peek_response = match self.sequence_peek(
+            &mut session,
+            PeekPlan {
+                source: MirRelationExpr::global_get(plan.id, desc.typ().clone()),
+                when: PeekWhen::AtTimestamp(ts),
+                finishing: RowSetFinishing {
+                    order_by: vec![],
+                    limit: None,
+                    offset: 0,
+                    project: (0..desc.arity()).collect(),
+                },
+                copy_to: None,
+            },
+        ) {
+            Ok(resp) => resp,
+            Err(e) => {
+                tx.send(Err(e), session);
+                return;
+            }
+        };
+
+        let internal_cmd_tx = self.internal_cmd_tx.clone();
+        tokio::spawn(async move {
+            let rows = match peek_response {
+                ExecuteResponse::SendingRows(batch) => match batch.await {
+                    PeekResponse::Rows(rows) => Ok(rows),
+                    PeekResponse::Canceled => {
+                        Err(CoordError::Unstructured(anyhow!("execution canceled")))
+                    }
+                    PeekResponse::Error(e) => Err(CoordError::Unstructured(anyhow!(e))),
+                },
+                _ => Err(CoordError::Unstructured(anyhow!("expected SendingRows"))),
+            };
+            internal_cmd_tx
+                .send(Message::RewriteTable(RewriteTable {
+                    session,
+                    tx,
+                    plan,
+                    rows,
+                }))
+                .expect("sending to internal_cmd_tx cannot fail");
+        });
+    }
+
+    /// Verifies that the table altered by `plan` can be rewritten, and that it
+    /// has not itself been altered since `plan` was planned. Returns the
+    /// table's current description.
+    fn validate_alter_table(&self, plan: &AlterTablePlan) -> Result<RelationDesc, CoordError> {
+        let entry = match self.catalog.try_get_by_id(plan.id) {
+            Some(entry) => entry,
+            None => {
+                return Err(CoordError::SqlCatalog(CatalogError::UnknownItem(
+                    plan.id.to_string(),
+                )))
+            }
+        };
+        let table = match entry.item() {
+            CatalogItem::Table(table) => table,
+            _ => coord_bail!("{} is not a table", entry.name()),
+        };
+        if table.persist.is_some() {
+            return Err(CoordError::Unsupported("ALTER TABLE on persisted tables"));
+        }
+        self.catalog.ensure_default_index_enabled(plan.id)?;
+
+        let mut names: Vec<_> = table.desc.iter_names().collect();
+        match &plan.rewrite {
+            AlterTableRewrite::AddColumn { .. } => {
+                names.extend(plan.table.desc.iter_names().last())
+            }
+            AlterTableRewrite::DropColumn { index } => {
+                names.remove(*index);
+            }
+        }
+        if names != plan.table.desc.iter_names().collect::<Vec<_>>() {
+            coord_bail!("table {} was altered concurrently", entry.name());
+        }
+        Ok(table.desc.clone())
+    }
+
+    fn sequence_rewrite_table(
+        &mut self,
+        plan: AlterTablePlan,
+        rows: Vec<Row>,
+    ) -> Result<ExecuteResponse, CoordError> {
+        // Catalog changes made by other sessions while the table was being read
+        // could have invalidated the plan.
+        self.validate_alter_table(&plan)?;
+        let AlterTablePlan { id, table, rewrite } = plan;
+
+        let rows = match rewrite {
+            AlterTableRewrite::AddColumn { mut default } => {
+                Self::prep_scalar_expr(&mut default, ExprPrepStyle::Write)?;
+                // The default is evaluated anew for each row, just as it would
+                // be had each row been inserted after the column was added.
+                let column = table.desc.arity() - 1;
+                rows.into_iter()
+                    .map(|row| {
+                        let arena = RowArena::new();
+                        let datum = default.eval(&[], &arena)?;
+                        table.desc.constraints_met(column, &datum)?;
+                        let mut datums = row.unpack();
+                        datums.push(datum);
+                        Ok(Row::pack_slice(&datums))
+                    })
+                    .collect::<Result<Vec<_>, CoordError>>()?
+            }
+            AlterTableRewrite::DropColumn { index } => rows
+                .into_iter()
+                .map(|row| {
+                    let mut datums = row.unpack();
+                    datums.remove(index);
+                    Row::pack_slice(&datums)
+                })
+                .collect(),
+        };
+
+        let entry = self.catalog.get_by_id(&id);
+        let conn_id = entry.item().conn_id();
+        // The default index is regenerated rather than replanned, as its key
+        // may refer to columns that no longer exist.
+        let default_index_id = self
+            .catalog
+            .default_index_for(id)
+            .expect("tables always have a default index");
+        let mut index_depends_on = table.depends_on.clone();
+        index_depends_on.push(id);
+        let default_index = auto_generate_primary_idx(
+            self.catalog
+                .get_by_id(&default_index_id)
+                .name()
+                .item
+                .clone(),
+            entry.name().clone(),
+            id,
+            &table.desc,
+            conn_id,
+            index_depends_on,
+            true,
+        );
+        let table = catalog::Table {
+            create_sql: table.create_sql,
+            desc: table.desc,
+            defaults: table.defaults,
+            unique_constraints: table.unique_constraints,
+            check_constraints: table.check_constraints,
+            conn_id,
+            depends_on: table.depends_on,
+            persist: None,
+        };
+        let ops = self.catalog.alter_table_ops(id, table, default_index)?;
+
+        // The dataflows of the recreated indexes replace those of the indexes
+        // they were recreated from, and inherit their options.
+        let mut replaced = vec![];
+        let mut created = vec![];
+        for op in &ops {
+            match op {
+                catalog::Op::DropItem(id) => replaced.push(*id),
+                catalog::Op::CreateItem { id, .. } => created.push(*id),
+                _ => (),
+            }
+        }
+        let compaction_windows: Vec<_> = replaced
+            .iter()
+            .map(|id| self.indexes.get(id).map(|index| index.compaction_window_ms))
+            .collect();
+        self.catalog_transact(ops)?;
+
+        // Indexes are rebuilt one at a time, in order, so that each can reuse
+        // the arrangements of the ones before it. In particular, the table's
+        // default index is rebuilt first and hosts the table's data.
+        for (index_id, compaction_window_ms) in created.into_iter().zip(compaction_windows) {
+            if let Some((name, description)) = self.prepare_index_build(&index_id) {
+                let df = self
+                    .dataflow_builder()
+                    .build_index_dataflow(name, index_id, description);
+                self.ship_dataflow(df);
+                if let (Some(index), Some(window)) =
+                    (self.indexes.get_mut(&index_id), compaction_window_ms)
+                {
+                    index.set_compaction_window_ms(window);
+                }
+            }
+        }
+
+        let timestamp = self.get_write_ts();
+        let updates = rows
+            .into_iter()
+            .map(|row| Update {
+                row,
+                diff: 1,
+                timestamp,
+            })
+            .collect();
+        self.broadcast(dataflow::Command::Insert { id, updates });
+
+        Ok(ExecuteResponse::AlteredObject(ObjectType::Table))
+    }
+
     fn sequence_alter_index_set_options(
         &mut self,
         plan: AlterIndexSetOptionsPlan,
@@ -3580,6 +4176,7 @@ impl Coordinator {
 
         if !sources_to_drop.is_empty() {
             for &id in &sources_to_drop {
+                self.table_keys.remove(&id);
                 self.update_timestamper(id, false);
                 self.catalog.delete_timestamp_bindings(id)?;
                 self.sources.remove(&id);
@@ -3589,6 +4186,9 @@ impl Coordinator {
         if !sinks_to_drop.is_empty() {
             for id in sinks_to_drop.iter() {
                 self.sink_writes.remove(id);
+                if let Some(error) = self.sink_errors.remove(id) {
+                    self.send_builtin_table_updates(vec![pack_sink_error_update(*id, &error, -1)]);
+                }
             }
             self.broadcast(dataflow::Command::DropSinks(sinks_to_drop));
         }
@@ -4051,6 +4651,7 @@ pub async fn serve(
         build_info,
         metrics_registry,
         persist,
+        bootstrap_password,
     }: Config<'_>,
 ) -> Result<(Handle, Client), CoordError> {
     let (cmd_tx, cmd_rx) = mpsc::unbounded_channel();
@@ -4064,7 +4665,7 @@ pub async fn serve(
     };
 
     let path = data_directory.join("catalog");
-    let (catalog, builtin_table_updates, persister) = Catalog::open(&catalog::Config {
+    let (mut catalog, builtin_table_updates, persister) = Catalog::open(&catalog::Config {
         path: &path,
         experimental_mode: Some(experimental_mode),
         safe_mode,
@@ -4078,6 +4679,24 @@ pub async fn serve(
         metrics_registry: &metrics_registry,
         disable_user_indexes,
     })?;
+    // Give the default role a password, so that it can log in when passwords
+    // are required, but never override a password set via `ALTER ROLE`.
+    if let Some(password) = bootstrap_password {
+        let role_name = "materialize";
+        match catalog.try_get_role(role_name) {
+            Some(role) if role.password.is_none() => {
+                catalog.transact(vec![catalog::Op::AlterRole {
+                    name: role_name.into(),
+                    password: Some(password),
+                }])?;
+            }
+            Some(_) => (),
+            None => log::warn!(
+                "cannot set bootstrap password: role {} does not exist",
+                role_name
+            ),
+        }
+    }
     let cluster_id = catalog.config().cluster_id;
     let session_id = catalog.config().session_id;
     let start_instant = catalog.config().start_instant;
@@ -4149,6 +4768,8 @@ pub async fn serve(
                 since_handles: HashMap::new(),
                 since_updates: Rc::new(RefCell::new(HashMap::new())),
                 sink_writes: HashMap::new(),
+                sink_errors: HashMap::new(),
+                table_keys: TableKeys::default(),
                 now,
                 pending_peeks: HashMap::new(),
                 pending_tails: HashMap::new(),
@@ -4311,6 +4932,8 @@ pub fn serve_debug(
             since_handles: HashMap::new(),
             since_updates: Rc::new(RefCell::new(HashMap::new())),
             sink_writes: HashMap::new(),
+            sink_errors: HashMap::new(),
+            table_keys: TableKeys::default(),
             now: get_debug_timestamp,
             pending_peeks: HashMap::new(),
             pending_tails: HashMap::new(),
@@ -4354,6 +4977,16 @@ enum ExprPrepStyle {
     Write,
 }
 
+/// Constructs the row of `mz_sink_errors` that records that the sink
+/// `sink_id` failed with `error`.
+fn pack_sink_error_update(sink_id: GlobalId, error: &str, diff: Diff) -> BuiltinTableUpdate {
+    BuiltinTableUpdate {
+        id: MZ_SINK_ERRORS.id,
+        row: Row::pack_slice(&[Datum::String(&sink_id.to_string()), Datum::String(error)]),
+        diff,
+    }
+}
+
 /// Constructs an [`ExecuteResponse`] that that will send some rows to the
 /// client immediately, as opposed to asking the dataflow layer to send along
 /// the rows after some computation.
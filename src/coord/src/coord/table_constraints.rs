@@ -0,0 +1,234 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Enforcement of the `PRIMARY KEY`, `UNIQUE`, and `CHECK` constraints of
+//! tables.
+//!
+//! All writes to tables flow through the coordinator, so the coordinator is
+//! responsible for rejecting writes that would violate a table's constraints.
+//! `CHECK` constraints depend only on the row being written, but unique
+//! constraints depend on the contents of the table, so the coordinator
+//! maintains a count of the rows with each key value for every unique
+//! constraint.
+//!
+//! The unique constraints of a table also determine the rows with which the
+//! rows of an `INSERT ... ON CONFLICT` statement conflict.
+
+use std::collections::{HashMap, HashSet};
+use std::mem;
+
+use expr::GlobalId;
+use repr::{Datum, Diff, RelationDesc, Row, RowArena};
+use sql::plan::{ConflictAction, OnConflictPlan};
+
+use crate::catalog::Table;
+use crate::error::CoordError;
+
+/// The number of rows in a table with each key value of a unique constraint.
+type KeyCounts = HashMap<Row, Diff>;
+
+/// Tracks the key values present in tables with unique constraints.
+#[derive(Debug, Default)]
+pub struct TableKeys {
+    /// For each table, the key counts for each of its unique constraints, in
+    /// the order in which the constraints are declared.
+    tables: HashMap<GlobalId, Vec<KeyCounts>>,
+}
+
+impl TableKeys {
+    /// Verifies that applying `updates` to the table `id` would not violate
+    /// any of the table's unique constraints.
+    ///
+    /// `updates` must include all updates to the table that have not yet been
+    /// applied via [`TableKeys::apply`].
+    pub fn validate<'a, I>(&self, id: GlobalId, table: &Table, updates: I) -> Result<(), CoordError>
+    where
+        I: IntoIterator<Item = &'a (Row, Diff)> + Clone,
+    {
+        for (i, constraint) in table.unique_constraints.iter().enumerate() {
+            let committed = self.tables.get(&id).and_then(|counts| counts.get(i));
+            let deltas = key_deltas(&constraint.columns, updates.clone());
+            for (key, delta) in deltas {
+                let count = committed.and_then(|c| c.get(&key)).copied().unwrap_or(0);
+                if count + delta > 1 {
+                    return Err(CoordError::UniqueViolation(constraint.name.clone()));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Records that `updates` have been applied to the table `id`.
+    pub fn apply(&mut self, id: GlobalId, table: &Table, updates: &[(Row, Diff)]) {
+        if table.unique_constraints.is_empty() {
+            return;
+        }
+        let counts = self
+            .tables
+            .entry(id)
+            .or_insert_with(|| vec![KeyCounts::new(); table.unique_constraints.len()]);
+        for (constraint, counts) in table.unique_constraints.iter().zip(counts) {
+            for (key, delta) in key_deltas(&constraint.columns, updates) {
+                let count = counts.entry(key.clone()).or_insert(0);
+                *count += delta;
+                if *count == 0 {
+                    counts.remove(&key);
+                }
+            }
+        }
+    }
+
+    /// Forgets the keys of the table `id`, which has been dropped.
+    pub fn remove(&mut self, id: &GlobalId) {
+        self.tables.remove(id);
+    }
+}
+
+/// Verifies that `row` satisfies the `CHECK` constraints of `table`.
+///
+/// As in PostgreSQL, a constraint is satisfied if its expression evaluates to
+/// either true or null.
+pub fn validate_checks(table: &Table, relation: &str, row: &Row) -> Result<(), CoordError> {
+    if table.check_constraints.is_empty() {
+        return Ok(());
+    }
+    let datums = row.unpack();
+    let arena = RowArena::new();
+    for constraint in &table.check_constraints {
+        if constraint.expr.eval(&datums, &arena)? == Datum::False {
+            return Err(CoordError::CheckViolation {
+                relation: relation.into(),
+                constraint: constraint.name.clone(),
+            });
+        }
+    }
+    Ok(())
+}
+
+/// Computes the updates to a table that result from inserting the `proposed`
+/// rows into a table whose current contents are `existing`, resolving
+/// conflicts as described by `on_conflict`.
+///
+/// The returned updates are not validated against the table's constraints.
+pub fn resolve_conflicts(
+    desc: &RelationDesc,
+    on_conflict: &OnConflictPlan,
+    existing: Vec<Row>,
+    proposed: Vec<Row>,
+) -> Result<Vec<(Row, Diff)>, CoordError> {
+    let arena = RowArena::new();
+
+    // The current version of every row in the table, and, for each of the
+    // conflict keys, the position of the row with each key value.
+    let mut rows = existing;
+    let mut indexes = vec![HashMap::new(); on_conflict.keys.len()];
+    for (pos, row) in rows.iter().enumerate() {
+        for (columns, index) in on_conflict.keys.iter().zip(&mut indexes) {
+            if let Some(key) = key(columns, row) {
+                index.insert(key, pos);
+            }
+        }
+    }
+
+    // The positions of the rows that have been inserted or updated by this
+    // statement.
+    let mut affected = HashSet::new();
+    let mut diffs = vec![];
+    for row in proposed {
+        let conflict = on_conflict
+            .keys
+            .iter()
+            .zip(&indexes)
+            .find_map(|(columns, index)| key(columns, &row).and_then(|k| index.get(&k)))
+            .copied();
+        let pos = match (conflict, &on_conflict.action) {
+            (None, _) => {
+                diffs.push((row.clone(), 1));
+                rows.push(row);
+                rows.len() - 1
+            }
+            (Some(_), ConflictAction::DoNothing) => continue,
+            (
+                Some(pos),
+                ConflictAction::DoUpdate {
+                    assignments,
+                    selection,
+                },
+            ) => {
+                if affected.contains(&pos) {
+                    return Err(CoordError::OnConflictRowAffectedTwice);
+                }
+                let mut datums = rows[pos].unpack();
+                let existing_arity = datums.len();
+                datums.extend(row.unpack());
+                if let Some(selection) = selection {
+                    if selection.eval(&datums, &arena)? != Datum::True {
+                        continue;
+                    }
+                }
+                let mut updates = vec![];
+                for (idx, expr) in assignments {
+                    let updated = expr.eval(&datums, &arena)?;
+                    desc.constraints_met(*idx, &updated)?;
+                    updates.push((*idx, updated));
+                }
+                for (idx, updated) in updates {
+                    datums[idx] = updated;
+                }
+                datums.truncate(existing_arity);
+                let updated = Row::pack_slice(&datums);
+                let old = mem::replace(&mut rows[pos], updated.clone());
+                for (columns, index) in on_conflict.keys.iter().zip(&mut indexes) {
+                    if let Some(key) = key(columns, &old) {
+                        index.remove(&key);
+                    }
+                }
+                diffs.push((old, -1));
+                diffs.push((updated, 1));
+                pos
+            }
+        };
+        affected.insert(pos);
+        for (columns, index) in on_conflict.keys.iter().zip(&mut indexes) {
+            if let Some(key) = key(columns, &rows[pos]) {
+                index.insert(key, pos);
+            }
+        }
+    }
+    Ok(diffs)
+}
+
+/// Extracts the values of the key columns `columns` from `row`, or returns
+/// `None` if any of them are null.
+fn key(columns: &[usize], row: &Row) -> Option<Row> {
+    let datums = row.unpack();
+    let key: Vec<_> = columns.iter().map(|i| datums[*i]).collect();
+    if key.iter().any(|d| d.is_null()) {
+        None
+    } else {
+        Some(Row::pack_slice(&key))
+    }
+}
+
+/// Sums the diffs of `updates` by their values in the key columns `columns`.
+///
+/// Updates with a null in any of the key columns are ignored, as a unique
+/// constraint admits any number of such rows.
+fn key_deltas<'a, I>(columns: &[usize], updates: I) -> KeyCounts
+where
+    I: IntoIterator<Item = &'a (Row, Diff)>,
+{
+    let mut deltas = KeyCounts::new();
+    for (row, diff) in updates {
+        if let Some(key) = key(columns, row) {
+            *deltas.entry(key).or_insert(0) += diff;
+        }
+    }
+    deltas
+}
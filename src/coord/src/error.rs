@@ -35,6 +35,11 @@ pub enum CoordError {
     DuplicateCursor(String),
     /// An error while evaluating an expression.
     Eval(EvalError),
+    /// A row violated the named `CHECK` constraint of a table.
+    CheckViolation {
+        relation: String,
+        constraint: String,
+    },
     /// The ID allocator exhausted all valid IDs.
     IdExhaustionError,
     /// At least one input has no complete timestamps yet
@@ -47,6 +52,9 @@ pub enum CoordError {
     InvalidTableMutationSelection,
     /// Expression violated a column's constraint
     ConstraintViolation(NotNullViolation),
+    /// An `INSERT ... ON CONFLICT DO UPDATE` attempted to update the same row
+    /// more than once.
+    OnConflictRowAffectedTwice,
     /// The named operation cannot be run in a transaction.
     OperationProhibitsTransaction(String),
     /// The named operation requires an active transaction.
@@ -69,6 +77,8 @@ pub enum CoordError {
     SqlCatalog(sql::catalog::CatalogError),
     /// The transaction is in single-tail mode.
     TailOnlyTransaction,
+    /// A write violated the named `PRIMARY KEY` or `UNIQUE` constraint.
+    UniqueViolation(String),
     /// An error occurred in the optimizer.
     Transform(TransformError),
     /// The named cursor does not exist.
@@ -194,6 +204,11 @@ impl CoordError {
                 INDEX {} SET ENABLED",
                 idx.quoted()
             )),
+            CoordError::OnConflictRowAffectedTwice => Some(
+                "Ensure that no rows proposed for insertion within the same \
+                command have duplicate constrained values."
+                    .into(),
+            ),
             CoordError::UnknownLoginRole(_) => {
                 // TODO(benesch): this will be a bad hint when people are used
                 // to creating roles in Materialize, since they might drop the
@@ -226,6 +241,15 @@ impl fmt::Display for CoordError {
                 write!(f, "cursor {} already exists", name.quoted())
             }
             CoordError::Eval(e) => e.fmt(f),
+            CoordError::CheckViolation {
+                relation,
+                constraint,
+            } => write!(
+                f,
+                "new row for relation {} violates check constraint {}",
+                relation.quoted(),
+                constraint.quoted()
+            ),
             CoordError::IdExhaustionError => f.write_str("ID allocator exhausted all valid IDs"),
             CoordError::IncompleteTimestamp(unstarted) => write!(
                 f,
@@ -247,6 +271,9 @@ impl fmt::Display for CoordError {
             CoordError::ConstraintViolation(not_null_violation) => {
                 write!(f, "{}", not_null_violation)
             }
+            CoordError::OnConflictRowAffectedTwice => {
+                f.write_str("ON CONFLICT DO UPDATE command cannot affect row a second time")
+            }
             CoordError::OperationProhibitsTransaction(op) => {
                 write!(f, "{} cannot be run inside a transaction block", op)
             }
@@ -275,6 +302,11 @@ impl fmt::Display for CoordError {
                 f.write_str("TAIL in transactions must be the only read statement")
             }
             CoordError::Transform(e) => e.fmt(f),
+            CoordError::UniqueViolation(constraint) => write!(
+                f,
+                "duplicate key value violates unique constraint {}",
+                constraint.quoted()
+            ),
             CoordError::UnknownCursor(name) => {
                 write!(f, "cursor {} does not exist", name.quoted())
             }
@@ -22,7 +22,7 @@ use tokio::sync::OwnedMutexGuard;
 
 use expr::GlobalId;
 use pgrepr::Format;
-use repr::{Datum, Diff, Row, ScalarType, Timestamp};
+use repr::{Datum, Diff, RelationDesc, Row, ScalarType, Timestamp};
 use sql::ast::{Raw, Statement};
 use sql::plan::{Params, PlanContext, StatementDesc};
 
@@ -582,6 +582,9 @@ pub enum TransactionOps {
 pub struct WriteOp {
     /// The target table.
     pub id: GlobalId,
+    /// The description of the target table when the write was planned. The
+    /// write must not be committed if the table has since been altered.
+    pub desc: RelationDesc,
     /// The data rows.
     pub rows: Vec<(Row, Diff)>,
 }
@@ -8,7 +8,7 @@
 // by the Apache License, Version 2.0.
 
 use std::collections::HashMap;
-use std::fs::OpenOptions;
+use std::fs::{self, OpenOptions};
 use std::time::Duration;
 
 use anyhow::{anyhow, bail, Context};
@@ -20,9 +20,10 @@ use rdkafka::config::ClientConfig;
 use rdkafka::{Message, Offset, TopicPartitionList};
 
 use dataflow_types::{
-    AvroOcfSinkConnector, AvroOcfSinkConnectorBuilder, KafkaSinkConnector,
-    KafkaSinkConnectorBuilder, KafkaSinkConnectorRetention, KafkaSinkConsistencyConnector,
-    PublishedSchemaInfo, SinkConnector, SinkConnectorBuilder,
+    AvroOcfSinkConnector, AvroOcfSinkConnectorBuilder, FileSinkConnector, FileSinkConnectorBuilder,
+    FileSinkLocation, KafkaSinkConnector, KafkaSinkConnectorBuilder, KafkaSinkConnectorRetention,
+    KafkaSinkConsistencyConnector, KafkaSinkFormat, PublishedSchemaInfo, SinkConnector,
+    SinkConnectorBuilder,
 };
 use expr::GlobalId;
 use ore::collections::CollectionExt;
@@ -39,6 +40,7 @@ pub async fn build(
     match builder {
         SinkConnectorBuilder::Kafka(k) => build_kafka(k, id).await,
         SinkConnectorBuilder::AvroOcf(a) => build_avro_ocf(a, id),
+        SinkConnectorBuilder::File(f) => build_file(f, id).await,
     }
 }
 
@@ -67,6 +69,7 @@ fn get_next_message(
 // Retrieves the latest committed timestamp from the consistency topic
 fn get_latest_ts(
     consistency_topic: &str,
+    consistency_format: &KafkaSinkFormat,
     consumer: &mut BaseConsumer,
     timeout: Duration,
 ) -> Result<Option<Timestamp>, anyhow::Error> {
@@ -142,44 +145,73 @@ fn get_latest_ts(
 
     // the latest valid message should be an END message. If not, things have
     // gone wrong!
-    let timestamp = decode_consistency_end_record(&latest_message, consistency_topic)?;
+    let timestamp =
+        decode_consistency_end_record(&latest_message, consistency_format, consistency_topic)?;
 
     Ok(Some(timestamp))
 }
 
 fn decode_consistency_end_record(
     bytes: &[u8],
+    consistency_format: &KafkaSinkFormat,
     consistency_topic: &str,
 ) -> Result<Timestamp, anyhow::Error> {
-    // The first 5 bytes are reserved for the schema id/schema registry information
-    let mut bytes = &bytes[5..];
-    let record = mz_avro::from_avro_datum(get_debezium_transaction_schema(), &mut bytes)
-        .context("Failed to decode consistency topic message")?;
-
-    if let Value::Record(r) = record {
-        let m: HashMap<String, Value> = r.into_iter().collect();
-        let status = m.get("status");
-        let id = m.get("id");
-        match (status, id) {
-            (Some(Value::String(status)), Some(Value::String(id))) if status == "END" => {
-                if let Ok(ts) = id.parse::<u64>() {
-                    Ok(Timestamp::from(ts))
-                } else {
-                    bail!(
-                        "Malformed consistency record, failed to parse timestamp {} in topic {}",
-                        id,
-                        consistency_topic
-                    );
+    let (status, id) = match consistency_format {
+        KafkaSinkFormat::Avro { .. } => {
+            // The first 5 bytes are reserved for the schema id/schema registry information
+            let mut bytes = &bytes[5..];
+            let record = mz_avro::from_avro_datum(get_debezium_transaction_schema(), &mut bytes)
+                .context("Failed to decode consistency topic message")?;
+
+            if let Value::Record(r) = record {
+                let m: HashMap<String, Value> = r.into_iter().collect();
+                match (m.get("status"), m.get("id")) {
+                    (Some(Value::String(status)), Some(Value::String(id))) => {
+                        (status.clone(), id.clone())
+                    }
+                    (status, id) => {
+                        bail!(
+                            "Malformed consistency record in topic {}, expected END with a timestamp but record was {:?}, tried matching {:?} {:?}",
+                            consistency_topic, m, status, id);
+                    }
                 }
+            } else {
+                bail!("Failed to decode consistency topic message, was not a parseable record");
             }
-            _ => {
-                bail!(
-                    "Malformed consistency record in topic {}, expected END with a timestamp but record was {:?}, tried matching {:?} {:?}",
-                    consistency_topic, m, status, id);
+        }
+        KafkaSinkFormat::Json => {
+            let record: serde_json::Value = serde_json::from_slice(bytes)
+                .context("Failed to decode consistency topic message")?;
+            match (&record["status"], &record["id"]) {
+                (serde_json::Value::String(status), serde_json::Value::String(id)) => {
+                    (status.clone(), id.clone())
+                }
+                _ => {
+                    bail!(
+                        "Malformed consistency record in topic {}, expected END with a timestamp but record was {}",
+                        consistency_topic, record);
+                }
             }
         }
-    } else {
-        bail!("Failed to decode consistency topic message, was not a parseable record");
+        KafkaSinkFormat::Protobuf(_) => {
+            unreachable!("protobuf consistency topics are rejected at plan time")
+        }
+    };
+
+    if status != "END" {
+        bail!(
+            "Malformed consistency record in topic {}, expected END with a timestamp but got {} record",
+            consistency_topic,
+            status
+        );
+    }
+    match id.parse::<u64>() {
+        Ok(ts) => Ok(Timestamp::from(ts)),
+        Err(_) => bail!(
+            "Malformed consistency record, failed to parse timestamp {} in topic {}",
+            id,
+            consistency_topic
+        ),
     }
 }
 
@@ -392,8 +424,8 @@ async fn build_kafka(
     )
     .await
     .context("error registering kafka topic for sink")?;
-    let published_schema_info = match builder.format {
-        dataflow_types::KafkaSinkFormat::Avro {
+    let (published_schema_info, protobuf_schema) = match builder.format {
+        KafkaSinkFormat::Avro {
             key_schema,
             value_schema,
             ccsr_config,
@@ -410,20 +442,18 @@ async fn build_kafka(
             )
             .await
             .context("error publishing kafka schemas for sink")?;
-            Some(PublishedSchemaInfo {
+            let published_schema_info = PublishedSchemaInfo {
                 key_schema_id,
                 value_schema_id,
-            })
+            };
+            (Some(published_schema_info), None)
         }
-        dataflow_types::KafkaSinkFormat::Json => None,
+        KafkaSinkFormat::Json => (None, None),
+        KafkaSinkFormat::Protobuf(schema) => (None, Some(schema)),
     };
 
     let consistency = match builder.consistency_format {
-        Some(dataflow_types::KafkaSinkFormat::Avro {
-            value_schema,
-            ccsr_config,
-            ..
-        }) => {
+        Some(consistency_format) => {
             let consistency_topic = maybe_append_nonce(
                 builder
                     .consistency_topic_prefix
@@ -442,17 +472,31 @@ async fn build_kafka(
             .await
             .context("error registering kafka consistency topic for sink")?;
 
-            let ccsr = ccsr_config.build()?;
-            let (_, consistency_schema_id) = publish_kafka_schemas(
-                &ccsr,
-                &consistency_topic,
-                None,
-                None,
-                &value_schema,
-                ccsr::SchemaType::Avro,
-            )
-            .await
-            .context("error publishing kafka consistency schemas for sink")?;
+            let schema_id = match &consistency_format {
+                KafkaSinkFormat::Avro {
+                    value_schema,
+                    ccsr_config,
+                    ..
+                } => {
+                    let ccsr = ccsr_config.clone().build()?;
+                    let (_, consistency_schema_id) = publish_kafka_schemas(
+                        &ccsr,
+                        &consistency_topic,
+                        None,
+                        None,
+                        value_schema,
+                        ccsr::SchemaType::Avro,
+                    )
+                    .await
+                    .context("error publishing kafka consistency schemas for sink")?;
+                    Some(consistency_schema_id)
+                }
+                // JSON consistency records are not registered with a schema registry
+                KafkaSinkFormat::Json => None,
+                KafkaSinkFormat::Protobuf(_) => {
+                    unreachable!("protobuf consistency topics are rejected at plan time")
+                }
+            };
 
             // get latest committed timestamp from consistency topic
             let gate_ts = if builder.reuse_topic {
@@ -470,22 +514,31 @@ async fn build_kafka(
                     .create::<BaseConsumer>()
                     .context("creating consumer client failed")?;
 
-                get_latest_ts(&consistency_topic, &mut consumer, Duration::from_secs(5))
-                    .context("error restarting from existing kafka consistency topic for sink")?
+                get_latest_ts(
+                    &consistency_topic,
+                    &consistency_format,
+                    &mut consumer,
+                    Duration::from_secs(5),
+                )
+                .context("error restarting from existing kafka consistency topic for sink")?
             } else {
                 None
             };
 
             Some(KafkaSinkConsistencyConnector {
                 topic: consistency_topic,
-                schema_id: consistency_schema_id,
+                schema_id,
                 gate_ts,
             })
         }
-        Some(other) => unreachable!("non-Avro consistency format for Kafka sink {:#?}", &other),
-        _ => None,
+        None => None,
     };
 
+    // Sinks with consistency metadata write each timestamp in a transaction,
+    // so that the consistency records and the updates they describe become
+    // visible together.
+    let transactional = builder.reuse_topic || consistency.is_some();
+
     Ok(SinkConnector::Kafka(KafkaSinkConnector {
         topic,
         topic_prefix: builder.topic_prefix,
@@ -493,9 +546,12 @@ async fn build_kafka(
         relation_key_indices: builder.relation_key_indices,
         key_desc_and_indices: builder.key_desc_and_indices,
         value_desc: builder.value_desc,
+        enum_labels: builder.enum_labels,
         published_schema_info,
+        protobuf_schema,
         consistency,
         exactly_once: builder.reuse_topic,
+        transactional,
         transitive_source_dependencies: builder.transitive_source_dependencies,
         fuel: builder.fuel,
         config_options: builder.config_options,
@@ -539,5 +595,53 @@ fn build_avro_ocf(
     Ok(SinkConnector::AvroOcf(AvroOcfSinkConnector {
         path,
         value_desc: builder.value_desc,
+        enum_labels: builder.enum_labels,
+    }))
+}
+
+async fn build_file(
+    builder: FileSinkConnectorBuilder,
+    id: GlobalId,
+) -> Result<SinkConnector, CoordError> {
+    let name = format!("{}-{}", id, builder.name_suffix);
+    let location = match builder.location {
+        FileSinkLocation::Directory(dir) => {
+            // Each sink writes to a directory of its own, so that it never
+            // mixes its files with those of another sink. The directory
+            // already exists if the sink is being recreated after a restart.
+            let path = dir.join(name);
+            fs::create_dir_all(&path).map_err(|e| {
+                anyhow!(
+                    "unable to create file sink directory {}: {}",
+                    path.display(),
+                    e
+                )
+            })?;
+            FileSinkLocation::Directory(path)
+        }
+        FileSinkLocation::S3 {
+            bucket,
+            prefix,
+            aws_info,
+        } => FileSinkLocation::S3 {
+            bucket,
+            prefix: format!("{}{}/", prefix, name),
+            aws_info,
+        },
+    };
+    // A sink that is recreated after a restart resumes writing where its
+    // manifest left off. Sinks that do not resume always start out with a
+    // new, empty directory or prefix.
+    let resume_upper = dataflow::read_file_sink_upper(&location)
+        .await
+        .context("reading file sink manifest")?;
+    Ok(SinkConnector::File(FileSinkConnector {
+        location,
+        format: builder.format,
+        value_desc: builder.value_desc,
+        enum_labels: builder.enum_labels,
+        seal_interval: builder.seal_interval,
+        resume_upper,
+        transitive_source_dependencies: builder.transitive_source_dependencies,
     }))
 }
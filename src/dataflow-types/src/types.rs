@@ -14,6 +14,7 @@
 //! avoid the dependency, as the dataflow crate is very slow to compile.
 
 use std::collections::{BTreeMap, HashMap, HashSet};
+use std::convert::TryFrom;
 use std::fmt;
 use std::ops::Add;
 use std::path::PathBuf;
@@ -33,6 +34,8 @@ use expr::{GlobalId, MirRelationExpr, MirScalarExpr, OptimizedMirRelationExpr, P
 use interchange::avro::{self, DebeziumDeduplicationStrategy};
 use interchange::protobuf::{decode_descriptors, validate_descriptors};
 use kafka_util::KafkaAddrs;
+use repr::adt::enums::EnumLabels;
+use repr::adt::numeric::NUMERIC_DATUM_MAX_PRECISION;
 use repr::{ColumnName, ColumnType, Diff, RelationDesc, RelationType, Row, ScalarType, Timestamp};
 
 /// The response from a `Peek`.
@@ -308,8 +311,11 @@ pub enum DataEncoding {
     AvroOcf(AvroOcfEncoding),
     Protobuf(ProtobufEncoding),
     Csv(CsvEncoding),
+    Json(JsonEncoding),
+    Parquet(ParquetEncoding),
     Regex(RegexEncoding),
-    Postgres,
+    /// Rows replicated from Postgres, given the schema of the upstream tables if it is known
+    Postgres(Option<PostgresSourceDetails>),
     Bytes,
     Text,
 }
@@ -493,6 +499,7 @@ impl DataEncoding {
             DataEncoding::Protobuf(ProtobufEncoding {
                 descriptors,
                 message_name,
+                ..
             }) => {
                 let descriptors = decode_descriptors(descriptors)?;
                 let message_name = message_name
@@ -533,10 +540,21 @@ impl DataEncoding {
                     })
                 }
             },
+            DataEncoding::Json(JsonEncoding { columns }) => match columns {
+                None => key_desc.with_named_column("data", ScalarType::Jsonb.nullable(false)),
+                Some(columns) => columns.iter().fold(key_desc, |desc, (name, ty)| {
+                    desc.with_named_column(name.clone(), ty.clone())
+                }),
+            },
+            DataEncoding::Parquet(ParquetEncoding { columns }) => {
+                columns.iter().fold(key_desc, |desc, (name, ty)| {
+                    desc.with_named_column(name.clone(), ty.clone())
+                })
+            }
             DataEncoding::Text => {
                 key_desc.with_named_column("text", ScalarType::String.nullable(false))
             }
-            DataEncoding::Postgres => key_desc
+            DataEncoding::Postgres(None) => key_desc
                 .with_named_column("oid", ScalarType::Int32.nullable(false))
                 .with_named_column(
                     "row_data",
@@ -546,6 +564,42 @@ impl DataEncoding {
                     }
                     .nullable(false),
                 ),
+            // If the schema of the upstream tables is known, each table whose columns can be
+            // decoded gets a record column of its own. Only the column of the row's table is set.
+            DataEncoding::Postgres(Some(details)) => details.typed_tables().fold(
+                key_desc
+                    .with_named_column("oid", ScalarType::Int32.nullable(false))
+                    .with_named_column(
+                        "row_data",
+                        ScalarType::List {
+                            element_type: Box::new(ScalarType::String),
+                            custom_oid: None,
+                        }
+                        .nullable(true),
+                    ),
+                |desc, (table, types)| {
+                    let fields = table
+                        .columns
+                        .iter()
+                        .zip(types)
+                        .map(|(column, typ)| {
+                            (
+                                ColumnName::from(column.name.clone()),
+                                typ.scalar_type.nullable(true),
+                            )
+                        })
+                        .collect();
+                    desc.with_named_column(
+                        table.source_column_name(),
+                        ScalarType::Record {
+                            fields,
+                            custom_oid: None,
+                            custom_name: None,
+                        }
+                        .nullable(true),
+                    )
+                },
+            ),
         })
     }
 
@@ -557,8 +611,10 @@ impl DataEncoding {
             DataEncoding::Protobuf(_) => "Protobuf",
             DataEncoding::Regex { .. } => "Regex",
             DataEncoding::Csv(_) => "Csv",
+            DataEncoding::Json(_) => "Json",
+            DataEncoding::Parquet(_) => "Parquet",
             DataEncoding::Text => "Text",
-            DataEncoding::Postgres => "Postgres",
+            DataEncoding::Postgres(_) => "Postgres",
         }
     }
 
@@ -585,6 +641,25 @@ pub struct AvroOcfEncoding {
 pub struct ProtobufEncoding {
     pub descriptors: Vec<u8>,
     pub message_name: Option<String>,
+    /// Whether each record is prefixed with the header written by the
+    /// Confluent Schema Registry serializers.
+    pub confluent_wire_format: bool,
+}
+
+/// Arguments necessary to define how to decode from JSON format
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct JsonEncoding {
+    /// The columns to extract from the fields of each JSON object, or `None` if each JSON value
+    /// is presented as a single `jsonb` column.
+    pub columns: Option<Vec<(ColumnName, ColumnType)>>,
+}
+
+/// Arguments necessary to define how to decode from Parquet format
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct ParquetEncoding {
+    /// The columns to decode from each Parquet file, which are matched to the
+    /// top-level columns of the file by name.
+    pub columns: Vec<(ColumnName, ColumnType)>,
 }
 
 /// Arguments necessary to define how to decode from CSV format
@@ -663,6 +738,10 @@ pub struct SinkDesc {
 pub enum SinkEnvelope {
     Debezium,
     Upsert,
+    /// Emits only insertions. Retractions are an error.
+    None,
+    /// Emits updates and progress statements in the CDCv2 format.
+    CdcV2,
 }
 
 #[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
@@ -700,6 +779,14 @@ pub enum DebeziumMode {
 #[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize)]
 pub enum Compression {
     Gzip,
+    Zstd,
+    /// The Snappy framing format.
+    Snappy,
+    /// The LZ4 frame format.
+    Lz4,
+    /// Detect the compression of each file or object from its leading bytes,
+    /// falling back to its extension.
+    Auto,
     None,
 }
 
@@ -966,9 +1053,58 @@ pub struct KafkaSourceConnector {
     pub start_offsets: HashMap<i32, i64>,
     pub group_id_prefix: Option<String>,
     pub key_envelope: KeyEnvelope,
+    // Metadata columns to append to each decoded row, in order
+    pub include_metadata: Vec<KafkaMetadataColumn>,
     pub cluster_id: Uuid,
 }
 
+/// Per-message Kafka metadata that can be appended to a source's relation via
+/// `INCLUDE PARTITION, OFFSET, TIMESTAMP, HEADERS`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub enum KafkaMetadataColumn {
+    /// The partition the message was read from.
+    Partition,
+    /// The offset of the message within its partition.
+    Offset,
+    /// The timestamp the broker or producer assigned to the message.
+    Timestamp,
+    /// The message's headers, as a list of `(key, value)` records.
+    Headers,
+}
+
+impl KafkaMetadataColumn {
+    /// The name the column gets if the user does not supply one.
+    pub fn default_name(&self) -> &'static str {
+        match self {
+            KafkaMetadataColumn::Partition => "partition",
+            KafkaMetadataColumn::Offset => "offset",
+            KafkaMetadataColumn::Timestamp => "timestamp",
+            KafkaMetadataColumn::Headers => "headers",
+        }
+    }
+
+    /// The type of the column.
+    pub fn column_type(&self) -> ColumnType {
+        match self {
+            KafkaMetadataColumn::Partition => ScalarType::Int32.nullable(false),
+            KafkaMetadataColumn::Offset => ScalarType::Int64.nullable(false),
+            KafkaMetadataColumn::Timestamp => ScalarType::Timestamp.nullable(true),
+            KafkaMetadataColumn::Headers => ScalarType::List {
+                element_type: Box::new(ScalarType::Record {
+                    fields: vec![
+                        ("key".into(), ScalarType::String.nullable(false)),
+                        ("value".into(), ScalarType::Bytes.nullable(false)),
+                    ],
+                    custom_oid: None,
+                    custom_name: None,
+                }),
+                custom_oid: None,
+            }
+            .nullable(false),
+        }
+    }
+}
+
 /// Whether and how to include the key portion of a stream in dataflows
 ///
 /// Currently only Kafka streams have Key parts of messages, but there do exist other streaming
@@ -1007,6 +1143,128 @@ pub struct PostgresSourceConnector {
     pub conn: String,
     pub publication: String,
     pub slot_name: String,
+    /// The schema of the upstream tables at the time the source was created.
+    ///
+    /// Sources created before this information was recorded have no details,
+    /// in which case the schema is captured when the initial snapshot is taken.
+    pub details: Option<PostgresSourceDetails>,
+    /// Whether to retract the contents of upstream tables that are truncated.
+    ///
+    /// `TRUNCATE` messages do not carry the removed rows, so handling them
+    /// requires the source to keep a copy of every upstream table in memory.
+    /// If disabled, truncating an upstream table puts the source into an error
+    /// state.
+    pub handle_truncate: bool,
+}
+
+/// The upstream schema that a Postgres source expects to replicate.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct PostgresSourceDetails {
+    pub tables: Vec<PostgresTableDesc>,
+}
+
+/// The description of an upstream Postgres table.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct PostgresTableDesc {
+    /// The OID of the table
+    pub oid: u32,
+    /// The namespace the table belongs to
+    pub namespace: String,
+    /// The name of the table
+    pub name: String,
+    /// The columns of the table, in order
+    pub columns: Vec<PostgresColumnDesc>,
+}
+
+/// The description of a column of an upstream Postgres table.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct PostgresColumnDesc {
+    pub name: String,
+    pub type_oid: u32,
+    pub type_mod: i32,
+    pub nullable: bool,
+    pub primary_key: bool,
+}
+
+impl PostgresSourceDetails {
+    /// Returns the tables whose rows are decoded into typed columns, in the order in which their
+    /// columns appear in the source, along with the type of each of their columns.
+    ///
+    /// Rows of any other table are represented as a list of the textual form of each column.
+    pub fn typed_tables(&self) -> impl Iterator<Item = (&PostgresTableDesc, Vec<PostgresType>)> {
+        self.tables
+            .iter()
+            .filter_map(|table| Some((table, table.column_types()?)))
+    }
+}
+
+impl PostgresTableDesc {
+    /// Returns the type of each column of the table, or `None` if any column has a type that
+    /// cannot be decoded.
+    pub fn column_types(&self) -> Option<Vec<PostgresType>> {
+        self.columns.iter().map(|c| c.column_type()).collect()
+    }
+
+    /// Returns the name of the source column that holds the decoded rows of this table.
+    pub fn source_column_name(&self) -> ColumnName {
+        ColumnName::from(format!("table_{}", self.oid))
+    }
+}
+
+impl PostgresColumnDesc {
+    /// Returns the type of the column, or `None` if the column has a type that cannot be decoded.
+    pub fn column_type(&self) -> Option<PostgresType> {
+        let pg_type = pgrepr::Type::from_oid(self.type_oid)?;
+        // The type modifier is -1 if not specified.
+        let type_mod = if self.type_mod >= 0 {
+            Some(self.type_mod)
+        } else {
+            None
+        };
+        let with_type_mod = |scalar_type: ScalarType| match (scalar_type, type_mod) {
+            // https://github.com/postgres/postgres/blob/REL_13_3/src/backend/utils/adt/numeric.c#L978-L983
+            (ScalarType::Numeric { .. }, Some(type_mod)) => {
+                let type_mod = type_mod - 4;
+                let precision = usize::try_from((type_mod >> 16) & 0xffff).ok()?;
+                let scale = u8::try_from(type_mod & 0xffff).ok()?;
+                if precision > NUMERIC_DATUM_MAX_PRECISION {
+                    return None;
+                }
+                Some(ScalarType::Numeric { scale: Some(scale) })
+            }
+            // https://github.com/postgres/postgres/blob/REL_13_3/src/backend/utils/adt/varchar.c#L282-L286
+            (ScalarType::Char { .. }, Some(type_mod)) if type_mod >= 4 => Some(ScalarType::Char {
+                length: Some(usize::try_from(type_mod - 4).ok()?),
+            }),
+            (ScalarType::VarChar { .. }, Some(type_mod)) if type_mod >= 4 => {
+                Some(ScalarType::VarChar {
+                    length: Some(usize::try_from(type_mod - 4).ok()?),
+                })
+            }
+            (scalar_type, _) => Some(scalar_type),
+        };
+        let scalar_type = match &pg_type {
+            pgrepr::Type::Array(elem_type) => {
+                ScalarType::Array(Box::new(with_type_mod(elem_type.to_scalar_type_lossy())?))
+            }
+            // Values of these types cannot be converted into datums.
+            pgrepr::Type::Record(_) | pgrepr::Type::RegProc => return None,
+            pg_type => with_type_mod(pg_type.to_scalar_type_lossy())?,
+        };
+        Some(PostgresType {
+            pg_type,
+            scalar_type,
+        })
+    }
+}
+
+/// The type of a column of an upstream Postgres table.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PostgresType {
+    /// The type of the column's values in the replication stream
+    pub pg_type: pgrepr::Type,
+    /// The type of the column's values in Materialize
+    pub scalar_type: ScalarType,
 }
 
 #[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
@@ -1040,12 +1298,15 @@ pub enum SinkConnector {
     Kafka(KafkaSinkConnector),
     Tail(TailSinkConnector),
     AvroOcf(AvroOcfSinkConnector),
+    File(FileSinkConnector),
 }
 
 #[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
 pub struct KafkaSinkConsistencyConnector {
     pub topic: String,
-    pub schema_id: i32,
+    /// The ID of the Avro schema of the consistency records in the schema registry, or `None` if
+    /// the consistency records are encoded as JSON.
+    pub schema_id: Option<i32>,
     // gate_ts is the most recent high watermark tailed from the consistency topic
     // Exactly-once sinks use this to determine when they should start publishing again. This
     // tells them when they have caught up to where the previous materialize instance stopped.
@@ -1060,9 +1321,22 @@ pub struct KafkaSinkConnector {
     pub key_desc_and_indices: Option<(RelationDesc, Vec<usize>)>,
     pub relation_key_indices: Option<Vec<usize>>,
     pub value_desc: RelationDesc,
+    /// The labels of the enum types within `value_desc`, as looked up in the
+    /// catalog when the sink was created.
+    pub enum_labels: EnumLabels,
     pub published_schema_info: Option<PublishedSchemaInfo>,
+    /// The protobuf message as which to encode values, if the sink is
+    /// protobuf-encoded.
+    pub protobuf_schema: Option<ProtobufSinkSchema>,
     pub consistency: Option<KafkaSinkConsistencyConnector>,
+    /// Whether the sink resumes from the latest timestamp recorded in its
+    /// consistency topic after a restart, rather than writing to new topics.
     pub exactly_once: bool,
+    /// Whether the sink writes the updates of each timestamp, along with their
+    /// consistency records, in a Kafka transaction of their own, so that
+    /// consumers that read committed messages never observe partially
+    /// written timestamps.
+    pub transactional: bool,
     // Source dependencies for exactly-once sinks.
     pub transitive_source_dependencies: Vec<GlobalId>,
     // Maximum number of records the sink will attempt to send each time it is
@@ -1074,14 +1348,87 @@ pub struct KafkaSinkConnector {
 #[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
 pub struct AvroOcfSinkConnector {
     pub value_desc: RelationDesc,
+    /// The labels of the enum types within `value_desc`, as looked up in the
+    /// catalog when the sink was created.
+    pub enum_labels: EnumLabels,
     pub path: PathBuf,
 }
 
+/// A sink that writes the updates of a relation to rolling files.
+///
+/// Updates are grouped into files by timestamp. Once the sink's input has
+/// advanced past the end of a range of timestamps, the file holding the
+/// updates in that range is sealed, and the range is recorded in a manifest,
+/// so that readers only ever observe complete timestamps.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct FileSinkConnector {
+    pub location: FileSinkLocation,
+    pub format: FileSinkFormat,
+    pub value_desc: RelationDesc,
+    /// The labels of the enum types within `value_desc`, as looked up in the
+    /// catalog when the sink was created.
+    pub enum_labels: EnumLabels,
+    /// The width of the timestamp ranges at which files are sealed.
+    pub seal_interval: Duration,
+    /// The upper of the manifest that a previous incarnation of the sink
+    /// wrote, if any. All updates before it have already been written.
+    pub resume_upper: Option<Timestamp>,
+    /// The sources whose timestamp bindings must be persisted, so that the
+    /// sink can resume after a restart. Empty if the sink does not resume.
+    pub transitive_source_dependencies: Vec<GlobalId>,
+}
+
+/// Where a [`FileSinkConnector`] writes its files.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub enum FileSinkLocation {
+    /// A local directory.
+    Directory(PathBuf),
+    /// The objects in an S3 bucket that share a key prefix.
+    S3 {
+        bucket: String,
+        /// The prefix of the objects' keys, which is either empty or ends in a
+        /// slash.
+        prefix: String,
+        aws_info: aws::ConnectInfo,
+    },
+}
+
+/// The format of the files written by a [`FileSinkConnector`].
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub enum FileSinkFormat {
+    AvroOcf,
+    Csv {
+        /// Whether each file starts with a header of column names.
+        header: bool,
+        delimiter: u8,
+    },
+    Parquet,
+}
+
+impl FileSinkFormat {
+    /// Returns the file name extension of files in this format.
+    pub fn extension(&self) -> &'static str {
+        match self {
+            FileSinkFormat::AvroOcf => "avro",
+            FileSinkFormat::Csv { .. } => "csv",
+            FileSinkFormat::Parquet => "parquet",
+        }
+    }
+}
+
 impl SinkConnector {
     /// Returns the name of the sink connector.
     pub fn name(&self) -> &'static str {
         match self {
             SinkConnector::AvroOcf(_) => "avro-ocf",
+            SinkConnector::File(FileSinkConnector {
+                location: FileSinkLocation::Directory(_),
+                ..
+            }) => "file",
+            SinkConnector::File(FileSinkConnector {
+                location: FileSinkLocation::S3 { .. },
+                ..
+            }) => "s3",
             SinkConnector::Kafka(_) => "kafka",
             SinkConnector::Tail(_) => "tail",
         }
@@ -1105,6 +1452,7 @@ impl SinkConnector {
         match self {
             SinkConnector::Kafka(k) => k.exactly_once,
             SinkConnector::AvroOcf(_) => false,
+            SinkConnector::File(f) => !f.transitive_source_dependencies.is_empty(),
             SinkConnector::Tail(_) => false,
         }
     }
@@ -1115,9 +1463,24 @@ impl SinkConnector {
         match self {
             SinkConnector::Kafka(k) => &k.transitive_source_dependencies,
             SinkConnector::AvroOcf(_) => &[],
+            SinkConnector::File(f) => &f.transitive_source_dependencies,
             SinkConnector::Tail(_) => &[],
         }
     }
+
+    /// Returns the latest timestamp that a previous incarnation of this sink
+    /// has completely written, if any.
+    ///
+    /// A sink with a resume timestamp only needs to emit the updates after it,
+    /// if its inputs can still be read at that timestamp.
+    pub fn resume_ts(&self) -> Option<Timestamp> {
+        match self {
+            SinkConnector::Kafka(k) => k.consistency.as_ref().and_then(|c| c.gate_ts),
+            SinkConnector::AvroOcf(_) => None,
+            SinkConnector::File(f) => f.resume_upper.and_then(|upper| upper.checked_sub(1)),
+            SinkConnector::Tail(_) => None,
+        }
+    }
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -1131,6 +1494,7 @@ pub struct TailSinkConnector {
 pub enum SinkConnectorBuilder {
     Kafka(KafkaSinkConnectorBuilder),
     AvroOcf(AvroOcfSinkConnectorBuilder),
+    File(FileSinkConnectorBuilder),
 }
 
 #[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
@@ -1138,6 +1502,29 @@ pub struct AvroOcfSinkConnectorBuilder {
     pub path: PathBuf,
     pub file_name_suffix: String,
     pub value_desc: RelationDesc,
+    /// The labels of the enum types within `value_desc`, as looked up in the
+    /// catalog when the sink was created.
+    pub enum_labels: EnumLabels,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct FileSinkConnectorBuilder {
+    /// The directory or S3 prefix under which the sink creates the directory
+    /// or prefix that holds its files.
+    pub location: FileSinkLocation,
+    /// A suffix that makes the sink's directory or prefix unique across
+    /// sinks. It is also stable across restarts if the sink resumes writing
+    /// where it left off, and unique across restarts otherwise.
+    pub name_suffix: String,
+    pub format: FileSinkFormat,
+    pub value_desc: RelationDesc,
+    /// The labels of the enum types within `value_desc`, as looked up in the
+    /// catalog when the sink was created.
+    pub enum_labels: EnumLabels,
+    pub seal_interval: Duration,
+    /// The sources whose timestamp bindings must be persisted, so that the
+    /// sink can resume after a restart. Empty if the sink does not resume.
+    pub transitive_source_dependencies: Vec<GlobalId>,
 }
 
 #[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
@@ -1149,6 +1536,9 @@ pub struct KafkaSinkConnectorBuilder {
     /// The user-specified key for the sink.
     pub key_desc_and_indices: Option<(RelationDesc, Vec<usize>)>,
     pub value_desc: RelationDesc,
+    /// The labels of the enum types within `value_desc`, as looked up in the
+    /// catalog when the sink was created.
+    pub enum_labels: EnumLabels,
     pub topic_prefix: String,
     pub consistency_topic_prefix: Option<String>,
     pub consistency_format: Option<KafkaSinkFormat>,
@@ -1180,6 +1570,15 @@ pub enum KafkaSinkFormat {
         ccsr_config: ccsr::ClientConfig,
     },
     Json,
+    Protobuf(ProtobufSinkSchema),
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct ProtobufSinkSchema {
+    /// The fully qualified name of the message as which values are encoded.
+    pub message_name: String,
+    /// The encoded `FileDescriptorSet` that contains the message.
+    pub descriptors: Vec<u8>,
 }
 
 #[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
@@ -0,0 +1,113 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use dataflow_types::{DataflowError, DecodeError, JsonEncoding};
+use repr::adt::jsonb::{Jsonb, JsonbRef};
+use repr::{ColumnName, ColumnType, Datum, Row, RowArena, ScalarType};
+
+#[derive(Debug)]
+pub struct JsonDecoderState {
+    /// The columns to extract from the fields of each JSON object, or `None`
+    /// if each JSON value is presented as a single `jsonb` column.
+    columns: Option<Vec<(ColumnName, ColumnType)>>,
+    row_packer: Row,
+}
+
+impl JsonDecoderState {
+    pub fn new(encoding: JsonEncoding) -> Self {
+        JsonDecoderState {
+            columns: encoding.columns,
+            row_packer: Row::default(),
+        }
+    }
+
+    pub fn decode(
+        &mut self,
+        bytes: &[u8],
+        upstream_coord: Option<i64>,
+        push_metadata: bool,
+    ) -> Result<Option<Row>, DataflowError> {
+        let jsonb = Jsonb::from_slice(bytes)
+            .map_err(|e| DecodeError::Text(format!("Failed to decode JSON: {}", e)))?;
+        let value_row = jsonb.into_row();
+        let value = value_row.unpack_first();
+
+        match &self.columns {
+            None => self.row_packer.push(value),
+            Some(columns) => {
+                let fields = match value {
+                    Datum::Map(fields) => fields,
+                    _ => {
+                        return Err(DecodeError::Text(format!(
+                            "Failed to decode JSON: expected an object, got {}",
+                            JsonbRef::from_datum(value)
+                        ))
+                        .into())
+                    }
+                };
+                let arena = RowArena::new();
+                let mut datums = Vec::with_capacity(columns.len());
+                for (name, typ) in columns {
+                    let field = fields
+                        .iter()
+                        .find(|(key, _)| *key == name.as_str())
+                        .map(|(_, field)| field);
+                    let datum = match field {
+                        None | Some(Datum::JsonNull) => Datum::Null,
+                        Some(field) => {
+                            decode_field(field, &typ.scalar_type, &arena).map_err(|e| {
+                                DecodeError::Text(format!(
+                                    "Failed to decode JSON field {}: {}",
+                                    name, e
+                                ))
+                            })?
+                        }
+                    };
+                    if datum.is_null() && !typ.nullable {
+                        return Err(DecodeError::Text(format!(
+                            "Failed to decode JSON: field {} is missing or null, but its column \
+                             is declared NOT NULL",
+                            name
+                        ))
+                        .into());
+                    }
+                    datums.push(datum);
+                }
+                self.row_packer.extend(datums);
+            }
+        }
+        if push_metadata {
+            self.row_packer.push(Datum::from(upstream_coord));
+        }
+        Ok(Some(self.row_packer.finish_and_reuse()))
+    }
+}
+
+/// Converts the value of a field of a JSON object to a datum of type
+/// `scalar_type`.
+///
+/// `jsonb` columns take the value as is. Columns of any other type take the
+/// contents of JSON strings, or the JSON representation of any other value, and
+/// parse it as the textual form of the type.
+fn decode_field<'a>(
+    field: Datum<'a>,
+    scalar_type: &ScalarType,
+    arena: &'a RowArena,
+) -> Result<Datum<'a>, String> {
+    if let ScalarType::Jsonb = scalar_type {
+        return Ok(field);
+    }
+    let text = match field {
+        Datum::String(s) => s.to_owned(),
+        _ => JsonbRef::from_datum(field).to_string(),
+    };
+    let ty = pgrepr::Type::from(scalar_type);
+    let value = pgrepr::Value::decode_text(&ty, text.as_bytes()).map_err(|e| e.to_string())?;
+    Ok(value.into_datum(arena, &ty).0)
+}
@@ -38,6 +38,8 @@ use repr::{Diff, Row, Timestamp};
 
 use self::avro::AvroDecoderState;
 use self::csv::CsvDecoderState;
+use self::json::JsonDecoderState;
+use self::parquet::ParquetDecoderState;
 use self::protobuf::ProtobufDecoderState;
 use crate::metrics::Metrics;
 use crate::source::DecodeResult;
@@ -45,6 +47,8 @@ use crate::source::SourceOutput;
 
 mod avro;
 mod csv;
+mod json;
+mod parquet;
 mod protobuf;
 
 /// Update row to blank out retractions of rows that we have never seen
@@ -186,6 +190,7 @@ pub(crate) enum PreDelimitedFormat {
     Text,
     Regex(Regex, Row),
     Protobuf(ProtobufDecoderState),
+    Json(JsonDecoderState),
 }
 
 impl PreDelimitedFormat {
@@ -235,6 +240,7 @@ impl PreDelimitedFormat {
             PreDelimitedFormat::Protobuf(pb) => pb
                 .get_value(bytes, upstream_coord, push_metadata)
                 .transpose(),
+            PreDelimitedFormat::Json(json) => json.decode(bytes, upstream_coord, push_metadata),
         }
     }
 }
@@ -247,6 +253,7 @@ pub(crate) enum DataDecoderInner {
         format: PreDelimitedFormat,
     },
     Csv(CsvDecoderState),
+    Parquet(ParquetDecoderState),
 
     PreDelimited(PreDelimitedFormat),
 }
@@ -280,6 +287,7 @@ impl DataDecoder {
                 avro.decode(bytes, upstream_coord, upstream_time_millis, push_metadata)
             }
             DataDecoderInner::Csv(csv) => csv.decode(bytes, upstream_coord, push_metadata),
+            DataDecoderInner::Parquet(parquet) => parquet.next(bytes),
             DataDecoderInner::PreDelimited(format) => {
                 let result = format.decode(*bytes, upstream_coord, push_metadata);
                 *bytes = &[];
@@ -314,6 +322,23 @@ impl DataDecoder {
                     format.decode(data, upstream_coord, push_metadata)
                 }
             }
+            DataDecoderInner::Parquet(parquet) => parquet.eof(bytes, upstream_coord, push_metadata),
+            _ => Ok(None),
+        }
+    }
+
+    /// Get a further record after an EOF has occurred.
+    ///
+    /// Most formats produce at most one record at EOF, but those that can only
+    /// decode entire objects at once, like Parquet, produce all of an object's
+    /// records at its EOF.
+    pub fn next_after_eof(
+        &mut self,
+        upstream_coord: Option<i64>,
+        push_metadata: bool,
+    ) -> Result<Option<Row>, DataflowError> {
+        match &mut self.inner {
+            DataDecoderInner::Parquet(parquet) => parquet.next_row(upstream_coord, push_metadata),
             _ => Ok(None),
         }
     }
@@ -369,6 +394,7 @@ fn get_decoder(
         DataEncoding::Text
         | DataEncoding::Bytes
         | DataEncoding::Protobuf(_)
+        | DataEncoding::Json(_)
         | DataEncoding::Regex(_) => {
             let after_delimiting = match encoding {
                 DataEncoding::Regex(RegexEncoding { regex }) => {
@@ -377,10 +403,13 @@ fn get_decoder(
                 DataEncoding::Protobuf(ProtobufEncoding {
                     descriptors,
                     message_name,
+                    confluent_wire_format,
                 }) => PreDelimitedFormat::Protobuf(ProtobufDecoderState::new(
                     &descriptors,
                     message_name,
+                    confluent_wire_format,
                 )),
+                DataEncoding::Json(enc) => PreDelimitedFormat::Json(JsonDecoderState::new(enc)),
                 DataEncoding::Bytes => PreDelimitedFormat::Bytes,
                 DataEncoding::Text => PreDelimitedFormat::Text,
                 _ => unreachable!(),
@@ -435,7 +464,14 @@ fn get_decoder(
                 metrics,
             }
         }
-        DataEncoding::Postgres => {
+        DataEncoding::Parquet(enc) => {
+            let state = ParquetDecoderState::new(enc, operators);
+            DataDecoder {
+                inner: DataDecoderInner::Parquet(state),
+                metrics,
+            }
+        }
+        DataEncoding::Postgres(_) => {
             unreachable!("Postgres sources should not go through the general decoding path.")
         }
     }
@@ -530,6 +566,7 @@ where
                         value,
                         position,
                         upstream_time_millis,
+                        metadata,
                     } in data.iter()
                     {
                         let key_cursor = &mut key.as_slice();
@@ -556,6 +593,7 @@ where
                                 key,
                                 value: None,
                                 position: *position,
+                                metadata: metadata.clone(),
                             });
                         } else {
                             let value = match &value {
@@ -599,6 +637,7 @@ where
                                 key,
                                 value,
                                 position: *position,
+                                metadata: metadata.clone(),
                             });
                         }
                     }
@@ -698,6 +737,7 @@ where
                     value,
                     position: _,
                     upstream_time_millis,
+                    metadata: _,
                 } in data.iter()
                 {
                     let key_cursor = &mut key.as_slice();
@@ -736,21 +776,22 @@ where
                             }
                             value_buf.clear();
 
-                            match result {
-                                None => continue,
-                                Some(value) => {
-                                    if matches!(&key, Some(Err(_))) || matches!(&value, Err(_)) {
-                                        n_errors += 1;
-                                    } else if matches!(&value, Ok(_)) {
-                                        n_successes += 1;
-                                    }
-                                    session.give(DecodeResult {
-                                        key,
-                                        value: Some(value),
-                                        position: Some(n_seen),
-                                    });
-                                    n_seen += 1;
+                            while let Some(value) = result {
+                                if matches!(&key, Some(Err(_))) || matches!(&value, Err(_)) {
+                                    n_errors += 1;
+                                } else if matches!(&value, Ok(_)) {
+                                    n_successes += 1;
                                 }
+                                session.give(DecodeResult {
+                                    key: key.clone(),
+                                    value: Some(value),
+                                    position: Some(n_seen),
+                                    metadata: Row::default(),
+                                });
+                                n_seen += 1;
+                                result = value_decoder
+                                    .next_after_eof(Some(n_seen + 1), push_metadata)
+                                    .transpose();
                             }
                             continue;
                         }
@@ -770,6 +811,7 @@ where
                             key,
                             value: None,
                             position: None,
+                            metadata: Row::default(),
                         });
                     } else {
                         let value_bytes_remaining = &mut value.as_slice();
@@ -811,6 +853,7 @@ where
                                     key,
                                     value: Some(value),
                                     position: Some(n_seen),
+                                    metadata: Row::default(),
                                 });
                                 value_buf = vec![];
                                 break;
@@ -819,6 +862,7 @@ where
                                     key: key.clone(),
                                     value: Some(value),
                                     position: Some(n_seen),
+                                    metadata: Row::default(),
                                 });
                             }
                             if is_err {
@@ -0,0 +1,129 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use std::mem;
+use std::vec;
+
+use dataflow_types::{DataflowError, DecodeError, LinearOperator, ParquetEncoding};
+use interchange::parquet::{Decoder, DecoderFile};
+use repr::{Datum, Row};
+
+/// Decodes Parquet files, which can only be decoded once they have been read
+/// in full.
+///
+/// Each file is buffered until its EOF, as its footer, which locates its row
+/// groups, comes last. Its row groups are then decoded one at a time, each once
+/// the rows of the previous one have been handed out.
+#[derive(Debug)]
+pub struct ParquetDecoderState {
+    decoder: Decoder,
+    /// The bytes of the file that is currently being read.
+    buf: Vec<u8>,
+    /// The last file, whose row groups have yet to be decoded in full.
+    file: Option<DecoderFile>,
+    /// The rows of the current row group that have yet to be handed out.
+    rows: vec::IntoIter<Row>,
+    row_packer: Row,
+}
+
+impl ParquetDecoderState {
+    pub fn new(encoding: ParquetEncoding, operators: &Option<LinearOperator>) -> Self {
+        let ParquetEncoding { columns } = encoding;
+        let n_cols = columns.len();
+
+        // Only the columns that are projected, or that the predicates depend
+        // on, need to be read. The operators are left in place, as they are
+        // still to be applied to the decoded rows.
+        let demanded = match operators {
+            None => vec![true; n_cols],
+            Some(operators) => {
+                let mut demanded = vec![false; n_cols];
+                let support = operators.predicates.iter().flat_map(|p| p.support());
+                for c in operators.projection.iter().copied().chain(support) {
+                    if c < n_cols {
+                        demanded[c] = true;
+                    }
+                }
+                demanded
+            }
+        };
+
+        ParquetDecoderState {
+            decoder: Decoder::new(columns, demanded),
+            buf: vec![],
+            file: None,
+            rows: vec![].into_iter(),
+            row_packer: Row::default(),
+        }
+    }
+
+    /// Buffers `bytes`, as no record can be decoded before the end of the
+    /// file.
+    pub fn next(&mut self, bytes: &mut &[u8]) -> Result<Option<Row>, DataflowError> {
+        self.buf.extend_from_slice(bytes);
+        *bytes = &[];
+        Ok(None)
+    }
+
+    /// Reads the footer of the buffered file, and returns its first row.
+    pub fn eof(
+        &mut self,
+        bytes: &mut &[u8],
+        upstream_coord: Option<i64>,
+        push_metadata: bool,
+    ) -> Result<Option<Row>, DataflowError> {
+        self.next(bytes)?;
+        if !self.buf.is_empty() {
+            let buf = mem::take(&mut self.buf);
+            self.file = Some(self.decoder.open(buf).map_err(decode_error)?);
+        }
+        self.next_row(upstream_coord, push_metadata)
+    }
+
+    /// Returns the next row of the last file, if any remain, decoding its next
+    /// row group once the rows of the current one have been handed out.
+    pub fn next_row(
+        &mut self,
+        upstream_coord: Option<i64>,
+        push_metadata: bool,
+    ) -> Result<Option<Row>, DataflowError> {
+        let mut row = self.rows.next();
+        while row.is_none() {
+            let file = match &mut self.file {
+                None => break,
+                Some(file) => file,
+            };
+            match self.decoder.next_row_group(file) {
+                Ok(Some(rows)) => {
+                    self.rows = rows.into_iter();
+                    row = self.rows.next();
+                }
+                Ok(None) => self.file = None,
+                Err(e) => {
+                    // The rest of the file is skipped.
+                    self.file = None;
+                    return Err(decode_error(e));
+                }
+            }
+        }
+        match row {
+            None => Ok(None),
+            Some(row) if push_metadata => {
+                self.row_packer.extend(row.iter());
+                self.row_packer.push(Datum::from(upstream_coord));
+                Ok(Some(self.row_packer.finish_and_reuse()))
+            }
+            Some(row) => Ok(Some(row)),
+        }
+    }
+}
+
+fn decode_error(e: anyhow::Error) -> DataflowError {
+    DecodeError::Text(format!("Failed to decode Parquet file: {:#}", e)).into()
+}
@@ -20,7 +20,11 @@ pub struct ProtobufDecoderState {
 }
 
 impl ProtobufDecoderState {
-    pub fn new(descriptors: &[u8], message_name: Option<String>) -> Self {
+    pub fn new(
+        descriptors: &[u8],
+        message_name: Option<String>,
+        confluent_wire_format: bool,
+    ) -> Self {
         let DecodedDescriptors {
             descriptors,
             first_message_name,
@@ -28,7 +32,7 @@ impl ProtobufDecoderState {
             .expect("descriptors provided to protobuf source are pre-validated");
         let message_name = message_name.as_ref().unwrap_or_else(|| &first_message_name);
         ProtobufDecoderState {
-            decoder: Decoder::new(descriptors, &message_name),
+            decoder: Decoder::new(descriptors, &message_name, confluent_wire_format),
             events_success: 0,
             events_error: 0,
         }
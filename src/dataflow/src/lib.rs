@@ -24,3 +24,4 @@ pub mod source;
 
 pub use render::plan::Plan;
 pub use server::{serve, Command, Config, Response, TimestampBindingFeedback, WorkerFeedback};
+pub use sink::read_file_sink_upper;
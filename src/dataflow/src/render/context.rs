@@ -27,6 +27,7 @@ use timely::progress::timestamp::Refines;
 use timely::progress::{Antichain, Timestamp};
 
 use crate::arrangement::manager::{ErrSpine, RowSpine, TraceErrHandle, TraceRowHandle};
+use crate::render::RenderTimestamp;
 use dataflow_types::{DataflowDescription, DataflowError};
 use expr::{GlobalId, Id, MapFilterProject, MirScalarExpr};
 use repr::{Diff, Row, RowArena};
@@ -438,7 +439,8 @@ where
 
 impl<S> CollectionBundle<S, repr::Row, repr::Timestamp>
 where
-    S: Scope<Timestamp = repr::Timestamp>,
+    S: Scope,
+    S::Timestamp: RenderTimestamp,
 {
     /// Presents `self` as a stream of updates, having been subjected to `mfp`.
     ///
@@ -465,7 +467,18 @@ where
                 move |data, time, diff| {
                     let temp_storage = repr::RowArena::new();
                     let mut datums_local = datums.borrow_with(&data);
-                    mfp_plan.evaluate(&mut datums_local, &temp_storage, time.clone(), diff.clone())
+                    let time = time.clone();
+                    mfp_plan
+                        .evaluate(
+                            &mut datums_local,
+                            &temp_storage,
+                            time.system_time(),
+                            diff.clone(),
+                        )
+                        .map(move |result| match result {
+                            Ok((row, t, diff)) => Ok((row, time.with_system_time(t), diff)),
+                            Err((e, t, diff)) => Err((e, time.with_system_time(t), diff)),
+                        })
                 }
             });
 
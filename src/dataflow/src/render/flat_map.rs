@@ -16,19 +16,21 @@ use crate::operator::StreamExt;
 use crate::render::context::CollectionBundle;
 use crate::render::context::Context;
 use crate::render::datum_vec::DatumVec;
+use crate::render::RenderTimestamp;
 
 impl<G> Context<G, Row, repr::Timestamp>
 where
-    G: Scope<Timestamp = repr::Timestamp>,
+    G: Scope,
+    G::Timestamp: RenderTimestamp,
 {
     /// Renders `relation_expr` followed by `map_filter_project` if provided.
     pub fn render_flat_map(
         &mut self,
-        input: CollectionBundle<G, Row, G::Timestamp>,
+        input: CollectionBundle<G, Row, repr::Timestamp>,
         func: TableFunc,
         exprs: Vec<MirScalarExpr>,
         mfp: MapFilterProject,
-    ) -> CollectionBundle<G, Row, G::Timestamp> {
+    ) -> CollectionBundle<G, Row, repr::Timestamp> {
         let mfp_plan = mfp.into_plan().expect("MapFilterProject planning failed");
         let (ok_collection, err_collection) = input.as_collection();
         let (oks, errs) = ok_collection.inner.flat_map_fallible("FlatMapStage", {
@@ -54,6 +56,7 @@ where
                 // borrows are moved in and used by `mfp.evaluate`.
                 let temp_storage = &temp_storage;
                 let mfp_plan = &mfp_plan;
+                let system_time = time.system_time();
                 let output_rows_vec: Vec<_> = output_rows.collect();
                 output_rows_vec
                     .iter()
@@ -63,7 +66,11 @@ where
                         // Extend datums with additional columns, replace some with dummy values.
                         datums_local.extend(output_row.iter());
                         mfp_plan
-                            .evaluate(&mut datums_local, temp_storage, time, diff * *r)
+                            .evaluate(&mut datums_local, temp_storage, system_time, diff * *r)
+                            .map(|result| match result {
+                                Ok((row, t, diff)) => Ok((row, time.with_system_time(t), diff)),
+                                Err((e, t, diff)) => Err((e, time.with_system_time(t), diff)),
+                            })
                             .collect::<Vec<_>>()
                     })
                     .collect::<Vec<_>>()
@@ -102,21 +102,25 @@
 
 use std::any::Any;
 use std::cell::RefCell;
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 use std::rc::Rc;
 use std::rc::Weak;
+use std::sync::{Arc, Mutex};
 
+use differential_dataflow::lattice::Lattice;
 use differential_dataflow::AsCollection;
 use persist::indexed::runtime::RuntimeClient;
 use timely::communication::Allocate;
 use timely::dataflow::operators::to_stream::ToStream;
 use timely::dataflow::scopes::Child;
 use timely::dataflow::Scope;
+use timely::order::Product;
+use timely::progress::timestamp::Refines;
 use timely::progress::Antichain;
 use timely::worker::Worker as TimelyWorker;
 
 use dataflow_types::*;
-use expr::{GlobalId, Id};
+use expr::{GlobalId, Id, LocalId};
 use itertools::Itertools;
 use ore::collections::CollectionExt as _;
 use ore::now::NowFn;
@@ -126,6 +130,7 @@ use crate::arrangement::manager::{TraceBundle, TraceManager};
 use crate::metrics::Metrics;
 use crate::render::context::CollectionBundle;
 use crate::render::context::{ArrangementFlavor, Context};
+use crate::render::join::DeltaJoinPlan;
 use crate::server::LocalInput;
 use crate::sink::SinkBaseMetrics;
 use crate::source::metrics::SourceBaseMetrics;
@@ -167,6 +172,13 @@ pub struct RenderState {
     /// The entries are pairs of sink identifier (to identify the tail instance)
     /// and the response itself.
     pub tail_response_buffer: Rc<RefCell<Vec<(GlobalId, TailResponse)>>>,
+    /// Shared buffer with sinks by which they can report errors that stop
+    /// them from writing any further updates.
+    ///
+    /// The entries are pairs of sink identifier and a description of the
+    /// error. The buffer is shared with tasks outside of the worker thread,
+    /// like those that write files for file sinks.
+    pub sink_error_buffer: Arc<Mutex<Vec<(GlobalId, String)>>>,
 }
 
 /// A container for "tokens" that are relevant to an in-construction dataflow.
@@ -385,7 +397,8 @@ where
 
 impl<G> Context<G, Row, Timestamp>
 where
-    G: Scope<Timestamp = Timestamp>,
+    G: Scope,
+    G::Timestamp: RenderTimestamp,
 {
     /// Renders a plan to a differential dataflow, producing the collection of results.
     ///
@@ -396,7 +409,7 @@ where
         plan: plan::Plan,
         scope: &mut G,
         worker_index: usize,
-    ) -> CollectionBundle<G, Row, G::Timestamp> {
+    ) -> CollectionBundle<G, Row, Timestamp> {
         use plan::Plan;
         match plan {
             Plan::Constant { rows } => {
@@ -409,18 +422,29 @@ where
                 };
 
                 // We should advance times in constant collections to start from `as_of`.
-                use differential_dataflow::lattice::Lattice;
                 for (_, time, _) in rows.iter_mut() {
                     time.advance_by(self.as_of_frontier.borrow());
                 }
-                let mut error_time: G::Timestamp = timely::progress::Timestamp::minimum();
+                let mut error_time: Timestamp = timely::progress::Timestamp::minimum();
                 error_time.advance_by(self.as_of_frontier.borrow());
 
-                let ok_collection = rows.into_iter().to_stream(scope).as_collection();
+                let ok_collection = rows
+                    .into_iter()
+                    .map(|(row, time, diff)| {
+                        (row, <G::Timestamp as Refines<_>>::to_inner(time), diff)
+                    })
+                    .to_stream(scope)
+                    .as_collection();
 
                 let err_collection = errs
                     .into_iter()
-                    .map(move |e| (DataflowError::from(e), error_time, 1))
+                    .map(move |e| {
+                        (
+                            DataflowError::from(e),
+                            <G::Timestamp as Refines<_>>::to_inner(error_time),
+                            1,
+                        )
+                    })
                     .to_stream(scope)
                     .as_collection();
 
@@ -458,6 +482,9 @@ where
                 self.remove_id(Id::Local(id));
                 body
             }
+            Plan::LetRec { id, value, body } => {
+                G::Timestamp::render_let_rec(self, id, *value, *body, scope, worker_index)
+            }
             Plan::Mfp {
                 input,
                 mfp,
@@ -491,7 +518,7 @@ where
                         self.render_join(inputs, linear_plan, scope)
                     }
                     crate::render::join::JoinPlan::Delta(delta_plan) => {
-                        self.render_delta_join(inputs, delta_plan, scope)
+                        G::Timestamp::render_delta_join(self, inputs, delta_plan, scope)
                     }
                 }
             }
@@ -539,6 +566,175 @@ where
     }
 }
 
+/// Timestamps of the scopes into which plans are rendered.
+///
+/// Most plans are rendered into scopes whose timestamp is [`Timestamp`], but
+/// the values of recursive bindings are rendered into iterative scopes, whose
+/// timestamps additionally track the round of iteration. This trait captures
+/// what rendering needs to know about either.
+pub trait RenderTimestamp: timely::progress::Timestamp + Lattice + Refines<Timestamp> {
+    /// The system time that `self` refines.
+    fn system_time(&self) -> Timestamp;
+    /// Returns `self` with its system time replaced by `time`.
+    fn with_system_time(&self, time: Timestamp) -> Self;
+    /// A summary that delays only the system time, by `delay`.
+    fn system_delay(delay: Timestamp) -> <Self as timely::progress::Timestamp>::Summary;
+    /// Renders a delta join into `scope`.
+    fn render_delta_join<G>(
+        context: &mut Context<G, Row, Timestamp>,
+        inputs: Vec<CollectionBundle<G, Row, Timestamp>>,
+        join_plan: DeltaJoinPlan,
+        scope: &mut G,
+    ) -> CollectionBundle<G, Row, Timestamp>
+    where
+        G: Scope<Timestamp = Self>;
+    /// Renders the fixed point of `value` into `scope`, binds it to `id`, and
+    /// renders `body` with that binding.
+    fn render_let_rec<G>(
+        context: &mut Context<G, Row, Timestamp>,
+        id: LocalId,
+        value: plan::Plan,
+        body: plan::Plan,
+        scope: &mut G,
+        worker_index: usize,
+    ) -> CollectionBundle<G, Row, Timestamp>
+    where
+        G: Scope<Timestamp = Self>;
+}
+
+impl RenderTimestamp for Timestamp {
+    fn system_time(&self) -> Timestamp {
+        *self
+    }
+
+    fn with_system_time(&self, time: Timestamp) -> Self {
+        time
+    }
+
+    fn system_delay(delay: Timestamp) -> Timestamp {
+        delay
+    }
+
+    fn render_delta_join<G>(
+        context: &mut Context<G, Row, Timestamp>,
+        inputs: Vec<CollectionBundle<G, Row, Timestamp>>,
+        join_plan: DeltaJoinPlan,
+        scope: &mut G,
+    ) -> CollectionBundle<G, Row, Timestamp>
+    where
+        G: Scope<Timestamp = Self>,
+    {
+        context.render_delta_join(inputs, join_plan, scope)
+    }
+
+    fn render_let_rec<G>(
+        context: &mut Context<G, Row, Timestamp>,
+        id: LocalId,
+        value: plan::Plan,
+        body: plan::Plan,
+        scope: &mut G,
+        worker_index: usize,
+    ) -> CollectionBundle<G, Row, Timestamp>
+    where
+        G: Scope<Timestamp = Self>,
+    {
+        use differential_dataflow::operators::iterate::Variable;
+        use timely::dataflow::operators::generic::operator::empty;
+
+        let mut depends_on = BTreeSet::new();
+        value.depends_on(&mut depends_on);
+
+        let (oks, errs) = scope.clone().iterative::<u64, _, _>(|inner| {
+            let mut inner_context = Context {
+                debug_name: context.debug_name.clone(),
+                dataflow_id: context.dataflow_id,
+                as_of_frontier: context.as_of_frontier.clone(),
+                bindings: BTreeMap::new(),
+            };
+            // Arrangements cannot be brought into the iterative scope without
+            // changing their types, so we only bring in the collections.
+            for (other_id, bundle) in context.bindings.iter() {
+                if depends_on.contains(other_id) {
+                    let (oks, errs) = bundle.as_collection();
+                    inner_context.insert_id(
+                        *other_id,
+                        CollectionBundle::from_collections(oks.enter(inner), errs.enter(inner)),
+                    );
+                }
+            }
+
+            // The binding starts out empty, and in each round is set to the
+            // result of the previous round. Errors are not fed back, as they
+            // would otherwise accumulate without bound; the errors of the last
+            // round are reported when leaving the scope.
+            let variable = Variable::new(inner, Product::new(Default::default(), 1));
+            inner_context.insert_id(
+                Id::Local(id),
+                CollectionBundle::from_collections(
+                    (*variable).clone(),
+                    empty(inner).as_collection(),
+                ),
+            );
+            let (oks, errs) = inner_context
+                .render_plan(value, inner, worker_index)
+                .as_collection();
+            variable.set(&oks);
+            (oks.leave(), errs.leave())
+        });
+
+        let prebound =
+            context.insert_id(Id::Local(id), CollectionBundle::from_collections(oks, errs));
+        assert!(prebound.is_none());
+        let body = context.render_plan(body, scope, worker_index);
+        context.remove_id(Id::Local(id));
+        body
+    }
+}
+
+impl RenderTimestamp for Product<Timestamp, u64> {
+    fn system_time(&self) -> Timestamp {
+        self.outer
+    }
+
+    fn with_system_time(&self, time: Timestamp) -> Self {
+        Product::new(time, self.inner)
+    }
+
+    fn system_delay(delay: Timestamp) -> Product<Timestamp, u64> {
+        Product::new(delay, 0)
+    }
+
+    fn render_delta_join<G>(
+        _context: &mut Context<G, Row, Timestamp>,
+        _inputs: Vec<CollectionBundle<G, Row, Timestamp>>,
+        _join_plan: DeltaJoinPlan,
+        _scope: &mut G,
+    ) -> CollectionBundle<G, Row, Timestamp>
+    where
+        G: Scope<Timestamp = Self>,
+    {
+        // Delta joins rely on totally ordered times, and `Plan::from_mir`
+        // plans linear joins in their place within recursive bindings.
+        panic!("delta joins cannot be rendered in iterative scopes")
+    }
+
+    fn render_let_rec<G>(
+        _context: &mut Context<G, Row, Timestamp>,
+        _id: LocalId,
+        _value: plan::Plan,
+        _body: plan::Plan,
+        _scope: &mut G,
+        _worker_index: usize,
+    ) -> CollectionBundle<G, Row, Timestamp>
+    where
+        G: Scope<Timestamp = Self>,
+    {
+        // `Plan::from_mir` rejects recursive bindings within the values of
+        // other recursive bindings.
+        panic!("nested recursive bindings cannot be rendered")
+    }
+}
+
 /// A re-useable vector of `Datum` with varying lifetimes.
 ///
 /// This type is meant to allow us to recycle an underlying allocation with
@@ -616,12 +812,12 @@ pub mod plan {
     use crate::render::top_k::TopKPlan;
     use dataflow_types::DataflowDescription;
     use expr::{
-        EvalError, Id, JoinInputMapper, LocalId, MapFilterProject, MirRelationExpr, MirScalarExpr,
-        OptimizedMirRelationExpr, TableFunc,
+        EvalError, Id, JoinImplementation, JoinInputMapper, LocalId, MapFilterProject,
+        MirRelationExpr, MirScalarExpr, OptimizedMirRelationExpr, TableFunc,
     };
 
     use repr::{Datum, Diff, Row};
-    use std::collections::BTreeMap;
+    use std::collections::{BTreeMap, BTreeSet};
 
     /// A rendering plan with all conditional logic removed.
     ///
@@ -683,6 +879,25 @@ pub mod plan {
             /// that reference `Id::Local(id)`.
             body: Box<Plan>,
         },
+        /// Binds the fixed point of `value` to `id`, and then results in `body` with
+        /// that binding.
+        ///
+        /// The fixed point is maintained in an iterative scope, in which `value` is
+        /// repeatedly evaluated with `id` bound to the result of the previous round,
+        /// starting from the empty collection.
+        ///
+        /// The binding is not available outside of `value` and `body`.
+        LetRec {
+            /// The local identifier to be used, available to `value` and `body` as
+            /// `Id::Local(id)`.
+            id: LocalId,
+            /// The collection whose fixed point should be bound to `id`, which is
+            /// allowed to contain `Get` stages that reference `Id::Local(id)`.
+            value: Box<Plan>,
+            /// The collection that results, which is allowed to contain `Get` stages
+            /// that reference `Id::Local(id)`.
+            body: Box<Plan>,
+        },
         /// Map, Filter, and Project operators.
         ///
         /// This stage contains work that we would ideally like to fuse to other plan
@@ -917,6 +1132,45 @@ pub mod plan {
                         b_keys,
                     )
                 }
+                MirRelationExpr::LetRec { id, value, body } => {
+                    // The value is rendered in an iterative scope, into which
+                    // existing bindings are brought without their arrangements,
+                    // so we plan it without any arrangements at all. The fixed
+                    // point leaves that scope without arrangements as well, and
+                    // so `id` contributes none to the body.
+                    //
+                    // Iterative scopes support neither delta joins, which rely
+                    // on totally ordered times, nor further recursive bindings.
+                    // We plan any delta join as a linear join from its first
+                    // input, and report nested recursive bindings as errors.
+                    let mut value = (**value).clone();
+                    let mut nested = false;
+                    value.visit_mut(&mut |expr| match expr {
+                        MirRelationExpr::LetRec { .. } => nested = true,
+                        MirRelationExpr::Join { implementation, .. } => {
+                            if let JoinImplementation::DeltaQuery(orders) = implementation {
+                                let order = orders[0].clone();
+                                *implementation =
+                                    JoinImplementation::Differential((0, None), order);
+                            }
+                        }
+                        _ => {}
+                    });
+                    if nested {
+                        return Err(());
+                    }
+                    let (value, _v_keys) = Plan::from_mir(&value, &mut BTreeMap::new())?;
+                    let (body, b_keys) = Plan::from_mir(body, arrangements)?;
+                    // Return the plan, and any `body` arrangements.
+                    (
+                        Plan::LetRec {
+                            id: id.clone(),
+                            value: Box::new(value),
+                            body: Box::new(body),
+                        },
+                        b_keys,
+                    )
+                }
                 MirRelationExpr::FlatMap {
                     input,
                     func,
@@ -1124,6 +1378,34 @@ pub mod plan {
             Ok((plan, keys))
         }
 
+        /// Collects the identifiers of the collections that `self` reads.
+        ///
+        /// This includes identifiers that are bound within `self`.
+        pub fn depends_on(&self, ids: &mut BTreeSet<Id>) {
+            match self {
+                Plan::Constant { .. } => {}
+                Plan::Get { id, .. } => {
+                    ids.insert(id.clone());
+                }
+                Plan::Let { value, body, .. } | Plan::LetRec { value, body, .. } => {
+                    value.depends_on(ids);
+                    body.depends_on(ids);
+                }
+                Plan::Mfp { input, .. }
+                | Plan::FlatMap { input, .. }
+                | Plan::Reduce { input, .. }
+                | Plan::TopK { input, .. }
+                | Plan::Negate { input }
+                | Plan::Threshold { input, .. }
+                | Plan::ArrangeBy { input, .. } => input.depends_on(ids),
+                Plan::Join { inputs, .. } | Plan::Union { inputs } => {
+                    for input in inputs {
+                        input.depends_on(ids);
+                    }
+                }
+            }
+        }
+
         /// Convert the dataflow description into one that uses render plans.
         pub fn finalize_dataflow(
             desc: DataflowDescription<OptimizedMirRelationExpr>,
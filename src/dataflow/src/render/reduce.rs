@@ -1779,6 +1779,9 @@ fn convert_indexes_to_skips(mut indexes: Vec<usize>) -> Vec<usize> {
 /// significant input data). Hierarchical aggregates can be rendered more efficiently if the
 /// input stream is append-only as then we only need to retain the "currently winning" value.
 /// Every hierarchical aggregate needs to supply a corresponding ReductionMonoid implementation.
+///
+/// Basic aggregations are recomputed from scratch for each group whose input has changed. This
+/// includes window functions, which are planned as aggregations over each window partition.
 fn reduction_type(func: &AggregateFunc) -> ReductionType {
     match func {
         AggregateFunc::SumInt16
@@ -1817,7 +1820,14 @@ fn reduction_type(func: &AggregateFunc) -> ReductionType {
         | AggregateFunc::JsonbObjectAgg { .. }
         | AggregateFunc::ArrayConcat { .. }
         | AggregateFunc::ListConcat { .. }
-        | AggregateFunc::StringAgg { .. } => ReductionType::Basic,
+        | AggregateFunc::StringAgg { .. }
+        | AggregateFunc::RowNumber { .. }
+        | AggregateFunc::Rank { .. }
+        | AggregateFunc::DenseRank { .. }
+        | AggregateFunc::LagLead { .. }
+        | AggregateFunc::FirstValue { .. }
+        | AggregateFunc::LastValue { .. }
+        | AggregateFunc::WindowAggregate { .. } => ReductionType::Basic,
     }
 }
 
@@ -15,7 +15,8 @@ use std::collections::HashSet;
 use std::rc::Rc;
 
 use differential_dataflow::operators::arrange::arrangement::ArrangeByKey;
-use differential_dataflow::{Collection, Hashable};
+use differential_dataflow::{AsCollection, Collection, Hashable};
+use timely::dataflow::operators::Map;
 use timely::dataflow::Scope;
 
 use dataflow_types::*;
@@ -147,6 +148,11 @@ where
     //   It then renders those as Avro.
     // * Upsert" does the same, except at the last step, it renders the diff pair in upsert format.
     //   (As part of doing so, it asserts that there are not multiple conflicting values at the same timestamp)
+    // * "None" consolidates the stream and passes on the updates as they are. Retractions are
+    //   rejected by the sink.
+    // * "CdcV2" consolidates the stream and moves the time and diff of each update into the row,
+    //   as CDCv2 messages carry them explicitly. File sinks use this envelope to write the time
+    //   and diff of each update as columns.
     // * "Tail" writes some metadata.
     let collection = match sink.envelope {
         Some(SinkEnvelope::Debezium) => {
@@ -182,6 +188,27 @@ where
             });
             collection
         }
+        Some(SinkEnvelope::None) => {
+            // if there is no user-specified key, remove the synthetic
+            // distribution key again
+            let user_key_indices = sink_render.get_key_indices();
+            let keyed = if user_key_indices.is_some() {
+                keyed
+            } else {
+                keyed.map(|(_key, value)| (None, value))
+            };
+            keyed.consolidate().map(|(key, value)| (key, Some(value)))
+        }
+        Some(SinkEnvelope::CdcV2) => keyed
+            .map(|(_key, value)| value)
+            .consolidate()
+            .inner
+            .map(|(mut row, time, diff)| {
+                row.push(Datum::Int64(time as i64));
+                row.push(Datum::Int64(diff as i64));
+                ((None, Some(row)), time, 1)
+            })
+            .as_collection(),
         // No envelope, this can only happen for TAIL sinks, which work
         // on vanilla rows.
         None => keyed.map(|(key, value)| (key, Some(value))),
@@ -223,6 +250,7 @@ where
     match connector {
         SinkConnector::Kafka(connector) => Box::new(connector.clone()),
         SinkConnector::AvroOcf(connector) => Box::new(connector.clone()),
+        SinkConnector::File(connector) => Box::new(connector.clone()),
         SinkConnector::Tail(connector) => Box::new(connector.clone()),
     }
 }
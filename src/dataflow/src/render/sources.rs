@@ -561,6 +561,7 @@ where
 }
 
 /// Convert from streams of [`DecodeResult`] to Rows, inserting the Key according to [`KeyEnvelope`]
+/// and appending any metadata columns
 fn flatten_results<G>(
     key_envelope: KeyEnvelope,
     results: timely::dataflow::Stream<G, DecodeResult>,
@@ -573,7 +574,21 @@ where
 {
     match key_envelope {
         KeyEnvelope::None => results
-            .flat_map(|DecodeResult { key: _, value, .. }| value)
+            .flat_map(
+                |DecodeResult {
+                     key: _,
+                     value,
+                     metadata,
+                     ..
+                 }| {
+                    value.map(|value| {
+                        value.map(|mut value| {
+                            value.extend_by_row(&metadata);
+                            value
+                        })
+                    })
+                },
+            )
             .ok_err(std::convert::identity),
         KeyEnvelope::Flattened | KeyEnvelope::LegacyUpsert => results
             .flat_map(flatten_key_value)
@@ -609,12 +624,21 @@ where
     }
 }
 
-/// Handle possibly missing key or value portions of messages
+/// Handle possibly missing key or value portions of messages, appending any
+/// metadata columns to the value
 fn flatten_key_value(result: DecodeResult) -> Option<Result<(Row, Row), DataflowError>> {
-    let DecodeResult { key, value, .. } = result;
+    let DecodeResult {
+        key,
+        value,
+        metadata,
+        ..
+    } = result;
     match (key, value) {
         (Some(key), Some(value)) => match (key, value) {
-            (Ok(key), Ok(value)) => Some(Ok((key, value))),
+            (Ok(key), Ok(mut value)) => {
+                value.extend_by_row(&metadata);
+                Some(Ok((key, value)))
+            }
             // always prioritize the value error if either or both have an error
             (_, Err(e)) => Some(Err(e)),
             (Err(e), _) => Some(Err(e)),
@@ -32,6 +32,7 @@ use repr::{Diff, Row};
 
 use crate::render::context::CollectionBundle;
 use crate::render::context::Context;
+use crate::render::RenderTimestamp;
 
 /// A plan encapsulating different variants to compute a TopK operation.
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -151,16 +152,17 @@ pub struct BasicTopKPlan {
     arity: usize,
 }
 
-// The implementation requires integer timestamps to be able to delay feedback for monotonic inputs.
+// The implementation requires system timestamps to be able to delay feedback for monotonic inputs.
 impl<G> Context<G, Row, repr::Timestamp>
 where
-    G: Scope<Timestamp = repr::Timestamp>,
+    G: Scope,
+    G::Timestamp: RenderTimestamp,
 {
     pub fn render_topk(
         &mut self,
-        input: CollectionBundle<G, Row, G::Timestamp>,
+        input: CollectionBundle<G, Row, repr::Timestamp>,
         top_k_plan: TopKPlan,
-    ) -> CollectionBundle<G, Row, G::Timestamp> {
+    ) -> CollectionBundle<G, Row, repr::Timestamp> {
         let (ok_input, err_input) = input.as_collection();
 
         // We create a new region to compartmentalize the topk logic.
@@ -187,8 +189,10 @@ where
                     // stage.
                     use differential_dataflow::operators::iterate::Variable;
                     let delay = std::time::Duration::from_nanos(10_000_000_000);
-                    let retractions =
-                        Variable::new(&mut ok_input.scope(), delay.as_millis() as u64);
+                    let retractions = Variable::new(
+                        &mut ok_input.scope(),
+                        G::Timestamp::system_delay(delay.as_millis() as u64),
+                    );
                     let thinned = ok_input.concat(&retractions.negate());
                     let result = build_topk(thinned, group_key, order_key, 0, limit, arity);
                     retractions.set(&ok_input.concat(&result.negate()));
@@ -226,6 +226,9 @@ pub enum WorkerFeedback {
     PeekResponse(u32, PeekResponse),
     /// The worker's next response to a specified tail.
     TailResponse(GlobalId, TailResponse),
+    /// An error that stopped the specified sink from writing any further
+    /// updates.
+    SinkError(GlobalId, String),
 }
 
 /// Configures a dataflow server.
@@ -299,6 +302,7 @@ pub fn serve(config: Config) -> Result<WorkerGuards<()>, String> {
                     metrics,
                     persist: persist.clone(),
                     tail_response_buffer: std::rc::Rc::new(std::cell::RefCell::new(Vec::new())),
+                    sink_error_buffer: std::sync::Arc::new(Mutex::new(Vec::new())),
                 },
                 materialized_logger: None,
                 command_rx,
@@ -606,6 +610,7 @@ where
             self.metrics.observe_command_finish();
             self.process_peeks();
             self.process_tails();
+            self.process_sink_errors();
         }
     }
 
@@ -1144,6 +1149,19 @@ where
                 .expect("feedback receiver should not drop first");
         }
     }
+
+    /// Scan the shared sink error buffer, and forward errors along.
+    fn process_sink_errors(&mut self) {
+        let mut sink_errors = self.render_state.sink_error_buffer.lock().unwrap();
+        for (sink_id, error) in sink_errors.drain(..) {
+            self.feedback_tx
+                .send(Response {
+                    worker_id: self.timely_worker.index(),
+                    message: WorkerFeedback::SinkError(sink_id, error),
+                })
+                .expect("feedback receiver should not drop first");
+        }
+    }
 }
 
 pub struct LocalInput {
@@ -123,7 +123,8 @@ fn avro_ocf<G>(
                     };
 
                     for (v, _time, diff) in vector.drain(..) {
-                        let value = encode_datums_as_avro(v.iter(), &columns);
+                        let value =
+                            encode_datums_as_avro(v.iter(), &columns, &connector.enum_labels);
                         assert!(diff > 0, "can't sink negative multiplicities");
                         for value in repeat_n(value, diff as usize) {
                             avro_writer
@@ -0,0 +1,746 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! A sink that writes rolling files to a local directory or an S3 bucket.
+//!
+//! All updates are routed to a single worker, which buffers them until the
+//! input frontier passes the end of the current seal interval. The updates
+//! at the timestamps in the interval are then encoded into a single file,
+//! which a Tokio task writes out before rewriting the sink's manifest:
+//!
+//! ```text
+//! <lower>-<upper>.<extension>   updates at timestamps in [lower, upper)
+//! manifest-<lower>-<upper>.json {"files": [...]}
+//! manifest.json                 {"upper": ..., "parts": [...], "files": [...]}
+//! ```
+//!
+//! The manifest's `upper` is the timestamp before which all updates have been
+//! written, so readers that only read the files listed in the manifest
+//! observe consistent snapshots of the sinked relation. A sink that restarts
+//! resumes writing at the upper of its existing manifest.
+//!
+//! Intervals without updates are not written out on their own. They are
+//! instead covered by the next file that is written, so the manifest's upper
+//! only advances when a file is written or the input is complete. To keep the
+//! manifest small, every `MANIFEST_ROLL_FILES` files are moved out of it
+//! into an immutable manifest part, which the manifest then lists instead.
+//!
+//! Errors that stop the sink from writing, like retractions in a sink without
+//! an envelope, are reported in `mz_sink_errors`.
+
+use std::any::Any;
+use std::cell::RefCell;
+use std::convert::TryFrom;
+use std::path::PathBuf;
+use std::rc::Rc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use anyhow::{anyhow, bail};
+use differential_dataflow::lattice::Lattice;
+use differential_dataflow::{Collection, Hashable};
+use futures::TryStreamExt;
+use rusoto_core::RusotoError;
+use rusoto_s3::{GetObjectError, GetObjectRequest, PutObjectRequest, S3Client, S3};
+use serde::{Deserialize, Serialize};
+use timely::dataflow::channels::pact::Exchange;
+use timely::dataflow::operators::generic::builder_rc::OperatorBuilder;
+use timely::dataflow::operators::generic::FrontieredInputHandle;
+use timely::dataflow::Scope;
+use timely::progress::Antichain;
+use tokio::sync::mpsc::error::TrySendError;
+use tokio::sync::mpsc::{self, Receiver};
+
+use dataflow_types::{FileSinkConnector, FileSinkFormat, FileSinkLocation, SinkAsOf, SinkDesc};
+use expr::GlobalId;
+use interchange::avro::{encode_datums_as_avro, AvroSchemaGenerator};
+use interchange::encode::column_names_and_types;
+use interchange::parquet;
+use ore::cast::CastFrom;
+use ore::retry::Retry;
+use repr::adt::enums::EnumLabels;
+use repr::{ColumnName, ColumnType, Diff, RelationDesc, Row, Timestamp};
+
+use crate::render::sinks::SinkRender;
+use crate::render::RenderState;
+use crate::source::timestamp::TimestampBindingRc;
+
+use super::{report_sink_error, SinkBaseMetrics};
+
+/// The number of sealed files that may be queued for the writer before the
+/// sink stops sealing new files.
+const SEAL_QUEUE_CAPACITY: usize = 4;
+
+/// The number of files that the manifest lists before they are moved into a
+/// manifest part.
+const MANIFEST_ROLL_FILES: usize = 1000;
+
+impl<G> SinkRender<G> for FileSinkConnector
+where
+    G: Scope<Timestamp = Timestamp>,
+{
+    fn uses_keys(&self) -> bool {
+        false
+    }
+
+    fn get_key_desc(&self) -> Option<&RelationDesc> {
+        None
+    }
+
+    fn get_key_indices(&self) -> Option<&[usize]> {
+        None
+    }
+
+    fn get_relation_key_indices(&self) -> Option<&[usize]> {
+        None
+    }
+
+    fn get_value_desc(&self) -> &RelationDesc {
+        &self.value_desc
+    }
+
+    fn render_continuous_sink(
+        &self,
+        render_state: &mut RenderState,
+        sink: &SinkDesc,
+        sink_id: GlobalId,
+        sinked_collection: Collection<G, (Option<Row>, Option<Row>), Diff>,
+        _metrics: &SinkBaseMetrics,
+    ) -> Option<Box<dyn Any>>
+    where
+        G: Scope<Timestamp = Timestamp>,
+    {
+        // A sink that resumes after a restart needs its sources to persist
+        // their timestamp bindings, and may only seal timestamps whose
+        // bindings are durable.
+        let mut source_ts_histories = Vec::new();
+        for id in &self.transitive_source_dependencies {
+            if let Some(history) = render_state.ts_histories.get(id) {
+                history.enable_persistence();
+                let mut history_bindings = history.clone();
+                // We don't want these to block compaction ever.
+                history_bindings.set_compaction_frontier(Antichain::new().borrow());
+                source_ts_histories.push(history_bindings);
+            }
+        }
+
+        let scope = sinked_collection.inner.scope();
+        let active_write_worker =
+            (usize::cast_from(sink_id.hashed()) % scope.peers()) == scope.index();
+        let shared_frontier = Rc::new(RefCell::new(Antichain::from_elem(0)));
+
+        file(
+            sinked_collection,
+            sink_id,
+            self.clone(),
+            sink.as_of.clone(),
+            source_ts_histories,
+            Rc::clone(&shared_frontier),
+            Arc::clone(&render_state.sink_error_buffer),
+        );
+
+        if active_write_worker {
+            render_state
+                .sink_write_frontiers
+                .insert(sink_id, shared_frontier);
+        }
+
+        // no sink token
+        None
+    }
+}
+
+fn file<G>(
+    collection: Collection<G, (Option<Row>, Option<Row>)>,
+    id: GlobalId,
+    connector: FileSinkConnector,
+    as_of: SinkAsOf,
+    source_ts_histories: Vec<TimestampBindingRc>,
+    write_frontier: Rc<RefCell<Antichain<Timestamp>>>,
+    sink_errors: Arc<Mutex<Vec<(GlobalId, String)>>>,
+) where
+    G: Scope<Timestamp = Timestamp>,
+{
+    let collection = collection.map(|(k, v)| {
+        assert!(k.is_none(), "file sinks must not have keys");
+        v.expect("file sinks must have values")
+    });
+
+    // We want exactly one worker to write all files, so that each file holds
+    // all updates in its range of timestamps.
+    let hashed_id = id.hashed();
+    let scope = collection.scope();
+    let active_write_worker = (usize::cast_from(hashed_id) % scope.peers()) == scope.index();
+
+    let encoder = FileEncoder::new(
+        &connector.format,
+        connector.value_desc.clone(),
+        connector.enum_labels.clone(),
+    )
+    .expect("file sink formats are validated during planning");
+    let extension = connector.format.extension();
+    let seal_interval = Timestamp::try_from(connector.seal_interval.as_millis())
+        .unwrap_or(Timestamp::MAX)
+        .max(1);
+    // The updates before the upper of the manifest that a previous incarnation
+    // of the sink wrote have already been written.
+    let resume_upper = connector.resume_upper.unwrap_or(0);
+    // The upper of the manifest that the writer has last written.
+    let written_upper = Arc::new(AtomicU64::new(resume_upper));
+
+    let tx = if active_write_worker {
+        let (tx, rx) = mpsc::channel(SEAL_QUEUE_CAPACITY);
+        tokio::spawn(write_files(
+            id,
+            connector.location,
+            rx,
+            Arc::clone(&written_upper),
+            Arc::clone(&sink_errors),
+        ));
+        Some(tx)
+    } else {
+        None
+    };
+
+    let mut vector = vec![];
+    let mut updates: Vec<(Row, Timestamp, Diff)> = vec![];
+    // The timestamp before which all updates have been sealed into files.
+    let mut sealed_upper: Timestamp = resume_upper;
+    // A seal that is waiting for the writer to catch up.
+    let mut pending: Option<Seal> = None;
+    let mut failed = false;
+
+    let mut builder = OperatorBuilder::new(format!("file-{}", id), scope.clone());
+    let activator = scope.activator_for(&builder.operator_info().address[..]);
+    let mut input = builder.new_input(&collection.inner, Exchange::new(move |_| hashed_id));
+
+    let mut sink_logic = move |input: &mut FrontieredInputHandle<_, (Row, Timestamp, Diff), _>| {
+        input.for_each(|_, rows| {
+            rows.swap(&mut vector);
+            if failed {
+                vector.clear();
+            } else {
+                updates.extend(vector.drain(..).filter(|(_row, time, _diff)| {
+                    let should_emit = if as_of.strict {
+                        as_of.frontier.less_than(time)
+                    } else {
+                        as_of.frontier.less_equal(time)
+                    };
+                    should_emit && *time >= resume_upper
+                }));
+            }
+        });
+
+        let tx = match &tx {
+            Some(tx) if !failed => tx,
+            _ => return,
+        };
+
+        // Report the timestamps that have been durably written, so that
+        // the sink's sources can compact their timestamp bindings.
+        let written = written_upper.load(Ordering::SeqCst).saturating_sub(1);
+        if write_frontier.borrow().less_than(&written) {
+            let mut write_frontier = write_frontier.borrow_mut();
+            write_frontier.clear();
+            write_frontier.insert(written);
+        }
+
+        // Don't seal more files while the writer is behind, so that the
+        // sealed files that are queued for it stay bounded.
+        if let Some(seal) = pending.take() {
+            match tx.try_send(seal) {
+                Ok(()) => (),
+                Err(TrySendError::Full(seal)) => {
+                    pending = Some(seal);
+                    activator.activate_after(Duration::from_millis(100));
+                    return;
+                }
+                Err(TrySendError::Closed(_)) => {
+                    // The writer has failed and reported why.
+                    failed = true;
+                    return;
+                }
+            }
+        }
+
+        // Only timestamps whose bindings are durable can be sealed, as
+        // they could otherwise change across a restart.
+        let mut frontier = input.frontier().frontier().to_owned();
+        for history in &source_ts_histories {
+            frontier.meet_assign(&history.durability_frontier());
+        }
+
+        // Seal files at multiples of the seal interval, or seal all
+        // remaining updates once the input is complete.
+        let upper = match frontier.elements().iter().min() {
+            Some(frontier) => frontier - frontier % seal_interval,
+            None => Timestamp::MAX,
+        };
+        if upper <= sealed_upper {
+            return;
+        }
+
+        // Intervals without updates are covered by the next file instead,
+        // unless the input is complete.
+        if upper != Timestamp::MAX && !updates.iter().any(|(_row, time, _diff)| *time < upper) {
+            return;
+        }
+
+        let (mut sealed, unsealed) = updates
+            .drain(..)
+            .partition::<Vec<_>, _>(|(_row, time, _diff)| *time < upper);
+        updates = unsealed;
+        sealed.sort_by_key(|(_row, time, _diff)| *time);
+
+        let file = if sealed.is_empty() {
+            None
+        } else {
+            match encoder.encode(&sealed) {
+                Ok((data, records)) => Some(SealedFile {
+                    name: format!("{:020}-{:020}.{}", sealed_upper, upper, extension),
+                    data,
+                    records,
+                }),
+                Err(e) => {
+                    report_sink_error(
+                        &sink_errors,
+                        id,
+                        format!("failed to encode updates: {:#}", e),
+                    );
+                    failed = true;
+                    return;
+                }
+            }
+        };
+        let seal = Seal {
+            lower: sealed_upper,
+            upper,
+            file,
+        };
+        sealed_upper = upper;
+        match tx.try_send(seal) {
+            Ok(()) => (),
+            Err(TrySendError::Full(seal)) => {
+                pending = Some(seal);
+                activator.activate_after(Duration::from_millis(100));
+            }
+            Err(TrySendError::Closed(_)) => {
+                // The writer has failed and reported why.
+                failed = true;
+            }
+        }
+    };
+
+    builder.build(|_capabilities| {
+        move |frontiers| {
+            let mut input_handle = FrontieredInputHandle::new(&mut input, &frontiers[0]);
+            sink_logic(&mut input_handle)
+        }
+    });
+}
+
+/// Encodes sealed updates into the contents of a file.
+enum FileEncoder {
+    AvroOcf {
+        schema: mz_avro::Schema,
+        columns: Vec<(ColumnName, ColumnType)>,
+        enum_labels: EnumLabels,
+    },
+    Csv {
+        header: Option<String>,
+        delimiter: u8,
+        columns: Vec<(ColumnName, ColumnType)>,
+        enum_labels: EnumLabels,
+    },
+    Parquet(parquet::Encoder),
+}
+
+impl FileEncoder {
+    fn new(
+        format: &FileSinkFormat,
+        desc: RelationDesc,
+        enum_labels: EnumLabels,
+    ) -> Result<FileEncoder, anyhow::Error> {
+        match format {
+            FileSinkFormat::AvroOcf => {
+                let schema_generator = AvroSchemaGenerator::new(None, desc, false);
+                Ok(FileEncoder::AvroOcf {
+                    schema: schema_generator.value_writer_schema().clone(),
+                    columns: schema_generator.value_columns().to_vec(),
+                    enum_labels,
+                })
+            }
+            FileSinkFormat::Csv { header, delimiter } => {
+                let columns = column_names_and_types(desc);
+                let header = if *header {
+                    let mut line = String::new();
+                    for (i, (name, _typ)) in columns.iter().enumerate() {
+                        if i > 0 {
+                            line.push(char::from(*delimiter));
+                        }
+                        push_csv_field(&mut line, name.as_str(), *delimiter);
+                    }
+                    line.push('\n');
+                    Some(line)
+                } else {
+                    None
+                };
+                Ok(FileEncoder::Csv {
+                    header,
+                    delimiter: *delimiter,
+                    columns,
+                    enum_labels,
+                })
+            }
+            FileSinkFormat::Parquet => Ok(FileEncoder::Parquet(parquet::Encoder::new(desc)?)),
+        }
+    }
+
+    /// Encodes `updates` into a file, returning its contents and the number of
+    /// records in it.
+    fn encode(
+        &self,
+        updates: &[(Row, Timestamp, Diff)],
+    ) -> Result<(Vec<u8>, usize), anyhow::Error> {
+        let mut rows = vec![];
+        for (row, _time, diff) in updates {
+            if *diff < 0 {
+                bail!("cannot sink a retraction with ENVELOPE NONE");
+            }
+            for _ in 0..*diff {
+                rows.push(row);
+            }
+        }
+        let data = match self {
+            FileEncoder::AvroOcf {
+                schema,
+                columns,
+                enum_labels,
+            } => {
+                let mut writer = mz_avro::Writer::new(schema.clone(), Vec::new());
+                for row in &rows {
+                    writer.append(encode_datums_as_avro(row.iter(), columns, enum_labels))?;
+                }
+                writer.flush()?;
+                writer.into_inner()
+            }
+            FileEncoder::Csv {
+                header,
+                delimiter,
+                columns,
+                enum_labels,
+            } => {
+                let mut out = header.clone().unwrap_or_default();
+                let mut buf = String::new();
+                for row in &rows {
+                    for (i, (datum, (_name, typ))) in row.iter().zip(columns).enumerate() {
+                        if i > 0 {
+                            out.push(char::from(*delimiter));
+                        }
+                        // NULLs are empty, unquoted fields.
+                        if let Some(value) =
+                            pgrepr::Value::from_datum(datum, &typ.scalar_type, enum_labels)
+                        {
+                            buf.clear();
+                            value.encode_text(&mut buf);
+                            push_csv_field(&mut out, &buf, *delimiter);
+                        }
+                    }
+                    out.push('\n');
+                }
+                out.into_bytes()
+            }
+            FileEncoder::Parquet(encoder) => {
+                let rows: Vec<_> = rows.into_iter().cloned().collect();
+                return Ok((encoder.encode(&rows)?, rows.len()));
+            }
+        };
+        Ok((data, rows.len()))
+    }
+}
+
+/// Pushes `field` onto a CSV line, quoting it if it is empty or contains the
+/// delimiter, a quote, or a line break.
+fn push_csv_field(line: &mut String, field: &str, delimiter: u8) {
+    let needs_quotes = field.is_empty()
+        || field
+            .bytes()
+            .any(|b| b == delimiter || b == b'"' || b == b'\n' || b == b'\r');
+    if needs_quotes {
+        line.push('"');
+        line.push_str(&field.replace('"', "\"\""));
+        line.push('"');
+    } else {
+        line.push_str(field);
+    }
+}
+
+/// The updates at the timestamps in `[lower, upper)`.
+struct Seal {
+    lower: Timestamp,
+    upper: Timestamp,
+    /// The file holding the updates, if there are any.
+    file: Option<SealedFile>,
+}
+
+struct SealedFile {
+    name: String,
+    data: Vec<u8>,
+    records: usize,
+}
+
+#[derive(Serialize, Deserialize)]
+struct Manifest {
+    upper: Timestamp,
+    /// The manifest parts that list the files before the files in `files`.
+    #[serde(default)]
+    parts: Vec<ManifestPart>,
+    files: Vec<ManifestFile>,
+}
+
+/// An immutable manifest part, which lists files that have been moved out of
+/// the manifest.
+#[derive(Serialize, Deserialize)]
+struct ManifestPart {
+    name: String,
+    lower: Timestamp,
+    upper: Timestamp,
+}
+
+#[derive(Serialize)]
+struct ManifestPartContents<'a> {
+    files: &'a [ManifestFile],
+}
+
+#[derive(Serialize, Deserialize)]
+struct ManifestFile {
+    name: String,
+    lower: Timestamp,
+    upper: Timestamp,
+    records: usize,
+}
+
+/// Reads the upper of the manifest that the file sink at `location` has
+/// written, if it has written one.
+///
+/// All updates at timestamps before the upper have been written, so a sink
+/// that restarts at `location` only needs to write the updates after it.
+pub async fn read_file_sink_upper(
+    location: &FileSinkLocation,
+) -> Result<Option<Timestamp>, anyhow::Error> {
+    let writer = Writer::new(location.clone())?;
+    Ok(writer.read_manifest().await?.map(|manifest| manifest.upper))
+}
+
+/// Writes sealed files, and the manifest that lists them, in the order that
+/// they are sealed.
+async fn write_files(
+    id: GlobalId,
+    location: FileSinkLocation,
+    mut rx: Receiver<Seal>,
+    written_upper: Arc<AtomicU64>,
+    sink_errors: Arc<Mutex<Vec<(GlobalId, String)>>>,
+) {
+    let writer = match Writer::new(location) {
+        Ok(writer) => writer,
+        Err(e) => {
+            report_sink_error(&sink_errors, id, format!("failed to start: {:#}", e));
+            return;
+        }
+    };
+    // Continue the manifest of a previous incarnation of the sink, if any, so
+    // that it keeps listing the files that were already written.
+    let mut manifest = match writer.read_manifest().await {
+        Ok(manifest) => manifest.unwrap_or(Manifest {
+            upper: 0,
+            parts: vec![],
+            files: vec![],
+        }),
+        Err(e) => {
+            report_sink_error(
+                &sink_errors,
+                id,
+                format!("failed to read manifest: {:#}", e),
+            );
+            return;
+        }
+    };
+    while let Some(seal) = rx.recv().await {
+        // The data file must be written before the manifest that lists it.
+        if let Some(file) = seal.file {
+            if let Err(e) = writer.put(&file.name, file.data).await {
+                report_sink_error(
+                    &sink_errors,
+                    id,
+                    format!("failed to write {}: {:#}", file.name, e),
+                );
+                return;
+            }
+            manifest.files.push(ManifestFile {
+                name: file.name,
+                lower: seal.lower,
+                upper: seal.upper,
+                records: file.records,
+            });
+        }
+        // The manifest part must be written before the manifest that lists
+        // it in place of its files.
+        if manifest.files.len() >= MANIFEST_ROLL_FILES {
+            let part = ManifestPart {
+                name: format!(
+                    "manifest-{:020}-{:020}.json",
+                    manifest.files[0].lower, seal.upper
+                ),
+                lower: manifest.files[0].lower,
+                upper: seal.upper,
+            };
+            let data = serde_json::to_vec(&ManifestPartContents {
+                files: &manifest.files,
+            })
+            .expect("manifest serialization cannot fail");
+            if let Err(e) = writer.put(&part.name, data).await {
+                report_sink_error(
+                    &sink_errors,
+                    id,
+                    format!("failed to write {}: {:#}", part.name, e),
+                );
+                return;
+            }
+            manifest.parts.push(part);
+            manifest.files.clear();
+        }
+        manifest.upper = seal.upper;
+        let data = serde_json::to_vec(&manifest).expect("manifest serialization cannot fail");
+        if let Err(e) = writer.put("manifest.json", data).await {
+            report_sink_error(
+                &sink_errors,
+                id,
+                format!("failed to write manifest: {:#}", e),
+            );
+            return;
+        }
+        written_upper.store(manifest.upper, Ordering::SeqCst);
+    }
+}
+
+/// Writes whole files to a local directory or an S3 bucket.
+enum Writer {
+    Directory(PathBuf),
+    S3 {
+        client: S3Client,
+        bucket: String,
+        prefix: String,
+    },
+}
+
+impl Writer {
+    fn new(location: FileSinkLocation) -> Result<Writer, anyhow::Error> {
+        match location {
+            FileSinkLocation::Directory(path) => Ok(Writer::Directory(path)),
+            FileSinkLocation::S3 {
+                bucket,
+                prefix,
+                aws_info,
+            } => Ok(Writer::S3 {
+                client: aws_util::client::s3(aws_info)?,
+                bucket,
+                prefix,
+            }),
+        }
+    }
+
+    /// Reads the sink's manifest, if it has written one.
+    async fn read_manifest(&self) -> Result<Option<Manifest>, anyhow::Error> {
+        match self.get("manifest.json").await? {
+            None => Ok(None),
+            Some(data) => Ok(Some(serde_json::from_slice(&data)?)),
+        }
+    }
+
+    /// Reads the contents of the file named `name`, if it exists.
+    async fn get(&self, name: &str) -> Result<Option<Vec<u8>>, anyhow::Error> {
+        match self {
+            Writer::Directory(dir) => match tokio::fs::read(dir.join(name)).await {
+                Ok(data) => Ok(Some(data)),
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+                Err(e) => Err(e.into()),
+            },
+            Writer::S3 {
+                client,
+                bucket,
+                prefix,
+            } => {
+                let key = format!("{}{}", prefix, name);
+                // A missing object is an answer, not a failure to retry.
+                let object = Retry::default()
+                    .retry(|_| async {
+                        let request = GetObjectRequest {
+                            bucket: bucket.clone(),
+                            key: key.clone(),
+                            ..Default::default()
+                        };
+                        match client.get_object(request).await {
+                            Ok(object) => Ok(Some(object)),
+                            Err(RusotoError::Service(GetObjectError::NoSuchKey(_))) => Ok(None),
+                            Err(e) => Err(e),
+                        }
+                    })
+                    .await
+                    .map_err(|e| anyhow!("reading s3://{}/{} failed: {}", bucket, key, e))?;
+                let object = match object {
+                    Some(object) => object,
+                    None => return Ok(None),
+                };
+                let data = match object.body {
+                    Some(body) => body.map_ok(|bytes| bytes.to_vec()).try_concat().await?,
+                    None => vec![],
+                };
+                Ok(Some(data))
+            }
+        }
+    }
+
+    /// Writes `data` to the file named `name`, replacing any existing file
+    /// atomically and durably.
+    async fn put(&self, name: &str, data: Vec<u8>) -> Result<(), anyhow::Error> {
+        match self {
+            Writer::Directory(dir) => {
+                // Write to a temporary file first, so that readers never
+                // observe a partially written file. Both the file and the
+                // rename must be synced before the file is listed anywhere,
+                // or a crash could lose a file that the manifest lists.
+                let tmp_path = dir.join(format!(".{}.tmp", name));
+                tokio::fs::write(&tmp_path, data).await?;
+                tokio::fs::File::open(&tmp_path).await?.sync_all().await?;
+                tokio::fs::rename(&tmp_path, dir.join(name)).await?;
+                tokio::fs::File::open(dir).await?.sync_all().await?;
+            }
+            Writer::S3 {
+                client,
+                bucket,
+                prefix,
+            } => {
+                let key = format!("{}{}", prefix, name);
+                Retry::default()
+                    .retry(|_| {
+                        client.put_object(PutObjectRequest {
+                            bucket: bucket.clone(),
+                            key: key.clone(),
+                            body: Some(data.clone().into()),
+                            ..Default::default()
+                        })
+                    })
+                    .await
+                    .map_err(|e| anyhow!("writing s3://{}/{} failed: {}", bucket, key, e))?;
+            }
+        }
+        Ok(())
+    }
+}
@@ -13,11 +13,12 @@ use std::cmp;
 use std::collections::{HashMap, VecDeque};
 use std::rc::Rc;
 use std::sync::atomic::{AtomicBool, Ordering};
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 use std::time::Duration;
 
 use differential_dataflow::{AsCollection, Collection, Hashable};
-use interchange::json::JsonEncoder;
+use interchange::json::{self, JsonEncoder};
+use interchange::protobuf::encode::ProtobufEncoder;
 use itertools::Itertools;
 use log::{debug, error, info};
 use ore::metrics::{CounterVecExt, DeleteOnDropCounter, DeleteOnDropGauge, GaugeVecExt};
@@ -39,14 +40,15 @@ use timely::scheduling::Activator;
 
 use dataflow_types::{
     KafkaSinkConnector, KafkaSinkConsistencyConnector, PublishedSchemaInfo, SinkAsOf, SinkDesc,
+    SinkEnvelope,
 };
 use expr::GlobalId;
-use interchange::avro::{self, AvroEncoder, AvroSchemaGenerator};
+use interchange::avro::{self, cdc_v2, AvroEncoder, AvroSchemaGenerator};
 use interchange::encode::Encode;
 use ore::cast::CastFrom;
 use repr::{Datum, Diff, RelationDesc, Row, Timestamp};
 
-use super::{KafkaBaseMetrics, SinkBaseMetrics};
+use super::{report_sink_error, KafkaBaseMetrics, SinkBaseMetrics};
 use crate::render::sinks::SinkRender;
 use crate::render::RenderState;
 use crate::source::timestamp::TimestampBindingRc;
@@ -145,9 +147,11 @@ where
                 .clone()
                 .map(|(desc, _indices)| desc),
             self.value_desc.clone(),
+            sink.envelope,
             sink.as_of.clone(),
             source_ts_histories,
             shared_frontier.clone(),
+            Arc::clone(&render_state.sink_error_buffer),
             &metrics.kafka,
         );
 
@@ -199,15 +203,24 @@ impl SinkMetrics {
 }
 
 pub struct SinkProducerContext {
+    sink_id: GlobalId,
     metrics: Arc<SinkMetrics>,
     shutdown_flag: Arc<AtomicBool>,
+    sink_errors: Arc<Mutex<Vec<(GlobalId, String)>>>,
 }
 
 impl SinkProducerContext {
-    pub fn new(metrics: Arc<SinkMetrics>, shutdown_flag: Arc<AtomicBool>) -> Self {
+    pub fn new(
+        sink_id: GlobalId,
+        metrics: Arc<SinkMetrics>,
+        shutdown_flag: Arc<AtomicBool>,
+        sink_errors: Arc<Mutex<Vec<(GlobalId, String)>>>,
+    ) -> Self {
         SinkProducerContext {
+            sink_id,
             metrics,
             shutdown_flag,
+            sink_errors,
         }
     }
 }
@@ -221,10 +234,10 @@ impl ProducerContext for SinkProducerContext {
             Ok(_) => (),
             Err((e, msg)) => {
                 self.metrics.message_delivery_errors_counter.inc();
-                error!(
-                    "received error while writing to kafka sink topic {}: {}",
-                    msg.topic(),
-                    e
+                report_sink_error(
+                    &self.sink_errors,
+                    self.sink_id,
+                    format!("error writing to kafka topic {}: {}", msg.topic(), e),
                 );
                 self.shutdown_flag.store(true, Ordering::SeqCst);
             }
@@ -244,9 +257,12 @@ impl Drop for KafkaSinkToken {
 
 struct KafkaSinkState {
     name: String,
+    sink_id: GlobalId,
     topic: String,
     topic_prefix: String,
     shutdown_flag: Arc<AtomicBool>,
+    /// Errors that stopped the sink, which are reported to the coordinator.
+    sink_errors: Arc<Mutex<Vec<(GlobalId, String)>>>,
     metrics: Arc<SinkMetrics>,
     producer: ThreadedProducer<SinkProducerContext>,
     activator: timely::scheduling::Activator,
@@ -261,6 +277,13 @@ struct KafkaSinkState {
     /// Timestamp of the latest `END` record that was written out to Kafka.
     latest_progress_ts: Timestamp,
 
+    /// Encoder for the progress statements of CDCv2 sinks, or `None` for
+    /// sinks with other envelopes.
+    cdcv2_encoder: Option<cdc_v2::ConfluentEncoder>,
+    /// The upper bound of the latest CDCv2 progress statement, which is the
+    /// lower bound of the next one.
+    cdcv2_lower: Timestamp,
+
     /// Write frontier of this sink.
     ///
     /// The write frontier potentially blocks compaction of timestamp bindings
@@ -281,10 +304,12 @@ impl KafkaSinkState {
         shutdown_flag: Arc<AtomicBool>,
         activator: Activator,
         latest_progress_ts: Timestamp,
+        cdcv2_encoder: Option<cdc_v2::ConfluentEncoder>,
         write_frontier: Rc<RefCell<Antichain<Timestamp>>>,
+        sink_errors: Arc<Mutex<Vec<(GlobalId, String)>>>,
         metrics: &KafkaBaseMetrics,
     ) -> Self {
-        let config = Self::create_producer_config(&connector);
+        let config = Self::create_producer_config(&connector, sink_id);
 
         let metrics = Arc::new(SinkMetrics::new(
             metrics,
@@ -295,32 +320,38 @@ impl KafkaSinkState {
 
         let producer = config
             .create_with_context::<_, ThreadedProducer<_>>(SinkProducerContext::new(
+                *sink_id,
                 Arc::clone(&metrics),
                 Arc::clone(&shutdown_flag),
+                Arc::clone(&sink_errors),
             ))
             .expect("creating kafka producer for Kafka sink failed");
 
         KafkaSinkState {
             name: sink_name,
+            sink_id: *sink_id,
             topic: connector.topic,
             topic_prefix: connector.topic_prefix,
             shutdown_flag,
+            sink_errors,
             metrics,
             producer,
             activator,
             txn_timeout: Duration::from_secs(5),
-            transactional: connector.exactly_once,
+            transactional: connector.transactional,
             consistency: connector.consistency,
             fuel: connector.fuel,
             pending_rows: HashMap::new(),
             ready_rows: VecDeque::new(),
             send_state: SendState::Init,
             latest_progress_ts,
+            cdcv2_encoder,
+            cdcv2_lower: Timestamp::MIN,
             write_frontier,
         }
     }
 
-    fn create_producer_config(connector: &KafkaSinkConnector) -> ClientConfig {
+    fn create_producer_config(connector: &KafkaSinkConnector, sink_id: &GlobalId) -> ClientConfig {
         let mut config = ClientConfig::new();
         config.set("bootstrap.servers", &connector.addrs.to_string());
 
@@ -359,10 +390,12 @@ impl KafkaSinkState {
             }
         }
 
-        if connector.exactly_once {
-            // TODO(aljoscha): this only works for now, once there's an actual
-            // Kafka producer on each worker they would step on each others toes
-            let transactional_id = format!("mz-producer-{}", connector.topic);
+        if connector.transactional {
+            // The transactional ID is stable across restarts of the sink, so
+            // that initializing transactions fences out the producer of any
+            // previous incarnation and aborts its open transaction. Only the
+            // active worker ever initializes transactions.
+            let transactional_id = format!("mz-producer-{}-{}", sink_id, connector.topic);
             config.set("transactional.id", transactional_id);
         }
 
@@ -380,7 +413,7 @@ impl KafkaSinkState {
             &self.name, current_state, ts, e
         );
 
-        match e {
+        let next_state = match &e {
             KafkaError::Transaction(e) => {
                 if e.txn_requires_abort() {
                     SendState::AbortTxn
@@ -391,7 +424,17 @@ impl KafkaSinkState {
                 }
             }
             _ => SendState::Shutdown,
+        };
+        if let SendState::Shutdown = next_state {
+            self.fail(format!("kafka transaction failed: {}", e));
         }
+        next_state
+    }
+
+    /// Stops the sink, reporting `error` as the reason.
+    fn fail(&self, error: String) {
+        report_sink_error(&self.sink_errors, self.sink_id, error);
+        self.shutdown_flag.store(true, Ordering::SeqCst);
     }
 
     fn send<K, P>(&self, record: BaseRecord<K, P>) -> Result<(), bool>
@@ -408,7 +451,7 @@ impl KafkaSinkState {
                 Err(true)
             } else {
                 // We've received an error that is not transient
-                self.shutdown_flag.store(true, Ordering::SeqCst);
+                self.fail(format!("unable to produce message: {}", e));
                 Err(false)
             }
         } else {
@@ -428,13 +471,21 @@ impl KafkaSinkState {
             .as_ref()
             .expect("no consistency information");
 
-        let encoded = avro::encode_debezium_transaction_unchecked(
-            consistency.schema_id,
-            &self.topic_prefix,
-            transaction_id,
-            status,
-            message_count,
-        );
+        let encoded = match consistency.schema_id {
+            Some(schema_id) => avro::encode_debezium_transaction_unchecked(
+                schema_id,
+                &self.topic_prefix,
+                transaction_id,
+                status,
+                message_count,
+            ),
+            None => json::encode_debezium_transaction_unchecked(
+                &self.topic_prefix,
+                transaction_id,
+                status,
+                message_count,
+            ),
+        };
 
         let record = BaseRecord::to(&consistency.topic)
             .payload(&encoded)
@@ -443,6 +494,70 @@ impl KafkaSinkState {
         self.send(record)
     }
 
+    /// Initializes the producer's transactions, if the sink is transactional
+    /// and they have not been initialized yet.
+    fn maybe_init_transactions(&mut self) -> Result<(), KafkaError> {
+        if let SendState::Init = self.send_state {
+            if self.transactional {
+                self.producer.init_transactions(self.txn_timeout)?;
+            }
+            self.send_state = SendState::BeginTxn;
+        }
+        Ok(())
+    }
+
+    /// Sends the records produced by `send` in a transaction of their own, if
+    /// the sink is transactional.
+    ///
+    /// The transaction is aborted if any of the records cannot be sent, so
+    /// that it does not block later transactions.
+    fn send_in_transaction<F>(&mut self, what: &str, send: F) -> Result<(), anyhow::Error>
+    where
+        F: FnOnce(&mut Self) -> Result<(), bool>,
+    {
+        if !self.transactional {
+            return send(self).map_err(|_retry| anyhow::anyhow!("Error sending {}.", what));
+        }
+
+        self.maybe_init_transactions()?;
+        self.producer.begin_transaction()?;
+        if send(self).is_err() {
+            self.producer.abort_transaction(self.txn_timeout)?;
+            anyhow::bail!("Error sending {}.", what);
+        }
+        if let Err(e) = self.producer.commit_transaction(self.txn_timeout) {
+            if let KafkaError::Transaction(txn_error) = &e {
+                if txn_error.txn_requires_abort() {
+                    self.producer.abort_transaction(self.txn_timeout)?;
+                }
+            }
+            return Err(e.into());
+        }
+        Ok(())
+    }
+
+    /// Sends a CDCv2 progress statement that covers the times from the upper
+    /// bound of the previous progress statement up to `upper`.
+    ///
+    /// An `upper` of `None` signals that no further updates will be sent.
+    fn send_cdcv2_progress(
+        &mut self,
+        upper: Option<Timestamp>,
+        counts: &[(i64, i64)],
+    ) -> Result<(), bool> {
+        let encoder = self.cdcv2_encoder.as_ref().expect("no CDCv2 encoder");
+
+        let lower = [self.cdcv2_lower as i64];
+        let upper_bound: Vec<_> = upper.iter().map(|upper| *upper as i64).collect();
+        let encoded = encoder.encode_progress_unchecked(&lower, &upper_bound, counts);
+
+        let record = BaseRecord::<(), _>::to(&self.topic).payload(&encoded);
+        self.send(record)?;
+
+        self.cdcv2_lower = upper.unwrap_or(Timestamp::MAX);
+        Ok(())
+    }
+
     /// Asserts that the write frontier has not yet advanced beyond `t`.
     fn assert_progress(&self, ts: &Timestamp) {
         assert!(self.write_frontier.borrow().less_equal(ts));
@@ -491,6 +606,20 @@ impl KafkaSinkState {
 
         let min_frontier = input_frontier.into_iter().chain(min_pending_ts).min();
 
+        // tell CDCv2 consumers that there are no further updates before the
+        // frontier, or no further updates at all if the frontier is empty.
+        if self.cdcv2_encoder.is_some() && self.cdcv2_lower != Timestamp::MAX {
+            let advanced = match min_frontier {
+                Some(min_frontier) => min_frontier > self.cdcv2_lower,
+                None => true,
+            };
+            if advanced {
+                self.send_in_transaction("CDCv2 progress statement", |s| {
+                    s.send_cdcv2_progress(min_frontier, &[])
+                })?;
+            }
+        }
+
         if let Some(min_frontier) = min_frontier {
             // a frontier of `t` means we still might receive updates with `t`.
             // The progress frontier we emit is a strict frontier, so subtract `1`.
@@ -499,16 +628,9 @@ impl KafkaSinkState {
             if min_frontier > self.latest_progress_ts {
                 // record the write frontier in the consistency topic.
                 if self.consistency.is_some() {
-                    if self.transactional {
-                        self.producer.begin_transaction()?
-                    }
-
-                    self.send_consistency_record(&min_frontier.to_string(), "END", None)
-                        .map_err(|_e| anyhow::anyhow!("Error sending write frontier update."))?;
-
-                    if self.transactional {
-                        self.producer.commit_transaction(self.txn_timeout)?
-                    }
+                    self.send_in_transaction("write frontier update", |s| {
+                        s.send_consistency_record(&min_frontier.to_string(), "END", None)
+                    })?;
                 }
                 self.latest_progress_ts = min_frontier;
             }
@@ -569,9 +691,11 @@ fn kafka<G>(
     connector: KafkaSinkConnector,
     key_desc: Option<RelationDesc>,
     value_desc: RelationDesc,
+    envelope: Option<SinkEnvelope>,
     as_of: SinkAsOf,
     source_timestamp_histories: Vec<TimestampBindingRc>,
     write_frontier: Rc<RefCell<Antichain<Timestamp>>>,
+    sink_errors: Arc<Mutex<Vec<(GlobalId, String)>>>,
     metrics: &KafkaBaseMetrics,
 ) -> Box<dyn Any>
 where
@@ -581,14 +705,70 @@ where
 
     let stream = &collection.inner;
 
+    let mut cdcv2_encoder = None;
     let encoded_stream = match connector.published_schema_info {
+        Some(PublishedSchemaInfo {
+            value_schema_id, ..
+        }) if envelope == Some(SinkEnvelope::CdcV2) => {
+            // The sink itself sends the progress statements, interleaved with
+            // the encoded updates.
+            cdcv2_encoder = Some(cdc_v2::ConfluentEncoder::new(
+                value_desc.clone(),
+                connector.enum_labels.clone(),
+                value_schema_id,
+            ));
+            let encoder = cdc_v2::ConfluentEncoder::new(
+                value_desc,
+                connector.enum_labels.clone(),
+                value_schema_id,
+            );
+            encode_stream(
+                stream,
+                as_of.clone(),
+                None,
+                encoder,
+                connector.fuel,
+                name.clone(),
+            )
+        }
         Some(PublishedSchemaInfo {
             key_schema_id,
             value_schema_id,
         }) => {
             let schema_generator =
                 AvroSchemaGenerator::new(key_desc, value_desc, connector.consistency.is_some());
-            let encoder = AvroEncoder::new(schema_generator, key_schema_id, value_schema_id);
+            let encoder = AvroEncoder::new(
+                schema_generator,
+                key_schema_id,
+                value_schema_id,
+                connector.enum_labels.clone(),
+            );
+            encode_stream(
+                stream,
+                as_of.clone(),
+                connector
+                    .consistency
+                    .clone()
+                    .and_then(|consistency| consistency.gate_ts),
+                encoder,
+                connector.fuel,
+                name.clone(),
+            )
+        }
+        None if connector.protobuf_schema.is_some() => {
+            let schema = connector
+                .protobuf_schema
+                .as_ref()
+                .expect("known to be present");
+            let encoder = ProtobufEncoder::new(
+                key_desc,
+                value_desc,
+                connector.consistency.is_some(),
+                &schema.message_name,
+                &schema.descriptors,
+                connector.enum_labels.clone(),
+            )
+            .expect("protobuf schema validated at plan time");
             encode_stream(
                 stream,
                 as_of.clone(),
@@ -602,7 +782,12 @@ where
             )
         }
         None => {
-            let encoder = JsonEncoder::new(key_desc, value_desc, connector.consistency.is_some());
+            let encoder = JsonEncoder::new(
+                key_desc,
+                value_desc,
+                connector.consistency.is_some(),
+                connector.enum_labels.clone(),
+            );
             encode_stream(
                 stream,
                 as_of.clone(),
@@ -624,7 +809,9 @@ where
         connector,
         as_of,
         source_timestamp_histories,
+        cdcv2_encoder,
         write_frontier,
+        sink_errors,
         metrics,
     )
 }
@@ -640,6 +827,10 @@ where
 ///
 /// Updates that are not beyond the given [`SinkAsOf`] and/or the `gate_ts` in
 /// [`KafkaSinkConnector`] will be discarded without producing them.
+///
+/// If a `cdcv2_encoder` is given, the operator follows the updates of each
+/// timestamp with a CDCv2 progress statement, and sends further progress
+/// statements as the input frontier advances.
 pub fn produce_to_kafka<G>(
     stream: Stream<G, ((Option<Vec<u8>>, Option<Vec<u8>>), Timestamp, Diff)>,
     id: GlobalId,
@@ -647,7 +838,9 @@ pub fn produce_to_kafka<G>(
     connector: KafkaSinkConnector,
     as_of: SinkAsOf,
     source_timestamp_histories: Vec<TimestampBindingRc>,
+    cdcv2_encoder: Option<cdc_v2::ConfluentEncoder>,
     write_frontier: Rc<RefCell<Antichain<Timestamp>>>,
+    sink_errors: Arc<Mutex<Vec<(GlobalId, String)>>>,
     metrics: &KafkaBaseMetrics,
 ) -> Box<dyn Any>
 where
@@ -676,14 +869,17 @@ where
         Arc::clone(&shutdown_flag),
         activator,
         latest_progress_ts,
+        cdcv2_encoder,
         write_frontier,
+        sink_errors,
         metrics,
     );
 
-    // Keep track of whether this operator/worker ever received updates. We
-    // use this to control who should send continuous END progress records
-    // to Kafka below.
-    let mut is_active_worker = false;
+    // We want exactly one worker to send all the data to the sink topic, and
+    // only that worker sends continuous END progress records to Kafka below.
+    let hashed_id = id.hashed();
+    let is_active_worker =
+        (usize::cast_from(hashed_id) % stream.scope().peers()) == stream.scope().index();
 
     let mut vector = Vec::new();
 
@@ -703,7 +899,6 @@ where
 
         // Queue all pending rows waiting to be sent to kafka
         input.for_each(|_, rows| {
-            is_active_worker = true;
             rows.swap(&mut vector);
             for ((key, value), time, diff) in vector.drain(..) {
                 let should_emit = if as_of.strict {
@@ -725,7 +920,12 @@ where
                     continue;
                 }
 
-                assert!(diff >= 0, "can't sink negative multiplicities");
+                if diff < 0 {
+                    // Only sinks with ENVELOPE NONE pass on retractions, which
+                    // they have no way to express.
+                    s.fail("cannot sink a retraction with ENVELOPE NONE".into());
+                    return;
+                }
                 if diff == 0 {
                     // Explicitly refuse to send no-op records
                     continue;
@@ -742,6 +942,11 @@ where
             }
         });
 
+        if s.shutdown_flag.load(Ordering::SeqCst) {
+            info!("shutting down sink: {}", &s.name);
+            return false;
+        }
+
         // Figure out the durablity frontier for all sources we depent on
         let mut durability_frontier = Antichain::new();
 
@@ -862,6 +1067,15 @@ where
                                 return retry;
                             }
                         }
+                        if s.cdcv2_encoder.is_some() {
+                            let ts = *ts;
+                            let result =
+                                s.send_cdcv2_progress(Some(ts + 1), &[(ts as i64, total_count)]);
+
+                            if let Err(retry) = result {
+                                return retry;
+                            }
+                        }
                         SendState::CommitTxn
                     }
                     SendState::CommitTxn => {
@@ -898,7 +1112,7 @@ where
                         }
                     }
                     SendState::Shutdown => {
-                        s.shutdown_flag.store(false, Ordering::SeqCst);
+                        s.shutdown_flag.store(true, Ordering::SeqCst);
                         break;
                     }
                 };
@@ -917,15 +1131,13 @@ where
         // While we still have ready rows that we're emitting, hold the write
         // frontier at the previous time.
         //
-        // Only ever emit progress records if this operator/worker received
-        // updates. Only on worker receives all the updates and we don't want
-        // the other workers to also emit END records.
+        // Only ever emit progress records from the active worker. Only one
+        // worker receives all the updates and we don't want the other workers
+        // to also emit END records.
         if s.ready_rows.is_empty() && is_active_worker {
             if let Err(e) = s.maybe_emit_progress(input.frontier.frontier()) {
-                // This can happen when the producer has not been
-                // initialized yet. This also means, that we only start
-                // emitting continuous updates once some real data
-                // has been emitted.
+                // Progress updates are retried when the operator is next
+                // scheduled.
                 debug!("Error writing out progress update: {}", e);
             }
         }
@@ -959,8 +1171,6 @@ where
         false
     };
 
-    // We want exactly one worker to send all the data to the sink topic.
-    let hashed_id = id.hashed();
     let mut input = builder.new_input(&stream, Exchange::new(move |_| hashed_id));
 
     builder.build_reschedule(|_capabilities| {
@@ -7,10 +7,25 @@
 // the Business Source License, use of this software will be governed
 // by the Apache License, Version 2.0.
 
+use std::sync::Mutex;
+
+use log::error;
+
+use expr::GlobalId;
+
 mod avro_ocf;
+mod file;
 mod kafka;
 mod metrics;
 mod tail;
 
+pub use file::read_file_sink_upper;
 pub(crate) use metrics::KafkaBaseMetrics;
 pub use metrics::SinkBaseMetrics;
+
+/// Reports an error that stops the sink `id` from writing any further
+/// updates, so that the coordinator can record it in `mz_sink_errors`.
+fn report_sink_error(sink_errors: &Mutex<Vec<(GlobalId, String)>>, id: GlobalId, error: String) {
+    error!("sink {} failed: {}", id, error);
+    sink_errors.lock().expect("lock poisoned").push((id, error));
+}
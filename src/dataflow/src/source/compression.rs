@@ -0,0 +1,231 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Decompression for file and S3 sources.
+
+use std::cmp;
+use std::future::Future;
+use std::io::{self, Read};
+use std::path::Path;
+use std::pin::Pin;
+
+use bytes::{Buf, Bytes};
+use flate2::read::MultiGzDecoder;
+use futures::stream::{self, StreamExt};
+use tokio::io::AsyncRead;
+use tokio::sync::mpsc;
+use tokio::task;
+use tokio_util::io::{ReaderStream, StreamReader};
+
+use dataflow_types::Compression;
+use mz_avro::{AvroRead, Skip};
+
+/// The number of chunks buffered between a blocking decoder and its async
+/// reader and writer.
+const BLOCKING_DECODER_CHANNEL_CAPACITY: usize = 16;
+
+/// The size of the chunks produced by a blocking decoder.
+const BLOCKING_DECODER_CHUNK_SIZE: usize = 8 * 1024;
+
+/// Detects the compression of a file or object from its leading bytes,
+/// falling back to the extension of its `name` if the leading bytes are not
+/// recognized.
+///
+/// Never returns [`Compression::Auto`].
+pub fn detect(prefix: &[u8], name: &str) -> Compression {
+    const MAGIC_BYTES: &[(&[u8], Compression)] = &[
+        (&[0x1f, 0x8b], Compression::Gzip),
+        (&[0x28, 0xb5, 0x2f, 0xfd], Compression::Zstd),
+        (b"\xff\x06\x00\x00sNaPpY", Compression::Snappy),
+        (&[0x04, 0x22, 0x4d, 0x18], Compression::Lz4),
+    ];
+
+    for (magic, compression) in MAGIC_BYTES {
+        if prefix.starts_with(magic) {
+            return *compression;
+        }
+    }
+    match Path::new(name).extension().and_then(|ext| ext.to_str()) {
+        Some("gz") => Compression::Gzip,
+        Some("zst") => Compression::Zstd,
+        Some("sz") => Compression::Snappy,
+        Some("lz4") => Compression::Lz4,
+        _ => Compression::None,
+    }
+}
+
+/// Wraps `reader` in a blocking decoder for `compression`.
+///
+/// `compression` must not be [`Compression::Auto`]; use [`detect`] to resolve
+/// it first.
+pub fn blocking_decoder<R>(
+    compression: Compression,
+    reader: R,
+) -> Result<Box<dyn AvroRead + Send>, io::Error>
+where
+    R: Read + Send + 'static,
+{
+    Ok(match compression {
+        Compression::Gzip => Box::new(MultiGzDecoder::new(reader)),
+        Compression::Zstd => Box::new(SkipByReading(zstd::stream::read::Decoder::new(reader)?)),
+        Compression::Snappy => Box::new(SkipByReading(snap::read::FrameDecoder::new(reader))),
+        Compression::Lz4 => Box::new(SkipByReading(lz4_flex::frame::FrameDecoder::new(reader))),
+        Compression::None => Box::new(SkipByReading(reader)),
+        Compression::Auto => unreachable!("compression must be detected before decoding"),
+    })
+}
+
+/// Decompresses `reader` with `decoder`, a blocking decoder, and passes the
+/// decompressed stream to `consume`.
+///
+/// The decoder runs on a blocking thread. Compressed bytes are streamed to it
+/// from `reader`, and decompressed bytes are streamed back, so that neither is
+/// ever buffered in full.
+pub async fn with_blocking_decoder<R, D, F, Fut>(
+    reader: R,
+    decoder: fn(ChannelReader) -> D,
+    consume: F,
+) -> Fut::Output
+where
+    R: AsyncRead + Unpin,
+    D: Read + 'static,
+    F: FnOnce(Pin<Box<dyn AsyncRead + Send>>) -> Fut,
+    Fut: Future,
+{
+    let (compressed_tx, compressed_rx) = mpsc::channel(BLOCKING_DECODER_CHANNEL_CAPACITY);
+    let (decompressed_tx, decompressed_rx) = mpsc::channel(BLOCKING_DECODER_CHANNEL_CAPACITY);
+
+    task::spawn_blocking(move || {
+        let mut decoder = decoder(ChannelReader {
+            rx: compressed_rx,
+            chunk: Bytes::new(),
+        });
+        loop {
+            let mut buf = vec![0; BLOCKING_DECODER_CHUNK_SIZE];
+            let chunk = match decoder.read(&mut buf) {
+                Ok(0) => break,
+                Ok(n) => {
+                    buf.truncate(n);
+                    Ok(Bytes::from(buf))
+                }
+                Err(e) => Err(e),
+            };
+            let failed = chunk.is_err();
+            // If the receiver has hung up, the consumer is no longer
+            // interested in the decompressed stream.
+            if decompressed_tx.blocking_send(chunk).is_err() || failed {
+                break;
+            }
+        }
+    });
+
+    let produce = async move {
+        let mut compressed = ReaderStream::new(reader);
+        while let Some(chunk) = compressed.next().await {
+            let failed = chunk.is_err();
+            if compressed_tx.send(chunk).await.is_err() || failed {
+                break;
+            }
+        }
+    };
+
+    let decompressed = StreamReader::new(stream::unfold(decompressed_rx, |mut rx| async move {
+        rx.recv().await.map(|chunk| (chunk, rx))
+    }));
+
+    let ((), output) = futures::join!(produce, consume(Box::pin(decompressed)));
+    output
+}
+
+/// A blocking reader over the chunks sent to a [`with_blocking_decoder`]
+/// decoder.
+pub struct ChannelReader {
+    rx: mpsc::Receiver<Result<Bytes, io::Error>>,
+    chunk: Bytes,
+}
+
+impl Read for ChannelReader {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        while self.chunk.is_empty() {
+            match self.rx.blocking_recv() {
+                Some(chunk) => self.chunk = chunk?,
+                None => return Ok(0),
+            }
+        }
+        let n = cmp::min(buf.len(), self.chunk.len());
+        buf[..n].copy_from_slice(&self.chunk[..n]);
+        self.chunk.advance(n);
+        Ok(n)
+    }
+}
+
+/// Adapts a reader that cannot seek to [`AvroRead`], by skipping bytes via
+/// reading and discarding them.
+struct SkipByReading<R>(R);
+
+impl<R: Read> Read for SkipByReading<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        self.0.read(buf)
+    }
+}
+
+impl<R: Read> Skip for SkipByReading<R> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect() {
+        let cases: &[(&[u8], &str, Compression)] = &[
+            (&[0x1f, 0x8b, 0x08], "data", Compression::Gzip),
+            (&[0x28, 0xb5, 0x2f, 0xfd, 0x00], "data", Compression::Zstd),
+            (b"\xff\x06\x00\x00sNaPpY", "data", Compression::Snappy),
+            (&[0x04, 0x22, 0x4d, 0x18, 0x60], "data", Compression::Lz4),
+            (b"a,b,c\n", "data.csv", Compression::None),
+            // The leading bytes take precedence over the extension.
+            (&[0x1f, 0x8b, 0x08], "data.zst", Compression::Gzip),
+            (b"", "data.gz", Compression::Gzip),
+            (b"", "data.csv.zst", Compression::Zstd),
+            (b"", "data.sz", Compression::Snappy),
+            (b"", "data.lz4", Compression::Lz4),
+            (b"", "data", Compression::None),
+        ];
+        for (prefix, name, expected) in cases {
+            assert_eq!(detect(prefix, name), *expected, "{:?} {}", prefix, name);
+        }
+    }
+
+    #[test]
+    fn test_blocking_decoder() {
+        let data = b"a,b,c\n1,2,3\n".repeat(100);
+        let compressed: &[(Compression, Vec<u8>)] = &[
+            (Compression::Zstd, zstd::encode_all(&data[..], 0).unwrap()),
+            (Compression::Snappy, {
+                let mut encoder = snap::write::FrameEncoder::new(vec![]);
+                io::Write::write_all(&mut encoder, &data).unwrap();
+                encoder.into_inner().unwrap()
+            }),
+            (Compression::Lz4, {
+                let mut encoder = lz4_flex::frame::FrameEncoder::new(vec![]);
+                io::Write::write_all(&mut encoder, &data).unwrap();
+                encoder.finish().unwrap()
+            }),
+            (Compression::None, data.clone()),
+        ];
+        for (compression, bytes) in compressed {
+            assert_eq!(detect(bytes, ""), *compression);
+            let mut decoder =
+                blocking_decoder(*compression, io::Cursor::new(bytes.clone())).unwrap();
+            let mut out = vec![];
+            decoder.read_to_end(&mut out).unwrap();
+            assert_eq!(out, data, "{:?}", compression);
+        }
+    }
+}
@@ -7,18 +7,17 @@
 // the Business Source License, use of this software will be governed
 // by the Apache License, Version 2.0.
 
-use std::io::{self, BufRead, Read};
+use std::io::{self, BufRead, BufReader, Read};
 use std::path::PathBuf;
 use std::str::FromStr;
 use std::sync::mpsc::{self, Receiver, TryRecvError};
 use std::thread;
 
 use anyhow::{Context, Error};
-use flate2::read::MultiGzDecoder;
 #[cfg(target_os = "linux")]
 use inotify::{EventMask, Inotify, WatchMask};
 use log::error;
-use repr::MessagePayload;
+use repr::{MessagePayload, Row};
 use timely::scheduling::SyncActivator;
 
 use dataflow_types::{
@@ -31,6 +30,7 @@ use mz_avro::BlockIter;
 use mz_avro::{AvroRead, Schema, Skip};
 
 use crate::logging::materialized::Logger;
+use crate::source::compression;
 use crate::source::{NextMessage, SourceMessage, SourceReader};
 
 use super::metrics::SourceBaseMetrics;
@@ -173,6 +173,7 @@ impl SourceReader for FileSourceReader {
                     upstream_time_millis: None,
                     key: None,
                     payload: Some(record),
+                    metadata: Row::default(),
                 };
                 Ok(NextMessage::Ready(message))
             }
@@ -225,9 +226,33 @@ pub fn read_file_task<Ctor, I, Err>(
         }
     };
 
-    let file_stream: Box<dyn AvroRead + Send> = match compression {
-        Compression::Gzip => Box::new(MultiGzDecoder::new(file_stream)),
-        Compression::None => Box::new(file_stream),
+    let file_stream = match compression {
+        Compression::None => Ok(file_stream),
+        Compression::Auto => {
+            let mut file_stream = BufReader::new(file_stream);
+            match file_stream.fill_buf() {
+                Ok(prefix) => {
+                    let compression = compression::detect(prefix, &path.to_string_lossy());
+                    compression::blocking_decoder(compression, file_stream)
+                }
+                Err(e) => Err(e),
+            }
+        }
+        _ => compression::blocking_decoder(compression, file_stream),
+    };
+    let file_stream = match file_stream.with_context(|| {
+        format!(
+            "file source: unable to decompress file at path {}",
+            path.to_string_lossy(),
+        )
+    }) {
+        Ok(f) => f,
+        Err(err) => {
+            // If we fail to send an error, it's likely due to a race condition
+            // with the source being closed.
+            let _ = tx.send(Err(err));
+            return;
+        }
     };
 
     let iter = iter_ctor(file_stream);
@@ -13,17 +13,19 @@ use std::convert::TryInto;
 use std::sync::Arc;
 use std::time::Duration;
 
+use chrono::NaiveDateTime;
 use rdkafka::consumer::base_consumer::PartitionQueue;
 use rdkafka::consumer::{BaseConsumer, Consumer, ConsumerContext};
 use rdkafka::error::KafkaError;
-use rdkafka::message::BorrowedMessage;
+use rdkafka::message::{BorrowedMessage, Headers};
 use rdkafka::topic_partition_list::Offset;
 use rdkafka::{ClientConfig, ClientContext, Message, Statistics, TopicPartitionList};
-use repr::MessagePayload;
+use repr::{Datum, MessagePayload, Row};
 use timely::scheduling::activate::SyncActivator;
 
 use dataflow_types::{
-    ExternalSourceConnector, KafkaOffset, KafkaSourceConnector, SourceDataEncoding,
+    ExternalSourceConnector, KafkaMetadataColumn, KafkaOffset, KafkaSourceConnector,
+    SourceDataEncoding,
 };
 use expr::{PartitionId, SourceInstanceId};
 use kafka_util::KafkaAddrs;
@@ -232,6 +234,8 @@ pub struct KafkaSourceReader {
     logger: Option<Logger>,
     /// Channel to receive Kafka statistics objects from the stats callback
     stats_rx: crossbeam_channel::Receiver<Statistics>,
+    /// Metadata columns to attach to each message
+    include_metadata: Vec<KafkaMetadataColumn>,
 }
 
 impl SourceReader for KafkaSourceReader {
@@ -452,6 +456,7 @@ impl KafkaSourceReader {
             config_options,
             group_id_prefix,
             cluster_id,
+            include_metadata,
             ..
         } = kc;
         let worker_id = worker_id.try_into().unwrap();
@@ -484,6 +489,7 @@ impl KafkaSourceReader {
             start_offsets,
             logger,
             stats_rx,
+            include_metadata,
         }
     }
 
@@ -531,8 +537,11 @@ impl KafkaSourceReader {
             .consumer
             .split_partition_queue(&self.topic_name, partition_id)
             .expect("partition known to be valid");
-        self.partition_consumers
-            .push_front(PartitionConsumer::new(partition_id, partition_queue));
+        self.partition_consumers.push_front(PartitionConsumer::new(
+            partition_id,
+            partition_queue,
+            self.include_metadata.clone(),
+        ));
         assert_eq!(
             self.consumer
                 .assignment()
@@ -679,19 +688,48 @@ fn create_kafka_config(
     kafka_config
 }
 
-impl<'a> From<&BorrowedMessage<'a>> for SourceMessage {
-    fn from(msg: &BorrowedMessage<'a>) -> Self {
-        let kafka_offset = KafkaOffset {
-            offset: msg.offset(),
-        };
-        Self {
-            payload: msg.payload().map(|p| MessagePayload::Data(p.to_vec())),
-            partition: PartitionId::Kafka(msg.partition()),
-            offset: kafka_offset.into(),
-            upstream_time_millis: msg.timestamp().to_millis(),
-            key: msg.key().map(|k| k.to_vec()),
+/// Converts a Kafka message into a [`SourceMessage`], packing the requested
+/// metadata columns in order.
+fn construct_source_message(
+    msg: &BorrowedMessage<'_>,
+    include_metadata: &[KafkaMetadataColumn],
+) -> SourceMessage {
+    let kafka_offset = KafkaOffset {
+        offset: msg.offset(),
+    };
+    let mut metadata = Row::default();
+    for column in include_metadata {
+        match column {
+            KafkaMetadataColumn::Partition => metadata.push(Datum::Int32(msg.partition())),
+            KafkaMetadataColumn::Offset => metadata.push(Datum::Int64(msg.offset())),
+            KafkaMetadataColumn::Timestamp => {
+                let ts = msg.timestamp().to_millis().and_then(|millis| {
+                    NaiveDateTime::from_timestamp_opt(
+                        millis.div_euclid(1_000),
+                        (millis.rem_euclid(1_000) * 1_000_000) as u32,
+                    )
+                });
+                metadata.push(ts.map_or(Datum::Null, Datum::Timestamp));
+            }
+            KafkaMetadataColumn::Headers => metadata.push_list_with(|row| {
+                if let Some(headers) = msg.headers() {
+                    for i in 0..headers.count() {
+                        if let Some((key, value)) = headers.get(i) {
+                            row.push_list(&[Datum::String(key), Datum::Bytes(value)]);
+                        }
+                    }
+                }
+            }),
         }
     }
+    SourceMessage {
+        payload: msg.payload().map(|p| MessagePayload::Data(p.to_vec())),
+        partition: PartitionId::Kafka(msg.partition()),
+        offset: kafka_offset.into(),
+        upstream_time_millis: msg.timestamp().to_millis(),
+        key: msg.key().map(|k| k.to_vec()),
+        metadata,
+    }
 }
 
 /// Wrapper around a partition containing the underlying consumer
@@ -704,16 +742,23 @@ struct PartitionConsumer {
     partition_stats: Option<(String, PartitionStats)>,
     /// Memoized Statistics for brokers
     broker_windows: HashMap<String, BrokerRTTWindow>,
+    /// Metadata columns to attach to each message
+    include_metadata: Vec<KafkaMetadataColumn>,
 }
 
 impl PartitionConsumer {
     /// Creates a new partition consumer from underlying Kafka consumer
-    fn new(pid: i32, partition_queue: PartitionQueue<GlueConsumerContext>) -> Self {
+    fn new(
+        pid: i32,
+        partition_queue: PartitionQueue<GlueConsumerContext>,
+        include_metadata: Vec<KafkaMetadataColumn>,
+    ) -> Self {
         PartitionConsumer {
             pid,
             partition_queue,
             partition_stats: None,
             broker_windows: HashMap::new(),
+            include_metadata,
         }
     }
 
@@ -721,7 +766,7 @@ impl PartitionConsumer {
     fn get_next_message(&mut self) -> Result<Option<SourceMessage>, KafkaError> {
         match self.partition_queue.poll(Duration::from_millis(0)) {
             Some(Ok(msg)) => {
-                let result = SourceMessage::from(&msg);
+                let result = construct_source_message(&msg, &self.include_metadata);
                 assert_eq!(result.partition, PartitionId::Kafka(self.pid));
                 Ok(Some(result))
             }
@@ -16,7 +16,7 @@ use aws_util::kinesis::{get_shard_ids, get_shard_iterator};
 use futures::executor::block_on;
 use log::error;
 use ore::metrics::{DeleteOnDropGauge, GaugeVecExt};
-use repr::MessagePayload;
+use repr::{MessagePayload, Row};
 use rusoto_core::RusotoError;
 use rusoto_kinesis::{GetRecordsError, GetRecordsInput, GetRecordsOutput, Kinesis, KinesisClient};
 use timely::scheduling::SyncActivator;
@@ -224,6 +224,7 @@ impl SourceReader for KinesisSourceReader {
                             upstream_time_millis: None,
                             key: None,
                             payload: Some(MessagePayload::Data(data)),
+                            metadata: Row::default(),
                         };
                         self.buffered_messages.push_back(source_message);
                     }
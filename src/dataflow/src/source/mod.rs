@@ -53,6 +53,7 @@ use crate::logging::materialized::{Logger, MaterializedEvent};
 use crate::operator::StreamExt;
 use crate::source::timestamp::TimestampBindingRc;
 
+mod compression;
 mod file;
 mod kafka;
 mod kinesis;
@@ -130,6 +131,8 @@ where
     pub position: Option<i64>,
     /// The time the record was created in the upstream systsem, as milliseconds since the epoch
     pub upstream_time_millis: Option<i64>,
+    /// Metadata columns requested via `INCLUDE`, to be appended to the decoded value
+    pub metadata: Row,
 }
 
 /// The data that we send from sources to the decode process
@@ -157,6 +160,8 @@ pub struct DecodeResult {
     pub value: Option<Result<Row, DataflowError>>,
     /// The index of the decoded value in the stream
     pub position: Option<i64>,
+    /// Metadata columns requested via `INCLUDE`, to be appended to the decoded value
+    pub metadata: Row,
 }
 
 impl<K, V> SourceOutput<K, V>
@@ -170,12 +175,14 @@ where
         value: V,
         position: Option<i64>,
         upstream_time_millis: Option<i64>,
+        metadata: Row,
     ) -> SourceOutput<K, V> {
         SourceOutput {
             key,
             value,
             position,
             upstream_time_millis,
+            metadata,
         }
     }
 }
@@ -342,6 +349,9 @@ pub struct SourceMessage {
     pub key: Option<Vec<u8>>,
     /// Optional payload
     pub payload: Option<MessagePayload>,
+    /// Metadata columns requested via `INCLUDE`, empty for sources without
+    /// such metadata
+    pub metadata: Row,
 }
 
 impl fmt::Debug for SourceMessage {
@@ -1436,6 +1446,7 @@ where
                 out,
                 Some(offset.offset),
                 message.upstream_time_millis,
+                message.metadata,
             )));
 
             // Update ingestion metrics
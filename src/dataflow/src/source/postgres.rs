@@ -7,7 +7,9 @@
 // the Business Source License, use of this software will be governed
 // by the Apache License, Version 2.0.
 
-use std::convert::TryInto;
+use std::collections::hash_map::Entry;
+use std::collections::{HashMap, HashSet};
+use std::convert::{TryFrom, TryInto};
 use std::error::Error;
 use std::io::{BufReader, Read, Seek, SeekFrom};
 use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
@@ -28,8 +30,10 @@ use tokio_postgres::types::PgLsn;
 use tokio_postgres::SimpleQueryMessage;
 
 use crate::source::{SimpleSource, SourceError, SourceTransaction, Timestamper};
-use dataflow_types::{PostgresSourceConnector, SourceErrorDetails};
-use repr::{Datum, Row};
+use dataflow_types::{
+    PostgresColumnDesc, PostgresSourceConnector, PostgresTableDesc, SourceErrorDetails,
+};
+use repr::{Datum, Diff, Row, RowArena};
 
 lazy_static! {
     /// Postgres epoch is 2000-01-01T00:00:00Z
@@ -43,6 +47,22 @@ pub struct PostgresSourceReader {
     connector: PostgresSourceConnector,
     /// Our cursor into the WAL
     lsn: PgLsn,
+    /// The expected schema of each upstream table, keyed by OID
+    source_tables: HashMap<u32, PostgresTableDesc>,
+    /// The current contents of each upstream table, keyed by OID, if the source handles
+    /// truncation. `Truncate` messages do not carry the removed rows, so we must remember them in
+    /// order to retract them.
+    ///
+    /// This is a second copy of every replicated table, in addition to any index on the source or
+    /// its views, and is held in memory for as long as the source exists.
+    table_contents: Option<HashMap<u32, HashMap<Row, Diff>>>,
+    /// The OIDs of the upstream tables whose schema has changed incompatibly. Their changes are no
+    /// longer replicated, and the source instead produces an error row for each of them (see
+    /// `error_row`).
+    errored_tables: HashSet<u32>,
+    /// The OID and column types of the tables whose rows are decoded into typed values, in the
+    /// order of their columns in the source. `None` if the source represents every row as text.
+    typed_tables: Option<Vec<(u32, Vec<pgrepr::Type>)>>,
 }
 
 trait ErrorExt {
@@ -119,10 +139,90 @@ macro_rules! try_recoverable {
 impl PostgresSourceReader {
     /// Constructs a new instance
     pub fn new(source_name: String, connector: PostgresSourceConnector) -> Self {
+        let source_tables = connector
+            .details
+            .iter()
+            .flat_map(|details| details.tables.iter())
+            .map(|table| (table.oid, table.clone()))
+            .collect();
+        let typed_tables = connector.details.as_ref().map(|details| {
+            details
+                .typed_tables()
+                .map(|(table, types)| {
+                    let types = types.into_iter().map(|ty| ty.pg_type).collect();
+                    (table.oid, types)
+                })
+                .collect()
+        });
+        // Handling truncation keeps a copy of every replicated table in memory, so it is only done
+        // for sources that opt in.
+        let table_contents = if connector.handle_truncate {
+            Some(HashMap::new())
+        } else {
+            None
+        };
         Self {
             source_name,
             connector,
             lsn: 0.into(),
+            source_tables,
+            table_contents,
+            errored_tables: HashSet::new(),
+            typed_tables,
+        }
+    }
+
+    /// Marks the upstream table `rel_id` as errored, because its schema has changed in a way that
+    /// is incompatible with the source, and returns the error row to produce for it.
+    ///
+    /// Sources created before the upstream schema was recorded at purification time may have views
+    /// that do not check for error rows, so for them the error is returned instead, and fails the
+    /// whole source.
+    fn table_error(
+        &mut self,
+        rel_id: u32,
+        err: anyhow::Error,
+    ) -> Result<Option<Row>, anyhow::Error> {
+        if self.connector.details.is_none() {
+            return Err(err);
+        }
+        if !self.errored_tables.insert(rel_id) {
+            return Ok(None);
+        }
+        log::warn!("source {}: {:#}", self.source_name, err);
+        Ok(Some(self.error_row(rel_id)))
+    }
+
+    /// Returns the error row of the upstream table `rel_id`, whose `row_data` and typed columns are
+    /// all null. The views created by `CREATE VIEWS` report an error if they see such a row.
+    fn error_row(&self, rel_id: u32) -> Row {
+        let mut row = Row::default();
+        row.push(Datum::Int32(rel_id as i32));
+        row.push(Datum::Null);
+        for _ in self.typed_tables.iter().flatten() {
+            row.push(Datum::Null);
+        }
+        row
+    }
+
+    /// Records that `diff` copies of `row` were added to the upstream table `rel_id`.
+    ///
+    /// Does nothing if the source does not handle truncation.
+    fn update_contents(&mut self, rel_id: u32, row: Row, diff: Diff) {
+        let table_contents = match &mut self.table_contents {
+            Some(table_contents) => table_contents,
+            None => return,
+        };
+        match table_contents.entry(rel_id).or_default().entry(row) {
+            Entry::Occupied(mut entry) => {
+                *entry.get_mut() += diff;
+                if *entry.get() == 0 {
+                    entry.remove();
+                }
+            }
+            Entry::Vacant(entry) => {
+                entry.insert(diff);
+            }
         }
     }
 
@@ -187,7 +287,52 @@ impl PostgresSourceReader {
             .parse()
             .or_else(|_| Err(anyhow!("invalid lsn"))));
 
+        // Any rows produced by a previous failed attempt have been reverted
+        if let Some(table_contents) = &mut self.table_contents {
+            table_contents.clear();
+        }
+        self.errored_tables.clear();
+
         for info in publication_tables {
+            let columns = info
+                .schema
+                .iter()
+                .map(|c| (c.name.as_str(), c.type_oid, c.type_mod))
+                .collect::<Vec<_>>();
+            let schema_error = match self.source_tables.get(&info.rel_id) {
+                Some(table) => validate_table_schema(table, &columns).err(),
+                // Sources created before the upstream schema was recorded at purification time
+                // expect the schema observed by the initial snapshot.
+                None if self.connector.details.is_none() => {
+                    let table = PostgresTableDesc {
+                        oid: info.rel_id,
+                        namespace: info.namespace.clone(),
+                        name: info.name.clone(),
+                        columns: info
+                            .schema
+                            .iter()
+                            .map(|c| PostgresColumnDesc {
+                                name: c.name.as_str().to_string(),
+                                type_oid: c.type_oid,
+                                type_mod: c.type_mod,
+                                nullable: c.nullable,
+                                primary_key: c.primary_key,
+                            })
+                            .collect(),
+                    };
+                    self.source_tables.insert(info.rel_id, table);
+                    None
+                }
+                None => None,
+            };
+            if let Some(err) = schema_error {
+                if let Some(row) = try_fatal!(self.table_error(info.rel_id, err)) {
+                    try_recoverable!(snapshot_tx.insert(row.clone()).await);
+                    try_fatal!(buffer.write(&try_fatal!(bincode::serialize(&row))).await);
+                }
+                continue;
+            }
+
             // TODO(petrosagg): use a COPY statement here for more efficient network transfer
             let data = client
                 .simple_query(&format!(
@@ -197,15 +342,11 @@ impl PostgresSourceReader {
                 .await?;
             for msg in data {
                 if let SimpleQueryMessage::Row(row) = msg {
-                    let mut mz_row = Row::default();
-                    let rel_id: Datum = (info.rel_id as i32).into();
-                    mz_row.push(rel_id);
-                    mz_row.push_list((0..row.len()).map(|n| {
-                        let a: Datum = row.get(n).into();
-                        a
-                    }));
+                    let values = (0..row.len()).map(|n| row.get(n).map(str::as_bytes));
+                    let mz_row = try_fatal!(self.pack_row(info.rel_id, values));
                     try_recoverable!(snapshot_tx.insert(mz_row.clone()).await);
                     try_fatal!(buffer.write(&try_fatal!(bincode::serialize(&mz_row))).await);
+                    self.update_contents(info.rel_id, mz_row, 1);
                 }
             }
         }
@@ -233,31 +374,94 @@ impl PostgresSourceReader {
     }
 
     /// Converts a Tuple received in the replication stream into a Row instance. The logical
-    /// replication protocol doesn't use the binary encoding for column values so contrary to the
-    /// initial snapshot here we need to parse the textual form of each column.
-    ///
-    /// The `old_tuple` argument can be used as a source of data to use when encountering unchanged
-    /// TOAST values.
-    fn row_from_tuple<'a, T>(&mut self, rel_id: u32, tuple_data: T) -> Result<Row, anyhow::Error>
+    /// replication protocol doesn't use the binary encoding for column values so, like the
+    /// initial snapshot, here we need to parse the textual form of each column.
+    fn row_from_tuple<'a, T>(&self, rel_id: u32, tuple_data: T) -> Result<Row, anyhow::Error>
     where
         T: IntoIterator<Item = &'a TupleData>,
     {
+        let values = tuple_data
+            .into_iter()
+            .map(|val| match val {
+                TupleData::Null => Ok(None),
+                TupleData::UnchangedToast => bail!("Unsupported TOAST value"),
+                TupleData::Text(b) => Ok(Some(&b[..])),
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        self.pack_row(rel_id, values)
+    }
+
+    /// Packs the textual form of the columns of a row of the upstream table `rel_id` into a Row
+    /// instance.
+    ///
+    /// If the table is decoded into typed values, the row is packed into the record column of the
+    /// table, and every other column is null. Otherwise the text of each column is packed into the
+    /// `row_data` list.
+    ///
+    /// Values of columns that were added upstream after the source was created are dropped.
+    fn pack_row<'a, I>(&self, rel_id: u32, values: I) -> Result<Row, anyhow::Error>
+    where
+        I: IntoIterator<Item = Option<&'a [u8]>>,
+    {
+        let column_count = self
+            .source_tables
+            .get(&rel_id)
+            .map_or(usize::MAX, |table| table.columns.len());
+        let values = values.into_iter().take(column_count);
+
         let mut row = Row::default();
+        row.push(Datum::Int32(rel_id as i32));
 
-        let rel_id: Datum = (rel_id as i32).into();
-        row.push(rel_id);
-        row.push_list_with(move |packer| {
-            for val in tuple_data.into_iter() {
-                let datum = match val {
-                    TupleData::Null => Datum::Null,
-                    TupleData::UnchangedToast => bail!("Unsupported TOAST value"),
-                    TupleData::Text(b) => std::str::from_utf8(&b)?.into(),
+        let typed_tables = match &self.typed_tables {
+            Some(typed_tables) => typed_tables,
+            None => {
+                pack_text(&mut row, values)?;
+                return Ok(row);
+            }
+        };
+        let position = match typed_tables.iter().position(|(oid, _)| *oid == rel_id) {
+            Some(position) => position,
+            None => {
+                pack_text(&mut row, values)?;
+                for _ in typed_tables {
+                    row.push(Datum::Null);
+                }
+                return Ok(row);
+            }
+        };
+
+        // The row is not represented as text
+        row.push(Datum::Null);
+        for _ in 0..position {
+            row.push(Datum::Null);
+        }
+        let (_, types) = &typed_tables[position];
+        let arena = RowArena::new();
+        row.push_list_with(|packer| {
+            for (i, (value, ty)) in values.zip(types).enumerate() {
+                let datum = match value {
+                    None => Datum::Null,
+                    Some(raw) => match pgrepr::Value::decode_text(ty, raw) {
+                        Ok(value) => value.into_datum(&arena, ty).0,
+                        Err(e) => {
+                            let table = &self.source_tables[&rel_id];
+                            bail!(
+                                "unable to decode column {} of table {}.{}: {}",
+                                table.columns[i].name,
+                                table.namespace,
+                                table.name,
+                                e
+                            );
+                        }
+                    },
                 };
                 packer.push(datum);
             }
             Ok(())
         })?;
-
+        for _ in position + 1..typed_tables.len() {
+            row.push(Datum::Null);
+        }
         Ok(row)
     }
 
@@ -283,8 +487,8 @@ impl PostgresSourceReader {
         tokio::pin!(stream);
 
         let mut last_keepalive = Instant::now();
-        let mut inserts = vec![];
-        let mut deletes = vec![];
+        // The updates of the current transaction, in the order they were received
+        let mut updates: Vec<(u32, Row, Diff)> = vec![];
         while let Some(item) = stream.try_next().await? {
             use ReplicationMessage::*;
             // The upstream will periodically request keepalive responses by setting the reply field
@@ -318,17 +522,20 @@ impl PostgresSourceReader {
 
                     match xlog_data.data() {
                         Begin(_) => {
-                            if !inserts.is_empty() || !deletes.is_empty() {
+                            if !updates.is_empty() {
                                 return Err(Fatal(anyhow!(
                                     "got BEGIN statement after uncommitted data"
                                 )));
                             }
                         }
+                        Insert(insert) if self.errored_tables.contains(&insert.rel_id()) => {}
+                        Update(update) if self.errored_tables.contains(&update.rel_id()) => {}
+                        Delete(delete) if self.errored_tables.contains(&delete.rel_id()) => {}
                         Insert(insert) => {
                             let rel_id = insert.rel_id();
                             let new_tuple = insert.tuple().tuple_data();
                             let row = try_fatal!(self.row_from_tuple(rel_id, new_tuple));
-                            inserts.push(row);
+                            updates.push((rel_id, row, 1));
                         }
                         Update(update) => {
                             let rel_id = update.rel_id();
@@ -337,7 +544,7 @@ impl PostgresSourceReader {
                                 .ok_or_else(|| anyhow!("full row missing from update")))
                             .tuple_data();
                             let old_row = try_fatal!(self.row_from_tuple(rel_id, old_tuple));
-                            deletes.push(old_row);
+                            updates.push((rel_id, old_row, -1));
 
                             // If the new tuple contains unchanged toast values, reuse the ones
                             // from the old tuple
@@ -351,7 +558,7 @@ impl PostgresSourceReader {
                                     _ => new,
                                 });
                             let new_row = try_fatal!(self.row_from_tuple(rel_id, new_tuple));
-                            inserts.push(new_row);
+                            updates.push((rel_id, new_row, 1));
                         }
                         Delete(delete) => {
                             let rel_id = delete.rel_id();
@@ -360,22 +567,80 @@ impl PostgresSourceReader {
                                 .ok_or_else(|| anyhow!("full row missing from delete")))
                             .tuple_data();
                             let row = try_fatal!(self.row_from_tuple(rel_id, old_tuple));
-                            deletes.push(row);
+                            updates.push((rel_id, row, -1));
                         }
                         Commit(commit) => {
                             self.lsn = commit.end_lsn().into();
 
                             let tx = timestamper.start_tx().await;
 
-                            for row in deletes.drain(..) {
-                                try_fatal!(tx.delete(row).await);
+                            for (rel_id, row, diff) in updates.drain(..) {
+                                if diff > 0 {
+                                    try_fatal!(tx.insert(row.clone()).await);
+                                } else {
+                                    try_fatal!(tx.delete(row.clone()).await);
+                                }
+                                self.update_contents(rel_id, row, diff);
                             }
-                            for row in inserts.drain(..) {
-                                try_fatal!(tx.insert(row).await);
+                        }
+                        Relation(relation) => {
+                            // Postgres sends a relation message before the first change to a
+                            // table in each replication session, and again whenever the schema
+                            // of the table changes.
+                            //
+                            // An incompatible change only affects the changed table, which
+                            // produces an error row rather than failing the whole source.
+                            let rel_id = relation.rel_id();
+                            if let Some(table) = self.source_tables.get(&rel_id) {
+                                let columns = try_fatal!(relation
+                                    .columns()
+                                    .iter()
+                                    .map(|c| Ok((
+                                        c.name()?,
+                                        u32::try_from(c.type_id())?,
+                                        c.type_modifier()
+                                    )))
+                                    .collect::<Result<Vec<_>, anyhow::Error>>());
+                                if let Err(err) = validate_table_schema(table, &columns) {
+                                    if let Some(row) = try_fatal!(self.table_error(rel_id, err)) {
+                                        updates.push((rel_id, row, 1));
+                                    }
+                                }
                             }
                         }
-                        Origin(_) | Relation(_) | Type(_) => (),
-                        Truncate(_) => return Err(Fatal(anyhow!("source table got truncated"))),
+                        Truncate(truncate) => {
+                            // The contents of errored tables are no longer maintained.
+                            let rel_ids = truncate
+                                .rel_ids()
+                                .iter()
+                                .filter(|rel_id| !self.errored_tables.contains(*rel_id));
+                            for &rel_id in rel_ids {
+                                let table_contents = match &self.table_contents {
+                                    Some(table_contents) => table_contents,
+                                    None => {
+                                        return Err(Fatal(anyhow!(
+                                            "source table got truncated; recreate the source \
+                                             WITH (handle_truncate = true) to support TRUNCATE"
+                                        )))
+                                    }
+                                };
+                                // Retract the contents of the table as of this point in the
+                                // transaction.
+                                let mut contents =
+                                    table_contents.get(&rel_id).cloned().unwrap_or_default();
+                                for (_, row, diff) in
+                                    updates.iter().filter(|(id, _, _)| *id == rel_id)
+                                {
+                                    *contents.entry(row.clone()).or_default() += diff;
+                                }
+                                for (row, count) in contents {
+                                    for _ in 0..count {
+                                        updates.push((rel_id, row.clone(), -1));
+                                    }
+                                }
+                            }
+                        }
+                        Origin(_) | Type(_) => (),
                         // The enum is marked as non_exaustive. Better to be conservative here in
                         // case a new message is relevant to the semantics of our source
                         _ => return Err(Fatal(anyhow!("unexpected logical replication message"))),
@@ -457,7 +722,7 @@ impl SimpleSource for PostgresSourceReader {
                 Err(ReplicationError::Fatal(e)) => {
                     return Err(SourceError {
                         source_name: self.source_name,
-                        error: SourceErrorDetails::FileIO(e.to_string()),
+                        error: SourceErrorDetails::Other(e.to_string()),
                     })
                 }
                 Ok(_) => unreachable!("replication stream cannot exit without an error"),
@@ -469,3 +734,52 @@ impl SimpleSource for PostgresSourceReader {
         }
     }
 }
+
+/// Packs the textual form of each value into a list.
+fn pack_text<'a, I>(row: &mut Row, values: I) -> Result<(), anyhow::Error>
+where
+    I: IntoIterator<Item = Option<&'a [u8]>>,
+{
+    row.push_list_with(|packer| {
+        for value in values {
+            let datum = match value {
+                None => Datum::Null,
+                Some(b) => std::str::from_utf8(b)?.into(),
+            };
+            packer.push(datum);
+        }
+        Ok(())
+    })
+}
+
+/// Validates that the current schema of an upstream table, given as the name, type OID, and type
+/// modifier of each of its columns, is compatible with the schema the source expects.
+///
+/// Columns added to the end of the table are ignored by the source, so they are compatible. Any
+/// other change to the existing columns is not.
+fn validate_table_schema(
+    table: &PostgresTableDesc,
+    columns: &[(&str, u32, i32)],
+) -> Result<(), anyhow::Error> {
+    for (i, expected) in table.columns.iter().enumerate() {
+        let error = match columns.get(i) {
+            None => "has been dropped",
+            Some((name, _, _)) if *name != expected.name => "has been dropped or renamed",
+            Some((_, type_oid, type_mod))
+                if *type_oid != expected.type_oid || *type_mod != expected.type_mod =>
+            {
+                "has changed type"
+            }
+            Some(_) => continue,
+        };
+        bail!(
+            "source table {}.{} with oid {} has been altered: column {} {}",
+            table.namespace,
+            table.name,
+            table.oid,
+            expected.name,
+            error
+        );
+    }
+    Ok(())
+}
@@ -34,7 +34,7 @@ use std::fmt::Formatter;
 use std::ops::AddAssign;
 
 use anyhow::anyhow;
-use async_compression::tokio::bufread::GzipDecoder;
+use async_compression::tokio::bufread::{GzipDecoder, ZstdDecoder};
 use futures::{FutureExt, StreamExt};
 use globset::GlobMatcher;
 use rusoto_core::RusotoError;
@@ -56,9 +56,10 @@ use dataflow_types::{
 use expr::{PartitionId, SourceInstanceId};
 use metrics::BucketMetrics;
 use notifications::Event;
-use repr::MessagePayload;
+use repr::{MessagePayload, Row};
 
 use crate::logging::materialized::Logger;
+use crate::source::compression;
 use crate::source::{NextMessage, SourceMessage, SourceReader};
 
 use self::metrics::ScanBucketMetrics;
@@ -734,9 +735,11 @@ async fn download_object(
         // source, emit a debug message and trust the user-specified compression
         if let Some(s) = obj.content_encoding.as_deref() {
             match (s, compression) {
+                (_, Compression::Auto) => (),
                 ("gzip", Compression::Gzip) => (),
+                ("zstd", Compression::Zstd) => (),
                 ("identity", Compression::None) => (),
-                ("identity" | "gzip", _) => {
+                ("identity" | "gzip" | "zstd", _) => {
                     log::debug!("object {} has mismatched Content-Encoding: {}", key, s)
                 }
                 _ => log::debug!("object {} has unrecognized Content-Encoding: {}", key, s),
@@ -751,12 +754,16 @@ async fn download_object(
     let mut reader = Box::pin(BufReader::new(retry_reader));
 
     // Check for empty files by filling up the buffer of bufreader and checking if it got any bytes
-    match reader.fill_buf().await {
+    let compression = match reader.fill_buf().await {
         Ok(buf) => {
             if buf.is_empty() {
                 log::trace!("source_id={} empty object {}/{}", source_id, bucket, key);
                 return (DownloadStatus::Ok, Default::default());
             }
+            match compression {
+                Compression::Auto => compression::detect(buf, key),
+                _ => compression,
+            }
         }
         Err(_) => {
             return (
@@ -775,6 +782,26 @@ async fn download_object(
             let decoder = GzipDecoder::new(reader);
             read_object_chunked(source_id, decoder, tx).await
         }
+        Compression::Zstd => {
+            let mut decoder = ZstdDecoder::new(reader);
+            decoder.multiple_members(true);
+            read_object_chunked(source_id, decoder, tx).await
+        }
+        Compression::Snappy => {
+            compression::with_blocking_decoder(reader, snap::read::FrameDecoder::new, |decoder| {
+                read_object_chunked(source_id, decoder, tx)
+            })
+            .await
+        }
+        Compression::Lz4 => {
+            compression::with_blocking_decoder(
+                reader,
+                lz4_flex::frame::FrameDecoder::new,
+                |decoder| read_object_chunked(source_id, decoder, tx),
+            )
+            .await
+        }
+        Compression::Auto => unreachable!("compression was detected above"),
     };
 
     log::debug!(
@@ -952,6 +979,7 @@ impl SourceReader for S3SourceReader {
                     upstream_time_millis: None,
                     key: None,
                     payload: Some(record),
+                    metadata: Row::default(),
                 }))
             }
             Some(Some(Err(e))) => {
@@ -36,10 +36,14 @@ gen_reflect_info_func!(
         MirRelationExpr,
         JoinImplementation,
         EvalError,
+        LagLeadType,
+        WindowFrameUnits,
+        WindowFrameBound,
     ],
     [
         AggregateExpr,
         ColumnOrder,
+        WindowFrame,
         ColumnType,
         RelationType,
         IsNull,
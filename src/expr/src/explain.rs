@@ -24,7 +24,7 @@
 //! printed in contexts where trailing whitespace is unacceptable, like
 //! sqllogictest files.
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::fmt;
 use std::iter;
 
@@ -50,6 +50,8 @@ pub struct ViewExplanation<'a> {
     local_id_chains: HashMap<LocalId, usize>,
     /// Records the local ID that corresponds to a chain ID, if any.
     chain_local_ids: HashMap<usize, LocalId>,
+    /// Records the local IDs that are bound recursively.
+    recursive_local_ids: HashSet<LocalId>,
     /// The ID of the current chain. Incremented while constructing the
     /// `Explanation`.
     chain: usize,
@@ -75,7 +77,11 @@ impl<'a> fmt::Display for ViewExplanation<'a> {
                 }
                 write!(f, "%{} =", node.chain)?;
                 if let Some(local_id) = self.chain_local_ids.get(&node.chain) {
-                    write!(f, " Let {} =", local_id)?;
+                    if self.recursive_local_ids.contains(local_id) {
+                        write!(f, " LetRec {} =", local_id)?;
+                    } else {
+                        write!(f, " Let {} =", local_id)?;
+                    }
                 }
                 writeln!(f)?;
             }
@@ -122,7 +128,11 @@ impl<'a> ViewExplanation<'a> {
                 Union { base, inputs, .. } => {
                     walk_many(iter::once(&**base).chain(inputs), explanation)
                 }
-                Let { id, body, value } => {
+                Let { id, body, value } | LetRec { id, body, value } => {
+                    if let LetRec { .. } = expr {
+                        explanation.recursive_local_ids.insert(*id);
+                    }
+
                     // Similarly the definition of a let goes in its own chain.
                     walk(value, explanation);
                     explanation.chain += 1;
@@ -153,14 +163,18 @@ impl<'a> ViewExplanation<'a> {
         {
             for expr in exprs {
                 // Elide chains that would consist only a of single Get node.
-                if let MirRelationExpr::Get {
-                    id: Id::Local(id), ..
-                } = expr
-                {
-                    explanation.expr_chains.insert(
-                        expr as *const MirRelationExpr,
-                        explanation.local_id_chains[id],
-                    );
+                // References to a recursive binding from within its own
+                // definition do not yet have a chain, and so are not elided.
+                let local_id_chain = match expr {
+                    MirRelationExpr::Get {
+                        id: Id::Local(id), ..
+                    } => explanation.local_id_chains.get(id).copied(),
+                    _ => None,
+                };
+                if let Some(chain) = local_id_chain {
+                    explanation
+                        .expr_chains
+                        .insert(expr as *const MirRelationExpr, chain);
                 } else {
                     walk(expr, explanation);
                     explanation.chain += 1;
@@ -174,6 +188,7 @@ impl<'a> ViewExplanation<'a> {
             expr_chains: HashMap::new(),
             local_id_chains: HashMap::new(),
             chain_local_ids: HashMap::new(),
+            recursive_local_ids: HashSet::new(),
             chain: 0,
         };
         walk(expr, &mut explanation);
@@ -228,7 +243,7 @@ impl<'a> ViewExplanation<'a> {
                 Id::LocalBareSource => writeln!(f, "| Get Bare Source for This Source")?,
             },
             // Lets are annotated on the chain ID that they correspond to.
-            Let { .. } => (),
+            Let { .. } | LetRec { .. } => (),
             Project { outputs, .. } => {
                 writeln!(f, "| Project {}", bracketed("(", ")", Indices(outputs)))?
             }
@@ -33,7 +33,9 @@ pub use linear::{
     plan::{MfpPlan, SafeMfpPlan},
     MapFilterProject,
 };
-pub use relation::func::{AggregateFunc, TableFunc};
+pub use relation::func::{
+    AggregateFunc, LagLeadType, TableFunc, WindowFrame, WindowFrameBound, WindowFrameUnits,
+};
 pub use relation::func::{AnalyzedRegex, CaptureGroupDesc};
 pub use relation::join_input_mapper::JoinInputMapper;
 pub use relation::{
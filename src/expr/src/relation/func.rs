@@ -9,6 +9,7 @@
 
 #![allow(missing_docs)]
 
+use std::convert::TryFrom;
 use std::fmt;
 use std::iter;
 
@@ -19,15 +20,15 @@ use ordered_float::OrderedFloat;
 use regex::Regex;
 use serde::{Deserialize, Serialize};
 
-use lowertest::MzEnumReflect;
+use lowertest::{MzEnumReflect, MzStructReflect};
 use ore::cast::CastFrom;
 use repr::adt::array::ArrayDimension;
 use repr::adt::numeric;
 use repr::adt::regex::Regex as ReprRegex;
-use repr::{ColumnType, Datum, Diff, RelationType, Row, RowArena, ScalarType};
+use repr::{ColumnName, ColumnType, Datum, Diff, RelationType, Row, RowArena, ScalarType};
 
 use crate::relation::{compare_columns, ColumnOrder};
-use crate::scalar::func::jsonb_stringify;
+use crate::scalar::func::{build_regex, jsonb_stringify, regexp_split};
 use crate::EvalError;
 
 // TODO(jamii) be careful about overflow in sum/avg
@@ -511,6 +512,20 @@ fn order_aggregate_datums<'a, I>(
     datums: I,
     order_by: &[ColumnOrder],
 ) -> impl Iterator<Item = Datum<'a>>
+where
+    I: IntoIterator<Item = Datum<'a>>,
+{
+    order_aggregate_datums_with_keys(datums, order_by)
+        .into_iter()
+        .map(|(expr, _order_row)| expr)
+}
+
+// Like `order_aggregate_datums`, but also returns the 2nd through Nth elements
+// of each list, packed into a row, alongside the 1st element.
+fn order_aggregate_datums_with_keys<'a, I>(
+    datums: I,
+    order_by: &[ColumnOrder],
+) -> Vec<(Datum<'a>, Row)>
 where
     I: IntoIterator<Item = Datum<'a>>,
 {
@@ -531,7 +546,7 @@ where
         })
     };
     rows.sort_by(&mut sort_by);
-    rows.into_iter().map(|(expr, _order_row)| expr)
+    rows
 }
 
 fn array_concat<'a, I>(datums: I, temp_storage: &'a RowArena, order_by: &[ColumnOrder]) -> Datum<'a>
@@ -563,6 +578,495 @@ where
     })
 }
 
+// The window functions below all accept `Datum::List`s whose first element is
+// a record of the input row and the function's argument, and whose other
+// elements are columns used by `order_by`. They produce a `Datum::List` of
+// records, each pairing an input row with the value computed for it.
+
+fn unwrap_window_input(d: Datum) -> (Datum, Datum) {
+    let mut row_arg = d.unwrap_list().iter();
+    (row_arg.next().unwrap(), row_arg.next().unwrap())
+}
+
+fn window_output<'a, I>(rows_values: I, temp_storage: &'a RowArena) -> Datum<'a>
+where
+    I: IntoIterator<Item = (Datum<'a>, Datum<'a>)>,
+{
+    temp_storage.make_datum(|packer| {
+        packer.push_list_with(|packer| {
+            for (row, value) in rows_values {
+                packer.push_list(&[row, value]);
+            }
+        });
+    })
+}
+
+fn row_number<'a, I>(datums: I, temp_storage: &'a RowArena, order_by: &[ColumnOrder]) -> Datum<'a>
+where
+    I: IntoIterator<Item = Datum<'a>>,
+{
+    let datums = order_aggregate_datums(datums, order_by);
+    let mut row_number = 0;
+    let rows_values = datums.map(|d| {
+        row_number += 1;
+        let (row, _arg) = unwrap_window_input(d);
+        (row, Datum::Int64(row_number))
+    });
+    window_output(rows_values, temp_storage)
+}
+
+fn rank<'a, I>(
+    datums: I,
+    temp_storage: &'a RowArena,
+    order_by: &[ColumnOrder],
+    dense: bool,
+) -> Datum<'a>
+where
+    I: IntoIterator<Item = Datum<'a>>,
+{
+    let datums = order_aggregate_datums_with_keys(datums, order_by);
+    let mut rows_values = Vec::with_capacity(datums.len());
+    let mut rank = 0;
+    let mut prev_key: Option<&Row> = None;
+    for (position, (d, key)) in datums.iter().enumerate() {
+        // Peers, i.e. rows that are equal according to `order_by`, share a
+        // rank. Ranks skip over peers unless `dense` is set.
+        if prev_key != Some(key) {
+            rank = if dense {
+                rank + 1
+            } else {
+                i64::try_from(position).expect("partition size fits in i64") + 1
+            };
+        }
+        prev_key = Some(key);
+        let (row, _arg) = unwrap_window_input(*d);
+        rows_values.push((row, Datum::Int64(rank)));
+    }
+    window_output(rows_values, temp_storage)
+}
+
+fn lag_or_lead<'a, I>(
+    datums: I,
+    temp_storage: &'a RowArena,
+    order_by: &[ColumnOrder],
+    lag_lead: &LagLeadType,
+) -> Datum<'a>
+where
+    I: IntoIterator<Item = Datum<'a>>,
+{
+    let datums: Vec<_> = order_aggregate_datums(datums, order_by)
+        .map(unwrap_window_input)
+        .collect();
+    // The argument is a record of the value, the offset, and the default.
+    let values: Vec<_> = datums
+        .iter()
+        .map(|(_row, arg)| arg.unwrap_list().iter().next().unwrap())
+        .collect();
+    let rows_values = datums.iter().enumerate().map(|(position, (row, arg))| {
+        let mut arg = arg.unwrap_list().iter();
+        let (_value, offset, default) = (
+            arg.next().unwrap(),
+            arg.next().unwrap(),
+            arg.next().unwrap(),
+        );
+        if offset.is_null() {
+            return (*row, Datum::Null);
+        }
+        let offset = i64::from(offset.unwrap_int32());
+        let position = i64::try_from(position).expect("partition size fits in i64");
+        let target = match lag_lead {
+            LagLeadType::Lag => position.checked_sub(offset),
+            LagLeadType::Lead => position.checked_add(offset),
+        };
+        let value = target
+            .and_then(|target| usize::try_from(target).ok())
+            .and_then(|target| values.get(target))
+            .copied()
+            .unwrap_or(default);
+        (*row, value)
+    });
+    window_output(rows_values, temp_storage)
+}
+
+fn first_last_value<'a, I>(
+    datums: I,
+    temp_storage: &'a RowArena,
+    order_by: &[ColumnOrder],
+    window_frame: &WindowFrame,
+    last: bool,
+) -> Datum<'a>
+where
+    I: IntoIterator<Item = Datum<'a>>,
+{
+    let (datums, keys): (Vec<_>, Vec<_>) = order_aggregate_datums_with_keys(datums, order_by)
+        .into_iter()
+        .map(|(d, key)| (unwrap_window_input(d), key))
+        .unzip();
+    let peers = peer_groups(&keys);
+    let rows_values = (0..datums.len()).map(|position| {
+        let (start, end) = window_frame.bounds(position, &peers);
+        let value = match (start < end, last) {
+            (false, _) => Datum::Null,
+            (true, false) => datums[start].1,
+            (true, true) => datums[end - 1].1,
+        };
+        (datums[position].0, value)
+    });
+    window_output(rows_values, temp_storage)
+}
+
+/// Returns the half-open range of positions of the peers of each row within a
+/// sorted partition, i.e. of the rows whose sort key, given in `keys`, is equal
+/// to its own.
+fn peer_groups(keys: &[Row]) -> Vec<(usize, usize)> {
+    let mut peers = Vec::with_capacity(keys.len());
+    let mut start = 0;
+    for end in 1..=keys.len() {
+        if end == keys.len() || keys[end] != keys[start] {
+            peers.extend((start..end).map(|_| (start, end)));
+            start = end;
+        }
+    }
+    peers
+}
+
+fn window_aggregate<'a, I>(
+    datums: I,
+    temp_storage: &'a RowArena,
+    wrapped_aggregate: &AggregateFunc,
+    order_by: &[ColumnOrder],
+    window_frame: &WindowFrame,
+) -> Datum<'a>
+where
+    I: IntoIterator<Item = Datum<'a>>,
+{
+    let (datums, keys): (Vec<_>, Vec<_>) = order_aggregate_datums_with_keys(datums, order_by)
+        .into_iter()
+        .map(|(d, key)| (unwrap_window_input(d), key))
+        .unzip();
+    let peers = peer_groups(&keys);
+    let args = datums.iter().map(|(_row, arg)| *arg).collect::<Vec<_>>();
+    let mut frame = FrameAggregate::new(wrapped_aggregate, temp_storage);
+    let rows_values = (0..datums.len()).map(|position| {
+        let (start, end) = window_frame.bounds(position, &peers);
+        (datums[position].0, frame.advance(&args, start, end))
+    });
+    window_output(rows_values, temp_storage)
+}
+
+/// The value of an aggregate over a window frame, which is maintained as the
+/// frame moves through a sorted partition rather than being recomputed for
+/// each row.
+///
+/// The frames of successive rows never move backwards, so rows only ever enter
+/// the frame at its end and leave it at its start.
+struct FrameAggregate<'a, 'b> {
+    aggregate: &'b AggregateFunc,
+    temp_storage: &'a RowArena,
+    start: usize,
+    end: usize,
+    state: FrameState<'a>,
+}
+
+enum FrameState<'a> {
+    /// Sums of integers and counts, which rows can both enter and leave.
+    Sum { sum: i128, non_nulls: i64 },
+    /// Aggregates whose value over a frame and an additional row is their
+    /// value over the frame extended by that row. Rows can enter the frame,
+    /// but if any leave it, the value is computed anew.
+    Fold(Datum<'a>),
+    /// Other aggregates, whose value is computed anew for each frame.
+    Eval(Datum<'a>),
+}
+
+impl<'a, 'b> FrameAggregate<'a, 'b> {
+    /// Constructs the value of `aggregate` over an empty frame.
+    fn new(aggregate: &'b AggregateFunc, temp_storage: &'a RowArena) -> FrameAggregate<'a, 'b> {
+        let state = match aggregate {
+            AggregateFunc::SumInt16
+            | AggregateFunc::SumInt32
+            | AggregateFunc::SumInt64
+            | AggregateFunc::Count => FrameState::Sum {
+                sum: 0,
+                non_nulls: 0,
+            },
+            AggregateFunc::MaxNumeric
+            | AggregateFunc::MaxInt16
+            | AggregateFunc::MaxInt32
+            | AggregateFunc::MaxInt64
+            | AggregateFunc::MaxFloat32
+            | AggregateFunc::MaxFloat64
+            | AggregateFunc::MaxBool
+            | AggregateFunc::MaxString
+            | AggregateFunc::MaxDate
+            | AggregateFunc::MaxTimestamp
+            | AggregateFunc::MaxTimestampTz
+            | AggregateFunc::MinNumeric
+            | AggregateFunc::MinInt16
+            | AggregateFunc::MinInt32
+            | AggregateFunc::MinInt64
+            | AggregateFunc::MinFloat32
+            | AggregateFunc::MinFloat64
+            | AggregateFunc::MinBool
+            | AggregateFunc::MinString
+            | AggregateFunc::MinDate
+            | AggregateFunc::MinTimestamp
+            | AggregateFunc::MinTimestampTz
+            | AggregateFunc::SumFloat32
+            | AggregateFunc::SumFloat64
+            | AggregateFunc::SumNumeric
+            | AggregateFunc::Any
+            | AggregateFunc::All => FrameState::Fold(aggregate.eval(iter::empty(), temp_storage)),
+            _ => FrameState::Eval(aggregate.eval(iter::empty(), temp_storage)),
+        };
+        FrameAggregate {
+            aggregate,
+            temp_storage,
+            start: 0,
+            end: 0,
+            state,
+        }
+    }
+
+    /// Moves the frame to the half-open range `start..end` of `args`, and
+    /// returns the value of the aggregate over it.
+    ///
+    /// Neither `start` nor `end` may lie before the current bounds of the
+    /// frame.
+    fn advance(&mut self, args: &[Datum<'a>], start: usize, end: usize) -> Datum<'a> {
+        assert!(self.start <= start && self.end <= end);
+        let aggregate = self.aggregate;
+        let temp_storage = self.temp_storage;
+        match &mut self.state {
+            FrameState::Sum { sum, non_nulls } => {
+                if start >= self.end {
+                    self.end = start;
+                    *sum = 0;
+                    *non_nulls = 0;
+                } else {
+                    for arg in &args[self.start..start] {
+                        if !arg.is_null() {
+                            *sum -= frame_summand(aggregate, *arg);
+                            *non_nulls -= 1;
+                        }
+                    }
+                }
+                for arg in &args[self.end..end] {
+                    if !arg.is_null() {
+                        *sum += frame_summand(aggregate, *arg);
+                        *non_nulls += 1;
+                    }
+                }
+            }
+            FrameState::Fold(value) => {
+                if start != self.start {
+                    self.end = start;
+                    *value = aggregate.eval(iter::empty(), temp_storage);
+                }
+                for arg in &args[self.end..end] {
+                    *value = aggregate.eval([*value, *arg].iter().copied(), temp_storage);
+                }
+            }
+            FrameState::Eval(value) => {
+                if (start, end) != (self.start, self.end) {
+                    *value = aggregate.eval(args[start..end].iter().copied(), temp_storage);
+                }
+            }
+        }
+        self.start = start;
+        self.end = end;
+        match self.state {
+            FrameState::Sum { sum, non_nulls } => match aggregate {
+                AggregateFunc::Count => Datum::from(non_nulls),
+                _ if non_nulls == 0 => Datum::Null,
+                AggregateFunc::SumInt64 => Datum::from(sum),
+                _ => Datum::from(sum as i64),
+            },
+            FrameState::Fold(value) | FrameState::Eval(value) => value,
+        }
+    }
+}
+
+/// Returns the amount by which `arg`, which must not be null, contributes to
+/// the value of `aggregate` in a [`FrameState::Sum`].
+fn frame_summand(aggregate: &AggregateFunc, arg: Datum) -> i128 {
+    match aggregate {
+        AggregateFunc::SumInt16 => i128::from(arg.unwrap_int16()),
+        AggregateFunc::SumInt32 => i128::from(arg.unwrap_int32()),
+        AggregateFunc::SumInt64 => i128::from(arg.unwrap_int64()),
+        AggregateFunc::Count => 0,
+        _ => unreachable!(),
+    }
+}
+
+/// Computes the output type of a window function, given the type of the
+/// function's argument and the type of its input (see the comment above
+/// `unwrap_window_input`).
+fn window_output_type(input_type: ColumnType, value_type: ColumnType) -> ScalarType {
+    let row_type = window_input_types(input_type).0;
+    ScalarType::List {
+        element_type: Box::new(ScalarType::Record {
+            fields: vec![
+                (ColumnName::from("?row?"), row_type),
+                (ColumnName::from("?value?"), value_type),
+            ],
+            custom_oid: None,
+            custom_name: None,
+        }),
+        custom_oid: None,
+    }
+}
+
+/// Extracts the types of the input row and of the argument from the input type
+/// of a window function.
+fn window_input_types(input_type: ColumnType) -> (ColumnType, ColumnType) {
+    match input_type.scalar_type {
+        ScalarType::Record { mut fields, .. } => match fields.swap_remove(0).1.scalar_type {
+            ScalarType::Record { mut fields, .. } => {
+                let arg_type = fields.swap_remove(1).1;
+                let row_type = fields.swap_remove(0).1;
+                (row_type, arg_type)
+            }
+            _ => unreachable!(),
+        },
+        _ => unreachable!(),
+    }
+}
+
+/// Whether a window function computes values from the row `offset` rows
+/// before or after the current row.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize, Hash, MzEnumReflect)]
+pub enum LagLeadType {
+    Lag,
+    Lead,
+}
+
+impl fmt::Display for LagLeadType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            LagLeadType::Lag => f.write_str("lag"),
+            LagLeadType::Lead => f.write_str("lead"),
+        }
+    }
+}
+
+/// Specifies the set of rows, relative to the current row, over which a
+/// window function is evaluated.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize, Hash, MzStructReflect)]
+pub struct WindowFrame {
+    /// Whether the bounds count rows or peer groups.
+    pub units: WindowFrameUnits,
+    /// The first row of the frame.
+    pub start_bound: WindowFrameBound,
+    /// The last row of the frame.
+    pub end_bound: WindowFrameBound,
+}
+
+impl Default for WindowFrame {
+    /// The frame used when none is specified, which spans from the start of
+    /// the partition through the current row and its peers.
+    fn default() -> WindowFrame {
+        WindowFrame {
+            units: WindowFrameUnits::Range,
+            start_bound: WindowFrameBound::UnboundedPreceding,
+            end_bound: WindowFrameBound::CurrentRow,
+        }
+    }
+}
+
+impl WindowFrame {
+    /// Returns the half-open range of positions within a sorted partition that
+    /// make up the frame of the row at `position`. `peers` holds the range of
+    /// the peers of every row of the partition, as computed by `peer_groups`.
+    ///
+    /// The returned range is empty if the start of the frame lies after its
+    /// end.
+    fn bounds(&self, position: usize, peers: &[(usize, usize)]) -> (usize, usize) {
+        let len = peers.len();
+        let (peers_start, peers_end) = peers[position];
+        let start = match &self.start_bound {
+            WindowFrameBound::UnboundedPreceding => 0,
+            WindowFrameBound::OffsetPreceding(n) => position.saturating_sub(usize::cast_from(*n)),
+            WindowFrameBound::CurrentRow => match self.units {
+                WindowFrameUnits::Rows => position,
+                WindowFrameUnits::Range => peers_start,
+            },
+            WindowFrameBound::OffsetFollowing(n) => {
+                position.saturating_add(usize::cast_from(*n)).min(len)
+            }
+            WindowFrameBound::UnboundedFollowing => len,
+        };
+        let end = match &self.end_bound {
+            WindowFrameBound::UnboundedPreceding => 0,
+            WindowFrameBound::OffsetPreceding(n) => {
+                (position + 1).saturating_sub(usize::cast_from(*n))
+            }
+            WindowFrameBound::CurrentRow => match self.units {
+                WindowFrameUnits::Rows => position + 1,
+                WindowFrameUnits::Range => peers_end,
+            },
+            WindowFrameBound::OffsetFollowing(n) => {
+                (position + 1).saturating_add(usize::cast_from(*n)).min(len)
+            }
+            WindowFrameBound::UnboundedFollowing => len,
+        };
+        (start, end.max(start))
+    }
+}
+
+impl fmt::Display for WindowFrame {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} between {} and {}",
+            self.units, self.start_bound, self.end_bound
+        )
+    }
+}
+
+/// Describes whether a [`WindowFrame`] counts its bounds in rows or in
+/// groups of peers.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize, Hash, MzEnumReflect)]
+pub enum WindowFrameUnits {
+    /// Each row is counted separately.
+    Rows,
+    /// Rows that are peers according to the window's `ORDER BY` are treated
+    /// as a single unit. Only `CURRENT ROW` and unbounded bounds are
+    /// supported.
+    Range,
+}
+
+impl fmt::Display for WindowFrameUnits {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            WindowFrameUnits::Rows => f.write_str("rows"),
+            WindowFrameUnits::Range => f.write_str("range"),
+        }
+    }
+}
+
+/// One end of a [`WindowFrame`].
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize, Hash, MzEnumReflect)]
+pub enum WindowFrameBound {
+    UnboundedPreceding,
+    OffsetPreceding(u64),
+    CurrentRow,
+    OffsetFollowing(u64),
+    UnboundedFollowing,
+}
+
+impl fmt::Display for WindowFrameBound {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            WindowFrameBound::UnboundedPreceding => f.write_str("unbounded preceding"),
+            WindowFrameBound::OffsetPreceding(n) => write!(f, "{} preceding", n),
+            WindowFrameBound::CurrentRow => f.write_str("current row"),
+            WindowFrameBound::OffsetFollowing(n) => write!(f, "{} following", n),
+            WindowFrameBound::UnboundedFollowing => f.write_str("unbounded following"),
+        }
+    }
+}
+
 #[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize, Hash, MzEnumReflect)]
 pub enum AggregateFunc {
     MaxNumeric,
@@ -627,6 +1131,49 @@ pub enum AggregateFunc {
     StringAgg {
         order_by: Vec<ColumnOrder>,
     },
+    /// Accumulates `Datum::List`s whose first element is a record of an input
+    /// row and an ignored argument into a `Datum::List` of records pairing
+    /// each row with its position within the group. The other elements are
+    /// columns used by `order_by`.
+    RowNumber {
+        order_by: Vec<ColumnOrder>,
+    },
+    /// Like `RowNumber`, but rows that are equal according to `order_by`
+    /// share a rank, and ranks skip over such ties.
+    Rank {
+        order_by: Vec<ColumnOrder>,
+    },
+    /// Like `Rank`, but ranks do not skip over ties.
+    DenseRank {
+        order_by: Vec<ColumnOrder>,
+    },
+    /// Like `RowNumber`, but pairs each row with a value taken from the row
+    /// that is some offset before (`Lag`) or after (`Lead`) it. The argument
+    /// is a record of the value, the offset, and the default value to use
+    /// when no such row exists.
+    LagLead {
+        order_by: Vec<ColumnOrder>,
+        lag_lead: LagLeadType,
+    },
+    /// Like `RowNumber`, but pairs each row with the argument of the first
+    /// row in its window frame.
+    FirstValue {
+        order_by: Vec<ColumnOrder>,
+        window_frame: WindowFrame,
+    },
+    /// Like `RowNumber`, but pairs each row with the argument of the last row
+    /// in its window frame.
+    LastValue {
+        order_by: Vec<ColumnOrder>,
+        window_frame: WindowFrame,
+    },
+    /// Like `RowNumber`, but pairs each row with the result of applying
+    /// `wrapped_aggregate` to the arguments of the rows in its window frame.
+    WindowAggregate {
+        wrapped_aggregate: Box<AggregateFunc>,
+        order_by: Vec<ColumnOrder>,
+        window_frame: WindowFrame,
+    },
     /// Accumulates any number of `Datum::Dummy`s into `Datum::Dummy`.
     ///
     /// Useful for removing an expensive aggregation while maintaining the shape
@@ -678,6 +1225,31 @@ impl AggregateFunc {
             AggregateFunc::ArrayConcat { order_by } => array_concat(datums, temp_storage, order_by),
             AggregateFunc::ListConcat { order_by } => list_concat(datums, temp_storage, order_by),
             AggregateFunc::StringAgg { order_by } => string_agg(datums, temp_storage, order_by),
+            AggregateFunc::RowNumber { order_by } => row_number(datums, temp_storage, order_by),
+            AggregateFunc::Rank { order_by } => rank(datums, temp_storage, order_by, false),
+            AggregateFunc::DenseRank { order_by } => rank(datums, temp_storage, order_by, true),
+            AggregateFunc::LagLead { order_by, lag_lead } => {
+                lag_or_lead(datums, temp_storage, order_by, lag_lead)
+            }
+            AggregateFunc::FirstValue {
+                order_by,
+                window_frame,
+            } => first_last_value(datums, temp_storage, order_by, window_frame, false),
+            AggregateFunc::LastValue {
+                order_by,
+                window_frame,
+            } => first_last_value(datums, temp_storage, order_by, window_frame, true),
+            AggregateFunc::WindowAggregate {
+                wrapped_aggregate,
+                order_by,
+                window_frame,
+            } => window_aggregate(
+                datums,
+                temp_storage,
+                wrapped_aggregate,
+                order_by,
+                window_frame,
+            ),
             AggregateFunc::Dummy => Datum::Dummy,
         }
     }
@@ -730,6 +1302,31 @@ impl AggregateFunc {
                 }
             }
             AggregateFunc::StringAgg { .. } => ScalarType::String,
+            AggregateFunc::RowNumber { .. }
+            | AggregateFunc::Rank { .. }
+            | AggregateFunc::DenseRank { .. } => {
+                window_output_type(input_type.clone(), ScalarType::Int64.nullable(false))
+            }
+            AggregateFunc::LagLead { .. } => {
+                // The argument is a record of the value, the offset and the
+                // default value.
+                let value_type = match window_input_types(input_type.clone()).1.scalar_type {
+                    ScalarType::Record { mut fields, .. } => fields.swap_remove(0).1,
+                    _ => unreachable!(),
+                };
+                window_output_type(input_type.clone(), value_type.nullable(true))
+            }
+            AggregateFunc::FirstValue { .. } | AggregateFunc::LastValue { .. } => {
+                let value_type = window_input_types(input_type.clone()).1;
+                window_output_type(input_type.clone(), value_type.nullable(true))
+            }
+            AggregateFunc::WindowAggregate {
+                wrapped_aggregate, ..
+            } => {
+                let arg_type = window_input_types(input_type.clone()).1;
+                let value_type = wrapped_aggregate.output_type(arg_type);
+                window_output_type(input_type.clone(), value_type.nullable(true))
+            }
             // Note AggregateFunc::MaxString, MinString rely on returning input
             // type as output type to support the proper return type for
             // character input.
@@ -744,6 +1341,21 @@ impl AggregateFunc {
         scalar_type.nullable(nullable)
     }
 
+    /// Returns true if the aggregation is a window function, i.e., if it
+    /// produces a list of records that pair each input row with a value.
+    pub fn is_window_func(&self) -> bool {
+        matches!(
+            self,
+            AggregateFunc::RowNumber { .. }
+                | AggregateFunc::Rank { .. }
+                | AggregateFunc::DenseRank { .. }
+                | AggregateFunc::LagLead { .. }
+                | AggregateFunc::FirstValue { .. }
+                | AggregateFunc::LastValue { .. }
+                | AggregateFunc::WindowAggregate { .. }
+        )
+    }
+
     /// Returns true if the non-null constraint on the aggregation can be
     /// converted into a non-null constraint on its parameter expression, ie.
     /// whether the result of the aggregation is null if all the input values
@@ -843,6 +1455,20 @@ fn regexp_extract(a: Datum, r: &AnalyzedRegex) -> Option<(Row, Diff)> {
     Some((Row::pack(datums), 1))
 }
 
+fn regexp_split_to_table<'a>(
+    datums: &[Datum<'a>],
+) -> Result<impl Iterator<Item = (Row, Diff)> + 'a, EvalError> {
+    let haystack = datums[0].unwrap_str();
+    let flags = match datums.get(2) {
+        Some(d) => d.unwrap_str(),
+        None => "",
+    };
+    let needle = build_regex(datums[1].unwrap_str(), flags)?;
+    Ok(regexp_split(haystack, &needle)
+        .into_iter()
+        .map(|field| (Row::pack_slice(&[Datum::String(field)]), 1)))
+}
+
 fn generate_series<N>(
     start: N,
     stop: N,
@@ -913,6 +1539,21 @@ impl fmt::Display for AggregateFunc {
             AggregateFunc::ArrayConcat { .. } => f.write_str("array_agg"),
             AggregateFunc::ListConcat { .. } => f.write_str("list_agg"),
             AggregateFunc::StringAgg { .. } => f.write_str("string_agg"),
+            AggregateFunc::RowNumber { .. } => f.write_str("row_number"),
+            AggregateFunc::Rank { .. } => f.write_str("rank"),
+            AggregateFunc::DenseRank { .. } => f.write_str("dense_rank"),
+            AggregateFunc::LagLead { lag_lead, .. } => write!(f, "{}", lag_lead),
+            AggregateFunc::FirstValue { window_frame, .. } => {
+                write!(f, "first_value[{}]", window_frame)
+            }
+            AggregateFunc::LastValue { window_frame, .. } => {
+                write!(f, "last_value[{}]", window_frame)
+            }
+            AggregateFunc::WindowAggregate {
+                wrapped_aggregate,
+                window_frame,
+                ..
+            } => write!(f, "{}[{}]", wrapped_aggregate, window_frame),
             AggregateFunc::Dummy => f.write_str("dummy"),
         }
     }
@@ -990,6 +1631,7 @@ pub enum TableFunc {
     JsonbObjectKeys,
     JsonbArrayElements { stringify: bool },
     RegexpExtract(AnalyzedRegex),
+    RegexpSplitToTable,
     CsvExtract(usize),
     GenerateSeriesInt32,
     GenerateSeriesInt64,
@@ -1021,6 +1663,7 @@ impl TableFunc {
                 *stringify,
             ))),
             TableFunc::RegexpExtract(a) => Ok(Box::new(regexp_extract(datums[0], a).into_iter())),
+            TableFunc::RegexpSplitToTable => Ok(Box::new(regexp_split_to_table(&datums)?)),
             TableFunc::CsvExtract(n_cols) => {
                 Ok(Box::new(csv_extract(datums[0], *n_cols).into_iter()))
             }
@@ -1067,6 +1710,7 @@ impl TableFunc {
                 .capture_groups_iter()
                 .map(|cg| ScalarType::String.nullable(cg.nullable))
                 .collect(),
+            TableFunc::RegexpSplitToTable => vec![ScalarType::String.nullable(false)],
             TableFunc::CsvExtract(n_cols) => iter::repeat(ScalarType::String.nullable(false))
                 .take(*n_cols)
                 .collect(),
@@ -1088,6 +1732,7 @@ impl TableFunc {
             TableFunc::JsonbObjectKeys => 1,
             TableFunc::JsonbArrayElements { .. } => 1,
             TableFunc::RegexpExtract(a) => a.capture_groups_len(),
+            TableFunc::RegexpSplitToTable => 1,
             TableFunc::CsvExtract(n_cols) => *n_cols,
             TableFunc::GenerateSeriesInt32 => 1,
             TableFunc::GenerateSeriesInt64 => 1,
@@ -1110,6 +1755,7 @@ impl TableFunc {
             | TableFunc::GenerateSeriesInt32
             | TableFunc::GenerateSeriesInt64
             | TableFunc::RegexpExtract(_)
+            | TableFunc::RegexpSplitToTable
             | TableFunc::CsvExtract(_)
             | TableFunc::Repeat
             | TableFunc::UnnestArray { .. }
@@ -1126,6 +1772,7 @@ impl TableFunc {
             TableFunc::JsonbObjectKeys => true,
             TableFunc::JsonbArrayElements { .. } => true,
             TableFunc::RegexpExtract(_) => true,
+            TableFunc::RegexpSplitToTable => true,
             TableFunc::CsvExtract(_) => true,
             TableFunc::GenerateSeriesInt32 => true,
             TableFunc::GenerateSeriesInt64 => true,
@@ -1143,6 +1790,7 @@ impl fmt::Display for TableFunc {
             TableFunc::JsonbObjectKeys => f.write_str("jsonb_object_keys"),
             TableFunc::JsonbArrayElements { .. } => f.write_str("jsonb_array_elements"),
             TableFunc::RegexpExtract(a) => write!(f, "regexp_extract({:?}, _)", a.0),
+            TableFunc::RegexpSplitToTable => f.write_str("regexp_split_to_table"),
             TableFunc::CsvExtract(n_cols) => write!(f, "csv_extract({}, _)", n_cols),
             TableFunc::GenerateSeriesInt32 => f.write_str("generate_series"),
             TableFunc::GenerateSeriesInt64 => f.write_str("generate_series"),
@@ -66,6 +66,24 @@ pub enum MirRelationExpr {
         /// The result of the `Let`, evaluated with `name` bound to `value`.
         body: Box<MirRelationExpr>,
     },
+    /// Introduce a temporary dataflow that is defined in terms of itself.
+    ///
+    /// The identifier is bound to the least fixed point of `value`: starting
+    /// from the empty collection, `value` is repeatedly evaluated with `id`
+    /// bound to the result of the previous evaluation, until the result no
+    /// longer changes. `value` may refer to `id`, but `id` is not visible
+    /// outside of the `LetRec`, except in `body`.
+    ///
+    /// The runtime memory footprint of this operator is proportional to the
+    /// size of the fixed point, which is maintained across iterations.
+    LetRec {
+        /// The identifier to be used in `Get` variants to retrieve `value`.
+        id: LocalId,
+        /// The collection to be bound to `id`, which may itself refer to `id`.
+        value: Box<MirRelationExpr>,
+        /// The result of the `LetRec`, evaluated with `id` bound to `value`.
+        body: Box<MirRelationExpr>,
+    },
     /// Project out some columns from a dataflow
     ///
     /// The runtime memory footprint of this operator is zero.
@@ -251,7 +269,8 @@ impl MirRelationExpr {
         let mut type_stack = Vec::new();
         self.visit_pre_post(
             &mut |e: &MirRelationExpr| -> Option<Vec<&MirRelationExpr>> {
-                if let MirRelationExpr::Let { body, .. } = &e {
+                if let MirRelationExpr::Let { body, .. } | MirRelationExpr::LetRec { body, .. } = &e
+                {
                     // Do not traverse the value sub-graph, since it's not relevant for
                     // determing the relation type of Let operators.
                     Some(vec![&*body])
@@ -260,7 +279,7 @@ impl MirRelationExpr {
                 }
             },
             &mut |e: &MirRelationExpr| {
-                if let MirRelationExpr::Let { .. } = &e {
+                if let MirRelationExpr::Let { .. } | MirRelationExpr::LetRec { .. } = &e {
                     let body_typ = type_stack.pop().unwrap();
                     // Insert a dummy relation type for the value, since `typ_with_input_types`
                     // won't look at it, but expects the relation type of the body to be second.
@@ -336,7 +355,9 @@ impl MirRelationExpr {
                 }
             }
             MirRelationExpr::Get { typ, .. } => typ.clone(),
-            MirRelationExpr::Let { .. } => input_types.last().unwrap().clone(),
+            MirRelationExpr::Let { .. } | MirRelationExpr::LetRec { .. } => {
+                input_types.last().unwrap().clone()
+            }
             MirRelationExpr::Project { input: _, outputs } => {
                 let input_typ = &input_types[0];
                 let mut output_typ = RelationType::new(
@@ -667,7 +688,9 @@ impl MirRelationExpr {
         match self {
             MirRelationExpr::Constant { rows: _, typ } => typ.arity(),
             MirRelationExpr::Get { typ, .. } => typ.arity(),
-            MirRelationExpr::Let { body, .. } => body.arity(),
+            MirRelationExpr::Let { body, .. } | MirRelationExpr::LetRec { body, .. } => {
+                body.arity()
+            }
             MirRelationExpr::Project { input: _, outputs } => outputs.len(),
             MirRelationExpr::Map { input, scalars } => input.arity() + scalars.len(),
             MirRelationExpr::FlatMap { input, func, .. } => {
@@ -990,6 +1013,17 @@ impl MirRelationExpr {
         self.visit1(|expr| expr.global_uses_into(out))
     }
 
+    /// Reports whether this expression contains a [`MirRelationExpr::LetRec`].
+    pub fn is_recursive(&self) -> bool {
+        let mut recursive = false;
+        self.visit(&mut |expr| {
+            if let MirRelationExpr::LetRec { .. } = expr {
+                recursive = true;
+            }
+        });
+        recursive
+    }
+
     /// Applies a fallible `f` to each child `MirRelationExpr`.
     pub fn try_visit1<'a, F, E>(&'a self, mut f: F) -> Result<(), E>
     where
@@ -997,7 +1031,8 @@ impl MirRelationExpr {
     {
         match self {
             MirRelationExpr::Constant { .. } | MirRelationExpr::Get { .. } => (),
-            MirRelationExpr::Let { value, body, .. } => {
+            MirRelationExpr::Let { value, body, .. }
+            | MirRelationExpr::LetRec { value, body, .. } => {
                 f(value)?;
                 f(body)?;
             }
@@ -1079,7 +1114,8 @@ impl MirRelationExpr {
     {
         match self {
             MirRelationExpr::Constant { .. } | MirRelationExpr::Get { .. } => (),
-            MirRelationExpr::Let { value, body, .. } => {
+            MirRelationExpr::Let { value, body, .. }
+            | MirRelationExpr::LetRec { value, body, .. } => {
                 f(value)?;
                 f(body)?;
             }
@@ -1267,6 +1303,11 @@ impl MirRelationExpr {
                 value: _,
                 body: _,
             }
+            | MirRelationExpr::LetRec {
+                id: _,
+                value: _,
+                body: _,
+            }
             | MirRelationExpr::Project {
                 input: _,
                 outputs: _,
@@ -36,11 +36,13 @@ use ore::fmt::FormatBuffer;
 use ore::result::ResultExt;
 use ore::str::StrExt;
 use pgrepr::Type;
-use repr::adt::array::ArrayDimension;
+use repr::adt::array::{Array, ArrayDimension};
 use repr::adt::datetime::{DateTimeUnits, Timezone};
+use repr::adt::enums::EnumLabels;
 use repr::adt::interval::Interval;
 use repr::adt::jsonb::JsonbRef;
 use repr::adt::numeric::{self, Numeric};
+use repr::adt::range::RangeBound;
 use repr::adt::regex::Regex;
 use repr::{strconv, ColumnName, ColumnType, Datum, Row, RowArena, ScalarType};
 
@@ -488,6 +490,26 @@ fn cast_string_to_map<'a>(
     }))
 }
 
+fn cast_string_to_range<'a>(
+    a: Datum<'a>,
+    cast_expr: &'a MirScalarExpr,
+    temp_storage: &'a RowArena,
+) -> Result<Datum<'a>, EvalError> {
+    let parsed_range = strconv::parse_range(a.unwrap_str(), |elem_text| {
+        let elem_text = match elem_text {
+            Cow::Owned(s) => temp_storage.push_string(s),
+            Cow::Borrowed(s) => s,
+        };
+        cast_expr.eval(&[Datum::String(elem_text)], temp_storage)
+    })?;
+    match parsed_range {
+        None => Ok(temp_storage.make_datum(|packer| packer.push_empty_range())),
+        Some((lower, upper)) => {
+            temp_storage.try_make_datum(|packer| packer.push_range(lower, upper).err_into())
+        }
+    }
+}
+
 fn cast_string_to_time<'a>(a: Datum<'a>) -> Result<Datum<'a>, EvalError> {
     strconv::parse_time(a.unwrap_str())
         .map(Datum::Time)
@@ -512,6 +534,14 @@ fn cast_string_to_interval<'a>(a: Datum<'a>) -> Result<Datum<'a>, EvalError> {
         .err_into()
 }
 
+pub fn cast_string_to_enum<'a>(a: Datum<'a>, labels: &[String]) -> Result<Datum<'a>, EvalError> {
+    let label = a.unwrap_str();
+    match labels.iter().position(|l| l == label) {
+        Some(i) => Ok(Datum::Int32(i as i32)),
+        None => Err(EvalError::InvalidEnumLabel(label.to_owned())),
+    }
+}
+
 fn cast_string_to_uuid<'a>(a: Datum<'a>) -> Result<Datum<'a>, EvalError> {
     strconv::parse_uuid(a.unwrap_str())
         .map(Datum::Uuid)
@@ -2640,6 +2670,7 @@ pub enum BinaryFunc {
     Position,
     Right,
     RepeatString,
+    StartsWith,
     Trim,
     TrimLeading,
     TrimTrailing,
@@ -2651,9 +2682,19 @@ pub enum BinaryFunc {
     ArrayLength,
     ArrayLower,
     ArrayUpper,
+    ArrayArrayConcat,
+    ArrayElementConcat,
+    ElementArrayConcat,
+    ArrayContainsArray,
+    ArrayOverlaps,
+    ArrayPositions,
+    ArrayRemove,
     ListListConcat,
     ListElementConcat,
     ElementListConcat,
+    RangeContainsElem,
+    RangeContainsRange,
+    RangeOverlaps,
     DigestString,
     DigestBytes,
     MzRenderTypemod,
@@ -2825,6 +2866,7 @@ impl BinaryFunc {
             BinaryFunc::Left => eager!(left),
             BinaryFunc::Position => eager!(position),
             BinaryFunc::Right => eager!(right),
+            BinaryFunc::StartsWith => Ok(eager!(starts_with)),
             BinaryFunc::Trim => Ok(eager!(trim)),
             BinaryFunc::TrimLeading => Ok(eager!(trim_leading)),
             BinaryFunc::TrimTrailing => Ok(eager!(trim_trailing)),
@@ -2836,9 +2878,19 @@ impl BinaryFunc {
             BinaryFunc::ArrayIndex => Ok(eager!(array_index)),
             BinaryFunc::ArrayLower => Ok(eager!(array_lower)),
             BinaryFunc::ArrayUpper => Ok(eager!(array_upper)),
+            BinaryFunc::ArrayArrayConcat => eager!(array_array_concat, temp_storage),
+            BinaryFunc::ArrayElementConcat => eager!(array_element_concat, temp_storage),
+            BinaryFunc::ElementArrayConcat => eager!(element_array_concat, temp_storage),
+            BinaryFunc::ArrayContainsArray => Ok(eager!(array_contains_array)),
+            BinaryFunc::ArrayOverlaps => Ok(eager!(array_overlaps)),
+            BinaryFunc::ArrayPositions => eager!(array_positions, temp_storage),
+            BinaryFunc::ArrayRemove => eager!(array_remove, temp_storage),
             BinaryFunc::ListListConcat => Ok(eager!(list_list_concat, temp_storage)),
             BinaryFunc::ListElementConcat => Ok(eager!(list_element_concat, temp_storage)),
             BinaryFunc::ElementListConcat => Ok(eager!(element_list_concat, temp_storage)),
+            BinaryFunc::RangeContainsElem => Ok(eager!(range_contains_elem)),
+            BinaryFunc::RangeContainsRange => Ok(eager!(range_contains_range)),
+            BinaryFunc::RangeOverlaps => Ok(eager!(range_overlaps)),
             BinaryFunc::DigestString => eager!(digest_string, temp_storage),
             BinaryFunc::DigestBytes => eager!(digest_bytes, temp_storage),
             BinaryFunc::MzRenderTypemod => Ok(eager!(mz_render_typemod, temp_storage)),
@@ -2959,6 +3011,12 @@ impl BinaryFunc {
             JsonbContainsString | JsonbContainsJsonb | MapContainsKey | MapContainsAllKeys
             | MapContainsAnyKeys | MapContainsMap => ScalarType::Bool.nullable(in_nullable),
 
+            RangeContainsElem | RangeContainsRange | RangeOverlaps | StartsWith => {
+                ScalarType::Bool.nullable(in_nullable)
+            }
+
+            ArrayContainsArray | ArrayOverlaps => ScalarType::Bool.nullable(in_nullable),
+
             MapGetValue => input1_type
                 .scalar_type
                 .unwrap_map_value_type()
@@ -2996,6 +3054,22 @@ impl BinaryFunc {
                 .default_embedded_value()
                 .nullable(true),
 
+            ArrayArrayConcat | ArrayElementConcat => input1_type
+                .scalar_type
+                .default_embedded_value()
+                .nullable(true),
+
+            ElementArrayConcat => input2_type
+                .scalar_type
+                .default_embedded_value()
+                .nullable(true),
+
+            ArrayRemove => input1_type.scalar_type.nullable(input1_type.nullable),
+
+            ArrayPositions => {
+                ScalarType::Array(Box::new(ScalarType::Int32)).nullable(input1_type.nullable)
+            }
+
             DigestString | DigestBytes => ScalarType::Bytes.nullable(true),
             Position => ScalarType::Int32.nullable(in_nullable),
             Encode => ScalarType::String.nullable(in_nullable),
@@ -3019,6 +3093,11 @@ impl BinaryFunc {
                 | BinaryFunc::ListListConcat
                 | BinaryFunc::ListElementConcat
                 | BinaryFunc::ElementListConcat
+                | BinaryFunc::ArrayArrayConcat
+                | BinaryFunc::ArrayElementConcat
+                | BinaryFunc::ElementArrayConcat
+                | BinaryFunc::ArrayPositions
+                | BinaryFunc::ArrayRemove
         )
     }
 
@@ -3199,7 +3278,15 @@ impl BinaryFunc {
             | ArrayUpper
             | ListListConcat
             | ListElementConcat
-            | ElementListConcat => true,
+            | ElementListConcat
+            | ArrayArrayConcat
+            | ArrayElementConcat
+            | ElementArrayConcat
+            | ArrayContainsArray
+            | ArrayOverlaps
+            | RangeContainsElem
+            | RangeContainsRange
+            | RangeOverlaps => true,
             IsLikePatternMatch { .. }
             | ToCharTimestamp
             | ToCharTimestampTz
@@ -3232,7 +3319,10 @@ impl BinaryFunc {
             | LogNumeric
             | Power
             | PowerNumeric
-            | RepeatString => false,
+            | RepeatString
+            | StartsWith
+            | ArrayPositions
+            | ArrayRemove => false,
         }
     }
 
@@ -3368,6 +3458,7 @@ impl fmt::Display for BinaryFunc {
             BinaryFunc::Left => f.write_str("left"),
             BinaryFunc::Position => f.write_str("position"),
             BinaryFunc::Right => f.write_str("right"),
+            BinaryFunc::StartsWith => f.write_str("starts_with"),
             BinaryFunc::Trim => f.write_str("btrim"),
             BinaryFunc::TrimLeading => f.write_str("ltrim"),
             BinaryFunc::TrimTrailing => f.write_str("rtrim"),
@@ -3379,9 +3470,18 @@ impl fmt::Display for BinaryFunc {
             BinaryFunc::ArrayLength => f.write_str("array_length"),
             BinaryFunc::ArrayLower => f.write_str("array_lower"),
             BinaryFunc::ArrayUpper => f.write_str("array_upper"),
+            BinaryFunc::ArrayArrayConcat => f.write_str("||"),
+            BinaryFunc::ArrayElementConcat => f.write_str("||"),
+            BinaryFunc::ElementArrayConcat => f.write_str("||"),
+            BinaryFunc::ArrayContainsArray => f.write_str("@>"),
+            BinaryFunc::ArrayOverlaps => f.write_str("&&"),
+            BinaryFunc::ArrayPositions => f.write_str("array_positions"),
+            BinaryFunc::ArrayRemove => f.write_str("array_remove"),
             BinaryFunc::ListListConcat => f.write_str("||"),
             BinaryFunc::ListElementConcat => f.write_str("||"),
             BinaryFunc::ElementListConcat => f.write_str("||"),
+            BinaryFunc::RangeContainsElem | BinaryFunc::RangeContainsRange => f.write_str("@>"),
+            BinaryFunc::RangeOverlaps => f.write_str("&&"),
             BinaryFunc::DigestString | BinaryFunc::DigestBytes => f.write_str("digest"),
             BinaryFunc::MzRenderTypemod => f.write_str("mz_render_typemod"),
             BinaryFunc::Encode => f.write_str("encode"),
@@ -3510,12 +3610,25 @@ pub enum UnaryFunc {
         // type.
         cast_expr: Box<MirScalarExpr>,
     },
+    CastStringToRange {
+        // Target range's type.
+        return_ty: ScalarType,
+        // The expression to cast the discovered bounds to the range's element
+        // type.
+        cast_expr: Box<MirScalarExpr>,
+    },
     CastStringToTime,
     CastStringToTimestamp,
     CastStringToTimestampTz,
     CastStringToInterval,
     CastStringToNumeric(Option<u8>),
     CastStringToUuid,
+    CastStringToEnum {
+        return_ty: ScalarType,
+        // The labels of the enum type, as looked up in the catalog during
+        // planning.
+        labels: Vec<String>,
+    },
     CastStringToChar {
         length: Option<usize>,
         fail_on_len: bool,
@@ -3559,12 +3672,21 @@ pub enum UnaryFunc {
     CastUuidToString,
     CastRecordToString {
         ty: ScalarType,
+        // The labels of the enum types within `ty`, as looked up in the
+        // catalog during planning.
+        enum_labels: EnumLabels,
     },
     CastArrayToString {
         ty: ScalarType,
+        // The labels of the enum types within `ty`, as looked up in the
+        // catalog during planning.
+        enum_labels: EnumLabels,
     },
     CastListToString {
         ty: ScalarType,
+        // The labels of the enum types within `ty`, as looked up in the
+        // catalog during planning.
+        enum_labels: EnumLabels,
     },
     CastList1ToList2 {
         // List2's type
@@ -3574,6 +3696,18 @@ pub enum UnaryFunc {
     },
     CastMapToString {
         ty: ScalarType,
+        // The labels of the enum types within `ty`, as looked up in the
+        // catalog during planning.
+        enum_labels: EnumLabels,
+    },
+    CastEnumToString {
+        ty: ScalarType,
+        // The labels of the enum types within `ty`, as looked up in the
+        // catalog during planning.
+        enum_labels: EnumLabels,
+    },
+    CastRangeToString {
+        ty: ScalarType,
     },
     CastInPlace {
         return_ty: ScalarType,
@@ -3616,8 +3750,19 @@ pub enum UnaryFunc {
     TrimTrailingWhitespace,
     RecordGet(usize),
     ListLength,
+    StackArrays {
+        return_ty: ScalarType,
+    },
+    RangeLower {
+        return_ty: ScalarType,
+    },
+    RangeUpper {
+        return_ty: ScalarType,
+    },
+    RangeIsEmpty,
     Upper,
     Lower,
+    Initcap,
     Cos(Cos),
     Cosh(Cosh),
     Sin(Sin),
@@ -3803,11 +3948,13 @@ impl UnaryFunc {
                 cast_expr,
                 return_ty,
             } => cast_string_to_map(a, return_ty, cast_expr, temp_storage),
+            CastStringToRange { cast_expr, .. } => cast_string_to_range(a, cast_expr, temp_storage),
             CastStringToTime => cast_string_to_time(a),
             CastStringToTimestamp => cast_string_to_timestamp(a),
             CastStringToTimestampTz => cast_string_to_timestamptz(a),
             CastStringToInterval => cast_string_to_interval(a),
             CastStringToUuid => cast_string_to_uuid(a),
+            CastStringToEnum { labels, .. } => cast_string_to_enum(a, labels),
             CastStringToChar {
                 length,
                 fail_on_len,
@@ -3851,10 +3998,20 @@ impl UnaryFunc {
             CastJsonbToNumeric(scale) => cast_jsonb_to_numeric(a, *scale),
             CastJsonbToBool => cast_jsonb_to_bool(a),
             CastUuidToString => Ok(cast_uuid_to_string(a, temp_storage)),
-            CastRecordToString { ty }
-            | CastArrayToString { ty }
-            | CastListToString { ty }
-            | CastMapToString { ty } => Ok(cast_collection_to_string(a, ty, temp_storage)),
+            CastRecordToString { ty, enum_labels }
+            | CastArrayToString { ty, enum_labels }
+            | CastListToString { ty, enum_labels }
+            | CastMapToString { ty, enum_labels }
+            | CastEnumToString { ty, enum_labels } => {
+                Ok(cast_collection_to_string(a, ty, enum_labels, temp_storage))
+            }
+            // Ranges never contain enum values.
+            CastRangeToString { ty } => Ok(cast_collection_to_string(
+                a,
+                ty,
+                &EnumLabels::default(),
+                temp_storage,
+            )),
             CastList1ToList2 { cast_expr, .. } => cast_list1_to_list2(a, &*cast_expr, temp_storage),
             CastInPlace { .. } => Ok(a),
             CeilNumeric => Ok(ceil_numeric(a)),
@@ -3886,8 +4043,13 @@ impl UnaryFunc {
             TrimTrailingWhitespace => Ok(trim_trailing_whitespace(a)),
             RecordGet(i) => Ok(record_get(a, *i)),
             ListLength => Ok(list_length(a)),
+            StackArrays { .. } => stack_arrays(a, temp_storage),
+            RangeLower { .. } => Ok(range_lower(a)),
+            RangeUpper { .. } => Ok(range_upper(a)),
+            RangeIsEmpty => Ok(range_is_empty(a)),
             Upper => Ok(upper(a, temp_storage)),
             Lower => Ok(lower(a, temp_storage)),
+            Initcap => Ok(initcap(a, temp_storage)),
             Log10Numeric => log_numeric(a, dec::Context::log10, "log10"),
             LnNumeric => log_numeric(a, dec::Context::ln, "ln"),
             ExpNumeric => exp_numeric(a),
@@ -3965,6 +4127,7 @@ impl UnaryFunc {
             CastStringToBytes => ScalarType::Bytes.nullable(nullable),
             CastStringToInterval | CastTimeToInterval => ScalarType::Interval.nullable(nullable),
             CastStringToUuid => ScalarType::Uuid.nullable(nullable),
+            CastStringToEnum { return_ty, .. } => return_ty.clone().nullable(nullable),
             CastStringToJsonb => ScalarType::Jsonb.nullable(nullable),
 
             CastBoolToString
@@ -3986,11 +4149,14 @@ impl UnaryFunc {
             | CastArrayToString { .. }
             | CastListToString { .. }
             | CastMapToString { .. }
+            | CastEnumToString { .. }
+            | CastRangeToString { .. }
             | TrimWhitespace
             | TrimLeadingWhitespace
             | TrimTrailingWhitespace
             | Upper
-            | Lower => ScalarType::String.nullable(nullable),
+            | Lower
+            | Initcap => ScalarType::String.nullable(nullable),
 
             CastStringToFloat32 | CastInt16ToFloat32 | CastInt32ToFloat32 | CastInt64ToFloat32
             | CastNumericToFloat32 => ScalarType::Float32.nullable(nullable),
@@ -4054,6 +4220,7 @@ impl UnaryFunc {
 
             CastStringToArray { return_ty, .. }
             | CastStringToMap { return_ty, .. }
+            | CastStringToRange { return_ty, .. }
             | CastInPlace { return_ty } => (return_ty.clone()).nullable(nullable),
 
             CastList1ToList2 { return_ty, .. } | CastStringToList { return_ty, .. } => {
@@ -4093,6 +4260,11 @@ impl UnaryFunc {
 
             ListLength => ScalarType::Int64.nullable(nullable),
 
+            StackArrays { return_ty } | RangeLower { return_ty } | RangeUpper { return_ty } => {
+                return_ty.clone().nullable(nullable)
+            }
+            RangeIsEmpty => ScalarType::Bool.nullable(nullable),
+
             RegexpMatch(_) => ScalarType::Array(Box::new(ScalarType::String)).nullable(nullable),
 
             RescaleNumeric(scale) => (ScalarType::Numeric {
@@ -4181,6 +4353,10 @@ impl UnaryFunc {
             RegexpMatch(_) => true,
             // Returns null on non-array input
             JsonbArrayLength => true,
+            // Returns null for empty ranges and infinite bounds
+            RangeLower { .. } | RangeUpper { .. } => true,
+            // Returns null for empty lists
+            StackArrays { .. } => true,
 
             Ascii | CharLength | BitLengthBytes | BitLengthString | ByteLengthBytes
             | ByteLengthString => false,
@@ -4211,11 +4387,14 @@ impl UnaryFunc {
             | CastArrayToString { .. }
             | CastListToString { .. }
             | CastMapToString { .. }
+            | CastEnumToString { .. }
+            | CastRangeToString { .. }
             | TrimWhitespace
             | TrimLeadingWhitespace
             | TrimTrailingWhitespace
             | Upper
-            | Lower => false,
+            | Lower
+            | Initcap => false,
             CastStringToFloat32 | CastInt32ToFloat32 | CastInt16ToFloat32 | CastInt64ToFloat32
             | CastNumericToFloat32 => false,
             CastStringToFloat64 | CastInt32ToFloat64 | CastInt16ToFloat64 | CastInt64ToFloat64
@@ -4244,14 +4423,16 @@ impl UnaryFunc {
             | CastTimestampToTimestampTz
             | TimezoneTimestamp(_) => false,
             CastStringToUuid => false,
+            CastStringToEnum { .. } => false,
             CastList1ToList2 { .. }
             | CastStringToArray { .. }
             | CastStringToList { .. }
             | CastStringToMap { .. }
+            | CastStringToRange { .. }
             | CastInPlace { .. } => false,
             CastStringToChar { .. } | PadChar { .. } => false,
             CastStringToVarChar { .. } => false,
-            JsonbTypeof | JsonbStripNulls | JsonbPretty | ListLength => false,
+            JsonbTypeof | JsonbStripNulls | JsonbPretty | ListLength | RangeIsEmpty => false,
             DatePartInterval(_) | DatePartTimestamp(_) | DatePartTimestampTz(_) => false,
             DateTruncTimestamp(_) | DateTruncTimestampTz(_) => false,
             NegInterval => false,
@@ -4330,7 +4511,9 @@ impl UnaryFunc {
             | CastDateToTimestampTz
             | CastDateToString
             | CastTimeToInterval
-            | CastTimeToString => true,
+            | CastTimeToString
+            | CastStringToEnum { .. }
+            | CastEnumToString { .. } => true,
             _ => false,
         }
     }
@@ -4438,11 +4621,13 @@ impl UnaryFunc {
             CastStringToArray { .. } => f.write_str("strtoarray"),
             CastStringToList { .. } => f.write_str("strtolist"),
             CastStringToMap { .. } => f.write_str("strtomap"),
+            CastStringToRange { .. } => f.write_str("strtorange"),
             CastStringToTime => f.write_str("strtotime"),
             CastStringToTimestamp => f.write_str("strtots"),
             CastStringToTimestampTz => f.write_str("strtotstz"),
             CastStringToInterval => f.write_str("strtoiv"),
             CastStringToUuid => f.write_str("strtouuid"),
+            CastStringToEnum { .. } => f.write_str("strtoenum"),
             CastStringToChar { .. } => f.write_str("strtochar"),
             PadChar { .. } => f.write_str("padchar"),
             CastCharToString => f.write_str("chartostr"),
@@ -4478,6 +4663,8 @@ impl UnaryFunc {
             CastListToString { .. } => f.write_str("listtostr"),
             CastList1ToList2 { .. } => f.write_str("list1tolist2"),
             CastMapToString { .. } => f.write_str("maptostr"),
+            CastEnumToString { .. } => f.write_str("enumtostr"),
+            CastRangeToString { .. } => f.write_str("rangetostr"),
             CastInPlace { .. } => f.write_str("castinplace"),
             CeilNumeric => f.write_str("ceilnumeric"),
             FloorNumeric => f.write_str("floornumeric"),
@@ -4508,8 +4695,13 @@ impl UnaryFunc {
             TrimTrailingWhitespace => f.write_str("rtrim"),
             RecordGet(i) => write!(f, "record_get[{}]", i),
             ListLength => f.write_str("list_length"),
+            StackArrays { .. } => f.write_str("stack_arrays"),
+            RangeLower { .. } => f.write_str("range_lower"),
+            RangeUpper { .. } => f.write_str("range_upper"),
+            RangeIsEmpty => f.write_str("isempty"),
             Upper => f.write_str("upper"),
             Lower => f.write_str("lower"),
+            Initcap => f.write_str("initcap"),
             Log10Numeric => f.write_str("log10numeric"),
             LnNumeric => f.write_str("lnnumeric"),
             ExpNumeric => f.write_str("expnumeric"),
@@ -4783,6 +4975,96 @@ pub fn build_regex(needle: &str, flags: &str) -> Result<regex::Regex, EvalError>
     Ok(regex.build()?)
 }
 
+fn regexp_replace<'a>(
+    datums: &[Datum<'a>],
+    temp_storage: &'a RowArena,
+) -> Result<Datum<'a>, EvalError> {
+    let source = datums[0].unwrap_str();
+    let needle = datums[1].unwrap_str();
+    let replacement = regexp_replacement(datums[2].unwrap_str());
+    let flags = match datums.get(3) {
+        Some(d) => d.unwrap_str(),
+        None => "",
+    };
+    // The `g` flag, which replaces every match rather than just the first, is
+    // specific to regexp_replace, so it is stripped before building the regex.
+    let limit = if flags.contains('g') { 0 } else { 1 };
+    let needle = build_regex(needle, &flags.replace('g', ""))?;
+    let replaced = needle.replacen(source, limit, replacement.as_str());
+    Ok(Datum::String(
+        temp_storage.push_string(replaced.into_owned()),
+    ))
+}
+
+/// Converts a PostgreSQL regexp_replace replacement string, which refers to
+/// capture groups as `\1` through `\9` and to the whole match as `\&`, into
+/// the syntax understood by [`regex::Regex::replacen`].
+fn regexp_replacement(replacement: &str) -> String {
+    let mut out = String::with_capacity(replacement.len());
+    let mut chars = replacement.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => match chars.peek() {
+                Some(&d) if ('1'..='9').contains(&d) => {
+                    out.push_str("${");
+                    out.push(d);
+                    out.push('}');
+                    chars.next();
+                }
+                Some('&') => {
+                    out.push_str("${0}");
+                    chars.next();
+                }
+                Some('\\') => {
+                    out.push('\\');
+                    chars.next();
+                }
+                _ => out.push('\\'),
+            },
+            '$' => out.push_str("$$"),
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+fn regexp_split_to_array<'a>(
+    datums: &[Datum<'a>],
+    temp_storage: &'a RowArena,
+) -> Result<Datum<'a>, EvalError> {
+    let haystack = datums[0].unwrap_str();
+    let needle = datums[1].unwrap_str();
+    let flags = match datums.get(2) {
+        Some(d) => d.unwrap_str(),
+        None => "",
+    };
+    let needle = build_regex(needle, flags)?;
+    let fields = regexp_split(haystack, &needle)
+        .into_iter()
+        .map(Datum::String)
+        .collect::<Vec<_>>();
+    array_create_scalar(&fields, temp_storage)
+}
+
+/// Splits `haystack` at each match of `needle`.
+///
+/// Like PostgreSQL, zero-length matches at the start or end of `haystack`, or
+/// immediately following a previous match, are ignored, so that splitting on
+/// a pattern that can match the empty string does not produce empty fields.
+pub fn regexp_split<'a>(haystack: &'a str, needle: &regex::Regex) -> Vec<&'a str> {
+    let mut fields = vec![];
+    let mut start = 0;
+    for mtch in needle.find_iter(haystack) {
+        if mtch.start() >= haystack.len() || mtch.end() <= start {
+            continue;
+        }
+        fields.push(&haystack[start..mtch.start()]);
+        start = mtch.end();
+    }
+    fields.push(&haystack[start..]);
+    fields
+}
+
 pub fn hmac_string<'a>(
     datums: &[Datum<'a>],
     temp_storage: &'a RowArena,
@@ -4869,6 +5151,414 @@ fn replace<'a>(datums: &[Datum<'a>], temp_storage: &'a RowArena) -> Datum<'a> {
     )
 }
 
+fn translate<'a>(datums: &[Datum<'a>], temp_storage: &'a RowArena) -> Datum<'a> {
+    let string = datums[0].unwrap_str();
+    let from = datums[1].unwrap_str().chars().collect::<Vec<_>>();
+    let to = datums[2].unwrap_str().chars().collect::<Vec<_>>();
+    // Characters in `from` without a counterpart in `to` are deleted.
+    let translated = string
+        .chars()
+        .filter_map(|c| match from.iter().position(|f| *f == c) {
+            Some(i) => to.get(i).copied(),
+            None => Some(c),
+        })
+        .collect();
+    Datum::String(temp_storage.push_string(translated))
+}
+
+fn string_to_array<'a>(
+    datums: &[Datum<'a>],
+    temp_storage: &'a RowArena,
+) -> Result<Datum<'a>, EvalError> {
+    if datums[0].is_null() {
+        return Ok(Datum::Null);
+    }
+    let string = datums[0].unwrap_str();
+    if string.is_empty() {
+        return array_create_scalar(&[], temp_storage);
+    }
+    let fields = match datums[1] {
+        // A null delimiter splits the string into its individual characters.
+        Datum::Null => string
+            .char_indices()
+            .map(|(i, c)| &string[i..i + c.len_utf8()])
+            .collect::<Vec<_>>(),
+        // An empty delimiter does not split the string at all.
+        Datum::String("") => vec![string],
+        delimiter => string.split(delimiter.unwrap_str()).collect(),
+    };
+    let null_string = match datums.get(2) {
+        Some(Datum::String(s)) => Some(*s),
+        _ => None,
+    };
+    let elems = fields
+        .into_iter()
+        .map(|field| {
+            if Some(field) == null_string {
+                Datum::Null
+            } else {
+                Datum::String(field)
+            }
+        })
+        .collect::<Vec<_>>();
+    array_create_scalar(&elems, temp_storage)
+}
+
+/// The maximum length of the string produced by `format`, which matches the
+/// largest allocation that PostgreSQL permits.
+const MAX_FORMAT_LENGTH: usize = (1 << 30) - 1;
+
+/// Implements PostgreSQL's `format` function.
+///
+/// All arguments after the format string must already have been converted to
+/// strings. Format specifiers have the form `%[position][flags][width]type`,
+/// where `type` is one of `s`, `I`, or `L`, and `width` is either a number or
+/// `*[position$]`, in which case the width is taken from an argument.
+fn format<'a>(datums: &[Datum<'a>], temp_storage: &'a RowArena) -> Result<Datum<'a>, EvalError> {
+    if datums[0].is_null() {
+        return Ok(Datum::Null);
+    }
+    let invalid = |msg: &str| EvalError::InvalidParameterValue(msg.into());
+    let fmt = datums[0].unwrap_str();
+    let args = &datums[1..];
+    let get_arg = |i: usize| match args.get(i) {
+        Some(arg) => Ok(*arg),
+        None => Err(invalid("too few arguments for format()")),
+    };
+    let mut out = String::new();
+    let mut next_arg = 0;
+    let mut chars = fmt.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c != '%' {
+            out.push(c);
+            continue;
+        }
+        if chars.peek() == Some(&'%') {
+            chars.next();
+            out.push('%');
+            continue;
+        }
+
+        // A leading number is an argument position if it is followed by `$`,
+        // and otherwise a width.
+        let mut arg_pos = None;
+        let mut left_justify = false;
+        let mut width = parse_format_digits(&mut chars)?;
+        // The position of the argument that holds the width, if any, where
+        // `Some(None)` refers to the next argument.
+        let mut width_pos = None;
+        if let Some(n) = width {
+            if chars.peek() == Some(&'$') {
+                chars.next();
+                arg_pos = Some(format_arg_position(n)?);
+                width = None;
+            }
+        }
+        if width.is_none() {
+            while chars.peek() == Some(&'-') {
+                chars.next();
+                left_justify = true;
+            }
+            if chars.peek() == Some(&'*') {
+                chars.next();
+                match parse_format_digits(&mut chars)? {
+                    Some(n) => {
+                        if chars.next() != Some('$') {
+                            return Err(invalid("width argument position must be ended by \"$\""));
+                        }
+                        width_pos = Some(Some(format_arg_position(n)?));
+                    }
+                    None => width_pos = Some(None),
+                }
+            } else {
+                width = parse_format_digits(&mut chars)?;
+            }
+        }
+
+        let ty = match chars.next() {
+            Some(ty @ 's') | Some(ty @ 'I') | Some(ty @ 'L') => ty,
+            Some(c) => {
+                return Err(EvalError::InvalidParameterValue(format!(
+                    "unrecognized format() type specifier \"{}\"",
+                    c
+                )))
+            }
+            None => return Err(invalid("unterminated format() type specifier")),
+        };
+        if let Some(pos) = width_pos {
+            let pos = pos.unwrap_or(next_arg);
+            next_arg = pos + 1;
+            // A null width is treated as zero, and a negative width requests
+            // left justification.
+            width = match get_arg(pos)? {
+                Datum::Null => None,
+                arg => match strconv::parse_int32(arg.unwrap_str())? {
+                    i32::MIN => return Err(invalid("number is out of range")),
+                    n if n < 0 => {
+                        left_justify = true;
+                        Some(-n as usize)
+                    }
+                    n => Some(n as usize),
+                },
+            };
+        }
+        let pos = arg_pos.unwrap_or(next_arg);
+        next_arg = pos + 1;
+        let formatted = match (ty, get_arg(pos)?) {
+            ('s', Datum::Null) => String::new(),
+            ('I', Datum::Null) => {
+                return Err(invalid(
+                    "null values cannot be formatted as an SQL identifier",
+                ))
+            }
+            ('L', Datum::Null) => "NULL".to_owned(),
+            ('I', arg) => quote_ident(arg.unwrap_str()),
+            ('L', arg) => quote_literal(arg.unwrap_str()),
+            (_, arg) => arg.unwrap_str().to_owned(),
+        };
+
+        let padding = width.unwrap_or(0).saturating_sub(formatted.chars().count());
+        if out.len() + formatted.len() + padding > MAX_FORMAT_LENGTH {
+            return Err(invalid("requested length too large"));
+        }
+        if left_justify {
+            out.push_str(&formatted);
+            out.extend(iter::repeat(' ').take(padding));
+        } else {
+            out.extend(iter::repeat(' ').take(padding));
+            out.push_str(&formatted);
+        }
+    }
+    Ok(Datum::String(temp_storage.push_string(out)))
+}
+
+/// Parses the digits at the front of `chars`, if any, as a number no greater
+/// than [`i32::MAX`].
+fn parse_format_digits(
+    chars: &mut iter::Peekable<std::str::Chars>,
+) -> Result<Option<usize>, EvalError> {
+    let mut n: Option<i32> = None;
+    while let Some(d) = chars.peek().and_then(|c| c.to_digit(10)) {
+        chars.next();
+        match n
+            .unwrap_or(0)
+            .checked_mul(10)
+            .and_then(|n| n.checked_add(d as i32))
+        {
+            Some(m) => n = Some(m),
+            None => {
+                return Err(EvalError::InvalidParameterValue(
+                    "number is out of range".into(),
+                ))
+            }
+        }
+    }
+    Ok(n.map(|n| n as usize))
+}
+
+/// Converts the one-based argument position `n` of a format specifier to an
+/// index into the arguments.
+fn format_arg_position(n: usize) -> Result<usize, EvalError> {
+    match n.checked_sub(1) {
+        Some(i) => Ok(i),
+        None => Err(EvalError::InvalidParameterValue(
+            "format specifies argument 0, but arguments are numbered from 1".into(),
+        )),
+    }
+}
+
+/// Keywords that are not unreserved in PostgreSQL's grammar, which
+/// PostgreSQL's `quote_ident` therefore quotes, along with `option`, which
+/// Materialize reserves. Sorted for binary search.
+const QUOTED_KEYWORDS: &[&str] = &[
+    "all",
+    "analyse",
+    "analyze",
+    "and",
+    "any",
+    "array",
+    "as",
+    "asc",
+    "asymmetric",
+    "authorization",
+    "between",
+    "bigint",
+    "binary",
+    "bit",
+    "boolean",
+    "both",
+    "case",
+    "cast",
+    "char",
+    "character",
+    "check",
+    "coalesce",
+    "collate",
+    "collation",
+    "column",
+    "concurrently",
+    "constraint",
+    "create",
+    "cross",
+    "current_catalog",
+    "current_date",
+    "current_role",
+    "current_schema",
+    "current_time",
+    "current_timestamp",
+    "current_user",
+    "dec",
+    "decimal",
+    "default",
+    "deferrable",
+    "desc",
+    "distinct",
+    "do",
+    "else",
+    "end",
+    "except",
+    "exists",
+    "extract",
+    "false",
+    "fetch",
+    "float",
+    "for",
+    "foreign",
+    "freeze",
+    "from",
+    "full",
+    "grant",
+    "greatest",
+    "group",
+    "grouping",
+    "having",
+    "ilike",
+    "in",
+    "initially",
+    "inner",
+    "inout",
+    "int",
+    "integer",
+    "intersect",
+    "interval",
+    "into",
+    "is",
+    "isnull",
+    "join",
+    "lateral",
+    "leading",
+    "least",
+    "left",
+    "like",
+    "limit",
+    "localtime",
+    "localtimestamp",
+    "national",
+    "natural",
+    "nchar",
+    "none",
+    "normalize",
+    "not",
+    "notnull",
+    "null",
+    "nullif",
+    "numeric",
+    "offset",
+    "on",
+    "only",
+    "option",
+    "or",
+    "order",
+    "out",
+    "outer",
+    "overlaps",
+    "overlay",
+    "placing",
+    "position",
+    "precision",
+    "primary",
+    "real",
+    "references",
+    "returning",
+    "right",
+    "row",
+    "select",
+    "session_user",
+    "setof",
+    "similar",
+    "smallint",
+    "some",
+    "substring",
+    "symmetric",
+    "table",
+    "tablesample",
+    "then",
+    "time",
+    "timestamp",
+    "to",
+    "trailing",
+    "treat",
+    "trim",
+    "true",
+    "union",
+    "unique",
+    "user",
+    "using",
+    "values",
+    "varchar",
+    "variadic",
+    "verbose",
+    "when",
+    "where",
+    "window",
+    "with",
+    "xmlattributes",
+    "xmlconcat",
+    "xmlelement",
+    "xmlexists",
+    "xmlforest",
+    "xmlnamespaces",
+    "xmlparse",
+    "xmlpi",
+    "xmlroot",
+    "xmlserialize",
+    "xmltable",
+];
+
+/// Quotes `s` for use as an SQL identifier, if necessary.
+///
+/// Like PostgreSQL's `quote_ident`, identifiers are left unquoted only if
+/// they consist of lowercase letters, digits, and underscores, do not start
+/// with a digit, and are not reserved keywords.
+fn quote_ident(s: &str) -> String {
+    let mut chars = s.chars();
+    let safe = matches!(chars.next(), Some('a'..='z') | Some('_'))
+        && chars.all(|c| matches!(c, 'a'..='z' | '0'..='9' | '_'))
+        && QUOTED_KEYWORDS.binary_search(&s).is_err();
+    if safe {
+        s.to_owned()
+    } else {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    }
+}
+
+/// Quotes `s` for use as an SQL string literal.
+fn quote_literal(s: &str) -> String {
+    let mut out = String::with_capacity(s.len() + 2);
+    if s.contains('\\') {
+        out.push('E');
+    }
+    out.push('\'');
+    for c in s.chars() {
+        if c == '\'' || c == '\\' {
+            out.push(c);
+        }
+        out.push(c);
+    }
+    out.push('\'');
+    out
+}
+
 fn jsonb_build_array<'a>(datums: &[Datum<'a>], temp_storage: &'a RowArena) -> Datum<'a> {
     if datums.iter().any(|datum| datum.is_null()) {
         // the inputs should all be valid jsonb types, but a casting error might produce a Datum::Null that needs to be propagated
@@ -4898,36 +5588,40 @@ fn jsonb_build_object<'a>(datums: &[Datum<'a>], temp_storage: &'a RowArena) -> D
 /// For example, if given three 1D arrays of length 2, this function will
 /// construct a 2D array with dimensions 3x2.
 ///
-/// The input datums in `datums` must all be arrays of the same dimensions.
-/// (The arrays must also be of the same element type, but that is checked by
-/// the SQL type system, rather than checked here at runtime.)
+/// The input datums in `datums` must all be arrays of the same dimensions,
+/// or else all be null or zero-dimensional arrays. (The arrays must also be of
+/// the same element type, but that is checked by the SQL type system, rather
+/// than checked here at runtime.)
 ///
-/// If all input arrays are zero-dimensional arrays, then the output is a zero-
-/// dimensional array. Otherwise the lower bound of the additional dimension is
-/// one and the length of the new dimension is equal to `datums.len()`.
+/// If all input arrays are null or zero-dimensional arrays, then the output is
+/// a zero-dimensional array. Otherwise the lower bound of the additional
+/// dimension is one and the length of the new dimension is equal to
+/// `datums.len()`.
 fn array_create_multidim<'a>(
     datums: &[Datum<'a>],
     temp_storage: &'a RowArena,
 ) -> Result<Datum<'a>, EvalError> {
-    // Per PostgreSQL, if all input arrays are zero dimensional, so is the
-    // output.
-    if datums.iter().all(|d| d.unwrap_array().dims().is_empty()) {
-        let dims = &[];
-        let datums = &[];
-        let datum = temp_storage.try_make_datum(|packer| packer.push_array(dims, datums))?;
-        return Ok(datum);
+    // Per PostgreSQL, null input arrays are treated as zero dimensional, and
+    // if all input arrays are zero dimensional, so is the output.
+    let arrays: Vec<_> = datums
+        .iter()
+        .filter(|d| !d.is_null())
+        .map(|d| d.unwrap_array())
+        .filter(|a| !a.dims().is_empty())
+        .collect();
+    if arrays.is_empty() {
+        return array_create_scalar(&[], temp_storage);
+    }
+    if arrays.len() != datums.len() || arrays.iter().any(|a| a.dims() != arrays[0].dims()) {
+        return Err(EvalError::MismatchedArrayDimensions);
     }
 
     let mut dims = vec![ArrayDimension {
         lower_bound: 1,
         length: datums.len(),
     }];
-    if let Some(d) = datums.first() {
-        dims.extend(d.unwrap_array().dims());
-    };
-    let elements = datums
-        .iter()
-        .flat_map(|d| d.unwrap_array().elements().iter());
+    dims.extend(arrays[0].dims());
+    let elements = arrays.iter().flat_map(|a| a.elements().iter());
     let datum = temp_storage.try_make_datum(move |packer| packer.push_array(&dims, elements))?;
     Ok(datum)
 }
@@ -4959,6 +5653,7 @@ fn array_create_scalar<'a>(
 fn array_to_string<'a>(
     datums: &[Datum<'a>],
     elem_type: &ScalarType,
+    enum_labels: &EnumLabels,
     temp_storage: &'a RowArena,
 ) -> Result<Datum<'a>, EvalError> {
     if datums[0].is_null() || datums[1].is_null() {
@@ -4979,7 +5674,7 @@ fn array_to_string<'a>(
                 out.push_str(delimiter);
             }
         } else {
-            stringify_datum(&mut out, elem, elem_type);
+            stringify_datum(&mut out, elem, elem_type, enum_labels);
             out.push_str(delimiter);
         }
     }
@@ -4994,14 +5689,20 @@ fn list_create<'a>(datums: &[Datum<'a>], temp_storage: &'a RowArena) -> Datum<'a
 fn cast_collection_to_string<'a>(
     a: Datum,
     ty: &ScalarType,
+    enum_labels: &EnumLabels,
     temp_storage: &'a RowArena,
 ) -> Datum<'a> {
     let mut buf = String::new();
-    stringify_datum(&mut buf, a, ty);
+    stringify_datum(&mut buf, a, ty, enum_labels);
     Datum::String(temp_storage.push_string(buf))
 }
 
-fn stringify_datum<'a, B>(buf: &mut B, d: Datum<'a>, ty: &ScalarType) -> strconv::Nestable
+fn stringify_datum<'a, B>(
+    buf: &mut B,
+    d: Datum<'a>,
+    ty: &ScalarType,
+    enum_labels: &EnumLabels,
+) -> strconv::Nestable
 where
     B: FormatBuffer,
 {
@@ -5034,6 +5735,9 @@ where
         ),
         Jsonb => strconv::format_jsonb(buf, JsonbRef::from_datum(d)),
         Uuid => strconv::format_uuid(buf, d.unwrap_uuid()),
+        Enum { custom_oid } => {
+            strconv::format_string(buf, enum_labels.label(*custom_oid, d.unwrap_int32()))
+        }
         Record { fields, .. } => {
             let mut fields = fields.iter();
             strconv::format_record(buf, &d.unwrap_list(), |buf, d| {
@@ -5041,7 +5745,7 @@ where
                 if d.is_null() {
                     buf.write_null()
                 } else {
-                    stringify_datum(buf.nonnull_buffer(), d, &ty.scalar_type)
+                    stringify_datum(buf.nonnull_buffer(), d, &ty.scalar_type, enum_labels)
                 }
             })
         }
@@ -5053,7 +5757,7 @@ where
                 if d.is_null() {
                     buf.write_null()
                 } else {
-                    stringify_datum(buf.nonnull_buffer(), d, elem_type)
+                    stringify_datum(buf.nonnull_buffer(), d, elem_type, enum_labels)
                 }
             },
         ),
@@ -5061,19 +5765,58 @@ where
             if d.is_null() {
                 buf.write_null()
             } else {
-                stringify_datum(buf.nonnull_buffer(), d, element_type)
+                stringify_datum(buf.nonnull_buffer(), d, element_type, enum_labels)
             }
         }),
         Map { value_type, .. } => strconv::format_map(buf, &d.unwrap_map(), |buf, d| {
             if d.is_null() {
                 buf.write_null()
             } else {
-                stringify_datum(buf.nonnull_buffer(), d, value_type)
+                stringify_datum(buf.nonnull_buffer(), d, value_type, enum_labels)
             }
         }),
+        Range { element_type } => {
+            strconv::format_range(buf, d.unwrap_range().bounds(), |buf, d| {
+                stringify_datum(buf, d, element_type, enum_labels)
+            })
+        }
     }
 }
 
+fn range_create<'a>(
+    datums: &[Datum<'a>],
+    temp_storage: &'a RowArena,
+) -> Result<Datum<'a>, EvalError> {
+    let flags = match datums.get(2) {
+        None => "[)",
+        Some(Datum::Null) => {
+            return Err(EvalError::InvalidParameterValue(
+                "range constructor flags argument must not be null".into(),
+            ))
+        }
+        Some(d) => d.unwrap_str(),
+    };
+    let (lower_inclusive, upper_inclusive) = match flags {
+        "[)" => (true, false),
+        "[]" => (true, true),
+        "()" => (false, false),
+        "(]" => (false, true),
+        _ => {
+            return Err(EvalError::InvalidParameterValue(
+                "invalid range bound flags".into(),
+            ))
+        }
+    };
+    // Null bounds indicate that the range is unbounded in that direction.
+    let bound = |d: Datum<'a>, inclusive| RangeBound {
+        value: if d.is_null() { None } else { Some(d) },
+        inclusive,
+    };
+    let lower = bound(datums[0], lower_inclusive);
+    let upper = bound(datums[1], upper_inclusive);
+    temp_storage.try_make_datum(|packer| packer.push_range(lower, upper).err_into())
+}
+
 fn list_slice<'a>(datums: &[Datum<'a>], temp_storage: &'a RowArena) -> Datum<'a> {
     // Return value indicates whether this level's slices are empty results.
     fn slice_and_descend(d: Datum, ranges: &[(usize, usize)], row: &mut Row) -> bool {
@@ -5155,6 +5898,60 @@ fn list_length(a: Datum) -> Datum {
     Datum::Int64(a.unwrap_list().iter().count() as i64)
 }
 
+/// Stacks a list of arrays into a single array with one more dimension, as
+/// `array_agg` does for array inputs.
+///
+/// Like PostgreSQL, this requires every array to be non-null, non-empty and of
+/// the same dimensions. An empty list produces null.
+fn stack_arrays<'a>(a: Datum<'a>, temp_storage: &'a RowArena) -> Result<Datum<'a>, EvalError> {
+    let mut arrays: Vec<Array> = vec![];
+    for datum in a.unwrap_list().iter() {
+        if datum.is_null() {
+            return Err(EvalError::NullArrayAccumulation);
+        }
+        let array = datum.unwrap_array();
+        if array.dims().is_empty() {
+            return Err(EvalError::EmptyArrayAccumulation);
+        }
+        if let Some(first) = arrays.first() {
+            if array.dims() != first.dims() {
+                return Err(EvalError::MismatchedArrayAccumulation);
+            }
+        }
+        arrays.push(array);
+    }
+    if arrays.is_empty() {
+        return Ok(Datum::Null);
+    }
+
+    let mut dims = vec![ArrayDimension {
+        lower_bound: 1,
+        length: arrays.len(),
+    }];
+    dims.extend(arrays[0].dims());
+    let elements = arrays.iter().flat_map(|a| a.elements().iter());
+    let datum = temp_storage.try_make_datum(move |packer| packer.push_array(&dims, elements))?;
+    Ok(datum)
+}
+
+fn range_lower(a: Datum) -> Datum {
+    match a.unwrap_range().lower().and_then(|b| b.value) {
+        Some(d) => d,
+        None => Datum::Null,
+    }
+}
+
+fn range_upper(a: Datum) -> Datum {
+    match a.unwrap_range().upper().and_then(|b| b.value) {
+        Some(d) => d,
+        None => Datum::Null,
+    }
+}
+
+fn range_is_empty(a: Datum) -> Datum {
+    Datum::from(a.unwrap_range().is_empty())
+}
+
 fn upper<'a>(a: Datum<'a>, temp_storage: &'a RowArena) -> Datum<'a> {
     Datum::String(temp_storage.push_string(a.unwrap_str().to_owned().to_uppercase()))
 }
@@ -5163,6 +5960,23 @@ fn lower<'a>(a: Datum<'a>, temp_storage: &'a RowArena) -> Datum<'a> {
     Datum::String(temp_storage.push_string(a.unwrap_str().to_owned().to_lowercase()))
 }
 
+fn initcap<'a>(a: Datum<'a>, temp_storage: &'a RowArena) -> Datum<'a> {
+    // Like PostgreSQL, words are maximal sequences of alphanumeric characters.
+    // The first character of each word is converted to uppercase, and the
+    // remaining characters to lowercase.
+    let mut out = String::new();
+    let mut in_word = false;
+    for c in a.unwrap_str().chars() {
+        if in_word {
+            out.extend(c.to_lowercase());
+        } else {
+            out.extend(c.to_uppercase());
+        }
+        in_word = c.is_alphanumeric();
+    }
+    Datum::String(temp_storage.push_string(out))
+}
+
 fn make_timestamp<'a>(datums: &[Datum<'a>]) -> Datum<'a> {
     let year: i32 = match datums[0].unwrap_int64().try_into() {
         Ok(year) => year,
@@ -5275,6 +6089,10 @@ fn right<'a>(a: Datum<'a>, b: Datum<'a>) -> Result<Datum<'a>, EvalError> {
     Ok(Datum::String(&string[start_in_bytes..]))
 }
 
+fn starts_with<'a>(a: Datum<'a>, b: Datum<'a>) -> Datum<'a> {
+    Datum::from(a.unwrap_str().starts_with(b.unwrap_str()))
+}
+
 fn trim<'a>(a: Datum<'a>, b: Datum<'a>) -> Datum<'a> {
     let trim_chars = b.unwrap_str();
 
@@ -5398,6 +6216,273 @@ fn array_contains<'a>(a: Datum<'a>, b: Datum<'a>) -> Datum<'a> {
     Datum::from(array.elements().iter().any(|e| e == a))
 }
 
+// Like PostgreSQL, the array containment and overlap functions ignore the
+// dimensions of their inputs and consider null elements to be unequal to all
+// other elements.
+
+fn array_contains_array<'a>(a: Datum<'a>, b: Datum<'a>) -> Datum<'a> {
+    let a = a.unwrap_array().elements();
+    let b = b.unwrap_array().elements();
+    Datum::from(
+        b.iter()
+            .all(|b_elem| !b_elem.is_null() && a.iter().any(|a_elem| a_elem == b_elem)),
+    )
+}
+
+fn array_overlaps<'a>(a: Datum<'a>, b: Datum<'a>) -> Datum<'a> {
+    let a = a.unwrap_array().elements();
+    let b = b.unwrap_array().elements();
+    Datum::from(
+        a.iter()
+            .any(|a_elem| !a_elem.is_null() && b.iter().any(|b_elem| a_elem == b_elem)),
+    )
+}
+
+fn array_array_concat<'a>(
+    a: Datum<'a>,
+    b: Datum<'a>,
+    temp_storage: &'a RowArena,
+) -> Result<Datum<'a>, EvalError> {
+    if a.is_null() {
+        return Ok(b);
+    } else if b.is_null() {
+        return Ok(a);
+    }
+
+    let a_array = a.unwrap_array();
+    let b_array = b.unwrap_array();
+    let a_dims: Vec<_> = a_array.dims().into_iter().collect();
+    let b_dims: Vec<_> = b_array.dims().into_iter().collect();
+
+    // Per PostgreSQL, concatenating an empty array with another array produces
+    // the other array.
+    if a_dims.is_empty() {
+        return Ok(b);
+    } else if b_dims.is_empty() {
+        return Ok(a);
+    }
+
+    // Arrays with the same number of dimensions are concatenated along their
+    // outermost dimension. Otherwise, the array with fewer dimensions is
+    // appended or prepended as a new element of the other array's outermost
+    // dimension. Either way, the inner dimensions of the two arrays must agree.
+    let (outer, inner, added) = if a_dims.len() == b_dims.len() {
+        (a_dims[0], &a_dims[1..], b_dims[0].length)
+    } else if a_dims.len() == b_dims.len() + 1 {
+        (a_dims[0], &a_dims[1..], 1)
+    } else if a_dims.len() + 1 == b_dims.len() {
+        (b_dims[0], &b_dims[1..], 1)
+    } else {
+        return Err(EvalError::IncompatibleArrayDimensions {
+            dims: Some((a_dims.len(), b_dims.len())),
+        });
+    };
+    let a_inner = &a_dims[a_dims.len() - inner.len()..];
+    let b_inner = &b_dims[b_dims.len() - inner.len()..];
+    if a_inner != inner || b_inner != inner {
+        return Err(EvalError::IncompatibleArrayDimensions { dims: None });
+    }
+
+    let mut dims = vec![ArrayDimension {
+        lower_bound: outer.lower_bound,
+        length: outer.length + added,
+    }];
+    dims.extend(inner);
+    let elements = a_array.elements().iter().chain(b_array.elements().iter());
+    let datum = temp_storage.try_make_datum(|packer| packer.push_array(&dims, elements))?;
+    Ok(datum)
+}
+
+/// Returns the elements of `a`, which must be a null, zero-dimensional, or
+/// one-dimensional array, as for `array_append` and `array_prepend`.
+fn unwrap_one_dimensional_array<'a>(a: Datum<'a>) -> Result<Vec<Datum<'a>>, EvalError> {
+    if a.is_null() {
+        return Ok(vec![]);
+    }
+    let array = a.unwrap_array();
+    if array.dims().len() > 1 {
+        return Err(EvalError::InvalidParameterValue(
+            "argument must be empty or one-dimensional array".into(),
+        ));
+    }
+    Ok(array.elements().iter().collect())
+}
+
+fn array_element_concat<'a>(
+    a: Datum<'a>,
+    b: Datum<'a>,
+    temp_storage: &'a RowArena,
+) -> Result<Datum<'a>, EvalError> {
+    let mut elements = unwrap_one_dimensional_array(a)?;
+    elements.push(b);
+    array_create_scalar(&elements, temp_storage)
+}
+
+fn element_array_concat<'a>(
+    a: Datum<'a>,
+    b: Datum<'a>,
+    temp_storage: &'a RowArena,
+) -> Result<Datum<'a>, EvalError> {
+    let mut elements = vec![a];
+    elements.extend(unwrap_one_dimensional_array(b)?);
+    array_create_scalar(&elements, temp_storage)
+}
+
+fn array_remove<'a>(
+    a: Datum<'a>,
+    b: Datum<'a>,
+    temp_storage: &'a RowArena,
+) -> Result<Datum<'a>, EvalError> {
+    if a.is_null() {
+        return Ok(a);
+    }
+    let array = a.unwrap_array();
+    if array.dims().len() > 1 {
+        return Err(EvalError::MultidimensionalArrayRemovalNotSupported);
+    }
+    // Elements are compared as if by `IS NOT DISTINCT FROM`, so a null `b`
+    // removes null elements.
+    let elements: Vec<_> = array.elements().iter().filter(|e| *e != b).collect();
+    array_create_scalar(&elements, temp_storage)
+}
+
+fn array_positions<'a>(
+    a: Datum<'a>,
+    b: Datum<'a>,
+    temp_storage: &'a RowArena,
+) -> Result<Datum<'a>, EvalError> {
+    if a.is_null() {
+        return Ok(a);
+    }
+    let array = a.unwrap_array();
+    if array.dims().len() > 1 {
+        return Err(EvalError::MultidimensionalArraySearchNotSupported);
+    }
+    let positions = array
+        .elements()
+        .iter()
+        .enumerate()
+        .filter(|(_, e)| *e == b)
+        .map(|(i, _)| i32::try_from(i + 1).map(Datum::Int32))
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|_| EvalError::Int32OutOfRange)?;
+    array_create_scalar(&positions, temp_storage)
+}
+
+fn array_position<'a>(datums: &[Datum<'a>]) -> Result<Datum<'a>, EvalError> {
+    if datums[0].is_null() {
+        return Ok(Datum::Null);
+    }
+    let array = datums[0].unwrap_array();
+    if array.dims().len() > 1 {
+        return Err(EvalError::MultidimensionalArraySearchNotSupported);
+    }
+    let skip = match datums.get(2) {
+        None => 0,
+        Some(Datum::Null) => {
+            return Err(EvalError::InvalidParameterValue(
+                "initial position must not be null".into(),
+            ))
+        }
+        Some(d) => usize::try_from(d.unwrap_int32()).map_or(0, |start| start.saturating_sub(1)),
+    };
+    let position = array
+        .elements()
+        .iter()
+        .enumerate()
+        .skip(skip)
+        .find(|(_, e)| *e == datums[1]);
+    match position {
+        None => Ok(Datum::Null),
+        Some((i, _)) => i32::try_from(i + 1)
+            .map(Datum::Int32)
+            .map_err(|_| EvalError::Int32OutOfRange),
+    }
+}
+
+fn array_slice<'a>(
+    datums: &[Datum<'a>],
+    temp_storage: &'a RowArena,
+) -> Result<Datum<'a>, EvalError> {
+    assert_eq!(
+        datums.len() % 2,
+        1,
+        "expr::scalar::func::array_slice expects an odd number of arguments; 1 for array + 2 \
+        for each start-end pair"
+    );
+
+    let array = datums[0].unwrap_array();
+    let dims: Vec<_> = array.dims().into_iter().collect();
+    let positions: Vec<_> = datums[1..].iter().tuples::<(_, _)>().collect();
+
+    // Per PostgreSQL, slicing more dimensions than the array has produces an
+    // empty array.
+    if dims.is_empty() || positions.len() > dims.len() {
+        return array_create_scalar(&[], temp_storage);
+    }
+
+    // Clamp each position to the bounds of its dimension, producing the
+    // zero-based offset and length of the slice in that dimension. Dimensions
+    // without a position are included in their entirety.
+    let mut ranges = Vec::with_capacity(dims.len());
+    for (i, dim) in dims.iter().enumerate() {
+        let lower = dim.lower_bound as i64;
+        let upper = lower + dim.length as i64 - 1;
+        let (start, end) = match positions.get(i) {
+            Some((start, end)) => (
+                cmp::max(start.unwrap_int64(), lower),
+                cmp::min(end.unwrap_int64(), upper),
+            ),
+            None => (lower, upper),
+        };
+        if start > end {
+            return array_create_scalar(&[], temp_storage);
+        }
+        ranges.push(((start - lower) as usize, (end - start + 1) as usize));
+    }
+
+    // Elements are stored in row-major order, so recover each element's
+    // coordinates from its index, starting with the innermost dimension.
+    let elements = array
+        .elements()
+        .iter()
+        .enumerate()
+        .filter_map(|(mut i, elem)| {
+            let mut in_slice = true;
+            for (dim, (offset, length)) in dims.iter().zip(&ranges).rev() {
+                let coord = i % dim.length;
+                i /= dim.length;
+                in_slice &= coord >= *offset && coord < offset + length;
+            }
+            if in_slice {
+                Some(elem)
+            } else {
+                None
+            }
+        });
+    let slice_dims: Vec<_> = ranges
+        .iter()
+        .map(|(_, length)| ArrayDimension {
+            lower_bound: 1,
+            length: *length,
+        })
+        .collect();
+    let datum = temp_storage.try_make_datum(|packer| packer.push_array(&slice_dims, elements))?;
+    Ok(datum)
+}
+
+fn range_contains_elem<'a>(a: Datum<'a>, b: Datum<'a>) -> Datum<'a> {
+    Datum::from(a.unwrap_range().contains_elem(b))
+}
+
+fn range_contains_range<'a>(a: Datum<'a>, b: Datum<'a>) -> Datum<'a> {
+    Datum::from(a.unwrap_range().contains_range(&b.unwrap_range()))
+}
+
+fn range_overlaps<'a>(a: Datum<'a>, b: Datum<'a>) -> Datum<'a> {
+    Datum::from(a.unwrap_range().overlaps(&b.unwrap_range()))
+}
+
 fn list_list_concat<'a>(a: Datum<'a>, b: Datum<'a>, temp_storage: &'a RowArena) -> Datum<'a> {
     if a.is_null() {
         return b;
@@ -5517,6 +6602,9 @@ pub enum VariadicFunc {
     },
     ArrayToString {
         elem_type: ScalarType,
+        // The labels of the enum types within `elem_type`, as looked up in
+        // the catalog during planning.
+        enum_labels: EnumLabels,
     },
     ListCreate {
         // We need to know the element type to type empty lists.
@@ -5525,9 +6613,19 @@ pub enum VariadicFunc {
     RecordCreate {
         field_names: Vec<ColumnName>,
     },
+    RangeCreate {
+        elem_type: ScalarType,
+    },
     ListSlice,
+    ArraySlice,
+    ArrayPosition,
     SplitPart,
     RegexpMatch,
+    RegexpReplace,
+    RegexpSplitToArray,
+    StringToArray,
+    Translate,
+    Format,
     HmacString,
     HmacBytes,
     ErrorIfNull,
@@ -5565,15 +6663,24 @@ impl VariadicFunc {
                 elem_type: ScalarType::Array(_),
             } => eager!(array_create_multidim, temp_storage),
             VariadicFunc::ArrayCreate { .. } => eager!(array_create_scalar, temp_storage),
-            VariadicFunc::ArrayToString { elem_type } => {
-                eager!(array_to_string, elem_type, temp_storage)
-            }
+            VariadicFunc::ArrayToString {
+                elem_type,
+                enum_labels,
+            } => eager!(array_to_string, elem_type, enum_labels, temp_storage),
             VariadicFunc::ListCreate { .. } | VariadicFunc::RecordCreate { .. } => {
                 Ok(eager!(list_create, temp_storage))
             }
+            VariadicFunc::RangeCreate { .. } => eager!(range_create, temp_storage),
             VariadicFunc::ListSlice => Ok(eager!(list_slice, temp_storage)),
+            VariadicFunc::ArraySlice => eager!(array_slice, temp_storage),
+            VariadicFunc::ArrayPosition => eager!(array_position),
             VariadicFunc::SplitPart => eager!(split_part),
             VariadicFunc::RegexpMatch => eager!(regexp_match_dynamic, temp_storage),
+            VariadicFunc::RegexpReplace => eager!(regexp_replace, temp_storage),
+            VariadicFunc::RegexpSplitToArray => eager!(regexp_split_to_array, temp_storage),
+            VariadicFunc::StringToArray => eager!(string_to_array, temp_storage),
+            VariadicFunc::Translate => Ok(eager!(translate, temp_storage)),
+            VariadicFunc::Format => eager!(format, temp_storage),
             VariadicFunc::HmacString => eager!(hmac_string, temp_storage),
             VariadicFunc::HmacBytes => eager!(hmac_bytes, temp_storage),
             VariadicFunc::ErrorIfNull => error_if_null(datums, temp_storage, exprs),
@@ -5623,6 +6730,8 @@ impl VariadicFunc {
                 .nullable(false)
             }
             ListSlice { .. } => input_types[0].scalar_type.clone().nullable(true),
+            ArraySlice => input_types[0].scalar_type.clone().nullable(true),
+            ArrayPosition => ScalarType::Int32.nullable(true),
             RecordCreate { field_names } => ScalarType::Record {
                 fields: field_names
                     .clone()
@@ -5633,8 +6742,16 @@ impl VariadicFunc {
                 custom_name: None,
             }
             .nullable(true),
+            RangeCreate { elem_type } => ScalarType::Range {
+                element_type: Box::new(elem_type.clone()),
+            }
+            .nullable(false),
             SplitPart => ScalarType::String.nullable(true),
             RegexpMatch => ScalarType::Array(Box::new(ScalarType::String)).nullable(true),
+            RegexpReplace | Translate | Format => ScalarType::String.nullable(true),
+            RegexpSplitToArray | StringToArray => {
+                ScalarType::Array(Box::new(ScalarType::String)).nullable(true)
+            }
             HmacString | HmacBytes => ScalarType::Bytes.nullable(true),
             ErrorIfNull => input_types[0].scalar_type.clone().nullable(false),
         }
@@ -5650,8 +6767,12 @@ impl VariadicFunc {
                 | VariadicFunc::JsonbBuildObject
                 | VariadicFunc::ListCreate { .. }
                 | VariadicFunc::RecordCreate { .. }
+                | VariadicFunc::RangeCreate { .. }
                 | VariadicFunc::ArrayCreate { .. }
                 | VariadicFunc::ArrayToString { .. }
+                | VariadicFunc::StringToArray
+                | VariadicFunc::Format
+                | VariadicFunc::ArrayPosition
         )
     }
 }
@@ -5671,9 +6792,17 @@ impl fmt::Display for VariadicFunc {
             VariadicFunc::ArrayToString { .. } => f.write_str("array_to_string"),
             VariadicFunc::ListCreate { .. } => f.write_str("list_create"),
             VariadicFunc::RecordCreate { .. } => f.write_str("record_create"),
+            VariadicFunc::RangeCreate { .. } => f.write_str("range_create"),
             VariadicFunc::ListSlice => f.write_str("list_slice"),
+            VariadicFunc::ArraySlice => f.write_str("array_slice"),
+            VariadicFunc::ArrayPosition => f.write_str("array_position"),
             VariadicFunc::SplitPart => f.write_str("split_string"),
             VariadicFunc::RegexpMatch => f.write_str("regexp_match"),
+            VariadicFunc::RegexpReplace => f.write_str("regexp_replace"),
+            VariadicFunc::RegexpSplitToArray => f.write_str("regexp_split_to_array"),
+            VariadicFunc::StringToArray => f.write_str("string_to_array"),
+            VariadicFunc::Translate => f.write_str("translate"),
+            VariadicFunc::Format => f.write_str("format"),
             VariadicFunc::HmacString | VariadicFunc::HmacBytes => f.write_str("hmac"),
             VariadicFunc::ErrorIfNull => f.write_str("error_if_null"),
         }
@@ -5686,6 +6815,12 @@ mod test {
 
     use super::*;
 
+    #[test]
+    fn quoted_keywords_sorted() {
+        // `quote_ident` binary searches the list.
+        assert!(QUOTED_KEYWORDS.windows(2).all(|w| w[0] < w[1]));
+    }
+
     #[test]
     fn add_interval_months() {
         let dt = ym(2000, 1);
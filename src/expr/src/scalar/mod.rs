@@ -15,9 +15,10 @@ use serde::{Deserialize, Serialize};
 
 use lowertest::MzEnumReflect;
 use ore::collections::CollectionExt;
-use ore::str::separated;
+use ore::str::{separated, StrExt};
 use repr::adt::array::InvalidArrayError;
 use repr::adt::datetime::DateTimeUnits;
+use repr::adt::range::InvalidRangeError;
 use repr::adt::regex::Regex;
 use repr::strconv::{ParseError, ParseHexError};
 use repr::{ColumnType, Datum, RelationType, Row, RowArena, ScalarType};
@@ -1011,7 +1012,17 @@ pub enum EvalError {
         val: i64,
     },
     InvalidArray(InvalidArrayError),
+    IncompatibleArrayDimensions {
+        dims: Option<(usize, usize)>,
+    },
+    MismatchedArrayDimensions,
+    NullArrayAccumulation,
+    EmptyArrayAccumulation,
+    MismatchedArrayAccumulation,
+    MultidimensionalArrayRemovalNotSupported,
+    MultidimensionalArraySearchNotSupported,
     InvalidEncodingName(String),
+    InvalidEnumLabel(String),
     InvalidHashAlgorithm(String),
     InvalidByteSequence {
         byte_sequence: String,
@@ -1024,6 +1035,7 @@ pub enum EvalError {
     InvalidRegex(String),
     InvalidRegexFlag(char),
     InvalidParameterValue(String),
+    InvalidRange(InvalidRangeError),
     NegSqrt,
     UnknownUnits(String),
     UnsupportedDateTimeUnits(DateTimeUnits),
@@ -1081,7 +1093,27 @@ impl fmt::Display for EvalError {
                 val, max_dim
             ),
             EvalError::InvalidArray(e) => e.fmt(f),
+            EvalError::IncompatibleArrayDimensions { .. } => {
+                f.write_str("cannot concatenate incompatible arrays")
+            }
+            EvalError::MismatchedArrayDimensions => f.write_str(
+                "multidimensional arrays must have array expressions with matching dimensions",
+            ),
+            EvalError::NullArrayAccumulation => f.write_str("cannot accumulate null arrays"),
+            EvalError::EmptyArrayAccumulation => f.write_str("cannot accumulate empty arrays"),
+            EvalError::MismatchedArrayAccumulation => {
+                f.write_str("cannot accumulate arrays of different dimensionality")
+            }
+            EvalError::MultidimensionalArrayRemovalNotSupported => {
+                f.write_str("removing elements from multidimensional arrays is not supported")
+            }
+            EvalError::MultidimensionalArraySearchNotSupported => {
+                f.write_str("searching for elements in multidimensional arrays is not supported")
+            }
             EvalError::InvalidEncodingName(name) => write!(f, "invalid encoding name '{}'", name),
+            EvalError::InvalidEnumLabel(label) => {
+                write!(f, "invalid input value for enum: {}", label.quoted())
+            }
             EvalError::InvalidHashAlgorithm(alg) => write!(f, "invalid hash algorithm '{}'", alg),
             EvalError::InvalidByteSequence {
                 byte_sequence,
@@ -1095,6 +1127,7 @@ impl fmt::Display for EvalError {
             EvalError::InvalidRegex(e) => write!(f, "invalid regular expression: {}", e),
             EvalError::InvalidRegexFlag(c) => write!(f, "invalid regular expression flag: {}", c),
             EvalError::InvalidParameterValue(s) => f.write_str(s),
+            EvalError::InvalidRange(e) => e.fmt(f),
             EvalError::UnknownUnits(units) => write!(f, "unknown units '{}'", units),
             EvalError::UnsupportedDateTimeUnits(units) => {
                 write!(f, "unsupported timestamp units '{}'", units)
@@ -1138,7 +1171,18 @@ impl fmt::Display for EvalError {
 
 impl EvalError {
     pub fn detail(&self) -> Option<String> {
-        None
+        match self {
+            EvalError::IncompatibleArrayDimensions { dims: None } => Some(
+                "Arrays with differing dimensions are not compatible for concatenation.".into(),
+            ),
+            EvalError::IncompatibleArrayDimensions {
+                dims: Some((a_dims, b_dims)),
+            } => Some(format!(
+                "Arrays of {} and {} dimensions are not compatible for concatenation.",
+                a_dims, b_dims
+            )),
+            _ => None,
+        }
     }
 
     pub fn hint(&self) -> Option<String> {
@@ -1171,6 +1215,12 @@ impl From<InvalidArrayError> for EvalError {
     }
 }
 
+impl From<InvalidRangeError> for EvalError {
+    fn from(e: InvalidRangeError) -> EvalError {
+        EvalError::InvalidRange(e)
+    }
+}
+
 impl From<regex::Error> for EvalError {
     fn from(e: regex::Error) -> EvalError {
         EvalError::InvalidRegex(e.to_string())
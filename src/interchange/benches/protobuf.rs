@@ -67,6 +67,7 @@ pub fn bench_protobuf(c: &mut Criterion) {
     let mut decoder = Decoder::new(
         Descriptors::from_proto(&gen::file_descriptor_set()),
         ".bench.Record",
+        false,
     );
 
     let mut bg = c.benchmark_group("protobuf");
@@ -52,6 +52,7 @@ mod tests {
     use std::fs::File;
 
     use mz_avro::types::{DecimalValue, Value};
+    use repr::adt::enums::EnumLabels;
     use repr::adt::numeric;
     use repr::{ColumnName, ColumnType, Datum, RelationDesc, ScalarType};
 
@@ -159,12 +160,22 @@ mod tests {
                 Datum::String(&string),
                 Value::String(string.clone()),
             ),
+            (
+                ScalarType::Enum { custom_oid: 20_000 },
+                Datum::Int32(1),
+                Value::String("sad".into()),
+            ),
         ];
+        let mut enum_labels = EnumLabels::default();
+        enum_labels.insert(20_000, vec!["ok".into(), "sad".into()]);
         for (typ, datum, expected) in valid_pairings {
             let desc = RelationDesc::empty().with_named_column("column1", typ.nullable(false));
             let schema_generator = AvroSchemaGenerator::new(None, desc, false);
-            let avro_value =
-                encode_datums_as_avro(std::iter::once(datum), schema_generator.value_columns());
+            let avro_value = encode_datums_as_avro(
+                std::iter::once(datum),
+                schema_generator.value_columns(),
+                &enum_labels,
+            );
             assert_eq!(
                 Value::Record(vec![("column1".into(), expected)]),
                 avro_value
@@ -14,12 +14,13 @@ use chrono::Timelike;
 use itertools::Itertools;
 use lazy_static::lazy_static;
 use mz_avro::types::AvroMap;
+use repr::adt::enums::EnumLabels;
 use repr::adt::jsonb::JsonbRef;
 use repr::adt::numeric::{self, NUMERIC_AGG_MAX_PRECISION, NUMERIC_DATUM_MAX_PRECISION};
 use repr::{ColumnName, ColumnType, Datum, RelationDesc, Row, ScalarType};
 use serde_json::json;
 
-use crate::encode::{column_names_and_types, Encode, TypedDatum};
+use crate::encode::{column_names_and_types, format_range, Encode, TypedDatum};
 use crate::json::build_row_schema_json;
 use mz_avro::types::{DecimalValue, Value};
 use mz_avro::Schema;
@@ -92,7 +93,7 @@ fn build_schema(columns: &[(ColumnName, ColumnType)]) -> Schema {
     Schema::parse(&row_schema).expect("valid schema constructed")
 }
 
-fn encode_avro_header(buf: &mut Vec<u8>, schema_id: i32) {
+pub(super) fn encode_avro_header(buf: &mut Vec<u8>, schema_id: i32) {
     // The first byte is a magic byte (0) that indicates the Confluent
     // serialization format version, and the next four bytes are a
     // 32-bit schema ID.
@@ -113,10 +114,11 @@ fn encode_message_unchecked(
     row: Row,
     schema: &Schema,
     columns: &[(ColumnName, ColumnType)],
+    enum_labels: &EnumLabels,
 ) -> Vec<u8> {
     let mut buf = vec![];
     encode_avro_header(&mut buf, schema_id);
-    let value = encode_datums_as_avro(row.iter(), columns);
+    let value = encode_datums_as_avro(row.iter(), columns, enum_labels);
     mz_avro::encode_unchecked(&value, schema, &mut buf);
     buf
 }
@@ -207,6 +209,7 @@ pub struct AvroEncoder {
     schema_generator: AvroSchemaGenerator,
     key_schema_id: Option<i32>,
     value_schema_id: i32,
+    enum_labels: EnumLabels,
 }
 
 impl fmt::Debug for AvroEncoder {
@@ -222,24 +225,26 @@ impl AvroEncoder {
         schema_generator: AvroSchemaGenerator,
         key_schema_id: Option<i32>,
         value_schema_id: i32,
+        enum_labels: EnumLabels,
     ) -> Self {
         AvroEncoder {
             schema_generator,
             key_schema_id,
             value_schema_id,
+            enum_labels,
         }
     }
 
     pub fn encode_key_unchecked(&self, schema_id: i32, row: Row) -> Vec<u8> {
         let schema = self.schema_generator.key_writer_schema().unwrap();
         let columns = self.schema_generator.key_columns().unwrap();
-        encode_message_unchecked(schema_id, row, schema, columns)
+        encode_message_unchecked(schema_id, row, schema, columns, &self.enum_labels)
     }
 
     pub fn encode_value_unchecked(&self, schema_id: i32, row: Row) -> Vec<u8> {
         let schema = self.schema_generator.value_writer_schema();
         let columns = self.schema_generator.value_columns();
-        encode_message_unchecked(schema_id, row, schema, columns)
+        encode_message_unchecked(schema_id, row, schema, columns, &self.enum_labels)
     }
 }
 
@@ -258,7 +263,11 @@ impl Encode for AvroEncoder {
 }
 
 /// Encodes a sequence of `Datum` as Avro (key and value), using supplied column names and types.
-pub fn encode_datums_as_avro<'a, I>(datums: I, names_types: &[(ColumnName, ColumnType)]) -> Value
+pub fn encode_datums_as_avro<'a, I>(
+    datums: I,
+    names_types: &[(ColumnName, ColumnType)],
+    enum_labels: &EnumLabels,
+) -> Value
 where
     I: IntoIterator<Item = Datum<'a>>,
 {
@@ -268,7 +277,10 @@ where
         .map(|((name, typ), datum)| {
             let name = name.as_str().to_owned();
             use mz_avro::types::ToAvro;
-            (name, TypedDatum::new(datum, typ.clone()).avro())
+            (
+                name,
+                TypedDatum::new(datum, typ.clone(), enum_labels).avro(),
+            )
         })
         .collect();
     let v = Value::Record(value_fields);
@@ -277,7 +289,11 @@ where
 
 impl<'a> mz_avro::types::ToAvro for TypedDatum<'a> {
     fn avro(self) -> Value {
-        let TypedDatum { datum, typ } = self;
+        let TypedDatum {
+            datum,
+            typ,
+            enum_labels,
+        } = self;
         if typ.nullable && datum.is_null() {
             Value::Union {
                 index: 0,
@@ -353,6 +369,14 @@ impl<'a> mz_avro::types::ToAvro for TypedDatum<'a> {
                 }
                 ScalarType::Jsonb => Value::Json(JsonbRef::from_datum(datum).to_serde_json()),
                 ScalarType::Uuid => Value::Uuid(datum.unwrap_uuid()),
+                ScalarType::Enum { custom_oid } => Value::String(
+                    enum_labels
+                        .label(*custom_oid, datum.unwrap_int32())
+                        .to_owned(),
+                ),
+                ScalarType::Range { element_type } => {
+                    Value::String(format_range(datum, element_type))
+                }
                 ScalarType::Array(element_type) | ScalarType::List { element_type, .. } => {
                     let list = match typ.scalar_type {
                         ScalarType::Array(_) => datum.unwrap_array().elements(),
@@ -369,6 +393,7 @@ impl<'a> mz_avro::types::ToAvro for TypedDatum<'a> {
                                     nullable: true,
                                     scalar_type: (**element_type).clone(),
                                 },
+                                enum_labels,
                             );
                             datum.avro()
                         })
@@ -386,6 +411,7 @@ impl<'a> mz_avro::types::ToAvro for TypedDatum<'a> {
                                     nullable: true,
                                     scalar_type: (**value_type).clone(),
                                 },
+                                enum_labels,
                             );
                             let value = datum.avro();
                             (key.to_string(), value)
@@ -400,7 +426,7 @@ impl<'a> mz_avro::types::ToAvro for TypedDatum<'a> {
                         .zip(list.into_iter())
                         .map(|((name, typ), datum)| {
                             let name = name.to_string();
-                            let datum = TypedDatum::new(datum, typ.clone());
+                            let datum = TypedDatum::new(datum, typ.clone(), enum_labels);
                             let value = datum.avro();
                             (name, value)
                         })
@@ -10,6 +10,7 @@
 //! Logic for the Avro representation of the CDCv2 protocol.
 
 use mz_avro::schema::{FullName, SchemaNode};
+use repr::adt::enums::EnumLabels;
 use repr::{ColumnName, ColumnType, Diff, RelationDesc, Row, Timestamp};
 use serde_json::json;
 
@@ -25,14 +26,19 @@ use mz_avro::{
 };
 use std::{cell::RefCell, rc::Rc};
 
-use crate::encode::column_names_and_types;
+use crate::encode::{column_names_and_types, Encode};
 
+use super::encode::encode_avro_header;
 use super::RowWrapper;
 
+/// Finds the record of the data columns in `schema`, which is named
+/// `com.materialize.cdc.data`, or just `data` in the schemas that
+/// [`build_schema`] constructs.
 pub fn extract_data_columns<'a>(schema: &'a Schema) -> anyhow::Result<SchemaNode<'a>> {
     let data_name = FullName::from_parts("data", Some("com.materialize.cdc"), "");
     let data_schema = &schema
         .try_lookup_name(&data_name)
+        .or_else(|| schema.try_lookup_name(&FullName::from_parts("data", None, "")))
         .ok_or_else(|| anyhow!("record not found: {}", data_name))?
         .piece;
     Ok(SchemaNode {
@@ -47,22 +53,33 @@ pub fn extract_data_columns<'a>(schema: &'a Schema) -> anyhow::Result<SchemaNode
 pub struct Encoder {
     columns: Vec<(ColumnName, ColumnType)>,
     schema: Schema,
+    enum_labels: EnumLabels,
 }
 
 impl Encoder {
-    /// Creates a new CDCv2 encoder from a relation description.
-    pub fn new(desc: RelationDesc) -> Self {
+    /// Creates a new CDCv2 encoder from a relation description and the labels
+    /// of the enum types within it.
+    pub fn new(desc: RelationDesc, enum_labels: EnumLabels) -> Self {
         let columns = column_names_and_types(desc);
         let row_schema = super::build_row_schema_json(&columns, "data");
         let schema = build_schema(row_schema);
-        Self { columns, schema }
+        Self {
+            columns,
+            schema,
+            enum_labels,
+        }
+    }
+
+    /// Returns the Avro schema of the encoded messages.
+    pub fn writer_schema(&self) -> &Schema {
+        &self.schema
     }
 
     /// Encodes a batch of updates as an Avro value.
     pub fn encode_updates(&self, updates: &[(Row, i64, i64)]) -> Value {
         let mut enc_updates = Vec::new();
         for (data, time, diff) in updates {
-            let enc_data = super::encode_datums_as_avro(data, &self.columns);
+            let enc_data = super::encode_datums_as_avro(data, &self.columns, &self.enum_labels);
             let enc_time = Value::Long(time.clone());
             let enc_diff = Value::Long(diff.clone());
             enc_updates.push(Value::Record(vec![
@@ -109,6 +126,66 @@ impl Encoder {
     }
 }
 
+/// Encodes CDCv2 messages as Avro in the Confluent wire format, for use by
+/// sinks.
+///
+/// Each update is sent as a batch of its own. The rows handed to
+/// [`Encode::encode_value_unchecked`] must contain the time and the diff of
+/// the update, as `int8` datums, after the data columns.
+#[derive(Debug)]
+pub struct ConfluentEncoder {
+    encoder: Encoder,
+    schema_id: i32,
+}
+
+impl ConfluentEncoder {
+    /// Creates a new encoder for updates to the relation described by `desc`,
+    /// whose schema was published with ID `schema_id`.
+    pub fn new(desc: RelationDesc, enum_labels: EnumLabels, schema_id: i32) -> Self {
+        ConfluentEncoder {
+            encoder: Encoder::new(desc, enum_labels),
+            schema_id,
+        }
+    }
+
+    /// Encodes a progress statement.
+    pub fn encode_progress_unchecked(
+        &self,
+        lower: &[i64],
+        upper: &[i64],
+        counts: &[(i64, i64)],
+    ) -> Vec<u8> {
+        let value = self.encoder.encode_progress(lower, upper, counts);
+        self.encode_message(&value)
+    }
+
+    fn encode_message(&self, value: &Value) -> Vec<u8> {
+        let mut buf = vec![];
+        encode_avro_header(&mut buf, self.schema_id);
+        mz_avro::encode_unchecked(value, &self.encoder.schema, &mut buf);
+        buf
+    }
+}
+
+impl Encode for ConfluentEncoder {
+    fn get_format_name(&self) -> &str {
+        "avro-cdcv2"
+    }
+
+    fn encode_key_unchecked(&self, _row: Row) -> Vec<u8> {
+        unreachable!("CDCv2 messages do not have keys")
+    }
+
+    fn encode_value_unchecked(&self, row: Row) -> Vec<u8> {
+        let mut datums = row.unpack();
+        let diff = datums.pop().expect("missing diff").unwrap_int64();
+        let time = datums.pop().expect("missing time").unwrap_int64();
+        let data = Row::pack_slice(&datums);
+        let value = self.encoder.encode_updates(&[(data, time, diff)]);
+        self.encode_message(&value)
+    }
+}
+
 #[derive(AvroDecodable)]
 #[state_type(Rc<RefCell<Row>>, Rc<RefCell<Vec<u8>>>)]
 struct MyUpdate {
@@ -271,11 +348,14 @@ mod tests {
             .with_named_column("id", ScalarType::Int64.nullable(false))
             .with_named_column("price", ScalarType::Float64.nullable(true));
 
-        let encoder = Encoder::new(desc.clone());
+        let encoder = Encoder::new(desc.clone(), EnumLabels::default());
         let row_schema =
             build_row_schema_json(&crate::encode::column_names_and_types(desc), "data");
         let schema = build_schema(row_schema);
 
+        // The data columns must be discoverable by CDCv2 sources.
+        assert!(extract_data_columns(&schema).is_ok());
+
         let values = vec![
             encoder.encode_updates(&[]),
             encoder.encode_progress(&[0], &[3], &[]),
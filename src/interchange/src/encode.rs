@@ -9,7 +9,9 @@
 
 use std::collections::HashSet;
 
-use repr::{ColumnName, ColumnType, Datum, RelationDesc, Row};
+use repr::adt::enums::EnumLabels;
+use repr::strconv;
+use repr::{ColumnName, ColumnType, Datum, RelationDesc, Row, ScalarType};
 
 pub trait Encode {
     fn get_format_name(&self) -> &str;
@@ -24,15 +26,44 @@ pub trait Encode {
 pub struct TypedDatum<'a> {
     pub datum: Datum<'a>,
     pub typ: ColumnType,
+    /// The labels of the enum types within `typ`.
+    pub enum_labels: &'a EnumLabels,
 }
 
 impl<'a> TypedDatum<'a> {
     /// Pairs a datum and its type, for encoding.
-    pub fn new(datum: Datum<'a>, typ: ColumnType) -> Self {
-        Self { datum, typ }
+    pub fn new(datum: Datum<'a>, typ: ColumnType, enum_labels: &'a EnumLabels) -> Self {
+        Self {
+            datum,
+            typ,
+            enum_labels,
+        }
     }
 }
 
+/// Formats a range datum whose elements are of type `element_type` using its
+/// PostgreSQL text representation.
+///
+/// None of the supported data serialization formats have a native range type,
+/// so ranges are always encoded as strings.
+pub fn format_range(datum: Datum, element_type: &ScalarType) -> String {
+    let mut buf = String::new();
+    strconv::format_range(
+        &mut buf,
+        datum.unwrap_range().bounds(),
+        |buf, d| match element_type {
+            ScalarType::Int32 => strconv::format_int32(buf, d.unwrap_int32()),
+            ScalarType::Int64 => strconv::format_int64(buf, d.unwrap_int64()),
+            ScalarType::Numeric { .. } => strconv::format_numeric(buf, &d.unwrap_numeric()),
+            ScalarType::Date => strconv::format_date(buf, d.unwrap_date()),
+            ScalarType::Timestamp => strconv::format_timestamp(buf, d.unwrap_timestamp()),
+            ScalarType::TimestampTz => strconv::format_timestamptz(buf, d.unwrap_timestamptz()),
+            _ => unreachable!("invalid range element type: {:?}", element_type),
+        },
+    );
+    buf
+}
+
 /// Extracts deduplicated column names and types from a relation description.
 pub fn column_names_and_types(desc: RelationDesc) -> Vec<(ColumnName, ColumnType)> {
     // Invent names for columns that don't have a name.
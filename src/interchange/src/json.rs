@@ -12,18 +12,20 @@ use std::fmt;
 
 use ore::collections::CollectionExt;
 use repr::adt::char;
+use repr::adt::enums::EnumLabels;
 use repr::adt::jsonb::JsonbRef;
 use repr::adt::numeric::{NUMERIC_AGG_MAX_PRECISION, NUMERIC_DATUM_MAX_PRECISION};
 use repr::{ColumnName, ColumnType, Datum, RelationDesc, ScalarType};
 use serde_json::{json, Map};
 
-use crate::encode::{column_names_and_types, Encode, TypedDatum};
+use crate::encode::{column_names_and_types, format_range, Encode, TypedDatum};
 
 // Manages encoding of JSON-encoded bytes
 pub struct JsonEncoder {
     key_columns: Option<Vec<(ColumnName, ColumnType)>>,
     value_columns: Vec<(ColumnName, ColumnType)>,
     include_transaction: bool,
+    enum_labels: EnumLabels,
 }
 
 impl JsonEncoder {
@@ -31,6 +33,7 @@ impl JsonEncoder {
         key_desc: Option<RelationDesc>,
         value_desc: RelationDesc,
         include_transaction: bool,
+        enum_labels: EnumLabels,
     ) -> Self {
         JsonEncoder {
             key_columns: if let Some(desc) = key_desc {
@@ -40,11 +43,17 @@ impl JsonEncoder {
             },
             value_columns: column_names_and_types(value_desc),
             include_transaction,
+            enum_labels,
         }
     }
 
     pub fn encode_row(&self, row: repr::Row, names_types: &[(ColumnName, ColumnType)]) -> Vec<u8> {
-        let value = encode_datums_as_json(row.iter(), names_types, self.include_transaction);
+        let value = encode_datums_as_json(
+            row.iter(),
+            names_types,
+            self.include_transaction,
+            &self.enum_labels,
+        );
         value.to_string().into_bytes()
     }
 }
@@ -82,6 +91,7 @@ pub fn encode_datums_as_json<'a, I>(
     datums: I,
     names_types: &[(ColumnName, ColumnType)],
     include_transaction: bool,
+    enum_labels: &EnumLabels,
 ) -> serde_json::value::Value
 where
     I: IntoIterator<Item = Datum<'a>>,
@@ -104,13 +114,14 @@ where
                         scalar_type: ScalarType::String,
                         nullable: false,
                     },
+                    enum_labels,
                 )
                 .json(namer);
                 ("transaction".to_owned(), json!({ "id": transaction_id }))
             } else {
                 (
                     names_types[i].0.as_str().to_owned(),
-                    TypedDatum::new(datum, names_types[i].1.clone()).json(namer),
+                    TypedDatum::new(datum, names_types[i].1.clone(), enum_labels).json(namer),
                 )
             }
         })
@@ -118,6 +129,32 @@ where
     serde_json::value::Value::Object(value_fields)
 }
 
+/// Encodes a record of a Debezium transaction metadata topic as JSON.
+///
+/// The record has the same fields as the Avro records described by
+/// [`get_debezium_transaction_schema`](crate::avro::get_debezium_transaction_schema).
+pub fn encode_debezium_transaction_unchecked(
+    collection: &str,
+    id: &str,
+    status: &str,
+    message_count: Option<i64>,
+) -> Vec<u8> {
+    let data_collections = match message_count {
+        Some(message_count) => json!([{
+            "data_collection": collection,
+            "event_count": message_count,
+        }]),
+        None => serde_json::value::Value::Null,
+    };
+    let value = json!({
+        "id": id,
+        "status": status,
+        "event_count": message_count,
+        "data_collections": data_collections,
+    });
+    value.to_string().into_bytes()
+}
+
 pub trait ToJson {
     /// Transforms this value to a JSON value.
     fn json<F: FnMut() -> String>(self, namer: &mut F) -> serde_json::value::Value;
@@ -125,7 +162,11 @@ pub trait ToJson {
 
 impl<'a> ToJson for TypedDatum<'_> {
     fn json<F: FnMut() -> String>(self, namer: &mut F) -> serde_json::value::Value {
-        let TypedDatum { datum, typ } = self;
+        let TypedDatum {
+            datum,
+            typ,
+            enum_labels,
+        } = self;
         if typ.nullable && datum.is_null() {
             serde_json::value::Value::Null
         } else {
@@ -167,6 +208,10 @@ impl<'a> ToJson for TypedDatum<'_> {
                 }
                 ScalarType::Jsonb => JsonbRef::from_datum(datum).to_serde_json(),
                 ScalarType::Uuid => json!(datum.unwrap_uuid()),
+                ScalarType::Enum { custom_oid } => {
+                    json!(enum_labels.label(*custom_oid, datum.unwrap_int32()))
+                }
+                ScalarType::Range { element_type } => json!(format_range(datum, element_type)),
                 ScalarType::Array(element_type) | ScalarType::List { element_type, .. } => {
                     let list = match typ.scalar_type {
                         ScalarType::Array(_) => datum.unwrap_array().elements(),
@@ -182,6 +227,7 @@ impl<'a> ToJson for TypedDatum<'_> {
                                     nullable: true,
                                     scalar_type: (**element_type).clone(),
                                 },
+                                enum_labels,
                             );
                             datum.json(namer)
                         })
@@ -199,7 +245,7 @@ impl<'a> ToJson for TypedDatum<'_> {
                         .zip(list.into_iter())
                         .map(|((name, typ), datum)| {
                             let name = name.to_string();
-                            let datum = TypedDatum::new(datum, typ.clone());
+                            let datum = TypedDatum::new(datum, typ.clone(), enum_labels);
                             let value = datum.json(namer);
                             (name, value)
                         })
@@ -222,6 +268,7 @@ impl<'a> ToJson for TypedDatum<'_> {
                                     nullable: true,
                                     scalar_type: (**value_type).clone(),
                                 },
+                                enum_labels,
                             );
                             let value = datum.json(namer);
                             (key.to_string(), value)
@@ -265,9 +312,11 @@ fn build_row_schema_field<F: FnMut() -> String>(
             "logicalType": "duration"
         }),
         ScalarType::Bytes => json!("bytes"),
-        ScalarType::String | ScalarType::Char { .. } | ScalarType::VarChar { .. } => {
-            json!("string")
-        }
+        ScalarType::String
+        | ScalarType::Char { .. }
+        | ScalarType::VarChar { .. }
+        | ScalarType::Enum { .. }
+        | ScalarType::Range { .. } => json!("string"),
         ScalarType::Jsonb => json!({
             "type": "string",
             "connect.name": "io.debezium.data.Json",
@@ -15,4 +15,5 @@ pub mod avro;
 pub mod encode;
 pub mod envelopes;
 pub mod json;
+pub mod parquet;
 pub mod protobuf;
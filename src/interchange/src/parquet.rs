@@ -0,0 +1,1032 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Parquet decoding and encoding.
+//!
+//! Only flat schemas are supported: each top-level column of a Parquet file
+//! must be a primitive, non-repeated column, whose physical and logical type
+//! are mapped to a [`ScalarType`] by [`column_type`].
+
+use std::cmp;
+use std::convert::TryFrom;
+use std::fmt;
+use std::str;
+use std::sync::Arc;
+
+use anyhow::{anyhow, bail};
+use chrono::{DateTime, NaiveDate, NaiveDateTime, NaiveTime, Timelike, Utc};
+use parquet::basic::{ConvertedType, Repetition, Type as PhysicalType};
+use parquet::column::reader::{ColumnReader, ColumnReaderImpl};
+use parquet::column::writer::{ColumnWriter, ColumnWriterImpl};
+use parquet::data_type::{ByteArray, DataType};
+use parquet::file::properties::WriterProperties;
+use parquet::file::reader::{ChunkReader, FileReader, RowGroupReader, SerializedFileReader};
+use parquet::file::writer::{FileWriter, RowGroupWriter, SerializedFileWriter};
+use parquet::schema::types::{ColumnDescriptor, Type, TypePtr};
+use parquet::util::cursor::{InMemoryWriteableCursor, SliceableCursor};
+
+use repr::adt::interval::Interval;
+use repr::adt::jsonb::{Jsonb, JsonbRef};
+use repr::adt::numeric::{self, NUMERIC_AGG_MAX_PRECISION, NUMERIC_DATUM_MAX_PRECISION};
+use repr::{ColumnName, ColumnType, Datum, RelationDesc, Row, ScalarType};
+
+use crate::encode::column_names_and_types;
+
+/// The number of values read from a column chunk at a time.
+const BATCH_SIZE: usize = 1024;
+
+/// The Julian day number of the Unix epoch, as used by `INT96` timestamps.
+const JULIAN_DAY_OF_EPOCH: i64 = 2_440_588;
+
+/// Describes the columns of the Parquet file read by `reader`.
+pub fn file_columns<R>(reader: R) -> Result<Vec<(ColumnName, ColumnType)>, anyhow::Error>
+where
+    R: ChunkReader + 'static,
+{
+    let reader = SerializedFileReader::new(reader)?;
+    let schema = reader.metadata().file_metadata().schema_descr();
+    schema
+        .columns()
+        .iter()
+        .map(|column| Ok((ColumnName::from(column.name()), column_type(column)?)))
+        .collect()
+}
+
+/// Determines the type of the column that a Parquet column decodes into.
+///
+/// The mapping is based on the physical type and the converted (legacy
+/// logical) type of the column, which Parquet writers record for all logical
+/// types that have one. Timestamps with a converted type are adjusted to UTC
+/// by definition, and so decode into `timestamptz` columns; legacy `INT96`
+/// timestamps decode into `timestamp` columns.
+pub fn column_type(column: &ColumnDescriptor) -> Result<ColumnType, anyhow::Error> {
+    if column.path().parts().len() > 1 || column.max_rep_level() > 0 {
+        bail!(
+            "Parquet column {} is nested or repeated, which is not supported",
+            column.path()
+        );
+    }
+    let scalar_type = match (column.physical_type(), column.converted_type()) {
+        (PhysicalType::BOOLEAN, ConvertedType::NONE) => ScalarType::Bool,
+        (PhysicalType::INT32, ConvertedType::INT_8 | ConvertedType::INT_16) => ScalarType::Int16,
+        (
+            PhysicalType::INT32,
+            ConvertedType::NONE
+            | ConvertedType::INT_32
+            | ConvertedType::UINT_8
+            | ConvertedType::UINT_16,
+        ) => ScalarType::Int32,
+        (PhysicalType::INT32, ConvertedType::UINT_32) => ScalarType::Int64,
+        (PhysicalType::INT32, ConvertedType::DATE) => ScalarType::Date,
+        (PhysicalType::INT32, ConvertedType::TIME_MILLIS) => ScalarType::Time,
+        (PhysicalType::INT64, ConvertedType::NONE | ConvertedType::INT_64) => ScalarType::Int64,
+        (PhysicalType::INT64, ConvertedType::UINT_64) => ScalarType::Numeric { scale: Some(0) },
+        (PhysicalType::INT64, ConvertedType::TIME_MICROS) => ScalarType::Time,
+        (
+            PhysicalType::INT64,
+            ConvertedType::TIMESTAMP_MILLIS | ConvertedType::TIMESTAMP_MICROS,
+        ) => ScalarType::TimestampTz,
+        (PhysicalType::INT96, ConvertedType::NONE) => ScalarType::Timestamp,
+        (PhysicalType::FLOAT, ConvertedType::NONE) => ScalarType::Float32,
+        (PhysicalType::DOUBLE, ConvertedType::NONE) => ScalarType::Float64,
+        (PhysicalType::BYTE_ARRAY, ConvertedType::UTF8 | ConvertedType::ENUM) => ScalarType::String,
+        (PhysicalType::BYTE_ARRAY, ConvertedType::JSON) => ScalarType::Jsonb,
+        (
+            PhysicalType::BYTE_ARRAY | PhysicalType::FIXED_LEN_BYTE_ARRAY,
+            ConvertedType::NONE | ConvertedType::BSON,
+        ) => ScalarType::Bytes,
+        (PhysicalType::FIXED_LEN_BYTE_ARRAY, ConvertedType::INTERVAL)
+            if column.type_length() == 12 =>
+        {
+            ScalarType::Interval
+        }
+        (
+            PhysicalType::INT32
+            | PhysicalType::INT64
+            | PhysicalType::BYTE_ARRAY
+            | PhysicalType::FIXED_LEN_BYTE_ARRAY,
+            ConvertedType::DECIMAL,
+        ) => {
+            if column.type_precision() > NUMERIC_DATUM_MAX_PRECISION as i32 {
+                bail!(
+                    "Parquet column {}: decimals with precision greater than {} are not supported",
+                    column.path(),
+                    NUMERIC_DATUM_MAX_PRECISION
+                );
+            }
+            ScalarType::Numeric {
+                scale: Some(u8::try_from(column.type_scale())?),
+            }
+        }
+        (physical_type, converted_type) => bail!(
+            "Parquet column {} has unsupported type {} ({})",
+            column.path(),
+            physical_type,
+            converted_type
+        ),
+    };
+    Ok(scalar_type.nullable(column.max_def_level() > 0))
+}
+
+/// Decodes Parquet files into rows of a fixed set of columns.
+///
+/// Columns are matched to the top-level columns of each file by name, so that
+/// files with different but compatible schemas can be decoded alike. Columns
+/// missing from a file decode as `NULL`.
+#[derive(Debug)]
+pub struct Decoder {
+    columns: Vec<(ColumnName, ColumnType)>,
+    /// Whether each column is read. Columns that are not read are never
+    /// decoded, and are filled with `NULL`s instead.
+    demanded: Vec<bool>,
+}
+
+/// A Parquet file whose footer has been read, and whose row groups are decoded
+/// one at a time by [`Decoder::next_row_group`].
+pub struct DecoderFile {
+    reader: SerializedFileReader<SliceableCursor>,
+    /// The index of the file column of each column, if it is read.
+    indices: Vec<Option<usize>>,
+    /// The index of the next row group to decode.
+    next_row_group: usize,
+}
+
+impl fmt::Debug for DecoderFile {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("DecoderFile")
+            .field("indices", &self.indices)
+            .field("next_row_group", &self.next_row_group)
+            .field("num_row_groups", &self.reader.num_row_groups())
+            .finish()
+    }
+}
+
+impl Decoder {
+    /// Constructs a decoder for `columns`, which reads only the columns for
+    /// which `demanded` is true.
+    pub fn new(columns: Vec<(ColumnName, ColumnType)>, demanded: Vec<bool>) -> Decoder {
+        assert_eq!(columns.len(), demanded.len());
+        Decoder { columns, demanded }
+    }
+
+    /// Decodes all rows of the Parquet file `bytes`.
+    pub fn decode(&self, bytes: Vec<u8>) -> Result<Vec<Row>, anyhow::Error> {
+        let mut file = self.open(bytes)?;
+        let mut rows = Vec::new();
+        while let Some(row_group) = self.next_row_group(&mut file)? {
+            rows.extend(row_group);
+        }
+        Ok(rows)
+    }
+
+    /// Reads the footer of the Parquet file `bytes`, and checks that each
+    /// demanded column can be decoded from it. No rows are decoded.
+    pub fn open(&self, bytes: Vec<u8>) -> Result<DecoderFile, anyhow::Error> {
+        let reader = SerializedFileReader::new(SliceableCursor::new(bytes))?;
+        let schema = reader.metadata().file_metadata().schema_descr_ptr();
+
+        // Find the index of the file column of each demanded column, and check
+        // that it can be decoded as the declared type.
+        let mut indices = Vec::with_capacity(self.columns.len());
+        for ((name, typ), demanded) in self.columns.iter().zip(&self.demanded) {
+            if !demanded {
+                indices.push(None);
+                continue;
+            }
+            let index = schema
+                .columns()
+                .iter()
+                .position(|column| column.path().parts()[0] == name.as_str());
+            match index {
+                None if typ.nullable => indices.push(None),
+                None => bail!(
+                    "file has no column {}, but its column is declared NOT NULL",
+                    name
+                ),
+                Some(index) => {
+                    let column = schema.column(index);
+                    let file_type = column_type(&column)?;
+                    if !can_cast(&file_type.scalar_type, &typ.scalar_type) {
+                        bail!(
+                            "column {} has Parquet type {} ({}), which cannot be decoded as {:?}",
+                            name,
+                            column.physical_type(),
+                            column.converted_type(),
+                            typ.scalar_type
+                        );
+                    }
+                    indices.push(Some(index));
+                }
+            }
+        }
+
+        Ok(DecoderFile {
+            reader,
+            indices,
+            next_row_group: 0,
+        })
+    }
+
+    /// Decodes the rows of the next row group of `file`, or returns `None` if
+    /// every row group has been decoded.
+    pub fn next_row_group(
+        &self,
+        file: &mut DecoderFile,
+    ) -> Result<Option<Vec<Row>>, anyhow::Error> {
+        if file.next_row_group >= file.reader.num_row_groups() {
+            return Ok(None);
+        }
+        let schema = file.reader.metadata().file_metadata().schema_descr_ptr();
+        let row_group = file.reader.get_row_group(file.next_row_group)?;
+        file.next_row_group += 1;
+        let num_rows = usize::try_from(row_group.metadata().num_rows())?;
+        // Each column of the row group is read into a row of its own, which
+        // holds all of its values.
+        let mut values = Vec::with_capacity(self.columns.len());
+        for (index, (name, typ)) in file.indices.iter().zip(&self.columns) {
+            match index {
+                None => values.push(None),
+                Some(index) => {
+                    let column = schema.column(*index);
+                    let reader = row_group.get_column_reader(*index)?;
+                    let column_values = read_column(reader, &column, typ, num_rows)
+                        .map_err(|e| anyhow!("column {}: {:#}", name, e))?;
+                    values.push(Some(column_values));
+                }
+            }
+        }
+        let mut iters: Vec<_> = values
+            .iter()
+            .map(|values| values.as_ref().map(|values| values.iter()))
+            .collect();
+        let mut rows = Vec::with_capacity(num_rows);
+        for _ in 0..num_rows {
+            rows.push(Row::pack(iters.iter_mut().map(|iter| match iter {
+                Some(iter) => iter.next().expect("column has a value for each row"),
+                None => Datum::Null,
+            })));
+        }
+        Ok(Some(rows))
+    }
+}
+
+/// Reports whether values of a Parquet column of type `from` can be decoded
+/// into a column of type `to`.
+fn can_cast(from: &ScalarType, to: &ScalarType) -> bool {
+    use ScalarType::*;
+    matches!(
+        (from, to),
+        (Int16, Int16 | Int32 | Int64 | Numeric { .. })
+            | (Int32, Int32 | Int64 | Numeric { .. })
+            | (Int64, Int64 | Numeric { .. })
+            | (Numeric { .. }, Numeric { .. })
+            | (Float32, Float32 | Float64)
+            | (Float64, Float64)
+            | (Timestamp | TimestampTz, Timestamp | TimestampTz)
+    ) || (!matches!(from, Numeric { .. }) && from == to)
+}
+
+/// Casts `datum`, a value of a Parquet column, to `to`, which must have been
+/// validated with [`can_cast`].
+fn cast<'a>(datum: Datum<'a>, to: &ScalarType) -> Result<Datum<'a>, anyhow::Error> {
+    Ok(match (datum, to) {
+        (Datum::Null, _) => Datum::Null,
+        (Datum::Int16(i), ScalarType::Int32) => Datum::Int32(i.into()),
+        (Datum::Int16(i), ScalarType::Int64) => Datum::Int64(i.into()),
+        (Datum::Int32(i), ScalarType::Int64) => Datum::Int64(i.into()),
+        (Datum::Int16(i), ScalarType::Numeric { scale }) => {
+            integer_to_numeric(&mut i.to_be_bytes(), *scale)?
+        }
+        (Datum::Int32(i), ScalarType::Numeric { scale }) => {
+            integer_to_numeric(&mut i.to_be_bytes(), *scale)?
+        }
+        (Datum::Int64(i), ScalarType::Numeric { scale }) => {
+            integer_to_numeric(&mut i.to_be_bytes(), *scale)?
+        }
+        (Datum::Numeric(n), ScalarType::Numeric { scale: Some(scale) }) => {
+            let mut n = n.0;
+            numeric::rescale(&mut n, *scale)?;
+            Datum::from(n)
+        }
+        (Datum::Float32(f), ScalarType::Float64) => Datum::Float64((f64::from(*f)).into()),
+        (Datum::Timestamp(ts), ScalarType::TimestampTz) => {
+            Datum::TimestampTz(DateTime::from_utc(ts, Utc))
+        }
+        (Datum::TimestampTz(ts), ScalarType::Timestamp) => Datum::Timestamp(ts.naive_utc()),
+        (datum, _) => datum,
+    })
+}
+
+/// Reads `num_rows` values of a column chunk, decoding each as a value of
+/// `typ`, into a row.
+fn read_column(
+    reader: ColumnReader,
+    column: &ColumnDescriptor,
+    typ: &ColumnType,
+    num_rows: usize,
+) -> Result<Row, anyhow::Error> {
+    let to = &typ.scalar_type;
+    let converted_type = column.converted_type();
+    let scale = match converted_type {
+        ConvertedType::DECIMAL => u8::try_from(column.type_scale())?,
+        _ => 0,
+    };
+    match reader {
+        ColumnReader::BoolColumnReader(reader) => {
+            read_values(reader, column, typ, num_rows, |v, row| {
+                row.push(Datum::from(*v));
+                Ok(())
+            })
+        }
+        ColumnReader::Int32ColumnReader(reader) => {
+            read_values(reader, column, typ, num_rows, |v, row| {
+                let datum = match converted_type {
+                    ConvertedType::INT_8 | ConvertedType::INT_16 => {
+                        Datum::Int16(i16::try_from(*v)?)
+                    }
+                    ConvertedType::UINT_32 => Datum::Int64(i64::from(*v as u32)),
+                    ConvertedType::DATE => Datum::Date(
+                        NaiveDate::from_ymd(1970, 1, 1)
+                            .checked_add_signed(chrono::Duration::days(i64::from(*v)))
+                            .ok_or_else(|| anyhow!("date out of range: {} days", v))?,
+                    ),
+                    ConvertedType::TIME_MILLIS => Datum::Time(time(i64::from(*v), 1_000)?),
+                    ConvertedType::DECIMAL => decimal(&mut v.to_be_bytes(), scale)?,
+                    _ => Datum::Int32(*v),
+                };
+                row.push(cast(datum, to)?);
+                Ok(())
+            })
+        }
+        ColumnReader::Int64ColumnReader(reader) => {
+            read_values(reader, column, typ, num_rows, |v, row| {
+                let datum = match converted_type {
+                    ConvertedType::UINT_64 => {
+                        let mut bytes = [0; 9];
+                        bytes[1..].copy_from_slice(&(*v as u64).to_be_bytes());
+                        decimal(&mut bytes, 0)?
+                    }
+                    ConvertedType::TIME_MICROS => Datum::Time(time(*v, 1_000_000)?),
+                    ConvertedType::TIMESTAMP_MILLIS => {
+                        Datum::TimestampTz(DateTime::from_utc(timestamp(*v, 1_000)?, Utc))
+                    }
+                    ConvertedType::TIMESTAMP_MICROS => {
+                        Datum::TimestampTz(DateTime::from_utc(timestamp(*v, 1_000_000)?, Utc))
+                    }
+                    ConvertedType::DECIMAL => decimal(&mut v.to_be_bytes(), scale)?,
+                    _ => Datum::Int64(*v),
+                };
+                row.push(cast(datum, to)?);
+                Ok(())
+            })
+        }
+        ColumnReader::Int96ColumnReader(reader) => {
+            read_values(reader, column, typ, num_rows, |v, row| {
+                // The first eight bytes are the nanoseconds since midnight, and
+                // the last four the Julian day, all little endian.
+                let data = v.data();
+                let nanos = (i64::from(data[1]) << 32) | i64::from(data[0]);
+                let days = i64::from(data[2]) - JULIAN_DAY_OF_EPOCH;
+                let ts = NaiveDate::from_ymd(1970, 1, 1)
+                    .and_hms(0, 0, 0)
+                    .checked_add_signed(chrono::Duration::days(days))
+                    .and_then(|ts| ts.checked_add_signed(chrono::Duration::nanoseconds(nanos)))
+                    .ok_or_else(|| anyhow!("timestamp out of range"))?;
+                row.push(cast(Datum::Timestamp(ts), to)?);
+                Ok(())
+            })
+        }
+        ColumnReader::FloatColumnReader(reader) => {
+            read_values(reader, column, typ, num_rows, |v, row| {
+                row.push(cast(Datum::from(*v), to)?);
+                Ok(())
+            })
+        }
+        ColumnReader::DoubleColumnReader(reader) => {
+            read_values(reader, column, typ, num_rows, |v, row| {
+                row.push(Datum::from(*v));
+                Ok(())
+            })
+        }
+        ColumnReader::ByteArrayColumnReader(reader) => {
+            read_values(reader, column, typ, num_rows, |v, row| {
+                push_byte_array(row, v.data(), converted_type, scale, to)
+            })
+        }
+        ColumnReader::FixedLenByteArrayColumnReader(reader) => {
+            read_values(reader, column, typ, num_rows, |v, row| {
+                push_byte_array(row, v.data(), converted_type, scale, to)
+            })
+        }
+    }
+}
+
+/// Reads `num_rows` values, including `NULL`s, of a column chunk into a row,
+/// pushing each non-`NULL` value with `push`.
+fn read_values<T, F>(
+    mut reader: ColumnReaderImpl<T>,
+    column: &ColumnDescriptor,
+    typ: &ColumnType,
+    num_rows: usize,
+    mut push: F,
+) -> Result<Row, anyhow::Error>
+where
+    T: DataType,
+    F: FnMut(&T::T, &mut Row) -> Result<(), anyhow::Error>,
+{
+    let max_def_level = column.max_def_level();
+    let mut values = vec![T::T::default(); BATCH_SIZE];
+    let mut def_levels = vec![0; BATCH_SIZE];
+    let mut row = Row::default();
+    let mut rows_read = 0;
+    while rows_read < num_rows {
+        let batch_size = cmp::min(BATCH_SIZE, num_rows - rows_read);
+        if max_def_level > 0 {
+            let (values_read, levels_read) =
+                reader.read_batch(batch_size, Some(&mut def_levels[..]), None, &mut values)?;
+            if levels_read == 0 {
+                bail!(
+                    "column chunk ended after {} of {} values",
+                    rows_read,
+                    num_rows
+                );
+            }
+            let mut values = values[..values_read].iter();
+            for level in &def_levels[..levels_read] {
+                if *level == max_def_level {
+                    let value = values.next().expect("one value per defined level");
+                    push(value, &mut row)?;
+                } else if typ.nullable {
+                    row.push(Datum::Null);
+                } else {
+                    bail!("value is null, but its column is declared NOT NULL");
+                }
+            }
+            rows_read += levels_read;
+        } else {
+            let (values_read, _) = reader.read_batch(batch_size, None, None, &mut values)?;
+            if values_read == 0 {
+                bail!(
+                    "column chunk ended after {} of {} values",
+                    rows_read,
+                    num_rows
+                );
+            }
+            for value in &values[..values_read] {
+                push(value, &mut row)?;
+            }
+            rows_read += values_read;
+        }
+    }
+    Ok(row)
+}
+
+/// Pushes the value of a `BYTE_ARRAY` or `FIXED_LEN_BYTE_ARRAY` column with
+/// the converted type `converted_type` onto `row`.
+fn push_byte_array(
+    row: &mut Row,
+    bytes: &[u8],
+    converted_type: ConvertedType,
+    scale: u8,
+    to: &ScalarType,
+) -> Result<(), anyhow::Error> {
+    match converted_type {
+        ConvertedType::UTF8 | ConvertedType::ENUM => {
+            row.push(Datum::String(str::from_utf8(bytes)?))
+        }
+        ConvertedType::JSON => row.extend(Jsonb::from_slice(bytes)?.into_row().iter()),
+        ConvertedType::DECIMAL => {
+            if bytes.is_empty() {
+                bail!("decimal has no bytes");
+            }
+            row.push(cast(decimal(&mut bytes.to_vec(), scale)?, to)?)
+        }
+        ConvertedType::INTERVAL => {
+            // Three little-endian unsigned integers: months, days, and
+            // milliseconds.
+            let field = |i: usize| {
+                let mut buf = [0; 4];
+                buf.copy_from_slice(&bytes[i * 4..(i + 1) * 4]);
+                u32::from_le_bytes(buf)
+            };
+            let (months, days, millis) = (field(0), field(1), field(2));
+            let interval = Interval::new(
+                i32::try_from(months)?,
+                i64::from(days) * 86_400 + i64::from(millis / 1_000),
+                i64::from(millis % 1_000) * 1_000_000,
+            )?;
+            row.push(Datum::Interval(interval))
+        }
+        _ => row.push(Datum::Bytes(bytes)),
+    }
+    Ok(())
+}
+
+/// Decodes a decimal from its unscaled value, as big-endian two's complement
+/// bytes.
+fn decimal(bytes: &mut [u8], scale: u8) -> Result<Datum<'static>, anyhow::Error> {
+    let n = numeric::twos_complement_be_to_numeric(bytes, scale)?;
+    if n.is_special() || numeric::get_precision(&n) > NUMERIC_DATUM_MAX_PRECISION as u32 {
+        bail!(
+            "decimal exceeds maximum precision {}",
+            NUMERIC_DATUM_MAX_PRECISION
+        );
+    }
+    Ok(Datum::from(n))
+}
+
+/// Decodes an integer, as big-endian two's complement bytes, into a numeric
+/// of scale `scale`.
+fn integer_to_numeric(
+    bytes: &mut [u8],
+    scale: Option<u8>,
+) -> Result<Datum<'static>, anyhow::Error> {
+    let mut n = numeric::twos_complement_be_to_numeric(bytes, 0)?;
+    if let Some(scale) = scale {
+        numeric::rescale(&mut n, scale)?;
+    }
+    Ok(Datum::from(n))
+}
+
+/// Decodes a time of day from a number of `units` per second since midnight.
+fn time(value: i64, units: i64) -> Result<NaiveTime, anyhow::Error> {
+    u32::try_from(value.div_euclid(units))
+        .ok()
+        .and_then(|secs| {
+            let nanos = value.rem_euclid(units) * (1_000_000_000 / units);
+            NaiveTime::from_num_seconds_from_midnight_opt(secs, nanos as u32)
+        })
+        .ok_or_else(|| anyhow!("time out of range: {}", value))
+}
+
+/// Decodes a timestamp from a number of `units` per second since the Unix
+/// epoch.
+fn timestamp(value: i64, units: i64) -> Result<NaiveDateTime, anyhow::Error> {
+    let nanos = value.rem_euclid(units) * (1_000_000_000 / units);
+    NaiveDateTime::from_timestamp_opt(value.div_euclid(units), nanos as u32)
+        .ok_or_else(|| anyhow!("timestamp out of range: {}", value))
+}
+
+/// Encodes rows of a relation as Parquet files.
+///
+/// Each column of the relation is written as a top-level column of the same
+/// name, which is optional if the column is nullable. Numeric columns without
+/// a scale are written as decimals with a precision of
+/// [`NUMERIC_AGG_MAX_PRECISION`] and a scale of [`NUMERIC_DATUM_MAX_PRECISION`],
+/// which can represent any numeric value.
+#[derive(Debug)]
+pub struct Encoder {
+    columns: Vec<(ColumnName, ColumnType)>,
+    schema: TypePtr,
+}
+
+impl Encoder {
+    /// Constructs an encoder for rows of `desc`, or returns an error if any of
+    /// its columns has a type that cannot be written to Parquet.
+    pub fn new(desc: RelationDesc) -> Result<Encoder, anyhow::Error> {
+        let columns = column_names_and_types(desc);
+        let mut fields = columns
+            .iter()
+            .map(|(name, typ)| parquet_type(name, typ))
+            .collect::<Result<Vec<_>, _>>()?;
+        let schema = Type::group_type_builder("schema")
+            .with_fields(&mut fields)
+            .build()?;
+        Ok(Encoder {
+            columns,
+            schema: Arc::new(schema),
+        })
+    }
+
+    /// Encodes `rows` as a Parquet file with a single row group.
+    pub fn encode(&self, rows: &[Row]) -> Result<Vec<u8>, anyhow::Error> {
+        let cursor = InMemoryWriteableCursor::default();
+        let props = Arc::new(WriterProperties::builder().build());
+        let mut writer =
+            SerializedFileWriter::new(cursor.clone(), Arc::clone(&self.schema), props)?;
+        if !rows.is_empty() {
+            let rows: Vec<_> = rows.iter().map(|row| row.unpack()).collect();
+            let mut row_group = writer.next_row_group()?;
+            let mut i = 0;
+            while let Some(mut column) = row_group.next_column()? {
+                let (name, typ) = &self.columns[i];
+                let datums = rows.iter().map(|datums| datums[i]);
+                write_column(&mut column, typ, datums)
+                    .map_err(|e| anyhow!("column {}: {:#}", name, e))?;
+                row_group.close_column(column)?;
+                i += 1;
+            }
+            writer.close_row_group(row_group)?;
+        }
+        writer.close()?;
+        Ok(cursor.data())
+    }
+}
+
+/// Determines the Parquet column that a column of type `typ` is written as.
+fn parquet_type(name: &ColumnName, typ: &ColumnType) -> Result<TypePtr, anyhow::Error> {
+    let (physical_type, converted_type) = match &typ.scalar_type {
+        ScalarType::Bool => (PhysicalType::BOOLEAN, ConvertedType::NONE),
+        ScalarType::Int16 => (PhysicalType::INT32, ConvertedType::INT_16),
+        ScalarType::Int32 => (PhysicalType::INT32, ConvertedType::NONE),
+        ScalarType::Int64 => (PhysicalType::INT64, ConvertedType::NONE),
+        ScalarType::Float32 => (PhysicalType::FLOAT, ConvertedType::NONE),
+        ScalarType::Float64 => (PhysicalType::DOUBLE, ConvertedType::NONE),
+        ScalarType::Numeric { .. } => (PhysicalType::BYTE_ARRAY, ConvertedType::DECIMAL),
+        ScalarType::Date => (PhysicalType::INT32, ConvertedType::DATE),
+        ScalarType::Time => (PhysicalType::INT64, ConvertedType::TIME_MICROS),
+        ScalarType::Timestamp | ScalarType::TimestampTz => {
+            (PhysicalType::INT64, ConvertedType::TIMESTAMP_MICROS)
+        }
+        ScalarType::String => (PhysicalType::BYTE_ARRAY, ConvertedType::UTF8),
+        ScalarType::Bytes => (PhysicalType::BYTE_ARRAY, ConvertedType::NONE),
+        ScalarType::Jsonb => (PhysicalType::BYTE_ARRAY, ConvertedType::JSON),
+        other => bail!(
+            "column {} has type {:?}, which cannot be written to Parquet",
+            name,
+            other
+        ),
+    };
+    let (precision, scale) = match typ.scalar_type {
+        ScalarType::Numeric { scale: Some(scale) } => {
+            (NUMERIC_DATUM_MAX_PRECISION, usize::from(scale))
+        }
+        ScalarType::Numeric { scale: None } => {
+            (NUMERIC_AGG_MAX_PRECISION, NUMERIC_DATUM_MAX_PRECISION)
+        }
+        _ => (0, 0),
+    };
+    let repetition = if typ.nullable {
+        Repetition::OPTIONAL
+    } else {
+        Repetition::REQUIRED
+    };
+    let mut builder = Type::primitive_type_builder(name.as_str(), physical_type)
+        .with_repetition(repetition)
+        .with_converted_type(converted_type);
+    if converted_type == ConvertedType::DECIMAL {
+        builder = builder
+            .with_precision(i32::try_from(precision)?)
+            .with_scale(i32::try_from(scale)?);
+    }
+    Ok(Arc::new(builder.build()?))
+}
+
+/// Writes `datums`, the values of a column of type `typ`, to `writer`, which
+/// must write the Parquet column determined by [`parquet_type`].
+fn write_column<'a, I>(
+    writer: &mut ColumnWriter,
+    typ: &ColumnType,
+    datums: I,
+) -> Result<(), anyhow::Error>
+where
+    I: Iterator<Item = Datum<'a>>,
+{
+    match writer {
+        ColumnWriter::BoolColumnWriter(writer) => {
+            write_values(writer, typ, datums, |datum| datum.unwrap_bool())
+        }
+        ColumnWriter::Int32ColumnWriter(writer) => {
+            write_values(writer, typ, datums, |datum| match datum {
+                Datum::Int16(i) => i32::from(i),
+                Datum::Date(date) => (date - NaiveDate::from_ymd(1970, 1, 1)).num_days() as i32,
+                datum => datum.unwrap_int32(),
+            })
+        }
+        ColumnWriter::Int64ColumnWriter(writer) => {
+            write_values(writer, typ, datums, |datum| match datum {
+                Datum::Time(time) => {
+                    i64::from(time.num_seconds_from_midnight()) * 1_000_000
+                        + i64::from(time.nanosecond()) / 1_000
+                }
+                Datum::Timestamp(ts) => timestamp_micros(ts),
+                Datum::TimestampTz(ts) => timestamp_micros(ts.naive_utc()),
+                datum => datum.unwrap_int64(),
+            })
+        }
+        ColumnWriter::FloatColumnWriter(writer) => {
+            write_values(writer, typ, datums, |datum| datum.unwrap_float32())
+        }
+        ColumnWriter::DoubleColumnWriter(writer) => {
+            write_values(writer, typ, datums, |datum| datum.unwrap_float64())
+        }
+        ColumnWriter::ByteArrayColumnWriter(writer) => {
+            let scale = match typ.scalar_type {
+                ScalarType::Numeric { scale } => scale,
+                _ => None,
+            };
+            write_values(writer, typ, datums, |datum| match datum {
+                Datum::String(s) => ByteArray::from(s),
+                Datum::Bytes(b) => ByteArray::from(b.to_vec()),
+                Datum::Numeric(n) => {
+                    let mut n = n.0;
+                    let unscaled = match scale {
+                        Some(scale) => {
+                            // Values must be rescaled to resaturate trailing
+                            // zeroes.
+                            numeric::rescale(&mut n, scale).unwrap();
+                            numeric::numeric_to_twos_complement_be(n).to_vec()
+                        }
+                        None => numeric::numeric_to_twos_complement_wide(n).to_vec(),
+                    };
+                    ByteArray::from(unscaled)
+                }
+                datum => ByteArray::from(JsonbRef::from_datum(datum).to_string().into_bytes()),
+            })
+        }
+        ColumnWriter::Int96ColumnWriter(_) | ColumnWriter::FixedLenByteArrayColumnWriter(_) => {
+            unreachable!("columns are never written as INT96 or FIXED_LEN_BYTE_ARRAY")
+        }
+    }
+}
+
+/// Writes the values of a column to `writer`, converting each non-`NULL`
+/// datum with `convert`.
+fn write_values<'a, T, I, F>(
+    writer: &mut ColumnWriterImpl<T>,
+    typ: &ColumnType,
+    datums: I,
+    mut convert: F,
+) -> Result<(), anyhow::Error>
+where
+    T: DataType,
+    I: Iterator<Item = Datum<'a>>,
+    F: FnMut(Datum<'a>) -> T::T,
+{
+    let mut values = Vec::new();
+    let mut def_levels = Vec::new();
+    for datum in datums {
+        if datum.is_null() {
+            if !typ.nullable {
+                bail!("value is null, but its column is not nullable");
+            }
+            def_levels.push(0);
+        } else {
+            def_levels.push(1);
+            values.push(convert(datum));
+        }
+    }
+    let def_levels = if typ.nullable {
+        Some(&def_levels[..])
+    } else {
+        None
+    };
+    writer.write_batch(&values, def_levels, None)?;
+    Ok(())
+}
+
+/// Converts a timestamp into microseconds since the Unix epoch.
+fn timestamp_micros(ts: NaiveDateTime) -> i64 {
+    ts.timestamp() * 1_000_000 + i64::from(ts.timestamp_subsec_micros())
+}
+
+#[cfg(test)]
+mod tests {
+    use parquet::schema::parser::parse_message_type;
+
+    use repr::RelationType;
+
+    use super::*;
+
+    /// Writes a Parquet file with a single row group of three rows.
+    fn test_file() -> Vec<u8> {
+        let schema = parse_message_type(
+            "message schema {
+                REQUIRED INT64 id;
+                OPTIONAL BYTE_ARRAY name (UTF8);
+                OPTIONAL INT32 price (DECIMAL(9, 2));
+                REQUIRED INT32 day (DATE);
+            }",
+        )
+        .unwrap();
+        let cursor = InMemoryWriteableCursor::default();
+        let props = Arc::new(WriterProperties::builder().build());
+        let mut writer =
+            SerializedFileWriter::new(cursor.clone(), Arc::new(schema), props).unwrap();
+        let mut row_group = writer.next_row_group().unwrap();
+        while let Some(mut column) = row_group.next_column().unwrap() {
+            match &mut column {
+                ColumnWriter::Int64ColumnWriter(w) => {
+                    w.write_batch(&[1, 2, 3], None, None).unwrap();
+                }
+                ColumnWriter::ByteArrayColumnWriter(w) => {
+                    let values = [ByteArray::from("a"), ByteArray::from("c")];
+                    w.write_batch(&values, Some(&[1, 0, 1]), None).unwrap();
+                }
+                ColumnWriter::Int32ColumnWriter(w) if w.get_descriptor().name() == "price" => {
+                    w.write_batch(&[1234, -5], Some(&[1, 1, 0]), None).unwrap();
+                }
+                ColumnWriter::Int32ColumnWriter(w) => {
+                    w.write_batch(&[0, 1, 18628], None, None).unwrap();
+                }
+                _ => unreachable!(),
+            }
+            row_group.close_column(column).unwrap();
+        }
+        writer.close_row_group(row_group).unwrap();
+        writer.close().unwrap();
+        cursor.data()
+    }
+
+    #[test]
+    fn test_file_columns() {
+        let columns = file_columns(SliceableCursor::new(test_file())).unwrap();
+        assert_eq!(
+            columns,
+            vec![
+                ("id".into(), ScalarType::Int64.nullable(false)),
+                ("name".into(), ScalarType::String.nullable(true)),
+                (
+                    "price".into(),
+                    ScalarType::Numeric { scale: Some(2) }.nullable(true)
+                ),
+                ("day".into(), ScalarType::Date.nullable(false)),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_decode() {
+        let columns = vec![
+            ("day".into(), ScalarType::Date.nullable(false)),
+            (
+                "id".into(),
+                ScalarType::Numeric { scale: None }.nullable(false),
+            ),
+            ("missing".into(), ScalarType::Int32.nullable(true)),
+            ("name".into(), ScalarType::String.nullable(true)),
+            (
+                "price".into(),
+                ScalarType::Numeric { scale: Some(3) }.nullable(true),
+            ),
+        ];
+        let date =
+            |days| Datum::Date(NaiveDate::from_ymd(1970, 1, 1) + chrono::Duration::days(days));
+        let num = |i: i32, scale| integer_to_numeric(&mut i.to_be_bytes(), Some(scale)).unwrap();
+        let price = |i: i32| {
+            let mut n = decimal(&mut i.to_be_bytes(), 2).unwrap().unwrap_numeric().0;
+            numeric::rescale(&mut n, 3).unwrap();
+            Datum::from(n)
+        };
+
+        let decoder = Decoder::new(columns.clone(), vec![true; 5]);
+        let rows = decoder.decode(test_file()).unwrap();
+        assert_eq!(
+            rows,
+            vec![
+                Row::pack(&[
+                    date(0),
+                    num(1, 0),
+                    Datum::Null,
+                    Datum::String("a"),
+                    price(1234)
+                ]),
+                Row::pack(&[date(1), num(2, 0), Datum::Null, Datum::Null, price(-5)]),
+                Row::pack(&[
+                    date(18628),
+                    num(3, 0),
+                    Datum::Null,
+                    Datum::String("c"),
+                    Datum::Null
+                ]),
+            ]
+        );
+
+        // Columns that are not demanded are not read.
+        let decoder = Decoder::new(columns.clone(), vec![false, true, false, false, false]);
+        let rows = decoder.decode(test_file()).unwrap();
+        assert_eq!(
+            rows,
+            (1..=3)
+                .map(|i| Row::pack(&[
+                    Datum::Null,
+                    num(i, 0),
+                    Datum::Null,
+                    Datum::Null,
+                    Datum::Null
+                ]))
+                .collect::<Vec<_>>()
+        );
+
+        // Columns that are missing from the file must be nullable.
+        let decoder = Decoder::new(
+            vec![("missing".into(), ScalarType::Int32.nullable(false))],
+            vec![true],
+        );
+        assert!(decoder.decode(test_file()).is_err());
+
+        // Columns must be decodable as their declared type.
+        let decoder = Decoder::new(
+            vec![("name".into(), ScalarType::Int32.nullable(true))],
+            vec![true],
+        );
+        assert!(decoder.decode(test_file()).is_err());
+
+        // Columns with nulls cannot be declared NOT NULL.
+        let decoder = Decoder::new(
+            vec![("name".into(), ScalarType::String.nullable(false))],
+            vec![true],
+        );
+        assert!(decoder.decode(test_file()).is_err());
+    }
+
+    #[test]
+    fn test_decode_row_groups() {
+        let schema = parse_message_type("message schema { REQUIRED INT64 id; }").unwrap();
+        let cursor = InMemoryWriteableCursor::default();
+        let props = Arc::new(WriterProperties::builder().build());
+        let mut writer =
+            SerializedFileWriter::new(cursor.clone(), Arc::new(schema), props).unwrap();
+        for ids in &[&[1, 2][..], &[3]] {
+            let mut row_group = writer.next_row_group().unwrap();
+            while let Some(mut column) = row_group.next_column().unwrap() {
+                match &mut column {
+                    ColumnWriter::Int64ColumnWriter(w) => {
+                        w.write_batch(ids, None, None).unwrap();
+                    }
+                    _ => unreachable!(),
+                }
+                row_group.close_column(column).unwrap();
+            }
+            writer.close_row_group(row_group).unwrap();
+        }
+        writer.close().unwrap();
+
+        // Each row group is decoded on its own.
+        let decoder = Decoder::new(
+            vec![("id".into(), ScalarType::Int64.nullable(false))],
+            vec![true],
+        );
+        let mut file = decoder.open(cursor.data()).unwrap();
+        let row = |i: i64| Row::pack(&[Datum::Int64(i)]);
+        assert_eq!(
+            decoder.next_row_group(&mut file).unwrap(),
+            Some(vec![row(1), row(2)])
+        );
+        assert_eq!(
+            decoder.next_row_group(&mut file).unwrap(),
+            Some(vec![row(3)])
+        );
+        assert_eq!(decoder.next_row_group(&mut file).unwrap(), None);
+    }
+
+    #[test]
+    fn test_encode() {
+        let desc = RelationDesc::new(
+            RelationType::new(vec![
+                ScalarType::Int64.nullable(false),
+                ScalarType::String.nullable(true),
+                ScalarType::Numeric { scale: Some(2) }.nullable(true),
+                ScalarType::Date.nullable(false),
+                ScalarType::TimestampTz.nullable(true),
+            ]),
+            vec![
+                Some(ColumnName::from("id")),
+                Some(ColumnName::from("name")),
+                Some(ColumnName::from("price")),
+                Some(ColumnName::from("day")),
+                Some(ColumnName::from("updated")),
+            ],
+        );
+        let encoder = Encoder::new(desc.clone()).unwrap();
+        let day = NaiveDate::from_ymd(2021, 1, 1);
+        let price = |s: &str| Datum::from(s.parse::<numeric::Numeric>().unwrap());
+        let rows = vec![
+            Row::pack(&[
+                Datum::Int64(1),
+                Datum::String("a"),
+                price("12.30"),
+                Datum::Date(day),
+                Datum::TimestampTz(DateTime::from_utc(day.and_hms_micro(1, 2, 3, 4), Utc)),
+            ]),
+            Row::pack(&[
+                Datum::Int64(-2),
+                Datum::Null,
+                Datum::Null,
+                Datum::Date(day),
+                Datum::Null,
+            ]),
+        ];
+        let bytes = encoder.encode(&rows).unwrap();
+
+        // Encoded files decode into the same rows.
+        let columns = file_columns(SliceableCursor::new(bytes.clone())).unwrap();
+        assert_eq!(columns, column_names_and_types(desc));
+        let decoder = Decoder::new(columns, vec![true; 5]);
+        assert_eq!(decoder.decode(bytes).unwrap(), rows);
+
+        // Nulls cannot be written to columns that are not nullable.
+        assert!(encoder.encode(&[Row::pack(&[Datum::Null; 5])]).is_err());
+
+        // Columns must have a type that can be written to Parquet.
+        let desc =
+            RelationDesc::empty().with_named_column("i", ScalarType::Interval.nullable(false));
+        assert!(Encoder::new(desc).is_err());
+    }
+}
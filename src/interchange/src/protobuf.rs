@@ -7,9 +7,10 @@
 // the Business Source License, use of this software will be governed
 // by the Apache License, Version 2.0.
 
-//! Protobuf source connector
+//! Protobuf source and sink connectors
 
 pub mod decode;
+pub mod encode;
 
 use std::collections::HashSet;
 
@@ -128,6 +129,29 @@ pub fn decode_descriptors(descriptors: &[u8]) -> Result<decode::DecodedDescripto
     })
 }
 
+/// Returns the fully qualified name of the first message defined in the file
+/// named `file_name` within the encoded file descriptor set `descriptors`.
+pub fn first_message_name_in_file(descriptors: &[u8], file_name: &str) -> Result<String> {
+    let proto: protobuf::descriptor::FileDescriptorSet =
+        protobuf::Message::parse_from_bytes(descriptors)
+            .context("parsing encoded protobuf descriptors failed")?;
+    let file = proto
+        .file
+        .iter()
+        .find(|file| file.get_name() == file_name)
+        .ok_or_else(|| anyhow!("file descriptor set does not contain {}", file_name))?;
+    let name = file
+        .get_message_type()
+        .iter()
+        .next()
+        .ok_or_else(|| anyhow!("proto must have at least one message"))?
+        .get_name();
+    match file.get_package() {
+        "" => Ok(format!(".{}", name)),
+        package => Ok(format!(".{}.{}", package, name)),
+    }
+}
+
 pub fn validate_descriptors(message_name: &str, descriptors: &Descriptors) -> Result<RelationDesc> {
     let proto_name = proto_message_name(message_name);
     let message = descriptors.message_by_name(&proto_name).ok_or_else(|| {
@@ -177,13 +201,16 @@ mod tests {
         Descriptors, FieldDescriptor, FieldLabel, FieldType, InternalFieldType, MessageDescriptor,
     };
 
-    use repr::{Datum, DatumList, RelationDesc, ScalarType};
+    use repr::adt::enums::EnumLabels;
+    use repr::{Datum, DatumList, RelationDesc, Row, ScalarType};
 
     use gen::fuzz::{
         Color, TestNestedRecord, TestRecord, TestRepeatedNestedRecord, TestRepeatedRecord,
     };
 
     use super::decode;
+    use super::encode::ProtobufEncoder;
+    use crate::encode::Encode;
 
     mod gen {
         include!(concat!(env!("OUT_DIR"), "/protobuf/mod.rs"));
@@ -344,7 +371,7 @@ mod tests {
             &descriptors,
         )
         .expect("Sanity checking descriptors failed");
-        decode::Decoder::new(descriptors, message_name)
+        decode::Decoder::new(descriptors, message_name, false)
     }
 
     #[test]
@@ -409,6 +436,46 @@ mod tests {
         assert_eq!(datums, expected);
     }
 
+    #[test]
+    fn test_decode_confluent_wire_format() {
+        let mut test_record = TestRecord::new();
+
+        test_record.set_int_field(1);
+        let bytes = test_record
+            .write_to_bytes()
+            .expect("test failed to serialize to bytes");
+
+        let mut decoder = decode::Decoder::new(
+            Descriptors::from_proto(&gen::file_descriptor_set()),
+            ".TestRecord",
+            true,
+        );
+
+        // The message path [0] can be written either in full or as an empty
+        // path.
+        for path in &[&[0][..], &[2, 0][..]] {
+            let mut datum = vec![0, 0, 0, 0, 1];
+            datum.extend(*path);
+            datum.extend(&bytes);
+            let row = decoder
+                .decode(&datum, None, false)
+                .expect("deserialize protobuf into a row")
+                .unwrap();
+            assert_eq!(row.iter().next(), Some(Datum::Int32(1)));
+        }
+
+        for (datum, error) in &[
+            (&[0, 0, 0][..], "expected at least 5 bytes, got 3"),
+            (&[1, 0, 0, 0, 1, 0][..], "expected 0, got 1"),
+            (&[0, 0, 0, 0, 1][..], "header is truncated"),
+            (&[0, 0, 0, 0, 1, 2, 2][..], "does not use the first message"),
+            (&[0, 0, 0, 0, 1, 1][..], "has negative length -1"),
+        ] {
+            let err = decoder.decode(datum, None, false).unwrap_err();
+            assert!(err.to_string().contains(error), "{}", err);
+        }
+    }
+
     #[test]
     fn test_repeated() {
         let mut test_record = TestRepeatedRecord::new();
@@ -585,4 +652,124 @@ mod tests {
             assert_eq!(d, Datum::List(DatumList::empty()));
         }
     }
+
+    #[test]
+    fn test_encode() {
+        let descriptors = gen::file_descriptor_set()
+            .write_to_bytes()
+            .expect("test failed to serialize descriptors");
+        let desc = RelationDesc::empty()
+            .with_named_column("double_field", ScalarType::Float32.nullable(false))
+            .with_named_column("color_field", ScalarType::Int16.nullable(false))
+            .with_named_column("string_field", ScalarType::String.nullable(true))
+            .with_named_column("int_field", ScalarType::Int32.nullable(false));
+        let encoder = ProtobufEncoder::new(
+            None,
+            desc,
+            false,
+            ".TestRecord",
+            &descriptors,
+            EnumLabels::default(),
+        )
+        .expect("valid encoder");
+
+        let row = Row::pack_slice(&[
+            Datum::Float32(OrderedFloat::from(1.5)),
+            Datum::Int16(2),
+            Datum::Null,
+            Datum::Int32(-7),
+        ]);
+        let bytes = encoder.encode_value_unchecked(row);
+        let record = TestRecord::parse_from_bytes(&bytes).expect("valid protobuf message");
+
+        let mut expected = TestRecord::new();
+        expected.set_double_field(1.5);
+        expected.set_color_field(Color::BLUE);
+        expected.set_int_field(-7);
+        assert_eq!(record, expected);
+    }
+
+    #[test]
+    fn test_encode_enum() {
+        let descriptors = gen::file_descriptor_set()
+            .write_to_bytes()
+            .expect("test failed to serialize descriptors");
+        let desc = RelationDesc::empty().with_named_column(
+            "string_field",
+            ScalarType::Enum { custom_oid: 20_000 }.nullable(false),
+        );
+        let mut enum_labels = EnumLabels::default();
+        enum_labels.insert(20_000, vec!["ok".into(), "sad".into()]);
+        let encoder =
+            ProtobufEncoder::new(None, desc, false, ".TestRecord", &descriptors, enum_labels)
+                .expect("valid encoder");
+
+        let bytes = encoder.encode_value_unchecked(Row::pack_slice(&[Datum::Int32(1)]));
+        let record = TestRecord::parse_from_bytes(&bytes).expect("valid protobuf message");
+
+        assert_eq!(record.get_string_field(), "sad");
+    }
+
+    #[test]
+    fn test_encode_repeated() {
+        let descriptors = gen::file_descriptor_set()
+            .write_to_bytes()
+            .expect("test failed to serialize descriptors");
+        let desc = RelationDesc::empty().with_named_column(
+            "int_field",
+            ScalarType::List {
+                element_type: Box::new(ScalarType::Int32),
+                custom_oid: None,
+            }
+            .nullable(false),
+        );
+        let encoder = ProtobufEncoder::new(
+            None,
+            desc,
+            false,
+            ".TestRepeatedRecord",
+            &descriptors,
+            EnumLabels::default(),
+        )
+        .expect("valid encoder");
+
+        let mut row = Row::default();
+        row.push_list(vec![Datum::Int32(1), Datum::Null, Datum::Int32(3)]);
+        let bytes = encoder.encode_value_unchecked(row);
+        let record = TestRepeatedRecord::parse_from_bytes(&bytes).expect("valid protobuf message");
+
+        assert_eq!(record.get_int_field(), &[1, 3]);
+    }
+
+    #[test]
+    fn test_encode_invalid() {
+        let descriptors = gen::file_descriptor_set()
+            .write_to_bytes()
+            .expect("test failed to serialize descriptors");
+        let new_encoder = |column: &str, scalar_type: ScalarType| {
+            let desc = RelationDesc::empty().with_named_column(column, scalar_type.nullable(true));
+            ProtobufEncoder::new(
+                None,
+                desc,
+                false,
+                ".TestRecord",
+                &descriptors,
+                EnumLabels::default(),
+            )
+        };
+
+        assert!(new_encoder("int_field", ScalarType::Int32).is_ok());
+        assert!(new_encoder("int_field", ScalarType::Int64).is_err());
+        assert!(new_encoder("float_field", ScalarType::Float64).is_err());
+        assert!(new_encoder("no_such_field", ScalarType::Int32).is_err());
+        assert!(ProtobufEncoder::new(
+            None,
+            RelationDesc::empty(),
+            false,
+            ".NoSuchMessage",
+            &descriptors,
+            EnumLabels::default(),
+        )
+        .is_err());
+    }
 }
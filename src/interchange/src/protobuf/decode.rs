@@ -26,6 +26,7 @@ use crate::protobuf::{proto_message_name, validate_descriptors};
 pub struct Decoder {
     descriptors: Descriptors,
     message_name: String,
+    confluent_wire_format: bool,
     packer: Row,
 }
 
@@ -33,12 +34,19 @@ impl Decoder {
     /// Build a decoder from a pre-validated message.
     ///
     /// The message `message_name` must exist in the descriptor set and be
-    /// valid.
-    pub fn new(descriptors: Descriptors, message_name: &str) -> Decoder {
+    /// valid. If `confluent_wire_format` is set, each record is expected to
+    /// begin with the header written by the Confluent Schema Registry
+    /// serializers.
+    pub fn new(
+        descriptors: Descriptors,
+        message_name: &str,
+        confluent_wire_format: bool,
+    ) -> Decoder {
         // TODO: verify that name exists
         Decoder {
             descriptors,
             message_name: proto_message_name(message_name),
+            confluent_wire_format,
             packer: Row::default(),
         }
     }
@@ -49,6 +57,11 @@ impl Decoder {
         position: Option<i64>,
         push_metadata: bool,
     ) -> Result<Option<Row>> {
+        let bytes = if self.confluent_wire_format {
+            strip_confluent_header(bytes)?
+        } else {
+            bytes
+        };
         let input_stream = protobuf::CodedInputStream::from_bytes(bytes);
         let mut deserializer =
             Deserializer::for_named_message(&self.descriptors, &self.message_name, input_stream)
@@ -78,6 +91,64 @@ impl Decoder {
     }
 }
 
+/// Strips the header that the Confluent Schema Registry serializers prepend to
+/// each record: a magic byte, the four-byte ID of the writer's schema, and the
+/// path to the message within that schema.
+///
+/// The writer's schema is otherwise ignored. Records are decoded with the
+/// schema that was fetched when the source was created, which is sound so long
+/// as the registry only permits backwards-compatible changes to the subject:
+/// fields added by newer schemas are skipped like any other unknown field.
+fn strip_confluent_header(bytes: &[u8]) -> Result<&[u8]> {
+    if bytes.len() < 5 {
+        bail!(
+            "Confluent-style protobuf datum is too few bytes: expected at least 5 bytes, got {}",
+            bytes.len()
+        );
+    }
+    if bytes[0] != 0 {
+        bail!(
+            "wrong Confluent-style protobuf serialization magic: expected 0, got {}",
+            bytes[0]
+        );
+    }
+    let mut bytes = &bytes[5..];
+    // The message path is a count followed by that many indexes, where a
+    // count of zero is shorthand for the path [0], i.e., the first message.
+    // Sources always decode the first message in the schema.
+    let count = read_zigzag_varint(&mut bytes)?;
+    if count < 0 {
+        bail!(
+            "Confluent-style protobuf message path has negative length {}",
+            count
+        );
+    }
+    for _ in 0..count {
+        let index = read_zigzag_varint(&mut bytes)?;
+        if count != 1 || index != 0 {
+            bail!("Confluent-style protobuf datum does not use the first message in its schema");
+        }
+    }
+    Ok(bytes)
+}
+
+/// Reads a zigzag-encoded varint from the front of `bytes`, as written by the
+/// Confluent Schema Registry serializers.
+fn read_zigzag_varint(bytes: &mut &[u8]) -> Result<i64> {
+    let mut value: u64 = 0;
+    for shift in (0..64).step_by(7) {
+        let (byte, rest) = bytes
+            .split_first()
+            .ok_or_else(|| anyhow!("Confluent-style protobuf header is truncated"))?;
+        *bytes = rest;
+        value |= u64::from(byte & 0x7f) << shift;
+        if byte & 0x80 == 0 {
+            return Ok((value >> 1) as i64 ^ -((value & 1) as i64));
+        }
+    }
+    bail!("Confluent-style protobuf header contains an invalid varint")
+}
+
 #[derive(Debug)]
 pub struct DecodedDescriptors {
     pub descriptors: Descriptors,
@@ -0,0 +1,432 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use std::convert::TryFrom;
+use std::fmt;
+
+use anyhow::{anyhow, bail, Context, Result};
+use protobuf::CodedOutputStream;
+use serde_protobuf::descriptor::{Descriptors, FieldLabel, FieldType, MessageDescriptor};
+
+use ore::str::StrExt;
+use repr::adt::char;
+use repr::adt::enums::EnumLabels;
+use repr::adt::jsonb::JsonbRef;
+use repr::{strconv, ColumnName, ColumnType, Datum, RelationDesc, Row, ScalarType};
+
+use crate::encode::{column_names_and_types, format_range, Encode};
+use crate::protobuf::proto_message_name;
+
+/// Manages encoding of protobuf-encoded bytes.
+///
+/// Values are encoded according to a user-supplied message descriptor. Keys
+/// are encoded according to a message that is generated from the key columns,
+/// whose fields are numbered sequentially in column order.
+pub struct ProtobufEncoder {
+    key: Option<MessageEncoding>,
+    value: MessageEncoding,
+    message_name: String,
+    enum_labels: EnumLabels,
+}
+
+impl ProtobufEncoder {
+    /// Builds an encoder that encodes values as the message `message_name`
+    /// in the encoded `FileDescriptorSet` `descriptors`.
+    ///
+    /// Returns an error if the message does not exist, or if any of the
+    /// columns in `value_desc` cannot be encoded as the identically named
+    /// field of the message.
+    pub fn new(
+        key_desc: Option<RelationDesc>,
+        value_desc: RelationDesc,
+        include_transaction: bool,
+        message_name: &str,
+        descriptors: &[u8],
+        enum_labels: EnumLabels,
+    ) -> Result<Self> {
+        let proto: protobuf::descriptor::FileDescriptorSet =
+            protobuf::Message::parse_from_bytes(descriptors)
+                .context("parsing encoded protobuf descriptors failed")?;
+        let descriptors = Descriptors::from_proto(&proto);
+        let proto_name = proto_message_name(message_name);
+        let message = descriptors.message_by_name(&proto_name).ok_or_else(|| {
+            anyhow!(
+                "Message {} not found in file descriptor set",
+                proto_name.quoted()
+            )
+        })?;
+
+        let key = match key_desc {
+            Some(desc) => Some(MessageEncoding::generate(&column_names_and_types(desc))?),
+            None => None,
+        };
+
+        let mut value_columns = column_names_and_types(value_desc);
+        if include_transaction {
+            value_columns.push((
+                ColumnName::from("transaction"),
+                ColumnType {
+                    scalar_type: ScalarType::Record {
+                        fields: vec![(
+                            ColumnName::from("id"),
+                            ColumnType {
+                                scalar_type: ScalarType::String,
+                                nullable: false,
+                            },
+                        )],
+                        custom_oid: None,
+                        custom_name: None,
+                    },
+                    nullable: false,
+                },
+            ));
+        }
+        let value = MessageEncoding::from_descriptor(&value_columns, message, &descriptors)?;
+
+        Ok(ProtobufEncoder {
+            key,
+            value,
+            message_name: proto_name,
+            enum_labels,
+        })
+    }
+}
+
+impl Encode for ProtobufEncoder {
+    fn get_format_name(&self) -> &str {
+        "protobuf"
+    }
+
+    fn encode_key_unchecked(&self, row: Row) -> Vec<u8> {
+        self.key
+            .as_ref()
+            .expect("key schema must exist")
+            .encode(row.iter(), &self.enum_labels)
+    }
+
+    fn encode_value_unchecked(&self, row: Row) -> Vec<u8> {
+        self.value.encode(row.iter(), &self.enum_labels)
+    }
+}
+
+impl fmt::Debug for ProtobufEncoder {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("ProtobufEncoder")
+            .field("message_name", &self.message_name)
+            .finish()
+    }
+}
+
+/// Describes how to encode a sequence of datums as a protobuf message.
+#[derive(Debug)]
+struct MessageEncoding {
+    /// The encoding of each datum, in order.
+    fields: Vec<FieldEncoding>,
+}
+
+/// Describes how to encode a datum as a protobuf field.
+#[derive(Debug)]
+struct FieldEncoding {
+    number: u32,
+    /// Whether the datum is a list or array whose elements are each encoded
+    /// as an occurrence of a repeated field.
+    repeated: bool,
+    /// The type of the datum, or of its elements if `repeated` is set.
+    scalar_type: ScalarType,
+    kind: FieldKind,
+}
+
+/// The wire type of a protobuf field.
+#[derive(Debug)]
+enum FieldKind {
+    Bool,
+    Int32,
+    SInt32,
+    SFixed32,
+    Int64,
+    SInt64,
+    SFixed64,
+    Enum,
+    Float,
+    Double,
+    String,
+    Bytes,
+    Message(MessageEncoding),
+}
+
+impl MessageEncoding {
+    /// Matches each of `columns` to the identically named field of `message`.
+    fn from_descriptor(
+        columns: &[(ColumnName, ColumnType)],
+        message: &MessageDescriptor,
+        descriptors: &Descriptors,
+    ) -> Result<Self> {
+        let mut fields = Vec::with_capacity(columns.len());
+        for (name, typ) in columns {
+            let field = message.field_by_name(name.as_str()).ok_or_else(|| {
+                anyhow!(
+                    "protobuf message {} has no field named {}",
+                    message.name().quoted(),
+                    name.as_str().quoted()
+                )
+            })?;
+            let (repeated, scalar_type) = match field.field_label() {
+                FieldLabel::Required => bail!(
+                    "required protobuf field {} is not supported",
+                    field.name().quoted()
+                ),
+                FieldLabel::Optional => (false, &typ.scalar_type),
+                FieldLabel::Repeated => match &typ.scalar_type {
+                    ScalarType::Array(element_type) | ScalarType::List { element_type, .. } => {
+                        (true, &**element_type)
+                    }
+                    _ => bail!(
+                        "column {} of type {:?} cannot be encoded as repeated protobuf field {}",
+                        name.as_str().quoted(),
+                        typ.scalar_type,
+                        field.name().quoted()
+                    ),
+                },
+            };
+            let kind = match (scalar_type, field.field_type(descriptors)) {
+                (ScalarType::Bool, FieldType::Bool) => FieldKind::Bool,
+                (ScalarType::Int16 | ScalarType::Int32, FieldType::Int32) => FieldKind::Int32,
+                (ScalarType::Int16 | ScalarType::Int32, FieldType::SInt32) => FieldKind::SInt32,
+                (ScalarType::Int16 | ScalarType::Int32, FieldType::SFixed32) => FieldKind::SFixed32,
+                (ScalarType::Int16 | ScalarType::Int32, FieldType::Enum(_)) => FieldKind::Enum,
+                (
+                    ScalarType::Int16
+                    | ScalarType::Int32
+                    | ScalarType::Int64
+                    | ScalarType::Timestamp
+                    | ScalarType::TimestampTz,
+                    FieldType::Int64,
+                ) => FieldKind::Int64,
+                (
+                    ScalarType::Int16
+                    | ScalarType::Int32
+                    | ScalarType::Int64
+                    | ScalarType::Timestamp
+                    | ScalarType::TimestampTz,
+                    FieldType::SInt64,
+                ) => FieldKind::SInt64,
+                (
+                    ScalarType::Int16
+                    | ScalarType::Int32
+                    | ScalarType::Int64
+                    | ScalarType::Timestamp
+                    | ScalarType::TimestampTz,
+                    FieldType::SFixed64,
+                ) => FieldKind::SFixed64,
+                (ScalarType::Float32, FieldType::Float) => FieldKind::Float,
+                (ScalarType::Float32 | ScalarType::Float64, FieldType::Double) => FieldKind::Double,
+                (ScalarType::Bytes, FieldType::Bytes) => FieldKind::Bytes,
+                (scalar_type, FieldType::String) if is_text(scalar_type) => FieldKind::String,
+                (ScalarType::Record { fields, .. }, FieldType::Message(message)) => {
+                    FieldKind::Message(Self::from_descriptor(fields, message, descriptors)?)
+                }
+                (
+                    _,
+                    FieldType::UInt32 | FieldType::UInt64 | FieldType::Fixed32 | FieldType::Fixed64,
+                ) => bail!(
+                    "protobuf field {} has an unsigned type, which is not supported",
+                    field.name().quoted()
+                ),
+                (scalar_type, field_type) => bail!(
+                    "column {} of type {:?} cannot be encoded as protobuf field {} of type {:?}",
+                    name.as_str().quoted(),
+                    scalar_type,
+                    field.name().quoted(),
+                    field_type
+                ),
+            };
+            fields.push(FieldEncoding {
+                number: u32::try_from(field.number()).expect("field numbers are positive"),
+                repeated,
+                scalar_type: scalar_type.clone(),
+                kind,
+            });
+        }
+        Ok(MessageEncoding { fields })
+    }
+
+    /// Generates a message with one field per column, numbered sequentially
+    /// from 1.
+    fn generate(columns: &[(ColumnName, ColumnType)]) -> Result<Self> {
+        let mut fields = Vec::with_capacity(columns.len());
+        for (i, (name, typ)) in columns.iter().enumerate() {
+            let (repeated, scalar_type) = match &typ.scalar_type {
+                ScalarType::Array(element_type) | ScalarType::List { element_type, .. } => {
+                    (true, &**element_type)
+                }
+                scalar_type => (false, scalar_type),
+            };
+            let kind = match scalar_type {
+                ScalarType::Bool => FieldKind::Bool,
+                ScalarType::Int16 | ScalarType::Int32 => FieldKind::Int32,
+                ScalarType::Int64 | ScalarType::Timestamp | ScalarType::TimestampTz => {
+                    FieldKind::Int64
+                }
+                ScalarType::Float32 => FieldKind::Float,
+                ScalarType::Float64 => FieldKind::Double,
+                ScalarType::Bytes => FieldKind::Bytes,
+                ScalarType::Record { fields, .. } => FieldKind::Message(Self::generate(fields)?),
+                scalar_type if is_text(scalar_type) => FieldKind::String,
+                _ => bail!(
+                    "column {} of type {:?} cannot be encoded as protobuf",
+                    name.as_str().quoted(),
+                    typ.scalar_type
+                ),
+            };
+            fields.push(FieldEncoding {
+                number: u32::try_from(i + 1)?,
+                repeated,
+                scalar_type: scalar_type.clone(),
+                kind,
+            });
+        }
+        Ok(MessageEncoding { fields })
+    }
+
+    fn encode<'a, I>(&self, datums: I, enum_labels: &EnumLabels) -> Vec<u8>
+    where
+        I: IntoIterator<Item = Datum<'a>>,
+    {
+        let mut buf = vec![];
+        {
+            let mut os = CodedOutputStream::vec(&mut buf);
+            for (field, datum) in self.fields.iter().zip(datums) {
+                field.encode(&mut os, datum, enum_labels);
+            }
+            os.flush().expect("writing to a vec cannot fail");
+        }
+        buf
+    }
+}
+
+impl FieldEncoding {
+    /// Writes `datum` as this field. NULL datums, and NULL elements of
+    /// repeated fields, are omitted.
+    fn encode(&self, os: &mut CodedOutputStream, datum: Datum, enum_labels: &EnumLabels) {
+        if datum.is_null() {
+            return;
+        }
+        if self.repeated {
+            let elements = match datum {
+                Datum::Array(array) => array.elements(),
+                Datum::List(list) => list,
+                _ => unreachable!(),
+            };
+            for element in elements.iter() {
+                if !element.is_null() {
+                    self.encode_value(os, element, enum_labels);
+                }
+            }
+        } else {
+            self.encode_value(os, datum, enum_labels);
+        }
+    }
+
+    fn encode_value(&self, os: &mut CodedOutputStream, datum: Datum, enum_labels: &EnumLabels) {
+        let n = self.number;
+        let result = match &self.kind {
+            FieldKind::Bool => os.write_bool(n, datum.unwrap_bool()),
+            FieldKind::Int32 => os.write_int32(n, int32(datum)),
+            FieldKind::SInt32 => os.write_sint32(n, int32(datum)),
+            FieldKind::SFixed32 => os.write_sfixed32(n, int32(datum)),
+            FieldKind::Enum => os.write_enum(n, int32(datum)),
+            FieldKind::Int64 => os.write_int64(n, int64(datum)),
+            FieldKind::SInt64 => os.write_sint64(n, int64(datum)),
+            FieldKind::SFixed64 => os.write_sfixed64(n, int64(datum)),
+            FieldKind::Float => os.write_float(n, datum.unwrap_float32()),
+            FieldKind::Double => os.write_double(n, float64(datum)),
+            FieldKind::Bytes => os.write_bytes(n, datum.unwrap_bytes()),
+            FieldKind::String => os.write_string(n, &text(datum, &self.scalar_type, enum_labels)),
+            FieldKind::Message(message) => {
+                os.write_bytes(n, &message.encode(datum.unwrap_list().iter(), enum_labels))
+            }
+        };
+        result.expect("writing to a vec cannot fail")
+    }
+}
+
+/// Reports whether values of `scalar_type` are encoded as protobuf strings.
+fn is_text(scalar_type: &ScalarType) -> bool {
+    matches!(
+        scalar_type,
+        ScalarType::String
+            | ScalarType::VarChar { .. }
+            | ScalarType::Char { .. }
+            | ScalarType::Numeric { .. }
+            | ScalarType::Date
+            | ScalarType::Time
+            | ScalarType::Timestamp
+            | ScalarType::TimestampTz
+            | ScalarType::Interval
+            | ScalarType::Uuid
+            | ScalarType::Jsonb
+            | ScalarType::Enum { .. }
+            | ScalarType::Range { .. }
+    )
+}
+
+fn int32(datum: Datum) -> i32 {
+    match datum {
+        Datum::Int16(i) => i.into(),
+        Datum::Int32(i) => i,
+        _ => unreachable!(),
+    }
+}
+
+/// Timestamps are encoded as milliseconds since the Unix epoch.
+fn int64(datum: Datum) -> i64 {
+    match datum {
+        Datum::Int16(i) => i.into(),
+        Datum::Int32(i) => i.into(),
+        Datum::Int64(i) => i,
+        Datum::Timestamp(ts) => ts.timestamp_millis(),
+        Datum::TimestampTz(ts) => ts.timestamp_millis(),
+        _ => unreachable!(),
+    }
+}
+
+fn float64(datum: Datum) -> f64 {
+    match datum {
+        Datum::Float32(f) => f.into_inner().into(),
+        Datum::Float64(f) => f.into_inner(),
+        _ => unreachable!(),
+    }
+}
+
+/// Formats `datum` using its PostgreSQL text representation.
+fn text(datum: Datum, scalar_type: &ScalarType, enum_labels: &EnumLabels) -> String {
+    let mut buf = String::new();
+    match scalar_type {
+        ScalarType::String | ScalarType::VarChar { .. } => return datum.unwrap_str().to_owned(),
+        ScalarType::Char { length } => return char::format_str_pad(datum.unwrap_str(), *length),
+        ScalarType::Numeric { .. } => strconv::format_numeric(&mut buf, &datum.unwrap_numeric()),
+        ScalarType::Date => strconv::format_date(&mut buf, datum.unwrap_date()),
+        ScalarType::Time => strconv::format_time(&mut buf, datum.unwrap_time()),
+        ScalarType::Timestamp => strconv::format_timestamp(&mut buf, datum.unwrap_timestamp()),
+        ScalarType::TimestampTz => {
+            strconv::format_timestamptz(&mut buf, datum.unwrap_timestamptz())
+        }
+        ScalarType::Interval => strconv::format_interval(&mut buf, datum.unwrap_interval()),
+        ScalarType::Uuid => strconv::format_uuid(&mut buf, datum.unwrap_uuid()),
+        ScalarType::Jsonb => strconv::format_jsonb(&mut buf, JsonbRef::from_datum(datum)),
+        ScalarType::Enum { custom_oid } => {
+            return enum_labels
+                .label(*custom_oid, datum.unwrap_int32())
+                .to_owned()
+        }
+        ScalarType::Range { element_type } => return format_range(datum, element_type),
+        _ => unreachable!(),
+    };
+    buf
+}
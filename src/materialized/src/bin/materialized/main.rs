@@ -47,6 +47,7 @@ use ore::cgroup::{detect_memory_limit, MemoryLimit};
 use ore::metric;
 use ore::metrics::ThirdPartyMetric;
 use ore::metrics::{raw::IntCounterVec, MetricsRegistry};
+use sql::auth::ScramSha256Verifier;
 use structopt::StructOpt;
 use sysinfo::{ProcessorExt, SystemExt};
 
@@ -284,6 +285,40 @@ struct Args {
         value_name = "PATH"
     )]
     tls_key: Option<PathBuf>,
+    /// How PostgreSQL clients must authenticate with a password.
+    ///
+    /// If set to "scram-sha-256", then all PostgreSQL clients must prove that
+    /// they know the password of the role they connect as using the
+    /// SCRAM-SHA-256 mechanism, which does not send the password over the wire.
+    ///
+    /// If set to "password", then all PostgreSQL clients must send the
+    /// password of the role they connect as in cleartext. This method
+    /// requires TLS.
+    ///
+    /// In either mode, roles without a password cannot log in. If unset,
+    /// clients that connect as a role with a password must authenticate via
+    /// SCRAM-SHA-256, while clients that connect as a role without a password
+    /// are not asked for one.
+    #[structopt(
+        long,
+        env = "MZ_PASSWORD_AUTH",
+        possible_values = &["password", "scram-sha-256"],
+        value_name = "METHOD"
+    )]
+    password_auth: Option<String>,
+    /// A password to assign to the default `materialize` role at startup.
+    ///
+    /// The password is only assigned if the role does not already have a
+    /// password, so a password later changed via `ALTER ROLE` is preserved
+    /// across restarts. Use this option to log in as the `materialize` role
+    /// when `--password-auth` is set.
+    #[structopt(
+        long,
+        env = "MZ_BOOTSTRAP_PASSWORD",
+        hide_env_values = true,
+        value_name = "PASSWORD"
+    )]
+    bootstrap_password: Option<String>,
 
     // === Storage options. ===
     /// Where to store data.
@@ -431,6 +466,24 @@ fn run(args: Args) -> Result<(), anyhow::Error> {
         let key = args.tls_key.unwrap();
         Some(materialized::TlsConfig { mode, cert, key })
     };
+    let password_auth = match args.password_auth.as_deref() {
+        None => None,
+        Some("password") => {
+            if tls.is_none() {
+                bail!(
+                    "cannot specify --password-auth=password and --tls-mode=disable simultaneously"
+                );
+            }
+            Some(pgwire::PasswordAuth::Cleartext)
+        }
+        Some("scram-sha-256") => Some(pgwire::PasswordAuth::ScramSha256),
+        Some(_) => unreachable!(),
+    };
+    let bootstrap_password = match args.bootstrap_password {
+        None => None,
+        Some(password) if password.is_empty() => bail!("--bootstrap-password must not be empty"),
+        Some(password) => Some(ScramSha256Verifier::new(&password)?),
+    };
 
     // Configure storage.
     let data_directory = args.data_directory;
@@ -676,6 +729,8 @@ swap: {swap_total}KB total, {swap_used}KB used{swap_limit}",
         listen_addr: args.listen_addr,
         third_party_metrics_listen_addr: args.third_party_metrics_listen_addr,
         tls,
+        password_auth,
+        bootstrap_password,
         data_directory,
         symbiosis_url: args.symbiosis,
         experimental_mode: args.experimental,
@@ -30,6 +30,7 @@ use build_info::BuildInfo;
 use coord::LoggingConfig;
 use ore::metrics::MetricsRegistry;
 use pid_file::PidFile;
+use sql::auth::ScramSha256Verifier;
 
 use crate::mux::Mux;
 use crate::server_metrics::Metrics;
@@ -110,6 +111,12 @@ pub struct Config {
     pub third_party_metrics_listen_addr: Option<SocketAddr>,
     /// TLS encryption configuration.
     pub tls: Option<TlsConfig>,
+    /// The password authentication method that PostgreSQL clients must use.
+    /// See [`pgwire::Config::password_auth`] for details.
+    pub password_auth: Option<pgwire::PasswordAuth>,
+    /// A password to assign to the `materialize` role at startup, if that role
+    /// does not already have a password.
+    pub bootstrap_password: Option<ScramSha256Verifier>,
 
     // === Storage options. ===
     /// The directory in which `materialized` should store its own metadata.
@@ -238,6 +245,7 @@ pub async fn serve(config: Config) -> Result<Server, anyhow::Error> {
         build_info: &BUILD_INFO,
         metrics_registry: config.metrics_registry.clone(),
         persist: config.persist,
+        bootstrap_password: config.bootstrap_password,
     })
     .await?;
 
@@ -267,6 +275,7 @@ pub async fn serve(config: Config) -> Result<Server, anyhow::Error> {
     tokio::spawn({
         let pgwire_server = pgwire::Server::new(pgwire::Config {
             tls: pgwire_tls,
+            password_auth: config.password_auth,
             coord_client: coord_client.clone(),
             metrics_registry: &metrics_registry,
         });
@@ -0,0 +1,178 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Integration tests for password authentication.
+
+use std::error::Error;
+
+use postgres::error::SqlState;
+use postgres::NoTls;
+
+use pgwire::PasswordAuth;
+
+use crate::util::PostgresErrorExt;
+
+pub mod util;
+
+#[test]
+fn test_password_auth_optional() -> Result<(), Box<dyn Error>> {
+    ore::test::init_logging();
+
+    let server = util::start_server(util::Config::default())?;
+    let mut client = server.connect(NoTls)?;
+    client.batch_execute("CREATE ROLE alice LOGIN SUPERUSER PASSWORD 'hunter2'")?;
+    client.batch_execute("CREATE ROLE bob LOGIN SUPERUSER")?;
+
+    // Roles with a password must authenticate, even though the server does
+    // not demand passwords.
+    server
+        .pg_config()
+        .user("alice")
+        .password("hunter2")
+        .connect(NoTls)?;
+    assert!(server.pg_config().user("alice").connect(NoTls).is_err());
+    let err = server
+        .pg_config()
+        .user("alice")
+        .password("hunter3")
+        .connect(NoTls)
+        .unwrap_db_error();
+    assert_eq!(err.code(), &SqlState::INVALID_PASSWORD);
+    assert_eq!(
+        err.message(),
+        "password authentication failed for user \"alice\""
+    );
+
+    // Roles without a password are not asked for one.
+    server.pg_config().user("bob").connect(NoTls)?;
+
+    // Passwords can be changed, added, and removed.
+    client.batch_execute("ALTER ROLE alice PASSWORD 'hunter3'")?;
+    client.batch_execute("ALTER USER bob WITH PASSWORD 'hunter4'")?;
+    server
+        .pg_config()
+        .user("alice")
+        .password("hunter3")
+        .connect(NoTls)?;
+    let err = server
+        .pg_config()
+        .user("alice")
+        .password("hunter2")
+        .connect(NoTls)
+        .unwrap_db_error();
+    assert_eq!(err.code(), &SqlState::INVALID_PASSWORD);
+    server
+        .pg_config()
+        .user("bob")
+        .password("hunter4")
+        .connect(NoTls)?;
+    // Only the role itself can remove its password.
+    let err = client
+        .batch_execute("ALTER ROLE alice PASSWORD NULL")
+        .unwrap_db_error();
+    assert_eq!(
+        err.message(),
+        "permission denied to remove the password of role \"alice\""
+    );
+    server
+        .pg_config()
+        .user("alice")
+        .password("hunter3")
+        .connect(NoTls)?
+        .batch_execute("ALTER ROLE alice PASSWORD NULL")?;
+    server.pg_config().user("alice").connect(NoTls)?;
+
+    Ok(())
+}
+
+#[test]
+fn test_password_auth_required() -> Result<(), Box<dyn Error>> {
+    ore::test::init_logging();
+
+    let data_dir = tempfile::tempdir()?;
+    let config = util::Config::default().data_directory(data_dir.path());
+
+    // Passwords must persist across restarts.
+    {
+        let server = util::start_server(config.clone())?;
+        let mut client = server.connect(NoTls)?;
+        client.batch_execute("CREATE ROLE alice LOGIN SUPERUSER PASSWORD 'hunter2'")?;
+    }
+
+    for method in &[PasswordAuth::ScramSha256, PasswordAuth::Cleartext] {
+        let server = util::start_server(config.clone().with_password_auth(*method))?;
+
+        let mut client = server
+            .pg_config()
+            .user("alice")
+            .password("hunter2")
+            .connect(NoTls)?;
+        assert_eq!(
+            client
+                .query_one("SELECT current_user()", &[])?
+                .get::<_, String>(0),
+            "alice"
+        );
+
+        let err = server
+            .pg_config()
+            .user("alice")
+            .password("hunter3")
+            .connect(NoTls)
+            .unwrap_db_error();
+        assert_eq!(err.code(), &SqlState::INVALID_PASSWORD);
+
+        // Roles without a password cannot log in when the server demands
+        // passwords.
+        let err = server
+            .pg_config()
+            .password("hunter2")
+            .connect(NoTls)
+            .unwrap_db_error();
+        assert_eq!(err.code(), &SqlState::INVALID_PASSWORD);
+        assert_eq!(
+            err.message(),
+            "password authentication failed for user \"materialize\""
+        );
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_bootstrap_password() -> Result<(), Box<dyn Error>> {
+    ore::test::init_logging();
+
+    let data_dir = tempfile::tempdir()?;
+    let config = util::Config::default()
+        .data_directory(data_dir.path())
+        .with_password_auth(PasswordAuth::ScramSha256)
+        .with_bootstrap_password("hunter2");
+
+    // The bootstrap password lets the default role log in when passwords are
+    // required.
+    {
+        let server = util::start_server(config.clone())?;
+        let mut client = server.pg_config().password("hunter2").connect(NoTls)?;
+        client.batch_execute("ALTER ROLE materialize PASSWORD 'hunter3'")?;
+    }
+
+    // The bootstrap password must not override a password that was changed
+    // via `ALTER ROLE`.
+    let server = util::start_server(config)?;
+    server.pg_config().password("hunter3").connect(NoTls)?;
+    let err = server
+        .pg_config()
+        .password("hunter2")
+        .connect(NoTls)
+        .unwrap_db_error();
+    assert_eq!(err.code(), &SqlState::INVALID_PASSWORD);
+
+    Ok(())
+}
@@ -28,6 +28,7 @@ use std::time::{Duration, Instant};
 
 use chrono::{DateTime, Utc};
 use log::info;
+use postgres::error::SqlState;
 use postgres::Row;
 use tempfile::NamedTempFile;
 
@@ -812,6 +813,69 @@ fn test_temporary_views() -> Result<(), Box<dyn Error>> {
     Ok(())
 }
 
+// Ensures that unique constraints are enforced across concurrent transactions.
+// A transaction's writes are validated against committed data when they are
+// issued, but another transaction may commit a conflicting key before the
+// transaction commits.
+#[test]
+fn test_unique_constraint_concurrent_transactions() -> Result<(), Box<dyn Error>> {
+    ore::test::init_logging();
+
+    let server = util::start_server(util::Config::default())?;
+    let mut client_a = server.connect(postgres::NoTls)?;
+    let mut client_b = server.connect(postgres::NoTls)?;
+    client_a.batch_execute("CREATE TABLE t (a int PRIMARY KEY, b text)")?;
+
+    client_a.batch_execute("BEGIN")?;
+    client_a.batch_execute("INSERT INTO t VALUES (1, 'a'), (2, 'a')")?;
+    client_b.batch_execute("INSERT INTO t VALUES (2, 'b')")?;
+    let err = client_a.batch_execute("COMMIT").unwrap_db_error();
+    assert_eq!(err.code(), &SqlState::UNIQUE_VIOLATION);
+    assert_eq!(
+        err.message(),
+        "duplicate key value violates unique constraint \"t_pkey\""
+    );
+
+    // None of the failed transaction's writes may be applied.
+    let rows: Vec<(i32, String)> = client_a
+        .query("SELECT a, b FROM t ORDER BY a", &[])?
+        .into_iter()
+        .map(|row| (row.get(0), row.get(1)))
+        .collect();
+    assert_eq!(rows, vec![(2, "b".into())]);
+    client_a.batch_execute("INSERT INTO t VALUES (1, 'a')")?;
+
+    Ok(())
+}
+
+// Ensures that a transaction's writes are not committed to a table that was
+// altered after the writes were issued, even if the table's arity is
+// unchanged.
+#[test]
+fn test_alter_table_concurrent_transactions() -> Result<(), Box<dyn Error>> {
+    ore::test::init_logging();
+
+    let server = util::start_server(util::Config::default())?;
+    let mut client_a = server.connect(postgres::NoTls)?;
+    let mut client_b = server.connect(postgres::NoTls)?;
+    client_a.batch_execute("CREATE TABLE t (a int, b text)")?;
+
+    client_a.batch_execute("BEGIN")?;
+    client_a.batch_execute("INSERT INTO t VALUES (1, 'a')")?;
+    client_b.batch_execute("ALTER TABLE t DROP COLUMN b")?;
+    client_b.batch_execute("ALTER TABLE t ADD COLUMN c int")?;
+    let err = client_a.batch_execute("COMMIT").unwrap_db_error();
+    assert_eq!(
+        err.message(),
+        "table materialize.public.t was altered during the transaction"
+    );
+
+    let count: i64 = client_a.query_one("SELECT count(*) FROM t", &[])?.get(0);
+    assert_eq!(count, 0);
+
+    Ok(())
+}
+
 // This test attempts to observe a linearizability violation by creating a set of
 // sources which are constantly being appended to, then creating a materialized
 // view of each of their sizes, then repeatedly reading from some subset of
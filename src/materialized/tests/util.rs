@@ -26,6 +26,7 @@ use tempfile::TempDir;
 use tokio::runtime::Runtime;
 
 use materialized::TlsMode;
+use sql::auth::ScramSha256Verifier;
 
 lazy_static! {
     pub static ref KAFKA_ADDRS: kafka_util::KafkaAddrs = match env::var("KAFKA_ADDRS") {
@@ -39,6 +40,8 @@ pub struct Config {
     data_directory: Option<PathBuf>,
     logging_granularity: Option<Duration>,
     tls: Option<materialized::TlsConfig>,
+    password_auth: Option<pgwire::PasswordAuth>,
+    bootstrap_password: Option<String>,
     experimental_mode: bool,
     safe_mode: bool,
     workers: usize,
@@ -51,6 +54,8 @@ impl Default for Config {
             data_directory: None,
             logging_granularity: Some(Duration::from_secs(1)),
             tls: None,
+            password_auth: None,
+            bootstrap_password: None,
             experimental_mode: false,
             safe_mode: false,
             workers: 1,
@@ -84,6 +89,16 @@ impl Config {
         self
     }
 
+    pub fn with_password_auth(mut self, password_auth: pgwire::PasswordAuth) -> Self {
+        self.password_auth = Some(password_auth);
+        self
+    }
+
+    pub fn with_bootstrap_password(mut self, bootstrap_password: &str) -> Self {
+        self.bootstrap_password = Some(bootstrap_password.into());
+        self
+    }
+
     pub fn experimental_mode(mut self) -> Self {
         self.experimental_mode = true;
         self
@@ -136,6 +151,11 @@ pub fn start_server(config: Config) -> Result<Server, Box<dyn Error>> {
         symbiosis_url: None,
         listen_addr: SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), 0),
         tls: config.tls,
+        password_auth: config.password_auth,
+        bootstrap_password: config
+            .bootstrap_password
+            .map(|p| ScramSha256Verifier::new(&p))
+            .transpose()?,
         experimental_mode: config.experimental_mode,
         safe_mode: config.safe_mode,
         disable_user_indexes: false,
@@ -65,6 +65,19 @@ pub enum Type {
     Uuid,
     /// A function name.
     RegProc,
+    /// A contiguous range of values.
+    Range {
+        /// The type of the bounds of the range.
+        element_type: Box<Type>,
+    },
+    /// A user-defined enumerated type.
+    ///
+    /// The OID immediately following `oid` is reserved for the type's array
+    /// type.
+    Enum {
+        /// The OID of the type.
+        oid: u32,
+    },
 }
 
 lazy_static! {
@@ -92,8 +105,16 @@ lazy_static! {
 
 impl Type {
     /// Returns the type corresponding to the provided OID, if the OID is known.
+    ///
+    /// Array types are known if their element type is known.
     pub fn from_oid(oid: u32) -> Option<Type> {
         let ty = postgres_types::Type::from_oid(oid)?;
+        if let postgres_types::Kind::Array(elem_type) = ty.kind() {
+            return match Type::from_oid(elem_type.oid())? {
+                Type::Array(_) => None,
+                elem_type => Some(Type::Array(Box::new(elem_type))),
+            };
+        }
         match ty {
             postgres_types::Type::BOOL => Some(Type::Bool),
             postgres_types::Type::BYTEA => Some(Type::Bytea),
@@ -115,6 +136,12 @@ impl Type {
             postgres_types::Type::TIMESTAMPTZ => Some(Type::TimestampTz),
             postgres_types::Type::UUID => Some(Type::Uuid),
             postgres_types::Type::REGPROC => Some(Type::RegProc),
+            postgres_types::Type::INT4_RANGE => Some(Type::range(Type::Int4)),
+            postgres_types::Type::INT8_RANGE => Some(Type::range(Type::Int8)),
+            postgres_types::Type::NUM_RANGE => Some(Type::range(Type::Numeric)),
+            postgres_types::Type::TS_RANGE => Some(Type::range(Type::Timestamp)),
+            postgres_types::Type::TSTZ_RANGE => Some(Type::range(Type::TimestampTz)),
+            postgres_types::Type::DATE_RANGE => Some(Type::range(Type::Date)),
             _ => None,
         }
     }
@@ -146,6 +173,18 @@ impl Type {
                 Type::TimestampTz => &postgres_types::Type::TIMESTAMPTZ_ARRAY,
                 Type::Uuid => &postgres_types::Type::UUID_ARRAY,
                 Type::RegProc => &postgres_types::Type::REGPROC_ARRAY,
+                Type::Range { element_type } => match &**element_type {
+                    Type::Int4 => &postgres_types::Type::INT4_RANGE_ARRAY,
+                    Type::Int8 => &postgres_types::Type::INT8_RANGE_ARRAY,
+                    Type::Numeric => &postgres_types::Type::NUM_RANGE_ARRAY,
+                    Type::Timestamp => &postgres_types::Type::TS_RANGE_ARRAY,
+                    Type::TimestampTz => &postgres_types::Type::TSTZ_RANGE_ARRAY,
+                    Type::Date => &postgres_types::Type::DATE_RANGE_ARRAY,
+                    _ => unreachable!(),
+                },
+                // The array types of user-defined enums are reported under
+                // their own OIDs by `Type::oid`.
+                Type::Enum { .. } => &postgres_types::Type::ANYARRAY,
             },
             Type::Bool => &postgres_types::Type::BOOL,
             Type::Bytea => &postgres_types::Type::BYTEA,
@@ -170,6 +209,22 @@ impl Type {
             Type::TimestampTz => &postgres_types::Type::TIMESTAMPTZ,
             Type::Uuid => &postgres_types::Type::UUID,
             Type::RegProc => &postgres_types::Type::REGPROC,
+            Type::Range { element_type } => match &**element_type {
+                Type::Int4 => &postgres_types::Type::INT4_RANGE,
+                Type::Int8 => &postgres_types::Type::INT8_RANGE,
+                Type::Numeric => &postgres_types::Type::NUM_RANGE,
+                Type::Timestamp => &postgres_types::Type::TS_RANGE,
+                Type::TimestampTz => &postgres_types::Type::TSTZ_RANGE,
+                Type::Date => &postgres_types::Type::DATE_RANGE,
+                _ => unreachable!(),
+            },
+            Type::Enum { .. } => &postgres_types::Type::ANYENUM,
+        }
+    }
+
+    fn range(element_type: Type) -> Type {
+        Type::Range {
+            element_type: Box::new(element_type),
         }
     }
 
@@ -207,6 +262,12 @@ impl Type {
             &postgres_types::Type::TIMESTAMPTZ => "timestamp with time zone",
             &postgres_types::Type::VARCHAR => "character varying",
             &postgres_types::Type::REGPROC_ARRAY => "regproc[]",
+            &postgres_types::Type::INT4_RANGE_ARRAY => "int4range[]",
+            &postgres_types::Type::INT8_RANGE_ARRAY => "int8range[]",
+            &postgres_types::Type::NUM_RANGE_ARRAY => "numrange[]",
+            &postgres_types::Type::TS_RANGE_ARRAY => "tsrange[]",
+            &postgres_types::Type::TSTZ_RANGE_ARRAY => "tstzrange[]",
+            &postgres_types::Type::DATE_RANGE_ARRAY => "daterange[]",
             other => other.name(),
         }
     }
@@ -215,7 +276,14 @@ impl Type {
     ///
     /// [OID]: https://www.postgresql.org/docs/current/datatype-oid.html
     pub fn oid(&self) -> u32 {
-        self.inner().oid()
+        match self {
+            Type::Enum { oid } => *oid,
+            Type::Array(t) => match &**t {
+                Type::Enum { oid } => oid + 1,
+                _ => self.inner().oid(),
+            },
+            _ => self.inner().oid(),
+        }
     }
 
     /// Returns the number of bytes in the binary representation of this
@@ -246,6 +314,8 @@ impl Type {
             Type::TimestampTz => 8,
             Type::Uuid => 16,
             Type::RegProc => 4,
+            Type::Range { .. } => -1,
+            Type::Enum { .. } => 4,
         }
     }
 
@@ -290,6 +360,10 @@ impl Type {
             Type::TimestampTz => ScalarType::TimestampTz,
             Type::Uuid => ScalarType::Uuid,
             Type::RegProc => ScalarType::RegProc,
+            Type::Range { element_type } => ScalarType::Range {
+                element_type: Box::new(element_type.to_scalar_type_lossy()),
+            },
+            Type::Enum { oid } => ScalarType::Enum { custom_oid: *oid },
         }
     }
 }
@@ -330,6 +404,10 @@ impl From<&ScalarType> for Type {
             ScalarType::Uuid => Type::Uuid,
             ScalarType::Numeric { .. } => Type::Numeric,
             ScalarType::RegProc => Type::RegProc,
+            ScalarType::Range { element_type } => Type::Range {
+                element_type: Box::new(From::from(&**element_type)),
+            },
+            ScalarType::Enum { custom_oid } => Type::Enum { oid: *custom_oid },
         }
     }
 }
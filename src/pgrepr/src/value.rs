@@ -8,7 +8,7 @@
 // by the Apache License, Version 2.0.
 
 use std::collections::BTreeMap;
-use std::convert::TryInto;
+use std::convert::{TryFrom, TryInto};
 use std::error::Error;
 use std::io;
 use std::str;
@@ -21,8 +21,12 @@ use uuid::Uuid;
 
 use ore::fmt::FormatBuffer;
 use repr::adt::array::ArrayDimension;
+use repr::adt::enums::EnumLabels;
 use repr::adt::jsonb::JsonbRef;
 use repr::adt::numeric::{self as adt_numeric};
+use repr::adt::range::{
+    RangeBound, RANGE_EMPTY, RANGE_LB_INC, RANGE_LB_INF, RANGE_UB_INC, RANGE_UB_INF,
+};
 use repr::strconv::{self, Nestable};
 use repr::{ColumnName, Datum, RelationType, Row, RowArena, ScalarType};
 
@@ -71,6 +75,8 @@ pub enum Value {
     Numeric(Numeric),
     /// A sequence of heterogeneous values.
     Record(Vec<Option<Value>>),
+    /// A contiguous range of values, or `None` if the range is empty.
+    Range(Option<(RangeBound<Box<Value>>, RangeBound<Box<Value>>)>),
     /// A time.
     Time(NaiveTime),
     /// A date and time, without a timezone.
@@ -97,7 +103,9 @@ impl Value {
     ///
     /// The conversion happens in the obvious manner, except that `Datum::Null`
     /// is converted to `None` to align with how PostgreSQL handles NULL.
-    pub fn from_datum(datum: Datum, typ: &ScalarType) -> Option<Value> {
+    /// Values of enum types are converted to their label, which is looked up
+    /// in `enum_labels`.
+    pub fn from_datum(datum: Datum, typ: &ScalarType, enum_labels: &EnumLabels) -> Option<Value> {
         match (datum, typ) {
             (Datum::Null, _) => None,
             (Datum::True, ScalarType::Bool) => Some(Value::Bool(true)),
@@ -106,6 +114,9 @@ impl Value {
             (Datum::Int32(i), ScalarType::Int32) => Some(Value::Int4(i)),
             (Datum::Int32(i), ScalarType::Oid) => Some(Value::Int4(i)),
             (Datum::Int32(i), ScalarType::RegProc) => Some(Value::Int4(i)),
+            (Datum::Int32(i), ScalarType::Enum { custom_oid }) => {
+                Some(Value::Text(enum_labels.label(*custom_oid, i).to_owned()))
+            }
             (Datum::Int64(i), ScalarType::Int64) => Some(Value::Int8(i)),
             (Datum::Float32(f), ScalarType::Float32) => Some(Value::Float4(*f)),
             (Datum::Float64(f), ScalarType::Float64) => Some(Value::Float8(*f)),
@@ -136,14 +147,14 @@ impl Value {
                 let elements = array
                     .elements()
                     .iter()
-                    .map(|elem| Value::from_datum(elem, elem_type))
+                    .map(|elem| Value::from_datum(elem, elem_type, enum_labels))
                     .collect();
                 Some(Value::Array { dims, elements })
             }
             (Datum::List(list), ScalarType::List { element_type, .. }) => {
                 let elements = list
                     .iter()
-                    .map(|elem| Value::from_datum(elem, element_type))
+                    .map(|elem| Value::from_datum(elem, element_type, enum_labels))
                     .collect();
                 Some(Value::List(elements))
             }
@@ -151,30 +162,62 @@ impl Value {
                 let fields = record
                     .iter()
                     .zip(fields)
-                    .map(|(e, (_name, ty))| Value::from_datum(e, &ty.scalar_type))
+                    .map(|(e, (_name, ty))| Value::from_datum(e, &ty.scalar_type, enum_labels))
                     .collect();
                 Some(Value::Record(fields))
             }
             (Datum::Map(dict), ScalarType::Map { value_type, .. }) => {
                 let entries = dict
                     .iter()
-                    .map(|(k, v)| (k.to_owned(), Value::from_datum(v, value_type)))
+                    .map(|(k, v)| (k.to_owned(), Value::from_datum(v, value_type, enum_labels)))
                     .collect();
                 Some(Value::Map(entries))
             }
+            (Datum::Range(range), ScalarType::Range { element_type }) => {
+                let bound = |bound: RangeBound<Datum>| {
+                    bound.map(|value| {
+                        let value = Value::from_datum(value, element_type, enum_labels);
+                        Box::new(value.expect("range bounds are never null"))
+                    })
+                };
+                Some(Value::Range(
+                    range
+                        .bounds()
+                        .map(|(lower, upper)| (bound(lower), bound(upper))),
+                ))
+            }
             _ => panic!("can't serialize {}::{:?}", datum, typ),
         }
     }
 
     /// Converts a Materialize datum and type from this value.
     ///
+    /// The labels of enum types are only known to the catalog, so values of
+    /// enum types are converted to a [`Datum::String`] holding their label,
+    /// which the caller must resolve to a proper enum value.
+    ///
     /// To construct a null datum, see the [`null_datum`] function.
     pub fn into_datum<'a>(self, buf: &'a RowArena, typ: &Type) -> (Datum<'a>, ScalarType) {
         match self {
-            Value::Array { .. } => {
-                // This situation is handled gracefully by Value::decode; if we
-                // wind up here it's a programming error.
-                unreachable!("into_datum cannot be called on Value::Array");
+            Value::Array { dims, elements } => {
+                let elem_pg_type = match typ {
+                    Type::Array(t) => &*t,
+                    _ => panic!("Value::Array should have type Type::Array. Found {:?}", typ),
+                };
+                let (_, elem_type) = null_datum(&elem_pg_type);
+                let mut row = Row::default();
+                row.push_array(
+                    &dims,
+                    elements.into_iter().map(|elem| match elem {
+                        Some(elem) => elem.into_datum(buf, &elem_pg_type).0,
+                        None => Datum::Null,
+                    }),
+                )
+                .expect("array dimensions match the number of elements");
+                (
+                    buf.push_unary_row(row),
+                    ScalarType::Array(Box::new(elem_type)),
+                )
             }
             Value::Bool(true) => (Datum::True, ScalarType::Bool),
             Value::Bool(false) => (Datum::False, ScalarType::Bool),
@@ -238,13 +281,49 @@ impl Value {
                 // wind up here it's a programming error.
                 unreachable!("into_datum cannot be called on Value::Record");
             }
+            Value::Range(range) => {
+                let elem_pg_type = match typ {
+                    Type::Range { element_type } => &**element_type,
+                    _ => panic!("Value::Range should have type Type::Range. Found {:?}", typ),
+                };
+                let (_, elem_type) = null_datum(&elem_pg_type);
+                let mut row = Row::default();
+                match range {
+                    None => row.push_empty_range(),
+                    Some((lower, upper)) => {
+                        let bound = |bound: RangeBound<Box<Value>>| {
+                            bound.map(|value| value.into_datum(buf, &elem_pg_type).0)
+                        };
+                        row.push_range(bound(lower), bound(upper))
+                            .expect("range bounds are validated when decoded");
+                    }
+                }
+                (
+                    buf.push_unary_row(row),
+                    ScalarType::Range {
+                        element_type: Box::new(elem_type),
+                    },
+                )
+            }
             Value::Time(t) => (Datum::Time(t), ScalarType::Time),
             Value::Timestamp(ts) => (Datum::Timestamp(ts), ScalarType::Timestamp),
             Value::TimestampTz(ts) => (Datum::TimestampTz(ts), ScalarType::TimestampTz),
             Value::Interval(iv) => (Datum::Interval(iv.0), ScalarType::Interval),
-            Value::Text(s) => (Datum::String(buf.push_string(s)), ScalarType::String),
+            Value::Text(s) => match typ {
+                Type::Enum { oid } => (
+                    Datum::String(buf.push_string(s)),
+                    ScalarType::Enum { custom_oid: *oid },
+                ),
+                _ => (Datum::String(buf.push_string(s)), ScalarType::String),
+            },
+            // Char data is stored with its blank padding removed.
             Value::Char { inner, length } => (
-                Datum::String(buf.push_string(inner)),
+                Datum::String(
+                    buf.push_string(
+                        repr::adt::char::format_str_trim(&inner, length, false)
+                            .expect("trimming char values cannot fail"),
+                    ),
+                ),
                 ScalarType::Char { length },
             ),
             Value::VarChar(s) => (
@@ -307,6 +386,13 @@ impl Value {
                 None => buf.write_null(),
                 Some(elem) => elem.encode_text(buf.nonnull_buffer()),
             }),
+            Value::Range(range) => strconv::format_range(
+                buf,
+                range
+                    .as_ref()
+                    .map(|(lower, upper)| (lower.as_ref(), upper.as_ref())),
+                |buf, elem| elem.encode_text(buf),
+            ),
             Value::Text(s) | Value::VarChar(s) => strconv::format_string(buf, s),
             Value::Char { inner, length } => {
                 strconv::format_string(buf, &repr::adt::char::format_str_pad(&inner, *length))
@@ -404,6 +490,33 @@ impl Value {
                 }
                 Ok(postgres_types::IsNull::No)
             }
+            Value::Range(range) => {
+                let elem_type = match ty {
+                    Type::Range { element_type } => element_type,
+                    _ => unreachable!(),
+                };
+                match range {
+                    None => buf.put_u8(RANGE_EMPTY),
+                    Some((lower, upper)) => {
+                        let mut flags = 0;
+                        match &lower.value {
+                            None => flags |= RANGE_LB_INF,
+                            Some(_) if lower.inclusive => flags |= RANGE_LB_INC,
+                            Some(_) => (),
+                        }
+                        match &upper.value {
+                            None => flags |= RANGE_UB_INF,
+                            Some(_) if upper.inclusive => flags |= RANGE_UB_INC,
+                            Some(_) => (),
+                        }
+                        buf.put_u8(flags);
+                        for value in lower.value.iter().chain(upper.value.iter()) {
+                            encode_element(buf, Some(&**value), elem_type)?;
+                        }
+                    }
+                }
+                Ok(postgres_types::IsNull::No)
+            }
             Value::Text(s) => s.to_sql(&PgType::TEXT, buf),
             Value::Char { inner, length } => {
                 repr::adt::char::format_str_pad(&inner, *length).to_sql(&PgType::BPCHAR, buf)
@@ -440,7 +553,19 @@ impl Value {
     pub fn decode_text(ty: &Type, raw: &[u8]) -> Result<Value, Box<dyn Error + Sync + Send>> {
         let raw = str::from_utf8(raw)?;
         Ok(match ty {
-            Type::Array(_) => return Err("input of array types is not implemented".into()),
+            Type::Array(elem_type) => {
+                let (mut dims, elements) = strconv::parse_array_with_dims(
+                    raw,
+                    || None,
+                    |elem_text| Value::decode_text(elem_type, elem_text.as_bytes()).map(Some),
+                )?;
+                // Materialize does not support arrays whose lower bounds are
+                // not 1, so explicit lower bounds are discarded.
+                for dim in &mut dims {
+                    dim.lower_bound = 1;
+                }
+                Value::Array { dims, elements }
+            }
             Type::Bool => Value::Bool(strconv::parse_bool(raw)?),
             Type::Bytea => Value::Bytea(strconv::parse_bytes(raw)?),
             Type::Date => Value::Date(strconv::parse_date(raw)?),
@@ -466,6 +591,12 @@ impl Value {
             Type::Record(_) => {
                 return Err("input of anonymous composite types is not implemented".into())
             }
+            Type::Range { element_type } => decode_range(
+                element_type,
+                strconv::parse_range(raw, |elem_text| {
+                    Value::decode_text(element_type, elem_text.as_bytes())
+                })?,
+            )?,
             Type::Text => Value::Text(raw.to_owned()),
             Type::Char => {
                 let inner = raw.to_owned();
@@ -481,6 +612,8 @@ impl Value {
             Type::Timestamp => Value::Timestamp(strconv::parse_timestamp(raw)?),
             Type::TimestampTz => Value::TimestampTz(strconv::parse_timestamptz(raw)?),
             Type::Uuid => Value::Uuid(Uuid::parse_str(raw)?),
+            // Enum labels are validated when the planner resolves them.
+            Type::Enum { .. } => Value::Text(raw.to_owned()),
         })
     }
 
@@ -505,6 +638,40 @@ impl Value {
             Type::Map { .. } => Err("binary decoding of map types is not implemented".into()),
             Type::Numeric => Numeric::from_sql(ty.inner(), raw).map(Value::Numeric),
             Type::Record(_) => Err("input of anonymous composite types is not implemented".into()),
+            Type::Range { element_type } => {
+                let (flags, mut raw) = match raw.split_first() {
+                    Some((flags, raw)) => (*flags, raw),
+                    None => return Err("insufficient data left in message".into()),
+                };
+                if flags & RANGE_EMPTY != 0 {
+                    return decode_range(element_type, None);
+                }
+                let mut bound = |inf_flag, inc_flag| -> Result<_, Box<dyn Error + Sync + Send>> {
+                    if flags & inf_flag != 0 {
+                        return Ok(RangeBound {
+                            value: None,
+                            inclusive: false,
+                        });
+                    }
+                    if raw.len() < 4 {
+                        return Err("insufficient data left in message".into());
+                    }
+                    let (len, rest) = raw.split_at(4);
+                    let len = usize::try_from(i32::from_be_bytes(len.try_into()?))?;
+                    if rest.len() < len {
+                        return Err("insufficient data left in message".into());
+                    }
+                    let (elem, rest) = rest.split_at(len);
+                    raw = rest;
+                    Ok(RangeBound {
+                        value: Some(Value::decode_binary(element_type, elem)?),
+                        inclusive: flags & inc_flag != 0,
+                    })
+                };
+                let lower = bound(RANGE_LB_INF, RANGE_LB_INC)?;
+                let upper = bound(RANGE_UB_INF, RANGE_UB_INC)?;
+                decode_range(element_type, Some((lower, upper)))
+            }
             Type::Text => String::from_sql(ty.inner(), raw).map(Value::Text),
             Type::Char => String::from_sql(ty.inner(), raw).map(|inner| {
                 let length = Some(inner.len());
@@ -515,10 +682,45 @@ impl Value {
             Type::Timestamp => NaiveDateTime::from_sql(ty.inner(), raw).map(Value::Timestamp),
             Type::TimestampTz => DateTime::<Utc>::from_sql(ty.inner(), raw).map(Value::TimestampTz),
             Type::Uuid => Uuid::from_sql(ty.inner(), raw).map(Value::Uuid),
+            // Enum values are transmitted as their label.
+            Type::Enum { .. } => {
+                let label = String::from_sql(ty.inner(), raw)?;
+                Value::decode_text(ty, label.as_bytes())
+            }
         }
     }
 }
 
+/// Constructs a range of values of type `element_type` from its decoded
+/// bounds, or from `None` if the range is empty.
+///
+/// The bounds are validated and canonicalized by packing them into a
+/// [`Datum::Range`], so that the resulting value can always be converted back
+/// into a datum.
+fn decode_range(
+    element_type: &Type,
+    range: Option<(RangeBound<Value>, RangeBound<Value>)>,
+) -> Result<Value, Box<dyn Error + Sync + Send>> {
+    let arena = RowArena::new();
+    let mut row = Row::default();
+    match range {
+        None => row.push_empty_range(),
+        Some((lower, upper)) => {
+            let bound = |bound: RangeBound<Value>| {
+                bound.map(|value| value.into_datum(&arena, element_type).0)
+            };
+            row.push_range(bound(lower), bound(upper))?;
+        }
+    }
+    let (_, element_type) = null_datum(element_type);
+    let typ = ScalarType::Range {
+        element_type: Box::new(element_type),
+    };
+    // Ranges never contain enum values.
+    let value = Value::from_datum(row.unpack_first(), &typ, &EnumLabels::default());
+    Ok(value.expect("ranges are never null"))
+}
+
 fn encode_element(buf: &mut BytesMut, elem: Option<&Value>, ty: &Type) -> Result<(), io::Error> {
     match elem {
         None => buf.put_i32(-1),
@@ -604,6 +806,13 @@ pub fn null_datum(ty: &Type) -> (Datum<'static>, ScalarType) {
             }
         }
         Type::RegProc => ScalarType::RegProc,
+        Type::Range { element_type } => {
+            let (_, element_type) = null_datum(element_type);
+            ScalarType::Range {
+                element_type: Box::new(element_type),
+            }
+        }
+        Type::Enum { oid } => ScalarType::Enum { custom_oid: *oid },
     };
     (Datum::Null, ty)
 }
@@ -612,9 +821,13 @@ pub fn null_datum(ty: &Type) -> (Datum<'static>, ScalarType) {
 ///
 /// Calling this function is equivalent to mapping [`Value::from_datum`] over
 /// every datum in `row`.
-pub fn values_from_row(row: Row, typ: &RelationType) -> Vec<Option<Value>> {
+pub fn values_from_row(
+    row: Row,
+    typ: &RelationType,
+    enum_labels: &EnumLabels,
+) -> Vec<Option<Value>> {
     row.iter()
         .zip(typ.column_types.iter())
-        .map(|(col, typ)| Value::from_datum(col, &typ.scalar_type))
+        .map(|(col, typ)| Value::from_datum(col, &typ.scalar_type, enum_labels))
         .collect()
 }
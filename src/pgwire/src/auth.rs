@@ -0,0 +1,349 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Password authentication of pgwire connections.
+//!
+//! See "[Authentication Methods][1]" and "[SASL Authentication][2]" in the
+//! PostgreSQL reference for details on the message flows.
+//!
+//! [1]: https://www.postgresql.org/docs/11/auth-methods.html
+//! [2]: https://www.postgresql.org/docs/11/sasl-authentication.html
+
+use std::str;
+
+use openssl::base64;
+use openssl::error::ErrorStack;
+use openssl::rand;
+use postgres::error::SqlState;
+use tokio::io::{self, AsyncRead, AsyncWrite};
+
+use ore::str::StrExt;
+use sql::auth::{ScramSha256Verifier, SCRAM_SHA_256};
+
+use crate::codec::{self, FramedConn};
+use crate::message::{BackendMessage, ErrorResponse, FrontendMessage};
+use crate::server::PasswordAuth;
+
+/// The number of random bytes that the server contributes to the SCRAM nonce.
+const SCRAM_NONCE_LEN: usize = 18;
+
+/// The result of an authentication exchange.
+enum Outcome {
+    /// The client proved knowledge of the role's password.
+    Success,
+    /// The client failed to authenticate. The error should be delivered to
+    /// the client.
+    Failure(ErrorResponse),
+    /// The client hung up during the exchange. This is common, as clients
+    /// like `psql` disconnect to prompt the user for a password upon learning
+    /// that one is required.
+    Hangup,
+}
+
+/// Authenticates the client on `conn` as `user` using the specified password
+/// authentication `method`.
+///
+/// `verifier` is the verifier for the role's password, or `None` if the role
+/// does not have a password, in which case authentication always fails. The
+/// SCRAM exchange is then carried out with a mock verifier, whose salt is
+/// derived from `mock_auth_secret`.
+///
+/// Returns whether authentication succeeded. If authentication fails, an
+/// appropriate fatal error has already been delivered to the client.
+pub async fn authenticate<A>(
+    conn: &mut FramedConn<A>,
+    method: PasswordAuth,
+    user: &str,
+    verifier: Option<&ScramSha256Verifier>,
+    mock_auth_secret: &[u8],
+) -> Result<bool, io::Error>
+where
+    A: AsyncRead + AsyncWrite + Unpin,
+{
+    let outcome = match method {
+        PasswordAuth::Cleartext => authenticate_cleartext(conn, user, verifier).await?,
+        PasswordAuth::ScramSha256 => {
+            authenticate_scram(conn, user, verifier, mock_auth_secret).await?
+        }
+    };
+    match outcome {
+        Outcome::Success => Ok(true),
+        Outcome::Failure(err) => {
+            conn.send(err).await?;
+            Ok(false)
+        }
+        Outcome::Hangup => Ok(false),
+    }
+}
+
+async fn authenticate_cleartext<A>(
+    conn: &mut FramedConn<A>,
+    user: &str,
+    verifier: Option<&ScramSha256Verifier>,
+) -> Result<Outcome, io::Error>
+where
+    A: AsyncRead + AsyncWrite + Unpin,
+{
+    conn.send(BackendMessage::AuthenticationCleartextPassword)
+        .await?;
+    conn.flush().await?;
+
+    let data = match recv_password(conn).await? {
+        Some(data) => data,
+        None => return Ok(Outcome::Hangup),
+    };
+    let password = match codec::decode_password(&data) {
+        Ok(password) => password,
+        Err(_) => return Ok(malformed("malformed password message")),
+    };
+
+    let verified = match verifier {
+        Some(verifier) => verifier.verify_password(&password).map_err(openssl_err)?,
+        None => false,
+    };
+    if verified {
+        Ok(Outcome::Success)
+    } else {
+        Ok(password_failed(user))
+    }
+}
+
+async fn authenticate_scram<A>(
+    conn: &mut FramedConn<A>,
+    user: &str,
+    verifier: Option<&ScramSha256Verifier>,
+    mock_auth_secret: &[u8],
+) -> Result<Outcome, io::Error>
+where
+    A: AsyncRead + AsyncWrite + Unpin,
+{
+    conn.send(BackendMessage::AuthenticationSasl {
+        mechanisms: vec![SCRAM_SHA_256],
+    })
+    .await?;
+    conn.flush().await?;
+
+    // Receive the client-first-message.
+    let data = match recv_password(conn).await? {
+        Some(data) => data,
+        None => return Ok(Outcome::Hangup),
+    };
+    let (mechanism, client_first) = match codec::decode_sasl_initial_response(&data) {
+        Ok(response) => response,
+        Err(_) => return Ok(malformed("malformed SASL initial response")),
+    };
+    if mechanism != SCRAM_SHA_256 {
+        return Ok(Outcome::Failure(ErrorResponse::fatal(
+            SqlState::PROTOCOL_VIOLATION,
+            "client selected an invalid SASL authentication mechanism",
+        )));
+    }
+    let (gs2_header, client_first_bare, client_nonce) = match str::from_utf8(&client_first)
+        .ok()
+        .and_then(parse_client_first_message)
+    {
+        Some(parsed) => parsed,
+        None => return Ok(malformed("malformed SCRAM message")),
+    };
+
+    // To avoid revealing whether the role has a password, carry out the
+    // exchange against a mock verifier if it does not. The exchange is
+    // guaranteed to fail in that case. The mock salt is the same in every
+    // exchange with the role, just like a real salt.
+    let mock_verifier;
+    let (verifier, can_succeed) = match verifier {
+        Some(verifier) => (verifier, true),
+        None => {
+            mock_verifier =
+                ScramSha256Verifier::mock(user, mock_auth_secret).map_err(openssl_err)?;
+            (&mock_verifier, false)
+        }
+    };
+
+    // Send the server-first-message.
+    let mut server_nonce = [0; SCRAM_NONCE_LEN];
+    rand::rand_bytes(&mut server_nonce).map_err(openssl_err)?;
+    let nonce = format!("{}{}", client_nonce, base64::encode_block(&server_nonce));
+    let server_first = format!(
+        "r={},s={},i={}",
+        nonce,
+        base64::encode_block(&verifier.salt),
+        verifier.iterations
+    );
+    conn.send(BackendMessage::AuthenticationSaslContinue(
+        server_first.clone().into_bytes(),
+    ))
+    .await?;
+    conn.flush().await?;
+
+    // Receive and check the client-final-message.
+    let data = match recv_password(conn).await? {
+        Some(data) => data,
+        None => return Ok(Outcome::Hangup),
+    };
+    let (client_final_without_proof, channel_binding, final_nonce, proof) =
+        match str::from_utf8(&data)
+            .ok()
+            .and_then(parse_client_final_message)
+        {
+            Some(parsed) => parsed,
+            None => return Ok(malformed("malformed SCRAM message")),
+        };
+    if base64::decode_block(channel_binding).ok().as_deref() != Some(gs2_header.as_bytes()) {
+        return Ok(malformed("SCRAM channel binding check failed"));
+    }
+    if final_nonce != nonce {
+        return Ok(malformed("SCRAM nonce mismatch"));
+    }
+    let proof = match base64::decode_block(proof) {
+        Ok(proof) => proof,
+        Err(_) => return Ok(malformed("malformed SCRAM message")),
+    };
+
+    let auth_message = format!(
+        "{},{},{}",
+        client_first_bare, server_first, client_final_without_proof
+    );
+    let verified = verifier
+        .verify_client_proof(auth_message.as_bytes(), &proof)
+        .map_err(openssl_err)?;
+    if !can_succeed || !verified {
+        return Ok(password_failed(user));
+    }
+
+    // Send the server-final-message, which proves to the client that we know
+    // the verifier.
+    let server_signature = verifier
+        .server_signature(auth_message.as_bytes())
+        .map_err(openssl_err)?;
+    let server_final = format!("v={}", base64::encode_block(&server_signature));
+    conn.send(BackendMessage::AuthenticationSaslFinal(
+        server_final.into_bytes(),
+    ))
+    .await?;
+    Ok(Outcome::Success)
+}
+
+/// Receives the payload of the next password message from the client.
+///
+/// Returns `None` if the client hangs up.
+async fn recv_password<A>(conn: &mut FramedConn<A>) -> Result<Option<Vec<u8>>, io::Error>
+where
+    A: AsyncRead + AsyncWrite + Unpin,
+{
+    match conn.recv().await? {
+        Some(FrontendMessage::Password(data)) => Ok(Some(data)),
+        Some(FrontendMessage::Terminate) | None => Ok(None),
+        Some(message) => Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!(
+                "expected password message during authentication, got {}",
+                message.name()
+            ),
+        )),
+    }
+}
+
+/// Parses a SCRAM client-first-message, returning the GS2 header, the
+/// client-first-message-bare, and the client's nonce.
+///
+/// Neither channel binding nor authorization identities are supported. The
+/// user name in the message is ignored in favor of the user name from the
+/// startup message, as in PostgreSQL.
+fn parse_client_first_message(message: &str) -> Option<(&str, &str, &str)> {
+    let mut parts = message.splitn(3, ',');
+    let cbind_flag = parts.next()?;
+    let authzid = parts.next()?;
+    let bare = parts.next()?;
+    if (cbind_flag != "n" && cbind_flag != "y") || !authzid.is_empty() {
+        return None;
+    }
+    let gs2_header = &message[..cbind_flag.len() + authzid.len() + 2];
+    let mut attrs = bare.split(',');
+    attrs.next()?.strip_prefix("n=")?;
+    let nonce = attrs.next()?.strip_prefix("r=")?;
+    if nonce.is_empty() {
+        return None;
+    }
+    Some((gs2_header, bare, nonce))
+}
+
+/// Parses a SCRAM client-final-message, returning the message without the
+/// proof, the base64-encoded channel binding, the nonce, and the
+/// base64-encoded proof.
+fn parse_client_final_message(message: &str) -> Option<(&str, &str, &str, &str)> {
+    let (without_proof, proof) = message.rsplit_once(",p=")?;
+    let mut attrs = without_proof.split(',');
+    let channel_binding = attrs.next()?.strip_prefix("c=")?;
+    let nonce = attrs.next()?.strip_prefix("r=")?;
+    Some((without_proof, channel_binding, nonce, proof))
+}
+
+fn password_failed(user: &str) -> Outcome {
+    Outcome::Failure(ErrorResponse::fatal(
+        SqlState::INVALID_PASSWORD,
+        format!("password authentication failed for user {}", user.quoted()),
+    ))
+}
+
+fn malformed(message: &str) -> Outcome {
+    Outcome::Failure(ErrorResponse::fatal(SqlState::PROTOCOL_VIOLATION, message))
+}
+
+fn openssl_err(e: ErrorStack) -> io::Error {
+    io::Error::new(io::ErrorKind::Other, e)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_client_first_message() {
+        assert_eq!(
+            parse_client_first_message("n,,n=user,r=fyko+d2lbbFgONRv9qkxdawL"),
+            Some((
+                "n,,",
+                "n=user,r=fyko+d2lbbFgONRv9qkxdawL",
+                "fyko+d2lbbFgONRv9qkxdawL"
+            ))
+        );
+        assert_eq!(
+            parse_client_first_message("y,,n=,r=abc,ext=1"),
+            Some(("y,,", "n=,r=abc,ext=1", "abc"))
+        );
+        for message in &[
+            "",
+            "n,,",
+            "n,,n=user",
+            "n,,n=user,r=",
+            "n,a=admin,n=user,r=abc",
+            "p=tls-server-end-point,,n=user,r=abc",
+            "n,,r=abc,n=user",
+        ] {
+            assert_eq!(parse_client_first_message(message), None, "{}", message);
+        }
+    }
+
+    #[test]
+    fn test_parse_client_final_message() {
+        assert_eq!(
+            parse_client_final_message("c=biws,r=abcdef,p=cHJvb2Y="),
+            Some(("c=biws,r=abcdef", "biws", "abcdef", "cHJvb2Y="))
+        );
+        for message in &[
+            "",
+            "c=biws,r=abcdef",
+            "r=abcdef,c=biws,p=cHJvb2Y=",
+            "c=biws,p=x",
+        ] {
+            assert_eq!(parse_client_final_message(message), None, "{}", message);
+        }
+    }
+}
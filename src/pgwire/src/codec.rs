@@ -196,7 +196,11 @@ impl Encoder<BackendMessage> for Codec {
     fn encode(&mut self, msg: BackendMessage, dst: &mut BytesMut) -> Result<(), io::Error> {
         // Write type byte.
         let byte = match &msg {
-            BackendMessage::AuthenticationOk => b'R',
+            BackendMessage::AuthenticationOk
+            | BackendMessage::AuthenticationCleartextPassword
+            | BackendMessage::AuthenticationSasl { .. }
+            | BackendMessage::AuthenticationSaslContinue(_)
+            | BackendMessage::AuthenticationSaslFinal(_) => b'R',
             BackendMessage::RowDescription(_) => b'T',
             BackendMessage::DataRow(_) => b'D',
             BackendMessage::CommandComplete { .. } => b'C',
@@ -251,6 +255,24 @@ impl Encoder<BackendMessage> for Codec {
             BackendMessage::AuthenticationOk => {
                 dst.put_u32(0);
             }
+            BackendMessage::AuthenticationCleartextPassword => {
+                dst.put_u32(3);
+            }
+            BackendMessage::AuthenticationSasl { mechanisms } => {
+                dst.put_u32(10);
+                for mechanism in mechanisms {
+                    dst.put_string(mechanism);
+                }
+                dst.put_u8(b'\0');
+            }
+            BackendMessage::AuthenticationSaslContinue(data) => {
+                dst.put_u32(11);
+                dst.put_slice(&data);
+            }
+            BackendMessage::AuthenticationSaslFinal(data) => {
+                dst.put_u32(12);
+                dst.put_slice(&data);
+            }
             BackendMessage::RowDescription(fields) => {
                 dst.put_length_i16(fields.len())?;
                 for f in &fields {
@@ -506,6 +528,9 @@ impl Decoder for Codec {
                         b'd' => decode_copy_data(buf, frame_len)?,
                         b'c' => decode_copy_done(buf)?,
 
+                        // Authentication flow.
+                        b'p' => decode_password_message(buf, frame_len)?,
+
                         // Invalid.
                         _ => {
                             return Err(io::Error::new(
@@ -649,6 +674,43 @@ fn decode_copy_fail(mut buf: Cursor) -> Result<FrontendMessage, io::Error> {
     Ok(FrontendMessage::CopyFail(buf.read_cstr()?.to_string()))
 }
 
+fn decode_password_message(
+    mut buf: Cursor,
+    frame_len: usize,
+) -> Result<FrontendMessage, io::Error> {
+    let mut data = Vec::with_capacity(frame_len);
+    for _ in 0..frame_len {
+        data.push(buf.read_byte()?);
+    }
+    Ok(FrontendMessage::Password(data))
+}
+
+/// Decodes the payload of a [`FrontendMessage::Password`] as a
+/// `PasswordMessage`, returning the cleartext password.
+pub fn decode_password(data: &[u8]) -> Result<String, io::Error> {
+    let mut buf = Cursor::new(data);
+    Ok(buf.read_cstr()?.to_owned())
+}
+
+/// Decodes the payload of a [`FrontendMessage::Password`] as a
+/// `SASLInitialResponse`, returning the name of the selected SASL mechanism
+/// and the mechanism-specific initial response.
+pub fn decode_sasl_initial_response(data: &[u8]) -> Result<(String, Vec<u8>), io::Error> {
+    let mut buf = Cursor::new(data);
+    let mechanism = buf.read_cstr()?.to_owned();
+    let len = buf.read_i32()?;
+    let response = if len == -1 {
+        vec![]
+    } else {
+        let len = usize::try_from(len).map_err(input_err)?;
+        if buf.buf.len() != len {
+            return Err(input_err("invalid SASL initial response length"));
+        }
+        buf.buf.to_vec()
+    };
+    Ok((mechanism, response))
+}
+
 /// Decodes data within pgwire messages.
 ///
 /// The API provided is very similar to [`bytes::Buf`], but operations return
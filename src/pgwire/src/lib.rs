@@ -22,6 +22,7 @@
 
 #![warn(clippy::as_conversions)]
 
+mod auth;
 mod codec;
 mod message;
 mod metrics;
@@ -30,4 +31,4 @@ mod server;
 
 pub use metrics::Metrics;
 pub use protocol::match_handshake;
-pub use server::{Config, Server, TlsConfig, TlsMode};
+pub use server::{Config, PasswordAuth, Server, TlsConfig, TlsMode};
@@ -8,7 +8,7 @@
 // by the Apache License, Version 2.0.
 
 use std::collections::HashMap;
-use std::convert::TryFrom;
+use std::convert::{TryFrom, TryInto};
 use std::io;
 
 use bytes::BytesMut;
@@ -17,6 +17,7 @@ use itertools::Itertools;
 use postgres::error::SqlState;
 
 use coord::session::TransactionStatus as CoordTransactionStatus;
+use repr::adt::enums::EnumLabels;
 use repr::adt::numeric::NUMERIC_DATUM_MAX_PRECISION;
 use repr::{
     ColumnName, Datum, NotNullViolation, RelationDesc, RelationType, Row, RowArena, ScalarType,
@@ -190,6 +191,15 @@ pub enum FrontendMessage {
     CopyDone,
 
     CopyFail(String),
+
+    /// The payload of a `PasswordMessage`, `SASLInitialResponse`, or
+    /// `SASLResponse` message.
+    ///
+    /// All three messages share the same type byte, so the payload can only be
+    /// interpreted by the authentication flow that is in progress. See
+    /// [`decode_password`](crate::codec::decode_password) and
+    /// [`decode_sasl_initial_response`](crate::codec::decode_sasl_initial_response).
+    Password(Vec<u8>),
 }
 
 impl FrontendMessage {
@@ -209,6 +219,7 @@ impl FrontendMessage {
             FrontendMessage::CopyData(_) => "copy_data",
             FrontendMessage::CopyDone => "copy_done",
             FrontendMessage::CopyFail(_) => "copy_fail",
+            FrontendMessage::Password(_) => "password",
         }
     }
 }
@@ -219,6 +230,12 @@ impl FrontendMessage {
 #[derive(Debug)]
 pub enum BackendMessage {
     AuthenticationOk,
+    AuthenticationCleartextPassword,
+    AuthenticationSasl {
+        mechanisms: Vec<&'static str>,
+    },
+    AuthenticationSaslContinue(Vec<u8>),
+    AuthenticationSaslFinal(Vec<u8>),
     CommandComplete {
         tag: String,
     },
@@ -345,11 +362,13 @@ impl ErrorResponse {
             CoordError::AutomaticTimestampFailure { .. } => SqlState::INTERNAL_ERROR,
             CoordError::DuplicateCursor(_) => SqlState::DUPLICATE_CURSOR,
             CoordError::Eval(_) => SqlState::INTERNAL_ERROR,
+            CoordError::CheckViolation { .. } => SqlState::CHECK_VIOLATION,
             CoordError::IdExhaustionError => SqlState::INTERNAL_ERROR,
             CoordError::IncompleteTimestamp(_) => SqlState::SQL_STATEMENT_NOT_YET_COMPLETE,
             CoordError::InvalidParameterType(_) => SqlState::INVALID_PARAMETER_VALUE,
             CoordError::InvalidTableMutationSelection => SqlState::INVALID_TRANSACTION_STATE,
             CoordError::ConstraintViolation(NotNullViolation(_)) => SqlState::NOT_NULL_VIOLATION,
+            CoordError::OnConflictRowAffectedTwice => SqlState::CARDINALITY_VIOLATION,
             CoordError::OperationProhibitsTransaction(_) => SqlState::ACTIVE_SQL_TRANSACTION,
             CoordError::OperationRequiresTransaction(_) => SqlState::NO_ACTIVE_SQL_TRANSACTION,
             CoordError::PreparedStatementExists(_) => SqlState::DUPLICATE_PSTATEMENT,
@@ -360,6 +379,7 @@ impl ErrorResponse {
             CoordError::SqlCatalog(_) => SqlState::INTERNAL_ERROR,
             CoordError::TailOnlyTransaction => SqlState::INVALID_TRANSACTION_STATE,
             CoordError::Transform(_) => SqlState::INTERNAL_ERROR,
+            CoordError::UniqueViolation(_) => SqlState::UNIQUE_VIOLATION,
             CoordError::UnknownCursor(_) => SqlState::INVALID_CURSOR_NAME,
             CoordError::UnknownParameter(_) => SqlState::INVALID_SQL_STATEMENT_NAME,
             CoordError::UnknownPreparedStatement(_) => SqlState::UNDEFINED_PSTATEMENT,
@@ -450,6 +470,7 @@ pub struct FieldDescription {
 pub fn encode_copy_row_binary(
     row: Row,
     typ: &RelationType,
+    enum_labels: &EnumLabels,
     out: &mut Vec<u8>,
 ) -> Result<(), io::Error> {
     const NULL_BYTES: [u8; 4] = (-1i32).to_be_bytes();
@@ -464,11 +485,12 @@ pub fn encode_copy_row_binary(
 
     out.extend(&count.to_be_bytes());
     let mut buf = BytesMut::new();
-    for (field, typ) in row
-        .iter()
-        .zip(&typ.column_types)
-        .map(|(datum, typ)| (pgrepr::Value::from_datum(datum, &typ.scalar_type), typ))
-    {
+    for (field, typ) in row.iter().zip(&typ.column_types).map(|(datum, typ)| {
+        (
+            pgrepr::Value::from_datum(datum, &typ.scalar_type, enum_labels),
+            typ,
+        )
+    }) {
         match field {
             None => out.extend(&NULL_BYTES),
             Some(field) => {
@@ -494,12 +516,16 @@ pub fn encode_copy_row_binary(
 pub fn encode_copy_row_text(
     row: Row,
     typ: &RelationType,
+    enum_labels: &EnumLabels,
     out: &mut Vec<u8>,
 ) -> Result<(), io::Error> {
     let delim = b'\t';
     let null = b"\\N";
     let mut buf = BytesMut::new();
-    for (idx, field) in pgrepr::values_from_row(row, typ).into_iter().enumerate() {
+    for (idx, field) in pgrepr::values_from_row(row, typ, enum_labels)
+        .into_iter()
+        .enumerate()
+    {
         if idx > 0 {
             out.push(delim);
         }
@@ -811,6 +837,327 @@ pub fn decode_copy_text_format(
     Ok(rows)
 }
 
+struct CopyCsvFormatParser<'a> {
+    data: &'a [u8],
+    position: usize,
+    column_delimiter: u8,
+    quote: u8,
+    escape: u8,
+    null_string: &'a str,
+    buffer: Vec<u8>,
+}
+
+impl<'a> CopyCsvFormatParser<'a> {
+    fn new(
+        data: &'a [u8],
+        column_delimiter: u8,
+        quote: u8,
+        escape: u8,
+        null_string: &'a str,
+    ) -> Self {
+        Self {
+            data,
+            position: 0,
+            column_delimiter,
+            quote,
+            escape,
+            null_string,
+            buffer: Vec::new(),
+        }
+    }
+
+    fn peek(&self) -> Option<u8> {
+        self.data.get(self.position).copied()
+    }
+
+    fn peek_n(&self, n: usize) -> Option<u8> {
+        self.data.get(self.position + n).copied()
+    }
+
+    fn consume_n(&mut self, n: usize) {
+        self.position = std::cmp::min(self.position + n, self.data.len());
+    }
+
+    /// Reports whether the parser is at the end of the data, or at an
+    /// end-of-copy marker, which in CSV mode must appear on a line of its own.
+    fn is_eof(&self) -> bool {
+        self.peek().is_none() || self.is_end_of_copy_marker()
+    }
+
+    fn is_end_of_copy_marker(&self) -> bool {
+        (self.position == 0 || self.data[self.position - 1] == b'\n')
+            && self.peek() == Some(b'\\')
+            && self.peek_n(1) == Some(b'.')
+            && match self.peek_n(2) {
+                None | Some(b'\n') => true,
+                Some(b'\r') => matches!(self.peek_n(3), None | Some(b'\n')),
+                _ => false,
+            }
+    }
+
+    /// Verifies there is no extra data after the end-of-copy marker.
+    fn expect_no_junk_data(&mut self) -> Result<(), io::Error> {
+        if self.is_end_of_copy_marker() {
+            self.consume_n(2);
+            if self.peek() == Some(b'\r') {
+                self.consume_n(1);
+            }
+            if self.peek() == Some(b'\n') {
+                self.consume_n(1);
+            }
+            if self.peek().is_some() {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    "end-of-copy marker corrupt",
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    fn is_end_of_line(&self) -> bool {
+        match self.peek() {
+            Some(b'\n') | None => true,
+            Some(b'\r') => matches!(self.peek_n(1), Some(b'\n') | None),
+            _ => false,
+        }
+    }
+
+    fn expect_end_of_line(&mut self) -> Result<(), io::Error> {
+        if self.is_end_of_line() {
+            if self.peek() == Some(b'\r') {
+                self.consume_n(1);
+            }
+            self.consume_n(1);
+            Ok(())
+        } else {
+            Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "extra data after last expected column",
+            ))
+        }
+    }
+
+    fn expect_column_delimiter(&mut self) -> Result<(), io::Error> {
+        if self.peek() == Some(self.column_delimiter) {
+            self.consume_n(1);
+            Ok(())
+        } else {
+            Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "missing data for column",
+            ))
+        }
+    }
+
+    /// Skips the remainder of the current line, including any quoted values
+    /// that span multiple lines.
+    fn skip_line(&mut self) -> Result<(), io::Error> {
+        loop {
+            self.consume_raw_value()?;
+            if self.is_end_of_line() {
+                return self.expect_end_of_line();
+            }
+            self.expect_column_delimiter()?;
+        }
+    }
+
+    /// Consumes a single value, which may be partially or entirely quoted.
+    ///
+    /// As in PostgreSQL, a value is only considered to be NULL if it matches
+    /// the NULL string and contains no quotes, so that `""` can be used to
+    /// distinguish an empty string from NULL under the default NULL string.
+    fn consume_raw_value(&mut self) -> Result<Option<&[u8]>, io::Error> {
+        let start = self.position;
+        let mut saw_quote = false;
+
+        // buffer where unquoted data is accumulated
+        self.buffer.clear();
+
+        while !self.is_end_of_line() && self.peek() != Some(self.column_delimiter) {
+            match self.peek() {
+                Some(c) if c == self.quote => {
+                    saw_quote = true;
+                    self.consume_n(1);
+                    loop {
+                        match self.peek() {
+                            None => {
+                                return Err(io::Error::new(
+                                    io::ErrorKind::InvalidData,
+                                    "unterminated CSV quoted field",
+                                ))
+                            }
+                            Some(c)
+                                if c == self.escape
+                                    && (self.peek_n(1) == Some(self.quote)
+                                        || self.peek_n(1) == Some(self.escape)) =>
+                            {
+                                let escaped = self.peek_n(1).unwrap();
+                                self.buffer.push(escaped);
+                                self.consume_n(2);
+                            }
+                            Some(c) if c == self.quote => {
+                                self.consume_n(1);
+                                break;
+                            }
+                            Some(c) => {
+                                self.buffer.push(c);
+                                self.consume_n(1);
+                            }
+                        }
+                    }
+                }
+                Some(c) => {
+                    self.buffer.push(c);
+                    self.consume_n(1);
+                }
+                None => unreachable!("end of data is an end of line"),
+            }
+        }
+
+        if !saw_quote && &self.data[start..self.position] == self.null_string.as_bytes() {
+            Ok(None)
+        } else {
+            Ok(Some(&self.buffer[..]))
+        }
+    }
+}
+
+pub fn decode_copy_csv_format(
+    data: &[u8],
+    column_types: &[pgrepr::Type],
+    delimiter: &Option<String>,
+    quote: &Option<String>,
+    escape: &Option<String>,
+    null: &Option<String>,
+    header: bool,
+) -> Result<Vec<Row>, io::Error> {
+    // The planner ensures that the delimiter, quote, and escape are each a
+    // single byte.
+    let single_byte = |s: &Option<String>, default: u8| match s {
+        Some(s) => s.as_bytes()[0],
+        None => default,
+    };
+    let delimiter = single_byte(delimiter, b',');
+    let quote = single_byte(quote, b'"');
+    let escape = match escape {
+        Some(escape) => escape.as_bytes()[0],
+        None => quote,
+    };
+    let null = null.as_deref().unwrap_or("");
+
+    let mut rows = Vec::new();
+    let mut parser = CopyCsvFormatParser::new(data, delimiter, quote, escape, null);
+    if header && !parser.is_eof() {
+        parser.skip_line()?;
+    }
+    while !parser.is_eof() {
+        let mut row = Vec::new();
+        let buf = RowArena::new();
+        for (col, typ) in column_types.iter().enumerate() {
+            if col > 0 {
+                parser.expect_column_delimiter()?;
+            }
+            let raw_value = parser.consume_raw_value()?;
+            if let Some(raw_value) = raw_value {
+                match pgrepr::Value::decode_text(&typ, raw_value) {
+                    Ok(value) => row.push(value.into_datum(&buf, &typ).0),
+                    Err(err) => {
+                        let msg = format!("unable to decode column: {}", err);
+                        return Err(io::Error::new(io::ErrorKind::InvalidData, msg));
+                    }
+                }
+            } else {
+                row.push(Datum::Null);
+            }
+        }
+        parser.expect_end_of_line()?;
+        rows.push(Row::pack(row));
+    }
+    parser.expect_no_junk_data()?;
+    Ok(rows)
+}
+
+pub fn decode_copy_binary_format(
+    data: &[u8],
+    column_types: &[pgrepr::Type],
+) -> Result<Vec<Row>, io::Error> {
+    const SIGNATURE: &[u8] = b"PGCOPY\n\xFF\r\n\0";
+
+    fn invalid(msg: &str) -> io::Error {
+        io::Error::new(io::ErrorKind::InvalidData, msg.to_owned())
+    }
+
+    /// Splits the next `n` bytes off of the front of `data`.
+    fn take<'a>(data: &mut &'a [u8], n: usize) -> Result<&'a [u8], io::Error> {
+        if data.len() < n {
+            return Err(invalid("unexpected EOF in COPY data"));
+        }
+        let (head, tail) = data.split_at(n);
+        *data = tail;
+        Ok(head)
+    }
+
+    let mut data = data;
+
+    if take(&mut data, SIGNATURE.len())? != SIGNATURE {
+        return Err(invalid("COPY file signature not recognized"));
+    }
+    let flags = i32::from_be_bytes(take(&mut data, 4)?.try_into().unwrap());
+    // Bit 16 indicates that OIDs are included in the data. The low-order 16
+    // bits are reserved for critical flags, which we must reject if set.
+    if flags & (1 << 16) != 0 {
+        return Err(invalid("COPY data with OIDs is not supported"));
+    }
+    if flags & 0xffff != 0 {
+        return Err(invalid("unrecognized critical flags in COPY file header"));
+    }
+    let extension_len = i32::from_be_bytes(take(&mut data, 4)?.try_into().unwrap());
+    let extension_len =
+        usize::try_from(extension_len).map_err(|_| invalid("invalid COPY file header"))?;
+    take(&mut data, extension_len)?;
+
+    let mut rows = Vec::new();
+    loop {
+        let field_count = i16::from_be_bytes(take(&mut data, 2)?.try_into().unwrap());
+        if field_count == -1 {
+            break;
+        }
+        if usize::try_from(field_count).ok() != Some(column_types.len()) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!(
+                    "row field count is {}, expected {}",
+                    field_count,
+                    column_types.len()
+                ),
+            ));
+        }
+        let mut row = Vec::new();
+        let buf = RowArena::new();
+        for typ in column_types {
+            let len = i32::from_be_bytes(take(&mut data, 4)?.try_into().unwrap());
+            if len == -1 {
+                row.push(Datum::Null);
+                continue;
+            }
+            let len = usize::try_from(len).map_err(|_| invalid("invalid field size"))?;
+            match pgrepr::Value::decode_binary(&typ, take(&mut data, len)?) {
+                Ok(value) => row.push(value.into_datum(&buf, &typ).0),
+                Err(err) => {
+                    let msg = format!("unable to decode column: {}", err);
+                    return Err(io::Error::new(io::ErrorKind::InvalidData, msg));
+                }
+            }
+        }
+        rows.push(Row::pack(row));
+    }
+    if !data.is_empty() {
+        return Err(invalid("received copy data after EOF marker"));
+    }
+    Ok(rows)
+}
+
 pub fn encode_row_description(
     desc: &RelationDesc,
     formats: &[pgrepr::Format],
@@ -1014,4 +1361,136 @@ mod tests {
             assert!(parser.is_eof());
         }
     }
+
+    #[test]
+    fn test_copy_format_csv_parser() {
+        struct TestCase {
+            input: &'static str,
+            escape: u8,
+            null: &'static str,
+            expect: &'static [Option<&'static [u8]>],
+        }
+        let tests = vec![
+            TestCase {
+                input: "a,b,c",
+                escape: b'"',
+                null: "",
+                expect: &[
+                    Some(b"a" as &[u8]),
+                    Some(b"b" as &[u8]),
+                    Some(b"c" as &[u8]),
+                ],
+            },
+            TestCase {
+                input: "\"a,b\",\"\",,\"x\"\"y\"",
+                escape: b'"',
+                null: "",
+                expect: &[
+                    Some(b"a,b" as &[u8]),
+                    Some(b"" as &[u8]),
+                    None,
+                    Some(b"x\"y" as &[u8]),
+                ],
+            },
+            TestCase {
+                input: "\"multi\nline\",pre\"fix\"ed",
+                escape: b'"',
+                null: "",
+                expect: &[Some(b"multi\nline" as &[u8]), Some(b"prefixed" as &[u8])],
+            },
+            TestCase {
+                input: "\"a\\\"b\\\\c\\d\",\\N,\"\\N\"",
+                escape: b'\\',
+                null: "\\N",
+                expect: &[Some(b"a\"b\\c\\d" as &[u8]), None, Some(b"\\N" as &[u8])],
+            },
+        ];
+
+        for test in tests {
+            let mut parser =
+                CopyCsvFormatParser::new(test.input.as_bytes(), b',', b'"', test.escape, test.null);
+            for (i, expect) in test.expect.iter().enumerate() {
+                if i > 0 {
+                    parser
+                        .expect_column_delimiter()
+                        .expect("expected column delimiter");
+                }
+                assert_eq!(
+                    parser.consume_raw_value().expect("unexpected error"),
+                    *expect,
+                    "input: {}",
+                    test.input
+                );
+            }
+            assert!(parser.is_eof(), "input: {}", test.input);
+        }
+    }
+
+    #[test]
+    fn test_copy_format_csv_decode() {
+        let text = "id,name\r\n1,\"one\"\r\n2,\r\n\\.\r\n";
+        let rows = decode_copy_csv_format(
+            text.as_bytes(),
+            &[pgrepr::Type::Int4, pgrepr::Type::Text],
+            &None,
+            &None,
+            &None,
+            &None,
+            true,
+        )
+        .expect("unexpected error");
+        assert_eq!(
+            rows,
+            vec![
+                Row::pack_slice(&[Datum::Int32(1), Datum::String("one")]),
+                Row::pack_slice(&[Datum::Int32(2), Datum::Null]),
+            ]
+        );
+
+        let err = decode_copy_csv_format(
+            b"\"unterminated",
+            &[pgrepr::Type::Text],
+            &None,
+            &None,
+            &None,
+            &None,
+            false,
+        )
+        .unwrap_err();
+        assert_eq!(err.to_string(), "unterminated CSV quoted field");
+    }
+
+    #[test]
+    fn test_copy_format_binary_decode() {
+        let mut data = b"PGCOPY\n\xFF\r\n\0".to_vec();
+        data.extend(&0i32.to_be_bytes());
+        data.extend(&0i32.to_be_bytes());
+        // (1, 'one')
+        data.extend(&2i16.to_be_bytes());
+        data.extend(&4i32.to_be_bytes());
+        data.extend(&1i32.to_be_bytes());
+        data.extend(&3i32.to_be_bytes());
+        data.extend(b"one");
+        // (2, NULL)
+        data.extend(&2i16.to_be_bytes());
+        data.extend(&4i32.to_be_bytes());
+        data.extend(&2i32.to_be_bytes());
+        data.extend(&(-1i32).to_be_bytes());
+        data.extend(&(-1i16).to_be_bytes());
+
+        let column_types = [pgrepr::Type::Int4, pgrepr::Type::Text];
+        let rows = decode_copy_binary_format(&data, &column_types).expect("unexpected error");
+        assert_eq!(
+            rows,
+            vec![
+                Row::pack_slice(&[Datum::Int32(1), Datum::String("one")]),
+                Row::pack_slice(&[Datum::Int32(2), Datum::Null]),
+            ]
+        );
+
+        let err = decode_copy_binary_format(&data[..data.len() - 2], &column_types).unwrap_err();
+        assert_eq!(err.to_string(), "unexpected EOF in COPY data");
+        let err = decode_copy_binary_format(b"PGCOPY", &column_types).unwrap_err();
+        assert_eq!(err.to_string(), "unexpected EOF in COPY data");
+    }
 }
@@ -19,7 +19,7 @@ use expr::GlobalId;
 use futures::future::{BoxFuture, FutureExt};
 use itertools::izip;
 use log::debug;
-use message::decode_copy_text_format;
+use message::{decode_copy_binary_format, decode_copy_csv_format, decode_copy_text_format};
 use openssl::nid::Nid;
 use postgres::error::SqlState;
 use tokio::io::{self, AsyncRead, AsyncWrite, Interest};
@@ -35,17 +35,19 @@ use dataflow_types::PeekResponse;
 use ore::cast::CastFrom;
 use ore::netio::AsyncReady;
 use ore::str::StrExt;
+use repr::adt::enums::EnumLabels;
 use repr::{Datum, RelationDesc, RelationType, Row, RowArena};
 use sql::ast::display::AstDisplay;
 use sql::ast::{FetchDirection, Ident, Raw, Statement};
 use sql::plan::{CopyFormat, CopyParams, ExecuteTimeout, StatementDesc};
 
+use crate::auth;
 use crate::codec::FramedConn;
 use crate::message::{
     self, BackendMessage, ErrorResponse, FrontendMessage, Severity, VERSIONS, VERSION_3,
 };
 use crate::metrics::Metrics;
-use crate::server::{Conn, TlsMode};
+use crate::server::{Conn, PasswordAuth, TlsMode};
 
 /// Reports whether the given stream begins with a pgwire handshake.
 ///
@@ -71,6 +73,8 @@ pub fn match_handshake(buf: &[u8]) -> bool {
 pub struct RunParams<'a, A> {
     /// The TLS mode of the pgwire server.
     pub tls_mode: Option<TlsMode>,
+    /// The password authentication method of the pgwire server.
+    pub password_auth: Option<PasswordAuth>,
     /// A client for the coordinator.
     pub coord_client: coord::ConnClient,
     /// The connection to the client.
@@ -95,6 +99,7 @@ pub struct RunParams<'a, A> {
 pub async fn run<'a, A>(
     RunParams {
         tls_mode,
+        password_auth,
         coord_client,
         conn,
         version,
@@ -175,6 +180,29 @@ where
     // From this point forward we must not fail without calling `coord_client.terminate`!
 
     let res = async {
+        // Roles with a password must always authenticate. Roles without a
+        // password need only authenticate if the server demands it, in which
+        // case they are guaranteed to fail.
+        let method = match (password_auth, &startup.password) {
+            (Some(method), _) => Some(method),
+            (None, Some(_)) => Some(PasswordAuth::ScramSha256),
+            (None, None) => None,
+        };
+        if let Some(method) = method {
+            let user = coord_client.session().user().to_owned();
+            let authenticated = auth::authenticate(
+                conn,
+                method,
+                &user,
+                startup.password.as_ref(),
+                startup.mock_auth_secret.as_bytes(),
+            )
+            .await?;
+            if !authenticated {
+                return Ok(());
+            }
+        }
+
         let session = coord_client.session();
         let mut buf = vec![BackendMessage::AuthenticationOk];
         for var in session.vars().notify_set() {
@@ -300,7 +328,8 @@ where
 
             Some(FrontendMessage::CopyData(_))
             | Some(FrontendMessage::CopyDone)
-            | Some(FrontendMessage::CopyFail(_)) => State::Drain,
+            | Some(FrontendMessage::CopyFail(_))
+            | Some(FrontendMessage::Password(_)) => State::Drain,
             None => State::Done,
         };
 
@@ -1161,7 +1190,15 @@ where
                             .await;
                     }
                 };
-                self.copy_rows(format, row_desc, rows).await
+                let enum_labels = self
+                    .coord_client
+                    .session()
+                    .get_portal(&portal_name)
+                    .expect("valid portal name for copy rows")
+                    .desc
+                    .enum_labels
+                    .clone();
+                self.copy_rows(format, row_desc, enum_labels, rows).await
             }
             ExecuteResponse::CopyFrom {
                 id,
@@ -1207,6 +1244,14 @@ where
             .expect("valid fetch portal name for send rows")
             .result_formats
             .clone();
+        let enum_labels = self
+            .coord_client
+            .session()
+            .get_portal(&portal_name)
+            .expect("valid portal name for send rows")
+            .desc
+            .enum_labels
+            .clone();
 
         let (mut wait_once, mut deadline) = match timeout {
             ExecuteTimeout::None => (false, None),
@@ -1298,7 +1343,11 @@ where
                     let drain_rows = cmp::min(want_rows, batch_rows.len());
                     self.conn
                         .send_all(batch_rows.drain(..drain_rows).map(|row| {
-                            BackendMessage::DataRow(pgrepr::values_from_row(row, row_desc.typ()))
+                            BackendMessage::DataRow(pgrepr::values_from_row(
+                                row,
+                                row_desc.typ(),
+                                &enum_labels,
+                            ))
                         }))
                         .await?;
                     total_sent_rows += drain_rows;
@@ -1353,10 +1402,11 @@ where
         &mut self,
         format: CopyFormat,
         row_desc: RelationDesc,
+        enum_labels: EnumLabels,
         mut stream: RowBatchStream,
     ) -> Result<State, io::Error> {
         let (encode_fn, encode_format): (
-            fn(Row, &RelationType, &mut Vec<u8>) -> Result<(), std::io::Error>,
+            fn(Row, &RelationType, &EnumLabels, &mut Vec<u8>) -> Result<(), std::io::Error>,
             pgrepr::Format,
         ) = match format {
             CopyFormat::Text => (message::encode_copy_row_text, pgrepr::Format::Text),
@@ -1435,7 +1485,7 @@ where
                     Some(rows) => {
                         count += rows.len();
                         for row in rows {
-                            encode_fn(row, typ, &mut out)?;
+                            encode_fn(row, typ, &enum_labels, &mut out)?;
                             self.conn
                                 .send(BackendMessage::CopyData(mem::take(&mut out)))
                                 .await?;
@@ -1473,16 +1523,8 @@ where
         row_desc: RelationDesc,
     ) -> Result<State, io::Error> {
         let encode_format: pgrepr::Format = match params.format {
-            CopyFormat::Text => pgrepr::Format::Text,
-            // CopyFormat::Binary => pgrepr::Format::Binary,
-            _ => {
-                return self
-                    .error(ErrorResponse::error(
-                        SqlState::FEATURE_NOT_SUPPORTED,
-                        format!("COPY FROM format {:?} not supported", params.format),
-                    ))
-                    .await
-            }
+            CopyFormat::Text | CopyFormat::Csv => pgrepr::Format::Text,
+            CopyFormat::Binary => pgrepr::Format::Binary,
         };
 
         let typ = row_desc.typ();
@@ -1536,12 +1578,22 @@ where
             .collect::<Vec<pgrepr::Type>>();
 
         if let State::Ready = next_state {
-            let rows = match decode_copy_text_format(
-                &data,
-                &column_types,
-                &params.delimiter,
-                &params.null,
-            ) {
+            let rows = match params.format {
+                CopyFormat::Text => {
+                    decode_copy_text_format(&data, &column_types, &params.delimiter, &params.null)
+                }
+                CopyFormat::Csv => decode_copy_csv_format(
+                    &data,
+                    &column_types,
+                    &params.delimiter,
+                    &params.quote,
+                    &params.escape,
+                    &params.null,
+                    params.header,
+                ),
+                CopyFormat::Binary => decode_copy_binary_format(&data, &column_types),
+            };
+            let rows = match rows {
                 Ok(rows) => rows,
                 Err(e) => {
                     return self
@@ -37,6 +37,14 @@ pub struct Config<'a> {
     /// If not present, then TLS is not enabled, and clients requests to
     /// negotiate TLS will be rejected.
     pub tls: Option<TlsConfig>,
+    /// The password authentication method that all clients must use.
+    ///
+    /// If not present, clients that connect as a role with a password must
+    /// authenticate via SCRAM-SHA-256, while clients that connect as a role
+    /// without a password are not asked for one. If present, all clients must
+    /// authenticate using the specified method, and roles without a password
+    /// cannot log in.
+    pub password_auth: Option<PasswordAuth>,
 
     /// The registry that the pg wire server uses to report metrics.
     pub metrics_registry: &'a MetricsRegistry,
@@ -61,9 +69,21 @@ pub enum TlsMode {
     VerifyUser,
 }
 
+/// Specifies how clients authenticate with a password.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PasswordAuth {
+    /// Clients send their password in cleartext. This method should only be
+    /// used when TLS encryption is required.
+    Cleartext,
+    /// Clients authenticate via the SCRAM-SHA-256 SASL mechanism, which does
+    /// not send the password over the wire.
+    ScramSha256,
+}
+
 /// A server that communicates with clients via the pgwire protocol.
 pub struct Server {
     tls: Option<TlsConfig>,
+    password_auth: Option<PasswordAuth>,
     coord_client: coord::Client,
     metrics: Metrics,
 }
@@ -74,6 +94,7 @@ impl Server {
         Server {
             metrics: Metrics::register_into(config.metrics_registry),
             tls: config.tls,
+            password_auth: config.password_auth,
             coord_client: config.coord_client,
         }
     }
@@ -106,6 +127,7 @@ impl Server {
                     let mut conn = FramedConn::new(conn_id, conn);
                     protocol::run(protocol::RunParams {
                         tls_mode: self.tls.as_ref().map(|tls| tls.mode),
+                        password_auth: self.password_auth,
                         coord_client,
                         conn: &mut conn,
                         version,
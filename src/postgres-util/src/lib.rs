@@ -81,6 +81,10 @@ impl_display!(PgScalarType);
 pub struct PgColumn {
     pub name: Ident,
     pub scalar_type: PgScalarType,
+    /// The OID of the column's type
+    pub type_oid: u32,
+    /// The type modifier of the column, e.g. the precision of a `numeric`
+    pub type_mod: i32,
     pub nullable: bool,
     pub primary_key: bool,
 }
@@ -112,6 +116,9 @@ pub struct TableInfo {
     pub name: String,
     /// The schema of each column, in order
     pub schema: Vec<PgColumn>,
+    /// Whether the table uses `REPLICA IDENTITY FULL`, i.e. whether updates and deletes replicate
+    /// the full previous version of each row, including unchanged TOASTed values
+    pub replica_identity_full: bool,
 }
 
 /// Creates a TLS connector for the given [`Config`].
@@ -188,7 +195,7 @@ pub async fn publication_info(
     let tables = client
         .query(
             "SELECT
-                c.oid, p.schemaname, p.tablename
+                c.oid, p.schemaname, p.tablename, c.relreplident = 'f' AS replica_identity_full
             FROM
                 pg_catalog.pg_class AS c
                 JOIN pg_namespace AS n ON c.relnamespace = n.oid
@@ -228,11 +235,11 @@ pub async fn publication_info(
             .map(|row| {
                 let name: String = row.get("name");
                 let oid = row.get("oid");
+                let modifier: i32 = row.get("modifier");
                 let pg_type =
                     PgType::from_oid(oid).ok_or_else(|| anyhow!("unknown type OID: {}", oid))?;
                 let scalar_type = match pg_type {
                     typ @ PgType::NUMERIC | typ @ PgType::NUMERIC_ARRAY => {
-                        let modifier: i32 = row.get("modifier");
                         // https://github.com/postgres/postgres/blob/REL_13_3/src/backend/utils/adt/numeric.c#L978-L983
                         let tmp_mod = modifier - 4;
                         let scale = (tmp_mod & 0xffff) as u16;
@@ -248,7 +255,6 @@ pub async fn publication_info(
                     | typ @ PgType::BPCHAR_ARRAY
                     | typ @ PgType::VARCHAR
                     | typ @ PgType::VARCHAR_ARRAY => {
-                        let modifier: i32 = row.get("modifier");
                         // BPCHAR: https://github.com/postgres/postgres/blob/272d82ec6febb97ab25fd7c67e9c84f4660b16ac/src/backend/utils/adt/varchar.c#L282-L286
                         // VARCHAR https://github.com/postgres/postgres/blob/272d82ec6febb97ab25fd7c67e9c84f4660b16ac/src/backend/utils/adt/varchar.c#L617
                         let length = if modifier < 4 { i32::MAX } else { modifier - 4 };
@@ -268,6 +274,8 @@ pub async fn publication_info(
                 Ok(PgColumn {
                     name: Ident::new(name),
                     scalar_type,
+                    type_oid: oid,
+                    type_mod: modifier,
                     nullable: !not_null,
                     primary_key,
                 })
@@ -279,6 +287,7 @@ pub async fn publication_info(
             namespace: row.get("schemaname"),
             name: row.get("tablename"),
             schema,
+            replica_identity_full: row.get("replica_identity_full"),
         });
     }
 
@@ -20,9 +20,11 @@
 pub mod array;
 pub mod char;
 pub mod datetime;
+pub mod enums;
 pub mod interval;
 pub mod jsonb;
 pub mod numeric;
+pub mod range;
 pub mod regex;
 mod util;
 pub mod varchar;
@@ -0,0 +1,51 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! User-defined enum types.
+
+use std::collections::BTreeMap;
+use std::convert::TryFrom;
+
+use serde::{Deserialize, Serialize};
+
+/// The labels of user-defined enum types, keyed by the OID of their type.
+///
+/// Values of a [`ScalarType::Enum`](crate::ScalarType::Enum) only store the
+/// position of their label, so whatever outputs them must be handed the labels
+/// of their type, as looked up in the catalog.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Ord, PartialOrd, Hash, Serialize, Deserialize)]
+pub struct EnumLabels(BTreeMap<u32, Vec<String>>);
+
+impl EnumLabels {
+    /// Records the labels of the enum type with OID `oid`, in declaration
+    /// order.
+    pub fn insert(&mut self, oid: u32, labels: Vec<String>) {
+        self.0.insert(oid, labels);
+    }
+
+    /// Reports whether the labels of any enum type are recorded.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Returns the label at `position` of the enum type with OID `oid`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the labels of the type are not recorded, or the type does not
+    /// have a label at `position`.
+    #[track_caller]
+    pub fn label(&self, oid: u32, position: i32) -> &str {
+        let labels = self
+            .0
+            .get(&oid)
+            .unwrap_or_else(|| panic!("labels of enum type {} not recorded", oid));
+        &labels[usize::try_from(position).expect("enum positions are never negative")]
+    }
+}
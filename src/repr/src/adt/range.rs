@@ -0,0 +1,284 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! A range data type.
+//!
+//! The semantics of ranges, including their canonicalization and the
+//! comparison of their bounds, mirror PostgreSQL's [range types].
+//!
+//! [range types]: https://www.postgresql.org/docs/current/rangetypes.html
+
+use std::cmp::Ordering;
+use std::error::Error;
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::row::DatumList;
+use crate::Datum;
+
+// The flags that describe a range. Their values are those used in
+// PostgreSQL's binary encoding of ranges.
+
+/// The flag set on empty ranges.
+pub const RANGE_EMPTY: u8 = 0x01;
+/// The flag set when a range's lower bound is inclusive.
+pub const RANGE_LB_INC: u8 = 0x02;
+/// The flag set when a range's upper bound is inclusive.
+pub const RANGE_UB_INC: u8 = 0x04;
+/// The flag set when a range's lower bound is infinite.
+pub const RANGE_LB_INF: u8 = 0x08;
+/// The flag set when a range's upper bound is infinite.
+pub const RANGE_UB_INF: u8 = 0x10;
+
+/// A contiguous range of values of some element type.
+///
+/// Ranges are always stored in their canonical form. In particular, ranges
+/// over discrete element types always have an inclusive lower bound and an
+/// exclusive upper bound, and ranges that contain no values are always stored
+/// as the empty range.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub struct Range<'a> {
+    /// The flags describing the range, whose values match the flags byte of
+    /// PostgreSQL's binary encoding of ranges.
+    pub(crate) flags: u8,
+    /// The finite bounds of the range, lower bound first.
+    pub(crate) bounds: DatumList<'a>,
+}
+
+impl<'a> Range<'a> {
+    /// Reports whether the range contains no values.
+    pub fn is_empty(&self) -> bool {
+        self.flags & RANGE_EMPTY != 0
+    }
+
+    /// Returns the lower bound of the range, or `None` if the range is empty.
+    pub fn lower(&self) -> Option<RangeBound<Datum<'a>>> {
+        if self.is_empty() {
+            return None;
+        }
+        Some(RangeBound {
+            value: if self.flags & RANGE_LB_INF != 0 {
+                None
+            } else {
+                self.bounds.iter().next()
+            },
+            inclusive: self.flags & RANGE_LB_INC != 0,
+        })
+    }
+
+    /// Returns the upper bound of the range, or `None` if the range is empty.
+    pub fn upper(&self) -> Option<RangeBound<Datum<'a>>> {
+        if self.is_empty() {
+            return None;
+        }
+        Some(RangeBound {
+            value: if self.flags & RANGE_UB_INF != 0 {
+                None
+            } else {
+                self.bounds.iter().last()
+            },
+            inclusive: self.flags & RANGE_UB_INC != 0,
+        })
+    }
+
+    /// Returns the lower and upper bounds of the range, or `None` if the range
+    /// is empty.
+    pub fn bounds(&self) -> Option<(RangeBound<Datum<'a>>, RangeBound<Datum<'a>>)> {
+        Some((self.lower()?, self.upper()?))
+    }
+
+    /// Reports whether the range contains the specified element.
+    pub fn contains_elem(&self, elem: Datum) -> bool {
+        let (lower, upper) = match self.bounds() {
+            None => return false,
+            Some(bounds) => bounds,
+        };
+        if let Some(value) = lower.value {
+            match value.cmp(&elem) {
+                Ordering::Greater => return false,
+                Ordering::Equal if !lower.inclusive => return false,
+                _ => (),
+            }
+        }
+        if let Some(value) = upper.value {
+            match value.cmp(&elem) {
+                Ordering::Less => return false,
+                Ordering::Equal if !upper.inclusive => return false,
+                _ => (),
+            }
+        }
+        true
+    }
+
+    /// Reports whether every value in `other` is also contained in the range.
+    pub fn contains_range(&self, other: &Range) -> bool {
+        let (other_lower, other_upper) = match other.bounds() {
+            None => return true,
+            Some(bounds) => bounds,
+        };
+        let (lower, upper) = match self.bounds() {
+            None => return false,
+            Some(bounds) => bounds,
+        };
+        cmp_bounds(&lower, true, &other_lower, true) != Ordering::Greater
+            && cmp_bounds(&upper, false, &other_upper, false) != Ordering::Less
+    }
+
+    /// Reports whether the range has any values in common with `other`.
+    pub fn overlaps(&self, other: &Range) -> bool {
+        let ((l1, u1), (l2, u2)) = match (self.bounds(), other.bounds()) {
+            (Some(a), Some(b)) => (a, b),
+            _ => return false,
+        };
+        (cmp_bounds(&l1, true, &l2, true) != Ordering::Less
+            && cmp_bounds(&l1, true, &u2, false) != Ordering::Greater)
+            || (cmp_bounds(&l2, true, &l1, true) != Ordering::Less
+                && cmp_bounds(&l2, true, &u1, false) != Ordering::Greater)
+    }
+}
+
+/// Orders ranges as PostgreSQL does: the empty range sorts before all other
+/// ranges, and non-empty ranges are compared first by their lower bounds and
+/// then by their upper bounds.
+impl Ord for Range<'_> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self.bounds(), other.bounds()) {
+            (None, None) => Ordering::Equal,
+            (None, Some(_)) => Ordering::Less,
+            (Some(_), None) => Ordering::Greater,
+            (Some((l1, u1)), Some((l2, u2))) => {
+                cmp_bounds(&l1, true, &l2, true).then_with(|| cmp_bounds(&u1, false, &u2, false))
+            }
+        }
+    }
+}
+
+impl PartialOrd for Range<'_> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// One bound of a [`Range`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub struct RangeBound<D> {
+    /// The value of the bound, or `None` if the bound is infinite.
+    pub value: Option<D>,
+    /// Whether the bound's value is itself contained in the range.
+    pub inclusive: bool,
+}
+
+impl<D> RangeBound<D> {
+    /// Converts from `&RangeBound<D>` to `RangeBound<&D>`.
+    pub fn as_ref(&self) -> RangeBound<&D> {
+        RangeBound {
+            value: self.value.as_ref(),
+            inclusive: self.inclusive,
+        }
+    }
+
+    /// Maps the value of the bound, if the bound is finite, using `f`.
+    pub fn map<E>(self, f: impl FnOnce(D) -> E) -> RangeBound<E> {
+        RangeBound {
+            value: self.value.map(f),
+            inclusive: self.inclusive,
+        }
+    }
+}
+
+/// Compares two range bounds, each of which may be either a lower bound or an
+/// upper bound, as specified by `a_lower` and `b_lower`.
+///
+/// This is PostgreSQL's `range_cmp_bounds`. Infinite lower bounds sort before
+/// all other bounds, and infinite upper bounds sort after all other bounds.
+/// When two finite bounds have the same value, an exclusive lower bound sorts
+/// after, and an exclusive upper bound sorts before, an inclusive bound.
+fn cmp_bounds(
+    a: &RangeBound<Datum>,
+    a_lower: bool,
+    b: &RangeBound<Datum>,
+    b_lower: bool,
+) -> Ordering {
+    let (a_value, b_value) = match (a.value, b.value) {
+        (None, None) => {
+            return match (a_lower, b_lower) {
+                (true, false) => Ordering::Less,
+                (false, true) => Ordering::Greater,
+                _ => Ordering::Equal,
+            }
+        }
+        (None, Some(_)) if a_lower => return Ordering::Less,
+        (None, Some(_)) => return Ordering::Greater,
+        (Some(_), None) if b_lower => return Ordering::Greater,
+        (Some(_), None) => return Ordering::Less,
+        (Some(a_value), Some(b_value)) => (a_value, b_value),
+    };
+    match a_value.cmp(&b_value) {
+        Ordering::Equal => match (a.inclusive, b.inclusive) {
+            (false, false) if a_lower == b_lower => Ordering::Equal,
+            (false, _) if a_lower => Ordering::Greater,
+            (false, _) => Ordering::Less,
+            (true, false) if b_lower => Ordering::Less,
+            (true, false) => Ordering::Greater,
+            (true, true) => Ordering::Equal,
+        },
+        ordering => ordering,
+    }
+}
+
+/// Returns the discrete successor of `d`, for element types whose ranges are
+/// canonicalized to the form `[lower, upper)`.
+///
+/// Returns `Ok(None)` if `d`'s type is not discrete.
+pub(crate) fn discrete_successor<'a>(d: Datum<'a>) -> Result<Option<Datum<'a>>, InvalidRangeError> {
+    let overflow = |ty: &str| InvalidRangeError::CanonicalizationOverflow(ty.into());
+    match d {
+        Datum::Int32(i) => match i.checked_add(1) {
+            Some(i) => Ok(Some(Datum::Int32(i))),
+            None => Err(overflow("integer")),
+        },
+        Datum::Int64(i) => match i.checked_add(1) {
+            Some(i) => Ok(Some(Datum::Int64(i))),
+            None => Err(overflow("bigint")),
+        },
+        Datum::Date(d) => match d.succ_opt() {
+            Some(d) => Ok(Some(Datum::Date(d))),
+            None => Err(overflow("date")),
+        },
+        _ => Ok(None),
+    }
+}
+
+/// An error that can occur when constructing a range.
+#[derive(Clone, Debug, Eq, PartialEq, Hash, Ord, PartialOrd, Serialize, Deserialize)]
+pub enum InvalidRangeError {
+    /// The range's lower bound is greater than its upper bound.
+    MisorderedRangeBounds,
+    /// Canonicalizing a bound of the range overflowed the element type, which
+    /// is named by the contained string.
+    CanonicalizationOverflow(String),
+}
+
+impl fmt::Display for InvalidRangeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            InvalidRangeError::MisorderedRangeBounds => {
+                f.write_str("range lower bound must be less than or equal to range upper bound")
+            }
+            InvalidRangeError::CanonicalizationOverflow(ty) => write!(f, "{} out of range", ty),
+        }
+    }
+}
+
+impl Error for InvalidRangeError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        None
+    }
+}
@@ -26,6 +26,10 @@ use crate::adt::array::{
 use crate::adt::interval::Interval;
 use crate::adt::numeric;
 use crate::adt::numeric::Numeric;
+use crate::adt::range::{
+    self, InvalidRangeError, Range, RangeBound, RANGE_EMPTY, RANGE_LB_INC, RANGE_LB_INF,
+    RANGE_UB_INC, RANGE_UB_INF,
+};
 use crate::Datum;
 use fmt::Debug;
 
@@ -228,6 +232,7 @@ enum Tag {
     JsonNull,
     Dummy,
     Numeric,
+    Range,
 }
 
 // --------------------------------------------------------------------------------
@@ -398,6 +403,16 @@ unsafe fn read_datum<'a>(data: &'a [u8], offset: &mut usize) -> Datum<'a> {
             let d = Numeric::from_raw_parts(digits, exponent, bits, lsu_u8);
             Datum::from(d)
         }
+        Tag::Range => {
+            // See the comment in `Row::push_range` for details on the encoding
+            // of ranges.
+            let flags = read_copy::<u8>(data, offset);
+            let bounds = read_untagged_bytes(data, offset);
+            Datum::Range(Range {
+                flags,
+                bounds: DatumList { data: bounds },
+            })
+        }
     }
 }
 
@@ -575,6 +590,13 @@ fn push_datum<T: Bytes>(data: &mut T, datum: Datum) {
             data.push(bits);
             data.extend_from_slice(lsu);
         }
+        Datum::Range(range) => {
+            // See the comment in `Row::push_range` for details on the encoding
+            // of ranges.
+            data.push(Tag::Range as u8);
+            data.push(range.flags);
+            push_untagged_bytes(data, &range.bounds.data);
+        }
     }
 }
 
@@ -653,6 +675,7 @@ pub fn datum_size(datum: &Datum) -> usize {
             // 4 = 1 bit each for tag, digits, exponent, bits
             4 + (d.coefficient_units().len() * 2)
         }
+        Datum::Range(range) => 1 + size_of::<u8>() + size_of::<usize>() + range.bounds.data.len(),
     }
 }
 
@@ -978,6 +1001,101 @@ impl Row {
         Ok(())
     }
 
+    /// Pushes a range with the specified bounds.
+    ///
+    /// The range is canonicalized before it is pushed: ranges over discrete
+    /// element types are converted to the form `[lower, upper)`, and ranges
+    /// that contain no values are pushed as the empty range.
+    ///
+    /// Returns an error if `lower` is greater than `upper`, or if
+    /// canonicalizing a bound overflows the element type. If an error occurs,
+    /// the packer's state will be unchanged.
+    pub fn push_range<'a>(
+        &mut self,
+        mut lower: RangeBound<Datum<'a>>,
+        mut upper: RangeBound<Datum<'a>>,
+    ) -> Result<(), InvalidRangeError> {
+        // Ranges are encoded as follows.
+        //
+        // u8      flags, as in PostgreSQL's binary encoding of ranges
+        // usize   bound data size in bytes
+        // u8      bound data, where the finite bounds are encoded in order
+        //
+        // Empty ranges have no bound data.
+
+        fn is_empty(lower: &RangeBound<Datum>, upper: &RangeBound<Datum>) -> bool {
+            match (lower.value, upper.value) {
+                (Some(l), Some(u)) => match l.cmp(&u) {
+                    Ordering::Less => false,
+                    Ordering::Equal => !(lower.inclusive && upper.inclusive),
+                    Ordering::Greater => true,
+                },
+                _ => false,
+            }
+        }
+
+        if let (Some(l), Some(u)) = (lower.value, upper.value) {
+            if l > u {
+                return Err(InvalidRangeError::MisorderedRangeBounds);
+            }
+        }
+        if is_empty(&lower, &upper) {
+            self.push_empty_range();
+            return Ok(());
+        }
+
+        // Canonicalize discrete ranges to the form `[lower, upper)`.
+        if let Some(l) = lower.value {
+            if !lower.inclusive {
+                if let Some(succ) = range::discrete_successor(l)? {
+                    lower.value = Some(succ);
+                    lower.inclusive = true;
+                }
+            }
+        }
+        if let Some(u) = upper.value {
+            if upper.inclusive {
+                if let Some(succ) = range::discrete_successor(u)? {
+                    upper.value = Some(succ);
+                    upper.inclusive = false;
+                }
+            }
+        }
+        if is_empty(&lower, &upper) {
+            self.push_empty_range();
+            return Ok(());
+        }
+
+        let mut flags = 0;
+        match lower.value {
+            None => flags |= RANGE_LB_INF,
+            Some(_) if lower.inclusive => flags |= RANGE_LB_INC,
+            Some(_) => (),
+        }
+        match upper.value {
+            None => flags |= RANGE_UB_INF,
+            Some(_) if upper.inclusive => flags |= RANGE_UB_INC,
+            Some(_) => (),
+        }
+        self.data.push(Tag::Range as u8);
+        self.data.push(flags);
+        let off = self.data.len();
+        push_copy!(&mut self.data, 0, usize); // dummy length fixed up below
+        for value in lower.value.into_iter().chain(upper.value) {
+            self.push(value);
+        }
+        let len = self.data.len() - off - size_of::<usize>();
+        self.data[off..off + size_of::<usize>()].copy_from_slice(&len.to_le_bytes());
+        Ok(())
+    }
+
+    /// Pushes the empty range.
+    pub fn push_empty_range(&mut self) {
+        self.data.push(Tag::Range as u8);
+        self.data.push(RANGE_EMPTY);
+        push_copy!(&mut self.data, 0, usize);
+    }
+
     /// Convenience function to push a `DatumList` from an iter of `Datum`s
     ///
     /// See [`Row::push_dict_with`] if you need to be able to handle errors
@@ -1528,6 +1646,65 @@ mod tests {
         assert!(row.data.is_empty());
     }
 
+    #[test]
+    fn test_range() {
+        fn bound(value: Option<i32>, inclusive: bool) -> RangeBound<Datum<'static>> {
+            RangeBound {
+                value: value.map(Datum::Int32),
+                inclusive,
+            }
+        }
+
+        // Discrete ranges are canonicalized to the form `[lower, upper)`.
+        let mut row = Row::default();
+        row.push_range(bound(Some(1), false), bound(Some(5), true))
+            .unwrap();
+        let range = row.unpack_first().unwrap_range();
+        assert_eq!(range.lower(), Some(bound(Some(2), true)));
+        assert_eq!(range.upper(), Some(bound(Some(6), false)));
+        assert!(range.contains_elem(Datum::Int32(2)));
+        assert!(!range.contains_elem(Datum::Int32(6)));
+
+        // Infinite bounds are never inclusive.
+        let mut row = Row::default();
+        row.push_range(bound(None, true), bound(Some(5), false))
+            .unwrap();
+        let unbounded = row.unpack_first().unwrap_range();
+        assert_eq!(unbounded.lower(), Some(bound(None, false)));
+        assert!(!unbounded.contains_range(&range));
+        assert!(unbounded.overlaps(&range));
+
+        // Ranges that contain no values are empty.
+        let mut row = Row::default();
+        row.push_range(bound(Some(1), false), bound(Some(2), false))
+            .unwrap();
+        let empty = row.unpack_first().unwrap_range();
+        assert!(empty.is_empty());
+        assert!(range.contains_range(&empty));
+        assert!(empty < unbounded);
+
+        // Misordered bounds are rejected.
+        let mut row = Row::default();
+        let res = row.push_range(bound(Some(2), true), bound(Some(1), true));
+        assert_eq!(res, Err(InvalidRangeError::MisorderedRangeBounds));
+        assert!(row.data.is_empty());
+
+        // Canonicalization that overflows is rejected.
+        let res = row.push_range(bound(Some(1), true), bound(Some(i32::MAX), true));
+        assert_eq!(
+            res,
+            Err(InvalidRangeError::CanonicalizationOverflow(
+                "integer".into()
+            ))
+        );
+        assert!(row.data.is_empty());
+
+        // Pack a previously-constructed `Datum::Range` and verify that it
+        // unpacks correctly.
+        let row = Row::pack_slice(&[Datum::Range(range)]);
+        assert_eq!(row.unpack_first().unwrap_range(), range);
+    }
+
     #[test]
     fn test_nesting() {
         let mut row = Row::default();
@@ -25,6 +25,7 @@ use lowertest::MzEnumReflect;
 use crate::adt::array::Array;
 use crate::adt::interval::Interval;
 use crate::adt::numeric::Numeric;
+use crate::adt::range::Range;
 use crate::{ColumnName, ColumnType, DatumList, DatumMap};
 use crate::{Row, RowArena};
 
@@ -81,6 +82,8 @@ pub enum Datum<'a> {
     JsonNull,
     /// A universally unique identifier.
     Uuid(Uuid),
+    /// A contiguous range of values.
+    Range(Range<'a>),
     /// A placeholder value.
     ///
     /// Dummy values are never meant to be observed. Many operations on `Datum`
@@ -451,6 +454,19 @@ impl<'a> Datum<'a> {
         }
     }
 
+    /// Unwraps the range value within this datum.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the datum is not [`Datum::Range`].
+    #[track_caller]
+    pub fn unwrap_range(&self) -> Range<'a> {
+        match self {
+            Datum::Range(range) => *range,
+            _ => panic!("Datum::unwrap_range called on {:?}", self),
+        }
+    }
+
     /// Unwraps the list value within this datum.
     ///
     /// # Panics
@@ -524,6 +540,7 @@ impl<'a> Datum<'a> {
                     (Datum::Int32(_), ScalarType::Int32) => true,
                     (Datum::Int32(_), ScalarType::Oid) => true,
                     (Datum::Int32(_), ScalarType::RegProc) => true,
+                    (Datum::Int32(_), ScalarType::Enum { .. }) => true,
                     (Datum::Int32(_), _) => false,
                     (Datum::Int64(_), ScalarType::Int64) => true,
                     (Datum::Int64(_), _) => false,
@@ -573,6 +590,12 @@ impl<'a> Datum<'a> {
                     (Datum::JsonNull, _) => false,
                     (Datum::Numeric(_), ScalarType::Numeric { .. }) => true,
                     (Datum::Numeric(_), _) => false,
+                    (Datum::Range(range), ScalarType::Range { element_type }) => range
+                        .bounds()
+                        .into_iter()
+                        .flat_map(|(lower, upper)| lower.value.into_iter().chain(upper.value))
+                        .all(|e| is_instance_of_scalar(e, element_type)),
+                    (Datum::Range(_), _) => false,
                 }
             }
         }
@@ -846,6 +869,20 @@ impl fmt::Display for Datum<'_> {
                 f.write_str("}")
             }
             Datum::Numeric(n) => write!(f, "{}", n.0.to_standard_notation_string()),
+            Datum::Range(range) => match range.bounds() {
+                None => f.write_str("empty"),
+                Some((lower, upper)) => {
+                    f.write_str(if lower.inclusive { "[" } else { "(" })?;
+                    if let Some(value) = lower.value {
+                        write!(f, "{}", value)?;
+                    }
+                    f.write_str(", ")?;
+                    if let Some(value) = upper.value {
+                        write!(f, "{}", value)?;
+                    }
+                    f.write_str(if upper.inclusive { "]" } else { ")" })
+                }
+            },
             Datum::JsonNull => f.write_str("json_null"),
             Datum::Dummy => f.write_str("dummy"),
         }
@@ -966,6 +1003,20 @@ pub enum ScalarType {
     },
     /// A PostgreSQL function name.
     RegProc,
+    /// A user-defined enumerated type, whose labels are stored in the catalog.
+    ///
+    /// Values are stored as a [`Datum::Int32`] holding the position of their
+    /// label, so that they sort in the order in which the labels were
+    /// declared. Outputting them requires their labels, which are looked up
+    /// in the catalog into an [`EnumLabels`](crate::adt::enums::EnumLabels).
+    Enum { custom_oid: u32 },
+    /// The type of [`Datum::Range`].
+    ///
+    /// The bounds of the range are of the specified type, which must be one of
+    /// [`ScalarType::Int32`], [`ScalarType::Int64`], [`ScalarType::Numeric`],
+    /// [`ScalarType::Date`], [`ScalarType::Timestamp`] or
+    /// [`ScalarType::TimestampTz`].
+    Range { element_type: Box<ScalarType> },
 }
 
 /// [FromTy] is a utility trait for [ScalarType] that defines a mapping between a Rust type T and
@@ -1130,6 +1181,18 @@ impl<'a> ScalarType {
         }
     }
 
+    /// Returns the [`ScalarType`] of the bounds of a [`ScalarType::Range`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if called on anything other than a [`ScalarType::Range`].
+    pub fn unwrap_range_element_type(&self) -> &ScalarType {
+        match self {
+            ScalarType::Range { element_type } => &**element_type,
+            _ => panic!("ScalarType::unwrap_range_element_type called on {:?}", self),
+        }
+    }
+
     /// Returns the length of a [`ScalarType::Char`] or [`ScalarType::VarChar`].
     ///
     /// # Panics
@@ -1172,6 +1235,8 @@ impl<'a> ScalarType {
                 value_type: t,
                 custom_oid,
             } => custom_oid.is_some() || t.is_custom_type(),
+            Record { custom_oid, .. } => custom_oid.is_some(),
+            Enum { .. } => true,
             _ => false,
         }
     }
@@ -1225,6 +1290,8 @@ impl<'a> ScalarType {
                     custom_name: name_b,
                 },
             ) => fields_a.eq(fields_b) && oid_a == oid_b && name_a == name_b,
+            (Enum { custom_oid: oid_a }, Enum { custom_oid: oid_b }) => oid_a == oid_b,
+            (Range { element_type: a }, Range { element_type: b }) => a.base_eq(b),
             (s, o) => ScalarBaseType::from(s) == ScalarBaseType::from(o),
         }
     }
@@ -51,6 +51,7 @@ use crate::adt::datetime::{self, DateTimeField, ParsedDateTime};
 use crate::adt::interval::Interval;
 use crate::adt::jsonb::{Jsonb, JsonbRef};
 use crate::adt::numeric::{self, Numeric, NUMERIC_DATUM_MAX_PRECISION};
+use crate::adt::range::RangeBound;
 
 macro_rules! bail {
     ($($arg:tt)*) => { return Err(format!($($arg)*)) };
@@ -634,34 +635,142 @@ pub fn parse_array<'a, T, E>(
 where
     E: fmt::Display,
 {
-    parse_array_inner(s, make_null, gen_elem)
-        .map_err(|details| ParseError::invalid_input_syntax("array", s).with_details(details))
+    let (dims, elems) = parse_array_with_dims(s, make_null, gen_elem)?;
+    if dims.len() > 1 {
+        return Err(ParseError::invalid_input_syntax("array", s)
+            .with_details("parsing multi-dimensional arrays is not supported"));
+    }
+    if dims.iter().any(|dim| dim.lower_bound != 1) {
+        return Err(ParseError::invalid_input_syntax("array", s)
+            .with_details("specifying array lower bounds is not supported"));
+    }
+    Ok(elems)
 }
 
-fn parse_array_inner<'a, T, E>(
+/// Parses an array literal of any dimensionality, optionally preceded by
+/// explicit dimension bounds (e.g., `[0:1][1:2]={{1,2},{3,4}}`), as produced
+/// by PostgreSQL's `array_out`.
+///
+/// Returns the dimensions of the array along with its elements in row-major
+/// order. Empty arrays have no dimensions.
+pub fn parse_array_with_dims<'a, T, E>(
     s: &'a str,
     mut make_null: impl FnMut() -> T,
     mut gen_elem: impl FnMut(Cow<'a, str>) -> Result<T, E>,
-) -> Result<Vec<T>, String>
+) -> Result<(Vec<ArrayDimension>, Vec<T>), ParseError>
 where
     E: fmt::Display,
 {
+    let mut gen = |elem| gen_elem(elem).map_err_to_string();
+    parse_array_inner(s, &mut make_null, &mut gen)
+        .map_err(|details| ParseError::invalid_input_syntax("array", s).with_details(details))
+}
+
+fn parse_array_inner<'a, T>(
+    s: &'a str,
+    make_null: &mut impl FnMut() -> T,
+    gen: &mut impl FnMut(Cow<'a, str>) -> Result<T, String>,
+) -> Result<(Vec<ArrayDimension>, Vec<T>), String> {
+    let mut state = ArrayParseState {
+        lengths: vec![],
+        scalar_depth: None,
+    };
     let mut elems = vec![];
     let buf = &mut LexBuf::new(s);
 
+    let mut bounds = vec![];
+    while buf.consume('[') {
+        let lower = buf.take_while(|ch| ch.is_ascii_digit());
+        if !buf.consume(':') {
+            bail!("malformed array literal: invalid array dimensions");
+        }
+        let upper = buf.take_while(|ch| ch.is_ascii_digit());
+        if !buf.consume(']') {
+            bail!("malformed array literal: missing \"]\" in array dimensions");
+        }
+        match (lower.parse::<usize>(), upper.parse::<usize>()) {
+            (Ok(lower), Ok(upper)) if lower <= upper => bounds.push((lower, upper)),
+            _ => bail!("malformed array literal: invalid array dimensions"),
+        }
+    }
+    if !bounds.is_empty() && !buf.consume('=') {
+        bail!("malformed array literal: missing assignment operator");
+    }
+
     if !buf.consume('{') {
         bail!("malformed array literal: missing opening left brace");
     }
+    parse_array_dim(buf, 0, &mut state, &mut elems, make_null, gen)?;
+
+    if buf.next().is_some() {
+        bail!("malformed array literal: junk after closing right brace");
+    }
+
+    // Empty arrays have no dimensions.
+    if elems.is_empty() {
+        if !bounds.is_empty() {
+            bail!(
+                "malformed array literal: specified array dimensions do not match array contents"
+            );
+        }
+        return Ok((vec![], elems));
+    }
+    let lengths: Vec<usize> = state
+        .lengths
+        .into_iter()
+        .map(|length| length.expect("every dimension is closed"))
+        .collect();
+    if bounds.is_empty() {
+        bounds = lengths.iter().map(|length| (1, *length)).collect();
+    } else if bounds.len() != lengths.len()
+        || bounds
+            .iter()
+            .zip(&lengths)
+            .any(|((lower, upper), length)| upper - lower + 1 != *length)
+    {
+        bail!("malformed array literal: specified array dimensions do not match array contents");
+    }
+    let dims = bounds
+        .into_iter()
+        .map(|(lower, upper)| ArrayDimension {
+            lower_bound: lower,
+            length: upper - lower + 1,
+        })
+        .collect();
+    Ok((dims, elems))
+}
+
+/// Tracks the shape of a multi-dimensional array literal while it is parsed.
+struct ArrayParseState {
+    /// The length of each dimension, once the first sub-array at that depth
+    /// has been closed.
+    lengths: Vec<Option<usize>>,
+    /// The depth at which scalar elements appear, once one has been seen.
+    scalar_depth: Option<usize>,
+}
+
+/// Parses the items of the (sub-)array at `depth`, whose opening brace has
+/// already been consumed, through its closing brace.
+fn parse_array_dim<'a, T>(
+    buf: &mut LexBuf<'a>,
+    depth: usize,
+    state: &mut ArrayParseState,
+    elems: &mut Vec<T>,
+    make_null: &mut impl FnMut() -> T,
+    gen: &mut impl FnMut(Cow<'a, str>) -> Result<T, String>,
+) -> Result<(), String> {
+    const MISMATCHED_DIMS: &str =
+        "multidimensional arrays must have sub-arrays with matching dimensions";
 
-    let mut gen = |elem| gen_elem(elem).map_err_to_string();
     let is_special_char = |c| matches!(c, '{' | '}' | ',' | '\\' | '"');
     let is_end_of_literal = |c| matches!(c, ',' | '}');
 
+    let mut len = 0;
     loop {
         buf.take_while(|ch| ch.is_ascii_whitespace());
         match buf.next() {
             Some('}') => break,
-            _ if elems.len() == 0 => {
+            _ if len == 0 => {
                 buf.prev();
             }
             Some(',') => {}
@@ -670,23 +779,48 @@ where
         }
         buf.take_while(|ch| ch.is_ascii_whitespace());
 
-        let elem = match buf.peek() {
-            Some('"') => gen(lex_quoted_element(buf)?)?,
-            Some('{') => bail!("parsing multi-dimensional arrays is not supported"),
-            Some(_) => match lex_unquoted_element(buf, is_special_char, is_end_of_literal)? {
-                Some(elem) => gen(elem)?,
-                None => make_null(),
-            },
+        match buf.peek() {
+            Some('{') => {
+                if state.scalar_depth == Some(depth) {
+                    bail!("{}", MISMATCHED_DIMS);
+                }
+                buf.next();
+                parse_array_dim(buf, depth + 1, state, elems, make_null, gen)?;
+            }
+            Some(c) => {
+                match state.scalar_depth {
+                    None => state.scalar_depth = Some(depth),
+                    Some(d) if d != depth => bail!("{}", MISMATCHED_DIMS),
+                    Some(_) => (),
+                }
+                let elem = if c == '"' {
+                    gen(lex_quoted_element(buf)?)?
+                } else {
+                    match lex_unquoted_element(buf, is_special_char, is_end_of_literal)? {
+                        Some(elem) => gen(elem)?,
+                        None => make_null(),
+                    }
+                };
+                elems.push(elem);
+            }
             None => bail!("unexpected end of input"),
-        };
-        elems.push(elem);
+        }
+        len += 1;
     }
 
-    if buf.next().is_some() {
-        bail!("malformed array literal: junk after closing right brace");
+    // Only the outermost array may be empty.
+    if len == 0 && depth > 0 {
+        bail!("{}", MISMATCHED_DIMS);
     }
-
-    Ok(elems)
+    if state.lengths.len() <= depth {
+        state.lengths.resize(depth + 1, None);
+    }
+    match state.lengths[depth] {
+        None => state.lengths[depth] = Some(len),
+        Some(l) if l != len => bail!("{}", MISMATCHED_DIMS),
+        Some(_) => (),
+    }
+    Ok(())
 }
 
 pub fn parse_list<'a, T, E>(
@@ -898,6 +1032,108 @@ fn lex_unquoted_element<'a>(
     })
 }
 
+/// Parses a range from `s`, returning `None` if `s` describes the empty range.
+///
+/// Each finite bound of the range is converted using `gen_elem`.
+pub fn parse_range<'a, T, E>(
+    s: &'a str,
+    gen_elem: impl FnMut(Cow<'a, str>) -> Result<T, E>,
+) -> Result<Option<(RangeBound<T>, RangeBound<T>)>, ParseError>
+where
+    E: fmt::Display,
+{
+    parse_range_inner(s, gen_elem)
+        .map_err(|details| ParseError::invalid_input_syntax("range", s).with_details(details))
+}
+
+fn parse_range_inner<'a, T, E>(
+    s: &'a str,
+    mut gen_elem: impl FnMut(Cow<'a, str>) -> Result<T, E>,
+) -> Result<Option<(RangeBound<T>, RangeBound<T>)>, String>
+where
+    E: fmt::Display,
+{
+    if s.trim().eq_ignore_ascii_case("empty") {
+        return Ok(None);
+    }
+
+    let buf = &mut LexBuf::new(s);
+    let mut gen = |elem| gen_elem(elem).map_err_to_string();
+
+    buf.take_while(|ch| ch.is_ascii_whitespace());
+    let lower_inclusive = match buf.next() {
+        Some('[') => true,
+        Some('(') => false,
+        _ => bail!("missing left parenthesis or bracket"),
+    };
+    let lower = match lex_range_element(buf)? {
+        Some(elem) => Some(gen(elem)?),
+        None => None,
+    };
+    if !buf.consume(',') {
+        bail!("missing comma after lower bound");
+    }
+    let upper = match lex_range_element(buf)? {
+        Some(elem) => Some(gen(elem)?),
+        None => None,
+    };
+    let upper_inclusive = match buf.next() {
+        Some(']') => true,
+        Some(')') => false,
+        Some(',') => bail!("too many commas"),
+        _ => bail!("missing right parenthesis or bracket"),
+    };
+
+    buf.take_while(|ch| ch.is_ascii_whitespace());
+    if buf.next().is_some() {
+        bail!("junk after right parenthesis or bracket");
+    }
+
+    Ok(Some((
+        RangeBound {
+            value: lower,
+            inclusive: lower_inclusive,
+        },
+        RangeBound {
+            value: upper,
+            inclusive: upper_inclusive,
+        },
+    )))
+}
+
+// Result of `None` indicates the bound is infinite.
+//
+// Unlike list and array elements, range bounds may mix quoted and unquoted
+// sections, and whitespace within them is significant.
+fn lex_range_element<'a>(buf: &mut LexBuf<'a>) -> Result<Option<Cow<'a, str>>, String> {
+    if let Some(',') | Some(')') | Some(']') = buf.peek() {
+        return Ok(None);
+    }
+
+    let mut s = String::new();
+    let mut in_quotes = false;
+    loop {
+        match buf.next() {
+            Some('\\') => match buf.next() {
+                Some(c) => s.push(c),
+                None => bail!("unexpected end of input"),
+            },
+            Some('"') if in_quotes && buf.peek() == Some('"') => {
+                buf.next();
+                s.push('"');
+            }
+            Some('"') => in_quotes = !in_quotes,
+            Some(c) if !in_quotes && matches!(c, ',' | ')' | ']') => {
+                buf.prev();
+                break;
+            }
+            Some(c) => s.push(c),
+            None => bail!("unexpected end of input"),
+        }
+    }
+    Ok(Some(Cow::Owned(s)))
+}
+
 pub fn parse_map<'a, V, E>(
     s: &'a str,
     is_value_type_map: bool,
@@ -1152,7 +1388,27 @@ impl ElementEscaper for RecordElementEscaper {
     }
 }
 
-/// Escapes a list, record, or map element in place.
+struct RangeElementEscaper;
+
+impl ElementEscaper for RangeElementEscaper {
+    fn needs_escaping(elem: &[u8]) -> bool {
+        elem.is_empty()
+            || elem.iter().any(|c| {
+                matches!(c, b'(' | b')' | b'[' | b']' | b',' | b'"' | b'\\')
+                    || c.is_ascii_whitespace()
+            })
+    }
+
+    fn escape_char(c: u8) -> u8 {
+        if c == b'"' {
+            b'"'
+        } else {
+            b'\\'
+        }
+    }
+}
+
+/// Escapes a list, record, range, or map element in place.
 ///
 /// The element must start at `start` and extend to the end of the buffer. The
 /// buffer will be resized if escaping is necessary to account for the
@@ -1295,6 +1551,42 @@ where
     }
 }
 
+/// Formats a range, where `None` indicates the empty range.
+///
+/// Each finite bound of the range is formatted using `format_elem`.
+pub fn format_range<F, T>(
+    buf: &mut F,
+    range: Option<(RangeBound<T>, RangeBound<T>)>,
+    mut format_elem: impl FnMut(&mut F, T) -> Nestable,
+) -> Nestable
+where
+    F: FormatBuffer,
+{
+    let (lower, upper) = match range {
+        None => {
+            buf.write_str("empty");
+            return Nestable::Yes;
+        }
+        Some(bounds) => bounds,
+    };
+    buf.write_char(if lower.inclusive { '[' } else { '(' });
+    if let Some(value) = lower.value {
+        let start = buf.len();
+        if let Nestable::MayNeedEscaping = format_elem(buf, value) {
+            escape_elem::<_, RangeElementEscaper>(buf, start);
+        }
+    }
+    buf.write_char(',');
+    if let Some(value) = upper.value {
+        let start = buf.len();
+        if let Nestable::MayNeedEscaping = format_elem(buf, value) {
+            escape_elem::<_, RangeElementEscaper>(buf, start);
+        }
+    }
+    buf.write_char(if upper.inclusive { ']' } else { ')' });
+    Nestable::MayNeedEscaping
+}
+
 /// An error while parsing an input as a type.
 #[derive(Ord, PartialOrd, Clone, Debug, Eq, PartialEq, Serialize, Deserialize, Hash)]
 pub struct ParseError {
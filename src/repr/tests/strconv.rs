@@ -9,8 +9,10 @@
 
 use chrono::{DateTime, FixedOffset, NaiveDate, NaiveDateTime, NaiveTime, TimeZone, Utc};
 
+use repr::adt::array::ArrayDimension;
 use repr::adt::datetime::DateTimeField;
 use repr::adt::interval::Interval;
+use repr::adt::range::RangeBound;
 use repr::strconv;
 
 #[test]
@@ -451,3 +453,157 @@ fn miri_test_format_list() {
         r#"{a,"a\"b","",NULL,"NULL",nUlL,"  spaces ","a,b","\\","a\\b\"c\\d\""}"#
     );
 }
+
+#[test]
+fn test_parse_range() {
+    fn parse(s: &str) -> Result<Option<(RangeBound<String>, RangeBound<String>)>, String> {
+        strconv::parse_range(s, |elem| Ok::<_, String>(elem.into_owned()))
+            .map_err(|e| e.to_string())
+    }
+
+    fn bound(value: Option<&str>, inclusive: bool) -> RangeBound<String> {
+        RangeBound {
+            value: value.map(|v| v.to_owned()),
+            inclusive,
+        }
+    }
+
+    assert_eq!(parse("  EMPTY "), Ok(None));
+    assert_eq!(
+        parse("[1,5)"),
+        Ok(Some((bound(Some("1"), true), bound(Some("5"), false))))
+    );
+    assert_eq!(
+        parse("(,\"a\"\"b\\\\c\"]"),
+        Ok(Some((bound(None, false), bound(Some("a\"b\\c"), true))))
+    );
+    assert_eq!(
+        parse("( 1 ,)"),
+        Ok(Some((bound(Some(" 1 "), false), bound(None, false))))
+    );
+    assert_eq!(
+        parse("1,5)"),
+        Err(
+            "invalid input syntax for type range: missing left parenthesis or bracket: \"1,5)\""
+                .into()
+        )
+    );
+    assert_eq!(
+        parse("[1,5,7)"),
+        Err("invalid input syntax for type range: too many commas: \"[1,5,7)\"".into())
+    );
+    assert_eq!(
+        parse("[1,5) x"),
+        Err("invalid input syntax for type range: junk after right parenthesis or bracket: \"[1,5) x\"".into())
+    );
+}
+
+#[test]
+fn test_format_range() {
+    fn format(range: Option<(RangeBound<&str>, RangeBound<&str>)>) -> String {
+        let mut out = String::new();
+        strconv::format_range(&mut out, range, |buf, el| strconv::format_string(buf, el));
+        out
+    }
+
+    assert_eq!(format(None), "empty");
+    assert_eq!(
+        format(Some((
+            RangeBound {
+                value: Some("a"),
+                inclusive: true,
+            },
+            RangeBound {
+                value: None,
+                inclusive: false,
+            },
+        ))),
+        "[a,)"
+    );
+    assert_eq!(
+        format(Some((
+            RangeBound {
+                value: Some("a b"),
+                inclusive: false,
+            },
+            RangeBound {
+                value: Some("c\"d"),
+                inclusive: true,
+            },
+        ))),
+        r#"("a b","c""d"]"#
+    );
+}
+
+#[test]
+fn test_parse_array_with_dims() {
+    fn dims(dims: &[(usize, usize)]) -> Vec<ArrayDimension> {
+        dims.iter()
+            .map(|(lower_bound, length)| ArrayDimension {
+                lower_bound: *lower_bound,
+                length: *length,
+            })
+            .collect()
+    }
+
+    fn parse(s: &str) -> Result<(Vec<ArrayDimension>, Vec<Option<String>>), String> {
+        strconv::parse_array_with_dims(s, || None, |elem| Ok::<_, String>(Some(elem.into_owned())))
+            .map_err(|e| e.to_string())
+    }
+
+    fn elems(elems: &[Option<&str>]) -> Vec<Option<String>> {
+        elems.iter().map(|e| e.map(|e| e.to_owned())).collect()
+    }
+
+    assert_eq!(parse("{}").unwrap(), (vec![], vec![]));
+    assert_eq!(
+        parse("{a, NULL}").unwrap(),
+        (dims(&[(1, 2)]), elems(&[Some("a"), None]))
+    );
+    assert_eq!(
+        parse(r#"{{a,b,c},{"d}",e,f}}"#).unwrap(),
+        (
+            dims(&[(1, 2), (1, 3)]),
+            elems(&[
+                Some("a"),
+                Some("b"),
+                Some("c"),
+                Some("d}"),
+                Some("e"),
+                Some("f")
+            ])
+        )
+    );
+    assert_eq!(
+        parse("{{{a}},{{b}}}").unwrap(),
+        (
+            dims(&[(1, 2), (1, 1), (1, 1)]),
+            elems(&[Some("a"), Some("b")])
+        )
+    );
+    assert_eq!(
+        parse("[0:1][2:2]={{a},{b}}").unwrap(),
+        (dims(&[(0, 2), (2, 1)]), elems(&[Some("a"), Some("b")]))
+    );
+
+    for s in &["{{a},{b,c}}", "{{a},b}", "{a,{b}}", "{{a},{{b}}}", "{{}}"] {
+        assert_eq!(
+            parse(s).unwrap_err(),
+            format!(
+                "invalid input syntax for type array: multidimensional arrays must have \
+                 sub-arrays with matching dimensions: \"{}\"",
+                s
+            )
+        );
+    }
+    for s in &["[1:3]={a,b}", "[1:1][1:1]={a}", "[1:0]={}"] {
+        assert!(parse(s).is_err(), "{} should not parse", s);
+    }
+
+    // `parse_array` only accepts one-dimensional arrays with default bounds.
+    let parse_1d =
+        |s: &'static str| strconv::parse_array(s, || None, |elem| Ok::<_, String>(Some(elem)));
+    assert!(parse_1d("{a,b}").is_ok());
+    assert!(parse_1d("{{a},{b}}").is_err());
+    assert!(parse_1d("[0:1]={a,b}").is_err());
+}
@@ -92,7 +92,7 @@ impl_display_t!(AvroSchema);
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum ProtobufSchema<T: AstInfo> {
     Csr {
-        csr_connector: CsrConnector<T>,
+        csr_connector: CsrConnectorProto<T>,
     },
     InlineSchema {
         message_name: String,
@@ -166,6 +166,88 @@ impl AstDisplay for CsrSeed {
 }
 impl_display!(CsrSeed);
 
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct CsrConnectorProto<T: AstInfo> {
+    pub url: String,
+    pub seed: Option<CsrSeedCompiledOrLegacy>,
+    pub with_options: Vec<SqlOption<T>>,
+}
+
+impl<T: AstInfo> AstDisplay for CsrConnectorProto<T> {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        f.write_str("USING CONFLUENT SCHEMA REGISTRY '");
+        f.write_node(&display::escape_single_quote_string(&self.url));
+        f.write_str("'");
+        if let Some(seed) = &self.seed {
+            f.write_str(" ");
+            f.write_node(seed);
+        }
+        if !self.with_options.is_empty() {
+            f.write_str(" WITH (");
+            f.write_node(&display::comma_separated(&self.with_options));
+            f.write_str(")");
+        }
+    }
+}
+impl_display_t!(CsrConnectorProto);
+
+/// The seed of a protobuf source that uses a schema registry.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum CsrSeedCompiledOrLegacy {
+    /// File descriptor sets compiled from the schemas in the registry.
+    Compiled(CsrSeedCompiled),
+    /// The raw text of the schemas in the registry, as recorded by versions
+    /// that did not resolve schema references.
+    Legacy(CsrSeed),
+}
+
+impl AstDisplay for CsrSeedCompiledOrLegacy {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        match self {
+            CsrSeedCompiledOrLegacy::Compiled(c) => f.write_node(c),
+            CsrSeedCompiledOrLegacy::Legacy(l) => f.write_node(l),
+        }
+    }
+}
+impl_display!(CsrSeedCompiledOrLegacy);
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct CsrSeedCompiled {
+    pub key: Option<CsrSeedCompiledEncoding>,
+    pub value: CsrSeedCompiledEncoding,
+}
+
+impl AstDisplay for CsrSeedCompiled {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        f.write_str("SEED COMPILED");
+        if let Some(key) = &self.key {
+            f.write_str(" KEY ");
+            f.write_node(key);
+        }
+        f.write_str(" VALUE ");
+        f.write_node(&self.value);
+    }
+}
+impl_display!(CsrSeedCompiled);
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct CsrSeedCompiledEncoding {
+    /// The encoded `FileDescriptorSet`, as a bytea literal.
+    pub schema: String,
+    pub message_name: String,
+}
+
+impl AstDisplay for CsrSeedCompiledEncoding {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        f.write_str("SCHEMA '");
+        f.write_node(&display::escape_single_quote_string(&self.schema));
+        f.write_str("' MESSAGE '");
+        f.write_node(&display::escape_single_quote_string(&self.message_name));
+        f.write_str("'");
+    }
+}
+impl_display!(CsrSeedCompiledEncoding);
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum CreateSourceFormat<T: AstInfo> {
     None,
@@ -223,13 +305,25 @@ impl_display_t!(CreateSourceFormat);
 pub enum Format<T: AstInfo> {
     Bytes,
     Avro(AvroSchema<T>),
+    /// Avro object container files, which embed their own schema. Only
+    /// supported for file and S3 sinks.
+    AvroOcf,
     Protobuf(ProtobufSchema<T>),
     Regex(String),
     Csv {
         columns: CsvColumns,
         delimiter: char,
     },
-    Json,
+    Json {
+        /// The columns to extract from each JSON object. Empty if each JSON
+        /// value is presented as a single column.
+        columns: Vec<ColumnDef<T>>,
+    },
+    Parquet {
+        /// The columns to decode from each Parquet file. Empty if the columns
+        /// are to be discovered from the file during purification.
+        columns: Vec<ColumnDef<T>>,
+    },
     Text,
 }
 
@@ -297,6 +391,44 @@ impl CreateSourceKeyEnvelope {
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SourceIncludeMetadataType {
+    Partition,
+    Offset,
+    Timestamp,
+    Headers,
+}
+
+impl AstDisplay for SourceIncludeMetadataType {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        match self {
+            SourceIncludeMetadataType::Partition => f.write_str("PARTITION"),
+            SourceIncludeMetadataType::Offset => f.write_str("OFFSET"),
+            SourceIncludeMetadataType::Timestamp => f.write_str("TIMESTAMP"),
+            SourceIncludeMetadataType::Headers => f.write_str("HEADERS"),
+        }
+    }
+}
+impl_display!(SourceIncludeMetadataType);
+
+/// A metadata column requested via `INCLUDE`, e.g. `INCLUDE PARTITION AS p`
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SourceIncludeMetadata {
+    pub ty: SourceIncludeMetadataType,
+    pub alias: Option<Ident>,
+}
+
+impl AstDisplay for SourceIncludeMetadata {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        f.write_node(&self.ty);
+        if let Some(alias) = &self.alias {
+            f.write_str(" AS ");
+            f.write_node(alias);
+        }
+    }
+}
+impl_display!(SourceIncludeMetadata);
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum Envelope {
     None,
@@ -341,6 +473,7 @@ impl<T: AstInfo> AstDisplay for Format<T> {
                 f.write_str("AVRO ");
                 f.write_node(inner);
             }
+            Self::AvroOcf => f.write_str("AVRO OCF"),
             Self::Protobuf(inner) => {
                 f.write_str("PROTOBUF ");
                 f.write_node(inner);
@@ -360,7 +493,22 @@ impl<T: AstInfo> AstDisplay for Format<T> {
                     f.write_str("'");
                 }
             }
-            Self::Json => f.write_str("JSON"),
+            Self::Json { columns } => {
+                f.write_str("JSON");
+                if !columns.is_empty() {
+                    f.write_str(" (");
+                    f.write_node(&display::comma_separated(columns));
+                    f.write_str(")");
+                }
+            }
+            Self::Parquet { columns } => {
+                f.write_str("PARQUET");
+                if !columns.is_empty() {
+                    f.write_str(" (");
+                    f.write_node(&display::comma_separated(columns));
+                    f.write_str(")");
+                }
+            }
             Self::Text => f.write_str("TEXT"),
         }
     }
@@ -370,6 +518,11 @@ impl_display_t!(Format);
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum Compression {
     Gzip,
+    Zstd,
+    Snappy,
+    Lz4,
+    /// Detect the compression of each file or object individually.
+    Auto,
     None,
 }
 
@@ -383,6 +536,10 @@ impl AstDisplay for Compression {
     fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
         match self {
             Self::Gzip => f.write_str("GZIP"),
+            Self::Zstd => f.write_str("ZSTD"),
+            Self::Snappy => f.write_str("SNAPPY"),
+            Self::Lz4 => f.write_str("LZ4"),
+            Self::Auto => f.write_str("AUTO"),
             Self::None => f.write_str("NONE"),
         }
     }
@@ -446,6 +603,8 @@ pub enum CreateSourceConnector {
         publication: String,
         /// The replication slot name that will be created upstream
         slot: Option<String>,
+        /// The schema of the upstream tables, captured during purification
+        details: Option<String>,
     },
     PubNub {
         /// PubNub's subscribe key
@@ -512,6 +671,7 @@ impl AstDisplay for CreateSourceConnector {
                 conn,
                 publication,
                 slot,
+                details,
             } => {
                 f.write_str("POSTGRES CONNECTION '");
                 f.write_str(&display::escape_single_quote_string(conn));
@@ -521,6 +681,10 @@ impl AstDisplay for CreateSourceConnector {
                     f.write_str("' SLOT '");
                     f.write_str(&display::escape_single_quote_string(slot));
                 }
+                if let Some(details) = details {
+                    f.write_str("' DETAILS '");
+                    f.write_str(&display::escape_single_quote_string(details));
+                }
                 f.write_str("'");
             }
             CreateSourceConnector::PubNub {
@@ -543,6 +707,8 @@ impl<T: AstInfo> From<&CreateSinkConnector<T>> for ConnectorType {
         match connector {
             CreateSinkConnector::Kafka { .. } => ConnectorType::Kafka,
             CreateSinkConnector::AvroOcf { .. } => ConnectorType::AvroOcf,
+            CreateSinkConnector::File { .. } => ConnectorType::File,
+            CreateSinkConnector::S3 { .. } => ConnectorType::S3,
         }
     }
 }
@@ -558,6 +724,14 @@ pub enum CreateSinkConnector<T: AstInfo> {
     },
     /// Avro Object Container File
     AvroOcf { path: String },
+    /// Rolling files in a local directory
+    File { path: String },
+    /// Rolling objects in an S3 bucket
+    S3 {
+        bucket: String,
+        /// The prefix of the keys of the objects: `PREFIX 'a/b'`
+        prefix: Option<String>,
+    },
 }
 
 impl<T: AstInfo> AstDisplay for CreateSinkConnector<T> {
@@ -589,6 +763,21 @@ impl<T: AstInfo> AstDisplay for CreateSinkConnector<T> {
                 f.write_node(&display::escape_single_quote_string(path));
                 f.write_str("'");
             }
+            CreateSinkConnector::File { path } => {
+                f.write_str("FILE '");
+                f.write_node(&display::escape_single_quote_string(path));
+                f.write_str("'");
+            }
+            CreateSinkConnector::S3 { bucket, prefix } => {
+                f.write_str("S3 BUCKET '");
+                f.write_node(&display::escape_single_quote_string(bucket));
+                f.write_str("'");
+                if let Some(prefix) = prefix {
+                    f.write_str(" PREFIX '");
+                    f.write_node(&display::escape_single_quote_string(prefix));
+                    f.write_str("'");
+                }
+            }
         }
     }
 }
@@ -90,6 +90,8 @@ impl AstInfo for Raw {
 pub struct Query<T: AstInfo> {
     /// WITH (common table expressions, or CTEs)
     pub ctes: Vec<Cte<T>>,
+    /// Whether the CTEs were introduced by `WITH RECURSIVE`
+    pub recursive: bool,
     /// SELECT or UNION / EXCEPT / INTECEPT
     pub body: SetExpr<T>,
     /// ORDER BY
@@ -105,6 +107,9 @@ impl<T: AstInfo> AstDisplay for Query<T> {
     fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
         if !self.ctes.is_empty() {
             f.write_str("WITH ");
+            if self.recursive {
+                f.write_str("RECURSIVE ");
+            }
             f.write_node(&display::comma_separated(&self.ctes));
             f.write_str(" ");
         }
@@ -143,6 +148,7 @@ impl<T: AstInfo> Query<T> {
     pub fn select(select: Select<T>) -> Query<T> {
         Query {
             ctes: vec![],
+            recursive: false,
             body: SetExpr::Select(Box::new(select)),
             order_by: vec![],
             limit: None,
@@ -155,6 +161,7 @@ impl<T: AstInfo> Query<T> {
             self,
             Query::<T> {
                 ctes: vec![],
+                recursive: false,
                 order_by: vec![],
                 body: SetExpr::Values(Values(vec![])),
                 limit: None,
@@ -24,7 +24,7 @@ use crate::ast::display::{self, AstDisplay, AstFormatter};
 use crate::ast::{
     AstInfo, ColumnDef, CreateSinkConnector, CreateSourceConnector, CreateSourceFormat,
     CreateSourceKeyEnvelope, DataType, Envelope, Expr, Format, Ident, KeyConstraint, Query,
-    TableConstraint, UnresolvedObjectName, Value,
+    SourceIncludeMetadata, TableConstraint, UnresolvedObjectName, Value,
 };
 
 /// A top-level statement (SELECT, INSERT, CREATE, etc.)
@@ -48,6 +48,8 @@ pub enum Statement<T: AstInfo> {
     CreateRole(CreateRoleStatement),
     AlterObjectRename(AlterObjectRenameStatement),
     AlterIndex(AlterIndexStatement),
+    AlterTable(AlterTableStatement<T>),
+    AlterRole(AlterRoleStatement),
     Discard(DiscardStatement),
     DropDatabase(DropDatabaseStatement),
     DropObjects(DropObjectsStatement),
@@ -106,6 +108,8 @@ impl<T: AstInfo> AstDisplay for Statement<T> {
             Statement::CreateType(stmt) => f.write_node(stmt),
             Statement::AlterObjectRename(stmt) => f.write_node(stmt),
             Statement::AlterIndex(stmt) => f.write_node(stmt),
+            Statement::AlterTable(stmt) => f.write_node(stmt),
+            Statement::AlterRole(stmt) => f.write_node(stmt),
             Statement::Discard(stmt) => f.write_node(stmt),
             Statement::DropDatabase(stmt) => f.write_node(stmt),
             Statement::DropObjects(stmt) => f.write_node(stmt),
@@ -164,6 +168,8 @@ pub struct InsertStatement<T: AstInfo> {
     pub columns: Vec<Ident>,
     /// A SQL query that specifies what to insert.
     pub source: InsertSource<T>,
+    /// The action to take when inserted rows conflict with existing rows.
+    pub on_conflict: Option<OnConflict<T>>,
 }
 
 impl<T: AstInfo> AstDisplay for InsertStatement<T> {
@@ -177,10 +183,70 @@ impl<T: AstInfo> AstDisplay for InsertStatement<T> {
         }
         f.write_str(" ");
         f.write_node(&self.source);
+        if let Some(on_conflict) = &self.on_conflict {
+            f.write_str(" ");
+            f.write_node(on_conflict);
+        }
     }
 }
 impl_display_t!(InsertStatement);
 
+/// `ON CONFLICT [(<columns>)] DO <action>`
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct OnConflict<T: AstInfo> {
+    /// The columns whose values identify conflicting rows. Empty if no
+    /// conflict target was specified.
+    pub columns: Vec<Ident>,
+    /// The action to take for conflicting rows.
+    pub action: OnConflictAction<T>,
+}
+
+impl<T: AstInfo> AstDisplay for OnConflict<T> {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        f.write_str("ON CONFLICT ");
+        if !self.columns.is_empty() {
+            f.write_str("(");
+            f.write_node(&display::comma_separated(&self.columns));
+            f.write_str(") ");
+        }
+        f.write_str("DO ");
+        f.write_node(&self.action);
+    }
+}
+impl_display_t!(OnConflict);
+
+/// The action of an `ON CONFLICT` clause.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum OnConflictAction<T: AstInfo> {
+    /// `NOTHING`
+    DoNothing,
+    /// `UPDATE SET <assignments> [WHERE <expr>]`
+    DoUpdate {
+        assignments: Vec<Assignment<T>>,
+        selection: Option<Expr<T>>,
+    },
+}
+
+impl<T: AstInfo> AstDisplay for OnConflictAction<T> {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        match self {
+            OnConflictAction::DoNothing => f.write_str("NOTHING"),
+            OnConflictAction::DoUpdate {
+                assignments,
+                selection,
+            } => {
+                f.write_str("UPDATE SET ");
+                f.write_node(&display::comma_separated(assignments));
+                if let Some(selection) = selection {
+                    f.write_str(" WHERE ");
+                    f.write_node(selection);
+                }
+            }
+        }
+    }
+}
+impl_display_t!(OnConflictAction);
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum CopyRelation<T: AstInfo> {
     Table {
@@ -365,6 +431,7 @@ pub struct CreateSourceStatement<T: AstInfo> {
     pub with_options: Vec<SqlOption<T>>,
     pub format: CreateSourceFormat<T>,
     pub key_envelope: CreateSourceKeyEnvelope,
+    pub include_metadata: Vec<SourceIncludeMetadata>,
     pub envelope: Envelope,
     pub if_not_exists: bool,
     pub materialized: bool,
@@ -405,6 +472,14 @@ impl<T: AstInfo> AstDisplay for CreateSourceStatement<T> {
         }
         f.write_node(&self.format);
         f.write_node(&self.key_envelope);
+        if !self.include_metadata.is_empty() {
+            if self.key_envelope.is_present() {
+                f.write_str(", ");
+            } else {
+                f.write_str(" INCLUDE ");
+            }
+            f.write_node(&display::comma_separated(&self.include_metadata));
+        }
         match self.envelope {
             Envelope::None => (),
             _ => {
@@ -732,7 +807,8 @@ impl AstDisplay for CreateRoleStatement {
 }
 impl_display!(CreateRoleStatement);
 
-/// Options that can be attached to [`CreateRoleStatement`].
+/// Options that can be attached to [`CreateRoleStatement`] and
+/// [`AlterRoleStatement`].
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum CreateRoleOption {
     /// The `SUPERUSER` option.
@@ -743,6 +819,8 @@ pub enum CreateRoleOption {
     Login,
     /// The `NOLOGIN` option.
     NoLogin,
+    /// The `PASSWORD` option. `None` indicates `PASSWORD NULL`.
+    Password(Option<String>),
 }
 
 impl AstDisplay for CreateRoleOption {
@@ -752,6 +830,12 @@ impl AstDisplay for CreateRoleOption {
             CreateRoleOption::NoSuperUser => f.write_str("NOSUPERUSER"),
             CreateRoleOption::Login => f.write_str("LOGIN"),
             CreateRoleOption::NoLogin => f.write_str("NOLOGIN"),
+            CreateRoleOption::Password(None) => f.write_str("PASSWORD NULL"),
+            CreateRoleOption::Password(Some(password)) => {
+                f.write_str("PASSWORD '");
+                f.write_node(&display::escape_single_quote_string(password));
+                f.write_str("'");
+            }
         }
     }
 }
@@ -766,6 +850,9 @@ pub struct CreateTypeStatement<T: AstInfo> {
     pub as_type: CreateTypeAs,
     /// Provides the name and type for the key
     /// and value.
+    ///
+    /// For composite types, provides the name and type of each field, in the
+    /// order in which they were declared.
     pub with_options: Vec<SqlOption<T>>,
 }
 
@@ -774,12 +861,46 @@ impl<T: AstInfo> AstDisplay for CreateTypeStatement<T> {
         f.write_str("CREATE TYPE ");
         f.write_node(&self.name);
         f.write_str(" AS ");
-        f.write_str(&self.as_type);
-        f.write_str("( ");
-        if !self.with_options.is_empty() {
-            f.write_node(&display::comma_separated(&self.with_options));
+        match &self.as_type {
+            CreateTypeAs::List | CreateTypeAs::Map => {
+                f.write_str(&self.as_type);
+                f.write_str("( ");
+                if !self.with_options.is_empty() {
+                    f.write_node(&display::comma_separated(&self.with_options));
+                }
+                f.write_str(" )");
+            }
+            CreateTypeAs::Enum { labels } => {
+                f.write_str(&self.as_type);
+                f.write_str("(");
+                for (i, label) in labels.iter().enumerate() {
+                    if i > 0 {
+                        f.write_str(", ");
+                    }
+                    f.write_str("'");
+                    f.write_node(&display::escape_single_quote_string(label));
+                    f.write_str("'");
+                }
+                f.write_str(")");
+            }
+            CreateTypeAs::Record => {
+                f.write_str("(");
+                for (i, option) in self.with_options.iter().enumerate() {
+                    if i > 0 {
+                        f.write_str(", ");
+                    }
+                    match option {
+                        SqlOption::DataType { name, data_type } => {
+                            f.write_node(name);
+                            f.write_str(" ");
+                            f.write_node(data_type);
+                        }
+                        option => f.write_node(option),
+                    }
+                }
+                f.write_str(")");
+            }
         }
-        f.write_str(" )");
     }
 }
 impl_display_t!(CreateTypeStatement);
@@ -789,6 +910,12 @@ impl_display_t!(CreateTypeStatement);
 pub enum CreateTypeAs {
     List,
     Map,
+    Enum {
+        labels: Vec<String>,
+    },
+    /// A composite type, whose fields are described by the statement's
+    /// `with_options`.
+    Record,
 }
 
 impl AstDisplay for CreateTypeAs {
@@ -796,6 +923,8 @@ impl AstDisplay for CreateTypeAs {
         match self {
             CreateTypeAs::List => f.write_str("LIST "),
             CreateTypeAs::Map => f.write_str("MAP "),
+            CreateTypeAs::Enum { .. } => f.write_str("ENUM "),
+            CreateTypeAs::Record => f.write_str("RECORD "),
         }
     }
 }
@@ -867,6 +996,91 @@ impl AstDisplay for AlterIndexStatement {
 
 impl_display!(AlterIndexStatement);
 
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum AlterTableAction<T: AstInfo> {
+    /// `ADD [COLUMN] [IF NOT EXISTS] <column_def>`
+    AddColumn {
+        if_not_exists: bool,
+        column: ColumnDef<T>,
+    },
+    /// `DROP [COLUMN] [IF EXISTS] <name>`
+    DropColumn { if_exists: bool, name: Ident },
+}
+
+impl<T: AstInfo> AstDisplay for AlterTableAction<T> {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        match self {
+            AlterTableAction::AddColumn {
+                if_not_exists,
+                column,
+            } => {
+                f.write_str("ADD COLUMN ");
+                if *if_not_exists {
+                    f.write_str("IF NOT EXISTS ");
+                }
+                f.write_node(column);
+            }
+            AlterTableAction::DropColumn { if_exists, name } => {
+                f.write_str("DROP COLUMN ");
+                if *if_exists {
+                    f.write_str("IF EXISTS ");
+                }
+                f.write_node(name);
+            }
+        }
+    }
+}
+impl_display_t!(AlterTableAction);
+
+/// `ALTER TABLE ... {ADD, DROP} COLUMN`
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct AlterTableStatement<T: AstInfo> {
+    pub table_name: UnresolvedObjectName,
+    pub if_exists: bool,
+    pub action: AlterTableAction<T>,
+}
+
+impl<T: AstInfo> AstDisplay for AlterTableStatement<T> {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        f.write_str("ALTER TABLE ");
+        if self.if_exists {
+            f.write_str("IF EXISTS ");
+        }
+        f.write_node(&self.table_name);
+        f.write_str(" ");
+        f.write_node(&self.action);
+    }
+}
+impl_display_t!(AlterTableStatement);
+
+/// An `ALTER ROLE` statement.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct AlterRoleStatement {
+    /// Whether this was actually an `ALTER USER` statement.
+    pub is_user: bool,
+    /// The specified role.
+    pub name: Ident,
+    /// Any options that were attached, in the order they were presented.
+    pub options: Vec<CreateRoleOption>,
+}
+
+impl AstDisplay for AlterRoleStatement {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        f.write_str("ALTER ");
+        if self.is_user {
+            f.write_str("USER ");
+        } else {
+            f.write_str("ROLE ");
+        }
+        f.write_node(&self.name);
+        for option in &self.options {
+            f.write_str(" ");
+            option.fmt(f)
+        }
+    }
+}
+impl_display!(AlterRoleStatement);
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct DiscardStatement {
     pub target: DiscardTarget,
@@ -508,7 +508,7 @@ impl<'a> Parser<'a> {
                         let query = SetExpr::Query(Box::new(query));
                         let ctes = vec![];
                         let body = parser.parse_query_body_seeded(SetPrecedence::Zero, query)?;
-                        Ok(Either::Query(parser.parse_query_tail(ctes, body)?))
+                        Ok(Either::Query(parser.parse_query_tail(ctes, false, body)?))
                     }
 
                     // The next token is something else. That implies we have a
@@ -1466,8 +1466,12 @@ impl<'a> Parser<'a> {
 
     fn parse_format(&mut self) -> Result<Format<Raw>, ParserError> {
         let format = if self.parse_keyword(AVRO) {
-            self.expect_keyword(USING)?;
-            Format::Avro(self.parse_avro_schema()?)
+            if self.parse_keyword(OCF) {
+                Format::AvroOcf
+            } else {
+                self.expect_keyword(USING)?;
+                Format::Avro(self.parse_avro_schema()?)
+            }
         } else if self.parse_keyword(PROTOBUF) {
             Format::Protobuf(self.parse_protobuf_schema()?)
         } else if self.parse_keyword(REGEX) {
@@ -1495,7 +1499,25 @@ impl<'a> Parser<'a> {
             };
             Format::Csv { columns, delimiter }
         } else if self.parse_keyword(JSON) {
-            Format::Json
+            let (columns, constraints) = self.parse_columns(Optional)?;
+            if !constraints.is_empty() {
+                return parser_err!(
+                    self,
+                    self.peek_prev_pos(),
+                    "table constraints are not allowed in FORMAT JSON"
+                );
+            }
+            Format::Json { columns }
+        } else if self.parse_keyword(PARQUET) {
+            let (columns, constraints) = self.parse_columns(Optional)?;
+            if !constraints.is_empty() {
+                return parser_err!(
+                    self,
+                    self.peek_prev_pos(),
+                    "table constraints are not allowed in FORMAT PARQUET"
+                );
+            }
+            Format::Parquet { columns }
         } else if self.parse_keyword(TEXT) {
             Format::Text
         } else if self.parse_keyword(BYTES) {
@@ -1503,7 +1525,7 @@ impl<'a> Parser<'a> {
         } else {
             return self.expected(
                 self.peek_pos(),
-                "AVRO, PROTOBUF, REGEX, CSV, JSON, TEXT, or BYTES",
+                "AVRO, PROTOBUF, REGEX, CSV, JSON, PARQUET, TEXT, or BYTES",
                 self.peek_token(),
             );
         };
@@ -1541,7 +1563,7 @@ impl<'a> Parser<'a> {
 
     fn parse_protobuf_schema(&mut self) -> Result<ProtobufSchema<Raw>, ParserError> {
         if self.parse_keywords(&[USING, CONFLUENT, SCHEMA, REGISTRY]) {
-            let csr_connector = self.parse_csr_connector()?;
+            let csr_connector = self.parse_csr_connector_proto()?;
             Ok(ProtobufSchema::Csr { csr_connector })
         } else if self.parse_keyword(MESSAGE) {
             let message_name = self.parse_literal_string()?;
@@ -1564,18 +1586,7 @@ impl<'a> Parser<'a> {
         let url = self.parse_literal_string()?;
 
         let seed = if self.parse_keyword(SEED) {
-            let key_schema = if self.parse_keyword(KEY) {
-                self.expect_keyword(SCHEMA)?;
-                Some(self.parse_literal_string()?)
-            } else {
-                None
-            };
-            self.expect_keywords(&[VALUE, SCHEMA])?;
-            let value_schema = self.parse_literal_string()?;
-            Some(CsrSeed {
-                key_schema,
-                value_schema,
-            })
+            Some(self.parse_csr_seed()?)
         } else {
             None
         };
@@ -1595,6 +1606,70 @@ impl<'a> Parser<'a> {
         })
     }
 
+    fn parse_csr_connector_proto(&mut self) -> Result<CsrConnectorProto<Raw>, ParserError> {
+        let url = self.parse_literal_string()?;
+
+        let seed = if self.parse_keyword(SEED) {
+            if self.parse_keyword(COMPILED) {
+                let key = if self.parse_keyword(KEY) {
+                    Some(self.parse_csr_seed_compiled_encoding()?)
+                } else {
+                    None
+                };
+                self.expect_keyword(VALUE)?;
+                let value = self.parse_csr_seed_compiled_encoding()?;
+                Some(CsrSeedCompiledOrLegacy::Compiled(CsrSeedCompiled {
+                    key,
+                    value,
+                }))
+            } else {
+                Some(CsrSeedCompiledOrLegacy::Legacy(self.parse_csr_seed()?))
+            }
+        } else {
+            None
+        };
+
+        // Look ahead to avoid erroring on `WITH SNAPSHOT`; we only want to
+        // accept `WITH (...)` here.
+        let with_options = if self.peek_nth_token(1) == Some(Token::LParen) {
+            self.parse_opt_with_sql_options()?
+        } else {
+            vec![]
+        };
+
+        Ok(CsrConnectorProto {
+            url,
+            seed,
+            with_options,
+        })
+    }
+
+    fn parse_csr_seed(&mut self) -> Result<CsrSeed, ParserError> {
+        let key_schema = if self.parse_keyword(KEY) {
+            self.expect_keyword(SCHEMA)?;
+            Some(self.parse_literal_string()?)
+        } else {
+            None
+        };
+        self.expect_keywords(&[VALUE, SCHEMA])?;
+        let value_schema = self.parse_literal_string()?;
+        Ok(CsrSeed {
+            key_schema,
+            value_schema,
+        })
+    }
+
+    fn parse_csr_seed_compiled_encoding(&mut self) -> Result<CsrSeedCompiledEncoding, ParserError> {
+        self.expect_keyword(SCHEMA)?;
+        let schema = self.parse_literal_string()?;
+        self.expect_keyword(MESSAGE)?;
+        let message_name = self.parse_literal_string()?;
+        Ok(CsrSeedCompiledEncoding {
+            schema,
+            message_name,
+        })
+    }
+
     fn parse_schema(&mut self) -> Result<Schema, ParserError> {
         self.expect_keyword(SCHEMA)?;
         let schema = if self.parse_keyword(FILE) {
@@ -1630,13 +1705,16 @@ impl<'a> Parser<'a> {
     }
 
     fn parse_compression(&mut self) -> Result<Compression, ParserError> {
-        let compression = if self.parse_keyword(NONE) {
-            Compression::None
-        } else if self.parse_keyword(GZIP) {
-            Compression::Gzip
-        } else {
-            return self.expected(self.peek_pos(), "NONE or GZIP", self.peek_token());
-        };
+        let compression =
+            match self.expect_one_of_keywords(&[NONE, GZIP, ZSTD, SNAPPY, LZ4, AUTO])? {
+                NONE => Compression::None,
+                GZIP => Compression::Gzip,
+                ZSTD => Compression::Zstd,
+                SNAPPY => Compression::Snappy,
+                LZ4 => Compression::Lz4,
+                AUTO => Compression::Auto,
+                _ => unreachable!(),
+            };
         Ok(compression)
     }
 
@@ -1663,7 +1741,7 @@ impl<'a> Parser<'a> {
             Some(_) => unreachable!("parse_one_of_keywords returns None for this"),
             None => CreateSourceFormat::None,
         };
-        let key_envelope = self.parse_include_key()?;
+        let (key_envelope, include_metadata) = self.parse_source_include()?;
         let envelope = if self.parse_keyword(ENVELOPE) {
             let envelope = self.parse_envelope()?;
             if matches!(envelope, Envelope::Upsert) {
@@ -1693,6 +1771,7 @@ impl<'a> Parser<'a> {
             with_options,
             format,
             key_envelope,
+            include_metadata,
             envelope,
             if_not_exists,
             materialized,
@@ -1816,11 +1895,17 @@ impl<'a> Parser<'a> {
                 } else {
                     None
                 };
+                let details = if self.parse_keyword(DETAILS) {
+                    Some(self.parse_literal_string()?)
+                } else {
+                    None
+                };
 
                 Ok(CreateSourceConnector::Postgres {
                     conn,
                     publication,
                     slot,
+                    details,
                 })
             }
             FILE => {
@@ -1910,7 +1995,7 @@ impl<'a> Parser<'a> {
     }
 
     fn parse_create_sink_connector(&mut self) -> Result<CreateSinkConnector<Raw>, ParserError> {
-        match self.expect_one_of_keywords(&[KAFKA, AVRO])? {
+        match self.expect_one_of_keywords(&[KAFKA, AVRO, FILE, S3])? {
             KAFKA => {
                 self.expect_keyword(BROKER)?;
                 let broker = self.parse_literal_string()?;
@@ -1940,6 +2025,20 @@ impl<'a> Parser<'a> {
                 let path = self.parse_literal_string()?;
                 Ok(CreateSinkConnector::AvroOcf { path })
             }
+            FILE => {
+                let path = self.parse_literal_string()?;
+                Ok(CreateSinkConnector::File { path })
+            }
+            S3 => {
+                self.expect_keyword(BUCKET)?;
+                let bucket = self.parse_literal_string()?;
+                let prefix = if self.parse_keyword(PREFIX) {
+                    Some(self.parse_literal_string()?)
+                } else {
+                    None
+                };
+                Ok(CreateSinkConnector::S3 { bucket, prefix })
+            }
             _ => unreachable!(),
         }
     }
@@ -2121,30 +2220,66 @@ impl<'a> Parser<'a> {
             _ => unreachable!(),
         };
         let name = self.parse_identifier()?;
+        let options = self.parse_role_options()?;
+        Ok(Statement::CreateRole(CreateRoleStatement {
+            is_user,
+            name,
+            options,
+        }))
+    }
+
+    fn parse_role_options(&mut self) -> Result<Vec<CreateRoleOption>, ParserError> {
         let _ = self.parse_keyword(WITH);
         let mut options = vec![];
         loop {
-            match self.parse_one_of_keywords(&[SUPERUSER, NOSUPERUSER, LOGIN, NOLOGIN]) {
+            match self.parse_one_of_keywords(&[SUPERUSER, NOSUPERUSER, LOGIN, NOLOGIN, PASSWORD]) {
                 None => break,
                 Some(SUPERUSER) => options.push(CreateRoleOption::SuperUser),
                 Some(NOSUPERUSER) => options.push(CreateRoleOption::NoSuperUser),
                 Some(LOGIN) => options.push(CreateRoleOption::Login),
                 Some(NOLOGIN) => options.push(CreateRoleOption::NoLogin),
+                Some(PASSWORD) => {
+                    let password = if self.parse_keyword(NULL) {
+                        None
+                    } else {
+                        Some(self.parse_literal_string()?)
+                    };
+                    options.push(CreateRoleOption::Password(password));
+                }
                 Some(_) => unreachable!(),
             }
         }
-        Ok(Statement::CreateRole(CreateRoleStatement {
-            is_user,
-            name,
-            options,
-        }))
+        Ok(options)
     }
 
     fn parse_create_type(&mut self) -> Result<Statement<Raw>, ParserError> {
         self.expect_keyword(TYPE)?;
         let name = self.parse_object_name()?;
         self.expect_keyword(AS)?;
-        let as_type = match self.expect_one_of_keywords(&[LIST, MAP])? {
+        if self.consume_token(&Token::LParen) {
+            let with_options = self.parse_comma_separated(Parser::parse_composite_type_field)?;
+            self.expect_token(&Token::RParen)?;
+            return Ok(Statement::CreateType(CreateTypeStatement {
+                name,
+                as_type: CreateTypeAs::Record,
+                with_options,
+            }));
+        }
+        let as_type = match self.expect_one_of_keywords(&[ENUM, LIST, MAP])? {
+            ENUM => {
+                self.expect_token(&Token::LParen)?;
+                let labels = if self.peek_token() == Some(Token::RParen) {
+                    vec![]
+                } else {
+                    self.parse_comma_separated(Parser::parse_literal_string)?
+                };
+                self.expect_token(&Token::RParen)?;
+                return Ok(Statement::CreateType(CreateTypeStatement {
+                    name,
+                    as_type: CreateTypeAs::Enum { labels },
+                    with_options: vec![],
+                }));
+            }
             LIST => CreateTypeAs::List,
             MAP => CreateTypeAs::Map,
             _ => unreachable!(),
@@ -2161,6 +2296,13 @@ impl<'a> Parser<'a> {
         }))
     }
 
+    fn parse_composite_type_field(&mut self) -> Result<SqlOption<Raw>, ParserError> {
+        Ok(SqlOption::DataType {
+            name: self.parse_identifier()?,
+            data_type: self.parse_data_type()?,
+        })
+    }
+
     fn parse_data_type_option(&mut self) -> Result<SqlOption<Raw>, ParserError> {
         let name = self.parse_identifier()?;
         self.expect_token(&Token::Eq)?;
@@ -2188,16 +2330,62 @@ impl<'a> Parser<'a> {
         }
     }
 
-    fn parse_include_key(&mut self) -> Result<CreateSourceKeyEnvelope, ParserError> {
-        Ok(if self.parse_keywords(&[INCLUDE, KEY]) {
-            if self.parse_keyword(AS) {
-                CreateSourceKeyEnvelope::Named(self.parse_identifier()?)
-            } else {
-                CreateSourceKeyEnvelope::Included
+    /// Parses the optional `INCLUDE` clause of a `CREATE SOURCE` statement,
+    /// which may request the message key and any number of metadata columns,
+    /// e.g. `INCLUDE KEY AS k, PARTITION, OFFSET AS o`.
+    fn parse_source_include(
+        &mut self,
+    ) -> Result<(CreateSourceKeyEnvelope, Vec<SourceIncludeMetadata>), ParserError> {
+        let mut key_envelope = CreateSourceKeyEnvelope::None;
+        let mut include_metadata = vec![];
+        if !self.parse_keyword(INCLUDE) {
+            return Ok((key_envelope, include_metadata));
+        }
+        loop {
+            let ty =
+                match self.expect_one_of_keywords(&[KEY, PARTITION, OFFSET, TIMESTAMP, HEADERS])? {
+                    KEY => {
+                        if key_envelope.is_present() {
+                            return Err(self.error(
+                                self.peek_prev_pos(),
+                                "KEY specified more than once in INCLUDE".into(),
+                            ));
+                        }
+                        key_envelope = if self.parse_keyword(AS) {
+                            CreateSourceKeyEnvelope::Named(self.parse_identifier()?)
+                        } else {
+                            CreateSourceKeyEnvelope::Included
+                        };
+                        None
+                    }
+                    PARTITION => Some(SourceIncludeMetadataType::Partition),
+                    OFFSET => Some(SourceIncludeMetadataType::Offset),
+                    TIMESTAMP => Some(SourceIncludeMetadataType::Timestamp),
+                    HEADERS => Some(SourceIncludeMetadataType::Headers),
+                    _ => unreachable!(),
+                };
+            if let Some(ty) = ty {
+                if include_metadata
+                    .iter()
+                    .any(|m: &SourceIncludeMetadata| m.ty == ty)
+                {
+                    return Err(self.error(
+                        self.peek_prev_pos(),
+                        format!("{} specified more than once in INCLUDE", ty),
+                    ));
+                }
+                let alias = if self.parse_keyword(AS) {
+                    Some(self.parse_identifier()?)
+                } else {
+                    None
+                };
+                include_metadata.push(SourceIncludeMetadata { ty, alias });
             }
-        } else {
-            CreateSourceKeyEnvelope::None
-        })
+            if !self.consume_token(&Token::Comma) {
+                break;
+            }
+        }
+        Ok((key_envelope, include_metadata))
     }
 
     fn parse_discard(&mut self) -> Result<Statement<Raw>, ParserError> {
@@ -2308,26 +2496,7 @@ impl<'a> Parser<'a> {
             if let Some(constraint) = self.parse_optional_table_constraint()? {
                 constraints.push(constraint);
             } else if let Some(column_name) = self.consume_identifier() {
-                let data_type = self.parse_data_type()?;
-                let collation = if self.parse_keyword(COLLATE) {
-                    Some(self.parse_object_name()?)
-                } else {
-                    None
-                };
-                let mut options = vec![];
-                loop {
-                    match self.peek_token() {
-                        None | Some(Token::Comma) | Some(Token::RParen) => break,
-                        _ => options.push(self.parse_column_option_def()?),
-                    }
-                }
-
-                columns.push(ColumnDef {
-                    name: column_name,
-                    data_type,
-                    collation,
-                    options,
-                });
+                columns.push(self.parse_column_def(column_name)?);
             } else {
                 return self.expected(
                     self.peek_pos(),
@@ -2351,6 +2520,30 @@ impl<'a> Parser<'a> {
         Ok((columns, constraints))
     }
 
+    /// Parses the remainder of a column definition whose name has already
+    /// been consumed.
+    fn parse_column_def(&mut self, name: Ident) -> Result<ColumnDef<Raw>, ParserError> {
+        let data_type = self.parse_data_type()?;
+        let collation = if self.parse_keyword(COLLATE) {
+            Some(self.parse_object_name()?)
+        } else {
+            None
+        };
+        let mut options = vec![];
+        loop {
+            match self.peek_token() {
+                None | Some(Token::Comma) | Some(Token::RParen) | Some(Token::Semicolon) => break,
+                _ => options.push(self.parse_column_option_def()?),
+            }
+        }
+        Ok(ColumnDef {
+            name,
+            data_type,
+            collation,
+            options,
+        })
+    }
+
     fn parse_column_option_def(&mut self) -> Result<ColumnOptionDef<Raw>, ParserError> {
         let name = if self.parse_keyword(CONSTRAINT) {
             Some(self.parse_identifier()?)
@@ -2518,6 +2711,16 @@ impl<'a> Parser<'a> {
     }
 
     fn parse_alter(&mut self) -> Result<Statement<Raw>, ParserError> {
+        if let Some(kw) = self.parse_one_of_keywords(&[ROLE, USER]) {
+            let name = self.parse_identifier()?;
+            let options = self.parse_role_options()?;
+            return Ok(Statement::AlterRole(AlterRoleStatement {
+                is_user: kw == USER,
+                name,
+                options,
+            }));
+        }
+
         let object_type = match self.expect_one_of_keywords(&[INDEX, SINK, SOURCE, VIEW, TABLE])? {
             INDEX => ObjectType::Index,
             SINK => ObjectType::Sink,
@@ -2562,6 +2765,38 @@ impl<'a> Parser<'a> {
             }
         }
 
+        // We support `ALTER TABLE ... {ADD, DROP} [COLUMN]`
+        if object_type == ObjectType::Table {
+            let action = match self.parse_one_of_keywords(&[ADD, DROP]) {
+                Some(ADD) => {
+                    let _ = self.parse_keyword(COLUMN);
+                    let if_not_exists = self.parse_if_not_exists()?;
+                    let column_name = self.parse_identifier()?;
+                    let column = self.parse_column_def(column_name)?;
+                    Some(AlterTableAction::AddColumn {
+                        if_not_exists,
+                        column,
+                    })
+                }
+                Some(DROP) => {
+                    let _ = self.parse_keyword(COLUMN);
+                    let if_exists = self.parse_if_exists()?;
+                    let name = self.parse_identifier()?;
+                    Some(AlterTableAction::DropColumn { if_exists, name })
+                }
+                Some(_) => unreachable!(),
+                None => None,
+            };
+
+            if let Some(action) = action {
+                return Ok(Statement::AlterTable(AlterTableStatement {
+                    table_name: name,
+                    if_exists,
+                    action,
+                }));
+            }
+        }
+
         self.expect_keywords(&[RENAME, TO])?;
         let to_item_name = self.parse_identifier()?;
 
@@ -3081,44 +3316,47 @@ impl<'a> Parser<'a> {
     /// expect the initial keyword to be already consumed
     fn parse_query(&mut self) -> Result<Query<Raw>, ParserError> {
         self.check_descent(|parser| {
-            let ctes = if parser.parse_keyword(WITH) {
-                // TODO: optional RECURSIVE
-                parser.parse_comma_separated(Parser::parse_cte)?
+            let (ctes, recursive) = if parser.parse_keyword(WITH) {
+                let recursive = parser.parse_keyword(RECURSIVE);
+                (parser.parse_comma_separated(Parser::parse_cte)?, recursive)
             } else {
-                vec![]
+                (vec![], false)
             };
 
             let body = parser.parse_query_body(SetPrecedence::Zero)?;
 
-            parser.parse_query_tail(ctes, body)
+            parser.parse_query_tail(ctes, recursive, body)
         })
     }
 
     fn parse_query_tail(
         &mut self,
         ctes: Vec<Cte<Raw>>,
+        recursive: bool,
         body: SetExpr<Raw>,
     ) -> Result<Query<Raw>, ParserError> {
-        let (inner_ctes, inner_order_by, inner_limit, inner_offset, body) = match body {
-            SetExpr::Query(query) => {
-                let Query {
-                    ctes,
-                    body,
-                    order_by,
-                    limit,
-                    offset,
-                } = *query;
-                (ctes, order_by, limit, offset, body)
-            }
-            _ => (vec![], vec![], None, None, body),
-        };
+        let (inner_ctes, inner_recursive, inner_order_by, inner_limit, inner_offset, body) =
+            match body {
+                SetExpr::Query(query) => {
+                    let Query {
+                        ctes,
+                        recursive,
+                        body,
+                        order_by,
+                        limit,
+                        offset,
+                    } = *query;
+                    (ctes, recursive, order_by, limit, offset, body)
+                }
+                _ => (vec![], false, vec![], None, None, body),
+            };
 
-        let ctes = if ctes.is_empty() {
-            inner_ctes
+        let (ctes, recursive) = if ctes.is_empty() {
+            (inner_ctes, inner_recursive)
         } else if !inner_ctes.is_empty() {
             return parser_err!(self, self.peek_pos(), "multiple WITH clauses not allowed");
         } else {
-            ctes
+            (ctes, recursive)
         };
 
         let order_by = if self.parse_keywords(&[ORDER, BY]) {
@@ -3197,6 +3435,7 @@ impl<'a> Parser<'a> {
 
         Ok(Query {
             ctes,
+            recursive,
             body,
             order_by,
             limit,
@@ -3796,13 +4035,42 @@ impl<'a> Parser<'a> {
         } else {
             InsertSource::Query(self.parse_query()?)
         };
+        let on_conflict = if self.parse_keywords(&[ON, CONFLICT]) {
+            Some(self.parse_on_conflict()?)
+        } else {
+            None
+        };
         Ok(Statement::Insert(InsertStatement {
             table_name,
             columns,
             source,
+            on_conflict,
         }))
     }
 
+    fn parse_on_conflict(&mut self) -> Result<OnConflict<Raw>, ParserError> {
+        let columns = self.parse_parenthesized_column_list(Optional)?;
+        self.expect_keyword(DO)?;
+        let action = match self.expect_one_of_keywords(&[NOTHING, UPDATE])? {
+            NOTHING => OnConflictAction::DoNothing,
+            UPDATE => {
+                self.expect_keyword(SET)?;
+                let assignments = self.parse_comma_separated(Parser::parse_assignment)?;
+                let selection = if self.parse_keyword(WHERE) {
+                    Some(self.parse_expr()?)
+                } else {
+                    None
+                };
+                OnConflictAction::DoUpdate {
+                    assignments,
+                    selection,
+                }
+            }
+            _ => unreachable!(),
+        };
+        Ok(OnConflict { columns, action })
+    }
+
     fn parse_update(&mut self) -> Result<Statement<Raw>, ParserError> {
         let table_name = self.parse_object_name()?;
         self.expect_keyword(SET)?;
@@ -0,0 +1,286 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Password authentication for roles.
+//!
+//! Role passwords are never stored directly. Instead, the catalog stores a
+//! SCRAM-SHA-256 verifier, as described in [RFC 5802] and [RFC 7677], using
+//! the same textual format as PostgreSQL:
+//!
+//! ```text
+//! SCRAM-SHA-256$<iterations>:<salt>$<StoredKey>:<ServerKey>
+//! ```
+//!
+//! The verifier is sufficient both to check a cleartext password and to
+//! complete a SCRAM-SHA-256 exchange with a client.
+//!
+//! [RFC 5802]: https://tools.ietf.org/html/rfc5802
+//! [RFC 7677]: https://tools.ietf.org/html/rfc7677
+
+use std::fmt;
+use std::str::FromStr;
+
+use openssl::base64;
+use openssl::error::ErrorStack;
+use openssl::hash::MessageDigest;
+use openssl::memcmp;
+use openssl::pkcs5;
+use openssl::pkey::PKey;
+use openssl::rand;
+use openssl::sha;
+use openssl::sign::Signer;
+
+use ore::cast::CastFrom;
+
+/// The name of the SCRAM-SHA-256 SASL mechanism.
+pub const SCRAM_SHA_256: &str = "SCRAM-SHA-256";
+
+/// The number of PBKDF2 iterations used for new verifiers. This matches
+/// PostgreSQL's default.
+const SCRAM_ITERATIONS: u32 = 4096;
+
+/// The length of the random salt used for new verifiers, in bytes.
+const SCRAM_SALT_LEN: usize = 16;
+
+/// A SCRAM-SHA-256 verifier for a role's password.
+#[derive(Clone, PartialEq, Eq)]
+pub struct ScramSha256Verifier {
+    /// The number of PBKDF2 iterations used to salt the password.
+    pub iterations: u32,
+    /// The salt.
+    pub salt: Vec<u8>,
+    /// `H(HMAC(SaltedPassword, "Client Key"))`.
+    pub stored_key: Vec<u8>,
+    /// `HMAC(SaltedPassword, "Server Key")`.
+    pub server_key: Vec<u8>,
+}
+
+impl ScramSha256Verifier {
+    /// Constructs a verifier for `password` with a randomly-generated salt.
+    pub fn new(password: &str) -> Result<ScramSha256Verifier, ErrorStack> {
+        let mut salt = vec![0; SCRAM_SALT_LEN];
+        rand::rand_bytes(&mut salt)?;
+        ScramSha256Verifier::with_salt(password, salt, SCRAM_ITERATIONS)
+    }
+
+    /// Constructs a verifier to carry out a SCRAM exchange with role `user`,
+    /// which does not have a password.
+    ///
+    /// The salt is derived from `user` and the server's `secret`, so that, like
+    /// the salt of a real verifier, it is the same in every exchange with the
+    /// role, and does not reveal that the role has no password.
+    pub fn mock(user: &str, secret: &[u8]) -> Result<ScramSha256Verifier, ErrorStack> {
+        let mut salt = hmac(secret, user.as_bytes())?;
+        salt.truncate(SCRAM_SALT_LEN);
+        ScramSha256Verifier::with_salt(user, salt, SCRAM_ITERATIONS)
+    }
+
+    fn with_salt(
+        password: &str,
+        salt: Vec<u8>,
+        iterations: u32,
+    ) -> Result<ScramSha256Verifier, ErrorStack> {
+        let salted_password = salt_password(password, &salt, iterations)?;
+        let client_key = hmac(&salted_password, b"Client Key")?;
+        Ok(ScramSha256Verifier {
+            iterations,
+            salt,
+            stored_key: sha::sha256(&client_key).to_vec(),
+            server_key: hmac(&salted_password, b"Server Key")?,
+        })
+    }
+
+    /// Reports whether `password` matches the password from which this
+    /// verifier was constructed.
+    pub fn verify_password(&self, password: &str) -> Result<bool, ErrorStack> {
+        let other = ScramSha256Verifier::with_salt(password, self.salt.clone(), self.iterations)?;
+        Ok(constant_time_eq(&self.stored_key, &other.stored_key)
+            && constant_time_eq(&self.server_key, &other.server_key))
+    }
+
+    /// Reports whether `client_proof` proves that the client knows the
+    /// password for this verifier, given the SCRAM `auth_message` for the
+    /// exchange.
+    pub fn verify_client_proof(
+        &self,
+        auth_message: &[u8],
+        client_proof: &[u8],
+    ) -> Result<bool, ErrorStack> {
+        let client_signature = hmac(&self.stored_key, auth_message)?;
+        if client_proof.len() != client_signature.len() {
+            return Ok(false);
+        }
+        let client_key: Vec<u8> = client_proof
+            .iter()
+            .zip(client_signature)
+            .map(|(p, s)| p ^ s)
+            .collect();
+        Ok(constant_time_eq(
+            &sha::sha256(&client_key),
+            &self.stored_key,
+        ))
+    }
+
+    /// Computes the server signature for the SCRAM `auth_message`, which
+    /// proves to the client that the server knows the verifier.
+    pub fn server_signature(&self, auth_message: &[u8]) -> Result<Vec<u8>, ErrorStack> {
+        hmac(&self.server_key, auth_message)
+    }
+}
+
+impl fmt::Debug for ScramSha256Verifier {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        // Avoid leaking key material into logs.
+        f.debug_struct("ScramSha256Verifier")
+            .field("iterations", &self.iterations)
+            .finish()
+    }
+}
+
+impl fmt::Display for ScramSha256Verifier {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{}${}:{}${}:{}",
+            SCRAM_SHA_256,
+            self.iterations,
+            base64::encode_block(&self.salt),
+            base64::encode_block(&self.stored_key),
+            base64::encode_block(&self.server_key),
+        )
+    }
+}
+
+impl FromStr for ScramSha256Verifier {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<ScramSha256Verifier, anyhow::Error> {
+        let invalid = || anyhow::anyhow!("invalid SCRAM-SHA-256 verifier");
+        let mut parts = s.split('$');
+        if parts.next() != Some(SCRAM_SHA_256) {
+            return Err(invalid());
+        }
+        let (iterations, salt) = parts
+            .next()
+            .and_then(|p| p.split_once(':'))
+            .ok_or_else(invalid)?;
+        let (stored_key, server_key) = parts
+            .next()
+            .and_then(|p| p.split_once(':'))
+            .ok_or_else(invalid)?;
+        if parts.next().is_some() {
+            return Err(invalid());
+        }
+        let decode = |s| base64::decode_block(s).map_err(|_| invalid());
+        Ok(ScramSha256Verifier {
+            iterations: iterations.parse().map_err(|_| invalid())?,
+            salt: decode(salt)?,
+            stored_key: decode(stored_key)?,
+            server_key: decode(server_key)?,
+        })
+    }
+}
+
+/// Computes `Hi(Normalize(password), salt, iterations)`, per RFC 5802.
+///
+/// Like PostgreSQL, passwords that cannot be normalized with SASLprep are used
+/// as is.
+fn salt_password(password: &str, salt: &[u8], iterations: u32) -> Result<Vec<u8>, ErrorStack> {
+    let password = stringprep::saslprep(password).unwrap_or_else(|_| password.into());
+    let mut salted_password = vec![0; 32];
+    pkcs5::pbkdf2_hmac(
+        password.as_bytes(),
+        salt,
+        usize::cast_from(iterations),
+        MessageDigest::sha256(),
+        &mut salted_password,
+    )?;
+    Ok(salted_password)
+}
+
+fn hmac(key: &[u8], data: &[u8]) -> Result<Vec<u8>, ErrorStack> {
+    let key = PKey::hmac(key)?;
+    let mut signer = Signer::new(MessageDigest::sha256(), &key)?;
+    signer.update(data)?;
+    signer.sign_to_vec()
+}
+
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && memcmp::eq(a, b)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_verifier_roundtrip() -> Result<(), anyhow::Error> {
+        let verifier = ScramSha256Verifier::new("hunter2")?;
+        let roundtripped: ScramSha256Verifier = verifier.to_string().parse()?;
+        assert_eq!(verifier, roundtripped);
+        assert!(roundtripped.verify_password("hunter2")?);
+        assert!(!roundtripped.verify_password("hunter3")?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_mock_verifier() -> Result<(), anyhow::Error> {
+        let verifier = ScramSha256Verifier::mock("alice", b"secret")?;
+        assert_eq!(verifier.salt.len(), SCRAM_SALT_LEN);
+        assert_eq!(verifier, ScramSha256Verifier::mock("alice", b"secret")?);
+        assert_ne!(
+            verifier.salt,
+            ScramSha256Verifier::mock("bob", b"secret")?.salt
+        );
+        assert_ne!(
+            verifier.salt,
+            ScramSha256Verifier::mock("alice", b"other secret")?.salt
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_rfc7677_exchange() -> Result<(), anyhow::Error> {
+        // The example exchange from RFC 7677, Section 3.
+        let salt = base64::decode_block("W22ZaJ0SNY7soEsUEjb6gQ==")?;
+        let verifier = ScramSha256Verifier::with_salt("pencil", salt, 4096)?;
+        assert_eq!(
+            verifier.to_string(),
+            "SCRAM-SHA-256$4096:W22ZaJ0SNY7soEsUEjb6gQ==$\
+             WG5d8oPm3OtcPnkdi4Uo7BkeZkBFzpcXkuLmtbsT4qY=:\
+             wfPLwcE6nTWhTAmQ7tl2KeoiWGPlZqQxSrmfPwDl2dU="
+        );
+
+        let auth_message = "n=user,r=rOprNGfwEbeRWgbNEkqO,\
+             r=rOprNGfwEbeRWgbNEkqO%hvYDpWUa2RaTCAfuxFIlj)hNlF$k0,\
+             s=W22ZaJ0SNY7soEsUEjb6gQ==,i=4096,\
+             c=biws,r=rOprNGfwEbeRWgbNEkqO%hvYDpWUa2RaTCAfuxFIlj)hNlF$k0";
+        let client_proof = base64::decode_block("dHzbZapWIk4jUhN+Ute9ytag9zjfMHgsqmmiz7AndVQ=")?;
+        assert!(verifier.verify_client_proof(auth_message.as_bytes(), &client_proof)?);
+        assert!(!verifier.verify_client_proof(b"bogus", &client_proof)?);
+        assert_eq!(
+            base64::encode_block(&verifier.server_signature(auth_message.as_bytes())?),
+            "6rriTRBi23WpRR/wtup+mMhUZUn/dB5nLTJRsjl95G4="
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_verifier_parse_errors() {
+        for s in &[
+            "",
+            "md5abcdef",
+            "SCRAM-SHA-256$4096:c2FsdA==",
+            "SCRAM-SHA-256$abc:c2FsdA==$a2V5:a2V5",
+            "SCRAM-SHA-256$4096:c2FsdA==$a2V5:a2V5$extra",
+        ] {
+            assert!(s.parse::<ScramSha256Verifier>().is_err(), "{}", s);
+        }
+    }
+}
@@ -29,6 +29,7 @@ use uuid::Uuid;
 use crate::func::Func;
 use crate::names::{FullName, PartialName, SchemaName};
 use crate::plan::statement::StatementDesc;
+use crate::plan::UniqueConstraint;
 
 /// A catalog keeps track of SQL objects and session state available to the
 /// planner.
@@ -125,6 +126,9 @@ pub trait SessionCatalog: fmt::Debug + ExprHumanizer {
     /// Panics if `id` does not specify a valid item.
     fn get_item_by_id(&self, id: &GlobalId) -> &dyn CatalogItem;
 
+    /// Gets an item by its OID.
+    fn try_get_item_by_oid(&self, oid: &u32) -> Option<&dyn CatalogItem>;
+
     /// Gets an item by its OID.
     ///
     /// Panics if `oid` does not specify a valid item.
@@ -275,6 +279,14 @@ pub trait CatalogItem {
     /// Returns the column defaults associated with the catalog item, if the
     /// catalog item is a table.
     fn table_details(&self) -> Option<&[Expr<Raw>]>;
+
+    /// Returns the `PRIMARY KEY` and `UNIQUE` constraints of the catalog item,
+    /// if the catalog item is a table.
+    fn table_unique_constraints(&self) -> Option<&[UniqueConstraint]>;
+
+    /// Returns the labels of the catalog item, in declaration order, if the
+    /// catalog item is an enum type.
+    fn enum_labels(&self) -> Option<&[String]>;
 }
 
 /// The type of a [`CatalogItem`].
@@ -440,6 +452,10 @@ impl SessionCatalog for DummyCatalog {
         unimplemented!();
     }
 
+    fn try_get_item_by_oid(&self, _: &u32) -> Option<&dyn CatalogItem> {
+        unimplemented!();
+    }
+
     fn get_item_by_oid(&self, _: &u32) -> &dyn CatalogItem {
         unimplemented!();
     }
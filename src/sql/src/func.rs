@@ -14,6 +14,7 @@ use std::cell::RefCell;
 use std::collections::HashMap;
 use std::convert::TryFrom;
 use std::fmt;
+use std::iter;
 use std::rc::Rc;
 
 use anyhow::{bail, Context};
@@ -28,8 +29,8 @@ use repr::{ColumnName, ColumnType, Datum, RelationType, Row, ScalarBaseType, Sca
 
 use crate::names::PartialName;
 use crate::plan::expr::{
-    AggregateFunc, BinaryFunc, CoercibleScalarExpr, ColumnOrder, HirScalarExpr, NullaryFunc,
-    TableFunc, UnaryFunc, VariadicFunc,
+    AggregateFunc, BinaryFunc, CoercibleScalarExpr, ColumnOrder, HirScalarExpr, LagLeadType,
+    NullaryFunc, TableFunc, UnaryFunc, VariadicFunc, WindowFrame,
 };
 use crate::plan::query::{self, ExprContext, QueryContext, QueryLifetime};
 use crate::plan::scope::Scope;
@@ -69,6 +70,7 @@ pub enum TypeCategory {
     List,
     Numeric,
     Pseudo,
+    Range,
     String,
     Timespan,
     UserDefined,
@@ -90,7 +92,9 @@ impl TypeCategory {
         match typ {
             ScalarType::Array(..) => Self::Array,
             ScalarType::Bool => Self::Bool,
-            ScalarType::Bytes | ScalarType::Jsonb | ScalarType::Uuid => Self::UserDefined,
+            ScalarType::Bytes | ScalarType::Jsonb | ScalarType::Uuid | ScalarType::Enum { .. } => {
+                Self::UserDefined
+            }
             ScalarType::Date
             | ScalarType::Time
             | ScalarType::Timestamp
@@ -110,6 +114,7 @@ impl TypeCategory {
             }
             ScalarType::Record { .. } => Self::Pseudo,
             ScalarType::Map { .. } => Self::Pseudo,
+            ScalarType::Range { .. } => Self::Range,
         }
     }
 
@@ -117,10 +122,14 @@ impl TypeCategory {
         match param {
             ParamType::Any
             | ParamType::ArrayAny
+            | ParamType::ArrayElementAny
             | ParamType::ListAny
             | ParamType::ListElementAny
             | ParamType::NonVecAny
-            | ParamType::MapAny => Self::Pseudo,
+            | ParamType::MapAny
+            | ParamType::EnumAny
+            | ParamType::RangeAny
+            | ParamType::RangeElementAny => Self::Pseudo,
             ParamType::Plain(t) => Self::from_type(t),
         }
     }
@@ -134,7 +143,7 @@ impl TypeCategory {
     /// ```
     fn preferred_type(&self) -> Option<ScalarType> {
         match self {
-            Self::Array | Self::List | Self::Pseudo | Self::UserDefined => None,
+            Self::Array | Self::List | Self::Pseudo | Self::Range | Self::UserDefined => None,
             Self::Bool => Some(ScalarType::Bool),
             Self::DateTime => Some(ScalarType::TimestampTz),
             Self::Numeric => Some(ScalarType::Float64),
@@ -195,6 +204,17 @@ impl<R> Operation<R> {
         })
     }
 
+    /// Builds an operation that takes no arguments and an order_by.
+    fn nullary_ordered<F>(f: F) -> Operation<R>
+    where
+        F: Fn(&ExprContext, Vec<ColumnOrder>) -> Result<R, anyhow::Error> + Send + Sync + 'static,
+    {
+        Self::new(move |ecx, _spec, cexprs, _params, order_by| {
+            assert!(cexprs.is_empty());
+            f(ecx, order_by)
+        })
+    }
+
     /// Builds an operation that takes one argument.
     fn unary<F>(f: F) -> Operation<R>
     where
@@ -257,6 +277,20 @@ impl<R> Operation<R> {
         })
     }
 
+    /// Builds an operation that takes any number of arguments and an order_by.
+    fn variadic_ordered<F>(f: F) -> Operation<R>
+    where
+        F: Fn(&ExprContext, Vec<HirScalarExpr>, Vec<ColumnOrder>) -> Result<R, anyhow::Error>
+            + Send
+            + Sync
+            + 'static,
+    {
+        Self::new(move |ecx, spec, cexprs, params, order_by| {
+            let exprs = coerce_args_to_types(ecx, spec, cexprs, params)?;
+            f(ecx, exprs, order_by)
+        })
+    }
+
     /// Builds an operation that takes any number of arguments.
     fn variadic<F>(f: F) -> Operation<R>
     where
@@ -458,12 +492,18 @@ impl ParamList {
     ///
     /// Polymorphic type consistency constraints include:
     /// - All arguments passed to `ArrayAny` must be `ScalarType::Array`s with
-    ///   the same types of elements.
+    ///   the same types of elements. All arguments passed to `ArrayElementAny`
+    ///   must also be of these elements' type.
     /// - All arguments passed to `ListAny` must be `ScalarType::List`s with the
     ///   same types of elements. All arguments passed to `ListElementAny` must
     ///   also be of these elements' type.
     /// - All arguments passed to `MapAny` must be `ScalarType::Map`s with the
     ///   same type of value in each key, value pair.
+    /// - All arguments passed to `EnumAny` must be the same
+    ///   `ScalarType::Enum`.
+    /// - All arguments passed to `RangeAny` must be `ScalarType::Range`s with
+    ///   the same type of element. All arguments passed to `RangeElementAny`
+    ///   must also be of this element type.
     ///
     /// Returns `Some` if the constraints were successfully resolved, or `None`
     /// otherwise.
@@ -556,7 +596,8 @@ impl ParamList {
                         element_type: r, ..
                     },
                 )
-                | (ScalarType::Map { value_type: l, .. }, ScalarType::Map { value_type: r, .. }) => {
+                | (ScalarType::Map { value_type: l, .. }, ScalarType::Map { value_type: r, .. })
+                | (ScalarType::Range { element_type: l }, ScalarType::Range { element_type: r }) => {
                     complex_base_eq(l, r)
                 }
                 (l, r) => ScalarBaseType::from(l) == ScalarBaseType::from(r),
@@ -579,6 +620,14 @@ impl ParamList {
                         return None;
                     }
                 }
+                (ParamType::ArrayElementAny, Some(t), None) => {
+                    constrained_type = Some(ScalarType::Array(Box::new(t.clone())));
+                }
+                (ParamType::ArrayElementAny, Some(t), Some(constrained_array)) => {
+                    if !complex_base_eq(t, constrained_array.unwrap_array_element_type()) {
+                        return None;
+                    }
+                }
                 (ParamType::ListAny, Some(typ), None) | (ParamType::MapAny, Some(typ), None) => {
                     constrained_type = Some(typ.clone());
                     custom_oid_lock = typ.is_custom_type();
@@ -629,6 +678,32 @@ impl ParamList {
                         element_lock = true;
                     }
                 }
+                (ParamType::EnumAny, Some(t), None) => {
+                    constrained_type = Some(t.clone());
+                }
+                (ParamType::EnumAny, Some(t), Some(constrained)) => {
+                    if t != constrained {
+                        return None;
+                    }
+                }
+                (ParamType::RangeAny, Some(t), None) => {
+                    constrained_type = Some(t.clone());
+                }
+                (ParamType::RangeAny, Some(t), Some(constrained)) => {
+                    if !complex_base_eq(t, constrained) {
+                        return None;
+                    }
+                }
+                (ParamType::RangeElementAny, Some(t), None) => {
+                    constrained_type = Some(ScalarType::Range {
+                        element_type: Box::new(t.clone()),
+                    });
+                }
+                (ParamType::RangeElementAny, Some(t), Some(constrained_range)) => {
+                    if !complex_base_eq(t, constrained_range.unwrap_range_element_type()) {
+                        return None;
+                    }
+                }
                 (ParamType::NonVecAny, Some(t), None) => {
                     constrained_type = Some(t.clone());
                 }
@@ -704,6 +779,10 @@ pub enum ParamType {
     /// than `Any`, i.e. it is subject to polymorphic constraints. For more
     /// details, see `ParamList::resolve_polymorphic_types`.
     ListElementAny,
+    /// A polymorphic pseudotype permitting the element type of the
+    /// `ScalarType::Array` passed to `ArrayAny`. For more details, see
+    /// `ParamList::resolve_polymorphic_types`.
+    ArrayElementAny,
     /// A polymorphic pseudotype with the same behavior as `ListElementAny`,
     /// except it does not permit either `ScalarType::Array` or
     /// `ScalarType::List`.
@@ -711,6 +790,17 @@ pub enum ParamType {
     /// A polymorphic pseudotype permitting a `ScalarType::Map` of any non-nested
     /// value type. For more details, see `ParamList::resolve_polymorphic_types`.
     MapAny,
+    /// A polymorphic pseudotype permitting any `ScalarType::Enum`. For more
+    /// details, see `ParamList::resolve_polymorphic_types`.
+    EnumAny,
+    /// A polymorphic pseudotype permitting a `ScalarType::Range` of any
+    /// element type. For more details, see
+    /// `ParamList::resolve_polymorphic_types`.
+    RangeAny,
+    /// A polymorphic pseudotype permitting the element type of the
+    /// `ScalarType::Range` passed to `RangeAny`. For more details, see
+    /// `ParamList::resolve_polymorphic_types`.
+    RangeElementAny,
     /// A standard parameter that accepts arguments that match its embedded
     /// `ScalarType`.
     Plain(ScalarType),
@@ -724,10 +814,13 @@ impl ParamType {
 
         match self {
             ArrayAny => matches!(t, Array(..)),
+            ArrayElementAny => !matches!(t, Array(..)),
             ListAny => matches!(t, List { .. }),
-            Any | ListElementAny => true,
+            Any | ListElementAny | RangeElementAny => true,
             NonVecAny => !t.is_vec(),
             MapAny => matches!(t, Map { .. }),
+            EnumAny => matches!(t, Enum { .. }),
+            RangeAny => matches!(t, Range { .. }),
             Plain(to) => typeconv::can_cast(ecx, CastContext::Implicit, t.clone(), to.clone()),
         }
     }
@@ -754,7 +847,8 @@ impl ParamType {
     fn is_polymorphic(&self) -> bool {
         use ParamType::*;
         match self {
-            ArrayAny | ListAny | MapAny | ListElementAny | NonVecAny => true,
+            ArrayAny | ArrayElementAny | ListAny | MapAny | ListElementAny | NonVecAny
+            | EnumAny | RangeAny | RangeElementAny => true,
             Any | Plain(_) => false,
         }
     }
@@ -774,9 +868,13 @@ impl ParamType {
             },
             ParamType::Any => postgres_types::Type::ANY.oid(),
             ParamType::ArrayAny => postgres_types::Type::ANYARRAY.oid(),
+            ParamType::ArrayElementAny => postgres_types::Type::ANYELEMENT.oid(),
             ParamType::ListAny => pgrepr::LIST.oid(),
             ParamType::ListElementAny => postgres_types::Type::ANYELEMENT.oid(),
             ParamType::MapAny => pgrepr::MAP.oid(),
+            ParamType::EnumAny => postgres_types::Type::ANYENUM.oid(),
+            ParamType::RangeAny => postgres_types::Type::ANYRANGE.oid(),
+            ParamType::RangeElementAny => postgres_types::Type::ANYELEMENT.oid(),
             ParamType::NonVecAny => postgres_types::Type::ANYNONARRAY.oid(),
         }
     }
@@ -828,7 +926,7 @@ impl From<ScalarBaseType> for ParamType {
             Uuid => ScalarType::Uuid,
             Oid => ScalarType::Oid,
             RegProc => ScalarType::RegProc,
-            Array | List | Record | Map => {
+            Array | List | Record | Map | Enum | Range => {
                 panic!("cannot convert ScalarBaseType::{:?} to ParamType", s)
             }
         })
@@ -1152,8 +1250,18 @@ fn coerce_args_to_types(
             ParamType::Plain(ty) => do_convert(arg, ty)?,
 
             // Polymorphic pseudotypes. Convert based on constrained type.
-            ParamType::ArrayAny | ParamType::ListAny | ParamType::MapAny => {
-                do_convert(arg, &get_constrained_ty())?
+            ParamType::ArrayAny
+            | ParamType::ListAny
+            | ParamType::MapAny
+            | ParamType::EnumAny
+            | ParamType::RangeAny => do_convert(arg, &get_constrained_ty())?,
+            ParamType::ArrayElementAny => {
+                let constrained_array = get_constrained_ty();
+                do_convert(arg, &constrained_array.unwrap_array_element_type())?
+            }
+            ParamType::RangeElementAny => {
+                let constrained_range = get_constrained_ty();
+                do_convert(arg, &constrained_range.unwrap_range_element_type())?
             }
             ParamType::ListElementAny => {
                 let constrained_list = get_constrained_ty();
@@ -1221,6 +1329,10 @@ pub enum Func {
     Scalar(Vec<FuncImpl<HirScalarExpr>>),
     Aggregate(Vec<FuncImpl<(HirScalarExpr, AggregateFunc)>>),
     Table(Vec<FuncImpl<TableFuncPlan>>),
+    /// Functions that may only be called with an `OVER` clause. Like
+    /// aggregates, they are planned as an argument expression and an
+    /// `AggregateFunc`, which must be a window function.
+    Window(Vec<FuncImpl<(HirScalarExpr, AggregateFunc)>>),
 }
 
 impl Func {
@@ -1229,10 +1341,48 @@ impl Func {
             Func::Scalar(impls) => impls.iter().map(|f| f.details()).collect::<Vec<_>>(),
             Func::Aggregate(impls) => impls.iter().map(|f| f.details()).collect::<Vec<_>>(),
             Func::Table(impls) => impls.iter().map(|f| f.details()).collect::<Vec<_>>(),
+            Func::Window(impls) => impls.iter().map(|f| f.details()).collect::<Vec<_>>(),
         }
     }
 }
 
+/// Plans the arguments to `lag` or `lead` into the record expected by
+/// `AggregateFunc::LagLead`, filling in the default offset and default value.
+fn plan_lag_lead(
+    ecx: &ExprContext,
+    lag_lead: LagLeadType,
+    exprs: Vec<HirScalarExpr>,
+    order_by: Vec<ColumnOrder>,
+) -> Result<(HirScalarExpr, AggregateFunc), anyhow::Error> {
+    let mut exprs = exprs.into_iter();
+    let value = exprs.next().unwrap();
+    let value_type = ecx.scalar_type(&value);
+    let offset = exprs
+        .next()
+        .unwrap_or_else(|| HirScalarExpr::literal(Datum::Int32(1), ScalarType::Int32));
+    let default = match exprs.next() {
+        Some(default) => typeconv::plan_cast(
+            &lag_lead.to_string(),
+            ecx,
+            CastContext::Implicit,
+            default,
+            &value_type,
+        )?,
+        None => HirScalarExpr::literal_null(value_type),
+    };
+    let e = HirScalarExpr::CallVariadic {
+        func: VariadicFunc::RecordCreate {
+            field_names: vec![
+                ColumnName::from("value"),
+                ColumnName::from("offset"),
+                ColumnName::from("default"),
+            ],
+        },
+        exprs: vec![value, offset, default],
+    };
+    Ok((e, AggregateFunc::LagLead { order_by, lag_lead }))
+}
+
 /// Functions using this macro should be transformed/planned away before
 /// reaching function selection code, but still need to be present in the
 /// catalog during planning.
@@ -1271,6 +1421,12 @@ lazy_static! {
                 params!(Float32) => UnaryFunc::AbsFloat32(func::AbsFloat32), 1394;
                 params!(Float64) => UnaryFunc::AbsFloat64(func::AbsFloat64), 1395;
             },
+            "array_append" => Scalar {
+                params!(ArrayAny, ArrayElementAny) => BinaryFunc::ArrayElementConcat, 378;
+            },
+            "array_cat" => Scalar {
+                params!(ArrayAny, ArrayAny) => BinaryFunc::ArrayArrayConcat, 383;
+            },
             "array_in" => Scalar {
                 params!(String, Oid, Int32) => Operation::unary(|_ecx, _e| bail_unsupported!("array_in")), 750;
             },
@@ -1280,6 +1436,19 @@ lazy_static! {
             "array_lower" => Scalar {
                 params!(ArrayAny, Int64) => BinaryFunc::ArrayLower, 2091;
             },
+            "array_position" => Scalar {
+                params!(ArrayAny, ArrayElementAny) => VariadicFunc::ArrayPosition, 3277;
+                params!(ArrayAny, ArrayElementAny, Int32) => VariadicFunc::ArrayPosition, 3278;
+            },
+            "array_positions" => Scalar {
+                params!(ArrayAny, ArrayElementAny) => BinaryFunc::ArrayPositions, 3279;
+            },
+            "array_prepend" => Scalar {
+                params!(ArrayElementAny, ArrayAny) => BinaryFunc::ElementArrayConcat, 379;
+            },
+            "array_remove" => Scalar {
+                params!(ArrayAny, ArrayElementAny) => BinaryFunc::ArrayRemove, 3167;
+            },
             "array_to_string" => Scalar {
                 params!(ArrayAny, String) => Operation::variadic(array_to_string), 395;
                 params!(ArrayAny, String, String) => Operation::variadic(array_to_string), 384;
@@ -1382,6 +1551,10 @@ lazy_static! {
                 params!(String, Timestamp) => BinaryFunc::DateTruncTimestamp, 2020;
                 params!(String, TimestampTz) => BinaryFunc::DateTruncTimestampTz, 1217;
             },
+            "daterange" => Scalar {
+                params!(Date, Date) => VariadicFunc::RangeCreate { elem_type: ScalarType::Date }, 3941;
+                params!(Date, Date, String) => VariadicFunc::RangeCreate { elem_type: ScalarType::Date }, 3942;
+            },
             "digest" => Scalar {
                 params!(String, String) => BinaryFunc::DigestString, 44154;
                 params!(Bytes, String) => BinaryFunc::DigestBytes, 44155;
@@ -1395,6 +1568,26 @@ lazy_static! {
                 params!(Float64) => UnaryFunc::FloorFloat64(func::FloorFloat64), 2309;
                 params!(Numeric) => UnaryFunc::FloorNumeric, 1712;
             },
+            "format" => Scalar {
+                params!(Any...) => Operation::variadic(|ecx, cexprs| {
+                    if cexprs.is_empty() {
+                        bail!("No function matches the given name and argument types. \
+                        You might need to add explicit type casts.")
+                    }
+                    let mut exprs = vec![];
+                    for expr in cexprs {
+                        exprs.push(match ecx.scalar_type(&expr) {
+                            // format, like concat, uses nonstandard bool ->
+                            // string casts to match PostgreSQL.
+                            ScalarType::Bool => expr.call_unary(UnaryFunc::CastBoolToStringNonstandard),
+                            // TODO(#7572): remove call to PadChar
+                            ScalarType::Char { length } => expr.call_unary(UnaryFunc::PadChar { length }),
+                            _ => typeconv::to_string(ecx, expr)
+                        });
+                    }
+                    Ok(HirScalarExpr::CallVariadic { func: VariadicFunc::Format, exprs })
+                }), 3540;
+            },
             "format_type" => Scalar {
                 params!(Oid, Int32) => sql_impl_func(
                     "CASE
@@ -1407,6 +1600,20 @@ lazy_static! {
                 params!(String, String, String) => VariadicFunc::HmacString, 44156;
                 params!(Bytes, Bytes, String) => VariadicFunc::HmacBytes, 44157;
             },
+            "initcap" => Scalar {
+                params!(String) => UnaryFunc::Initcap, 872;
+            },
+            "int4range" => Scalar {
+                params!(Int32, Int32) => VariadicFunc::RangeCreate { elem_type: ScalarType::Int32 }, 3840;
+                params!(Int32, Int32, String) => VariadicFunc::RangeCreate { elem_type: ScalarType::Int32 }, 3841;
+            },
+            "int8range" => Scalar {
+                params!(Int64, Int64) => VariadicFunc::RangeCreate { elem_type: ScalarType::Int64 }, 3945;
+                params!(Int64, Int64, String) => VariadicFunc::RangeCreate { elem_type: ScalarType::Int64 }, 3946;
+            },
+            "isempty" => Scalar {
+                params!(RangeAny) => UnaryFunc::RangeIsEmpty, 3850;
+            },
             "jsonb_array_length" => Scalar {
                 params!(Jsonb) => UnaryFunc::JsonbArrayLength, 3207;
             },
@@ -1466,6 +1673,10 @@ lazy_static! {
             },
             "lower" => Scalar {
                 params!(String) => UnaryFunc::Lower, 870;
+                params!(RangeAny) => Operation::unary(|ecx, e| {
+                    let return_ty = ecx.scalar_type(&e).unwrap_range_element_type().clone();
+                    Ok(e.call_unary(UnaryFunc::RangeLower { return_ty }))
+                }), 3848;
             },
             "lpad" => Scalar {
                 params!(String, Int64) => VariadicFunc::PadLeading, 879;
@@ -1487,6 +1698,10 @@ lazy_static! {
             "now" => Scalar {
                 params!() => Operation::nullary(|ecx| plan_current_timestamp(ecx, "now")), 1299;
             },
+            "numrange" => Scalar {
+                params!(Numeric, Numeric) => VariadicFunc::RangeCreate { elem_type: ScalarType::Numeric { scale: None } }, 3844;
+                params!(Numeric, Numeric, String) => VariadicFunc::RangeCreate { elem_type: ScalarType::Numeric { scale: None } }, 3845;
+            },
             "octet_length" => Scalar {
                 params!(Bytes) => UnaryFunc::ByteLengthBytes, 720;
                 params!(String) => UnaryFunc::ByteLengthString, 1374;
@@ -1587,6 +1802,14 @@ lazy_static! {
                 params!(String, String) => VariadicFunc::RegexpMatch, 3396;
                 params!(String, String, String) => VariadicFunc::RegexpMatch, 3397;
             },
+            "regexp_replace" => Scalar {
+                params!(String, String, String) => VariadicFunc::RegexpReplace, 2284;
+                params!(String, String, String, String) => VariadicFunc::RegexpReplace, 2285;
+            },
+            "regexp_split_to_array" => Scalar {
+                params!(String, String) => VariadicFunc::RegexpSplitToArray, 2767;
+                params!(String, String, String) => VariadicFunc::RegexpSplitToArray, 2768;
+            },
             "replace" => Scalar {
                 params!(String, String, String) => VariadicFunc::Replace, 2087;
             },
@@ -1612,6 +1835,9 @@ lazy_static! {
             "split_part" => Scalar {
                 params!(String, String, Int64) => VariadicFunc::SplitPart, 2088;
             },
+            "starts_with" => Scalar {
+                params!(String, String) => BinaryFunc::StartsWith, 3696;
+            },
             "stddev" => Scalar {
                 params!(Float32) => Operation::nullary(|_ecx| catalog_name_only!("stddev")), 2157;
                 params!(Float64) => Operation::nullary(|_ecx| catalog_name_only!("stddev")), 2158;
@@ -1633,6 +1859,10 @@ lazy_static! {
                 params!(Int32) => Operation::nullary(|_ecx| catalog_name_only!("stddev_samp")), 2713;
                 params!(Int64) => Operation::nullary(|_ecx| catalog_name_only!("stddev_samp")), 2712;
             },
+            "string_to_array" => Scalar {
+                params!(String, String) => VariadicFunc::StringToArray, 394;
+                params!(String, String, String) => VariadicFunc::StringToArray, 376;
+            },
             "substr" => Scalar {
                 params!(String, Int64) => VariadicFunc::Substr, 883;
                 params!(String, Int64, Int64) => VariadicFunc::Substr, 877;
@@ -1696,8 +1926,23 @@ lazy_static! {
             "to_timestamp" => Scalar {
                 params!(Float64) => UnaryFunc::ToTimestamp(func::ToTimestamp), 1158;
             },
+            "translate" => Scalar {
+                params!(String, String, String) => VariadicFunc::Translate, 878;
+            },
+            "tsrange" => Scalar {
+                params!(Timestamp, Timestamp) => VariadicFunc::RangeCreate { elem_type: ScalarType::Timestamp }, 3933;
+                params!(Timestamp, Timestamp, String) => VariadicFunc::RangeCreate { elem_type: ScalarType::Timestamp }, 3934;
+            },
+            "tstzrange" => Scalar {
+                params!(TimestampTz, TimestampTz) => VariadicFunc::RangeCreate { elem_type: ScalarType::TimestampTz }, 3937;
+                params!(TimestampTz, TimestampTz, String) => VariadicFunc::RangeCreate { elem_type: ScalarType::TimestampTz }, 3938;
+            },
             "upper" => Scalar {
                 params!(String) => UnaryFunc::Upper, 871;
+                params!(RangeAny) => Operation::unary(|ecx, e| {
+                    let return_ty = ecx.scalar_type(&e).unwrap_range_element_type().clone();
+                    Ok(e.call_unary(UnaryFunc::RangeUpper { return_ty }))
+                }), 3849;
             },
             "variance" => Scalar {
                 params!(Float32) => Operation::nullary(|_ecx| catalog_name_only!("variance")), 2151;
@@ -1744,7 +1989,17 @@ lazy_static! {
                     };
                     Ok((e_arr, AggregateFunc::ArrayConcat{order_by}))
                 }), 2335;
-                params!(ArrayAny) => Operation::unary(|_ecx, _e| bail_unsupported!("array_agg on arrays")), 4053;
+                params!(ArrayAny) => Operation::unary_ordered(|ecx, e, order_by| {
+                    // The input arrays are collected into a list, which
+                    // StackArrays turns into an array of one more dimension
+                    // once the aggregation is complete, erroring if the
+                    // arrays' dimensions differ.
+                    let e_list = HirScalarExpr::CallVariadic{
+                        func: VariadicFunc::ListCreate{elem_type: ecx.scalar_type(&e)},
+                        exprs: vec![e],
+                    };
+                    Ok((e_list, AggregateFunc::StackArrays{order_by}))
+                }), 4053;
             },
             "bool_and" => Aggregate {
                 params!(Any) => Operation::unary(|_ecx, _e| bail_unsupported!("bool_and")), 2517;
@@ -1863,6 +2118,57 @@ lazy_static! {
                 }), 2113;
             },
 
+            // Window functions.
+            "row_number" => Window {
+                params!() => Operation::nullary_ordered(|_ecx, order_by| {
+                    Ok((HirScalarExpr::literal_null(ScalarType::Bool), AggregateFunc::RowNumber { order_by }))
+                }), 3100;
+            },
+            "rank" => Window {
+                params!() => Operation::nullary_ordered(|_ecx, order_by| {
+                    Ok((HirScalarExpr::literal_null(ScalarType::Bool), AggregateFunc::Rank { order_by }))
+                }), 3101;
+            },
+            "dense_rank" => Window {
+                params!() => Operation::nullary_ordered(|_ecx, order_by| {
+                    Ok((HirScalarExpr::literal_null(ScalarType::Bool), AggregateFunc::DenseRank { order_by }))
+                }), 3102;
+            },
+            "lag" => Window {
+                params!(Any) => Operation::variadic_ordered(|ecx, exprs, order_by| {
+                    plan_lag_lead(ecx, LagLeadType::Lag, exprs, order_by)
+                }), 3106;
+                params!(Any, Int32) => Operation::variadic_ordered(|ecx, exprs, order_by| {
+                    plan_lag_lead(ecx, LagLeadType::Lag, exprs, order_by)
+                }), 3107;
+                params!(Any, Int32, Any) => Operation::variadic_ordered(|ecx, exprs, order_by| {
+                    plan_lag_lead(ecx, LagLeadType::Lag, exprs, order_by)
+                }), 3108;
+            },
+            "lead" => Window {
+                params!(Any) => Operation::variadic_ordered(|ecx, exprs, order_by| {
+                    plan_lag_lead(ecx, LagLeadType::Lead, exprs, order_by)
+                }), 3109;
+                params!(Any, Int32) => Operation::variadic_ordered(|ecx, exprs, order_by| {
+                    plan_lag_lead(ecx, LagLeadType::Lead, exprs, order_by)
+                }), 3110;
+                params!(Any, Int32, Any) => Operation::variadic_ordered(|ecx, exprs, order_by| {
+                    plan_lag_lead(ecx, LagLeadType::Lead, exprs, order_by)
+                }), 3111;
+            },
+            "first_value" => Window {
+                params!(Any) => Operation::unary_ordered(|_ecx, e, order_by| {
+                    // The window frame is filled in by the planner.
+                    Ok((e, AggregateFunc::FirstValue { order_by, window_frame: WindowFrame::default() }))
+                }), 3112;
+            },
+            "last_value" => Window {
+                params!(Any) => Operation::unary_ordered(|_ecx, e, order_by| {
+                    // The window frame is filled in by the planner.
+                    Ok((e, AggregateFunc::LastValue { order_by, window_frame: WindowFrame::default() }))
+                }), 3113;
+            },
+
             // Table functions.
             "generate_series" => Table {
                 params!(Int32, Int32) => Operation::binary(move |_ecx, start, stop| {
@@ -1943,6 +2249,22 @@ lazy_static! {
                     })
                 }), 3931;
             },
+            "regexp_split_to_table" => Table {
+                params!(String, String) => Operation::variadic(|_ecx, exprs| {
+                    Ok(TableFuncPlan {
+                        func: TableFunc::RegexpSplitToTable,
+                        exprs,
+                        column_names: vec![Some("regexp_split_to_table".into())],
+                    })
+                }), 2765;
+                params!(String, String, String) => Operation::variadic(|_ecx, exprs| {
+                    Ok(TableFuncPlan {
+                        func: TableFunc::RegexpSplitToTable,
+                        exprs,
+                        column_names: vec![Some("regexp_split_to_table".into())],
+                    })
+                }), 2766;
+            },
             "encode" => Scalar {
                 params!(Bytes, String) => BinaryFunc::Encode, 1946;
             },
@@ -2211,8 +2533,12 @@ fn array_to_string(
         ScalarType::Array(elem_type) => *elem_type,
         _ => unreachable!("array_to_string is guaranteed to receive array as first argument"),
     };
+    let enum_labels = typeconv::plan_enum_labels(ecx.catalog(), iter::once(&elem_type))?;
     Ok(HirScalarExpr::CallVariadic {
-        func: VariadicFunc::ArrayToString { elem_type },
+        func: VariadicFunc::ArrayToString {
+            elem_type,
+            enum_labels,
+        },
         exprs,
     })
 }
@@ -2490,6 +2816,9 @@ lazy_static! {
                 params!(ListAny, ListAny) => ListListConcat, oid::OP_CONCAT_LIST_LIST_OID;
                 params!(ListAny, ListElementAny) => ListElementConcat, oid::OP_CONCAT_LIST_ELEMENT_OID;
                 params!(ListElementAny, ListAny) => ElementListConcat, oid::OP_CONCAT_ELEMENY_LIST_OID;
+                params!(ArrayAny, ArrayAny) => ArrayArrayConcat, 375;
+                params!(ArrayAny, ArrayElementAny) => ArrayElementConcat, 349;
+                params!(ArrayElementAny, ArrayAny) => ElementArrayConcat, 374;
             },
 
             //JSON and MAP
@@ -2522,6 +2851,9 @@ lazy_static! {
                           .call_binary(rhs, JsonbContainsJsonb))
                 }), oid::OP_CONTAINS_STRING_JSONB_OID;
                 params!(MapAny, MapAny) => MapContainsMap, oid::OP_CONTAINS_MAP_MAP_OID;
+                params!(RangeAny, RangeAny) => RangeContainsRange, 3890;
+                params!(RangeAny, RangeElementAny) => RangeContainsElem, 3889;
+                params!(ArrayAny, ArrayAny) => ArrayContainsArray, 2751;
             },
             "<@" => Scalar {
                 params!(Jsonb, Jsonb) =>  Operation::binary(|_ecx, lhs, rhs| {
@@ -2543,6 +2875,19 @@ lazy_static! {
                 params!(MapAny, MapAny) => Operation::binary(|_ecx, lhs, rhs| {
                     Ok(rhs.call_binary(lhs, MapContainsMap))
                 }), oid::OP_CONTAINED_MAP_MAP_OID;
+                params!(RangeAny, RangeAny) => Operation::binary(|_ecx, lhs, rhs| {
+                    Ok(rhs.call_binary(lhs, RangeContainsRange))
+                }), 3892;
+                params!(RangeElementAny, RangeAny) => Operation::binary(|_ecx, lhs, rhs| {
+                    Ok(rhs.call_binary(lhs, RangeContainsElem))
+                }), 3891;
+                params!(ArrayAny, ArrayAny) => Operation::binary(|_ecx, lhs, rhs| {
+                    Ok(rhs.call_binary(lhs, ArrayContainsArray))
+                }), 2752;
+            },
+            "&&" => Scalar {
+                params!(RangeAny, RangeAny) => RangeOverlaps, 3888;
+                params!(ArrayAny, ArrayAny) => ArrayOverlaps, 2750;
             },
             "?" => Scalar {
                 params!(Jsonb, String) => JsonbContainsString, 3247;
@@ -2575,6 +2920,8 @@ lazy_static! {
                 params!(Char, Char) => BinaryFunc::Lt, 1058;
                 params!(Jsonb, Jsonb) => BinaryFunc::Lt, 3242;
                 params!(ArrayAny, ArrayAny) => BinaryFunc::Lt, 1072;
+                params!(EnumAny, EnumAny) => BinaryFunc::Lt, 3518;
+                params!(RangeAny, RangeAny) => BinaryFunc::Lt, 3884;
             },
             "<=" => Scalar {
                 params!(Numeric, Numeric) => BinaryFunc::Lte, 1755;
@@ -2596,6 +2943,8 @@ lazy_static! {
                 params!(Char, Char) => BinaryFunc::Lte, 1059;
                 params!(Jsonb, Jsonb) => BinaryFunc::Lte, 3244;
                 params!(ArrayAny, ArrayAny) => BinaryFunc::Lte, 1074;
+                params!(EnumAny, EnumAny) => BinaryFunc::Lte, 3520;
+                params!(RangeAny, RangeAny) => BinaryFunc::Lte, 3885;
             },
             ">" => Scalar {
                 params!(Numeric, Numeric) => BinaryFunc::Gt, 1756;
@@ -2617,6 +2966,8 @@ lazy_static! {
                 params!(Char, Char) => BinaryFunc::Gt, 1060;
                 params!(Jsonb, Jsonb) => BinaryFunc::Gt, 3243;
                 params!(ArrayAny, ArrayAny) => BinaryFunc::Gt, 1073;
+                params!(EnumAny, EnumAny) => BinaryFunc::Gt, 3519;
+                params!(RangeAny, RangeAny) => BinaryFunc::Gt, 3887;
             },
             ">=" => Scalar {
                 params!(Numeric, Numeric) => BinaryFunc::Gte, 1757;
@@ -2638,6 +2989,8 @@ lazy_static! {
                 params!(Char, Char) => BinaryFunc::Gte, 1061;
                 params!(Jsonb, Jsonb) => BinaryFunc::Gte, 3245;
                 params!(ArrayAny, ArrayAny) => BinaryFunc::Gte, 1075;
+                params!(EnumAny, EnumAny) => BinaryFunc::Gte, 3521;
+                params!(RangeAny, RangeAny) => BinaryFunc::Gte, 3886;
             },
             // Warning! If you are writing functions here that do not simply use
             // `BinaryFunc::Eq`, you will break row equality (used e.g. DISTINCT
@@ -2663,6 +3016,8 @@ lazy_static! {
                 params!(Jsonb, Jsonb) => BinaryFunc::Eq, 3240;
                 params!(ListAny, ListAny) => BinaryFunc::Eq, oid::FUNC_LIST_EQ_OID;
                 params!(ArrayAny, ArrayAny) => BinaryFunc::Eq, 1070;
+                params!(EnumAny, EnumAny) => BinaryFunc::Eq, 3516;
+                params!(RangeAny, RangeAny) => BinaryFunc::Eq, 3882;
             },
             "<>" => Scalar {
                 params!(Numeric, Numeric) => BinaryFunc::NotEq, 1753;
@@ -2684,6 +3039,8 @@ lazy_static! {
                 params!(Char, Char) => BinaryFunc::NotEq, 1057;
                 params!(Jsonb, Jsonb) => BinaryFunc::NotEq, 3241;
                 params!(ArrayAny, ArrayAny) => BinaryFunc::NotEq, 1071;
+                params!(EnumAny, EnumAny) => BinaryFunc::NotEq, 3517;
+                params!(RangeAny, RangeAny) => BinaryFunc::NotEq, 3883;
             }
         }
     };
@@ -83,6 +83,7 @@ macro_rules! bail_unsupported {
 }
 
 pub mod ast;
+pub mod auth;
 pub mod catalog;
 pub mod func;
 pub mod kafka_util;
@@ -267,6 +267,7 @@ pub fn create_statement(
             with_options: _,
             format: _,
             key_envelope: _,
+            include_metadata: _,
             envelope: _,
             if_not_exists,
             materialized,
@@ -38,6 +38,7 @@ use dataflow_types::{SinkConnectorBuilder, SinkEnvelope, SourceConnector};
 use repr::{ColumnName, Diff, RelationDesc, Row, ScalarType, Timestamp};
 
 use crate::ast::{ExplainOptions, ExplainStage, Expr, FetchDirection, ObjectType, Raw, Statement};
+use crate::auth::ScramSha256Verifier;
 use crate::names::{DatabaseSpecifier, FullName, SchemaName};
 
 pub(crate) mod error;
@@ -100,6 +101,8 @@ pub enum Plan {
     AlterIndexResetOptions(AlterIndexResetOptionsPlan),
     AlterIndexEnable(AlterIndexEnablePlan),
     AlterItemRename(AlterItemRenamePlan),
+    AlterTable(AlterTablePlan),
+    AlterRole(AlterRolePlan),
     Declare(DeclarePlan),
     Fetch(FetchPlan),
     Close(ClosePlan),
@@ -125,6 +128,8 @@ pub struct CreateSchemaPlan {
 #[derive(Debug)]
 pub struct CreateRolePlan {
     pub name: String,
+    /// The verifier for the role's password, if any.
+    pub password: Option<ScramSha256Verifier>,
 }
 
 #[derive(Debug)]
@@ -265,6 +270,34 @@ pub struct SendDiffsPlan {
 pub struct InsertPlan {
     pub id: GlobalId,
     pub values: ::expr::MirRelationExpr,
+    pub on_conflict: Option<OnConflictPlan>,
+}
+
+/// How to handle inserted rows that conflict with existing rows in a table.
+#[derive(Debug)]
+pub struct OnConflictPlan {
+    /// The column indices of the unique constraints whose keys identify
+    /// conflicting rows.
+    pub keys: Vec<Vec<usize>>,
+    /// The action to take for conflicting rows.
+    pub action: ConflictAction,
+}
+
+#[derive(Debug)]
+pub enum ConflictAction {
+    /// Skip the conflicting inserted row.
+    DoNothing,
+    /// Update the existing row.
+    ///
+    /// The expressions are evaluated over the columns of the existing row
+    /// followed by the columns of the inserted row.
+    DoUpdate {
+        /// Map from column index to SET expression.
+        assignments: HashMap<usize, ::expr::MirScalarExpr>,
+        /// The condition under which to update the existing row. If the
+        /// condition is not met, the inserted row is skipped.
+        selection: Option<::expr::MirScalarExpr>,
+    },
 }
 
 #[derive(Debug)]
@@ -299,6 +332,13 @@ pub struct AlterIndexEnablePlan {
     pub id: GlobalId,
 }
 
+#[derive(Debug)]
+pub struct AlterRolePlan {
+    pub name: String,
+    /// The verifier for the role's new password. `None` removes the password.
+    pub password: Option<ScramSha256Verifier>,
+}
+
 #[derive(Debug)]
 pub struct AlterItemRenamePlan {
     pub id: GlobalId,
@@ -306,6 +346,24 @@ pub struct AlterItemRenamePlan {
     pub object_type: ObjectType,
 }
 
+#[derive(Debug)]
+pub struct AlterTablePlan {
+    pub id: GlobalId,
+    /// The new definition of the table.
+    pub table: Table,
+    pub rewrite: AlterTableRewrite,
+}
+
+/// Describes how to rewrite the existing rows of a table altered by an
+/// [`AlterTablePlan`].
+#[derive(Debug)]
+pub enum AlterTableRewrite {
+    /// Append the result of evaluating `default` to each row.
+    AddColumn { default: ::expr::MirScalarExpr },
+    /// Remove the column at `index` from each row.
+    DropColumn { index: usize },
+}
+
 #[derive(Debug)]
 pub struct DeclarePlan {
     pub name: String,
@@ -347,10 +405,33 @@ pub struct Table {
     pub create_sql: String,
     pub desc: RelationDesc,
     pub defaults: Vec<Expr<Raw>>,
+    pub unique_constraints: Vec<UniqueConstraint>,
+    pub check_constraints: Vec<CheckConstraint>,
     pub temporary: bool,
     pub depends_on: Vec<GlobalId>,
 }
 
+/// A `PRIMARY KEY` or `UNIQUE` constraint on a table.
+#[derive(Clone, Debug)]
+pub struct UniqueConstraint {
+    /// The name of the constraint.
+    pub name: String,
+    /// The indices of the constrained columns.
+    pub columns: Vec<usize>,
+    /// Whether the constraint is the table's primary key.
+    pub is_primary: bool,
+}
+
+/// A `CHECK` constraint on a table.
+#[derive(Clone, Debug)]
+pub struct CheckConstraint {
+    /// The name of the constraint.
+    pub name: String,
+    /// The boolean expression that every row in the table must not violate,
+    /// in terms of the table's columns.
+    pub expr: ::expr::MirScalarExpr,
+}
+
 #[derive(Clone, Debug)]
 pub struct Source {
     pub create_sql: String,
@@ -402,6 +483,12 @@ pub enum TypeInner {
         key_id: GlobalId,
         value_id: GlobalId,
     },
+    Enum {
+        labels: Vec<String>,
+    },
+    Record {
+        fields: Vec<(ColumnName, GlobalId)>,
+    },
 }
 
 /// Specifies when a `Peek` should occur.
@@ -433,6 +520,13 @@ pub struct CopyParams {
     pub format: CopyFormat,
     pub null: Option<String>,
     pub delimiter: Option<String>,
+    /// The quote character. Only valid for [`CopyFormat::Csv`].
+    pub quote: Option<String>,
+    /// The escape character. Only valid for [`CopyFormat::Csv`].
+    pub escape: Option<String>,
+    /// Whether the first line is a header that should be ignored. Only valid
+    /// for [`CopyFormat::Csv`].
+    pub header: bool,
 }
 
 #[derive(Debug, Copy, Clone)]
@@ -27,6 +27,10 @@ pub enum PlanError {
     OverqualifiedSchemaName(String),
     Catalog(CatalogError),
     UpsertSinkWithoutKey,
+    InvalidRecursiveReference {
+        name: String,
+        context: &'static str,
+    },
 }
 
 impl fmt::Display for PlanError {
@@ -58,6 +62,12 @@ impl fmt::Display for PlanError {
             ),
             Self::Catalog(e) => write!(f, "{}", e),
             Self::UpsertSinkWithoutKey => write!(f, "upsert sinks must specify a key"),
+            Self::InvalidRecursiveReference { name, context } => write!(
+                f,
+                "recursive reference to query {} must not appear {}",
+                name.quoted(),
+                context
+            ),
         }
     }
 }
@@ -149,6 +149,12 @@ impl<'a> Explanation<'a> {
                         explanation.chain = id_gen.allocate_id();
                     }
                 }
+                LetRec { value, body, .. } => {
+                    walk(value, explanation, id_gen);
+                    explanation.chain = id_gen.allocate_id();
+                    walk(body, explanation, id_gen);
+                    explanation.chain = id_gen.allocate_id();
+                }
             }
 
             // Then collect subqueries.
@@ -162,6 +168,7 @@ impl<'a> Explanation<'a> {
                 | Threshold { .. }
                 | Union { .. }
                 | DeclareKeys { .. }
+                | LetRec { .. }
                 | TopK { .. } => (),
                 Map { scalars: exprs, .. }
                 | Filter {
@@ -253,9 +260,7 @@ impl<'a> Explanation<'a> {
                 writeln!(f)?;
             }
             Get { id, .. } => match id {
-                Id::Local(_) => {
-                    unreachable!("SQL expressions do not support Lets yet")
-                }
+                Id::Local(id) => writeln!(f, "| Get {}", id)?,
                 Id::LocalBareSource => writeln!(f, "| Get Local Bare Source")?,
                 Id::Global(id) => writeln!(
                     f,
@@ -368,6 +373,13 @@ impl<'a> Explanation<'a> {
                         .map(|input| bracketed("%", "", self.expr_chain(input)))
                 )
             )?,
+            LetRec { id, value, body } => writeln!(
+                f,
+                "| LetRec {} = %{} in %{}",
+                id,
+                self.expr_chain(value),
+                self.expr_chain(body)
+            )?,
         }
 
         if let Some(RelationType { column_types, keys }) = &node.typ {
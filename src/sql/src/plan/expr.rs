@@ -29,7 +29,10 @@ use crate::plan::typeconv::{self, CastContext};
 use crate::plan::Params;
 
 // these happen to be unchanged at the moment, but there might be additions later
-pub use expr::{BinaryFunc, ColumnOrder, NullaryFunc, TableFunc, UnaryFunc, VariadicFunc};
+pub use expr::{
+    BinaryFunc, ColumnOrder, LagLeadType, NullaryFunc, TableFunc, UnaryFunc, VariadicFunc,
+    WindowFrame, WindowFrameBound, WindowFrameUnits,
+};
 use repr::adt::array::ArrayDimension;
 
 use super::Explanation;
@@ -37,7 +40,9 @@ use super::Explanation;
 #[derive(Debug, Clone, PartialEq, Eq)]
 /// Just like MirRelationExpr, except where otherwise noted below.
 ///
-/// - There is no equivalent to `MirRelationExpr::Let`.
+/// - There is no equivalent to `MirRelationExpr::Let`, though there is an
+///   equivalent to `MirRelationExpr::LetRec`, which is used to plan recursive
+///   CTEs.
 pub enum HirRelationExpr {
     Constant {
         rows: Vec<Row>,
@@ -110,6 +115,16 @@ pub enum HirRelationExpr {
         input: Box<HirRelationExpr>,
         keys: Vec<Vec<usize>>,
     },
+    /// Binds `value` to `id` in both `value` and `body`. The value of `id` is
+    /// the least fixed point of `value`.
+    ///
+    /// Unlike all other relation expressions, references to `id` are only
+    /// permitted outside of subqueries, and `value` must be uncorrelated.
+    LetRec {
+        id: expr::LocalId,
+        value: Box<HirRelationExpr>,
+        body: Box<HirRelationExpr>,
+    },
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -414,9 +429,46 @@ pub enum AggregateFunc {
     ListConcat {
         order_by: Vec<ColumnOrder>,
     },
+    /// Accumulates `Datum::List`s whose first element is a `Datum::List` of
+    /// `Datum::Array`s into a single `Datum::List`, like `ListConcat`. The
+    /// result must then be stacked into a single `Datum::Array` with
+    /// `UnaryFunc::StackArrays`, which validates the arrays' dimensions. See
+    /// `AggregateFunc::finisher`.
+    StackArrays {
+        order_by: Vec<ColumnOrder>,
+    },
     StringAgg {
         order_by: Vec<ColumnOrder>,
     },
+    /// The window functions below produce a `Datum::List` of records, each of
+    /// which pairs an input row with the value computed for that row. See
+    /// `expr::AggregateFunc` for the format of their inputs.
+    RowNumber {
+        order_by: Vec<ColumnOrder>,
+    },
+    Rank {
+        order_by: Vec<ColumnOrder>,
+    },
+    DenseRank {
+        order_by: Vec<ColumnOrder>,
+    },
+    LagLead {
+        order_by: Vec<ColumnOrder>,
+        lag_lead: LagLeadType,
+    },
+    FirstValue {
+        order_by: Vec<ColumnOrder>,
+        window_frame: WindowFrame,
+    },
+    LastValue {
+        order_by: Vec<ColumnOrder>,
+        window_frame: WindowFrame,
+    },
+    WindowAggregate {
+        wrapped_aggregate: Box<AggregateFunc>,
+        order_by: Vec<ColumnOrder>,
+        window_frame: WindowFrame,
+    },
     /// Accumulates any number of `Datum::Dummy`s into `Datum::Dummy`.
     ///
     /// Useful for removing an expensive aggregation while maintaining the shape
@@ -466,8 +518,39 @@ impl AggregateFunc {
             AggregateFunc::ArrayConcat { order_by } => {
                 expr::AggregateFunc::ArrayConcat { order_by }
             }
-            AggregateFunc::ListConcat { order_by } => expr::AggregateFunc::ListConcat { order_by },
+            AggregateFunc::ListConcat { order_by } | AggregateFunc::StackArrays { order_by } => {
+                expr::AggregateFunc::ListConcat { order_by }
+            }
             AggregateFunc::StringAgg { order_by } => expr::AggregateFunc::StringAgg { order_by },
+            AggregateFunc::RowNumber { order_by } => expr::AggregateFunc::RowNumber { order_by },
+            AggregateFunc::Rank { order_by } => expr::AggregateFunc::Rank { order_by },
+            AggregateFunc::DenseRank { order_by } => expr::AggregateFunc::DenseRank { order_by },
+            AggregateFunc::LagLead { order_by, lag_lead } => {
+                expr::AggregateFunc::LagLead { order_by, lag_lead }
+            }
+            AggregateFunc::FirstValue {
+                order_by,
+                window_frame,
+            } => expr::AggregateFunc::FirstValue {
+                order_by,
+                window_frame,
+            },
+            AggregateFunc::LastValue {
+                order_by,
+                window_frame,
+            } => expr::AggregateFunc::LastValue {
+                order_by,
+                window_frame,
+            },
+            AggregateFunc::WindowAggregate {
+                wrapped_aggregate,
+                order_by,
+                window_frame,
+            } => expr::AggregateFunc::WindowAggregate {
+                wrapped_aggregate: Box::new(wrapped_aggregate.into_expr()),
+                order_by,
+                window_frame,
+            },
             AggregateFunc::Dummy => expr::AggregateFunc::Dummy,
         }
     }
@@ -480,11 +563,32 @@ impl AggregateFunc {
             AggregateFunc::All => Datum::True,
             AggregateFunc::Dummy => Datum::Dummy,
             AggregateFunc::ArrayConcat { .. } => Datum::empty_array(),
-            AggregateFunc::ListConcat { .. } => Datum::empty_list(),
+            AggregateFunc::ListConcat { .. } | AggregateFunc::StackArrays { .. } => {
+                Datum::empty_list()
+            }
             _ => Datum::Null,
         }
     }
 
+    /// Returns the function, if any, that must be applied to the result of the
+    /// aggregation to produce the value of the aggregate function.
+    ///
+    /// Aggregations cannot report errors, so aggregate functions that can fail
+    /// defer their validation to this function.
+    pub fn finisher(&self, input_type: ColumnType) -> Option<UnaryFunc> {
+        match self {
+            AggregateFunc::StackArrays { .. } => {
+                let return_ty = self
+                    .output_type(input_type)
+                    .scalar_type
+                    .unwrap_list_element_type()
+                    .clone();
+                Some(UnaryFunc::StackArrays { return_ty })
+            }
+            _ => None,
+        }
+    }
+
     /// The output column type for the result of an aggregation.
     ///
     /// The output column type also contains nullability information, which
@@ -500,13 +604,24 @@ impl AggregateFunc {
             AggregateFunc::StringAgg { .. } => ScalarType::String,
             AggregateFunc::SumInt16 | AggregateFunc::SumInt32 => ScalarType::Int64,
             AggregateFunc::SumInt64 => ScalarType::Numeric { scale: Some(0) },
-            AggregateFunc::ArrayConcat { .. } | AggregateFunc::ListConcat { .. } => {
+            AggregateFunc::ArrayConcat { .. }
+            | AggregateFunc::ListConcat { .. }
+            | AggregateFunc::StackArrays { .. } => {
                 match input_type.scalar_type {
                     // The input is wrapped in a Record if there's an ORDER BY, so extract it out.
                     ScalarType::Record { fields, .. } => fields[0].1.scalar_type.clone(),
                     _ => unreachable!(),
                 }
             }
+            AggregateFunc::RowNumber { .. }
+            | AggregateFunc::Rank { .. }
+            | AggregateFunc::DenseRank { .. }
+            | AggregateFunc::LagLead { .. }
+            | AggregateFunc::FirstValue { .. }
+            | AggregateFunc::LastValue { .. }
+            | AggregateFunc::WindowAggregate { .. } => {
+                self.clone().into_expr().output_type(input_type).scalar_type
+            }
             _ => input_type.scalar_type,
         };
         // max/min/sum return null on empty sets
@@ -522,6 +637,7 @@ impl AggregateFunc {
                 | JsonbObjectAgg { .. }
                 | ArrayConcat { .. }
                 | ListConcat { .. }
+                | StackArrays { .. }
                 | StringAgg { .. }
         )
     }
@@ -619,6 +735,7 @@ impl HirRelationExpr {
             HirRelationExpr::DeclareKeys { input, keys } => {
                 input.typ(outers, params).with_keys(keys.clone())
             }
+            HirRelationExpr::LetRec { body, .. } => body.typ(outers, params),
         }
     }
 
@@ -637,6 +754,7 @@ impl HirRelationExpr {
             | HirRelationExpr::Threshold { input } => input.arity(),
             HirRelationExpr::Join { left, right, .. } => left.arity() + right.arity(),
             HirRelationExpr::Union { base, .. } => base.arity(),
+            HirRelationExpr::LetRec { body, .. } => body.arity(),
             HirRelationExpr::Reduce {
                 group_key,
                 aggregates,
@@ -657,6 +775,37 @@ impl HirRelationExpr {
         }
     }
 
+    /// Reports whether this expression contains a `LetRec`, including within
+    /// any subqueries.
+    pub fn is_recursive(&self) -> bool {
+        fn scalar_is_recursive(expr: &HirScalarExpr) -> bool {
+            let mut found = false;
+            expr.visit(&mut |expr| match expr {
+                HirScalarExpr::Exists(input) | HirScalarExpr::Select(input) => {
+                    found |= input.is_recursive()
+                }
+                _ => (),
+            });
+            found
+        }
+
+        let mut found = false;
+        self.visit(&mut |expr| match expr {
+            HirRelationExpr::LetRec { .. } => found = true,
+            HirRelationExpr::Map { scalars: exprs, .. }
+            | HirRelationExpr::CallTable { exprs, .. }
+            | HirRelationExpr::Filter {
+                predicates: exprs, ..
+            } => found |= exprs.iter().any(scalar_is_recursive),
+            HirRelationExpr::Join { on, .. } => found |= scalar_is_recursive(on),
+            HirRelationExpr::Reduce { aggregates, .. } => {
+                found |= aggregates.iter().any(|a| scalar_is_recursive(&a.expr))
+            }
+            _ => (),
+        });
+        found
+    }
+
     pub fn project(self, outputs: Vec<usize>) -> Self {
         if outputs.iter().copied().eq(0..self.arity()) {
             // The projection is trivial. Suppress it.
@@ -833,6 +982,10 @@ impl HirRelationExpr {
                     f(input);
                 }
             }
+            HirRelationExpr::LetRec { value, body, .. } => {
+                f(value);
+                f(body);
+            }
         }
     }
 
@@ -889,6 +1042,10 @@ impl HirRelationExpr {
                     f(input);
                 }
             }
+            HirRelationExpr::LetRec { value, body, .. } => {
+                f(value);
+                f(body);
+            }
         }
     }
 
@@ -944,6 +1101,10 @@ impl HirRelationExpr {
                     input.visit_columns(depth, f);
                 }
             }
+            HirRelationExpr::LetRec { value, body, .. } => {
+                value.visit_columns(depth, f);
+                body.visit_columns(depth, f);
+            }
             HirRelationExpr::Project { input, .. }
             | HirRelationExpr::Distinct { input }
             | HirRelationExpr::TopK { input, .. }
@@ -999,6 +1160,10 @@ impl HirRelationExpr {
                 }
                 base.bind_parameters(params)
             }
+            HirRelationExpr::LetRec { value, body, .. } => {
+                value.bind_parameters(params)?;
+                body.bind_parameters(params)
+            }
             HirRelationExpr::Project { input, .. }
             | HirRelationExpr::Distinct { input, .. }
             | HirRelationExpr::TopK { input, .. }
@@ -1054,6 +1219,10 @@ impl HirRelationExpr {
                     input.splice_parameters(params, depth);
                 }
             }
+            HirRelationExpr::LetRec { value, body, .. } => {
+                value.splice_parameters(params, depth);
+                body.splice_parameters(params, depth);
+            }
             HirRelationExpr::Project { input, .. }
             | HirRelationExpr::Distinct { input }
             | HirRelationExpr::TopK { input, .. }
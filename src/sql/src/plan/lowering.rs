@@ -483,6 +483,41 @@ impl HirRelationExpr {
             DeclareKeys { input, keys } => input
                 .applied_to(id_gen, get_outer, col_map)
                 .declare_keys(keys),
+            LetRec {
+                id,
+                mut value,
+                mut body,
+            } => {
+                // The planner guarantees that `value` is uncorrelated and that
+                // references to `id` only appear outside of subqueries, so we
+                // can rename them to a fresh identifier without descending
+                // into scalar expressions, and evaluate `value` only once,
+                // rather than once per row of `get_outer`.
+                let new_id = expr::LocalId::new(id_gen.allocate_id());
+                for e in vec![&mut value, &mut body] {
+                    e.visit_mut(&mut |e| {
+                        if let Get {
+                            id: expr::Id::Local(get_id),
+                            ..
+                        } = e
+                        {
+                            if *get_id == id {
+                                *get_id = new_id;
+                            }
+                        }
+                    });
+                }
+                let value =
+                    expr::MirRelationExpr::constant(vec![vec![]], RelationType::new(vec![]))
+                        .let_in(id_gen, |id_gen, get_unit| {
+                            value.applied_to(id_gen, get_unit, &ColumnMap::empty())
+                        });
+                SR::LetRec {
+                    id: new_id,
+                    value: Box::new(value),
+                    body: Box::new(body.applied_to(id_gen, get_outer, col_map)),
+                }
+            }
         }
     }
 }
@@ -36,9 +36,9 @@ use sql_parser::ast::fold::Fold;
 use sql_parser::ast::visit::{self, Visit};
 use sql_parser::ast::{
     Assignment, AstInfo, Cte, DataType, Distinct, Expr, Function, FunctionArgs, Ident,
-    InsertSource, IsExprConstruct, JoinConstraint, JoinOperator, Limit, OrderByExpr, Query, Raw,
-    RawName, Select, SelectItem, SetExpr, SetOperator, Statement, TableAlias, TableFactor,
-    TableWithJoins, UnresolvedObjectName, Value, Values,
+    InsertSource, IsExprConstruct, JoinConstraint, JoinOperator, Limit, OnConflict,
+    OnConflictAction, OrderByExpr, Query, Raw, RawName, Select, SelectItem, SetExpr, SetOperator,
+    Statement, TableAlias, TableFactor, TableWithJoins, UnresolvedObjectName, Value, Values,
 };
 
 use ::expr::{GlobalId, Id, RowSetFinishing};
@@ -48,14 +48,15 @@ use repr::{
     Timestamp,
 };
 
-use crate::catalog::{CatalogItemType, SessionCatalog};
+use crate::catalog::{CatalogItem, CatalogItemType, SessionCatalog};
 use crate::func::{self, Func, FuncSpec};
 use crate::names::PartialName;
 use crate::normalize;
 use crate::plan::error::PlanError;
 use crate::plan::expr::{
-    AbstractColumnType, AbstractExpr, AggregateExpr, BinaryFunc, CoercibleScalarExpr, ColumnOrder,
-    ColumnRef, HirRelationExpr, HirScalarExpr, JoinKind, UnaryFunc, VariadicFunc,
+    AbstractColumnType, AbstractExpr, AggregateExpr, AggregateFunc, BinaryFunc,
+    CoercibleScalarExpr, ColumnOrder, ColumnRef, HirRelationExpr, HirScalarExpr, JoinKind,
+    TableFunc, UnaryFunc, VariadicFunc, WindowFrame, WindowFrameBound, WindowFrameUnits,
 };
 use crate::plan::scope::{Scope, ScopeItem, ScopeItemName};
 use crate::plan::statement::{StatementContext, StatementDesc};
@@ -145,16 +146,27 @@ impl<'a> Fold<Raw, Aug> for NameResolver<'a> {
             used_names.insert(cte_name.clone());
 
             let id = LocalId::new(self.ctes.len() as u64);
+            // The queries of recursive CTEs can refer to themselves, so the
+            // name must be in scope before we fold the query.
+            let query = if q.recursive {
+                let old_val = self.ctes.insert(cte_name.clone(), id);
+                old_cte_values.push((cte_name, old_val));
+                self.fold_query(cte.query)
+            } else {
+                let query = self.fold_query(cte.query);
+                let old_val = self.ctes.insert(cte_name.clone(), id);
+                old_cte_values.push((cte_name, old_val));
+                query
+            };
             ctes.push(Cte {
                 alias: cte.alias,
                 id: Id::Local(id),
-                query: self.fold_query(cte.query),
+                query,
             });
-            let old_val = self.ctes.insert(cte_name.clone(), id);
-            old_cte_values.push((cte_name, old_val));
         }
         let result = Query {
             ctes,
+            recursive: q.recursive,
             body: self.fold_set_expr(q.body),
             limit: q.limit.map(|l| self.fold_limit(l)),
             offset: q.offset.map(|l| self.fold_expr(l)),
@@ -400,12 +412,24 @@ fn try_push_projection_order_by(
     }
 }
 
+/// How to handle the rows of an `INSERT` that conflict with existing rows.
+pub struct OnConflictPlan {
+    /// The column indices of the unique constraints that identify conflicts.
+    pub keys: Vec<Vec<usize>>,
+    /// Map from column index to SET expression, over the existing row followed
+    /// by the inserted row. None for `DO NOTHING`.
+    pub assignments: Option<HashMap<usize, HirScalarExpr>>,
+    /// WHERE filter for `DO UPDATE`.
+    pub selection: Option<HirScalarExpr>,
+}
+
 pub fn plan_insert_query(
     scx: &StatementContext,
     table_name: UnresolvedObjectName,
     columns: Vec<Ident>,
     source: InsertSource<Raw>,
-) -> Result<(GlobalId, HirRelationExpr), anyhow::Error> {
+    on_conflict: Option<OnConflict<Raw>>,
+) -> Result<(GlobalId, HirRelationExpr, Option<OnConflictPlan>), anyhow::Error> {
     let mut qcx = QueryContext::root(scx, QueryLifetime::OneShot(scx.pcx()?));
     let table = scx.resolve_item(table_name)?;
 
@@ -475,6 +499,7 @@ pub fn plan_insert_query(
                 Query {
                     body: SetExpr::Values(values),
                     ctes,
+                    recursive: _,
                     order_by,
                     limit: None,
                     offset: None,
@@ -546,7 +571,146 @@ pub fn plan_insert_query(
         }
     }
 
-    Ok((table.id(), expr.map(map_exprs).project(project_key)))
+    let on_conflict = match on_conflict {
+        Some(on_conflict) => Some(plan_on_conflict(&mut qcx, table, on_conflict)?),
+        None => None,
+    };
+
+    Ok((
+        table.id(),
+        expr.map(map_exprs).project(project_key),
+        on_conflict,
+    ))
+}
+
+fn plan_on_conflict(
+    qcx: &mut QueryContext,
+    table: &dyn CatalogItem,
+    OnConflict { columns, action }: OnConflict<Raw>,
+) -> Result<OnConflictPlan, anyhow::Error> {
+    let scx = qcx.scx;
+    let desc = table.desc()?;
+    let constraints = table
+        .table_unique_constraints()
+        .expect("attempted to insert into non-table");
+
+    // Determine the unique constraints that identify conflicting rows. As in
+    // PostgreSQL, the conflict target must name exactly the columns of one of
+    // the table's unique constraints, and omitting the conflict target is only
+    // permitted for `DO NOTHING`, where it means any unique constraint.
+    let keys = if columns.is_empty() {
+        if let OnConflictAction::DoUpdate { .. } = action {
+            bail!("ON CONFLICT DO UPDATE requires inference specification or constraint name");
+        }
+        constraints.iter().map(|c| c.columns.clone()).collect()
+    } else {
+        let mut target = Vec::with_capacity(columns.len());
+        for c in columns {
+            let name = normalize::column_name(c);
+            match desc.get_by_name(&name) {
+                Some((idx, _)) => target.push(idx),
+                None => bail!("column {} does not exist", name.as_str().quoted()),
+            }
+        }
+        target.sort_unstable();
+        target.dedup();
+        let constraint = constraints.iter().find(|c| {
+            let mut columns = c.columns.clone();
+            columns.sort_unstable();
+            columns == target
+        });
+        match constraint {
+            Some(constraint) => vec![constraint.columns.clone()],
+            None => bail!(
+                "there is no unique or exclusion constraint matching the ON CONFLICT specification"
+            ),
+        }
+    };
+
+    let (assignments, selection) = match action {
+        OnConflictAction::DoNothing => (None, None),
+        OnConflictAction::DoUpdate {
+            assignments,
+            selection,
+        } => {
+            // The SET and WHERE clauses can refer to the existing row by the
+            // table's name and to the proposed row by the name `excluded`.
+            let scope = Scope::from_source(
+                Some(PartialName::from(table.name().clone())),
+                desc.iter_names(),
+                None,
+            )
+            .product(Scope::from_source(
+                Some(PartialName {
+                    database: None,
+                    schema: None,
+                    item: "excluded".into(),
+                }),
+                desc.iter_names(),
+                None,
+            ));
+            let relation_type = RelationType::new(
+                desc.iter_types()
+                    .chain(desc.iter_types())
+                    .cloned()
+                    .collect(),
+            );
+
+            let mut sets = HashMap::new();
+            for Assignment { id, mut value } in assignments {
+                let name = normalize::column_name(id);
+                match desc.get_by_name(&name) {
+                    Some((idx, typ)) => {
+                        transform_ast::transform_expr(scx, &mut value)?;
+                        let expr = resolve_names_expr(qcx, value)?;
+                        let ecx = &ExprContext {
+                            qcx,
+                            name: "SET clause",
+                            scope: &scope,
+                            relation_type: &relation_type,
+                            allow_aggregates: false,
+                            allow_subqueries: false,
+                        };
+                        let expr = plan_expr(&ecx, &expr)?.cast_to(
+                            "SET clause",
+                            ecx,
+                            CastContext::Assignment,
+                            &typ.scalar_type,
+                        )?;
+                        if sets.insert(idx, expr).is_some() {
+                            bail!("column {} set twice", name)
+                        }
+                    }
+                    None => bail!("unknown column {}", name),
+                }
+            }
+
+            let selection = match selection {
+                Some(mut expr) => {
+                    transform_ast::transform_expr(scx, &mut expr)?;
+                    let expr = resolve_names_expr(qcx, expr)?;
+                    let ecx = &ExprContext {
+                        qcx,
+                        name: "WHERE clause",
+                        scope: &scope,
+                        relation_type: &relation_type,
+                        allow_aggregates: false,
+                        allow_subqueries: false,
+                    };
+                    Some(plan_expr(&ecx, &expr)?.type_as(&ecx, &ScalarType::Bool)?)
+                }
+                None => None,
+            };
+
+            (Some(sets), selection)
+        }
+    };
+
+    Ok(OnConflictPlan {
+        keys,
+        assignments,
+        selection,
+    })
 }
 
 pub fn plan_copy_from(
@@ -628,7 +792,7 @@ pub fn plan_copy_from_rows(
         .table_details()
         .expect("attempted to insert into non-table");
 
-    let column_types = columns
+    let column_types: Vec<_> = columns
         .iter()
         .map(|x| desc.typ().column_types[*x].clone())
         .map(|mut x| {
@@ -638,6 +802,26 @@ pub fn plan_copy_from_rows(
             x
         })
         .collect();
+
+    // Enum values are decoded to their bare labels, which can only be resolved
+    // with access to the catalog.
+    let rows = rows
+        .into_iter()
+        .map(|row| {
+            let arena = RowArena::new();
+            let mut datums = vec![];
+            for (datum, typ) in row.iter().zip(&column_types) {
+                datums.push(typeconv::resolve_enum_labels(
+                    catalog,
+                    datum,
+                    &typ.scalar_type,
+                    &arena,
+                )?);
+            }
+            Ok(Row::pack(datums))
+        })
+        .collect::<Result<Vec<_>, anyhow::Error>>()?;
+
     let typ = RelationType::new(column_types);
     let expr = HirRelationExpr::Constant {
         rows,
@@ -994,6 +1178,35 @@ pub fn plan_index_exprs<'a>(
     Ok((out, qcx.ids.into_iter().collect()))
 }
 
+/// Plans the expression of a `CHECK` constraint on a table with the
+/// description `desc`.
+pub fn plan_check_expr(
+    scx: &StatementContext,
+    desc: &RelationDesc,
+    expr: &Expr<Raw>,
+) -> Result<(::expr::MirScalarExpr, Vec<GlobalId>), anyhow::Error> {
+    let scope = Scope::from_source(None, desc.iter_names(), Some(Scope::empty(None)));
+    let mut qcx = QueryContext::root(scx, QueryLifetime::Static);
+    let mut expr = expr.clone();
+    transform_ast::transform_expr(scx, &mut expr)?;
+    let expr = resolve_names_expr(&mut qcx, expr)?;
+    let ecx = &ExprContext {
+        qcx: &qcx,
+        name: "CHECK constraint",
+        scope: &scope,
+        relation_type: desc.typ(),
+        allow_aggregates: false,
+        allow_subqueries: false,
+    };
+    let expr = plan_expr(ecx, &expr)?
+        .type_as(ecx, &ScalarType::Bool)?
+        .lower_uncorrelated()?;
+    if expr.contains_temporal() {
+        bail!("CHECK constraint cannot contain calls to mz_logical_timestamp");
+    }
+    Ok((expr, qcx.ids.into_iter().collect()))
+}
+
 fn plan_expr_or_col_index(
     ecx: &ExprContext,
     e: &Expr<Aug>,
@@ -1044,30 +1257,48 @@ fn plan_query(
         }
         used_names.insert(cte_name.clone());
 
+        let id = match cte.id {
+            Id::Local(id) => id,
+            _ => unreachable!(),
+        };
+
         // Plan CTE.
-        let (val, scope) = plan_subquery(qcx, &cte.query)?;
-        let typ = qcx.relation_type(&val);
-        let mut val_desc = RelationDesc::new(typ, scope.column_names());
-        val_desc = plan_utils::maybe_rename_columns(
-            format!("CTE {}", cte.alias.name),
-            val_desc,
-            &cte.alias.columns,
-        )?;
+        let (val, val_desc) = if q.recursive && is_self_referential(cte) {
+            plan_recursive_cte(qcx, cte, id)?
+        } else {
+            let (val, scope) = plan_subquery(qcx, &cte.query)?;
+            let typ = qcx.relation_type(&val);
+            let val_desc = plan_utils::maybe_rename_columns(
+                format!("CTE {}", cte.alias.name),
+                RelationDesc::new(typ, scope.column_names()),
+                &cte.alias.columns,
+            )?;
+            (val, val_desc)
+        };
 
-        match cte.id {
-            Id::Local(id) => {
-                let old_val = qcx.ctes.insert(
-                    id,
-                    CteDesc {
-                        val,
-                        val_desc,
-                        level_offset: 0,
-                    },
-                );
-                old_cte_values.push((cte_name, old_val));
+        // A CTE defined within the recursive term of a recursive CTE may
+        // itself refer to the recursive CTE, in which case it is subject to
+        // the same restrictions.
+        let mut self_reference = false;
+        val.visit(&mut |expr| {
+            if let HirRelationExpr::Get {
+                id: Id::Local(id), ..
+            } = expr
+            {
+                self_reference |= qcx.ctes.get(id).map_or(false, |cte| cte.self_reference);
             }
-            _ => unreachable!(),
-        }
+        });
+
+        let old_val = qcx.ctes.insert(
+            id,
+            CteDesc {
+                val,
+                val_desc,
+                level_offset: 0,
+                self_reference,
+            },
+        );
+        old_cte_values.push((cte_name, old_val));
     }
     let limit = match &q.limit {
         None => None,
@@ -1125,6 +1356,311 @@ fn plan_query(
     result
 }
 
+/// Reports whether the query of `cte` refers to `cte` itself.
+fn is_self_referential(cte: &Cte<Aug>) -> bool {
+    struct SelfReferenceVisitor {
+        id: Id,
+        found: bool,
+    }
+
+    impl<'ast> Visit<'ast, Aug> for SelfReferenceVisitor {
+        fn visit_object_name(&mut self, name: &'ast ResolvedObjectName) {
+            self.found |= name.id == self.id;
+        }
+    }
+
+    let mut visitor = SelfReferenceVisitor {
+        id: cte.id,
+        found: false,
+    };
+    visitor.visit_query(&cte.query);
+    visitor.found
+}
+
+/// Finds references to a recursive CTE within its recursive term that would
+/// make the term non-monotonic in the CTE.
+struct RecursiveReferenceVisitor<'a> {
+    scx: &'a StatementContext<'a>,
+    id: Id,
+    name: String,
+    /// The construct that the visitor is currently within, in which a
+    /// reference to the CTE is not allowed.
+    context: Option<&'static str>,
+    references: usize,
+    err: Option<PlanError>,
+}
+
+impl<'a> RecursiveReferenceVisitor<'a> {
+    fn visit_within<F>(&mut self, context: &'static str, f: F)
+    where
+        F: FnOnce(&mut Self),
+    {
+        let outer = self.context.replace(context);
+        f(self);
+        self.context = outer;
+    }
+}
+
+impl<'a, 'ast> Visit<'ast, Aug> for RecursiveReferenceVisitor<'a> {
+    fn visit_object_name(&mut self, name: &'ast ResolvedObjectName) {
+        if name.id != self.id || self.err.is_some() {
+            return;
+        }
+        self.references += 1;
+        let context = if self.references > 1 {
+            Some("more than once")
+        } else {
+            self.context
+        };
+        if let Some(context) = context {
+            self.err = Some(PlanError::InvalidRecursiveReference {
+                name: self.name.clone(),
+                context,
+            });
+        }
+    }
+
+    fn visit_set_expr(&mut self, set_expr: &'ast SetExpr<Aug>) {
+        match set_expr {
+            SetExpr::SetOperation {
+                op: SetOperator::Except,
+                ..
+            } => self.visit_within("within EXCEPT", |v| visit::visit_set_expr(v, set_expr)),
+            _ => visit::visit_set_expr(self, set_expr),
+        }
+    }
+
+    fn visit_query(&mut self, query: &'ast Query<Aug>) {
+        if query.limit.is_some() || query.offset.is_some() {
+            self.visit_within("within a query with LIMIT or OFFSET", |v| {
+                visit::visit_query(v, query)
+            });
+        } else {
+            visit::visit_query(self, query);
+        }
+    }
+
+    fn visit_expr(&mut self, expr: &'ast Expr<Aug>) {
+        match expr {
+            // As in PostgreSQL, subqueries in expressions are rejected
+            // wholesale, as many of them, like `NOT EXISTS` and `NOT IN`, are
+            // not monotonic.
+            Expr::Exists(_)
+            | Expr::Subquery(_)
+            | Expr::InSubquery { .. }
+            | Expr::AnySubquery { .. }
+            | Expr::AllSubquery { .. } => {
+                self.visit_within("within a subquery", |v| visit::visit_expr(v, expr))
+            }
+            _ => visit::visit_expr(self, expr),
+        }
+    }
+
+    fn visit_select(&mut self, select: &'ast Select<Aug>) {
+        let mut aggregate_visitor = AggregateFuncVisitor::new(self.scx);
+        aggregate_visitor.visit_select(select);
+        let has_aggregates = match aggregate_visitor.into_result() {
+            Ok(aggs) => !aggs.is_empty(),
+            // Reported when the select is planned.
+            Err(_) => false,
+        };
+        let mut window_visitor = WindowFuncVisitor::new();
+        for item in &select.projection {
+            window_visitor.visit_select_item(item);
+        }
+        let has_window_funcs = match window_visitor.into_result() {
+            Ok(funcs) => !funcs.is_empty(),
+            // Reported when the select is planned.
+            Err(_) => false,
+        };
+        if has_aggregates || !select.group_by.is_empty() || select.having.is_some() {
+            self.visit_within("within an aggregation", |v| visit::visit_select(v, select));
+        } else if has_window_funcs {
+            self.visit_within("within a query with window functions", |v| {
+                visit::visit_select(v, select)
+            });
+        } else if let Some(Distinct::On(_)) = select.distinct {
+            self.visit_within("within a query with DISTINCT ON", |v| {
+                visit::visit_select(v, select)
+            });
+        } else {
+            visit::visit_select(self, select);
+        }
+    }
+
+    fn visit_table_with_joins(&mut self, table: &'ast TableWithJoins<Aug>) {
+        // A relation is on the nullable side of an outer join if it is the
+        // right input of a left or full join, or precedes a right or full
+        // join.
+        let nullable_from_right = |i: usize| {
+            table.joins[i..]
+                .iter()
+                .any(|join| match join.join_operator {
+                    JoinOperator::RightOuter(_) | JoinOperator::FullOuter(_) => true,
+                    _ => false,
+                })
+        };
+        if nullable_from_right(0) {
+            self.visit_within("within an outer join", |v| {
+                v.visit_table_factor(&table.relation)
+            });
+        } else {
+            self.visit_table_factor(&table.relation);
+        }
+        for (i, join) in table.joins.iter().enumerate() {
+            let nullable = match join.join_operator {
+                JoinOperator::LeftOuter(_) | JoinOperator::FullOuter(_) => true,
+                _ => nullable_from_right(i + 1),
+            };
+            if nullable {
+                self.visit_within("within an outer join", |v| v.visit_join(join));
+            } else {
+                self.visit_join(join);
+            }
+        }
+    }
+}
+
+/// Plans a CTE introduced by `WITH RECURSIVE` whose query refers to itself.
+///
+/// The query must have the form `base UNION [ALL] recursive`, where only the
+/// recursive term may refer to the CTE. The CTE evaluates to the least fixed
+/// point of the query: starting from the rows of the base term, the recursive
+/// term is repeatedly evaluated against the rows produced so far until no new
+/// rows are produced.
+fn plan_recursive_cte(
+    qcx: &mut QueryContext,
+    cte: &Cte<Aug>,
+    id: LocalId,
+) -> Result<(HirRelationExpr, RelationDesc), anyhow::Error> {
+    let name = normalize::ident(cte.alias.name.clone());
+    let (base, recursive, all) = match &cte.query {
+        Query {
+            ctes,
+            body:
+                SetExpr::SetOperation {
+                    op: SetOperator::Union,
+                    all,
+                    left,
+                    right,
+                },
+            order_by,
+            limit: None,
+            offset: None,
+            ..
+        } if ctes.is_empty() && order_by.is_empty() => (left, right, *all),
+        _ => bail!(
+            "recursive query {} does not have the form non-recursive-term UNION [ALL] recursive-term",
+            name.quoted()
+        ),
+    };
+    if qcx.ctes.values().any(|cte| cte.self_reference) {
+        bail_unsupported!("nested WITH RECURSIVE");
+    }
+
+    // Plan the non-recursive term without the CTE in scope, so that any
+    // reference to the CTE is reported as an error.
+    qcx.ctes.remove(&id);
+    let (base, base_scope) = plan_set_expr(qcx, base)?;
+
+    // As in PostgreSQL, the types of the CTE's columns are determined by the
+    // non-recursive term alone. The recursive term may introduce NULLs, so
+    // every column is assumed to be nullable.
+    let base_types = qcx.relation_type(&base).column_types;
+    let typ = RelationType::new(
+        base_types
+            .iter()
+            .map(|t| t.clone().nullable(true))
+            .collect(),
+    );
+    let val_desc = plan_utils::maybe_rename_columns(
+        format!("CTE {}", cte.alias.name),
+        RelationDesc::new(typ.clone(), base_scope.column_names()),
+        &cte.alias.columns,
+    )?;
+
+    // The fixed point is computed incrementally, which is only correct if the
+    // recursive term is monotonic in the CTE, so the recursive term may refer
+    // to the CTE at most once and only where more input rows can only produce
+    // more output rows.
+    let mut visitor = RecursiveReferenceVisitor {
+        scx: qcx.scx,
+        id: cte.id,
+        name: name.clone(),
+        context: None,
+        references: 0,
+        err: None,
+    };
+    visitor.visit_set_expr(recursive);
+    if let Some(err) = visitor.err {
+        return Err(err.into());
+    }
+
+    // Within the recursive term, the CTE refers to the rows produced so far.
+    qcx.ctes.insert(
+        id,
+        CteDesc {
+            val: HirRelationExpr::Get {
+                id: Id::Local(id),
+                typ: typ.clone(),
+            },
+            val_desc: val_desc.clone(),
+            level_offset: 0,
+            self_reference: true,
+        },
+    );
+    let recursive = plan_set_expr(qcx, recursive);
+    qcx.ctes.remove(&id);
+    let (recursive, _recursive_scope) = recursive?;
+
+    let recursive_types = qcx.relation_type(&recursive).column_types;
+    if base_types.len() != recursive_types.len() {
+        bail!(
+            "each UNION query must have the same number of columns: {} vs {}",
+            base_types.len(),
+            recursive_types.len(),
+        );
+    }
+    for (i, (base_type, recursive_type)) in base_types.iter().zip(&recursive_types).enumerate() {
+        if base_type.union(recursive_type).is_err() {
+            bail!(
+                "recursive query {} column {} has type {} in non-recursive term but type {} overall",
+                name.quoted(),
+                i + 1,
+                qcx.humanize_scalar_type(&base_type.scalar_type),
+                qcx.humanize_scalar_type(&recursive_type.scalar_type),
+            );
+        }
+    }
+
+    let mut value = if all {
+        base.union(recursive)
+    } else {
+        base.union(recursive).distinct()
+    };
+
+    // The recursive CTE is evaluated once, in its own iterative scope, so it
+    // cannot depend on the rows of an outer query.
+    let mut correlated = false;
+    value.visit_columns(0, &mut |depth, col| correlated |= col.level > depth);
+    if correlated {
+        bail_unsupported!("correlated WITH RECURSIVE");
+    }
+    if value.is_recursive() {
+        bail_unsupported!("nested WITH RECURSIVE");
+    }
+
+    let val = HirRelationExpr::LetRec {
+        id,
+        value: Box::new(value),
+        body: Box::new(HirRelationExpr::Get {
+            id: Id::Local(id),
+            typ,
+        }),
+    };
+    Ok((val, val_desc))
+}
+
 fn plan_subquery(
     qcx: &mut QueryContext,
     q: &Query<Aug>,
@@ -1414,7 +1950,19 @@ fn plan_view_select(
         aggregate_visitor.into_result()?
     };
 
-    // Step 4. Expand SELECT clause.
+    // Step 4. Gather window functions.
+    let window_funcs = {
+        let mut window_visitor = WindowFuncVisitor::new();
+        for si in projection {
+            window_visitor.visit_select_item(si);
+        }
+        for o in order_by_exprs {
+            window_visitor.visit_order_by_expr(o);
+        }
+        window_visitor.into_result()?
+    };
+
+    // Step 5. Expand SELECT clause.
     let projection = {
         let ecx = &ExprContext {
             qcx,
@@ -1434,8 +1982,8 @@ fn plan_view_select(
         out
     };
 
-    // Step 5. Handle GROUP BY clause.
-    let (group_scope, select_all_mapping) = {
+    // Step 6. Handle GROUP BY clause.
+    let (mut group_scope, select_all_mapping) = {
         // Compute GROUP BY expressions.
         let ecx = &ExprContext {
             qcx,
@@ -1506,11 +2054,30 @@ fn plan_view_select(
             });
         }
         if !agg_exprs.is_empty() || !group_key.is_empty() || having.is_some() {
+            // Some aggregate functions need a scalar function applied to the
+            // result of their aggregation, which replaces the aggregation's
+            // column.
+            let arity = group_key.len() + agg_exprs.len();
+            let mut finishers = vec![];
+            let mut outputs: Vec<_> = (0..arity).collect();
+            for (i, agg) in agg_exprs.iter().enumerate() {
+                if let Some(func) = agg.func.finisher(ecx.column_type(&*agg.expr)) {
+                    let column = group_key.len() + i;
+                    outputs[column] = arity + finishers.len();
+                    finishers.push(
+                        HirScalarExpr::Column(ColumnRef { level: 0, column }).call_unary(func),
+                    );
+                }
+            }
+
             // apply GROUP BY / aggregates
             relation_expr =
                 relation_expr
                     .map(group_exprs)
                     .reduce(group_key, agg_exprs, expected_group_size);
+            if !finishers.is_empty() {
+                relation_expr = relation_expr.map(finishers).project(outputs);
+            }
             (group_scope, select_all_mapping)
         } else {
             // if no GROUP BY, aggregates or having then all columns remain in scope
@@ -1521,7 +2088,7 @@ fn plan_view_select(
         }
     };
 
-    // Step 6. Handle HAVING clause.
+    // Step 7. Handle HAVING clause.
     if let Some(having) = having {
         let ecx = &ExprContext {
             qcx,
@@ -1535,7 +2102,29 @@ fn plan_view_select(
         relation_expr = relation_expr.filter(vec![expr]);
     }
 
-    // Step 7. Handle SELECT clause.
+    // Step 8. Handle window functions.
+    //
+    // Each window function is appended to the relation as a new column, and
+    // recorded in the scope so that references to it in the SELECT and ORDER
+    // BY clauses resolve to that column.
+    for window_func in window_funcs {
+        let ecx = &ExprContext {
+            qcx,
+            name: "window function",
+            scope: &group_scope,
+            relation_type: &qcx.relation_type(&relation_expr),
+            allow_aggregates: true,
+            allow_subqueries: true,
+        };
+        relation_expr = plan_window_function(ecx, relation_expr, window_func)?;
+        group_scope.items.push(ScopeItem {
+            names: vec![],
+            expr: Some(Expr::Function(window_func.clone())),
+            nameable: true,
+        });
+    }
+
+    // Step 9. Handle SELECT clause.
     let (mut project_key, map_scope) = {
         let mut new_exprs = vec![];
         let mut project_key = vec![];
@@ -1591,7 +2180,7 @@ fn plan_view_select(
         (project_key, map_scope)
     };
 
-    // Step 8. Handle intrusive ORDER BY and DISTINCT.
+    // Step 10. Handle intrusive ORDER BY and DISTINCT.
     let order_by = {
         let (mut order_by, mut map_exprs) = plan_projected_order_by_exprs(
             &ExprContext {
@@ -2635,13 +3224,13 @@ pub fn plan_expr<'a>(
                 0,
                 "subscript expression must contain at least one position"
             );
-            if positions.len() > 1 {
-                ecx.require_experimental_mode("layered/multidimensional slicing")?;
-            }
             let expr = plan_expr(ecx, expr)?.type_as_any(ecx)?;
             let ty = ecx.scalar_type(&expr);
-            match &ty {
+            let func = match &ty {
                 ScalarType::List { .. } => {
+                    if positions.len() > 1 {
+                        ecx.require_experimental_mode("layered/multidimensional slicing")?;
+                    }
                     let pos_len = positions.len();
                     let n_dims = ty.unwrap_list_n_dims();
                     if pos_len > n_dims {
@@ -2652,7 +3241,9 @@ pub fn plan_expr<'a>(
                             if n_dims == 1 { "" } else { "s" }
                         )
                     }
+                    VariadicFunc::ListSlice
                 }
+                ScalarType::Array(_) => VariadicFunc::ArraySlice,
                 ty => bail!("cannot subscript type {}", ecx.humanize_scalar_type(&ty)),
             };
 
@@ -2686,11 +3277,7 @@ pub fn plan_expr<'a>(
                 exprs.push(end);
             }
 
-            HirScalarExpr::CallVariadic {
-                func: VariadicFunc::ListSlice,
-                exprs,
-            }
-            .into()
+            HirScalarExpr::CallVariadic { func, exprs }.into()
         }
 
         // Subqueries.
@@ -2893,10 +3480,6 @@ fn plan_aggregate(
         _ => unreachable!("plan_aggregate called on non-aggregate function,"),
     };
 
-    if sql_func.over.is_some() {
-        bail_unsupported!(213, "window functions");
-    }
-
     let name = normalize::unresolved_object_name(sql_func.name.clone())?;
 
     // We follow PostgreSQL's rule here for mapping `count(*)` into the
@@ -2998,6 +3581,234 @@ fn plan_aggregate(
     })
 }
 
+/// Plans a window function, i.e., a function call with an `OVER` clause, and
+/// appends its result to `input` as a new column.
+///
+/// Window functions are planned as aggregations over each window partition.
+/// The aggregate receives, for each input row, a record containing the row
+/// itself, the function's argument, and the keys by which the partition is
+/// ordered. It produces a list that pairs each row with its computed value,
+/// which is then unnested to restore the original rows. Roughly, the plan is:
+///
+///     SELECT input.*, value
+///     FROM (
+///         SELECT <partition exprs>, <window func>((input, <arg>, <order keys>))
+///         FROM input GROUP BY <partition exprs>
+///     ), unnest(<result>) AS (input, value)
+fn plan_window_function(
+    ecx: &ExprContext,
+    input: HirRelationExpr,
+    sql_func: &Function<Aug>,
+) -> Result<HirRelationExpr, anyhow::Error> {
+    let window_spec = sql_func.over.as_ref().expect("window function has OVER");
+    let name = normalize::unresolved_object_name(sql_func.name.clone())?;
+    if sql_func.distinct {
+        bail_unsupported!("DISTINCT in window functions");
+    }
+    if let FunctionArgs::Args { order_by, .. } = &sql_func.args {
+        if !order_by.is_empty() {
+            bail_unsupported!("ORDER BY in window function arguments");
+        }
+    }
+
+    let mut partition_exprs = vec![];
+    for expr in &window_spec.partition_by {
+        partition_exprs.push(plan_expr(ecx, expr)?.type_as_any(ecx)?);
+    }
+    let mut order_exprs = vec![];
+    let mut order_by = vec![];
+    for (i, obe) in window_spec.order_by.iter().enumerate() {
+        order_exprs.push(plan_expr(ecx, &obe.expr)?.type_as_any(ecx)?);
+        order_by.push(ColumnOrder {
+            column: i,
+            desc: !obe.asc.unwrap_or(true),
+        });
+    }
+    let window_frame = plan_window_frame(&window_spec.window_frame)?;
+
+    let (arg, func) = match resolve_func(ecx, &sql_func.name, &sql_func.args)? {
+        Func::Window(impls) => {
+            if sql_func.filter.is_some() {
+                bail!(
+                    "FILTER specified, but {} is not an aggregate function",
+                    name
+                );
+            }
+            let args = match &sql_func.args {
+                FunctionArgs::Star => {
+                    bail!("* argument is invalid with non-aggregate function {}", name)
+                }
+                FunctionArgs::Args { args, .. } => plan_exprs(ecx, args)?,
+            };
+            let (arg, func) = func::select_impl(ecx, FuncSpec::Func(&name), impls, args, order_by)?;
+            let func = match func {
+                AggregateFunc::FirstValue { order_by, .. } => AggregateFunc::FirstValue {
+                    order_by,
+                    window_frame,
+                },
+                AggregateFunc::LastValue { order_by, .. } => AggregateFunc::LastValue {
+                    order_by,
+                    window_frame,
+                },
+                func => func,
+            };
+            (arg, func)
+        }
+        Func::Aggregate(_) => {
+            let agg = plan_aggregate(ecx, sql_func)?;
+            if agg.func.finisher(ecx.column_type(&*agg.expr)).is_some() {
+                bail_unsupported!(format!("{} as a window function", name));
+            }
+            let func = AggregateFunc::WindowAggregate {
+                wrapped_aggregate: Box::new(agg.func),
+                order_by,
+                window_frame,
+            };
+            (*agg.expr, func)
+        }
+        _ => bail!(
+            "OVER specified, but {} is not a window function nor an aggregate function",
+            name
+        ),
+    };
+
+    // Build the input record to the window function: the row and the
+    // argument, followed by the keys to order by.
+    let arity = ecx.relation_type.arity();
+    let row = HirScalarExpr::CallVariadic {
+        func: VariadicFunc::RecordCreate {
+            field_names: iter::repeat(ColumnName::from("")).take(arity).collect(),
+        },
+        exprs: (0..arity)
+            .map(|column| HirScalarExpr::Column(ColumnRef { level: 0, column }))
+            .collect(),
+    };
+    let row_arg = HirScalarExpr::CallVariadic {
+        func: VariadicFunc::RecordCreate {
+            field_names: vec![ColumnName::from("?row?"), ColumnName::from("?arg?")],
+        },
+        exprs: vec![row, arg],
+    };
+    let mut exprs = vec![row_arg];
+    exprs.extend(order_exprs);
+    let agg_input = HirScalarExpr::CallVariadic {
+        func: VariadicFunc::RecordCreate {
+            field_names: iter::repeat(ColumnName::from(""))
+                .take(exprs.len())
+                .collect(),
+        },
+        exprs,
+    };
+    let el_typ = func
+        .output_type(ecx.column_type(&agg_input))
+        .scalar_type
+        .unwrap_list_element_type()
+        .clone();
+
+    // Compute the window function over each partition, then unnest the
+    // resulting list to recover the input rows alongside their values.
+    let partition_arity = partition_exprs.len();
+    let reduced = input.map(partition_exprs).reduce(
+        (arity..arity + partition_arity).collect(),
+        vec![AggregateExpr {
+            func,
+            expr: Box::new(agg_input),
+            distinct: false,
+        }],
+        None,
+    );
+    let unnested = HirRelationExpr::Join {
+        left: Box::new(reduced),
+        right: Box::new(HirRelationExpr::CallTable {
+            func: TableFunc::UnnestList { el_typ },
+            exprs: vec![HirScalarExpr::Column(ColumnRef {
+                level: 1,
+                column: partition_arity,
+            })],
+        }),
+        on: HirScalarExpr::literal_true(),
+        kind: JoinKind::Inner { lateral: true },
+    };
+    let record = HirScalarExpr::Column(ColumnRef {
+        level: 0,
+        column: partition_arity + 1,
+    });
+    let mut map_exprs: Vec<_> = (0..arity)
+        .map(|i| {
+            record
+                .clone()
+                .call_unary(UnaryFunc::RecordGet(0))
+                .call_unary(UnaryFunc::RecordGet(i))
+        })
+        .collect();
+    map_exprs.push(record.call_unary(UnaryFunc::RecordGet(1)));
+    let start = partition_arity + 2;
+    Ok(unnested
+        .map(map_exprs)
+        .project((start..start + arity + 1).collect()))
+}
+
+/// Plans the frame clause of a window specification.
+///
+/// As in PostgreSQL, the absence of a frame clause is equivalent to
+/// `RANGE BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW`.
+fn plan_window_frame(
+    window_frame: &Option<sql_parser::ast::WindowFrame>,
+) -> Result<WindowFrame, anyhow::Error> {
+    use sql_parser::ast::WindowFrameBound as AstBound;
+    use sql_parser::ast::WindowFrameUnits as AstUnits;
+
+    let window_frame = match window_frame {
+        None => return Ok(WindowFrame::default()),
+        Some(window_frame) => window_frame,
+    };
+    let units = match window_frame.units {
+        AstUnits::Rows => WindowFrameUnits::Rows,
+        AstUnits::Range => WindowFrameUnits::Range,
+        AstUnits::Groups => bail_unsupported!("GROUPS window frames"),
+    };
+    let plan_bound = |bound: &AstBound| -> Result<WindowFrameBound, anyhow::Error> {
+        Ok(match bound {
+            AstBound::CurrentRow => WindowFrameBound::CurrentRow,
+            AstBound::Preceding(None) => WindowFrameBound::UnboundedPreceding,
+            AstBound::Following(None) => WindowFrameBound::UnboundedFollowing,
+            AstBound::Preceding(Some(_)) | AstBound::Following(Some(_))
+                if units == WindowFrameUnits::Range =>
+            {
+                bail_unsupported!("RANGE window frames with offsets")
+            }
+            AstBound::Preceding(Some(n)) => WindowFrameBound::OffsetPreceding(*n),
+            AstBound::Following(Some(n)) => WindowFrameBound::OffsetFollowing(*n),
+        })
+    };
+    let start_bound = plan_bound(&window_frame.start_bound)?;
+    let end_bound = match &window_frame.end_bound {
+        None => WindowFrameBound::CurrentRow,
+        Some(end_bound) => plan_bound(end_bound)?,
+    };
+    match (&start_bound, &end_bound) {
+        (WindowFrameBound::UnboundedFollowing, _) => {
+            bail!("frame start cannot be UNBOUNDED FOLLOWING")
+        }
+        (_, WindowFrameBound::UnboundedPreceding) => {
+            bail!("frame end cannot be UNBOUNDED PRECEDING")
+        }
+        (WindowFrameBound::CurrentRow, WindowFrameBound::OffsetPreceding(_)) => {
+            bail!("frame starting from current row cannot have preceding rows")
+        }
+        (WindowFrameBound::OffsetFollowing(_), WindowFrameBound::OffsetPreceding(_))
+        | (WindowFrameBound::OffsetFollowing(_), WindowFrameBound::CurrentRow) => {
+            bail!("frame starting from following row cannot have preceding rows")
+        }
+        _ => (),
+    }
+    Ok(WindowFrame {
+        units,
+        start_bound,
+        end_bound,
+    })
+}
+
 fn plan_identifier(ecx: &ExprContext, names: &[Ident]) -> Result<HirScalarExpr, PlanError> {
     let mut names = names.to_vec();
     let col_name = normalize::column_name(names.pop().unwrap());
@@ -3080,6 +3891,13 @@ fn plan_function<'a>(
         distinct,
     }: &'a Function<Aug>,
 ) -> Result<HirScalarExpr, anyhow::Error> {
+    if over.is_some() {
+        // Window functions in permitted positions are planned in advance by
+        // `plan_view_select`, and so are caught by `scope.resolve_expr` in
+        // `plan_expr`.
+        bail!("window functions are not allowed in {}", ecx.name);
+    }
+
     let impls = match resolve_func(ecx, name, args)? {
         Func::Aggregate(_) if ecx.allow_aggregates => {
             // should already have been caught by `scope.resolve_expr` in `plan_expr`
@@ -3097,12 +3915,12 @@ fn plan_function<'a>(
                 format!("table function ({}) in scalar position", name)
             );
         }
+        Func::Window(_) => {
+            bail!("window function {} requires an OVER clause", name);
+        }
         Func::Scalar(impls) => impls,
     };
 
-    if over.is_some() {
-        bail_unsupported!(213, "window functions");
-    }
     if *distinct {
         bail!(
             "DISTINCT specified, but {} is not an aggregate function",
@@ -3448,6 +4266,10 @@ pub fn scalar_type_from_pg(ty: &pgrepr::Type) -> Result<ScalarType, anyhow::Erro
             value_type: Box::new(scalar_type_from_pg(value_type)?),
             custom_oid: None,
         }),
+        pgrepr::Type::Range { element_type } => Ok(ScalarType::Range {
+            element_type: Box::new(scalar_type_from_pg(element_type)?),
+        }),
+        pgrepr::Type::Enum { oid } => Ok(ScalarType::Enum { custom_oid: *oid }),
     }
 }
 
@@ -3489,6 +4311,19 @@ impl<'a, 'ast> AggregateFuncVisitor<'a, 'ast> {
 
 impl<'a, 'ast> Visit<'ast, Aug> for AggregateFuncVisitor<'a, 'ast> {
     fn visit_function(&mut self, func: &'ast Function<Aug>) {
+        if func.over.is_some() {
+            // Window functions are planned separately, after aggregation, but
+            // their arguments may themselves contain aggregates.
+            if self.within_aggregate {
+                self.err = Some(anyhow!(
+                    "aggregate function calls cannot contain window function calls"
+                ));
+                return;
+            }
+            visit::visit_function(self, func);
+            return;
+        }
+
         let item = match self.scx.resolve_function(func.name.clone()) {
             Ok(i) => i,
             // Catching missing functions later in planning improves error messages.
@@ -3526,6 +4361,62 @@ impl<'a, 'ast> Visit<'ast, Aug> for AggregateFuncVisitor<'a, 'ast> {
     }
 }
 
+/// This is used to collect window functions, i.e., function calls with an
+/// `OVER` clause, from within an `Expr`.
+struct WindowFuncVisitor<'ast> {
+    funcs: Vec<&'ast Function<Aug>>,
+    within_window_func: bool,
+    err: Option<anyhow::Error>,
+}
+
+impl<'ast> WindowFuncVisitor<'ast> {
+    fn new() -> WindowFuncVisitor<'ast> {
+        WindowFuncVisitor {
+            funcs: Vec::new(),
+            within_window_func: false,
+            err: None,
+        }
+    }
+
+    fn into_result(self) -> Result<Vec<&'ast Function<Aug>>, anyhow::Error> {
+        match self.err {
+            Some(err) => Err(err),
+            None => {
+                // dedup window functions while preserving the order, as with
+                // aggregates
+                let mut seen = HashSet::new();
+                Ok(self
+                    .funcs
+                    .into_iter()
+                    .filter(move |func| seen.insert(&**func))
+                    .collect())
+            }
+        }
+    }
+}
+
+impl<'ast> Visit<'ast, Aug> for WindowFuncVisitor<'ast> {
+    fn visit_function(&mut self, func: &'ast Function<Aug>) {
+        if func.over.is_some() {
+            if self.within_window_func {
+                self.err = Some(anyhow!("window function calls cannot be nested"));
+                return;
+            }
+            self.funcs.push(func);
+            let old_within_window_func = self.within_window_func;
+            self.within_window_func = true;
+            visit::visit_function(self, func);
+            self.within_window_func = old_within_window_func;
+            return;
+        }
+        visit::visit_function(self, func);
+    }
+
+    fn visit_query(&mut self, _query: &'ast Query<Aug>) {
+        // Don't go into subqueries.
+    }
+}
+
 /// Specifies how long a query will live. This impacts whether the query is
 /// allowed to reason about the time at which it is running, e.g., by calling
 /// the `now()` function.
@@ -3548,6 +4439,9 @@ pub struct CteDesc {
     /// adjust the level of correlated columns to retain their original
     /// references.
     level_offset: usize,
+    /// Whether `val` refers to a recursive CTE from within its own
+    /// definition.
+    self_reference: bool,
 }
 
 /// The state required when planning a `Query`.
@@ -3615,7 +4509,24 @@ impl<'a> QueryContext<'a> {
         match object.id {
             Id::Local(id) => {
                 let name = object.raw_name;
-                let cte = self.ctes.get(&id).unwrap();
+                // The only CTEs that can be referred to before they are
+                // planned are recursive CTEs, from within their own
+                // non-recursive term.
+                let cte = match self.ctes.get(&id) {
+                    Some(cte) => cte,
+                    None => {
+                        return Err(PlanError::InvalidRecursiveReference {
+                            name: name.to_string(),
+                            context: "within its non-recursive term",
+                        })
+                    }
+                };
+                if cte.self_reference && cte.level_offset > 0 {
+                    return Err(PlanError::InvalidRecursiveReference {
+                        name: name.to_string(),
+                        context: "within a subquery",
+                    });
+                }
                 let mut val = cte.val.clone();
                 val.visit_columns(0, &mut |depth, col| {
                     if col.level > depth {
@@ -19,7 +19,8 @@ use anyhow::bail;
 
 use expr::GlobalId;
 use ore::collections::CollectionExt;
-use repr::{ColumnType, RelationDesc, ScalarType};
+use repr::adt::enums::EnumLabels;
+use repr::{ColumnType, RelationDesc, Row, RowArena, ScalarType};
 
 use crate::ast::{Ident, ObjectType, Raw, Statement, UnresolvedObjectName};
 use crate::catalog::{
@@ -29,6 +30,7 @@ use crate::names::{DatabaseSpecifier, FullName, PartialName};
 use crate::normalize;
 use crate::plan::error::PlanError;
 use crate::plan::query;
+use crate::plan::typeconv;
 use crate::plan::{Params, Plan, PlanContext};
 
 mod ddl;
@@ -47,6 +49,9 @@ pub struct StatementDesc {
     pub param_types: Vec<pgrepr::Type>,
     /// Whether the statement is a `COPY` statement.
     pub is_copy: bool,
+    /// The labels of the enum types in the statement's result set, which are
+    /// required to output its rows.
+    pub enum_labels: EnumLabels,
 }
 
 impl StatementDesc {
@@ -55,6 +60,7 @@ impl StatementDesc {
             relation_desc,
             param_types: vec![],
             is_copy: false,
+            enum_labels: EnumLabels::default(),
         }
     }
 
@@ -81,6 +87,11 @@ impl StatementDesc {
         self.is_copy = true;
         self
     }
+
+    fn with_enum_labels(mut self, enum_labels: EnumLabels) -> Self {
+        self.enum_labels = enum_labels;
+        self
+    }
 }
 
 /// Creates a description of the purified statement `stmt`.
@@ -121,6 +132,8 @@ pub fn describe(
         Statement::DropObjects(stmt) => ddl::describe_drop_objects(&scx, stmt)?,
         Statement::AlterObjectRename(stmt) => ddl::describe_alter_object_rename(&scx, stmt)?,
         Statement::AlterIndex(stmt) => ddl::describe_alter_index_options(&scx, stmt)?,
+        Statement::AlterTable(stmt) => ddl::describe_alter_table(&scx, stmt)?,
+        Statement::AlterRole(stmt) => ddl::describe_alter_role(&scx, stmt)?,
 
         // `SHOW` statements.
         Statement::ShowColumns(stmt) => show::show_columns(&scx, stmt)?.describe()?,
@@ -160,7 +173,20 @@ pub fn describe(
         Statement::Commit(stmt) => tcl::describe_commit(&scx, stmt)?,
     };
 
-    let desc = desc.with_params(scx.finalize_param_types()?);
+    let enum_labels = match &desc.relation_desc {
+        Some(relation_desc) => typeconv::plan_enum_labels(
+            catalog,
+            relation_desc
+                .typ()
+                .column_types
+                .iter()
+                .map(|ty| &ty.scalar_type),
+        )?,
+        None => EnumLabels::default(),
+    };
+    let desc = desc
+        .with_params(scx.finalize_param_types()?)
+        .with_enum_labels(enum_labels);
     Ok(desc)
 }
 
@@ -179,6 +205,7 @@ pub fn plan(
     stmt: Statement<Raw>,
     params: &Params,
 ) -> Result<Plan, anyhow::Error> {
+    let params = &resolve_enum_params(catalog, params)?;
     let param_types = params
         .types
         .iter()
@@ -208,6 +235,8 @@ pub fn plan(
         Statement::DropObjects(stmt) => ddl::plan_drop_objects(scx, stmt),
         Statement::AlterIndex(stmt) => ddl::plan_alter_index_options(scx, stmt),
         Statement::AlterObjectRename(stmt) => ddl::plan_alter_object_rename(scx, stmt),
+        Statement::AlterTable(stmt) => ddl::plan_alter_table(scx, stmt),
+        Statement::AlterRole(stmt) => ddl::plan_alter_role(scx, stmt),
 
         // DML statements.
         Statement::Insert(stmt) => dml::plan_insert(scx, stmt, params),
@@ -248,6 +277,23 @@ pub fn plan(
     }
 }
 
+/// Resolves the enum values in `params`, which are decoded to their bare
+/// labels, against the catalog.
+fn resolve_enum_params(
+    catalog: &dyn SessionCatalog,
+    params: &Params,
+) -> Result<Params, anyhow::Error> {
+    let arena = RowArena::new();
+    let mut datums = vec![];
+    for (datum, typ) in params.datums.iter().zip(&params.types) {
+        datums.push(typeconv::resolve_enum_labels(catalog, datum, typ, &arena)?);
+    }
+    Ok(Params {
+        datums: Row::pack(datums),
+        types: params.types.clone(),
+    })
+}
+
 pub fn plan_copy_from(
     pcx: &PlanContext,
     catalog: &dyn SessionCatalog,
@@ -14,17 +14,14 @@
 
 use std::collections::{BTreeMap, HashMap, HashSet};
 use std::convert::TryFrom;
-use std::fs;
-use std::io::{self, Write};
 use std::iter;
 use std::path::PathBuf;
 use std::time::Duration;
 
-use anyhow::{anyhow, bail, Context};
+use anyhow::{anyhow, bail};
 use aws_arn::ARN;
 use globset::GlobBuilder;
 use itertools::Itertools;
-use lazy_static::lazy_static;
 use log::{debug, error};
 use regex::Regex;
 use reqwest::Url;
@@ -32,32 +29,37 @@ use reqwest::Url;
 use dataflow_types::{
     AvroEncoding, AvroOcfEncoding, AvroOcfSinkConnectorBuilder, BringYourOwn, ColumnSpec,
     Consistency, CsvEncoding, DataEncoding, DebeziumMode, ExternalSourceConnector,
-    FileSourceConnector, KafkaSinkConnectorBuilder, KafkaSinkConnectorRetention, KafkaSinkFormat,
-    KafkaSourceConnector, KeyEnvelope, KinesisSourceConnector, PostgresSourceConnector,
-    ProtobufEncoding, PubNubSourceConnector, RegexEncoding, S3SourceConnector,
-    SinkConnectorBuilder, SinkEnvelope, SourceConnector, SourceDataEncoding, SourceEnvelope,
-    Timeline,
+    FileSinkConnectorBuilder, FileSinkFormat, FileSinkLocation, FileSourceConnector, JsonEncoding,
+    KafkaMetadataColumn, KafkaSinkConnectorBuilder, KafkaSinkConnectorRetention, KafkaSinkFormat,
+    KafkaSourceConnector, KeyEnvelope, KinesisSourceConnector, ParquetEncoding,
+    PostgresSourceConnector, ProtobufEncoding, ProtobufSinkSchema, PubNubSourceConnector,
+    RegexEncoding, S3SourceConnector, SinkConnectorBuilder, SinkEnvelope, SourceConnector,
+    SourceDataEncoding, SourceEnvelope, Timeline,
 };
 use expr::{func, GlobalId, MirRelationExpr, TableFunc, UnaryFunc};
 use interchange::avro::{self, AvroSchemaGenerator, DebeziumDeduplicationStrategy};
 use interchange::envelopes;
+use interchange::protobuf::encode::ProtobufEncoder;
 use ore::collections::CollectionExt;
 use ore::str::StrExt;
+use repr::adt::enums::EnumLabels;
 use repr::{strconv, ColumnName, ColumnType, Datum, RelationDesc, RelationType, Row, ScalarType};
 use sql_parser::ast::{CreateSourceFormat, CsvColumns, KeyConstraint};
 
 use crate::ast::display::AstDisplay;
 use crate::ast::{
-    AlterIndexAction, AlterIndexStatement, AlterObjectRenameStatement, AvroSchema, ColumnOption,
-    Compression, CreateDatabaseStatement, CreateIndexStatement, CreateRoleOption,
-    CreateRoleStatement, CreateSchemaStatement, CreateSinkConnector, CreateSinkStatement,
-    CreateSourceConnector, CreateSourceKeyEnvelope, CreateSourceStatement, CreateTableStatement,
-    CreateTypeAs, CreateTypeStatement, CreateViewStatement, CreateViewsDefinitions,
-    CreateViewsStatement, CsrConnector, CsrSeed, DataType, DbzMode, DropDatabaseStatement,
-    DropObjectsStatement, Envelope, Expr, Format, Ident, IfExistsBehavior, KafkaConsistency,
-    ObjectType, ProtobufSchema, Raw, SqlOption, Statement, UnresolvedObjectName, Value,
-    ViewDefinition, WithOption,
+    AlterIndexAction, AlterIndexStatement, AlterObjectRenameStatement, AlterRoleStatement,
+    AlterTableAction, AlterTableStatement, AvroSchema, ColumnDef, ColumnOption, Compression,
+    CreateDatabaseStatement, CreateIndexStatement, CreateRoleOption, CreateRoleStatement,
+    CreateSchemaStatement, CreateSinkConnector, CreateSinkStatement, CreateSourceConnector,
+    CreateSourceKeyEnvelope, CreateSourceStatement, CreateTableStatement, CreateTypeAs,
+    CreateTypeStatement, CreateViewStatement, CreateViewsDefinitions, CreateViewsStatement,
+    CsrConnector, CsrConnectorProto, CsrSeed, CsrSeedCompiled, CsrSeedCompiledOrLegacy, DataType,
+    DbzMode, DropDatabaseStatement, DropObjectsStatement, Envelope, Expr, Format, Ident,
+    IfExistsBehavior, KafkaConsistency, ObjectType, ProtobufSchema, Raw, SourceIncludeMetadataType,
+    SqlOption, Statement, TableConstraint, UnresolvedObjectName, Value, ViewDefinition, WithOption,
 };
+use crate::auth::ScramSha256Verifier;
 use crate::catalog::{CatalogItem, CatalogItemType};
 use crate::kafka_util;
 use crate::names::{DatabaseSpecifier, FullName, SchemaName};
@@ -66,15 +68,17 @@ use crate::plan::error::PlanError;
 use crate::plan::expr::{ColumnRef, HirScalarExpr, JoinKind};
 use crate::plan::query::{resolve_names_data_type, QueryLifetime};
 use crate::plan::statement::{StatementContext, StatementDesc};
+use crate::plan::typeconv;
 use crate::plan::{
     self, plan_utils, query, AlterIndexEnablePlan, AlterIndexResetOptionsPlan,
-    AlterIndexSetOptionsPlan, AlterItemRenamePlan, AlterNoopPlan, CreateDatabasePlan,
-    CreateIndexPlan, CreateRolePlan, CreateSchemaPlan, CreateSinkPlan, CreateSourcePlan,
-    CreateTablePlan, CreateTypePlan, CreateViewPlan, CreateViewsPlan, DropDatabasePlan,
-    DropItemsPlan, DropRolesPlan, DropSchemaPlan, HirRelationExpr, Index, IndexOption,
-    IndexOptionName, Params, Plan, Sink, Source, Table, Type, TypeInner, View,
+    AlterIndexSetOptionsPlan, AlterItemRenamePlan, AlterNoopPlan, AlterRolePlan, AlterTablePlan,
+    AlterTableRewrite, CheckConstraint, CreateDatabasePlan, CreateIndexPlan, CreateRolePlan,
+    CreateSchemaPlan, CreateSinkPlan, CreateSourcePlan, CreateTablePlan, CreateTypePlan,
+    CreateViewPlan, CreateViewsPlan, DropDatabasePlan, DropItemsPlan, DropRolesPlan,
+    DropSchemaPlan, HirRelationExpr, Index, IndexOption, IndexOptionName, Params, Plan, Sink,
+    Source, Table, Type, TypeInner, UniqueConstraint, View,
 };
-use crate::pure::Schema;
+use crate::pure::{compile_proto, Schema};
 
 pub fn describe_create_database(
     _: &StatementContext,
@@ -152,9 +156,6 @@ pub fn plan_create_table(
     if !with_options.is_empty() {
         bail_unsupported!("WITH options");
     }
-    if !constraints.is_empty() {
-        bail_unsupported!("CREATE TABLE with constraints")
-    }
 
     let names: Vec<_> = columns
         .iter()
@@ -168,18 +169,29 @@ pub fn plan_create_table(
         );
     }
 
+    let temporary = *temporary;
+    let name = if temporary {
+        scx.allocate_temporary_name(normalize::unresolved_object_name(name.to_owned())?)
+    } else {
+        scx.allocate_name(normalize::unresolved_object_name(name.to_owned())?)
+    };
+
     // Build initial relation type that handles declared data types
     // and NOT NULL constraints.
     let mut column_types = Vec::with_capacity(columns.len());
     let mut defaults = Vec::with_capacity(columns.len());
     let mut depends_on = Vec::new();
+    let mut constraint_names = ConstraintNames::new(&name.item);
+    let mut unique_constraints = vec![];
+    let mut checks = vec![];
 
-    for c in columns {
+    for (i, c) in columns.iter().enumerate() {
         let (aug_data_type, ids) = resolve_names_data_type(scx, c.data_type.clone())?;
         let ty = plan::scalar_type_from_sql(scx, &aug_data_type)?;
         let mut nullable = true;
         let mut default = Expr::null();
         for option in &c.options {
+            let constraint_name = option.name.clone().map(normalize::ident);
             match &option.option {
                 ColumnOption::NotNull => nullable = false,
                 ColumnOption::Default(expr) => {
@@ -189,6 +201,13 @@ pub fn plan_create_table(
                     depends_on.extend(expr_depends_on);
                     default = expr.clone();
                 }
+                ColumnOption::Unique { is_primary } => {
+                    unique_constraints.push((constraint_name, vec![i], *is_primary));
+                }
+                ColumnOption::Check(expr) => {
+                    let suffix = format!("{}_check", names[i]);
+                    checks.push((constraint_name, suffix, expr));
+                }
                 other => {
                     bail_unsupported!(format!("CREATE TABLE with column constraint: {}", other))
                 }
@@ -199,21 +218,117 @@ pub fn plan_create_table(
         depends_on.extend(ids);
     }
 
-    let typ = RelationType::new(column_types);
+    for constraint in constraints {
+        match constraint {
+            TableConstraint::Unique {
+                name: constraint_name,
+                columns: constraint_columns,
+                is_primary,
+            } => {
+                let mut indices = Vec::with_capacity(constraint_columns.len());
+                for column in constraint_columns {
+                    let column = normalize::column_name(column.clone());
+                    let index = match names.iter().position(|n| *n == column) {
+                        Some(index) => index,
+                        None => bail!(
+                            "column {} named in key does not exist",
+                            column.as_str().quoted()
+                        ),
+                    };
+                    if indices.contains(&index) {
+                        bail!(
+                            "column {} appears twice in {} constraint",
+                            column.as_str().quoted(),
+                            if *is_primary { "primary key" } else { "unique" }
+                        );
+                    }
+                    indices.push(index);
+                }
+                let constraint_name = constraint_name.clone().map(normalize::ident);
+                unique_constraints.push((constraint_name, indices, *is_primary));
+            }
+            TableConstraint::Check {
+                name: constraint_name,
+                expr,
+            } => {
+                let constraint_name = constraint_name.clone().map(normalize::ident);
+                checks.push((constraint_name, "check".into(), &**expr));
+            }
+            TableConstraint::ForeignKey { .. } => {
+                bail_unsupported!("CREATE TABLE with a FOREIGN KEY constraint")
+            }
+        }
+    }
 
-    let temporary = *temporary;
-    let name = if temporary {
-        scx.allocate_temporary_name(normalize::unresolved_object_name(name.to_owned())?)
-    } else {
-        scx.allocate_name(normalize::unresolved_object_name(name.to_owned())?)
-    };
+    // Primary keys imply NOT NULL. They are also registered before any other
+    // keys, so that they determine the key of the table's default index.
+    if unique_constraints.iter().filter(|(_, _, p)| *p).count() > 1 {
+        bail!(
+            "multiple primary keys for table {} are not allowed",
+            name.item.quoted()
+        );
+    }
+    unique_constraints.sort_by_key(|(_, _, is_primary)| !is_primary);
+    let unique_constraints = unique_constraints
+        .into_iter()
+        .map(|(constraint_name, columns, is_primary)| {
+            if is_primary {
+                for i in &columns {
+                    column_types[*i].nullable = false;
+                }
+            }
+            let constraint_name = match constraint_name {
+                Some(constraint_name) => constraint_names.declare(constraint_name)?,
+                None if is_primary => constraint_names.generate("pkey"),
+                None => constraint_names.generate(&format!(
+                    "{}_key",
+                    columns.iter().map(|i| names[*i].as_str()).join("_")
+                )),
+            };
+            Ok(UniqueConstraint {
+                name: constraint_name,
+                columns,
+                is_primary,
+            })
+        })
+        .collect::<Result<Vec<_>, anyhow::Error>>()?;
+
+    // Only constraints over non-nullable columns are keys in the relational
+    // sense, as a `UNIQUE` constraint admits any number of rows with `NULL`s
+    // in the constrained columns.
+    let mut typ = RelationType::new(column_types);
+    for constraint in &unique_constraints {
+        if constraint
+            .columns
+            .iter()
+            .all(|i| !typ.column_types[*i].nullable)
+        {
+            typ = typ.with_key(constraint.columns.clone());
+        }
+    }
     let desc = RelationDesc::new(typ, names.into_iter().map(Some));
 
+    let mut check_constraints = Vec::with_capacity(checks.len());
+    for (constraint_name, suffix, expr) in checks {
+        let (expr, expr_depends_on) = query::plan_check_expr(scx, &desc, expr)?;
+        depends_on.extend(expr_depends_on);
+        let constraint_name = match constraint_name {
+            Some(constraint_name) => constraint_names.declare(constraint_name)?,
+            None => constraint_names.generate(&suffix),
+        };
+        check_constraints.push(CheckConstraint {
+            name: constraint_name,
+            expr,
+        });
+    }
+
     let create_sql = normalize::create_statement(&scx, Statement::CreateTable(stmt.clone()))?;
     let table = Table {
         create_sql,
         desc,
         defaults,
+        unique_constraints,
+        check_constraints,
         temporary,
         depends_on,
     };
@@ -224,6 +339,48 @@ pub fn plan_create_table(
     }))
 }
 
+/// Assigns names to the constraints of a table, following PostgreSQL's naming
+/// conventions for constraints that are not explicitly named.
+struct ConstraintNames<'a> {
+    table_name: &'a str,
+    names: HashSet<String>,
+}
+
+impl<'a> ConstraintNames<'a> {
+    fn new(table_name: &'a str) -> ConstraintNames<'a> {
+        ConstraintNames {
+            table_name,
+            names: HashSet::new(),
+        }
+    }
+
+    /// Records an explicitly named constraint.
+    fn declare(&mut self, name: String) -> Result<String, anyhow::Error> {
+        if !self.names.insert(name.clone()) {
+            bail!(
+                "constraint {} for relation {} already exists",
+                name.quoted(),
+                self.table_name.quoted()
+            );
+        }
+        Ok(name)
+    }
+
+    /// Generates a name of the form `<table>_<suffix>` for a constraint,
+    /// appending a number if necessary to make the name unique.
+    fn generate(&mut self, suffix: &str) -> String {
+        let base = format!("{}_{}", self.table_name, suffix);
+        let mut name = base.clone();
+        let mut i = 0;
+        while self.names.contains(&name) {
+            i += 1;
+            name = format!("{}{}", base, i);
+        }
+        self.names.insert(name.clone());
+        name
+    }
+}
+
 pub fn describe_create_source(
     _: &StatementContext,
     _: CreateSourceStatement<Raw>,
@@ -398,6 +555,7 @@ pub fn plan_create_source(
         format,
         key_constraint,
         key_envelope,
+        include_metadata,
     } = &stmt;
 
     let with_options_original = with_options;
@@ -418,6 +576,45 @@ pub fn plan_create_source(
     if !matches!(connector, CreateSourceConnector::Kafka { .. }) && key_envelope.is_present() {
         bail_unsupported!("INCLUDE KEY with non-Kafka sources");
     }
+    if !include_metadata.is_empty() {
+        if !matches!(connector, CreateSourceConnector::Kafka { .. }) {
+            bail_unsupported!(format!(
+                "INCLUDE {} with non-Kafka sources",
+                include_metadata[0].ty
+            ));
+        }
+        if !matches!(envelope, Envelope::None) {
+            bail_unsupported!(format!(
+                "INCLUDE {} with ENVELOPE {}",
+                include_metadata[0].ty, envelope
+            ));
+        }
+    }
+    let metadata_columns = include_metadata
+        .iter()
+        .map(|item| {
+            let column = match item.ty {
+                SourceIncludeMetadataType::Partition => KafkaMetadataColumn::Partition,
+                SourceIncludeMetadataType::Offset => KafkaMetadataColumn::Offset,
+                SourceIncludeMetadataType::Timestamp => KafkaMetadataColumn::Timestamp,
+                SourceIncludeMetadataType::Headers => KafkaMetadataColumn::Headers,
+            };
+            let name = match &item.alias {
+                Some(alias) => normalize::column_name(alias.clone()),
+                None => ColumnName::from(column.default_name()),
+            };
+            (column, name)
+        })
+        .collect::<Vec<_>>();
+    let is_parquet = matches!(format, CreateSourceFormat::Bare(Format::Parquet { .. }));
+    if is_parquet
+        && !matches!(
+            connector,
+            CreateSourceConnector::File { .. } | CreateSourceConnector::S3 { .. }
+        )
+    {
+        bail!("FORMAT PARQUET is only supported for file and S3 sources");
+    }
 
     let (external_connector, encoding, key_envelope) = match connector {
         CreateSourceConnector::Kafka { broker, topic, .. } => {
@@ -467,7 +664,7 @@ pub fn plan_create_source(
                 Some(v) => bail!("invalid start_offset value: {}", v),
             }
 
-            let encoding = get_encoding(format, envelope, with_options_original)?;
+            let encoding = get_encoding(scx, format, envelope, with_options_original)?;
             let key_envelope = get_key_envelope(key_envelope, envelope, &encoding)?;
 
             let connector = ExternalSourceConnector::Kafka(KafkaSourceConnector {
@@ -478,6 +675,7 @@ pub fn plan_create_source(
                 group_id_prefix,
                 cluster_id: scx.catalog.config().cluster_id,
                 key_envelope: key_envelope.clone(),
+                include_metadata: metadata_columns.iter().map(|(column, _)| *column).collect(),
             });
 
             if consistency != Consistency::RealTime
@@ -511,7 +709,7 @@ pub fn plan_create_source(
                 stream_name,
                 aws_info,
             });
-            let encoding = get_encoding(format, envelope, with_options_original)?;
+            let encoding = get_encoding(scx, format, envelope, with_options_original)?;
             (connector, encoding, KeyEnvelope::None)
         }
         CreateSourceConnector::File { path, compression } => {
@@ -520,6 +718,9 @@ pub fn plan_create_source(
                 Some(Value::Boolean(b)) => b,
                 Some(_) => bail!("tail must be a boolean"),
             };
+            if tail && is_parquet {
+                bail!("FORMAT PARQUET does not support tail = true");
+            }
             consistency = match with_options.remove("consistency_topic") {
                 None => Consistency::RealTime,
                 Some(_) => bail!("BYO consistency not supported for file sources"),
@@ -527,13 +728,10 @@ pub fn plan_create_source(
 
             let connector = ExternalSourceConnector::File(FileSourceConnector {
                 path: path.clone().into(),
-                compression: match compression {
-                    Compression::Gzip => dataflow_types::Compression::Gzip,
-                    Compression::None => dataflow_types::Compression::None,
-                },
+                compression: get_compression(compression),
                 tail,
             });
-            let encoding = get_encoding(format, envelope, with_options_original)?;
+            let encoding = get_encoding(scx, format, envelope, with_options_original)?;
             (connector, encoding, KeyEnvelope::None)
         }
         CreateSourceConnector::S3 {
@@ -570,30 +768,40 @@ pub fn plan_create_source(
                     })
                     .transpose()?,
                 aws_info,
-                compression: match compression {
-                    Compression::Gzip => dataflow_types::Compression::Gzip,
-                    Compression::None => dataflow_types::Compression::None,
-                },
+                compression: get_compression(compression),
             });
-            let encoding = get_encoding(format, envelope, with_options_original)?;
+            let encoding = get_encoding(scx, format, envelope, with_options_original)?;
             (connector, encoding, KeyEnvelope::None)
         }
         CreateSourceConnector::Postgres {
             conn,
             publication,
             slot,
+            details,
         } => {
             let slot_name = slot
                 .as_ref()
                 .ok_or_else(|| anyhow!("Postgres sources must provide a slot name"))?;
+            let details = details
+                .as_ref()
+                .map(|details| serde_json::from_str(details))
+                .transpose()
+                .map_err(|e| anyhow!("invalid Postgres source details: {}", e))?;
+            let handle_truncate = match with_options.remove("handle_truncate") {
+                None => false,
+                Some(Value::Boolean(b)) => b,
+                Some(_) => bail!("handle_truncate must be a boolean"),
+            };
 
+            let encoding = SourceDataEncoding::Single(DataEncoding::Postgres(details.clone()));
             let connector = ExternalSourceConnector::Postgres(PostgresSourceConnector {
                 conn: conn.clone(),
                 publication: publication.clone(),
                 slot_name: slot_name.clone(),
+                details,
+                handle_truncate,
             });
 
-            let encoding = SourceDataEncoding::Single(DataEncoding::Postgres);
             (connector, encoding, KeyEnvelope::None)
         }
         CreateSourceConnector::PubNub {
@@ -773,6 +981,25 @@ pub fn plan_create_source(
     }
 
     let mut bare_desc = encoding.desc(&envelope, &key_envelope)?;
+    for ((column, name), item) in metadata_columns.into_iter().zip(include_metadata) {
+        if bare_desc.get_by_name(&name).is_some() {
+            match item.alias {
+                None => bail!(
+                    "INCLUDE {} column {} conflicts with an existing column; \
+                     specify a different name with INCLUDE {} AS <name>",
+                    item.ty,
+                    name.as_str().quoted(),
+                    item.ty
+                ),
+                Some(_) => bail!(
+                    "INCLUDE {} column {} conflicts with an existing column",
+                    item.ty,
+                    name.as_str().quoted()
+                ),
+            }
+        }
+        bare_desc = bare_desc.with_named_column(name, column.column_type());
+    }
     let ignore_source_keys = match with_options.remove("ignore_source_keys") {
         None => false,
         Some(Value::Boolean(b)) => b,
@@ -923,19 +1150,20 @@ pub fn plan_create_source(
 }
 
 fn get_encoding<T: sql_parser::ast::AstInfo>(
+    scx: &StatementContext,
     format: &CreateSourceFormat<Raw>,
     envelope: &Envelope,
     with_options: &Vec<SqlOption<T>>,
 ) -> Result<SourceDataEncoding, anyhow::Error> {
     let encoding = match format {
         CreateSourceFormat::None => bail!("Source format must be specified"),
-        CreateSourceFormat::Bare(format) => get_encoding_inner(format, with_options)?,
+        CreateSourceFormat::Bare(format) => get_encoding_inner(scx, format, with_options)?,
         CreateSourceFormat::KeyValue { key, value } => {
-            let key = match get_encoding_inner(key, with_options)? {
+            let key = match get_encoding_inner(scx, key, with_options)? {
                 SourceDataEncoding::Single(key) => key,
                 SourceDataEncoding::KeyValue { key, .. } => key,
             };
-            let value = match get_encoding_inner(value, with_options)? {
+            let value = match get_encoding_inner(scx, value, with_options)? {
                 SourceDataEncoding::Single(value) => value,
                 SourceDataEncoding::KeyValue { value, .. } => value,
             };
@@ -956,6 +1184,7 @@ fn get_encoding<T: sql_parser::ast::AstInfo>(
 }
 
 fn get_encoding_inner<T: sql_parser::ast::AstInfo>(
+    scx: &StatementContext,
     format: &Format<Raw>,
     with_options: &Vec<SqlOption<T>>,
 ) -> Result<SourceDataEncoding, anyhow::Error> {
@@ -1049,33 +1278,58 @@ fn get_encoding_inner<T: sql_parser::ast::AstInfo>(
         }
         Format::Protobuf(schema) => match schema {
             ProtobufSchema::Csr {
-                csr_connector: CsrConnector { seed, .. },
-            } => {
-                if let Some(CsrSeed {
+                csr_connector: CsrConnectorProto { seed, .. },
+            } => match seed {
+                Some(CsrSeedCompiledOrLegacy::Compiled(CsrSeedCompiled { key, value })) => {
+                    let value = DataEncoding::Protobuf(ProtobufEncoding {
+                        descriptors: strconv::parse_bytes(&value.schema)?,
+                        message_name: Some(value.message_name.clone()),
+                        confluent_wire_format: true,
+                    });
+                    if let Some(key) = key {
+                        return Ok(SourceDataEncoding::KeyValue {
+                            key: DataEncoding::Protobuf(ProtobufEncoding {
+                                descriptors: strconv::parse_bytes(&key.schema)?,
+                                message_name: Some(key.message_name.clone()),
+                                confluent_wire_format: true,
+                            }),
+                            value,
+                        });
+                    } else {
+                        value
+                    }
+                }
+                // Sources created before schema references were supported
+                // store the uncompiled schemas, and expect records without the
+                // Confluent header.
+                Some(CsrSeedCompiledOrLegacy::Legacy(CsrSeed {
                     key_schema,
                     value_schema,
-                }) = seed
-                {
-                    let descriptors = compile_proto(&value_schema)?;
+                })) => {
+                    let (descriptors, _) =
+                        compile_proto(&strconv::parse_bytes(value_schema)?, &[])?;
                     let value = DataEncoding::Protobuf(ProtobufEncoding {
                         descriptors,
                         message_name: None,
+                        confluent_wire_format: false,
                     });
                     if let Some(key_schema) = key_schema {
+                        let (descriptors, _) =
+                            compile_proto(&strconv::parse_bytes(key_schema)?, &[])?;
                         return Ok(SourceDataEncoding::KeyValue {
                             key: DataEncoding::Protobuf(ProtobufEncoding {
-                                descriptors: compile_proto(&key_schema)?,
+                                descriptors,
                                 message_name: None,
+                                confluent_wire_format: false,
                             }),
                             value,
                         });
                     } else {
                         value
                     }
-                } else {
-                    unreachable!("CSR seed resolution should already have been called")
                 }
-            }
+                None => unreachable!("CSR seed resolution should already have been called"),
+            },
             ProtobufSchema::InlineSchema {
                 message_name,
                 schema,
@@ -1090,6 +1344,7 @@ fn get_encoding_inner<T: sql_parser::ast::AstInfo>(
                 DataEncoding::Protobuf(ProtobufEncoding {
                     descriptors,
                     message_name: Some(message_name.to_owned()),
+                    confluent_wire_format: false,
                 })
             }
         },
@@ -1117,61 +1372,74 @@ fn get_encoding_inner<T: sql_parser::ast::AstInfo>(
                 },
             })
         }
-        Format::Json => bail_unsupported!("JSON sources"),
+        Format::Json { columns } => {
+            let columns = if columns.is_empty() {
+                None
+            } else {
+                Some(plan_format_columns(scx, columns, "FORMAT JSON")?)
+            };
+            DataEncoding::Json(JsonEncoding { columns })
+        }
+        Format::Parquet { columns } => {
+            if columns.is_empty() {
+                bail!("[internal error] FORMAT PARQUET should get columns in purify")
+            }
+            let columns = plan_format_columns(scx, columns, "FORMAT PARQUET")?;
+            DataEncoding::Parquet(ParquetEncoding { columns })
+        }
+        Format::AvroOcf => bail!("FORMAT AVRO OCF is only supported for file and S3 sinks"),
         Format::Text => DataEncoding::Text,
     }))
 }
 
-fn compile_proto(schema: &str) -> Result<Vec<u8>, anyhow::Error> {
-    // Write schema string to a file to compile it.
-    let schema_bytes = strconv::parse_bytes(schema)?;
-    let include_dir = tempfile::tempdir()?.into_path();
-    let mut file = tempfile::NamedTempFile::new_in(&include_dir)?;
-    file.write_all(&schema_bytes)?;
-    file.flush()?;
-
-    // Destroy and recreate the build directory, in case any inputs have
-    // been deleted since the last invocation.
-    let out_dir = tempfile::tempdir()?.into_path();
-    match fs::remove_dir_all(&out_dir) {
-        Ok(()) => (),
-        Err(e) if e.kind() == io::ErrorKind::NotFound => (),
-        Err(e) => {
-            return Err(anyhow::Error::new(e))
-                .with_context(|| format!("removing existing out directory {}", out_dir.display()))
-        }
-    }
-    fs::create_dir(&out_dir)
-        .with_context(|| format!("creating out directory {}", out_dir.display()))?;
-
-    // Compile schema string.
-    match protoc::Protoc::new()
-        .include(&include_dir)
-        .input(file.path())
-        .compile_into(&out_dir)
-    {
-        Ok(()) => (),
-        Err(e) => {
-            lazy_static! {
-                static ref MISSING_IMPORT_ERROR: Regex = Regex::new(
-                    r#"protobuf path \\"(?P<reference>.*)\\" is not found in import path"#
-                )
-                .expect("known valid");
-            }
-
-            // Make protobuf import errors more user-friendly.
-            if let Some(captures) = MISSING_IMPORT_ERROR.captures(&e.to_string()) {
-                bail!(
-                    "unsupported protobuf schema reference {}",
-                    &captures["reference"]
-                )
-            } else {
-                return Err(e);
+/// Plans the column list of a format, like `FORMAT JSON (...)`, which may
+/// only use built-in types and `NOT NULL` constraints.
+fn plan_format_columns(
+    scx: &StatementContext,
+    columns: &[ColumnDef<Raw>],
+    format_name: &str,
+) -> Result<Vec<(ColumnName, ColumnType)>, anyhow::Error> {
+    let names: Vec<_> = columns
+        .iter()
+        .map(|c| normalize::column_name(c.name.clone()))
+        .collect();
+    if let Some(dup) = names.iter().duplicates().next() {
+        bail!(
+            "column {} specified more than once in {}",
+            dup.as_str().quoted(),
+            format_name
+        );
+    }
+    let mut column_types = Vec::with_capacity(columns.len());
+    for c in columns {
+        let (aug_data_type, ids) = resolve_names_data_type(scx, c.data_type.clone())?;
+        if !ids.is_empty() {
+            bail_unsupported!(format!("custom types in {}", format_name));
+        }
+        let ty = plan::scalar_type_from_sql(scx, &aug_data_type)?;
+        let mut nullable = true;
+        for option in &c.options {
+            match &option.option {
+                ColumnOption::NotNull => nullable = false,
+                other => {
+                    bail_unsupported!(format!("{} with column constraint: {}", format_name, other))
+                }
             }
         }
+        column_types.push(ty.nullable(nullable));
     }
+    Ok(names.into_iter().zip(column_types).collect())
+}
 
-    Ok(fs::read(out_dir.join("file_descriptor_set.pb").as_path())?)
+fn get_compression(compression: &Compression) -> dataflow_types::Compression {
+    match compression {
+        Compression::Gzip => dataflow_types::Compression::Gzip,
+        Compression::Zstd => dataflow_types::Compression::Zstd,
+        Compression::Snappy => dataflow_types::Compression::Snappy,
+        Compression::Lz4 => dataflow_types::Compression::Lz4,
+        Compression::Auto => dataflow_types::Compression::Auto,
+        Compression::None => dataflow_types::Compression::None,
+    }
 }
 
 fn get_key_envelope(
@@ -1194,12 +1462,15 @@ fn get_key_envelope(
             // Otherwise it gets the names of the columns in the type
             if let SourceDataEncoding::KeyValue { key, value: _ } = encoding {
                 let is_composite = match key {
-                    DataEncoding::AvroOcf { .. } | DataEncoding::Postgres => {
+                    DataEncoding::AvroOcf { .. }
+                    | DataEncoding::Parquet(_)
+                    | DataEncoding::Postgres(_) => {
                         bail!("{} sources cannot use INCLUDE KEY", key.op_name())
                     }
                     DataEncoding::Bytes | DataEncoding::Text => false,
                     DataEncoding::Avro(_)
                     | DataEncoding::Csv(_)
+                    | DataEncoding::Json(_)
                     | DataEncoding::Protobuf(_)
                     | DataEncoding::Regex { .. } => true,
                 };
@@ -1352,6 +1623,7 @@ pub fn plan_create_views(
 #[allow(clippy::too_many_arguments)]
 fn kafka_sink_builder(
     format: Option<Format<Raw>>,
+    envelope: SinkEnvelope,
     consistency: Option<KafkaConsistency<Raw>>,
     with_options: &mut BTreeMap<String, Value>,
     broker: String,
@@ -1359,6 +1631,7 @@ fn kafka_sink_builder(
     relation_key_indices: Option<Vec<usize>>,
     key_desc_and_indices: Option<(RelationDesc, Vec<usize>)>,
     value_desc: RelationDesc,
+    enum_labels: EnumLabels,
     topic_suffix_nonce: String,
     root_dependencies: &[&dyn CatalogItem],
 ) -> Result<SinkConnectorBuilder, anyhow::Error> {
@@ -1379,6 +1652,20 @@ fn kafka_sink_builder(
     };
     let config_options = kafka_util::extract_config(with_options)?;
 
+    if envelope == SinkEnvelope::CdcV2 {
+        // CDCv2 messages carry their own progress information.
+        if consistency_topic.is_some() || consistency.is_some() || reuse_topic {
+            bail!("ENVELOPE MATERIALIZE sinks do not support consistency topics or reuse_topic");
+        }
+        if key_desc_and_indices.is_some() {
+            bail!("ENVELOPE MATERIALIZE sinks do not support KEY");
+        }
+        if !matches!(format, Some(Format::Avro(AvroSchema::Csr { .. }))) {
+            bail!("ENVELOPE MATERIALIZE sinks require FORMAT AVRO USING CONFLUENT SCHEMA REGISTRY");
+        }
+    }
+
+    let include_transaction = reuse_topic || consistency_topic.is_some() || consistency.is_some();
     let format = match format {
         Some(Format::Avro(AvroSchema::Csr {
             csr_connector:
@@ -1399,8 +1686,6 @@ fn kafka_sink_builder(
                 ccsr_with_options,
             )?;
 
-            let include_transaction =
-                reuse_topic || consistency_topic.is_some() || consistency.is_some();
             let schema_generator = AvroSchemaGenerator::new(
                 key_desc_and_indices
                     .as_ref()
@@ -1408,7 +1693,14 @@ fn kafka_sink_builder(
                 value_desc.clone(),
                 include_transaction,
             );
-            let value_schema = schema_generator.value_writer_schema().to_string();
+            let value_schema = match envelope {
+                SinkEnvelope::CdcV2 => {
+                    avro::cdc_v2::Encoder::new(value_desc.clone(), enum_labels.clone())
+                        .writer_schema()
+                        .to_string()
+                }
+                _ => schema_generator.value_writer_schema().to_string(),
+            };
             let key_schema = schema_generator
                 .key_writer_schema()
                 .map(|key_schema| key_schema.to_string());
@@ -1420,7 +1712,26 @@ fn kafka_sink_builder(
                 ccsr_config,
             }
         }
-        Some(Format::Json) => KafkaSinkFormat::Json,
+        Some(Format::Json { columns }) if columns.is_empty() => KafkaSinkFormat::Json,
+        Some(Format::Json { .. }) => bail!("FORMAT JSON for sinks does not accept a column list"),
+        Some(Format::Protobuf(ProtobufSchema::InlineSchema {
+            message_name,
+            schema,
+        })) => {
+            let descriptors = match schema {
+                sql_parser::ast::Schema::Inline(bytes) => strconv::parse_bytes(&bytes)?,
+                sql_parser::ast::Schema::File(_) => {
+                    unreachable!("File schema should already have been inlined")
+                }
+            };
+            KafkaSinkFormat::Protobuf(ProtobufSinkSchema {
+                message_name,
+                descriptors,
+            })
+        }
+        Some(Format::Protobuf(ProtobufSchema::Csr { .. })) => {
+            bail_unsupported!("FORMAT PROTOBUF USING CONFLUENT SCHEMA REGISTRY for sinks")
+        }
         Some(format) => bail_unsupported!(format!("sink format {:?}", format)),
         None => bail_unsupported!("sink without format"),
     };
@@ -1434,6 +1745,20 @@ fn kafka_sink_builder(
         consistency_topic,
     )?;
 
+    if let KafkaSinkFormat::Protobuf(schema) = &format {
+        // Check that the sinked relation can be encoded as the message.
+        ProtobufEncoder::new(
+            key_desc_and_indices
+                .as_ref()
+                .map(|(desc, _indices)| desc.clone()),
+            value_desc.clone(),
+            include_transaction,
+            &schema.message_name,
+            &schema.descriptors,
+            enum_labels.clone(),
+        )?;
+    }
+
     let broker_addrs = broker.parse()?;
 
     let transitive_source_dependencies: Vec<_> = if reuse_topic {
@@ -1525,6 +1850,7 @@ fn kafka_sink_builder(
         relation_key_indices,
         key_desc_and_indices,
         value_desc,
+        enum_labels,
         reuse_topic,
         transitive_source_dependencies,
         retention,
@@ -1584,46 +1910,52 @@ fn get_kafka_sink_consistency_config(
                 // If a CONSISTENCY FORMAT is not provided, default to the FORMAT of the sink.
                 match sink_format {
                     format @ KafkaSinkFormat::Avro { .. } => Some((topic, format.clone())),
-                    KafkaSinkFormat::Json => bail_unsupported!("CONSISTENCY FORMAT JSON"),
+                    KafkaSinkFormat::Json => Some((topic, KafkaSinkFormat::Json)),
+                    KafkaSinkFormat::Protobuf(_) => {
+                        bail!("FORMAT PROTOBUF sinks require an explicit CONSISTENCY FORMAT")
+                    }
+                }
+            }
+            Some(Format::Json { columns }) => {
+                if !columns.is_empty() {
+                    bail!("CONSISTENCY FORMAT JSON does not accept a column list");
                 }
+                Some((topic, KafkaSinkFormat::Json))
             }
             Some(other) => bail_unsupported!(format!("CONSISTENCY FORMAT {}", &other)),
         },
         None => {
-            // Support use of `consistency_topic` with option if the sink is Avro-formatted
-            // for backwards compatibility.
+            // Support use of `consistency_topic` with option for backwards compatibility. The
+            // consistency topic is encoded in the same format as the sink.
             if reuse_topic | consistency_topic.is_some() {
-                match sink_format {
+                let consistency_topic = match consistency_topic {
+                    Some(topic) => topic,
+                    None => {
+                        let default_consistency_topic = format!("{}-consistency", topic_prefix);
+                        debug!(
+                            "Using default consistency topic '{}' for topic '{}'",
+                            default_consistency_topic, topic_prefix
+                        );
+                        default_consistency_topic
+                    }
+                };
+                let consistency_format = match sink_format {
                     KafkaSinkFormat::Avro {
                         schema_registry_url,
                         ccsr_config,
                         ..
-                    } => {
-                        let consistency_topic = match consistency_topic {
-                            Some(topic) => topic,
-                            None => {
-                                let default_consistency_topic =
-                                    format!("{}-consistency", topic_prefix);
-                                debug!(
-                                    "Using default consistency topic '{}' for topic '{}'",
-                                    default_consistency_topic, topic_prefix
-                                );
-                                default_consistency_topic
-                            }
-                        };
-                        Some((
-                            consistency_topic,
-                            KafkaSinkFormat::Avro {
-                                schema_registry_url: schema_registry_url.clone(),
-                                key_schema: None,
-                                value_schema: avro::get_debezium_transaction_schema()
-                                    .canonical_form(),
-                                ccsr_config: ccsr_config.clone(),
-                            },
-                        ))
+                    } => KafkaSinkFormat::Avro {
+                        schema_registry_url: schema_registry_url.clone(),
+                        key_schema: None,
+                        value_schema: avro::get_debezium_transaction_schema().canonical_form(),
+                        ccsr_config: ccsr_config.clone(),
+                    },
+                    KafkaSinkFormat::Json => KafkaSinkFormat::Json,
+                    KafkaSinkFormat::Protobuf(_) => {
+                        bail!("FORMAT PROTOBUF sinks require an explicit CONSISTENCY FORMAT")
                     }
-                    KafkaSinkFormat::Json => bail!("For FORMAT JSON, you need to manually specify an Avro consistency topic using 'CONSISTENCY TOPIC consistency_topic CONSISTENCY FORMAT AVRO USING CONFLUENT SCHEMA REGISTRY url'. The default of using a JSON consistency topic is not supported."),
-                }
+                };
+                Some((consistency_topic, consistency_format))
             } else {
                 None
             }
@@ -1638,6 +1970,7 @@ fn avro_ocf_sink_builder(
     path: String,
     file_name_suffix: String,
     value_desc: RelationDesc,
+    enum_labels: EnumLabels,
 ) -> Result<SinkConnectorBuilder, anyhow::Error> {
     if format.is_some() {
         bail!("avro ocf sinks cannot specify a format");
@@ -1653,6 +1986,110 @@ fn avro_ocf_sink_builder(
         path,
         file_name_suffix,
         value_desc,
+        enum_labels,
+    }))
+}
+
+#[allow(clippy::too_many_arguments)]
+fn file_sink_builder(
+    scx: &StatementContext,
+    format: Option<Format<Raw>>,
+    envelope: SinkEnvelope,
+    with_options: &mut BTreeMap<String, Value>,
+    location: FileSinkLocation,
+    suffix_nonce: String,
+    desc: RelationDesc,
+    enum_labels: EnumLabels,
+    root_dependencies: &[&dyn CatalogItem],
+) -> Result<SinkConnectorBuilder, anyhow::Error> {
+    let value_desc = match envelope {
+        SinkEnvelope::None => desc,
+        // The timestamp and diff of each update are appended to its row, so
+        // that consumers can reconstruct the relation at any sealed timestamp.
+        SinkEnvelope::CdcV2 => desc
+            .with_named_column("mz_timestamp", ScalarType::Int64.nullable(false))
+            .with_named_column("mz_diff", ScalarType::Int64.nullable(false)),
+        SinkEnvelope::Debezium | SinkEnvelope::Upsert => {
+            bail!("file and S3 sinks require ENVELOPE NONE or ENVELOPE MATERIALIZE")
+        }
+    };
+
+    let format = match format {
+        Some(Format::AvroOcf) => FileSinkFormat::AvroOcf,
+        Some(Format::Csv { columns, delimiter }) => {
+            let header = match columns {
+                CsvColumns::Header { names } if names.is_empty() => true,
+                CsvColumns::Header { .. } => {
+                    bail!("CSV sinks cannot specify the names of their columns")
+                }
+                CsvColumns::Count(n) if n == value_desc.arity() => false,
+                CsvColumns::Count(n) => bail!(
+                    "CSV sink is declared to have {} columns, but its relation has {}",
+                    n,
+                    value_desc.arity()
+                ),
+            };
+            if !delimiter.is_ascii() {
+                bail!("CSV delimiter must be an ASCII character");
+            }
+            FileSinkFormat::Csv {
+                header,
+                delimiter: delimiter as u8,
+            }
+        }
+        Some(Format::Parquet { columns }) => {
+            if !columns.is_empty() {
+                bail!("Parquet sinks cannot specify their columns");
+            }
+            // Check that every column can be written to Parquet.
+            interchange::parquet::Encoder::new(value_desc.clone())?;
+            FileSinkFormat::Parquet
+        }
+        Some(format) => bail!(
+            "file and S3 sinks do not support FORMAT {}",
+            format.to_ast_string()
+        ),
+        None => bail!("file and S3 sinks require FORMAT AVRO OCF, CSV, or PARQUET"),
+    };
+
+    let seal_interval = match with_options.remove("seal_interval_ms") {
+        Some(Value::Number(n)) => match n.parse::<u64>() {
+            Ok(n) if n > 0 => Duration::from_millis(n),
+            _ => bail!("seal_interval_ms must be a positive u64"),
+        },
+        Some(_) => bail!("seal_interval_ms must be a positive u64"),
+        None => Duration::from_secs(60),
+    };
+
+    // A sink whose inputs are all replayable sources observes the same
+    // updates at the same timestamps after a restart, so it writes to the
+    // same directory or prefix and resumes where it left off. Other sinks
+    // write to a new directory or prefix after each restart.
+    let resumable = !root_dependencies.is_empty()
+        && root_dependencies.iter().all(|item| {
+            item.item_type() == CatalogItemType::Source
+                && item
+                    .source_connector()
+                    .map(|connector| connector.yields_stable_input())
+                    .unwrap_or(false)
+        });
+    let (name_suffix, transitive_source_dependencies) = if resumable {
+        (
+            scx.catalog.config().cluster_id.to_string(),
+            root_dependencies.iter().map(|item| item.id()).collect(),
+        )
+    } else {
+        (suffix_nonce, vec![])
+    };
+
+    Ok(SinkConnectorBuilder::File(FileSinkConnectorBuilder {
+        location,
+        name_suffix,
+        format,
+        value_desc,
+        enum_labels,
+        seal_interval,
+        transitive_source_dependencies,
     }))
 }
 
@@ -1683,11 +2120,11 @@ pub fn plan_create_sink(
     let envelope = match envelope {
         None | Some(Envelope::Debezium(sql_parser::ast::DbzMode::Plain)) => SinkEnvelope::Debezium,
         Some(Envelope::Upsert) => SinkEnvelope::Upsert,
-        Some(Envelope::CdcV2) => bail_unsupported!("CDCv2 sinks"),
+        Some(Envelope::CdcV2) => SinkEnvelope::CdcV2,
         Some(Envelope::Debezium(sql_parser::ast::DbzMode::Upsert)) => {
             bail_unsupported!("UPSERT doesn't make sense for sinks")
         }
-        Some(Envelope::None) => bail_unsupported!("\"ENVELOPE NONE\" sinks"),
+        Some(Envelope::None) => SinkEnvelope::None,
     };
     let name = scx.allocate_name(normalize::unresolved_object_name(name)?);
     let from = scx.resolve_item(from)?;
@@ -1738,7 +2175,9 @@ pub fn plan_create_sink(
                 None
             }
         }
-        CreateSinkConnector::AvroOcf { .. } => None,
+        CreateSinkConnector::AvroOcf { .. }
+        | CreateSinkConnector::File { .. }
+        | CreateSinkConnector::S3 { .. } => None,
     };
 
     // pick the first valid natural relation key, if any
@@ -1758,9 +2197,16 @@ pub fn plan_create_sink(
 
     let value_desc = match envelope {
         SinkEnvelope::Debezium => envelopes::dbz_desc(desc.clone()),
-        SinkEnvelope::Upsert => desc.clone(),
+        SinkEnvelope::Upsert | SinkEnvelope::None | SinkEnvelope::CdcV2 => desc.clone(),
     };
 
+    // The sinked values only store the positions of enum labels, so the sink
+    // must carry the labels of their types.
+    let enum_labels = typeconv::plan_enum_labels(
+        scx.catalog,
+        desc.typ().column_types.iter().map(|ty| &ty.scalar_type),
+    )?;
+
     if as_of.is_some() {
         bail!("CREATE SINK ... AS OF is no longer supported");
     }
@@ -1778,6 +2224,7 @@ pub fn plan_create_sink(
             ..
         } => kafka_sink_builder(
             format,
+            envelope,
             consistency,
             &mut with_options,
             broker,
@@ -1785,11 +2232,52 @@ pub fn plan_create_sink(
             relation_key_indices,
             key_desc_and_indices,
             value_desc,
+            enum_labels,
             suffix_nonce,
             &root_user_dependencies,
         )?,
         CreateSinkConnector::AvroOcf { path } => {
-            avro_ocf_sink_builder(format, path, suffix_nonce, value_desc)?
+            match envelope {
+                SinkEnvelope::None => bail_unsupported!("ENVELOPE NONE for Avro OCF sinks"),
+                SinkEnvelope::CdcV2 => {
+                    bail_unsupported!("ENVELOPE MATERIALIZE for Avro OCF sinks")
+                }
+                SinkEnvelope::Debezium | SinkEnvelope::Upsert => (),
+            }
+            avro_ocf_sink_builder(format, path, suffix_nonce, value_desc, enum_labels)?
+        }
+        CreateSinkConnector::File { path } => file_sink_builder(
+            scx,
+            format,
+            envelope,
+            &mut with_options,
+            FileSinkLocation::Directory(PathBuf::from(path)),
+            suffix_nonce,
+            desc,
+            enum_labels,
+            &root_user_dependencies,
+        )?,
+        CreateSinkConnector::S3 { bucket, prefix } => {
+            let aws_info = normalize::aws_connect_info(&mut with_options, None)?;
+            let mut prefix = prefix.unwrap_or_default();
+            if !prefix.is_empty() && !prefix.ends_with('/') {
+                prefix.push('/');
+            }
+            file_sink_builder(
+                scx,
+                format,
+                envelope,
+                &mut with_options,
+                FileSinkLocation::S3 {
+                    bucket,
+                    prefix,
+                    aws_info,
+                },
+                suffix_nonce,
+                desc,
+                enum_labels,
+                &root_user_dependencies,
+            )?
         }
     };
 
@@ -2018,16 +2506,55 @@ pub fn plan_create_type(
         with_options,
     } = stmt;
 
-    let mut with_options = normalize::option_objects(&with_options);
+    let name = scx.allocate_name(normalize::unresolved_object_name(name)?);
+    if scx.catalog.item_exists(&name) {
+        bail!("catalog item {} already exists", name.to_string().quoted());
+    }
 
-    let option_keys = match as_type {
-        CreateTypeAs::List => vec!["element_type"],
-        CreateTypeAs::Map => vec!["key_type", "value_type"],
+    let (option_keys, mut with_options) = match &as_type {
+        CreateTypeAs::List => (
+            vec!["element_type".into()],
+            normalize::option_objects(&with_options),
+        ),
+        CreateTypeAs::Map => (
+            vec!["key_type".into(), "value_type".into()],
+            normalize::option_objects(&with_options),
+        ),
+        CreateTypeAs::Enum { labels } => {
+            let mut seen = HashSet::new();
+            for label in labels {
+                if !seen.insert(label) {
+                    bail!("enum label {} specified more than once", label.quoted());
+                }
+            }
+            return Ok(Plan::CreateType(CreateTypePlan {
+                name,
+                typ: Type {
+                    create_sql,
+                    inner: TypeInner::Enum {
+                        labels: labels.clone(),
+                    },
+                    depends_on: vec![],
+                },
+            }));
+        }
+        CreateTypeAs::Record => {
+            let mut keys = vec![];
+            let mut options = BTreeMap::new();
+            for option in &with_options {
+                let key = normalize::ident(option.name().clone());
+                if options.insert(key.clone(), option.clone()).is_some() {
+                    bail!("column {} specified more than once", key.quoted());
+                }
+                keys.push(key);
+            }
+            (keys, options)
+        }
     };
 
     let mut ids = vec![];
-    for key in option_keys {
-        let item_name = match with_options.remove(&key.to_string()) {
+    for key in &option_keys {
+        let item_name = match with_options.remove(key) {
             Some(SqlOption::DataType { data_type, .. }) => match data_type {
                 DataType::Other { name, typ_mod } => {
                     if !typ_mod.is_empty() {
@@ -2080,11 +2607,6 @@ pub fn plan_create_type(
         )
     }
 
-    let name = scx.allocate_name(normalize::unresolved_object_name(name)?);
-    if scx.catalog.item_exists(&name) {
-        bail!("catalog item {} already exists", name.to_string().quoted());
-    }
-
     let inner = match as_type {
         CreateTypeAs::List => TypeInner::List {
             element_id: *ids.get(0).expect("custom type to have element id"),
@@ -2105,6 +2627,14 @@ pub fn plan_create_type(
                 value_id: *ids.get(1).expect("value"),
             }
         }
+        CreateTypeAs::Record => TypeInner::Record {
+            fields: option_keys
+                .into_iter()
+                .map(ColumnName::from)
+                .zip(ids.iter().copied())
+                .collect(),
+        },
+        CreateTypeAs::Enum { .. } => unreachable!("enums planned above"),
     };
 
     Ok(Plan::CreateType(CreateTypePlan {
@@ -2134,6 +2664,7 @@ pub fn plan_create_role(
 ) -> Result<Plan, anyhow::Error> {
     let mut login = None;
     let mut super_user = None;
+    let mut password = None;
     for option in options {
         match option {
             CreateRoleOption::Login | CreateRoleOption::NoLogin if login.is_some() => {
@@ -2142,10 +2673,14 @@ pub fn plan_create_role(
             CreateRoleOption::SuperUser | CreateRoleOption::NoSuperUser if super_user.is_some() => {
                 bail!("conflicting or redundant options");
             }
+            CreateRoleOption::Password(_) if password.is_some() => {
+                bail!("conflicting or redundant options");
+            }
             CreateRoleOption::Login => login = Some(true),
             CreateRoleOption::NoLogin => login = Some(false),
             CreateRoleOption::SuperUser => super_user = Some(true),
             CreateRoleOption::NoSuperUser => super_user = Some(false),
+            CreateRoleOption::Password(p) => password = Some(p),
         }
     }
     if is_user && login.is_none() {
@@ -2159,9 +2694,80 @@ pub fn plan_create_role(
     }
     Ok(Plan::CreateRole(CreateRolePlan {
         name: normalize::ident(name),
+        password: plan_role_password(password.flatten())?,
     }))
 }
 
+pub fn describe_alter_role(
+    _: &StatementContext,
+    _: AlterRoleStatement,
+) -> Result<StatementDesc, anyhow::Error> {
+    Ok(StatementDesc::new(None))
+}
+
+pub fn plan_alter_role(
+    scx: &StatementContext,
+    AlterRoleStatement { name, options, .. }: AlterRoleStatement,
+) -> Result<Plan, anyhow::Error> {
+    let name = normalize::ident(name);
+    scx.catalog.resolve_role(&name)?;
+    let mut login = None;
+    let mut super_user = None;
+    let mut password = None;
+    for option in options {
+        match option {
+            CreateRoleOption::Login | CreateRoleOption::NoLogin if login.is_some() => {
+                bail!("conflicting or redundant options");
+            }
+            CreateRoleOption::SuperUser | CreateRoleOption::NoSuperUser if super_user.is_some() => {
+                bail!("conflicting or redundant options");
+            }
+            CreateRoleOption::Password(_) if password.is_some() => {
+                bail!("conflicting or redundant options");
+            }
+            CreateRoleOption::Login => login = Some(true),
+            CreateRoleOption::NoLogin => login = Some(false),
+            CreateRoleOption::SuperUser => super_user = Some(true),
+            CreateRoleOption::NoSuperUser => super_user = Some(false),
+            CreateRoleOption::Password(p) => password = Some(p),
+        }
+    }
+    // All roles are login superusers, so those options can only be restated.
+    if login == Some(false) {
+        bail_unsupported!("non-login users");
+    }
+    if super_user == Some(false) {
+        bail_unsupported!("non-superusers");
+    }
+    match password {
+        None => Ok(Plan::AlterNoop(AlterNoopPlan {
+            object_type: ObjectType::Role,
+        })),
+        // Removing a password can let anyone log in as the role, so only the
+        // role itself may do so.
+        Some(None) if name != scx.catalog.user() => bail!(
+            "permission denied to remove the password of role {}",
+            name.quoted()
+        ),
+        Some(password) => Ok(Plan::AlterRole(AlterRolePlan {
+            name,
+            password: plan_role_password(password)?,
+        })),
+    }
+}
+
+/// Hashes a role's cleartext password, so that the cleartext password never
+/// leaves the planner.
+fn plan_role_password(
+    password: Option<String>,
+) -> Result<Option<ScramSha256Verifier>, anyhow::Error> {
+    match password {
+        None => Ok(None),
+        Some(password) if password.is_empty() => bail!("password must not be empty"),
+        Some(password) => Ok(Some(ScramSha256Verifier::new(&password)?)),
+    }
+}
+
 pub fn describe_drop_database(
     _: &StatementContext,
     _: DropDatabaseStatement,
@@ -2494,3 +3100,160 @@ pub fn plan_alter_object_rename(
         object_type,
     }))
 }
+
+pub fn describe_alter_table(
+    _: &StatementContext,
+    _: AlterTableStatement<Raw>,
+) -> Result<StatementDesc, anyhow::Error> {
+    Ok(StatementDesc::new(None))
+}
+
+pub fn plan_alter_table(
+    scx: &StatementContext,
+    AlterTableStatement {
+        table_name,
+        if_exists,
+        action,
+    }: AlterTableStatement<Raw>,
+) -> Result<Plan, anyhow::Error> {
+    let entry = match scx.resolve_item(table_name) {
+        Ok(entry) => entry,
+        Err(_) if if_exists => {
+            // TODO(benesch): generate a notice indicating this table does not
+            // exist.
+            return Ok(Plan::AlterNoop(AlterNoopPlan {
+                object_type: ObjectType::Table,
+            }));
+        }
+        Err(e) => return Err(e.into()),
+    };
+    if entry.item_type() != CatalogItemType::Table {
+        bail!("{} is a {} not a table", entry.name(), entry.item_type())
+    }
+    let id = entry.id();
+
+    // The new definition of the table is planned from its original `CREATE
+    // TABLE` statement, with the column added or removed.
+    let mut stmt = match crate::parse::parse(entry.create_sql())
+        .expect("create_sql for existing table should be valid sql")
+        .into_element()
+    {
+        Statement::CreateTable(stmt) => stmt,
+        _ => panic!("create_sql for existing table should parse as a table"),
+    };
+    let names: Vec<_> = stmt
+        .columns
+        .iter()
+        .map(|c| normalize::column_name(c.name.clone()))
+        .collect();
+
+    let index = match action {
+        AlterTableAction::AddColumn {
+            if_not_exists,
+            column,
+        } => {
+            let name = normalize::column_name(column.name.clone());
+            if names.contains(&name) {
+                if if_not_exists {
+                    // TODO(benesch): generate a notice indicating this column
+                    // already exists.
+                    return Ok(Plan::AlterNoop(AlterNoopPlan {
+                        object_type: ObjectType::Table,
+                    }));
+                }
+                bail!(
+                    "column {} of relation {} already exists",
+                    name.as_str().quoted(),
+                    entry.name()
+                );
+            }
+            for option in &column.options {
+                match &option.option {
+                    ColumnOption::Null | ColumnOption::NotNull | ColumnOption::Default(_) => (),
+                    other => bail_unsupported!(format!(
+                        "ALTER TABLE ADD COLUMN with column constraint: {}",
+                        other
+                    )),
+                }
+            }
+            stmt.columns.push(column);
+            None
+        }
+        AlterTableAction::DropColumn { if_exists, name } => {
+            let name = normalize::column_name(name);
+            let index = match names.iter().position(|n| *n == name) {
+                Some(index) => index,
+                None if if_exists => {
+                    // TODO(benesch): generate a notice indicating this column
+                    // does not exist.
+                    return Ok(Plan::AlterNoop(AlterNoopPlan {
+                        object_type: ObjectType::Table,
+                    }));
+                }
+                None => bail!(
+                    "column {} of relation {} does not exist",
+                    name.as_str().quoted(),
+                    entry.name()
+                ),
+            };
+            let table = plan_table(scx, stmt.clone())?;
+            let constraint = table
+                .unique_constraints
+                .iter()
+                .find(|c| c.columns.contains(&index))
+                .map(|c| &c.name)
+                .or_else(|| {
+                    table
+                        .check_constraints
+                        .iter()
+                        .find(|c| c.expr.support().contains(&index))
+                        .map(|c| &c.name)
+                });
+            if let Some(constraint) = constraint {
+                bail!(
+                    "cannot drop column {} of table {} because constraint {} depends on it",
+                    name.as_str().quoted(),
+                    entry.name(),
+                    constraint.quoted()
+                );
+            }
+            stmt.columns.remove(index);
+            Some(index)
+        }
+    };
+
+    let table = plan_table(scx, stmt)?;
+    // Items are loaded in ID order when the catalog is opened, so the table
+    // cannot come to depend on items that were created after it.
+    if table.depends_on.iter().any(|dep| *dep > id) {
+        bail_unsupported!("ALTER TABLE that refers to items created after the table");
+    }
+    let rewrite = match index {
+        Some(index) => AlterTableRewrite::DropColumn { index },
+        None => {
+            let default = table.defaults.last().expect("column was added");
+            let ty = table
+                .desc
+                .typ()
+                .column_types
+                .last()
+                .expect("column was added");
+            let (default, _) = query::plan_default_expr(scx, default, &ty.scalar_type)?;
+            AlterTableRewrite::AddColumn {
+                default: default.lower_uncorrelated()?,
+            }
+        }
+    };
+    Ok(Plan::AlterTable(AlterTablePlan { id, table, rewrite }))
+}
+
+/// Plans `stmt`, as for `CREATE TABLE`, and returns the resulting table.
+fn plan_table(
+    scx: &StatementContext,
+    stmt: CreateTableStatement<Raw>,
+) -> Result<Table, anyhow::Error> {
+    match plan_create_table(scx, stmt)? {
+        Plan::CreateTable(CreateTablePlan { table, .. }) => Ok(table),
+        _ => unreachable!("plan_create_table must return a CreateTablePlan"),
+    }
+}
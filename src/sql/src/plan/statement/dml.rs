@@ -32,8 +32,8 @@ use crate::plan::query;
 use crate::plan::query::QueryLifetime;
 use crate::plan::statement::{StatementContext, StatementDesc};
 use crate::plan::{
-    CopyFormat, CopyFromPlan, CopyParams, ExplainPlan, InsertPlan, MutationKind, Params, PeekPlan,
-    PeekWhen, Plan, ReadThenWritePlan, TailPlan,
+    ConflictAction, CopyFormat, CopyFromPlan, CopyParams, ExplainPlan, InsertPlan, MutationKind,
+    OnConflictPlan, Params, PeekPlan, PeekWhen, Plan, ReadThenWritePlan, TailPlan,
 };
 
 // TODO(benesch): currently, describing a `SELECT` or `INSERT` query
@@ -48,10 +48,10 @@ pub fn describe_insert(
         table_name,
         columns,
         source,
-        ..
+        on_conflict,
     }: InsertStatement<Raw>,
 ) -> Result<StatementDesc, anyhow::Error> {
-    query::plan_insert_query(scx, table_name, columns, source)?;
+    query::plan_insert_query(scx, table_name, columns, source, on_conflict)?;
     Ok(StatementDesc::new(None))
 }
 
@@ -61,14 +61,52 @@ pub fn plan_insert(
         table_name,
         columns,
         source,
+        on_conflict,
     }: InsertStatement<Raw>,
     params: &Params,
 ) -> Result<Plan, anyhow::Error> {
-    let (id, mut expr) = query::plan_insert_query(scx, table_name, columns, source)?;
+    let (id, mut expr, on_conflict) =
+        query::plan_insert_query(scx, table_name, columns, source, on_conflict)?;
     expr.bind_parameters(&params)?;
     let expr = expr.lower();
 
-    Ok(Plan::Insert(InsertPlan { id, values: expr }))
+    let on_conflict = match on_conflict {
+        Some(query::OnConflictPlan {
+            keys,
+            assignments,
+            selection,
+        }) => {
+            let action = match assignments {
+                None => ConflictAction::DoNothing,
+                Some(sets) => {
+                    let mut assignments = HashMap::new();
+                    for (idx, mut set) in sets {
+                        set.bind_parameters(&params)?;
+                        assignments.insert(idx, set.lower_uncorrelated()?);
+                    }
+                    let selection = match selection {
+                        Some(mut selection) => {
+                            selection.bind_parameters(&params)?;
+                            Some(selection.lower_uncorrelated()?)
+                        }
+                        None => None,
+                    };
+                    ConflictAction::DoUpdate {
+                        assignments,
+                        selection,
+                    }
+                }
+            };
+            Some(OnConflictPlan { keys, action })
+        }
+        None => None,
+    };
+
+    Ok(Plan::Insert(InsertPlan {
+        id,
+        values: expr,
+        on_conflict,
+    }))
 }
 
 pub fn describe_delete(
@@ -393,6 +431,9 @@ with_options! {
         format: String,
         delimiter: String,
         null: String,
+        quote: String,
+        escape: String,
+        header: bool,
     }
 }
 
@@ -422,6 +463,70 @@ fn plan_copy_from(
     }))
 }
 
+/// Checks the combination of options in `params`, following the rules that
+/// PostgreSQL applies in `ProcessCopyOptions`.
+fn validate_copy_params(params: &CopyParams) -> Result<(), anyhow::Error> {
+    let is_csv = matches!(params.format, CopyFormat::Csv);
+    if let CopyFormat::Binary = params.format {
+        if params.delimiter.is_some() {
+            bail!("cannot specify DELIMITER in BINARY mode");
+        }
+        if params.null.is_some() {
+            bail!("cannot specify NULL in BINARY mode");
+        }
+    }
+    if let Some(delimiter) = &params.delimiter {
+        if delimiter.len() != 1 {
+            bail!("COPY delimiter must be a single one-byte character");
+        }
+        if delimiter == "\r" || delimiter == "\n" {
+            bail!("COPY delimiter cannot be newline or carriage return");
+        }
+    }
+    if let Some(null) = &params.null {
+        if null.contains('\r') || null.contains('\n') {
+            bail!("COPY null representation cannot use newline or carriage return");
+        }
+    }
+    if params.header && !is_csv {
+        bail!("COPY HEADER available only in CSV mode");
+    }
+    if let Some(quote) = &params.quote {
+        if !is_csv {
+            bail!("COPY quote available only in CSV mode");
+        }
+        if quote.len() != 1 {
+            bail!("COPY quote must be a single one-byte character");
+        }
+    }
+    if let Some(escape) = &params.escape {
+        if !is_csv {
+            bail!("COPY escape available only in CSV mode");
+        }
+        if escape.len() != 1 {
+            bail!("COPY escape must be a single one-byte character");
+        }
+    }
+    if is_csv {
+        let delimiter = params.delimiter.as_deref().unwrap_or(",");
+        let quote = params.quote.as_deref().unwrap_or("\"");
+        if delimiter == quote {
+            bail!("COPY delimiter and quote must be different");
+        }
+        if let Some(null) = &params.null {
+            if null.contains(quote) {
+                bail!("CSV quote character must not appear in the NULL specification");
+            }
+        }
+    }
+    if let (Some(delimiter), Some(null)) = (&params.delimiter, &params.null) {
+        if null.contains(delimiter.as_str()) {
+            bail!("COPY delimiter must not appear in the NULL specification");
+        }
+    }
+    Ok(())
+}
+
 pub fn plan_copy(
     scx: &StatementContext,
     CopyStatement {
@@ -436,6 +541,9 @@ pub fn plan_copy(
         format: CopyFormat::Text,
         delimiter: options.delimiter,
         null: options.null,
+        quote: options.quote,
+        escape: options.escape,
+        header: options.header.unwrap_or(false),
     };
     if let Some(format) = options.format {
         copy_params.format = match format.to_lowercase().as_str() {
@@ -445,6 +553,7 @@ pub fn plan_copy(
             _ => bail!("unknown FORMAT: {}", format),
         };
     }
+    validate_copy_params(&copy_params)?;
     if let CopyDirection::To = direction {
         if copy_params.delimiter.is_some() {
             bail!("COPY TO does not support DELIMITER option yet");
@@ -452,6 +561,15 @@ pub fn plan_copy(
         if copy_params.null.is_some() {
             bail!("COPY TO does not support NULL option yet");
         }
+        if copy_params.quote.is_some() {
+            bail!("COPY TO does not support QUOTE option yet");
+        }
+        if copy_params.escape.is_some() {
+            bail!("COPY TO does not support ESCAPE option yet");
+        }
+        if copy_params.header {
+            bail!("COPY TO does not support HEADER option yet");
+        }
     }
     match (&direction, &target) {
         (CopyDirection::To, CopyTarget::Stdout) => match relation {
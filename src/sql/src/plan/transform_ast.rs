@@ -19,7 +19,7 @@ use uuid::Uuid;
 use sql_parser::ast::visit_mut::{self, VisitMut};
 use sql_parser::ast::{
     Expr, Function, FunctionArgs, Ident, OrderByExpr, Query, Raw, Select, SelectItem, TableAlias,
-    TableFactor, TableWithJoins, UnresolvedObjectName, Value,
+    TableFactor, TableWithJoins, UnresolvedObjectName, Value, WindowSpec,
 };
 
 use crate::normalize;
@@ -99,6 +99,7 @@ impl<'a> FuncRewriter<'a> {
         order_by: Vec<OrderByExpr<Raw>>,
         filter: Option<Box<Expr<Raw>>>,
         distinct: bool,
+        over: Option<WindowSpec<Raw>>,
     ) -> Expr<Raw> {
         Expr::Function(Function {
             name,
@@ -107,18 +108,24 @@ impl<'a> FuncRewriter<'a> {
                 order_by,
             },
             filter,
-            over: None,
+            over,
             distinct,
         })
     }
 
-    fn plan_avg(expr: Expr<Raw>, filter: Option<Box<Expr<Raw>>>, distinct: bool) -> Expr<Raw> {
+    fn plan_avg(
+        expr: Expr<Raw>,
+        filter: Option<Box<Expr<Raw>>>,
+        distinct: bool,
+        over: Option<WindowSpec<Raw>>,
+    ) -> Expr<Raw> {
         let sum = Self::plan_agg(
             UnresolvedObjectName::qualified(&["pg_catalog", "sum"]),
             expr.clone(),
             vec![],
             filter.clone(),
             distinct,
+            over.clone(),
         )
         .call_unary(vec!["mz_internal", "mz_avg_promotion"]);
         let count = Self::plan_agg(
@@ -127,6 +134,7 @@ impl<'a> FuncRewriter<'a> {
             vec![],
             filter,
             distinct,
+            over,
         );
         Self::plan_divide(sum, count)
     }
@@ -136,6 +144,7 @@ impl<'a> FuncRewriter<'a> {
         filter: Option<Box<Expr<Raw>>>,
         distinct: bool,
         sample: bool,
+        over: Option<WindowSpec<Raw>>,
     ) -> Expr<Raw> {
         // N.B. this variance calculation uses the "textbook" algorithm, which
         // is known to accumulate problematic amounts of error. The numerically
@@ -159,6 +168,7 @@ impl<'a> FuncRewriter<'a> {
             vec![],
             filter.clone(),
             distinct,
+            over.clone(),
         );
         let sum = Self::plan_agg(
             UnresolvedObjectName::qualified(&["pg_catalog", "sum"]),
@@ -166,6 +176,7 @@ impl<'a> FuncRewriter<'a> {
             vec![],
             filter.clone(),
             distinct,
+            over.clone(),
         );
         let sum_squared = sum.clone().multiply(sum);
         let count = Self::plan_agg(
@@ -174,6 +185,7 @@ impl<'a> FuncRewriter<'a> {
             vec![],
             filter,
             distinct,
+            over,
         );
         Self::plan_divide(
             sum_squares.minus(Self::plan_divide(sum_squared, count.clone())),
@@ -190,8 +202,9 @@ impl<'a> FuncRewriter<'a> {
         filter: Option<Box<Expr<Raw>>>,
         distinct: bool,
         sample: bool,
+        over: Option<WindowSpec<Raw>>,
     ) -> Expr<Raw> {
-        Self::plan_variance(expr, filter, distinct, sample).call_unary(vec!["sqrt"])
+        Self::plan_variance(expr, filter, distinct, sample, over).call_unary(vec!["sqrt"])
     }
 
     fn rewrite_expr(&mut self, expr: &Expr<Raw>) -> Option<(Ident, Expr<Raw>)> {
@@ -201,7 +214,7 @@ impl<'a> FuncRewriter<'a> {
                 args: FunctionArgs::Args { args, order_by: _ },
                 filter,
                 distinct,
-                over: None,
+                over,
             }) => {
                 let name = normalize::unresolved_object_name(name.clone()).ok()?;
                 if let Some(database) = &name.database {
@@ -217,17 +230,22 @@ impl<'a> FuncRewriter<'a> {
                 }
                 let filter = filter.clone();
                 let distinct = *distinct;
+                let over = over.clone();
                 let expr = if args.len() == 1 {
                     let arg = args[0].clone();
                     match name.item.as_str() {
-                        "avg" => Self::plan_avg(arg, filter, distinct),
-                        "variance" | "var_samp" => Self::plan_variance(arg, filter, distinct, true),
-                        "var_pop" => Self::plan_variance(arg, filter, distinct, false),
-                        "stddev" | "stddev_samp" => Self::plan_stddev(arg, filter, distinct, true),
-                        "stddev_pop" => Self::plan_stddev(arg, filter, distinct, false),
+                        "avg" => Self::plan_avg(arg, filter, distinct, over),
+                        "variance" | "var_samp" => {
+                            Self::plan_variance(arg, filter, distinct, true, over)
+                        }
+                        "var_pop" => Self::plan_variance(arg, filter, distinct, false, over),
+                        "stddev" | "stddev_samp" => {
+                            Self::plan_stddev(arg, filter, distinct, true, over)
+                        }
+                        "stddev_pop" => Self::plan_stddev(arg, filter, distinct, false, over),
                         _ => return None,
                     }
-                } else if args.len() == 2 {
+                } else if args.len() == 2 && over.is_none() {
                     let (lhs, rhs) = (args[0].clone(), args[1].clone());
                     match name.item.as_str() {
                         "mod" => lhs.modulo(rhs),
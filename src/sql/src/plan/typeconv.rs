@@ -14,6 +14,7 @@ use std::cell::RefCell;
 use std::collections::BTreeMap;
 use std::collections::HashMap;
 use std::fmt;
+use std::iter;
 use std::rc::Rc;
 
 use anyhow::bail;
@@ -21,7 +22,10 @@ use lazy_static::lazy_static;
 
 use expr::func;
 use expr::VariadicFunc;
-use repr::{ColumnName, ColumnType, Datum, RelationType, ScalarBaseType, ScalarType};
+use repr::adt::enums::EnumLabels;
+use repr::{ColumnName, ColumnType, Datum, RelationType, RowArena, ScalarBaseType, ScalarType};
+
+use crate::catalog::SessionCatalog;
 
 use super::expr::{CoercibleScalarExpr, ColumnRef, HirScalarExpr, UnaryFunc};
 use super::query::{ExprContext, QueryContext};
@@ -293,6 +297,19 @@ lazy_static! {
                     cast_expr: Box::new(cast_expr),
                 }))
             }),
+            (String, Range) => Explicit: CastTemplate::new(|ecx, ccx, from_type, to_type| {
+                let return_ty = to_type.clone();
+                let to_el_type = to_type.unwrap_range_element_type();
+                let cast_expr = plan_hypothetical_cast(ecx, ccx, from_type, to_el_type)?;
+                Some(|e: HirScalarExpr| e.call_unary(UnaryFunc::CastStringToRange {
+                    return_ty,
+                    cast_expr: Box::new(cast_expr),
+                }))
+            }),
+            (String, Enum) => Explicit: CastTemplate::new(|ecx, _ccx, _from_type, to_type| {
+                let func = plan_cast_string_to_enum(ecx.catalog(), to_type).ok()?;
+                Some(|e: HirScalarExpr| e.call_unary(func))
+            }),
             (String, Char) => Assignment: CastTemplate::new(|_ecx, ccx, _from_type, to_type| {
                 let length = to_type.unwrap_char_varchar_length();
                 Some(move |e: HirScalarExpr| e.call_unary(CastStringToChar {length, fail_on_len: ccx == CastContext::Assignment}))
@@ -325,21 +342,37 @@ lazy_static! {
             }),
 
             // RECORD
-            (Record, String) => Assignment: CastTemplate::new(|_ecx, _ccx, from_type, _to_type| {
+            (Record, String) => Assignment: CastTemplate::new(|ecx, _ccx, from_type, _to_type| {
+                let ty = from_type.clone();
+                let enum_labels = plan_enum_labels(ecx.catalog(), iter::once(&ty)).ok()?;
+                Some(|e: HirScalarExpr| e.call_unary(CastRecordToString { ty, enum_labels }))
+            }),
+
+            // ENUM
+            (Enum, String) => Assignment: CastTemplate::new(|ecx, _ccx, from_type, _to_type| {
                 let ty = from_type.clone();
-                Some(|e: HirScalarExpr| e.call_unary(CastRecordToString { ty }))
+                let enum_labels = plan_enum_labels(ecx.catalog(), iter::once(&ty)).ok()?;
+                Some(|e: HirScalarExpr| e.call_unary(CastEnumToString { ty, enum_labels }))
+            }),
+
+            // RANGE
+            (Range, String) => Assignment: CastTemplate::new(|_ecx, _ccx, from_type, _to_type| {
+                let ty = from_type.clone();
+                Some(|e: HirScalarExpr| e.call_unary(CastRangeToString { ty }))
             }),
 
             // ARRAY
-            (Array, String) => Assignment: CastTemplate::new(|_ecx, _ccx, from_type, _to_type| {
+            (Array, String) => Assignment: CastTemplate::new(|ecx, _ccx, from_type, _to_type| {
                 let ty = from_type.clone();
-                Some(|e: HirScalarExpr| e.call_unary(CastArrayToString { ty }))
+                let enum_labels = plan_enum_labels(ecx.catalog(), iter::once(&ty)).ok()?;
+                Some(|e: HirScalarExpr| e.call_unary(CastArrayToString { ty, enum_labels }))
             }),
 
             // LIST
-            (List, String) => Assignment: CastTemplate::new(|_ecx, _ccx, from_type, _to_type| {
+            (List, String) => Assignment: CastTemplate::new(|ecx, _ccx, from_type, _to_type| {
                 let ty = from_type.clone();
-                Some(|e: HirScalarExpr| e.call_unary(CastListToString { ty }))
+                let enum_labels = plan_enum_labels(ecx.catalog(), iter::once(&ty)).ok()?;
+                Some(|e: HirScalarExpr| e.call_unary(CastListToString { ty, enum_labels }))
             }),
             (List, List) => Implicit: CastTemplate::new(|ecx, ccx, from_type, to_type| {
                 let return_ty = to_type.clone();
@@ -353,9 +386,10 @@ lazy_static! {
             }),
 
             // MAP
-            (Map, String) => Assignment: CastTemplate::new(|_ecx, _ccx, from_type, _to_type| {
+            (Map, String) => Assignment: CastTemplate::new(|ecx, _ccx, from_type, _to_type| {
                 let ty = from_type.clone();
-                Some(|e: HirScalarExpr| e.call_unary(CastMapToString { ty }))
+                let enum_labels = plan_enum_labels(ecx.catalog(), iter::once(&ty)).ok()?;
+                Some(|e: HirScalarExpr| e.call_unary(CastMapToString { ty, enum_labels }))
             }),
 
             // JSONB
@@ -450,6 +484,12 @@ fn get_cast(
                 },
             )
             | (Map { value_type: l, .. }, Map { value_type: r, .. }) => structural_equality(&l, &r),
+            (Record { fields: l, .. }, Record { fields: r, .. }) => {
+                l.len() == r.len()
+                    && l.iter()
+                        .zip(r)
+                        .all(|((_, l), (_, r))| structural_equality(&l.scalar_type, &r.scalar_type))
+            }
             (l, r) => l == r,
         }
     }
@@ -799,3 +839,118 @@ pub fn can_cast(
     };
     get_cast(ecx, ccx, &cast_from, &cast_to).is_some()
 }
+
+/// Plans a cast from a string to the enum type `to_type`, whose labels are
+/// looked up in the catalog.
+fn plan_cast_string_to_enum(
+    catalog: &dyn SessionCatalog,
+    to_type: &ScalarType,
+) -> Result<UnaryFunc, anyhow::Error> {
+    Ok(UnaryFunc::CastStringToEnum {
+        return_ty: to_type.clone(),
+        labels: lookup_enum_labels(catalog, to_type)?.to_vec(),
+    })
+}
+
+/// Looks up the labels of the enum type `ty` in the catalog.
+fn lookup_enum_labels<'a>(
+    catalog: &'a dyn SessionCatalog,
+    ty: &ScalarType,
+) -> Result<&'a [String], anyhow::Error> {
+    match ty {
+        ScalarType::Enum { custom_oid } => match catalog
+            .try_get_item_by_oid(custom_oid)
+            .and_then(|item| item.enum_labels())
+        {
+            Some(labels) => Ok(labels),
+            None => bail!("type with OID {} does not exist", custom_oid),
+        },
+        _ => panic!("lookup_enum_labels called on {:?}", ty),
+    }
+}
+
+/// Looks up the labels of every enum type within `types` in the catalog, so
+/// that values of those types can be output.
+pub fn plan_enum_labels<'a, I>(
+    catalog: &dyn SessionCatalog,
+    types: I,
+) -> Result<EnumLabels, anyhow::Error>
+where
+    I: IntoIterator<Item = &'a ScalarType>,
+{
+    fn collect(
+        catalog: &dyn SessionCatalog,
+        ty: &ScalarType,
+        enum_labels: &mut EnumLabels,
+    ) -> Result<(), anyhow::Error> {
+        match ty {
+            ScalarType::Enum { custom_oid } => {
+                let labels = lookup_enum_labels(catalog, ty)?;
+                enum_labels.insert(*custom_oid, labels.to_vec());
+            }
+            ScalarType::Array(elem_type)
+            | ScalarType::List {
+                element_type: elem_type,
+                ..
+            }
+            | ScalarType::Map {
+                value_type: elem_type,
+                ..
+            }
+            | ScalarType::Range {
+                element_type: elem_type,
+            } => collect(catalog, elem_type, enum_labels)?,
+            ScalarType::Record { fields, .. } => {
+                for (_name, ty) in fields {
+                    collect(catalog, &ty.scalar_type, enum_labels)?;
+                }
+            }
+            _ => (),
+        }
+        Ok(())
+    }
+
+    let mut enum_labels = EnumLabels::default();
+    for ty in types {
+        collect(catalog, ty, &mut enum_labels)?;
+    }
+    Ok(enum_labels)
+}
+
+/// Resolves the enum values within `datum`, which is of type `ty`, from the
+/// bare labels that `pgrepr` decodes them to, to the positions of those
+/// labels.
+///
+/// Values that do not contain enum values are returned unchanged.
+pub fn resolve_enum_labels<'a>(
+    catalog: &dyn SessionCatalog,
+    datum: Datum<'a>,
+    ty: &ScalarType,
+    arena: &'a RowArena,
+) -> Result<Datum<'a>, anyhow::Error> {
+    match ty {
+        ScalarType::Enum { .. } => {
+            // Look up the labels even for nulls, so that a value of a dropped
+            // type is reported as such.
+            let labels = lookup_enum_labels(catalog, ty)?;
+            match datum {
+                Datum::Null => Ok(datum),
+                _ => Ok(func::cast_string_to_enum(datum, labels)?),
+            }
+        }
+        ScalarType::Array(elem_type) if matches!(**elem_type, ScalarType::Enum { .. }) => {
+            if datum.is_null() {
+                return Ok(datum);
+            }
+            let array = datum.unwrap_array();
+            let dims: Vec<_> = array.dims().into_iter().collect();
+            let elements = array
+                .elements()
+                .iter()
+                .map(|elem| resolve_enum_labels(catalog, elem, elem_type, arena))
+                .collect::<Result<Vec<_>, _>>()?;
+            Ok(arena.try_make_datum(|packer| packer.push_array(&dims, elements))?)
+        }
+        _ => Ok(datum),
+    }
+}
@@ -12,25 +12,36 @@
 //! See the [crate-level documentation](crate) for details.
 
 use std::collections::{BTreeMap, HashMap};
+use std::fs;
 use std::future::Future;
+use std::path::{Component, Path};
 
 use anyhow::{anyhow, bail, ensure, Context};
 use aws_arn::ARN;
 use aws_util::aws;
 use csv::ReaderBuilder;
 use itertools::Itertools;
+use lazy_static::lazy_static;
+use regex::Regex;
 use tokio::fs::File;
 use tokio::io::AsyncBufReadExt;
 use tokio::task;
 use uuid::Uuid;
 
-use dataflow_types::{ExternalSourceConnector, PostgresSourceConnector, SourceConnector};
-use repr::strconv;
+use dataflow_types::{
+    ExternalSourceConnector, PostgresColumnDesc, PostgresSourceConnector, PostgresSourceDetails,
+    PostgresTableDesc, SourceConnector,
+};
+use postgres_util::TableInfo;
+use repr::adt::numeric::NUMERIC_DATUM_MAX_PRECISION;
+use repr::{strconv, ScalarType};
 use sql_parser::ast::{
-    display::AstDisplay, AvroSchema, CreateSourceConnector, CreateSourceFormat,
-    CreateSourceStatement, CreateViewsDefinitions, CreateViewsSourceTarget, CreateViewsStatement,
-    CsrConnector, CsrSeed, CsvColumns, DbzMode, Envelope, Expr, Format, Ident, ProtobufSchema,
-    Query, Raw, RawName, Select, SelectItem, SetExpr, Statement, TableFactor, TableWithJoins,
+    display::AstDisplay, AvroSchema, ColumnDef, ColumnOption, ColumnOptionDef, Compression,
+    CreateSinkStatement, CreateSourceConnector, CreateSourceFormat, CreateSourceStatement,
+    CreateViewsDefinitions, CreateViewsSourceTarget, CreateViewsStatement, CsrConnector,
+    CsrConnectorProto, CsrSeed, CsrSeedCompiled, CsrSeedCompiledEncoding, CsrSeedCompiledOrLegacy,
+    CsvColumns, DataType, DbzMode, Envelope, Expr, Format, Ident, ProtobufSchema, Query, Raw,
+    RawName, Select, SelectItem, SetExpr, Statement, TableFactor, TableWithJoins,
     UnresolvedObjectName, Value, ViewDefinition, WithOption, WithOptionValue,
 };
 use sql_parser::parser::parse_columns;
@@ -181,6 +192,7 @@ pub fn purify(
                     conn,
                     publication,
                     slot,
+                    details,
                 } => {
                     slot.get_or_insert_with(|| {
                         format!(
@@ -189,10 +201,29 @@ pub fn purify(
                         )
                     });
 
-                    // verify that we can connect upstream
-                    // TODO(petrosagg): store this info along with the source for better error
-                    // detection
-                    let _ = postgres_util::publication_info(&conn, &publication).await?;
+                    // Verify that we can connect upstream and record the schema of the
+                    // published tables, so that the source can detect upstream schema changes.
+                    let tables = postgres_util::publication_info(&conn, &publication)
+                        .await?
+                        .into_iter()
+                        .map(|table| PostgresTableDesc {
+                            oid: table.rel_id,
+                            namespace: table.namespace,
+                            name: table.name,
+                            columns: table
+                                .schema
+                                .into_iter()
+                                .map(|column| PostgresColumnDesc {
+                                    name: column.name.into_string(),
+                                    type_oid: column.type_oid,
+                                    type_mod: column.type_mod,
+                                    nullable: column.nullable,
+                                    primary_key: column.primary_key,
+                                })
+                                .collect(),
+                        })
+                        .collect();
+                    *details = Some(serde_json::to_string(&PostgresSourceDetails { tables })?);
                 }
                 CreateSourceConnector::PubNub { .. } => (),
             }
@@ -218,6 +249,7 @@ pub fn purify(
                             ExternalSourceConnector::Postgres(PostgresSourceConnector {
                                 conn,
                                 publication,
+                                details,
                                 ..
                             }),
                         ..
@@ -242,6 +274,7 @@ pub fn purify(
                         });
 
                         let mut views = Vec::with_capacity(pub_info.len());
+                        let mut target_tables = Vec::with_capacity(pub_info.len());
 
                         // An index from table_name -> schema_name -> table_info
                         let mut pub_info_idx: HashMap<_, HashMap<_, _>> = HashMap::new();
@@ -274,39 +307,84 @@ pub fn purify(
                                 })?
                             };
 
+                            target_tables.push(table_info);
+
                             let view_name =
                                 target.alias.clone().unwrap_or_else(|| target.name.clone());
 
-                            let col_schema = table_info
-                                .schema
-                                .iter()
-                                .map(|c| c.to_ast_string())
-                                .collect::<Vec<String>>()
-                                .join(", ");
-                            let (columns, _constraints) =
-                                parse_columns(&format!("({})", col_schema))?;
-
+                            // Tables whose rows the source decodes have a record column of
+                            // their own. Other tables are represented as a list of the textual
+                            // form of each column, which must be cast to the right type.
+                            let typed_table = details.as_ref().and_then(|details| {
+                                details
+                                    .typed_tables()
+                                    .map(|(table, _types)| table)
+                                    .find(|table| table.oid == table_info.rel_id)
+                            });
                             let mut projection = vec![];
-                            for (i, column) in columns.iter().enumerate() {
-                                projection.push(SelectItem::Expr {
-                                    expr: Expr::Cast {
-                                        expr: Box::new(Expr::SubscriptIndex {
-                                            expr: Box::new(Expr::Identifier(vec![Ident::new(
-                                                "row_data",
-                                            )])),
-                                            subscript: Box::new(Expr::Value(Value::Number(
-                                                // LIST is one based
-                                                (i + 1).to_string(),
-                                            ))),
-                                        }),
-                                        data_type: column.data_type.clone(),
-                                    },
-                                    alias: Some(column.name.clone()),
-                                });
+                            let mut column_names = vec![];
+                            match typed_table {
+                                Some(table) => {
+                                    let record = error_if_table_altered(
+                                        Expr::Identifier(vec![Ident::new(
+                                            table.source_column_name().as_str(),
+                                        )]),
+                                        table_info,
+                                    );
+                                    for column in &table.columns {
+                                        let name = Ident::new(&column.name);
+                                        projection.push(SelectItem::Expr {
+                                            expr: Expr::FieldAccess {
+                                                expr: Box::new(record.clone()),
+                                                field: name.clone(),
+                                            },
+                                            alias: Some(name.clone()),
+                                        });
+                                        column_names.push(name);
+                                    }
+                                }
+                                None => {
+                                    let col_schema = table_info
+                                        .schema
+                                        .iter()
+                                        .map(|c| c.to_ast_string())
+                                        .collect::<Vec<String>>()
+                                        .join(", ");
+                                    let (columns, _constraints) =
+                                        parse_columns(&format!("({})", col_schema))?;
+
+                                    // Sources created before the upstream schema was recorded
+                                    // fail as a whole if a table is altered, rather than
+                                    // producing an error row for it.
+                                    let mut row_data =
+                                        Expr::Identifier(vec![Ident::new("row_data")]);
+                                    if details.is_some() {
+                                        row_data = error_if_table_altered(row_data, table_info);
+                                    }
+                                    for (i, column) in columns.into_iter().enumerate() {
+                                        projection.push(SelectItem::Expr {
+                                            expr: Expr::Cast {
+                                                expr: Box::new(Expr::SubscriptIndex {
+                                                    expr: Box::new(row_data.clone()),
+                                                    subscript: Box::new(Expr::Value(
+                                                        Value::Number(
+                                                            // LIST is one based
+                                                            (i + 1).to_string(),
+                                                        ),
+                                                    )),
+                                                }),
+                                                data_type: column.data_type,
+                                            },
+                                            alias: Some(column.name.clone()),
+                                        });
+                                        column_names.push(column.name);
+                                    }
+                                }
                             }
 
                             let query = Query {
                                 ctes: vec![],
+                                recursive: false,
                                 body: SetExpr::Select(Box::new(Select {
                                     distinct: None,
                                     projection,
@@ -335,11 +413,12 @@ pub fn purify(
 
                             views.push(ViewDefinition {
                                 name: view_name,
-                                columns: columns.iter().map(|c| c.name.clone()).collect(),
+                                columns: column_names,
                                 with_options: vec![],
                                 query,
                             });
                         }
+                        validate_replica_identity(&target_tables)?;
                         *definitions = CreateViewsDefinitions::Literal(views);
                     }
                     SourceConnector::External { connector, .. } => {
@@ -351,10 +430,65 @@ pub fn purify(
                 }
             }
         }
+
+        if let Statement::CreateSink(CreateSinkStatement {
+            format: Some(Format::Protobuf(ProtobufSchema::InlineSchema { schema, .. })),
+            ..
+        }) = &mut stmt
+        {
+            if let sql_parser::ast::Schema::File(path) = schema {
+                let descriptors = tokio::fs::read(path).await?;
+                let mut buf = String::new();
+                strconv::format_bytes(&mut buf, &descriptors);
+                *schema = sql_parser::ast::Schema::Inline(buf);
+            }
+        }
         Ok(stmt)
     }
 }
 
+/// Wraps `expr`, the column of a Postgres source that holds the rows of the upstream table
+/// `table`, so that it reports an error if the source has produced an error row for the table,
+/// in which that column is null. The source does so once the schema of the table changes in a way
+/// it cannot replicate.
+fn error_if_table_altered(expr: Expr<Raw>, table: &TableInfo) -> Expr<Raw> {
+    let message = format!(
+        "source table {}.{} has been altered; recreate the source to replicate its new schema",
+        table.namespace, table.name
+    );
+    Expr::Nested(Box::new(Expr::call(
+        vec!["mz_internal", "mz_error_if_null"],
+        vec![expr, Expr::Value(Value::String(message))],
+    )))
+}
+
+/// Ensures that updates and deletes of the upstream Postgres tables `tables` replicate the full
+/// previous version of each row, reporting every table that does not. The previous version is
+/// required to retract the row, and it is the only source of the values of TOASTed columns that
+/// an update did not change.
+fn validate_replica_identity(tables: &[&TableInfo]) -> Result<(), anyhow::Error> {
+    let mut unreplicable_tables = tables
+        .iter()
+        .filter(|table| !table.replica_identity_full)
+        .peekable();
+    if unreplicable_tables.peek().is_some() {
+        bail!(
+            "{}",
+            unreplicable_tables
+                .map(|table| format!(
+                    "table {}.{} must use REPLICA IDENTITY FULL to be replicated; \
+                     run ALTER TABLE {}.{} REPLICA IDENTITY FULL upstream",
+                    Ident::new(&table.namespace),
+                    Ident::new(&table.name),
+                    Ident::new(&table.namespace),
+                    Ident::new(&table.name),
+                ))
+                .join("\n")
+        );
+    }
+    Ok(())
+}
+
 async fn purify_source_format(
     format: &mut CreateSourceFormat<Raw>,
     connector: &mut CreateSourceConnector,
@@ -448,7 +582,8 @@ async fn purify_source_format_single(
         },
         Format::Protobuf(schema) => match schema {
             ProtobufSchema::Csr { csr_connector } => {
-                purify_csr_connector(connector, connector_options, envelope, csr_connector).await?
+                purify_csr_connector_proto(connector, connector_options, envelope, csr_connector)
+                    .await?
             }
             ProtobufSchema::InlineSchema { schema, .. } => {
                 if let sql_parser::ast::Schema::File(path) = schema {
@@ -465,7 +600,9 @@ async fn purify_source_format_single(
         } => {
             purify_csv(file, connector, *delimiter, columns).await?;
         }
-        Format::Bytes | Format::Regex(_) | Format::Json | Format::Text => (),
+        Format::Parquet { columns } => purify_parquet(connector, columns)?,
+        Format::AvroOcf => bail!("FORMAT AVRO OCF is only supported for file and S3 sinks"),
+        Format::Bytes | Format::Regex(_) | Format::Json { .. } | Format::Text => (),
     }
     Ok(())
 }
@@ -516,6 +653,159 @@ async fn purify_csr_connector(
     Ok(())
 }
 
+async fn purify_csr_connector_proto(
+    connector: &mut CreateSourceConnector,
+    connector_options: &BTreeMap<String, String>,
+    envelope: &Envelope,
+    csr_connector: &mut CsrConnectorProto<Raw>,
+) -> Result<(), anyhow::Error> {
+    let topic = if let CreateSourceConnector::Kafka { topic, .. } = connector {
+        topic
+    } else {
+        bail!("Confluent Schema Registry is only supported with Kafka sources")
+    };
+
+    let CsrConnectorProto {
+        url,
+        seed,
+        with_options: ccsr_options,
+    } = csr_connector;
+    if seed.is_none() {
+        let url = url.parse()?;
+
+        let ccsr_config = task::block_in_place(|| {
+            kafka_util::generate_ccsr_client_config(
+                url,
+                &connector_options,
+                normalize::options(ccsr_options),
+            )
+        })?;
+        let ccsr_client = ccsr_config.build()?;
+
+        let value_subject = format!("{}-value", topic);
+        let value = compile_csr_schema(&ccsr_client, &value_subject)
+            .await?
+            .ok_or_else(|| anyhow!(ccsr::GetBySubjectError::SubjectNotFound))
+            .with_context(|| {
+                format!(
+                    "fetching latest schema for subject '{}' from registry",
+                    value_subject
+                )
+            })?;
+        let key = compile_csr_schema(&ccsr_client, &format!("{}-key", topic)).await?;
+        if matches!(envelope, Envelope::Debezium(DbzMode::Upsert)) && key.is_none() {
+            bail!("Key schema is required for ENVELOPE DEBEZIUM UPSERT");
+        }
+
+        *seed = Some(CsrSeedCompiledOrLegacy::Compiled(CsrSeedCompiled {
+            key,
+            value,
+        }));
+    }
+
+    Ok(())
+}
+
+/// Fetches the latest protobuf schema for `subject`, along with the schemas it
+/// imports, and compiles them into a file descriptor set.
+///
+/// Returns `None` if the subject does not exist.
+async fn compile_csr_schema(
+    ccsr_client: &ccsr::Client,
+    subject: &str,
+) -> Result<Option<CsrSeedCompiledEncoding>, anyhow::Error> {
+    let (schema, references) = match ccsr_client.get_schema_and_references(subject).await {
+        Ok(res) => res,
+        Err(ccsr::GetBySubjectError::SubjectNotFound) => return Ok(None),
+        Err(e) => {
+            return Err(anyhow::Error::new(e)).with_context(|| {
+                format!(
+                    "fetching latest schema for subject '{}' from registry",
+                    subject
+                )
+            })
+        }
+    };
+    let references: Vec<_> = references
+        .into_iter()
+        .map(|(name, schema)| (name, schema.raw))
+        .collect();
+    let (descriptors, message_name) =
+        task::block_in_place(|| compile_proto(schema.raw.as_bytes(), &references))
+            .with_context(|| format!("compiling schema for subject '{}'", subject))?;
+    let mut schema = String::new();
+    strconv::format_bytes(&mut schema, &descriptors);
+    Ok(Some(CsrSeedCompiledEncoding {
+        schema,
+        message_name,
+    }))
+}
+
+/// The name under which [`compile_proto`] writes the schema to compile.
+const PROTO_SCHEMA_FILE_NAME: &str = "materialize_schema.proto";
+
+/// Compiles a protobuf schema into an encoded file descriptor set.
+///
+/// `references` maps the paths of the files that `schema` imports to their
+/// contents. Returns the encoded file descriptor set along with the fully
+/// qualified name of the first message in `schema`, which is the message that
+/// the Confluent Schema Registry associates with the schema.
+pub fn compile_proto(
+    schema: &[u8],
+    references: &[(String, String)],
+) -> Result<(Vec<u8>, String), anyhow::Error> {
+    // Write the schema and its references to files to compile them.
+    let include_dir = tempfile::tempdir()?;
+    fs::write(include_dir.path().join(PROTO_SCHEMA_FILE_NAME), schema)?;
+    for (name, reference) in references {
+        let relative_path = Path::new(name);
+        if !relative_path
+            .components()
+            .all(|c| matches!(c, Component::Normal(_)))
+        {
+            bail!("invalid protobuf schema reference {}", name);
+        }
+        let path = include_dir.path().join(relative_path);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, reference)?;
+    }
+
+    // Compile the schema.
+    let out_dir = tempfile::tempdir()?;
+    match protoc::Protoc::new()
+        .include(include_dir.path())
+        .input(include_dir.path().join(PROTO_SCHEMA_FILE_NAME))
+        .compile_into(out_dir.path())
+    {
+        Ok(()) => (),
+        Err(e) => {
+            lazy_static! {
+                static ref MISSING_IMPORT_ERROR: Regex = Regex::new(
+                    r#"protobuf path \\"(?P<reference>.*)\\" is not found in import path"#
+                )
+                .expect("known valid");
+            }
+
+            // Make protobuf import errors more user-friendly.
+            if let Some(captures) = MISSING_IMPORT_ERROR.captures(&e.to_string()) {
+                bail!(
+                    "unsupported protobuf schema reference {}",
+                    &captures["reference"]
+                )
+            } else {
+                return Err(e);
+            }
+        }
+    }
+
+    let descriptors = fs::read(out_dir.path().join("file_descriptor_set.pb"))?;
+    let message_name =
+        interchange::protobuf::first_message_name_in_file(&descriptors, PROTO_SCHEMA_FILE_NAME)?;
+    Ok((descriptors, message_name))
+}
+
 pub async fn purify_csv(
     file: Option<File>,
     connector: &CreateSourceConnector,
@@ -644,6 +934,82 @@ pub async fn purify_csv(
     Ok(())
 }
 
+/// Discovers the columns of a Parquet file source from the schema in the
+/// file's footer, if they are not specified.
+fn purify_parquet(
+    connector: &CreateSourceConnector,
+    columns: &mut Vec<ColumnDef<Raw>>,
+) -> anyhow::Result<()> {
+    if !columns.is_empty() {
+        return Ok(());
+    }
+    let path = match connector {
+        CreateSourceConnector::File {
+            path,
+            compression: Compression::None,
+        } => path.clone(),
+        CreateSourceConnector::File { .. } => {
+            bail!("FORMAT PARQUET for compressed files requires specifying the columns")
+        }
+        CreateSourceConnector::S3 { .. } => {
+            bail!("FORMAT PARQUET for S3 sources requires specifying the columns")
+        }
+        _ => bail!("FORMAT PARQUET is only supported for file and S3 sources"),
+    };
+    let file_columns = task::block_in_place(|| {
+        // The Parquet reader has no async equivalent, so we're stuck using
+        // blocking calls here.
+        let f = std::fs::File::open(&path)?;
+        interchange::parquet::file_columns(f)
+            .with_context(|| format!("reading Parquet schema of file {}", path))
+    })?;
+    for (name, typ) in file_columns {
+        let (type_name, typ_mod) = match typ.scalar_type {
+            ScalarType::Bool => ("bool", vec![]),
+            ScalarType::Int16 => ("int2", vec![]),
+            ScalarType::Int32 => ("int4", vec![]),
+            ScalarType::Int64 => ("int8", vec![]),
+            ScalarType::Float32 => ("float4", vec![]),
+            ScalarType::Float64 => ("float8", vec![]),
+            ScalarType::Numeric { scale: None } => ("numeric", vec![]),
+            ScalarType::Numeric { scale: Some(scale) } => (
+                "numeric",
+                vec![NUMERIC_DATUM_MAX_PRECISION as u64, u64::from(scale)],
+            ),
+            ScalarType::Date => ("date", vec![]),
+            ScalarType::Time => ("time", vec![]),
+            ScalarType::Timestamp => ("timestamp", vec![]),
+            ScalarType::TimestampTz => ("timestamptz", vec![]),
+            ScalarType::Interval => ("interval", vec![]),
+            ScalarType::Bytes => ("bytea", vec![]),
+            ScalarType::String => ("text", vec![]),
+            ScalarType::Jsonb => ("jsonb", vec![]),
+            other => bail!(
+                "[internal error] unexpected type for Parquet column {}: {:?}",
+                name,
+                other
+            ),
+        };
+        columns.push(ColumnDef {
+            name: Ident::new(name.as_str()),
+            data_type: DataType::Other {
+                name: RawName::Name(UnresolvedObjectName::unqualified(type_name)),
+                typ_mod,
+            },
+            collation: None,
+            options: if typ.nullable {
+                vec![]
+            } else {
+                vec![ColumnOptionDef {
+                    name: None,
+                    option: ColumnOption::NotNull,
+                }]
+            },
+        });
+    }
+    Ok(())
+}
+
 #[derive(Debug)]
 pub struct Schema {
     pub key_schema: Option<String>,
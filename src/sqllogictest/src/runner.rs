@@ -363,13 +363,17 @@ impl<'a> FromSql<'a> for Slt {
                         elements,
                     })
                 }
+                PgKind::Range(_) => match pgrepr::Type::from_oid(ty.oid()) {
+                    Some(ty) => Self(Value::decode_binary(&ty, raw)?),
+                    None => return Err("unknown oid".into()),
+                },
                 _ => unreachable!(),
             },
         })
     }
     fn accepts(ty: &PgType) -> bool {
         match ty.kind() {
-            PgKind::Array(_) | PgKind::Composite(_) => return true,
+            PgKind::Array(_) | PgKind::Composite(_) | PgKind::Range(_) => return true,
             _ => {}
         }
         matches!(
@@ -547,6 +551,8 @@ impl Runner {
             symbiosis_url: Some("postgres://".into()),
             listen_addr: SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), 0),
             tls: None,
+            password_auth: None,
+            bootstrap_password: None,
             experimental_mode: true,
             disable_user_indexes: false,
             safe_mode: false,
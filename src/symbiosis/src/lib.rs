@@ -233,6 +233,9 @@ END $$;
                     create_sql: stmt.to_string(),
                     desc,
                     defaults,
+                    // PostgreSQL enforces the table's constraints.
+                    unique_constraints: vec![],
+                    check_constraints: vec![],
                     temporary,
                     depends_on,
                 };
@@ -303,6 +306,12 @@ END $$;
                     kind: MutationKind::Delete,
                 })
             }
+            // RETURNING does not report the rows that an ON CONFLICT clause
+            // replaces, so we cannot compute the resulting diffs.
+            Statement::Insert(InsertStatement {
+                on_conflict: Some(_),
+                ..
+            }) => bail!("INSERT ... ON CONFLICT is not supported in symbiosis mode"),
             Statement::Insert(InsertStatement { table_name, .. }) => {
                 let mut updates = vec![];
                 let table = scx.resolve_item(table_name.clone())?;
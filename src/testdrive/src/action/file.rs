@@ -7,14 +7,15 @@
 // the Business Source License, use of this software will be governed
 // by the Apache License, Version 2.0.
 
+use std::io::Write;
 use std::path;
 use std::str::FromStr;
 
-use async_compression::tokio::write::GzipEncoder;
 use async_trait::async_trait;
+use flate2::write::GzEncoder;
 use ore::result::ResultExt;
 use tokio::fs::OpenOptions;
-use tokio::io::{AsyncWrite, AsyncWriteExt};
+use tokio::io::AsyncWriteExt;
 
 use crate::action::{Action, State};
 use crate::format::bytes;
@@ -28,15 +29,46 @@ pub struct AppendAction {
 
 pub enum Compression {
     Gzip,
+    Zstd,
+    Snappy,
+    Lz4,
     None,
 }
 
+impl Compression {
+    /// Compresses `contents` as a single, complete stream.
+    pub fn compress(&self, contents: &[u8]) -> Result<Vec<u8>, String> {
+        match self {
+            Compression::Gzip => {
+                let mut encoder = GzEncoder::new(Vec::new(), flate2::Compression::default());
+                encoder.write_all(contents).map_err_to_string()?;
+                encoder.finish().map_err_to_string()
+            }
+            Compression::Zstd => zstd::encode_all(contents, 0).map_err_to_string(),
+            Compression::Snappy => {
+                let mut encoder = snap::write::FrameEncoder::new(Vec::new());
+                encoder.write_all(contents).map_err_to_string()?;
+                encoder.into_inner().map_err_to_string()
+            }
+            Compression::Lz4 => {
+                let mut encoder = lz4_flex::frame::FrameEncoder::new(Vec::new());
+                encoder.write_all(contents).map_err_to_string()?;
+                encoder.finish().map_err_to_string()
+            }
+            Compression::None => Ok(contents.to_vec()),
+        }
+    }
+}
+
 impl FromStr for Compression {
     type Err = String;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         match s {
             "gzip" => Ok(Compression::Gzip),
+            "zstd" => Ok(Compression::Zstd),
+            "snappy" => Ok(Compression::Snappy),
+            "lz4" => Ok(Compression::Lz4),
             "none" => Ok(Compression::None),
             f => Err(format!("unknown compression format: {}", f)),
         }
@@ -91,19 +123,17 @@ impl Action for AppendAction {
     async fn redo(&self, state: &mut State) -> Result<(), String> {
         let path = state.temp_path.join(&self.path);
         println!("Appending to file {}", path.display());
-        let file = OpenOptions::new()
+        let mut file = OpenOptions::new()
             .create(true)
             .append(true)
             .open(&path)
             .await
             .map_err_to_string()?;
 
-        let mut file: Box<dyn AsyncWrite + Unpin + Send> = match self.compression {
-            Compression::Gzip => Box::new(GzipEncoder::new(file)),
-            Compression::None => Box::new(file),
-        };
-
-        file.write_all(&self.contents).await.map_err_to_string()?;
+        // Each append writes a complete compressed stream. All supported
+        // formats permit concatenated streams.
+        let contents = self.compression.compress(&self.contents)?;
+        file.write_all(&contents).await.map_err_to_string()?;
 
         file.shutdown().await.map_err_to_string()?;
         Ok(())
@@ -16,6 +16,7 @@ use byteorder::{NetworkEndian, WriteBytesExt};
 use futures::stream::{FuturesUnordered, StreamExt};
 use ore::display::DisplayExt;
 use ore::result::ResultExt;
+use rdkafka::message::OwnedHeaders;
 use rdkafka::producer::FutureRecord;
 use serde::de::DeserializeOwned;
 
@@ -31,6 +32,7 @@ pub struct IngestAction {
     format: Format,
     key_format: Option<Format>,
     timestamp: Option<i64>,
+    headers: Vec<(String, String)>,
     publish: bool,
     rows: Vec<String>,
     repeat: isize,
@@ -60,6 +62,8 @@ enum Transcoder {
     },
     Protobuf {
         message: protobuf::MessageType,
+        schema_id: i32,
+        confluent_wire_format: bool,
     },
     Bytes {
         terminator: Option<u8>,
@@ -107,7 +111,11 @@ impl Transcoder {
                     Ok(None)
                 }
             }
-            Transcoder::Protobuf { message } => {
+            Transcoder::Protobuf {
+                message,
+                schema_id,
+                confluent_wire_format,
+            } => {
                 fn convert<T: protobuf::Message>(decoded: T) -> Box<dyn protobuf::Message> {
                     let d: Box<dyn protobuf::Message> = Box::new(decoded);
                     d
@@ -143,7 +151,19 @@ impl Transcoder {
                 } else {
                     return Ok(None);
                 };
-                Ok(Some(val.write_to_bytes().map_err_to_string()?))
+                let mut out = vec![];
+                if *confluent_wire_format {
+                    // Like Avro, but the schema ID is followed by the path to
+                    // the message within the schema. A single zero byte is
+                    // shorthand for the first message in the schema.
+                    //
+                    // https://docs.confluent.io/platform/current/schema-registry/serdes-develop/index.html#wire-format
+                    out.write_u8(0).unwrap();
+                    out.write_i32::<NetworkEndian>(*schema_id).unwrap();
+                    out.write_u8(0).unwrap();
+                }
+                out.extend(val.write_to_bytes().map_err_to_string()?);
+                Ok(Some(out))
             }
             Transcoder::Bytes { terminator } => {
                 let mut out = vec![];
@@ -166,6 +186,7 @@ pub fn build_ingest(mut cmd: BuiltinCommand) -> Result<IngestAction, String> {
     let topic_prefix = format!("testdrive-{}", cmd.args.string("topic")?);
     let partition = cmd.args.opt_parse::<i32>("partition")?.unwrap_or(0);
     let repeat = cmd.args.opt_parse::<isize>("repeat")?.unwrap_or(1);
+    let publish = cmd.args.opt_bool("publish")?.unwrap_or(false);
     let format = match cmd.args.string("format")?.as_str() {
         "avro" => Format::Avro {
             schema: cmd.args.string("schema")?,
@@ -174,7 +195,10 @@ pub fn build_ingest(mut cmd: BuiltinCommand) -> Result<IngestAction, String> {
         "protobuf" => {
             let message = cmd.args.parse("message")?;
             let schema = cmd.args.opt_parse::<String>("schema")?;
-            let confluent_wire_format = cmd.args.opt_bool("confluent-wire-format")?.unwrap_or(true);
+            let confluent_wire_format = cmd
+                .args
+                .opt_bool("confluent-wire-format")?
+                .unwrap_or(publish);
             Format::Protobuf {
                 message,
                 schema,
@@ -192,7 +216,10 @@ pub fn build_ingest(mut cmd: BuiltinCommand) -> Result<IngestAction, String> {
         Some("protobuf") => {
             let message = cmd.args.parse("key-message")?;
             let schema = cmd.args.opt_parse::<String>("key-schema")?;
-            let confluent_wire_format = cmd.args.opt_bool("confluent-wire-format")?.unwrap_or(true);
+            let confluent_wire_format = cmd
+                .args
+                .opt_bool("confluent-wire-format")?
+                .unwrap_or(publish);
             Some(Format::Protobuf {
                 message,
                 schema,
@@ -210,7 +237,18 @@ pub fn build_ingest(mut cmd: BuiltinCommand) -> Result<IngestAction, String> {
         None => None,
     };
     let timestamp = cmd.args.opt_parse("timestamp")?;
-    let publish = cmd.args.opt_bool("publish")?.unwrap_or(false);
+    let headers = match cmd.args.opt_string("headers") {
+        None => vec![],
+        Some(headers) => headers
+            .split(',')
+            .map(
+                |header| match header.splitn(2, '=').collect::<Vec<_>>()[..] {
+                    [key, value] => Ok((key.to_owned(), value.to_owned())),
+                    _ => Err(format!("header must be of the form KEY=VALUE: {}", header)),
+                },
+            )
+            .collect::<Result<_, _>>()?,
+    };
     cmd.args.done()?;
 
     Ok(IngestAction {
@@ -219,6 +257,7 @@ pub fn build_ingest(mut cmd: BuiltinCommand) -> Result<IngestAction, String> {
         format,
         key_format,
         timestamp,
+        headers,
         publish,
         rows: cmd.input,
         repeat,
@@ -280,17 +319,25 @@ impl Action for IngestAction {
                     })
                 }
                 Format::Protobuf {
-                    message, schema, ..
+                    message,
+                    schema,
+                    confluent_wire_format,
                 } => {
-                    if self.publish {
+                    let schema_id = if self.publish {
                         let schema = schema.expect("schema");
                         let ccsr_subject = format!("{}-{}", topic_name, typ);
                         ccsr_client
                             .publish_schema(&ccsr_subject, &schema, ccsr::SchemaType::Protobuf, &[])
                             .await
-                            .map_err(|e| format!("schema registry error: {}", e))?;
-                    }
-                    Ok(Transcoder::Protobuf { message })
+                            .map_err(|e| format!("schema registry error: {}", e))?
+                    } else {
+                        1
+                    };
+                    Ok(Transcoder::Protobuf {
+                        message,
+                        schema_id,
+                        confluent_wire_format,
+                    })
                 }
                 Format::Bytes { terminator } => Ok(Transcoder::Bytes { terminator }),
             }
@@ -328,6 +375,13 @@ impl Action for IngestAction {
                     if let Some(timestamp) = self.timestamp {
                         record = record.timestamp(timestamp);
                     }
+                    if !self.headers.is_empty() {
+                        let mut headers = OwnedHeaders::new();
+                        for (key, value) in &self.headers {
+                            headers = headers.add(key, value);
+                        }
+                        record = record.headers(headers);
+                    }
                     producer.send(record, timeout).await
                 });
             }
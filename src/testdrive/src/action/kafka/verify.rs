@@ -20,12 +20,13 @@ use tokio::pin;
 use tokio_stream::StreamExt;
 
 use crate::action::{Action, Context, State};
-use crate::format::{avro, json};
+use crate::format::{avro, json, protobuf};
 use crate::parser::BuiltinCommand;
 
 pub enum SinkFormat {
     Avro,
     Json { key: bool },
+    Protobuf { message: protobuf::MessageType },
 }
 
 pub enum SinkConsistencyFormat {
@@ -47,6 +48,9 @@ pub fn build_verify(mut cmd: BuiltinCommand, context: Context) -> Result<VerifyA
         "json" => SinkFormat::Json {
             key: cmd.args.parse("key")?,
         },
+        "protobuf" => SinkFormat::Protobuf {
+            message: cmd.args.parse("message")?,
+        },
         f => return Err(format!("unknown format: {}", f)),
     };
     let sink = cmd.args.string("sink")?;
@@ -248,6 +252,30 @@ impl Action for VerifyAction {
                     &self.context.regex_replacement,
                 )
             }
+            SinkFormat::Protobuf { message } => {
+                // Keys are encoded as messages that are generated from the
+                // key columns, so only values are verified.
+                let mut actual_messages = vec![];
+                for (_key, value) in actual_bytes {
+                    let value_datum = match value {
+                        None => None,
+                        Some(bytes) => Some(message.decode_to_json(&bytes)?),
+                    };
+                    actual_messages.push((None, value_datum));
+                }
+
+                if self.sort_messages {
+                    actual_messages.sort_by_key(|k| format!("{:?}", k.1));
+                }
+
+                json::validate_sink(
+                    false,
+                    &self.expected_messages,
+                    &actual_messages,
+                    &self.context.regex,
+                    &self.context.regex_replacement,
+                )
+            }
         }
     }
 }
@@ -9,12 +9,9 @@
 
 use std::cmp;
 use std::collections::HashMap;
-use std::io::Write;
 use std::time::{Duration, Instant};
 
 use async_trait::async_trait;
-use flate2::write::GzEncoder;
-use flate2::Compression as Flate2Compression;
 use ore::result::ResultExt;
 use rusoto_core::{ByteStream, RusotoError};
 use rusoto_s3::{
@@ -106,19 +103,10 @@ impl Action for PutObjectAction {
         let bucket = format!("{}-{}", self.bucket_prefix, state.seed);
         println!("Put S3 object {}/{}", bucket, self.key);
 
-        let buffer = self.contents.clone().into_bytes();
-        let contents = match self.compression {
-            Compression::None => Ok(buffer),
-            Compression::Gzip => {
-                let mut encoder = GzEncoder::new(Vec::new(), Flate2Compression::default());
-                encoder
-                    .write_all(buffer.as_ref())
-                    .map_err(|e| format!("error writing bytes to encoder: {}", e))?;
-                encoder
-                    .finish()
-                    .map_err(|e| format!("error compressing contents: {}", e))
-            }
-        }?;
+        let contents = self
+            .compression
+            .compress(self.contents.as_bytes())
+            .map_err(|e| format!("error compressing contents: {}", e))?;
 
         state
             .s3_client
@@ -127,8 +115,9 @@ impl Action for PutObjectAction {
                 body: Some(ByteStream::from(contents)),
                 content_type: Some("application/octet-stream".to_string()),
                 content_encoding: match self.compression {
-                    Compression::None => None,
                     Compression::Gzip => Some("gzip".to_string()),
+                    Compression::Zstd => Some("zstd".to_string()),
+                    Compression::Snappy | Compression::Lz4 | Compression::None => None,
                 },
                 key: self.key.clone(),
                 ..Default::default()
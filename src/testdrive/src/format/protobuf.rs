@@ -45,3 +45,28 @@ impl FromStr for MessageType {
         }
     }
 }
+
+impl MessageType {
+    /// Decodes `bytes` as a message of this type and converts the message to
+    /// JSON.
+    pub fn decode_to_json(self, bytes: &[u8]) -> Result<serde_json::Value, String> {
+        fn convert<T>(bytes: &[u8]) -> Result<serde_json::Value, String>
+        where
+            T: Message + serde::Serialize,
+        {
+            let message = T::parse_from_bytes(bytes)
+                .map_err(|e| format!("decoding protobuf message: {}", e))?;
+            serde_json::to_value(&message)
+                .map_err(|e| format!("converting protobuf message to JSON: {}", e))
+        }
+        match self {
+            MessageType::Batch => convert::<gen::billing::Batch>(bytes),
+            MessageType::Struct => convert::<gen::simple::Struct>(bytes),
+            MessageType::Measurement => convert::<gen::billing::Measurement>(bytes),
+            MessageType::SimpleId => convert::<gen::simple::SimpleId>(bytes),
+            MessageType::NestedOuter => convert::<gen::nested::NestedOuter>(bytes),
+            MessageType::SimpleNestedOuter => convert::<gen::nested::SimpleNestedOuter>(bytes),
+            MessageType::Imported => convert::<gen::imported::Imported>(bytes),
+        }
+    }
+}
@@ -75,6 +75,20 @@ impl ColumnKnowledge {
                 }
                 body_knowledge
             }
+            MirRelationExpr::LetRec { id, value, body } => {
+                // The value may refer to itself, and so is harvested without
+                // any knowledge about `id`. Knowledge derived this way holds
+                // for any binding of `id`, and in particular the fixed point.
+                let prior_knowledge = knowledge.remove(&expr::Id::Local(id.clone()));
+                let value_knowledge = ColumnKnowledge::harvest(value, knowledge)?;
+                knowledge.insert(expr::Id::Local(id.clone()), value_knowledge);
+                let body_knowledge = ColumnKnowledge::harvest(body, knowledge)?;
+                knowledge.remove(&expr::Id::Local(id.clone()));
+                if let Some(prior_knowledge) = prior_knowledge {
+                    knowledge.insert(expr::Id::Local(id.clone()), prior_knowledge);
+                }
+                body_knowledge
+            }
             MirRelationExpr::Project { input, outputs } => {
                 let input_knowledge = ColumnKnowledge::harvest(input, knowledge)?;
                 outputs
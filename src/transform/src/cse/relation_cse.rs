@@ -30,6 +30,11 @@ impl crate::Transform for RelationCSE {
         relation: &mut MirRelationExpr,
         _: TransformArgs,
     ) -> Result<(), crate::TransformError> {
+        // Recursive bindings cannot be expressed in the flat sequence of `Let`
+        // bindings this transform produces, so we leave such expressions alone.
+        if relation.is_recursive() {
+            return Ok(());
+        }
         let mut bindings = Bindings::default();
         bindings.intern_expression(relation);
         bindings.populate_expression(relation);
@@ -357,6 +357,17 @@ pub mod monotonic {
                 }
                 result
             }
+            MirRelationExpr::LetRec { id, value, body } => {
+                // We do not attempt to reason about the monotonicity of a
+                // fixed point, and conservatively treat `id` as not monotonic.
+                let prior = locals.remove(id);
+                is_monotonic(value, sources, locals);
+                let result = is_monotonic(body, sources, locals);
+                if prior {
+                    locals.insert(*id);
+                }
+                result
+            }
             // The default behavior.
             // TODO: check that this is the behavior we want.
             MirRelationExpr::Negate { .. } | MirRelationExpr::Constant { rows: Err(_), .. } => {
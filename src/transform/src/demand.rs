@@ -82,6 +82,19 @@ impl Demand {
 
                 self.action(value, needs, gets);
             }
+            MirRelationExpr::LetRec { id, value, body } => {
+                // The value of a recursive binding feeds back into itself, so
+                // we conservatively demand all of its columns.
+                let id = Id::Local(*id);
+                let prior = gets.insert(id, HashSet::new());
+                self.action(body, columns, gets);
+                let arity = value.arity();
+                self.action(value, (0..arity).collect(), gets);
+                gets.remove(&id);
+                if let Some(prior) = prior {
+                    gets.insert(id, prior);
+                }
+            }
             MirRelationExpr::Project { input, outputs } => {
                 self.action(
                     input,
@@ -38,6 +38,15 @@ impl crate::Transform for InlineLet {
         relation: &mut MirRelationExpr,
         _: TransformArgs,
     ) -> Result<(), crate::TransformError> {
+        self.action_and_bind(relation);
+        Ok(())
+    }
+}
+
+impl InlineLet {
+    /// Applies [`InlineLet::action`] to `relation`, and re-installs the lifted
+    /// `Let` bindings around the result.
+    fn action_and_bind(&self, relation: &mut MirRelationExpr) {
         let mut lets = vec![];
         self.action(relation, &mut lets);
         for (id, value) in lets.into_iter().rev() {
@@ -47,11 +56,8 @@ impl crate::Transform for InlineLet {
                 body: Box::new(relation.take_dangerous()),
             };
         }
-        Ok(())
     }
-}
 
-impl InlineLet {
     /// Install replace certain `Get` operators with their `Let` value.
     ///
     /// IMPORTANT: This transform is used for cleaning up after `RelationCSE`, which
@@ -82,7 +88,9 @@ impl InlineLet {
             };
             let inlinable = match stripped_value {
                 MirRelationExpr::Get { .. } | MirRelationExpr::Constant { .. } => true,
-                _ => num_gets <= 1,
+                // Inlining a recursive binding could nest it within the value
+                // of another recursive binding, which we cannot render.
+                _ => num_gets <= 1 && !value.is_recursive(),
             };
 
             if inlinable {
@@ -101,6 +109,11 @@ impl InlineLet {
             *relation = body.take_dangerous();
             // might be another Let in the body so have to recur here
             self.action(relation, lets);
+        } else if let MirRelationExpr::LetRec { value, body, .. } = relation {
+            // Bindings within the value or body of a recursive binding may
+            // refer to it, and so must not be lifted out of it.
+            self.action_and_bind(value);
+            self.action_and_bind(body);
         } else {
             relation.visit1_mut(|child| self.action(child, lets));
         }
@@ -36,7 +36,7 @@ impl crate::Transform for JoinImplementation {
             let keys = idxs.iter().map(|(_id, keys)| keys.clone()).collect();
             arranged.insert(Id::Global(*on_id), keys);
         }
-        self.action_recursive(relation, &mut arranged);
+        self.action_recursive(relation, &mut arranged, false);
         Ok(())
     }
 }
@@ -46,13 +46,17 @@ impl JoinImplementation {
     ///
     /// This method accumulates state about let-bound arrangements, so that
     /// join operators can more accurately assess their available arrangements.
+    ///
+    /// The `recursive` argument indicates that `relation` is contained in the
+    /// value of a recursive binding.
     pub fn action_recursive(
         &self,
         relation: &mut MirRelationExpr,
         arranged: &mut HashMap<Id, Vec<Vec<MirScalarExpr>>>,
+        recursive: bool,
     ) {
         if let MirRelationExpr::Let { id, value, body } = relation {
-            self.action_recursive(value, arranged);
+            self.action_recursive(value, arranged, recursive);
             match &**value {
                 MirRelationExpr::ArrangeBy { keys, .. } => {
                     arranged.insert(Id::Local(*id), keys.clone());
@@ -65,19 +69,27 @@ impl JoinImplementation {
                 }
                 _ => {}
             }
-            self.action_recursive(body, arranged);
+            self.action_recursive(body, arranged, recursive);
             arranged.remove(&Id::Local(*id));
+        } else if let MirRelationExpr::LetRec { value, body, .. } = relation {
+            self.action_recursive(value, arranged, true);
+            self.action_recursive(body, arranged, recursive);
         } else {
-            relation.visit1_mut(|e| self.action_recursive(e, arranged));
-            self.action(relation, arranged);
+            relation.visit1_mut(|e| self.action_recursive(e, arranged, recursive));
+            self.action(relation, arranged, recursive);
         }
     }
 
     /// Determines the join implementation for join operators.
+    ///
+    /// Delta queries rely on a total order on times to avoid double counting
+    /// concurrent updates, and so are not used when `recursive` is set, as the
+    /// values of recursive bindings are rendered in iterative scopes.
     pub fn action(
         &self,
         relation: &mut MirRelationExpr,
         indexes: &HashMap<Id, Vec<Vec<MirScalarExpr>>>,
+        recursive: bool,
     ) {
         if let MirRelationExpr::Join {
             inputs,
@@ -169,12 +181,16 @@ impl JoinImplementation {
             // Determine if we can perform delta queries with the existing arrangements.
             // We could defer the execution if we are sure we know we want one input,
             // but we could imagine wanting the best from each and then comparing the two.
-            let delta_query_plan = delta_queries::plan(
-                relation,
-                &input_mapper,
-                &available_arrangements,
-                &unique_keys,
-            );
+            let delta_query_plan = if recursive {
+                None
+            } else {
+                delta_queries::plan(
+                    relation,
+                    &input_mapper,
+                    &available_arrangements,
+                    &unique_keys,
+                )
+            };
             let differential_plan = differential::plan(
                 relation,
                 &input_mapper,
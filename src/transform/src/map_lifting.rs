@@ -236,6 +236,15 @@ impl LiteralLifting {
                 gets.remove(&id);
                 result
             }
+            MirRelationExpr::LetRec { value, body, .. } => {
+                // The `value` is also read by `Get`s within itself, whose
+                // types we cannot correct, so its literals are re-installed.
+                let literals = self.action(value, gets);
+                if !literals.is_empty() {
+                    **value = value.take_dangerous().map(literals);
+                }
+                self.action(body, gets)
+            }
             MirRelationExpr::Project { input, outputs } => {
                 // We do not want to lift literals around projections.
                 // Projections are the highest lifted operator and lifting
@@ -81,6 +81,18 @@ impl NonNullRequirements {
                     self.action(value, need, gets);
                 }
             }
+            MirRelationExpr::LetRec { id, value, body } => {
+                // Requirements on the body do not hold for the iterates of
+                // the value, so no requirements are pushed at the value.
+                let id = Id::Local(*id);
+                let prior = gets.insert(id, Vec::new());
+                self.action(body, columns, gets);
+                self.action(value, HashSet::new(), gets);
+                gets.remove(&id);
+                if let Some(prior) = prior {
+                    gets.insert(id, prior);
+                }
+            }
             MirRelationExpr::Project { input, outputs } => {
                 self.action(
                     input,
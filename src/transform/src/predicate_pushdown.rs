@@ -130,7 +130,7 @@ impl PredicatePushdown {
                 // Depending on the type of `input` we have different
                 // logic to apply to consider pushing `predicates` down.
                 match &mut **input {
-                    MirRelationExpr::Let { body, .. } => {
+                    MirRelationExpr::Let { body, .. } | MirRelationExpr::LetRec { body, .. } => {
                         // Push all predicates to the body.
                         **body = body
                             .take_dangerous()
@@ -441,6 +441,14 @@ impl PredicatePushdown {
                 // Continue recursively on the value.
                 self.action(value, get_predicates);
             }
+            MirRelationExpr::LetRec { id, body, value } => {
+                // Predicates at uses of the binding do not hold for the
+                // iterates of the value, and so are not pushed to it.
+                self.action(body, get_predicates);
+                get_predicates.remove(&Id::Local(*id));
+                self.action(value, get_predicates);
+                get_predicates.remove(&Id::Local(*id));
+            }
             MirRelationExpr::Join {
                 inputs,
                 equivalences,
@@ -64,6 +64,12 @@ impl ProjectionLifting {
                 self.action(body, gets);
                 gets.remove(&id);
             }
+            MirRelationExpr::LetRec { value, body, .. } => {
+                // The `value` is also read by `Get`s within itself, so any
+                // projection lifted to its root must remain there.
+                self.action(value, gets);
+                self.action(body, gets);
+            }
             MirRelationExpr::Project { input, outputs } => {
                 self.action(input, gets);
                 if let MirRelationExpr::Project {
@@ -125,6 +125,20 @@ impl ProjectionPushdown {
                 self.action(value, desired_value_projection, gets);
                 desired_projection.clone()
             }
+            MirRelationExpr::LetRec { id, value, body } => {
+                // The value of a recursive binding feeds back into itself, so
+                // we conservatively push the projection of all its columns.
+                let id = Id::Local(*id);
+                let prior = gets.insert(id, BTreeSet::new());
+                self.action(body, desired_projection.clone(), gets);
+                let arity = value.arity();
+                self.action(value, (0..arity).collect(), gets);
+                gets.remove(&id);
+                if let Some(prior) = prior {
+                    gets.insert(id, prior);
+                }
+                desired_projection.clone()
+            }
             MirRelationExpr::Join {
                 inputs,
                 equivalences,
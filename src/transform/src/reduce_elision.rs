@@ -44,6 +44,11 @@ impl ReduceElision {
             expected_group_size: _,
         } = relation
         {
+            // Window functions produce a list of records rather than a plain
+            // value, and so cannot be replaced by their argument.
+            if aggregates.iter().any(|a| a.func.is_window_func()) {
+                return;
+            }
             let input_type = input.typ();
             if input_type.keys.iter().any(|keys| {
                 keys.iter()
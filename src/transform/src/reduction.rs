@@ -63,6 +63,7 @@ impl FoldConstants {
             MirRelationExpr::Constant { .. } => { /* handled after match */ }
             MirRelationExpr::Get { .. } => {}
             MirRelationExpr::Let { .. } => { /* constant prop done in InlineLet */ }
+            MirRelationExpr::LetRec { .. } => { /* no constant prop through fixed points */ }
             MirRelationExpr::Reduce {
                 input,
                 group_key,
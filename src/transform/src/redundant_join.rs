@@ -75,6 +75,17 @@ impl RedundantJoin {
                 }
                 result
             }
+            MirRelationExpr::LetRec { id, value, body } => {
+                // We do not attempt to determine the provenance of a fixed
+                // point, and only record that `id` is exactly itself.
+                let old = lets.remove(&Id::Local(*id));
+                self.action(value, lets);
+                let result = self.action(body, lets);
+                if let Some(old) = old {
+                    lets.insert(Id::Local(*id), old);
+                }
+                result
+            }
             MirRelationExpr::Get { id, typ } => {
                 // Extract the value provenance, or an empty list if unavailable.
                 let mut val_info = lets.get(id).cloned().unwrap_or_default();
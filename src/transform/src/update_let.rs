@@ -58,6 +58,20 @@ impl UpdateLet {
                 }
                 *id = new_id;
             }
+            MirRelationExpr::LetRec { id, value, body } => {
+                // The value may refer to the binding, so the new identifier
+                // must be in place before the value is visited.
+                let new_id = LocalId::new(id_gen.allocate_id());
+                let prev = remap.insert(id.clone(), (new_id, value.typ()));
+                self.action(value, remap, id_gen);
+                remap.insert(id.clone(), (new_id, value.typ()));
+                self.action(body, remap, id_gen);
+                remap.remove(id);
+                if let Some(prev_stuff) = prev {
+                    remap.insert(id.clone(), prev_stuff);
+                }
+                *id = new_id;
+            }
             MirRelationExpr::Get { id, typ } => {
                 if let Id::Local(local_id) = id {
                     if let Some((new_id, new_type)) = remap.get(local_id) {
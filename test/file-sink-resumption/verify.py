@@ -0,0 +1,74 @@
+# Copyright Materialize, Inc. and contributors. All rights reserved.
+#
+# Use of this software is governed by the Business Source License
+# included in the LICENSE file at the root of this repository.
+#
+# As of the Change Date specified in that file, in accordance with
+# the Business Source License, use of this software will be governed
+# by the Apache License, Version 2.0.
+
+"""Verifies the files that a CSV file sink has written.
+
+Waits for the sink's manifest and its parts to list the expected number of
+records, then checks that the listed files cover disjoint timestamp intervals and together
+contain every expected value exactly once.
+"""
+
+import argparse
+import json
+import sys
+import time
+from pathlib import Path
+from typing import List, Tuple
+
+
+def main() -> None:
+    parser = argparse.ArgumentParser()
+    parser.add_argument("sink_dir", type=Path)
+    parser.add_argument("values", nargs="+")
+    parser.add_argument("--timeout-secs", type=int, default=60)
+    args = parser.parse_args()
+
+    deadline = time.monotonic() + args.timeout_secs
+    while True:
+        manifest, files = read_manifest(args.sink_dir)
+        records = sum(f["records"] for f in files)
+        if records >= len(args.values):
+            break
+        if time.monotonic() > deadline:
+            sys.exit(f"timed out waiting for {len(args.values)} records, found {records}")
+        time.sleep(0.5)
+
+    lower = 0
+    values = []
+    for f in files:
+        if f["lower"] < lower or f["upper"] > manifest["upper"]:
+            sys.exit(f"file {f['name']} overlaps a previous file: {manifest}")
+        lower = f["upper"]
+        lines = next(args.sink_dir.glob("*/" + f["name"])).read_text().splitlines()
+        # Skip the header.
+        values.extend(lines[1:])
+
+    if sorted(values) != sorted(args.values):
+        sys.exit(f"expected values {sorted(args.values)}, found {sorted(values)}")
+    print(f"verified {len(values)} records in {len(files)} files")
+
+
+def read_manifest(sink_dir: Path) -> Tuple[dict, List[dict]]:
+    """Reads the sink's manifest and the files that it and its parts list."""
+    # A resumed sink must keep writing to the directory it started in.
+    dirs = list(sink_dir.glob("*"))
+    if len(dirs) > 1:
+        sys.exit(f"expected a single sink directory, found {dirs}")
+    if not dirs or not (dirs[0] / "manifest.json").exists():
+        return {}, []
+    manifest = json.loads((dirs[0] / "manifest.json").read_text())
+    files = []
+    for part in manifest.get("parts", []):
+        files.extend(json.loads((dirs[0] / part["name"]).read_text())["files"])
+    files.extend(manifest["files"])
+    return manifest, files
+
+
+if __name__ == "__main__":
+    main()